@@ -0,0 +1,62 @@
+//! Compares the cost of the TCPROS compression formats available to ROS1 publishers
+//! Run with `cargo bench --features ros1 --bench ros1_compression`
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use roslibrust::ros1::Compression;
+
+const PAYLOAD_SIZE: usize = 500 * 1024;
+
+/// Imitates a PointCloud2 payload: xyz floats from a scanning sensor, padding, and an intensity
+/// channel, which compresses far less well than repeated text would
+fn payload() -> Vec<u8> {
+    const POINT_STEP: usize = 16;
+    let mut data = Vec::with_capacity(PAYLOAD_SIZE);
+    for i in 0..PAYLOAD_SIZE / POINT_STEP {
+        let angle = (i % 1024) as f32 * std::f32::consts::TAU / 1024.0;
+        let ring = (i / 1024) as f32;
+        let range = 5.0 + ring * 0.1 + (i % 7) as f32 * 0.01;
+        data.extend_from_slice(&(range * angle.cos()).to_le_bytes());
+        data.extend_from_slice(&(range * angle.sin()).to_le_bytes());
+        data.extend_from_slice(&(ring * 0.05).to_le_bytes());
+        data.extend_from_slice(&[0, 0, (i % 13) as u8 * 10, 0]);
+    }
+    data
+}
+
+fn compression(c: &mut Criterion) {
+    let data = payload();
+    let formats = [Compression::None, Compression::Lz4, Compression::Zstd];
+
+    let mut group = c.benchmark_group("compress");
+    group.throughput(Throughput::Bytes(data.len() as u64));
+    for format in formats {
+        let compressed = format.compress(&data).unwrap();
+        println!(
+            "{format:?} compresses {} bytes to {} bytes ({:.1}%)",
+            data.len(),
+            compressed.len(),
+            100.0 * compressed.len() as f64 / data.len() as f64
+        );
+        group.bench_with_input(
+            BenchmarkId::from_parameter(format!("{format:?}")),
+            &data,
+            |b, data| b.iter(|| format.compress(data).unwrap()),
+        );
+    }
+    group.finish();
+
+    let mut group = c.benchmark_group("decompress");
+    group.throughput(Throughput::Bytes(data.len() as u64));
+    for format in formats {
+        let compressed = format.compress(&data).unwrap();
+        group.bench_with_input(
+            BenchmarkId::from_parameter(format!("{format:?}")),
+            &compressed,
+            |b, compressed| b.iter(|| format.decompress(compressed, data.len()).unwrap()),
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, compression);
+criterion_main!(benches);
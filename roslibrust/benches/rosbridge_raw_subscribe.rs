@@ -0,0 +1,99 @@
+//! Compares receiving messages from rosbridge parsed as json values with receiving their raw json
+//! Run with `cargo bench --bench rosbridge_raw_subscribe`
+
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use futures::{SinkExt, Stream, StreamExt};
+use serde_json::{json, Value};
+use tokio::{net::TcpListener, runtime::Runtime, sync::mpsc};
+use tokio_tungstenite::tungstenite::Message;
+
+/// Messages received per iteration
+const BATCH: usize = 100;
+
+/// Imitates a sensor_msgs/LaserScan from a high resolution lidar, which is mostly floats
+fn scan() -> Value {
+    let ranges: Vec<f64> = (0..4000).map(|i| 5.0 + (i % 97) as f64 * 0.013).collect();
+    json!({
+        "header": {"seq": 1, "stamp": {"secs": 1700000000, "nsecs": 250000000}, "frame_id": "laser"},
+        "angle_min": -3.14159, "angle_max": 3.14159, "angle_increment": 0.00157,
+        "time_increment": 0.0, "scan_time": 0.1, "range_min": 0.1, "range_max": 30.0,
+        "intensities": ranges.iter().map(|r| r * 10.0).collect::<Vec<_>>(),
+        "ranges": ranges,
+    })
+}
+
+/// A stand-in for rosbridge which answers rosapi's topic_type, and publishes `BATCH` scans on a
+/// topic each time it is sent the topic's name
+async fn serve(mut publish: mpsc::UnboundedReceiver<String>) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("ws://{}", listener.local_addr().unwrap());
+    let scan = scan();
+    tokio::spawn(async move {
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+        loop {
+            tokio::select! {
+                Some(Ok(Message::Text(text))) = ws.next() => {
+                    let op: Value = serde_json::from_str(&text).unwrap();
+                    if op["op"] == "call_service" {
+                        let response = json!({
+                            "op": "service_response", "service": op["service"], "id": op["id"],
+                            "values": {"type": "sensor_msgs/LaserScan"}, "result": true,
+                        });
+                        ws.send(Message::Text(response.to_string())).await.unwrap();
+                    }
+                }
+                Some(topic) = publish.recv() => {
+                    let op = json!({"op": "publish", "topic": topic, "msg": scan}).to_string();
+                    for _ in 0..BATCH {
+                        ws.send(Message::Text(op.clone())).await.unwrap();
+                    }
+                }
+            }
+        }
+    });
+    url
+}
+
+/// Has the server publish a batch on `topic` and waits until all of it has been received
+async fn receive_batch<T>(
+    publish: &mpsc::UnboundedSender<String>,
+    topic: &str,
+    messages: &mut (impl Stream<Item = T> + Unpin),
+) {
+    publish.send(topic.to_owned()).unwrap();
+    for _ in 0..BATCH {
+        criterion::black_box(messages.next().await.unwrap());
+    }
+}
+
+fn raw_subscribe(c: &mut Criterion) {
+    let runtime = Runtime::new().unwrap();
+    let (publish, requests) = mpsc::unbounded_channel();
+    let (mut parsed, mut raw) = runtime.block_on(async {
+        let url = serve(requests).await;
+        let client = roslibrust::ClientHandle::new(url).await.unwrap();
+        let parsed = client.subscribe_any("/parsed").await.unwrap();
+        let raw = client.subscribe_raw_json("/raw").await.unwrap();
+        (parsed, raw)
+    });
+    let size = json!({"op": "publish", "topic": "/raw", "msg": scan()})
+        .to_string()
+        .len();
+
+    let mut group = c.benchmark_group("rosbridge_subscribe");
+    group.throughput(Throughput::Bytes((size * BATCH) as u64));
+    group.bench_function("subscribe_any", |b| {
+        b.iter(|| runtime.block_on(receive_batch(&publish, "/parsed", &mut parsed)))
+    });
+    group.bench_function("subscribe_raw_json", |b| {
+        b.iter(|| runtime.block_on(receive_batch(&publish, "/raw", &mut raw)))
+    });
+    group.finish();
+
+    // Unsubscribing on drop needs the runtime
+    runtime.block_on(async move { drop((parsed, raw)) });
+}
+
+criterion_group!(benches, raw_subscribe);
+criterion_main!(benches);
@@ -0,0 +1,25 @@
+//! Prints every message on a topic as json, like `rostopic echo`, without needing its type.
+//! Usage: `cargo run --example echo -- <topic> [count] [field path]`,
+//! e.g. `cargo run --example echo -- /odom 10 pose.pose.position`.
+//! A running rosbridge websocket server at the default port (9090) with rosapi is required.
+use futures::StreamExt;
+use roslibrust::{ClientHandle, EchoOptions};
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let mut args = std::env::args().skip(1);
+    let topic = args
+        .next()
+        .ok_or("Usage: echo <topic> [count] [field path]")?;
+    let options = EchoOptions {
+        count: args.next().map(|count| count.parse()).transpose()?,
+        filter: args.next(),
+    };
+
+    let client = ClientHandle::new("ws://localhost:9090").await?;
+    let mut echo = client.echo_with_options(&topic, options).await?;
+    while let Some(output) = echo.next().await {
+        println!("{output}");
+    }
+    Ok(())
+}
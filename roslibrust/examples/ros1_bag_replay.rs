@@ -0,0 +1,65 @@
+//! Replays a bag file into code under test, without a rosmaster or any publishers running.
+//! Run with `cargo run --example ros1_bag_replay --features ros1 -- recording.bag`
+roslibrust_codegen_macro::find_and_generate_ros_messages!("assets/ros1_common_interfaces/std_msgs");
+
+/// The code under test, which would take a `Subscriber` from a real `NodeHandle` when running
+#[cfg(feature = "ros1")]
+async fn count_greetings(
+    mut chatter: roslibrust::ros1::Subscriber<std_msgs::String>,
+    expected: usize,
+) -> usize {
+    let mut greetings = 0;
+    for _ in 0..expected {
+        match chatter.next().await {
+            Ok(msg) if msg.data.starts_with("hello") => greetings += 1,
+            Ok(_) => {}
+            Err(e) => {
+                log::error!("Failed to read message: {e}");
+                break;
+            }
+        }
+    }
+    greetings
+}
+
+/// In a unit test this would be a `#[tokio::test]` loading a bag checked in next to the test
+#[cfg(feature = "ros1")]
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    use roslibrust::ros1::testing::{BagFixture, MockNodeHandle};
+
+    simple_logger::SimpleLogger::new()
+        .with_level(log::LevelFilter::Info)
+        .without_timestamps()
+        .init()
+        .unwrap();
+
+    let path = std::env::args()
+        .nth(1)
+        .ok_or("Usage: ros1_bag_replay <bag file>")?;
+    let fixture = BagFixture::load(path.as_ref())?;
+    let (start, end) = fixture.time_range();
+    log::info!(
+        "Bag covers {}.{:09} to {}.{:09} on topics {:?}",
+        start.secs,
+        start.nsecs,
+        end.secs,
+        end.nsecs,
+        fixture.topics()
+    );
+
+    // Subscribe before injecting, with a queue large enough for every recorded message
+    let mut node = MockNodeHandle::new();
+    let expected = fixture.messages().len();
+    let chatter = node.subscribe::<std_msgs::String>("/chatter", expected)?;
+    let delivered = fixture.inject_into(&mut node);
+
+    let greetings = count_greetings(chatter, delivered).await;
+    log::info!("{greetings} of {delivered} messages on /chatter were greetings");
+    Ok(())
+}
+
+#[cfg(not(feature = "ros1"))]
+fn main() {
+    eprintln!("This example does nothing without compiling with the feature 'ros1'");
+}
@@ -74,8 +74,8 @@
 //! ### How Subscribers Work
 //! Each time subscribe is called, a new queue for that subscriber is created. When a `publish` message is received from rosbridge,
 //! the message is duplicated and inserted into the queue for *each* subscriber. This means if you call subscribe multiple times
-//! on the same topic, each of the returned subscribers will receive a copy of every message. Currently, the size of the queue for
-//! every publisher is hardcoded to 1_000 messages control of this will be provided in a future version.
+//! on the same topic, each of the returned subscribers will receive a copy of every message. The size of the queue for every
+//! subscriber defaults to 1_000 messages and is set with `ClientHandleOptions::subscriber_queue_size`.
 //!
 //! If the queue for a subscriber is full and a new message arrives the central spin task will not block but simply drop that message
 //! for that subscriber (warnings will be logged).
@@ -85,13 +85,17 @@
 //! If an incorrect type is used, each time a message is received on the topic it will fail to de-serialize and an error will be emitted
 //! by the subscriber. This can be useful when building client designed to work with multiple different versions of a message definition.
 //!
+//! By default each subscriber's callback runs on a task of its own, so a message which is slow to deserialize only holds up that
+//! subscriber. `SubscribeOptions::dispatch` can instead run it directly in the central spin task, see `SubscriberDispatch`.
+//!
 //! When the subscriber returned from the subscribe call is dropped it removes is queue from the client. When the last subscriber
 //! on a given topic dropped the client will automatically unsubscribe from the topic with rosbridge.
 //!
 //! ### How Publishers Work
 //! When advertise is called a publisher an advertise message is sent to rosbridge_server and a publisher returned.
-//! Dropping the publisher will automatically unadvertise the topic.
-//! roslibrust currently does not support multiple publishers / multiple advertises for a single topic.
+//! Advertising a topic the client already advertises does not send another advertise, instead the returned publisher shares the
+//! existing advertisement, as do clones of a publisher. Advertising it with a different type fails with `TopicTypeMismatch`.
+//! The client counts the publishers sharing each topic and only unadvertises it once the last of them is dropped.
 //!
 //! ### How Service Servers Work
 //! When advertise service is called you must pass into it a callback conforming to the libraries requirements.
@@ -101,12 +105,25 @@
 mod rosbridge;
 pub use rosbridge::*;
 
+mod names;
+pub use names::{resolve_name, validate_topic_name, validate_type_name, InvalidName, Namespace};
+
+mod message_diff;
+pub use message_diff::{FieldChange, MessageDiff};
+
 #[cfg(feature = "rosapi")]
 pub mod rosapi;
 
+#[cfg(feature = "rcl-interfaces")]
+pub mod rcl_interfaces;
+
 #[cfg(feature = "ros1")]
 pub mod ros1;
 
+// Allows code generated by our macros to refer to ::roslibrust from within this crate
+#[cfg(feature = "ros1")]
+extern crate self as roslibrust;
+
 /// For now starting with a central error type, may break this up more in future
 #[derive(thiserror::Error, Debug)]
 pub enum RosLibRustError {
@@ -117,20 +134,103 @@ pub enum RosLibRustError {
     // currently we "technically" break the API when we change tungstenite verisons
     #[error("Websocket communication error: {0}")]
     CommFailure(#[from] tokio_tungstenite::tungstenite::Error),
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
     #[error("Operation timed out: {0}")]
     Timeout(#[from] tokio::time::error::Elapsed),
+    #[error("Failed to connect within the connect timeout of {0:?}")]
+    ConnectTimeout(std::time::Duration),
+    #[error("Websocket handshake did not complete within the handshake timeout of {0:?}")]
+    HandshakeTimeout(std::time::Duration),
+    #[error("No response to service call on {service} within {timeout:?}")]
+    ServiceCallTimeout {
+        service: String,
+        timeout: std::time::Duration,
+    },
+    #[error("Service {service} did not become available within {timeout:?}")]
+    ServiceUnavailable {
+        service: String,
+        timeout: std::time::Duration,
+    },
+    #[error("rosapi is not available, it must be running alongside rosbridge: {0}")]
+    RosapiUnavailable(String),
+    #[error("Failed to write publish within the publish flush timeout of {0:?}")]
+    PublishFlushTimeout(std::time::Duration),
+    #[error("Too many operations are already waiting for the client to connect")]
+    TooManyPendingOperations,
+    #[error("Topic {topic} has type {actual}, it can't be subscribed to with type {requested}")]
+    TypeMismatch {
+        topic: String,
+        actual: String,
+        requested: String,
+    },
+    #[error("Topic {topic} has type {topic_type}, but its definition differs from the one subscribed with")]
+    DefinitionMismatch { topic: String, topic_type: String },
+    #[error("Topic {0} does not exist")]
+    TopicNotFound(String),
+    #[error("Gave up connecting to rosbridge after {attempts} failed attempts")]
+    ConnectAttemptsExhausted { attempts: u32 },
+    #[error("Topic {topic} is already advertised with type {existing}, it can't also be advertised with type {requested}")]
+    TopicTypeMismatch {
+        topic: String,
+        existing: String,
+        requested: String,
+    },
+    #[error(
+        "Received a {size} byte message{} over the limit of {limit} bytes, raise the client's max_message_size and max_frame_size to accept it",
+        .topic.as_ref().map(|topic| format!(" on {topic}")).unwrap_or_default()
+    )]
+    MessageTooLarge {
+        size: usize,
+        limit: usize,
+        /// The topic the message was published on, when it can be told which one that was
+        topic: Option<String>,
+    },
+    /// Rosbridge, or a proxy in front of it, closed the connection with a websocket close code
+    /// that doesn't have a more specific error
+    #[error("Rosbridge closed the connection with code {code}, reason {reason:?}")]
+    ConnectionClosed { code: u16, reason: String },
+    /// The connection was closed with code 1008, as servers do after failing authentication
+    #[error("Rosbridge closed the connection for a policy violation (1008), reason {reason:?}")]
+    PolicyViolation { reason: String },
+    /// The connection was closed with code 1009, as a message sent to the server was over its
+    /// size limit
+    #[error("Rosbridge closed the connection as a message sent to it was too big (1009), reason {reason:?}")]
+    MessageTooBigForServer { reason: String },
+    /// The connection was closed with code 1013, as the server is overloaded
+    #[error("Rosbridge closed the connection to be tried again later (1013), reason {reason:?}")]
+    TryAgainLater { reason: String },
     #[error("Failed to parse message from JSON: {0}")]
     InvalidMessage(#[from] serde_json::Error),
     #[error("Rosbridge server reported an error: {0}")]
     ServerError(String),
     #[error("Name does not meet ROS requirements: {0}")]
     InvalidName(String),
+    /// A name was rejected before being used, with the naming rule it breaks
+    #[error(transparent)]
+    NameRuleViolation(#[from] InvalidName),
+    #[error("Invalid rosbridge url {0}")]
+    InvalidUrl(String),
     // Generic catch-all error type for not-yet-handled errors
     // TODO ultimately this type will be removed from API of library
     #[error(transparent)]
     Unexpected(#[from] anyhow::Error),
 }
 
+impl RosLibRustError {
+    /// The websocket close code the connection was ended with, for errors caused by rosbridge
+    /// closing the connection
+    pub fn close_code(&self) -> Option<u16> {
+        match self {
+            RosLibRustError::ConnectionClosed { code, .. } => Some(*code),
+            RosLibRustError::PolicyViolation { .. } => Some(1008),
+            RosLibRustError::MessageTooBigForServer { .. } => Some(1009),
+            RosLibRustError::TryAgainLater { .. } => Some(1013),
+            _ => None,
+        }
+    }
+}
+
 /// Generic result type used as standard throughout library.
 /// Note: many functions which currently return this will be updated to provide specific error
 /// types in the future instead of the generic error here.
@@ -0,0 +1,202 @@
+use roslibrust_codegen::RosMessageType;
+use serde_json::Value;
+
+/// A single field which differs between two messages, see [MessageDiff::diff]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FieldChange {
+    /// Path to the field from the root of the message, e.g. `header.stamp.secs` or
+    /// `poses[2].position.x`
+    pub field_path: String,
+    /// The field's value in the first message, formatted as json. Empty if an array element only
+    /// exists in the second message.
+    pub old: String,
+    /// The field's value in the second message, formatted as json. Empty if an array element only
+    /// exists in the first message.
+    pub new: String,
+}
+
+/// Compares two instances of the same message type field by field, for finding out exactly what
+/// changed between consecutive messages on a topic.
+/// ```
+/// # use roslibrust::MessageDiff;
+/// # #[derive(serde::Serialize, serde::Deserialize, Clone, Debug)]
+/// # struct Pose { x: f64, y: f64 }
+/// # impl roslibrust_codegen::RosMessageType for Pose { const ROS_TYPE_NAME: &'static str = "test_msgs/Pose"; }
+/// let changes = MessageDiff::diff(&Pose { x: 1.0, y: 2.0 }, &Pose { x: 1.0, y: 2.5 });
+/// assert!(MessageDiff::has_changes(&changes));
+/// assert_eq!(changes[0].field_path, "y");
+/// println!("{}", MessageDiff::format_diff(&changes));
+/// ```
+pub struct MessageDiff;
+
+impl MessageDiff {
+    /// Returns every field whose value differs between `a` and `b`, recursing into nested
+    /// messages and arrays so only the innermost fields which changed are reported. Fields within
+    /// each message are listed in alphabetical order.
+    pub fn diff<M: RosMessageType>(a: &M, b: &M) -> Vec<FieldChange> {
+        let a = serde_json::to_value(a).expect("Messages always serialize to json");
+        let b = serde_json::to_value(b).expect("Messages always serialize to json");
+        let mut changes = vec![];
+        diff_values(String::new(), Some(&a), Some(&b), &mut changes);
+        changes
+    }
+
+    /// True if [MessageDiff::diff] found any differences
+    pub fn has_changes(changes: &[FieldChange]) -> bool {
+        !changes.is_empty()
+    }
+
+    /// Formats changes one per line as `field: old -> new`, for printing to a terminal.
+    /// With the `colored` feature the old values are shown in red and the new values in green.
+    pub fn format_diff(changes: &[FieldChange]) -> String {
+        changes
+            .iter()
+            .map(|change| {
+                let (old, new) = highlight(&change.old, &change.new);
+                format!("{}: {old} -> {new}\n", change.field_path)
+            })
+            .collect()
+    }
+}
+
+#[cfg(feature = "colored")]
+fn highlight(old: &str, new: &str) -> (String, String) {
+    use colored::Colorize;
+    (old.red().to_string(), new.green().to_string())
+}
+
+#[cfg(not(feature = "colored"))]
+fn highlight(old: &str, new: &str) -> (String, String) {
+    (old.to_owned(), new.to_owned())
+}
+
+/// Records the differences between two values found at `path`, either of which may be missing
+/// when comparing arrays of different lengths
+fn diff_values(path: String, a: Option<&Value>, b: Option<&Value>, changes: &mut Vec<FieldChange>) {
+    match (a, b) {
+        (Some(Value::Object(a)), Some(Value::Object(b))) => {
+            // Both are the same message type, so have the same fields
+            for (field, a_value) in a {
+                let field_path = if path.is_empty() {
+                    field.clone()
+                } else {
+                    format!("{path}.{field}")
+                };
+                diff_values(field_path, Some(a_value), b.get(field), changes);
+            }
+        }
+        (Some(Value::Array(a)), Some(Value::Array(b))) => {
+            for i in 0..a.len().max(b.len()) {
+                diff_values(format!("{path}[{i}]"), a.get(i), b.get(i), changes);
+            }
+        }
+        (a, b) if a != b => changes.push(FieldChange {
+            field_path: path,
+            old: a.map(Value::to_string).unwrap_or_default(),
+            new: b.map(Value::to_string).unwrap_or_default(),
+        }),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use roslibrust_codegen::integral_types::Time;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+    struct Header {
+        seq: u32,
+        stamp: Time,
+        frame_id: String,
+    }
+
+    #[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+    struct Point {
+        x: f64,
+        y: f64,
+    }
+
+    #[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+    struct Path {
+        header: Header,
+        points: Vec<Point>,
+    }
+
+    impl RosMessageType for Path {
+        const ROS_TYPE_NAME: &'static str = "test_msgs/Path";
+    }
+
+    fn change(field_path: &str, old: &str, new: &str) -> FieldChange {
+        FieldChange {
+            field_path: field_path.to_owned(),
+            old: old.to_owned(),
+            new: new.to_owned(),
+        }
+    }
+
+    #[test_log::test]
+    fn identical_messages_have_no_changes() {
+        let path = Path {
+            points: vec![Point { x: 1.0, y: 2.0 }],
+            ..Default::default()
+        };
+        let changes = MessageDiff::diff(&path, &path.clone());
+        assert!(!MessageDiff::has_changes(&changes));
+        assert_eq!(MessageDiff::format_diff(&changes), "");
+    }
+
+    #[test_log::test]
+    fn nested_fields_are_reported_by_path() {
+        let old = Path {
+            header: Header {
+                seq: 1,
+                stamp: Time { secs: 5, nsecs: 0 },
+                frame_id: "map".to_owned(),
+            },
+            points: vec![Point { x: 1.0, y: 2.0 }, Point { x: 3.0, y: 4.0 }],
+        };
+        let mut new = old.clone();
+        new.header.seq = 2;
+        new.header.stamp.nsecs = 500;
+        new.header.frame_id = "odom".to_owned();
+        new.points[1].y = 4.5;
+
+        assert_eq!(
+            MessageDiff::diff(&old, &new),
+            vec![
+                change("header.frame_id", "\"map\"", "\"odom\""),
+                change("header.seq", "1", "2"),
+                change("header.stamp.nsecs", "0", "500"),
+                change("points[1].y", "4.0", "4.5"),
+            ]
+        );
+    }
+
+    #[test_log::test]
+    fn array_length_changes_report_each_element() {
+        let old = Path {
+            points: vec![Point { x: 1.0, y: 2.0 }],
+            ..Default::default()
+        };
+        let mut new = old.clone();
+        new.points.push(Point { x: 3.0, y: 4.0 });
+
+        let changes = MessageDiff::diff(&old, &new);
+        assert_eq!(
+            changes,
+            vec![change("points[1]", "", r#"{"x":3.0,"y":4.0}"#)]
+        );
+        assert_eq!(
+            MessageDiff::diff(&new, &old),
+            vec![change("points[1]", r#"{"x":3.0,"y":4.0}"#, "")]
+        );
+
+        #[cfg(not(feature = "colored"))]
+        assert_eq!(
+            MessageDiff::format_diff(&changes),
+            "points[1]:  -> {\"x\":3.0,\"y\":4.0}\n"
+        );
+    }
+}
@@ -0,0 +1,461 @@
+//! Validation and resolution of ROS graph names, shared by the rosbridge client and ros1 nodes
+
+#[cfg(feature = "ros1")]
+use crate::{RosLibRustError, RosLibRustResult};
+use std::fmt::Display;
+
+/// A name rejected by [validate_topic_name], [validate_type_name] or [resolve_name], along with
+/// the rule it breaks
+#[derive(thiserror::Error, Clone, Debug, PartialEq, Eq)]
+#[error("Name {name:?} does not meet ROS requirements: {reason}")]
+pub struct InvalidName {
+    pub name: String,
+    pub reason: &'static str,
+}
+
+impl InvalidName {
+    fn new(name: &str, reason: &'static str) -> Self {
+        Self {
+            name: name.to_owned(),
+            reason,
+        }
+    }
+}
+
+/// Checks that `name` is a valid ROS topic, service, or action name: global (`/chatter`),
+/// relative (`chatter`), or private (`~chatter`), starting with a letter and made of letters,
+/// digits, and underscores separated by single `/`. Global and private names may also start
+/// with an underscore, as ROS2 does for hidden names like `/_action`. Names with whitespace, e.g.
+/// a trailing space, are rejected, as the master would happily accept them and no publisher would
+/// ever match.
+pub fn validate_topic_name(name: &str) -> Result<(), InvalidName> {
+    match graph_name_error(name) {
+        None => Ok(()),
+        Some(reason) => Err(InvalidName::new(name, reason)),
+    }
+}
+
+/// Checks that `name` is a valid ROS1 message or service type name, `package/Type`
+pub fn validate_type_name(name: &str) -> Result<(), InvalidName> {
+    let is_identifier = |part: &str| {
+        part.starts_with(|c: char| c.is_ascii_alphabetic())
+            && part.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+    };
+    match name.split_once('/') {
+        Some((package, name_part)) if is_identifier(package) && is_identifier(name_part) => Ok(()),
+        _ => Err(InvalidName::new(
+            name,
+            "type names must be package/Type, each part starting with a letter and made of \
+             letters, digits, and underscores",
+        )),
+    }
+}
+
+/// Validates `name` and resolves it within `namespace`: global names are kept as they are, and
+/// relative names are nested in the namespace, so `cmd_vel` in `/robot1` is `/robot1/cmd_vel`.
+///
+/// Private names (`~name`) are rejected, as they can only be resolved within a node's name.
+pub fn resolve_name(name: &str, namespace: &Namespace) -> Result<String, InvalidName> {
+    validate_topic_name(name)?;
+    if name.starts_with('/') {
+        Ok(name.to_owned())
+    } else if name.starts_with('~') {
+        Err(InvalidName::new(
+            name,
+            "private names can only be resolved within a node's name",
+        ))
+    } else {
+        Ok(namespace.join(name).0)
+    }
+}
+
+/// The rule `name` breaks if it isn't a valid graph name
+fn graph_name_error(name: &str) -> Option<&'static str> {
+    if name.is_empty() {
+        return Some("names can't be empty");
+    }
+    if name.contains(char::is_whitespace) {
+        return Some("names can't contain whitespace");
+    }
+    let rest = name.strip_prefix(['/', '~']).unwrap_or(name);
+    // Global and private names may start with an underscore, as ROS2 hides names like /_action
+    let prefixed = rest.len() < name.len();
+    if !rest.starts_with(|c: char| c.is_ascii_alphabetic() || (prefixed && c == '_')) {
+        return Some(
+            "names must start with a letter, or with / or ~ followed by a letter or underscore",
+        );
+    }
+    if !rest
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '/')
+    {
+        return Some("names may only contain letters, digits, underscores, and /");
+    }
+    if rest.contains("//") {
+        return Some("names can't contain empty components (//)");
+    }
+    if rest.ends_with('/') {
+        return Some("names can't end with /");
+    }
+    None
+}
+
+#[cfg(feature = "ros1")]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Name {
+    inner: String,
+}
+
+#[cfg(feature = "ros1")]
+impl Name {
+    pub fn new(name: impl Into<String>) -> RosLibRustResult<Self> {
+        let name: String = name.into();
+        match validate_topic_name(&name) {
+            Ok(()) => Ok(Self { inner: name }),
+            Err(_) => Err(RosLibRustError::InvalidName(name)),
+        }
+    }
+
+    pub fn resolve_to_global(&self, node_name: &Name) -> Self {
+        if self.inner.starts_with('/') {
+            self.clone()
+        } else if let Some(private) = self.inner.strip_prefix('~') {
+            // Private names are resolved within the node's own name
+            Name {
+                inner: Namespace::new(node_name.inner.as_str()).join(private).0,
+            }
+        } else {
+            // Relative names are resolved within the namespace the node is in
+            let namespace = Namespace::new(node_name.inner.as_str())
+                .parent()
+                .unwrap_or_default();
+            Name {
+                inner: namespace.join(&self.inner).0,
+            }
+        }
+    }
+}
+
+/// A global ROS namespace, e.g. `/robot/sensors`.
+///
+/// Namespaces are always stored normalized: with a leading `/`, no trailing `/`, and no empty
+/// components, so `robot//sensors/` becomes `/robot/sensors`. The root namespace is `/`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Namespace(String);
+
+impl Namespace {
+    pub fn new(namespace: impl AsRef<str>) -> Self {
+        let mut normalized = Self::root();
+        normalized.push_str(namespace.as_ref());
+        normalized
+    }
+
+    /// The root namespace `/`
+    pub fn root() -> Self {
+        Self("/".to_owned())
+    }
+
+    pub fn is_root(&self) -> bool {
+        self.0 == "/"
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Returns the namespace nested within this one, `child` may itself contain several
+    /// components e.g. `/robot` joined with `sensors/lidar` is `/robot/sensors/lidar`.
+    /// Leading, trailing, and repeated `/` in `child` are ignored.
+    pub fn join(&self, child: &str) -> Namespace {
+        let mut joined = self.clone();
+        joined.push_str(child);
+        joined
+    }
+
+    /// Appends `component` to this namespace in place, following the same rules as
+    /// [Namespace::join]
+    pub fn push_str(&mut self, component: &str) {
+        for component in component.split('/').filter(|c| !c.is_empty()) {
+            if !self.is_root() {
+                self.0.push('/');
+            }
+            self.0.push_str(component);
+        }
+    }
+
+    /// The namespace containing this one, or None for the root namespace
+    pub fn parent(&self) -> Option<Namespace> {
+        if self.is_root() {
+            return None;
+        }
+        // Unwrap is safe, normalized namespaces always start with /
+        match self.0.rfind('/').unwrap() {
+            0 => Some(Self::root()),
+            idx => Some(Self(self.0[..idx].to_owned())),
+        }
+    }
+
+    /// True if this namespace is nested anywhere within `other`, a namespace is not a child of
+    /// itself
+    pub fn is_child_of(&self, other: &Namespace) -> bool {
+        if other.is_root() {
+            return !self.is_root();
+        }
+        self.0
+            .strip_prefix(other.as_str())
+            .is_some_and(|rest| rest.starts_with('/'))
+    }
+
+    /// The names making up this namespace in order, empty for the root namespace
+    pub fn components(&self) -> Vec<&str> {
+        self.0.split('/').filter(|c| !c.is_empty()).collect()
+    }
+}
+
+impl Default for Namespace {
+    fn default() -> Self {
+        Self::root()
+    }
+}
+
+impl Display for Namespace {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl AsRef<str> for Namespace {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+#[cfg(feature = "ros1")]
+impl Display for Name {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.inner.fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn is_valid(name: &str) -> bool {
+        graph_name_error(name).is_none()
+    }
+
+    #[test]
+    fn namespace_normalizes() {
+        assert_eq!(Namespace::new("").as_str(), "/");
+        assert_eq!(Namespace::new("/").as_str(), "/");
+        assert_eq!(Namespace::new("//").as_str(), "/");
+        assert_eq!(Namespace::new("robot").as_str(), "/robot");
+        assert_eq!(Namespace::new("/robot/").as_str(), "/robot");
+        assert_eq!(Namespace::new("robot//sensors/").as_str(), "/robot/sensors");
+        assert_eq!(Namespace::default(), Namespace::root());
+        assert!(Namespace::new("/").is_root());
+        assert!(!Namespace::new("/robot").is_root());
+    }
+
+    #[test]
+    fn namespace_join_and_push() {
+        let root = Namespace::root();
+        assert_eq!(root.join("robot").as_str(), "/robot");
+        assert_eq!(root.join("").as_str(), "/");
+        assert_eq!(root.join("/").as_str(), "/");
+
+        let robot = Namespace::new("/robot");
+        assert_eq!(robot.join("sensors").as_str(), "/robot/sensors");
+        assert_eq!(robot.join("sensors/lidar").as_str(), "/robot/sensors/lidar");
+        assert_eq!(robot.join("/sensors/").as_str(), "/robot/sensors");
+        assert_eq!(robot.join("").as_str(), "/robot");
+
+        let mut ns = Namespace::root();
+        ns.push_str("robot");
+        ns.push_str("sensors/");
+        ns.push_str("");
+        assert_eq!(ns.as_str(), "/robot/sensors");
+        assert_eq!(ns.to_string(), "/robot/sensors");
+    }
+
+    #[test]
+    fn namespace_parent() {
+        assert_eq!(Namespace::root().parent(), None);
+        assert_eq!(Namespace::new("/robot").parent(), Some(Namespace::root()));
+        assert_eq!(
+            Namespace::new("/robot/sensors/lidar").parent(),
+            Some(Namespace::new("/robot/sensors"))
+        );
+        assert_eq!(
+            Namespace::new("/robot/sensors/").parent(),
+            Some(Namespace::new("/robot"))
+        );
+    }
+
+    #[test]
+    fn namespace_is_child_of() {
+        let root = Namespace::root();
+        let robot = Namespace::new("/robot");
+        let sensors = Namespace::new("/robot/sensors");
+        assert!(robot.is_child_of(&root));
+        assert!(sensors.is_child_of(&root));
+        assert!(sensors.is_child_of(&robot));
+        assert!(!robot.is_child_of(&sensors));
+        assert!(!robot.is_child_of(&robot));
+        assert!(!root.is_child_of(&root));
+        assert!(!root.is_child_of(&robot));
+        // Sharing a prefix isn't enough, the prefix must end at a component boundary
+        assert!(!Namespace::new("/robot2/sensors").is_child_of(&robot));
+    }
+
+    #[test]
+    fn namespace_components() {
+        assert!(Namespace::root().components().is_empty());
+        assert_eq!(Namespace::new("/robot").components(), vec!["robot"]);
+        assert_eq!(
+            Namespace::new("robot/sensors/").components(),
+            vec!["robot", "sensors"]
+        );
+    }
+
+    #[test]
+    fn test_name_valid() {
+        assert!(is_valid("base"));
+        assert!(is_valid("relative/name"));
+        assert!(is_valid("/global/name"));
+        assert!(is_valid("~private/name"));
+        assert!(is_valid("a"));
+        assert!(is_valid("/a"));
+        // ROS2 hides names starting with an underscore
+        assert!(is_valid("/_hidden"));
+        assert!(is_valid("~_hidden"));
+        assert!(is_valid("/robot/_action/status"));
+
+        // These are invalid names
+        assert!(!is_valid("~"));
+        assert!(!is_valid("~~"));
+        assert!(!is_valid("_leading"));
+        assert!(!is_valid("/"));
+        assert!(!is_valid("/trailing/"));
+        assert!(!is_valid("/double//slash"));
+        assert!(!is_valid("/chatter "));
+        assert!(!is_valid("/1digit"));
+        assert!(!is_valid("/name]"));
+    }
+
+    #[test]
+    fn validate_names() {
+        assert!(validate_topic_name("/chatter").is_ok());
+        assert_eq!(
+            validate_topic_name("/chatter "),
+            Err(InvalidName::new(
+                "/chatter ",
+                "names can't contain whitespace"
+            ))
+        );
+        let reason = |name| validate_topic_name(name).unwrap_err().reason;
+        assert_eq!(reason(""), "names can't be empty");
+        assert_eq!(
+            reason("/1digit"),
+            "names must start with a letter, or with / or ~ followed by a letter or underscore"
+        );
+        assert_eq!(
+            reason("/cmd-vel"),
+            "names may only contain letters, digits, underscores, and /"
+        );
+        assert_eq!(
+            reason("/double//slash"),
+            "names can't contain empty components (//)"
+        );
+        assert_eq!(reason("/trailing/"), "names can't end with /");
+
+        assert!(validate_type_name("std_msgs/String").is_ok());
+        assert!(validate_type_name("std_msgs/String ").is_err());
+        assert!(validate_type_name("String").is_err());
+        assert!(validate_type_name("/std_msgs/String").is_err());
+        assert!(validate_type_name("std_msgs/msg/String").is_err());
+        assert!(validate_type_name("1std_msgs/String").is_err());
+    }
+
+    #[test]
+    fn resolve_names_in_namespace() {
+        let robot1 = Namespace::new("/robot1");
+        assert_eq!(resolve_name("cmd_vel", &robot1).unwrap(), "/robot1/cmd_vel");
+        assert_eq!(
+            resolve_name("arm/joint_states", &robot1).unwrap(),
+            "/robot1/arm/joint_states"
+        );
+        assert_eq!(resolve_name("/clock", &robot1).unwrap(), "/clock");
+        assert_eq!(
+            resolve_name("chatter", &Namespace::root()).unwrap(),
+            "/chatter"
+        );
+        assert_eq!(
+            resolve_name("~status", &robot1).unwrap_err().reason,
+            "private names can only be resolved within a node's name"
+        );
+        assert_eq!(
+            resolve_name("cmd vel", &robot1).unwrap_err().reason,
+            "names can't contain whitespace"
+        );
+    }
+
+    // Examples pulled from http://wiki.ros.org/Names
+    #[cfg(feature = "ros1")]
+    #[test]
+    fn resolve_names_to_global() {
+        let node1 = Name::new("/node1").unwrap();
+        assert_eq!(
+            Name::new("bar").unwrap().resolve_to_global(&node1),
+            Name::new("/bar").unwrap()
+        );
+        assert_eq!(
+            Name::new("/bar").unwrap().resolve_to_global(&node1),
+            Name::new("/bar").unwrap()
+        );
+        assert_eq!(
+            Name::new("~bar").unwrap().resolve_to_global(&node1),
+            Name::new("/node1/bar").unwrap()
+        );
+
+        let node2 = Name::new("/wg/node2").unwrap();
+        assert_eq!(
+            Name::new("bar").unwrap().resolve_to_global(&node2),
+            Name::new("/wg/bar").unwrap()
+        );
+        assert_eq!(
+            Name::new("/bar").unwrap().resolve_to_global(&node2),
+            Name::new("/bar").unwrap()
+        );
+        assert_eq!(
+            Name::new("~bar").unwrap().resolve_to_global(&node2),
+            Name::new("/wg/node2/bar").unwrap()
+        );
+
+        let node3 = Name::new("/wg/node3").unwrap();
+        assert_eq!(
+            Name::new("foo/bar").unwrap().resolve_to_global(&node3),
+            Name::new("/wg/foo/bar").unwrap()
+        );
+        assert_eq!(
+            Name::new("/foo/bar").unwrap().resolve_to_global(&node3),
+            Name::new("/foo/bar").unwrap()
+        );
+        assert_eq!(
+            Name::new("~foo/bar").unwrap().resolve_to_global(&node3),
+            Name::new("/wg/node3/foo/bar").unwrap()
+        );
+
+        // Node names without a leading slash are in the root namespace
+        let node4 = Name::new("node4").unwrap();
+        assert_eq!(
+            Name::new("bar").unwrap().resolve_to_global(&node4),
+            Name::new("/bar").unwrap()
+        );
+        assert_eq!(
+            Name::new("~bar").unwrap().resolve_to_global(&node4),
+            Name::new("/node4/bar").unwrap()
+        );
+    }
+}
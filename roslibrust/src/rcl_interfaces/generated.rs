@@ -0,0 +1,251 @@
+#[allow(unused_imports, deprecated)]
+pub mod rcl_interfaces {
+    #[allow(non_snake_case)]
+    #[derive(
+        :: serde :: Deserialize,
+        :: serde :: Serialize,
+        :: smart_default :: SmartDefault,
+        Debug,
+        Clone,
+        PartialEq,
+        Eq,
+        PartialOrd,
+        Ord,
+    )]
+    pub struct Log {
+        pub r#stamp: ::roslibrust_codegen::integral_types::Time,
+        pub r#level: u8,
+        pub r#name: ::std::string::String,
+        pub r#msg: ::std::string::String,
+        pub r#file: ::std::string::String,
+        pub r#function: ::std::string::String,
+        pub r#line: u32,
+    }
+    impl ::roslibrust_codegen::RosMessageType for Log {
+        const ROS_TYPE_NAME: &'static str = "rcl_interfaces/Log";
+        const MD5SUM: &'static str = "820dd47646ff916b480ced9b933606ce";
+        const DEFINITION : & 'static str = "##\n## Severity level constants\n##\n## These logging levels follow the Python Standard\n## https://docs.python.org/3/library/logging.html#logging-levels\n## And are implemented in rcutils as well\n## https://github.com/ros2/rcutils/blob/35f29850064e0c33a4063cbc947ebbfeada11dba/include/rcutils/logging.h#L164-L172\n## This leaves space for other standard logging levels to be inserted in the middle in the future,\n## as well as custom user defined levels.\n## Since there are several other logging enumeration standard for different implementations,\n## other logging implementations may need to provide level mappings to match their internal implementations.\n##\n\n# Debug is for pedantic information, which is useful when debugging issues.\nbyte DEBUG=10\n\n# Info is the standard informational level and is used to report expected\n# information.\nbyte INFO=20\n\n# Warning is for information that may potentially cause issues or possibly unexpected\n# behavior.\nbyte WARN=30\n\n# Error is for information that this node cannot resolve.\nbyte ERROR=40\n\n# Information about a impending node shutdown.\nbyte FATAL=50\n\n##\n## Fields\n##\n\n# Timestamp when this message was generated by the node.\nbuiltin_interfaces/Time stamp\n\n# Corresponding log level, see above definitions.\nuint8 level\n\n# The name representing the logger this message came from.\nstring name\n\n# The full log message.\nstring msg\n\n# The file the message came from.\nstring file\n\n# The function the message came from.\nstring function\n\n# The line in the file the message came from.\nuint32 line" ;
+    }
+    impl Log {
+        #[doc = " = 10"]
+        pub const r#DEBUG: u8 = 10u8;
+        #[doc = " = 20"]
+        pub const r#INFO: u8 = 20u8;
+        #[doc = " = 30"]
+        pub const r#WARN: u8 = 30u8;
+        #[doc = " = 40"]
+        pub const r#ERROR: u8 = 40u8;
+        #[doc = " = 50"]
+        pub const r#FATAL: u8 = 50u8;
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            8usize
+                + 1usize
+                + 4usize
+                + self.r#name.len()
+                + 4usize
+                + self.r#msg.len()
+                + 4usize
+                + self.r#file.len()
+                + 4usize
+                + self.r#function.len()
+                + 4usize
+        }
+    }
+    #[allow(non_snake_case)]
+    #[derive(
+        :: serde :: Deserialize,
+        :: serde :: Serialize,
+        :: smart_default :: SmartDefault,
+        Debug,
+        Clone,
+        PartialEq,
+        PartialOrd,
+    )]
+    pub struct Parameter {
+        pub r#name: ::std::string::String,
+        pub r#value: self::ParameterValue,
+    }
+    impl ::roslibrust_codegen::RosMessageType for Parameter {
+        const ROS_TYPE_NAME: &'static str = "rcl_interfaces/Parameter";
+        const MD5SUM: &'static str = "3965b6672807ab03da22801e25720a70";
+        const DEFINITION : & 'static str = "# This is the message to communicate a parameter's name and value.\n\n# The full name of the parameter.\nstring name\n\n# The parameter's value which can be one of several types, see\n# `ParameterValue.msg` and `ParameterType.msg`.\nParameterValue value" ;
+    }
+    impl Parameter {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            4usize + self.r#name.len() + self.r#value.serialized_size_upper_bound()
+        }
+    }
+    #[allow(non_snake_case)]
+    #[derive(
+        :: serde :: Deserialize,
+        :: serde :: Serialize,
+        :: smart_default :: SmartDefault,
+        Debug,
+        Clone,
+        PartialEq,
+        PartialOrd,
+    )]
+    pub struct ParameterEvent {
+        pub r#stamp: ::roslibrust_codegen::integral_types::Time,
+        pub r#node: ::std::string::String,
+        pub r#new_parameters: ::std::vec::Vec<self::Parameter>,
+        pub r#changed_parameters: ::std::vec::Vec<self::Parameter>,
+        pub r#deleted_parameters: ::std::vec::Vec<self::Parameter>,
+    }
+    impl ::roslibrust_codegen::RosMessageType for ParameterEvent {
+        const ROS_TYPE_NAME: &'static str = "rcl_interfaces/ParameterEvent";
+        const MD5SUM: &'static str = "cb8c5d05cd73a7ffd3b22315eab2037a";
+        const DEFINITION : & 'static str = "# This message contains a parameter event.\n# Because the parameter event was an atomic update, a specific parameter name\n# can only be in one of the three sets.\n\n# The time stamp when this parameter event occurred.\nbuiltin_interfaces/Time stamp\n\n# Fully qualified ROS path to node.\nstring node\n\n# New parameters that have been set for this node.\nParameter[] new_parameters\n\n# Parameters that have been changed during this event.\nParameter[] changed_parameters\n\n# Parameters that have been deleted during this event.\nParameter[] deleted_parameters" ;
+    }
+    impl ParameterEvent {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            8usize
+                + 4usize
+                + self.r#node.len()
+                + 4usize
+                + self
+                    .r#new_parameters
+                    .iter()
+                    .map(|element| element.serialized_size_upper_bound())
+                    .sum::<usize>()
+                + 4usize
+                + self
+                    .r#changed_parameters
+                    .iter()
+                    .map(|element| element.serialized_size_upper_bound())
+                    .sum::<usize>()
+                + 4usize
+                + self
+                    .r#deleted_parameters
+                    .iter()
+                    .map(|element| element.serialized_size_upper_bound())
+                    .sum::<usize>()
+        }
+    }
+    #[allow(non_snake_case)]
+    #[derive(
+        :: serde :: Deserialize,
+        :: serde :: Serialize,
+        :: smart_default :: SmartDefault,
+        Debug,
+        Clone,
+        PartialEq,
+        Eq,
+        PartialOrd,
+        Ord,
+    )]
+    pub struct ParameterType {}
+    impl ::roslibrust_codegen::RosMessageType for ParameterType {
+        const ROS_TYPE_NAME: &'static str = "rcl_interfaces/ParameterType";
+        const MD5SUM: &'static str = "607fc9419cfd453b9ade36cf9de88ce8";
+        const DEFINITION : & 'static str = "# These types correspond to the value that is set in the ParameterValue message.\n\n# Default value, which implies this is not a valid parameter.\nuint8 PARAMETER_NOT_SET=0\n\nuint8 PARAMETER_BOOL=1\nuint8 PARAMETER_INTEGER=2\nuint8 PARAMETER_DOUBLE=3\nuint8 PARAMETER_STRING=4\nuint8 PARAMETER_BYTE_ARRAY=5\nuint8 PARAMETER_BOOL_ARRAY=6\nuint8 PARAMETER_INTEGER_ARRAY=7\nuint8 PARAMETER_DOUBLE_ARRAY=8\nuint8 PARAMETER_STRING_ARRAY=9" ;
+    }
+    impl ParameterType {
+        #[doc = " = 0"]
+        pub const r#PARAMETER_NOT_SET: u8 = 0u8;
+        #[doc = " = 1"]
+        pub const r#PARAMETER_BOOL: u8 = 1u8;
+        #[doc = " = 2"]
+        pub const r#PARAMETER_INTEGER: u8 = 2u8;
+        #[doc = " = 3"]
+        pub const r#PARAMETER_DOUBLE: u8 = 3u8;
+        #[doc = " = 4"]
+        pub const r#PARAMETER_STRING: u8 = 4u8;
+        #[doc = " = 5"]
+        pub const r#PARAMETER_BYTE_ARRAY: u8 = 5u8;
+        #[doc = " = 6"]
+        pub const r#PARAMETER_BOOL_ARRAY: u8 = 6u8;
+        #[doc = " = 7"]
+        pub const r#PARAMETER_INTEGER_ARRAY: u8 = 7u8;
+        #[doc = " = 8"]
+        pub const r#PARAMETER_DOUBLE_ARRAY: u8 = 8u8;
+        #[doc = " = 9"]
+        pub const r#PARAMETER_STRING_ARRAY: u8 = 9u8;
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            0usize
+        }
+    }
+    #[allow(non_snake_case)]
+    #[derive(
+        :: serde :: Deserialize,
+        :: serde :: Serialize,
+        :: smart_default :: SmartDefault,
+        Debug,
+        Clone,
+        PartialEq,
+        PartialOrd,
+    )]
+    pub struct ParameterValue {
+        pub r#type: u8,
+        pub r#bool_value: bool,
+        pub r#integer_value: i64,
+        pub r#double_value: f64,
+        pub r#string_value: ::std::string::String,
+        pub r#byte_array_value: ::std::vec::Vec<u8>,
+        pub r#bool_array_value: ::std::vec::Vec<bool>,
+        pub r#integer_array_value: ::std::vec::Vec<i64>,
+        pub r#double_array_value: ::std::vec::Vec<f64>,
+        pub r#string_array_value: ::std::vec::Vec<::std::string::String>,
+    }
+    impl ::roslibrust_codegen::RosMessageType for ParameterValue {
+        const ROS_TYPE_NAME: &'static str = "rcl_interfaces/ParameterValue";
+        const MD5SUM: &'static str = "587c645177f28280b87cb5a8349befed";
+        const DEFINITION : & 'static str = "# Used to determine which of the next *_value fields are set.\n# ParameterType.PARAMETER_NOT_SET indicates that the parameter was not set\n# (if gotten) or is uninitialized.\n# Values are enumerated in `ParameterType.msg`.\n\n# The type of this parameter, which corresponds to the appropriate field below.\nuint8 type\n\n# \"Variant\" style storage of the parameter value. Only the value corresponding\n# the type field will have valid information.\n\n# Boolean value, can be either true or false.\nbool bool_value\n\n# Integer value ranging from -9,223,372,036,854,775,808 to\n# 9,223,372,036,854,775,807.\nint64 integer_value\n\n# A double precision floating point value following IEEE 754.\nfloat64 double_value\n\n# A textual value with no practical length limit.\nstring string_value\n\n# An array of bytes, used for non-textual information.\nbyte[] byte_array_value\n\n# An array of boolean values.\nbool[] bool_array_value\n\n# An array of 64-bit integer values.\nint64[] integer_array_value\n\n# An array of 64-bit floating point values.\nfloat64[] double_array_value\n\n# An array of string values.\nstring[] string_array_value" ;
+    }
+    impl ParameterValue {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            1usize
+                + 1usize
+                + 8usize
+                + 8usize
+                + 4usize
+                + self.r#string_value.len()
+                + 4usize
+                + self.r#byte_array_value.len()
+                + 4usize
+                + self.r#bool_array_value.len()
+                + 4usize
+                + self.r#integer_array_value.len() * 8usize
+                + 4usize
+                + self.r#double_array_value.len() * 8usize
+                + 4usize
+                + self
+                    .r#string_array_value
+                    .iter()
+                    .map(|element| 4usize + element.len())
+                    .sum::<usize>()
+        }
+    }
+    #[allow(non_snake_case)]
+    #[derive(
+        :: serde :: Deserialize,
+        :: serde :: Serialize,
+        :: smart_default :: SmartDefault,
+        Debug,
+        Clone,
+        PartialEq,
+        Eq,
+        PartialOrd,
+        Ord,
+    )]
+    pub struct SetParametersResult {
+        pub r#successful: bool,
+        pub r#reason: ::std::string::String,
+    }
+    impl ::roslibrust_codegen::RosMessageType for SetParametersResult {
+        const ROS_TYPE_NAME: &'static str = "rcl_interfaces/SetParametersResult";
+        const MD5SUM: &'static str = "f25d8aed319fb6d8decc390bac218615";
+        const DEFINITION : & 'static str = "# A true value of the same index indicates that the parameter was set\n# successfully. A false value indicates the change was rejected.\nbool successful\n\n# Reason why the setting was either successful or a failure. This should only be\n# used for logging and user interfaces.\nstring reason" ;
+    }
+    impl SetParametersResult {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            1usize + 4usize + self.r#reason.len()
+        }
+    }
+}
@@ -0,0 +1,97 @@
+//! Messages from ROS2's [rcl_interfaces](https://github.com/ros2/rcl_interfaces) package, which
+//! every ROS2 node uses for logging to `/rosout` and announcing parameter changes on
+//! `/parameter_events`.
+//!
+//! Unlike messages generated with the codegen macros these ship with the crate, so need no ROS2
+//! installation to build. Only the messages listed in `assets/ros2_rcl_interfaces` are included.
+//! ```no_run
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+//! use roslibrust::rcl_interfaces::Log;
+//! let handle = roslibrust::ClientHandle::new("ws://localhost:9090").await?;
+//! let rosout = handle.subscribe::<Log>("/rosout").await?;
+//! let log = rosout.next().await;
+//! if log.level >= Log::ERROR {
+//!     println!("{}: {}", log.name, log.msg);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+// Generated by roslibrust_test's main, which also checks it is up to date
+mod generated;
+pub use generated::rcl_interfaces::*;
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use roslibrust_codegen::integral_types::Time;
+    use roslibrust_codegen::RosMessageType;
+
+    fn round_trip<T: RosMessageType + PartialEq>(msg: T) {
+        let json = serde_json::to_string(&msg).unwrap();
+        let parsed: T = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, msg, "{} didn't round trip {json}", T::ROS_TYPE_NAME);
+    }
+
+    #[test_log::test]
+    fn log_round_trips() {
+        round_trip(Log {
+            stamp: Time {
+                secs: 1700000000,
+                nsecs: 5,
+            },
+            level: Log::WARN,
+            name: "planner".to_string(),
+            msg: "No path found".to_string(),
+            file: "planner.cpp".to_string(),
+            function: "plan".to_string(),
+            line: 42,
+        });
+    }
+
+    #[test_log::test]
+    fn parameter_event_round_trips() {
+        let parameter = |name: &str, value| Parameter {
+            name: name.to_string(),
+            value,
+        };
+        round_trip(ParameterEvent {
+            stamp: Time { secs: 1, nsecs: 2 },
+            node: "/planner".to_string(),
+            new_parameters: vec![parameter(
+                "robots",
+                ParameterValue {
+                    r#type: ParameterType::PARAMETER_STRING_ARRAY,
+                    string_array_value: vec!["a".to_string(), "b".to_string()],
+                    ..Default::default()
+                },
+            )],
+            changed_parameters: vec![parameter(
+                "speed",
+                ParameterValue {
+                    r#type: ParameterType::PARAMETER_DOUBLE,
+                    double_value: 0.5,
+                    ..Default::default()
+                },
+            )],
+            deleted_parameters: vec![parameter("old", ParameterValue::default())],
+        });
+    }
+
+    #[test_log::test]
+    fn set_parameters_result_round_trips() {
+        round_trip(SetParametersResult {
+            successful: false,
+            reason: "speed must be positive".to_string(),
+        });
+    }
+
+    #[test_log::test]
+    fn parameter_type_round_trips() {
+        // Only holds constants, so is an empty message
+        round_trip(ParameterType {});
+        assert_eq!(ParameterType::PARAMETER_NOT_SET, 0);
+        assert_eq!(ParameterType::PARAMETER_STRING_ARRAY, 9);
+    }
+}
@@ -0,0 +1,702 @@
+//! Reading ROS1 `.bag` files (format version 2.0) as recorded by `rosbag record`
+
+use byteorder::{LittleEndian, ReadBytesExt};
+use roslibrust_codegen::integral_types::Time;
+use std::{
+    collections::BTreeMap,
+    fs::File,
+    io::{BufReader, Read, Seek, SeekFrom},
+    path::{Path, PathBuf},
+};
+
+/// First line of every bag file of the supported version
+const MAGIC: &[u8] = b"#ROSBAG V2.0\n";
+
+// Record op codes, see http://wiki.ros.org/Bags/Format/2.0
+const OP_MESSAGE_DATA: u8 = 0x02;
+const OP_BAG_HEADER: u8 = 0x03;
+const OP_CHUNK: u8 = 0x05;
+const OP_CHUNK_INFO: u8 = 0x06;
+const OP_CONNECTION: u8 = 0x07;
+
+/// Errors reading a bag file
+#[derive(thiserror::Error, Debug)]
+pub enum BagError {
+    #[error("Failed to read bag file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Not a version 2.0 ROS bag file")]
+    NotABag,
+    #[error("Bag file is malformed: {0}")]
+    Malformed(String),
+    /// The bag was not closed cleanly, e.g. rosbag was killed while recording
+    #[error("Bag file has no index, it can be rebuilt with `rosbag reindex`")]
+    Unindexed,
+    #[error("Topic {0} is not in the bag")]
+    UnknownTopic(String),
+    /// Only chunks recorded without compression can be read so far
+    #[error("Bag chunks are compressed with {0}, which is not supported")]
+    UnsupportedCompression(String),
+}
+
+/// Summary of one topic recorded in a bag, see [BagIndex::topics]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TopicInfo {
+    pub name: String,
+    pub topic_type: String,
+    pub md5sum: String,
+    pub message_count: usize,
+}
+
+/// One message recorded in a bag, see [BagIndex::read_messages]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BagMessage {
+    pub topic: String,
+    /// When the message was recorded
+    pub time: Time,
+    /// The serialized message, without the length prefix it has when sent over TCPROS
+    pub data: Vec<u8>,
+}
+
+#[derive(Debug)]
+struct Connection {
+    topic: String,
+    topic_type: String,
+    md5sum: String,
+}
+
+#[derive(Debug)]
+struct ChunkInfo {
+    /// Offset of the chunk record in the file
+    position: u64,
+    start: Time,
+    end: Time,
+    /// Number of messages in the chunk for each connection id
+    counts: BTreeMap<u32, u32>,
+}
+
+/// The index of a bag file, describing what it contains and where without reading any messages.
+///
+/// Bags store their index at the end of the file, so [BagIndex::open] reads only the bag header
+/// at the start and the index records at the end, taking the same time for bags of any size.
+/// ```no_run
+/// # fn main() -> Result<(), roslibrust::ros1::bags::BagError> {
+/// use roslibrust::ros1::bags::BagIndex;
+/// let index = BagIndex::open("recording.bag".as_ref())?;
+/// for topic in index.topics() {
+///     println!("{} ({}): {} messages", topic.name, topic.topic_type, topic.message_count);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct BagIndex {
+    path: PathBuf,
+    connections: BTreeMap<u32, Connection>,
+    /// Ordered by position in the file
+    chunks: Vec<ChunkInfo>,
+    /// Offset of the index, which follows the last chunk
+    index_position: u64,
+}
+
+impl BagIndex {
+    /// Reads the index of the bag file at `path`.
+    ///
+    /// Fails with [BagError::Unindexed] for bags that were not closed cleanly.
+    pub fn open(path: &Path) -> Result<BagIndex, BagError> {
+        let mut file = BufReader::new(File::open(path)?);
+        let mut magic = [0u8; MAGIC.len()];
+        file.read_exact(&mut magic)?;
+        if magic != MAGIC {
+            return Err(BagError::NotABag);
+        }
+
+        let bag_header = read_record_header(&mut file)?
+            .ok_or_else(|| BagError::Malformed("missing bag header record".to_owned()))?;
+        if op(&bag_header)? != OP_BAG_HEADER {
+            return Err(BagError::Malformed(
+                "first record is not the bag header".to_owned(),
+            ));
+        }
+        let index_position = read_u64(field(&bag_header, "index_pos")?)?;
+        if index_position == 0 {
+            return Err(BagError::Unindexed);
+        }
+
+        file.seek(SeekFrom::Start(index_position))?;
+        let mut connections = BTreeMap::new();
+        let mut chunks = vec![];
+        while let Some(header) = read_record_header(&mut file)? {
+            let data_length = file.read_u32::<LittleEndian>()?;
+            match op(&header)? {
+                OP_CONNECTION => {
+                    let id = read_u32(field(&header, "conn")?)?;
+                    let data = read_data(&mut file, data_length)?;
+                    let data = parse_fields(&data)?;
+                    connections.insert(
+                        id,
+                        Connection {
+                            topic: read_string(field(&header, "topic")?)?,
+                            topic_type: read_string(field(&data, "type")?)?,
+                            md5sum: read_string(field(&data, "md5sum")?)?,
+                        },
+                    );
+                }
+                OP_CHUNK_INFO => {
+                    let count = read_u32(field(&header, "count")?)?;
+                    let data = read_data(&mut file, data_length)?;
+                    let mut data = data.as_slice();
+                    let mut counts = BTreeMap::new();
+                    for _ in 0..count {
+                        let connection = data.read_u32::<LittleEndian>()?;
+                        let messages = data.read_u32::<LittleEndian>()?;
+                        *counts.entry(connection).or_default() += messages;
+                    }
+                    chunks.push(ChunkInfo {
+                        position: read_u64(field(&header, "chunk_pos")?)?,
+                        start: read_time(field(&header, "start_time")?)?,
+                        end: read_time(field(&header, "end_time")?)?,
+                        counts,
+                    });
+                }
+                // Nothing else belongs in the index, skip it rather than failing
+                _ => {
+                    file.seek_relative(data_length as i64)?;
+                }
+            }
+        }
+        chunks.sort_by_key(|chunk| chunk.position);
+
+        Ok(BagIndex {
+            path: path.to_owned(),
+            connections,
+            chunks,
+            index_position,
+        })
+    }
+
+    /// Every topic in the bag, sorted by name
+    pub fn topics(&self) -> Vec<TopicInfo> {
+        let mut topics = BTreeMap::<&str, TopicInfo>::new();
+        for connection in self.connections.values() {
+            topics
+                .entry(connection.topic.as_str())
+                .or_insert_with(|| TopicInfo {
+                    name: connection.topic.clone(),
+                    topic_type: connection.topic_type.clone(),
+                    md5sum: connection.md5sum.clone(),
+                    message_count: 0,
+                });
+        }
+        for topic in topics.values_mut() {
+            topic.message_count = self.message_count(&topic.name);
+        }
+        topics.into_values().collect()
+    }
+
+    /// Times of the first and last messages in the bag, both zero if it has none
+    pub fn time_range(&self) -> (Time, Time) {
+        let start = self.chunks.iter().map(|chunk| chunk.start).min_by_key(key);
+        let end = self.chunks.iter().map(|chunk| chunk.end).max_by_key(key);
+        (start.unwrap_or_default(), end.unwrap_or_default())
+    }
+
+    /// Number of messages recorded on `topic`, zero if it isn't in the bag
+    pub fn message_count(&self, topic: &str) -> usize {
+        self.chunks
+            .iter()
+            .flat_map(|chunk| &chunk.counts)
+            .filter(|(id, _)| self.is_on_topic(**id, topic))
+            .map(|(_, count)| *count as usize)
+            .sum()
+    }
+
+    /// Finds where to start reading the bag to get every message on `topic` from `time` on.
+    ///
+    /// The index only locates messages to the chunk they were recorded in, so this is the offset of
+    /// the first chunk record holding a message on the topic at or after `time`. That chunk may
+    /// also hold earlier messages which need skipping. If there are no such messages the offset
+    /// is the end of the recorded data, where the index starts.
+    pub fn seek_to(&self, topic: &str, time: Time) -> Result<u64, BagError> {
+        if !self.connections.values().any(|c| c.topic == topic) {
+            return Err(BagError::UnknownTopic(topic.to_owned()));
+        }
+        let chunk = self.chunks.iter().find(|chunk| {
+            key(&chunk.end) >= key(&time)
+                && chunk.counts.keys().any(|id| self.is_on_topic(*id, topic))
+        });
+        Ok(chunk.map_or(self.index_position, |chunk| chunk.position))
+    }
+
+    /// Reads every message in the bag into memory, ordered by the time they were recorded.
+    ///
+    /// Messages recorded at the same time keep the order they have in the file. Fails with
+    /// [BagError::UnsupportedCompression] for bags recorded with `rosbag record --bz2` or `--lz4`.
+    pub fn read_messages(&self) -> Result<Vec<BagMessage>, BagError> {
+        let mut file = BufReader::new(File::open(&self.path)?);
+        let mut messages = vec![];
+        for chunk in &self.chunks {
+            file.seek(SeekFrom::Start(chunk.position))?;
+            let header = read_record_header(&mut file)?.ok_or_else(|| {
+                BagError::Malformed("chunk is past the end of the file".to_owned())
+            })?;
+            if op(&header)? != OP_CHUNK {
+                return Err(BagError::Malformed(
+                    "index points at a record which is not a chunk".to_owned(),
+                ));
+            }
+            let compression = read_string(field(&header, "compression")?)?;
+            if compression != "none" {
+                return Err(BagError::UnsupportedCompression(compression));
+            }
+            let data_length = file.read_u32::<LittleEndian>()?;
+            let data = read_data(&mut file, data_length)?;
+            let mut records = data.as_slice();
+            while let Some(header) = read_record_header(&mut records)? {
+                let data_length = records.read_u32::<LittleEndian>()?;
+                let data = read_data(&mut records, data_length)?;
+                // Chunks also repeat the connection records, which the index already has
+                if op(&header)? != OP_MESSAGE_DATA {
+                    continue;
+                }
+                let id = read_u32(field(&header, "conn")?)?;
+                let connection = self.connections.get(&id).ok_or_else(|| {
+                    BagError::Malformed(format!("message on unknown connection {id}"))
+                })?;
+                messages.push(BagMessage {
+                    topic: connection.topic.clone(),
+                    time: read_time(field(&header, "time")?)?,
+                    data,
+                });
+            }
+        }
+        messages.sort_by_key(|message| message.time);
+        Ok(messages)
+    }
+
+    fn is_on_topic(&self, connection: u32, topic: &str) -> bool {
+        self.connections
+            .get(&connection)
+            .is_some_and(|c| c.topic == topic)
+    }
+}
+
+/// Orders times, which don't implement Ord themselves
+fn key(time: &Time) -> (u32, u32) {
+    (time.secs, time.nsecs)
+}
+
+type Fields = BTreeMap<String, Vec<u8>>;
+
+/// Reads the header of the next record, None at the end of the file
+fn read_record_header(file: &mut impl Read) -> Result<Option<Fields>, BagError> {
+    let header_length = match file.read_u32::<LittleEndian>() {
+        Ok(length) => length,
+        Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
+    let header = read_data(file, header_length)?;
+    parse_fields(&header).map(Some)
+}
+
+fn read_data(file: &mut impl Read, length: u32) -> Result<Vec<u8>, BagError> {
+    let mut data = vec![];
+    file.take(length as u64).read_to_end(&mut data)?;
+    if data.len() != length as usize {
+        return Err(std::io::Error::from(std::io::ErrorKind::UnexpectedEof).into());
+    }
+    Ok(data)
+}
+
+/// Splits `name=value` fields, each prefixed with its length, as in a TCPROS connection header.
+/// Unlike connection headers, bag record values are often binary.
+fn parse_fields(mut bytes: &[u8]) -> Result<Fields, BagError> {
+    let mut fields = Fields::new();
+    while !bytes.is_empty() {
+        let length = bytes.read_u32::<LittleEndian>()? as usize;
+        if length > bytes.len() {
+            return Err(BagError::Malformed("field overruns its record".to_owned()));
+        }
+        let (field, rest) = bytes.split_at(length);
+        let separator = field
+            .iter()
+            .position(|b| *b == b'=')
+            .ok_or_else(|| BagError::Malformed("field without a name".to_owned()))?;
+        let name = String::from_utf8_lossy(&field[..separator]).into_owned();
+        fields.insert(name, field[separator + 1..].to_vec());
+        bytes = rest;
+    }
+    Ok(fields)
+}
+
+fn field<'a>(fields: &'a Fields, name: &str) -> Result<&'a [u8], BagError> {
+    fields
+        .get(name)
+        .map(Vec::as_slice)
+        .ok_or_else(|| BagError::Malformed(format!("record is missing field {name}")))
+}
+
+fn op(fields: &Fields) -> Result<u8, BagError> {
+    match field(fields, "op")? {
+        [op] => Ok(*op),
+        _ => Err(BagError::Malformed("op field is not one byte".to_owned())),
+    }
+}
+
+fn read_u32(mut value: &[u8]) -> Result<u32, BagError> {
+    Ok(value.read_u32::<LittleEndian>()?)
+}
+
+fn read_u64(mut value: &[u8]) -> Result<u64, BagError> {
+    Ok(value.read_u64::<LittleEndian>()?)
+}
+
+fn read_time(mut value: &[u8]) -> Result<Time, BagError> {
+    Ok(Time {
+        secs: value.read_u32::<LittleEndian>()?,
+        nsecs: value.read_u32::<LittleEndian>()?,
+    })
+}
+
+fn read_string(value: &[u8]) -> Result<String, BagError> {
+    String::from_utf8(value.to_vec())
+        .map_err(|_| BagError::Malformed("field is not valid utf8".to_owned()))
+}
+
+#[cfg(test)]
+pub(crate) mod test {
+    use super::*;
+    use std::io::Write;
+    use std::time::{Duration, Instant};
+
+    fn time(secs: u32) -> Time {
+        Time { secs, nsecs: 0 }
+    }
+
+    fn fields(fields: &[(&str, &[u8])]) -> Vec<u8> {
+        let mut bytes = vec![];
+        for (name, value) in fields {
+            let field = [name.as_bytes(), b"=", value].concat();
+            bytes.extend((field.len() as u32).to_le_bytes());
+            bytes.extend(field);
+        }
+        bytes
+    }
+
+    fn write_record(file: &mut File, header: &[(&str, &[u8])], data: &[u8]) {
+        write_record_fields(file, &fields(header), data);
+    }
+
+    fn write_record_fields(file: &mut File, header: &[u8], data: &[u8]) {
+        file.write_all(&(header.len() as u32).to_le_bytes())
+            .unwrap();
+        file.write_all(header).unwrap();
+        file.write_all(&(data.len() as u32).to_le_bytes()).unwrap();
+        file.write_all(data).unwrap();
+    }
+
+    fn time_bytes(time: Time) -> Vec<u8> {
+        [time.secs.to_le_bytes(), time.nsecs.to_le_bytes()].concat()
+    }
+
+    /// A chunk holding `count` messages from connection `conn` between two times. The chunk is
+    /// `size` bytes left empty unless it has `messages`, which are written with their times.
+    pub(crate) struct TestChunk {
+        pub(crate) conn: u32,
+        pub(crate) count: u32,
+        pub(crate) start: Time,
+        pub(crate) end: Time,
+        pub(crate) size: u32,
+        pub(crate) messages: Vec<(Time, Vec<u8>)>,
+    }
+
+    /// Writes a bag in the layout used by rosbag. Chunk contents are left as holes in the file,
+    /// so bags of several gigabytes are cheap to create where the filesystem supports sparse files.
+    /// Returns the positions of the chunks.
+    pub(crate) fn write_bag(
+        path: &Path,
+        topics: &[(&str, &str)],
+        chunks: &[TestChunk],
+    ) -> Vec<u64> {
+        let mut file = File::create(path).unwrap();
+        file.write_all(MAGIC).unwrap();
+        let bag_header = |index_pos: u64| {
+            fields(&[
+                ("op", &[OP_BAG_HEADER]),
+                ("index_pos", &index_pos.to_le_bytes()),
+                ("conn_count", &(topics.len() as u32).to_le_bytes()),
+                ("chunk_count", &(chunks.len() as u32).to_le_bytes()),
+            ])
+        };
+        // Padded to 4KB like rosbag does, so it can be rewritten once the index position is known
+        let padding = vec![b' '; 4096 - bag_header(0).len() - 8];
+        write_record_fields(&mut file, &bag_header(0), &padding);
+
+        let mut positions = vec![];
+        for chunk in chunks {
+            positions.push(file.stream_position().unwrap());
+            let mut records = vec![];
+            for (time, data) in &chunk.messages {
+                let header = fields(&[
+                    ("op", &[OP_MESSAGE_DATA]),
+                    ("conn", &chunk.conn.to_le_bytes()),
+                    ("time", &time_bytes(*time)),
+                ]);
+                records.extend((header.len() as u32).to_le_bytes());
+                records.extend(header);
+                records.extend((data.len() as u32).to_le_bytes());
+                records.extend(data);
+            }
+            let size = if records.is_empty() {
+                chunk.size
+            } else {
+                records.len() as u32
+            };
+            let header = fields(&[
+                ("op", &[OP_CHUNK]),
+                ("compression", b"none"),
+                ("size", &size.to_le_bytes()),
+            ]);
+            file.write_all(&(header.len() as u32).to_le_bytes())
+                .unwrap();
+            file.write_all(&header).unwrap();
+            file.write_all(&size.to_le_bytes()).unwrap();
+            if records.is_empty() {
+                file.seek(SeekFrom::Current(size as i64)).unwrap();
+            } else {
+                file.write_all(&records).unwrap();
+            }
+            // Index data records follow each chunk, BagIndex doesn't need them
+            let entries: Vec<u8> = (0..chunk.count)
+                .flat_map(|i| [time_bytes(chunk.start), i.to_le_bytes().to_vec()].concat())
+                .collect();
+            write_record(
+                &mut file,
+                &[
+                    ("op", &[0x04]),
+                    ("ver", &1u32.to_le_bytes()),
+                    ("conn", &chunk.conn.to_le_bytes()),
+                    ("count", &chunk.count.to_le_bytes()),
+                ],
+                &entries,
+            );
+        }
+
+        let index_pos = file.stream_position().unwrap();
+        for (id, (topic, topic_type)) in topics.iter().enumerate() {
+            let id = id as u32;
+            write_record(
+                &mut file,
+                &[
+                    ("op", &[OP_CONNECTION]),
+                    ("conn", &id.to_le_bytes()),
+                    ("topic", topic.as_bytes()),
+                ],
+                &fields(&[
+                    ("topic", topic.as_bytes()),
+                    ("type", topic_type.as_bytes()),
+                    ("md5sum", b"992ce8a1687cec8c8bd883ec73ca41d1"),
+                    ("message_definition", b"string data"),
+                ]),
+            );
+        }
+        for (chunk, position) in chunks.iter().zip(&positions) {
+            write_record(
+                &mut file,
+                &[
+                    ("op", &[OP_CHUNK_INFO]),
+                    ("ver", &1u32.to_le_bytes()),
+                    ("chunk_pos", &position.to_le_bytes()),
+                    ("start_time", &time_bytes(chunk.start)),
+                    ("end_time", &time_bytes(chunk.end)),
+                    ("count", &1u32.to_le_bytes()),
+                ],
+                &[chunk.conn.to_le_bytes(), chunk.count.to_le_bytes()].concat(),
+            );
+        }
+
+        file.seek(SeekFrom::Start(MAGIC.len() as u64 + 4)).unwrap();
+        file.write_all(&bag_header(index_pos)).unwrap();
+        positions
+    }
+
+    /// Path in the temp dir which is removed when dropped
+    pub(crate) struct TempBag(pub(crate) std::path::PathBuf);
+
+    impl TempBag {
+        pub(crate) fn new(name: &str) -> TempBag {
+            let file = format!("roslibrust_{name}_{}.bag", std::process::id());
+            TempBag(std::env::temp_dir().join(file))
+        }
+    }
+
+    impl Drop for TempBag {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_file(&self.0);
+        }
+    }
+
+    fn chunk(conn: u32, count: u32, start: u32, end: u32) -> TestChunk {
+        TestChunk {
+            conn,
+            count,
+            start: time(start),
+            end: time(end),
+            size: 1000,
+            messages: vec![],
+        }
+    }
+
+    #[test_log::test]
+    fn index_describes_bag() {
+        let bag = TempBag::new("index_describes_bag");
+        let positions = write_bag(
+            &bag.0,
+            &[
+                ("/chatter", "std_msgs/String"),
+                ("/odom", "nav_msgs/Odometry"),
+                ("/chatter", "std_msgs/String"),
+            ],
+            &[
+                chunk(0, 10, 100, 110),
+                chunk(1, 5, 105, 120),
+                chunk(2, 3, 120, 130),
+            ],
+        );
+
+        let index = BagIndex::open(&bag.0).unwrap();
+        assert_eq!(
+            index.topics(),
+            [
+                TopicInfo {
+                    name: "/chatter".to_owned(),
+                    topic_type: "std_msgs/String".to_owned(),
+                    md5sum: "992ce8a1687cec8c8bd883ec73ca41d1".to_owned(),
+                    message_count: 13,
+                },
+                TopicInfo {
+                    name: "/odom".to_owned(),
+                    topic_type: "nav_msgs/Odometry".to_owned(),
+                    md5sum: "992ce8a1687cec8c8bd883ec73ca41d1".to_owned(),
+                    message_count: 5,
+                },
+            ]
+        );
+        assert_eq!(index.time_range(), (time(100), time(130)));
+        assert_eq!(index.message_count("/odom"), 5);
+        assert_eq!(index.message_count("/missing"), 0);
+
+        assert_eq!(index.seek_to("/chatter", time(0)).unwrap(), positions[0]);
+        assert_eq!(index.seek_to("/chatter", time(110)).unwrap(), positions[0]);
+        // The odom chunk covers this time, but holds nothing on /chatter
+        assert_eq!(index.seek_to("/chatter", time(115)).unwrap(), positions[2]);
+        assert_eq!(index.seek_to("/odom", time(115)).unwrap(), positions[1]);
+        assert!(index.seek_to("/chatter", time(131)).unwrap() > positions[2]);
+        assert!(matches!(
+            index.seek_to("/missing", time(0)),
+            Err(BagError::UnknownTopic(_))
+        ));
+    }
+
+    #[test_log::test]
+    fn unindexed_and_foreign_files_are_rejected() {
+        let bag = TempBag::new("unindexed");
+        write_bag(&bag.0, &[], &[]);
+        // Zero the index position, as rosbag leaves it while still recording
+        let mut bytes = std::fs::read(&bag.0).unwrap();
+        let field = bytes.windows(10).position(|w| w == b"index_pos=").unwrap() + 10;
+        bytes[field..field + 8].fill(0);
+        std::fs::write(&bag.0, bytes).unwrap();
+        assert!(matches!(BagIndex::open(&bag.0), Err(BagError::Unindexed)));
+
+        std::fs::write(&bag.0, b"#ROSBAG V1.2\n").unwrap();
+        assert!(matches!(BagIndex::open(&bag.0), Err(BagError::NotABag)));
+    }
+
+    #[test_log::test]
+    // Writes over 3GB to the temp directory and times the load, so is only run on request with
+    // `cargo test -- --ignored`
+    #[ignore]
+    fn multi_gigabyte_index_loads_quickly() {
+        let bag = TempBag::new("multi_gigabyte");
+        let chunks: Vec<_> = (0..3)
+            .map(|i| TestChunk {
+                conn: 0,
+                count: 1000,
+                start: time(i * 100),
+                end: time(i * 100 + 99),
+                size: 1 << 30,
+                messages: vec![],
+            })
+            .collect();
+        let positions = write_bag(&bag.0, &[("/points", "sensor_msgs/PointCloud2")], &chunks);
+        assert!(std::fs::metadata(&bag.0).unwrap().len() > 3 << 30);
+
+        let start = Instant::now();
+        let index = BagIndex::open(&bag.0).unwrap();
+        assert!(start.elapsed() < Duration::from_secs(1));
+        assert_eq!(index.message_count("/points"), 3000);
+        assert_eq!(index.time_range(), (time(0), time(299)));
+        assert_eq!(index.seek_to("/points", time(150)).unwrap(), positions[1]);
+    }
+
+    /// A chunk whose messages are each `data` recorded at the given times
+    pub(crate) fn message_chunk(conn: u32, messages: &[(u32, &[u8])]) -> TestChunk {
+        let times: Vec<u32> = messages.iter().map(|(secs, _)| *secs).collect();
+        TestChunk {
+            conn,
+            count: messages.len() as u32,
+            start: time(*times.iter().min().unwrap()),
+            end: time(*times.iter().max().unwrap()),
+            size: 0,
+            messages: messages
+                .iter()
+                .map(|(secs, data)| (time(*secs), data.to_vec()))
+                .collect(),
+        }
+    }
+
+    #[test_log::test]
+    fn messages_are_read_in_time_order() {
+        let bag = TempBag::new("messages_in_time_order");
+        write_bag(
+            &bag.0,
+            &[
+                ("/chatter", "std_msgs/String"),
+                ("/count", "std_msgs/UInt8"),
+            ],
+            &[
+                message_chunk(0, &[(10, b"a"), (12, b"c")]),
+                // rosbag can split connections across chunks whose times overlap
+                message_chunk(1, &[(11, b"b"), (12, b"d")]),
+            ],
+        );
+
+        let messages = BagIndex::open(&bag.0).unwrap().read_messages().unwrap();
+        let messages: Vec<_> = messages
+            .iter()
+            .map(|m| (m.topic.as_str(), m.time.secs, m.data.as_slice()))
+            .collect();
+        assert_eq!(
+            messages,
+            [
+                ("/chatter", 10, &b"a"[..]),
+                ("/count", 11, b"b"),
+                ("/chatter", 12, b"c"),
+                ("/count", 12, b"d"),
+            ]
+        );
+
+        let mut bytes = std::fs::read(&bag.0).unwrap();
+        let field = bytes
+            .windows(16)
+            .position(|w| w == b"compression=none")
+            .unwrap()
+            + 12;
+        bytes[field..field + 4].copy_from_slice(b"lz4\0");
+        std::fs::write(&bag.0, bytes).unwrap();
+        assert!(matches!(
+            BagIndex::open(&bag.0).unwrap().read_messages(),
+            Err(BagError::UnsupportedCompression(_))
+        ));
+    }
+}
@@ -0,0 +1,125 @@
+use std::{
+    ops::{Deref, DerefMut},
+    sync::{Arc, Mutex},
+};
+
+/// A pool of byte buffers which subscriptions read incoming messages into, so that high rate
+/// topics don't allocate a new buffer for every message, see
+/// [SubscriberOptions::buffer_pool](crate::ros1::SubscriberOptions::buffer_pool).
+///
+/// Buffers go back to the pool once every subscriber has received the message and it has left
+/// the subscription's queue, so a pool needs about `queue_size` buffers to avoid allocating once
+/// warmed up. Clones share the same buffers, allowing one pool to be used for several topics.
+#[derive(Clone, Debug)]
+pub struct BufferPool {
+    inner: Arc<PoolInner>,
+}
+
+#[derive(Debug)]
+struct PoolInner {
+    buffers: Mutex<Vec<Vec<u8>>>,
+    max_buffers: usize,
+}
+
+impl BufferPool {
+    /// Creates an empty pool which keeps at most `max_buffers` returned buffers for reuse,
+    /// any more are freed
+    pub fn new(max_buffers: usize) -> Self {
+        Self {
+            inner: Arc::new(PoolInner {
+                buffers: Mutex::new(Vec::with_capacity(max_buffers)),
+                max_buffers,
+            }),
+        }
+    }
+
+    /// Takes an empty buffer from the pool, allocating a new one if none are available
+    pub fn get(&self) -> PooledBuffer {
+        let data = self.inner.buffers.lock().unwrap().pop().unwrap_or_default();
+        PooledBuffer {
+            data,
+            pool: Some(self.clone()),
+        }
+    }
+
+    /// Number of buffers currently waiting in the pool to be reused
+    pub fn available(&self) -> usize {
+        self.inner.buffers.lock().unwrap().len()
+    }
+
+    fn give_back(&self, mut data: Vec<u8>) {
+        let mut buffers = self.inner.buffers.lock().unwrap();
+        if buffers.len() < self.inner.max_buffers {
+            data.clear();
+            buffers.push(data);
+        }
+    }
+}
+
+/// A raw message received by a subscription, which returns its buffer to the [BufferPool] it
+/// came from when dropped. Buffers not taken from a pool are simply freed.
+#[derive(Debug, Default)]
+pub struct PooledBuffer {
+    data: Vec<u8>,
+    pool: Option<BufferPool>,
+}
+
+impl From<Vec<u8>> for PooledBuffer {
+    fn from(data: Vec<u8>) -> Self {
+        Self { data, pool: None }
+    }
+}
+
+impl Deref for PooledBuffer {
+    type Target = Vec<u8>;
+
+    fn deref(&self) -> &Vec<u8> {
+        &self.data
+    }
+}
+
+impl DerefMut for PooledBuffer {
+    fn deref_mut(&mut self) -> &mut Vec<u8> {
+        &mut self.data
+    }
+}
+
+impl Drop for PooledBuffer {
+    fn drop(&mut self) {
+        if let Some(pool) = self.pool.take() {
+            pool.give_back(std::mem::take(&mut self.data));
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{BufferPool, PooledBuffer};
+
+    #[test_log::test]
+    fn buffers_are_reused() {
+        let pool = BufferPool::new(2);
+        let mut buffer = pool.get();
+        buffer.extend_from_slice(&[1, 2, 3]);
+        let allocation = buffer.as_ptr();
+        drop(buffer);
+        assert_eq!(pool.available(), 1);
+
+        let buffer = pool.get();
+        assert!(buffer.is_empty());
+        assert!(buffer.capacity() >= 3);
+        assert_eq!(buffer.as_ptr(), allocation);
+        assert_eq!(pool.available(), 0);
+    }
+
+    #[test_log::test]
+    fn pool_keeps_at_most_max_buffers() {
+        let pool = BufferPool::new(2);
+        let buffers: Vec<_> = (0..4).map(|_| pool.get()).collect();
+        drop(buffers);
+        assert_eq!(pool.available(), 2);
+
+        drop(PooledBuffer::from(vec![1, 2, 3]));
+        assert_eq!(pool.available(), 2);
+    }
+}
@@ -0,0 +1,450 @@
+use byteorder::{LittleEndian, ReadBytesExt};
+use roslibrust_codegen::integral_types::{Duration, Time};
+use std::collections::HashMap;
+use std::io::{Cursor, Read};
+
+/// Nested messages deeper than this are rejected, guarding against self referential definitions
+const MAX_DEPTH: usize = 64;
+
+/// A message whose type is only known at runtime, decoded with [DynamicMessageDecoder].
+/// Fields are kept in the order they appear in the message definition.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DynamicMessage {
+    /// The combination pkg_name/type_name of the message, e.g. `geometry_msgs/Point`
+    pub msg_type: String,
+    pub fields: Vec<(String, DynamicValue)>,
+}
+
+impl DynamicMessage {
+    /// Looks up the value of a field by name
+    pub fn get(&self, field_name: &str) -> Option<&DynamicValue> {
+        self.fields
+            .iter()
+            .find(|(name, _)| name == field_name)
+            .map(|(_, value)| value)
+    }
+}
+
+/// The value of a single field of a [DynamicMessage]
+#[derive(Clone, Debug, PartialEq)]
+pub enum DynamicValue {
+    Bool(bool),
+    I8(i8),
+    U8(u8),
+    I16(i16),
+    U16(u16),
+    I32(i32),
+    U32(u32),
+    I64(i64),
+    U64(u64),
+    F32(f32),
+    F64(f64),
+    String(String),
+    Time(Time),
+    Duration(Duration),
+    Array(Vec<DynamicValue>),
+    Message(DynamicMessage),
+}
+
+/// A single field of a message definition, with its type resolved to a full name
+#[derive(Clone, Debug)]
+struct FieldDefinition {
+    name: String,
+    field_type: String,
+    /// None if not an array, Some(None) for variable length arrays, Some(Some(N)) for fixed length
+    array_info: Option<Option<usize>>,
+}
+
+/// Decodes serialized messages of a type which is only known at runtime.
+///
+/// Built from the full message definition as sent in the `message_definition` field of a TCPROS
+/// connection header, which includes the definitions of all nested message types separated by
+/// lines of `=` and introduced with `MSG: pkg/Type`.
+#[derive(Clone, Debug)]
+pub struct DynamicMessageDecoder {
+    msg_type: String,
+    definitions: HashMap<String, Vec<FieldDefinition>>,
+}
+
+impl DynamicMessageDecoder {
+    /// Parses the full definition of `msg_type`, e.g. `geometry_msgs/PoseStamped`
+    pub fn new(msg_type: &str, definition: &str) -> std::io::Result<Self> {
+        let mut sections = vec![(msg_type.to_owned(), String::new())];
+        for line in definition.lines() {
+            let trimmed = line.trim();
+            if !trimmed.is_empty() && trimmed.chars().all(|c| c == '=') {
+                sections.push((String::new(), String::new()));
+            } else if let Some(name) = trimmed.strip_prefix("MSG:") {
+                let section = sections.last_mut().expect("There is always a section");
+                section.0 = name.trim().to_owned();
+            } else {
+                let section = sections.last_mut().expect("There is always a section");
+                section.1.push_str(line);
+                section.1.push('\n');
+            }
+        }
+
+        let names = sections
+            .iter()
+            .map(|(name, _)| name.clone())
+            .collect::<Vec<_>>();
+        let mut definitions = HashMap::new();
+        for (name, source) in &sections {
+            if name.is_empty() {
+                return Err(invalid_data(format!(
+                    "Definition of {msg_type} contains a section without a MSG: line"
+                )));
+            }
+            let package = name.split('/').next().unwrap_or_default();
+            let fields = source
+                .lines()
+                .filter_map(|line| parse_field(line, package, &names).transpose())
+                .collect::<std::io::Result<Vec<_>>>()?;
+            definitions.insert(name.clone(), fields);
+        }
+
+        // Catch missing dependencies now rather than when the first message arrives
+        for fields in definitions.values() {
+            for field in fields {
+                if !is_primitive(&field.field_type) && !definitions.contains_key(&field.field_type)
+                {
+                    return Err(invalid_data(format!(
+                        "Definition of {msg_type} does not include a definition for {}",
+                        field.field_type
+                    )));
+                }
+            }
+        }
+
+        Ok(Self {
+            msg_type: msg_type.to_owned(),
+            definitions,
+        })
+    }
+
+    /// The type of message this decoder decodes
+    pub fn msg_type(&self) -> &str {
+        &self.msg_type
+    }
+
+    /// Decodes a single serialized message, without its leading length prefix
+    pub fn decode(&self, data: &[u8]) -> std::io::Result<DynamicMessage> {
+        let mut cursor = Cursor::new(data);
+        let msg = self.decode_message(&self.msg_type, &mut cursor, 0)?;
+        if cursor.position() as usize != data.len() {
+            return Err(invalid_data(format!(
+                "{} bytes left over after decoding {}",
+                data.len() - cursor.position() as usize,
+                self.msg_type
+            )));
+        }
+        Ok(msg)
+    }
+
+    fn decode_message(
+        &self,
+        msg_type: &str,
+        cursor: &mut Cursor<&[u8]>,
+        depth: usize,
+    ) -> std::io::Result<DynamicMessage> {
+        if depth > MAX_DEPTH {
+            return Err(invalid_data(format!(
+                "Messages nested more than {MAX_DEPTH} deep while decoding {}",
+                self.msg_type
+            )));
+        }
+        let definition = self
+            .definitions
+            .get(msg_type)
+            .ok_or_else(|| invalid_data(format!("No definition for {msg_type}")))?;
+        let mut fields = Vec::with_capacity(definition.len());
+        for field in definition {
+            let value = match field.array_info {
+                None => self.decode_value(&field.field_type, cursor, depth)?,
+                Some(len) => {
+                    let len = match len {
+                        Some(len) => len,
+                        None => cursor.read_u32::<LittleEndian>()? as usize,
+                    };
+                    // Don't trust the length for allocation, it may be garbage
+                    let remaining = cursor.get_ref().len() - cursor.position() as usize;
+                    if len.saturating_mul(min_encoded_size(&field.field_type)) > remaining {
+                        return Err(std::io::ErrorKind::UnexpectedEof.into());
+                    }
+                    let mut items = Vec::with_capacity(len);
+                    for _ in 0..len {
+                        items.push(self.decode_value(&field.field_type, cursor, depth)?);
+                    }
+                    DynamicValue::Array(items)
+                }
+            };
+            fields.push((field.name.clone(), value));
+        }
+        Ok(DynamicMessage {
+            msg_type: msg_type.to_owned(),
+            fields,
+        })
+    }
+
+    fn decode_value(
+        &self,
+        field_type: &str,
+        cursor: &mut Cursor<&[u8]>,
+        depth: usize,
+    ) -> std::io::Result<DynamicValue> {
+        Ok(match field_type {
+            "bool" => DynamicValue::Bool(cursor.read_u8()? != 0),
+            "int8" => DynamicValue::I8(cursor.read_i8()?),
+            "uint8" | "byte" | "char" => DynamicValue::U8(cursor.read_u8()?),
+            "int16" => DynamicValue::I16(cursor.read_i16::<LittleEndian>()?),
+            "uint16" => DynamicValue::U16(cursor.read_u16::<LittleEndian>()?),
+            "int32" => DynamicValue::I32(cursor.read_i32::<LittleEndian>()?),
+            "uint32" => DynamicValue::U32(cursor.read_u32::<LittleEndian>()?),
+            "int64" => DynamicValue::I64(cursor.read_i64::<LittleEndian>()?),
+            "uint64" => DynamicValue::U64(cursor.read_u64::<LittleEndian>()?),
+            "float32" => DynamicValue::F32(cursor.read_f32::<LittleEndian>()?),
+            "float64" => DynamicValue::F64(cursor.read_f64::<LittleEndian>()?),
+            "string" => {
+                let len = cursor.read_u32::<LittleEndian>()? as usize;
+                let remaining = cursor.get_ref().len() - cursor.position() as usize;
+                if len > remaining {
+                    return Err(std::io::ErrorKind::UnexpectedEof.into());
+                }
+                let mut bytes = vec![0; len];
+                cursor.read_exact(&mut bytes)?;
+                DynamicValue::String(String::from_utf8(bytes).map_err(|err| {
+                    invalid_data(format!("String field is not valid utf8: {err}"))
+                })?)
+            }
+            "time" => DynamicValue::Time(Time {
+                secs: cursor.read_u32::<LittleEndian>()?,
+                nsecs: cursor.read_u32::<LittleEndian>()?,
+            }),
+            "duration" => DynamicValue::Duration(Duration {
+                sec: cursor.read_i32::<LittleEndian>()?,
+                nsec: cursor.read_i32::<LittleEndian>()?,
+            }),
+            msg_type => DynamicValue::Message(self.decode_message(msg_type, cursor, depth + 1)?),
+        })
+    }
+}
+
+fn is_primitive(field_type: &str) -> bool {
+    matches!(
+        field_type,
+        "bool"
+            | "int8"
+            | "uint8"
+            | "byte"
+            | "char"
+            | "int16"
+            | "uint16"
+            | "int32"
+            | "uint32"
+            | "int64"
+            | "uint64"
+            | "float32"
+            | "float64"
+            | "string"
+            | "time"
+            | "duration"
+    )
+}
+
+/// The fewest bytes a value of `field_type` can be serialized in. Messages are taken to be at
+/// least a byte, so arrays of messages without any fields can't be longer than what's left of
+/// the data, which is no loss in practice.
+fn min_encoded_size(field_type: &str) -> usize {
+    match field_type {
+        "int16" | "uint16" => 2,
+        "int32" | "uint32" | "float32" | "string" => 4,
+        "int64" | "uint64" | "float64" | "time" | "duration" => 8,
+        _ => 1,
+    }
+}
+
+/// Parses a single line of a message definition, returning None for blank lines, comments, and constants
+fn parse_field(
+    line: &str,
+    package: &str,
+    known_types: &[String],
+) -> std::io::Result<Option<FieldDefinition>> {
+    let line = line.trim();
+    let Some((type_str, rest)) = line.split_once(char::is_whitespace) else {
+        if line.is_empty() || line.starts_with('#') {
+            return Ok(None);
+        }
+        return Err(invalid_data(format!("Invalid field in definition: {line}")));
+    };
+    if type_str.starts_with('#') {
+        return Ok(None);
+    }
+    let name = rest.split('#').next().unwrap_or_default().trim();
+    if name.contains('=') {
+        // Constants aren't part of the serialized message
+        return Ok(None);
+    }
+
+    let (base_type, array_info) = match type_str.split_once('[') {
+        None => (type_str, None),
+        Some((base_type, len)) => {
+            let len = len.trim_end_matches(']');
+            let len = if len.is_empty() {
+                None
+            } else {
+                Some(len.parse::<usize>().map_err(|err| {
+                    invalid_data(format!("Invalid array length in {type_str}: {err}"))
+                })?)
+            };
+            (base_type, Some(len))
+        }
+    };
+    let field_type = if is_primitive(base_type) || base_type.contains('/') {
+        base_type.to_owned()
+    } else {
+        // Unqualified types refer to the same package, except Header which falls back to std_msgs
+        let same_package = format!("{package}/{base_type}");
+        if base_type == "Header" && !known_types.contains(&same_package) {
+            "std_msgs/Header".to_owned()
+        } else {
+            same_package
+        }
+    };
+    Ok(Some(FieldDefinition {
+        name: name.to_owned(),
+        field_type,
+        array_info,
+    }))
+}
+
+fn invalid_data(msg: String) -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::InvalidData, msg)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{DynamicMessageDecoder, DynamicValue};
+    use roslibrust_codegen::integral_types::Time;
+
+    const POSE_STAMPED_DEFINITION: &str = "\
+# A Pose with reference coordinate frame and timestamp
+Header header
+Pose pose
+
+================================================================================
+MSG: std_msgs/Header
+uint32 seq
+time stamp
+string frame_id
+
+================================================================================
+MSG: geometry_msgs/Pose
+Point position
+float64[4] orientation # not really a quaternion, but exercises fixed arrays
+uint8 FLAG=1
+
+================================================================================
+MSG: geometry_msgs/Point
+float64 x
+float64 y
+float64 z
+";
+
+    #[derive(serde::Serialize)]
+    struct Header {
+        seq: u32,
+        stamp: Time,
+        frame_id: String,
+    }
+
+    #[derive(serde::Serialize)]
+    struct PoseStamped {
+        header: Header,
+        position: [f64; 3],
+        orientation: [f64; 4],
+    }
+
+    #[test_log::test]
+    fn decodes_nested_messages() {
+        let decoder =
+            DynamicMessageDecoder::new("geometry_msgs/PoseStamped", POSE_STAMPED_DEFINITION)
+                .unwrap();
+        let data = serde_rosmsg::to_vec(&PoseStamped {
+            header: Header {
+                seq: 3,
+                stamp: Time { secs: 1, nsecs: 2 },
+                frame_id: "map".to_owned(),
+            },
+            position: [1.0, 2.0, 3.0],
+            orientation: [0.0, 0.0, 0.0, 1.0],
+        })
+        .unwrap();
+        let msg = decoder.decode(&data[4..]).unwrap();
+
+        assert_eq!(msg.msg_type, "geometry_msgs/PoseStamped");
+        let Some(DynamicValue::Message(header)) = msg.get("header") else {
+            panic!("Expected header to be a message: {msg:?}");
+        };
+        assert_eq!(header.msg_type, "std_msgs/Header");
+        assert_eq!(header.get("seq"), Some(&DynamicValue::U32(3)));
+        assert_eq!(
+            header.get("stamp"),
+            Some(&DynamicValue::Time(Time { secs: 1, nsecs: 2 }))
+        );
+        assert_eq!(
+            header.get("frame_id"),
+            Some(&DynamicValue::String("map".to_owned()))
+        );
+        let Some(DynamicValue::Message(pose)) = msg.get("pose") else {
+            panic!("Expected pose to be a message: {msg:?}");
+        };
+        // Constants are not fields
+        assert_eq!(pose.fields.len(), 2);
+        let Some(DynamicValue::Message(position)) = pose.get("position") else {
+            panic!("Expected position to be a message: {pose:?}");
+        };
+        assert_eq!(position.get("z"), Some(&DynamicValue::F64(3.0)));
+        assert_eq!(
+            pose.get("orientation"),
+            Some(&DynamicValue::Array(vec![
+                DynamicValue::F64(0.0),
+                DynamicValue::F64(0.0),
+                DynamicValue::F64(0.0),
+                DynamicValue::F64(1.0)
+            ]))
+        );
+    }
+
+    #[test_log::test]
+    fn rejects_incomplete_definitions_and_data() {
+        assert!(DynamicMessageDecoder::new("geometry_msgs/Pose", "Point position").is_err());
+
+        let decoder = DynamicMessageDecoder::new("std_msgs/String", "string data").unwrap();
+        let data = serde_rosmsg::to_vec(&"hello").unwrap();
+        assert_eq!(
+            decoder.decode(&data[4..]).unwrap().get("data"),
+            Some(&DynamicValue::String("hello".to_owned()))
+        );
+        // Truncated and oversized messages are errors rather than panics
+        assert!(decoder.decode(&data[4..data.len() - 1]).is_err());
+        assert!(decoder.decode(&[255, 255, 255, 255]).is_err());
+        let mut data = data[4..].to_vec();
+        data.push(0);
+        assert!(decoder.decode(&data).is_err());
+
+        // Array lengths longer than what's left of the data fail before anything is allocated
+        let decoder = DynamicMessageDecoder::new("pkg/Array", "float64[] data").unwrap();
+        assert!(decoder.decode(&[255, 255, 255, 255, 0, 0, 0, 0]).is_err());
+        let data = serde_rosmsg::to_vec(&vec![1.0f64, 2.0]).unwrap();
+        assert_eq!(
+            decoder.decode(&data[4..]).unwrap().get("data"),
+            Some(&DynamicValue::Array(vec![
+                DynamicValue::F64(1.0),
+                DynamicValue::F64(2.0)
+            ]))
+        );
+
+        let recursive = DynamicMessageDecoder::new("pkg/Loop", "Loop inner").unwrap();
+        assert!(recursive.decode(&[]).is_err());
+    }
+}
@@ -0,0 +1,184 @@
+//! Following changes to the ROS graph, such as nodes starting or stopping publishing a topic
+
+use super::{names::Namespace, NodeHandle};
+use abort_on_drop::ChildTask;
+use std::{future::Future, sync::Mutex, time::Duration};
+use tokio::sync::watch;
+
+/// How often [TopicMonitor] asks the master for the topic's publishers
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Follows how many nodes publish a topic, e.g. to stop processing when the last publisher goes
+/// away and resume when one appears again.
+///
+/// The count is polled from the master, so changes are noticed within half a second. Stops
+/// polling, and calling any callbacks, when dropped.
+/// ```no_run
+/// # use roslibrust::ros1::{graph::TopicMonitor, NodeHandle};
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+///   let nh = NodeHandle::new("http://localhost:11311", "/monitor").await?;
+///   let monitor = TopicMonitor::new(nh, "/scan");
+///   monitor.on_publisher_count_change(|count| println!("/scan has {count} publishers"));
+///   monitor.await_publisher().await;
+/// # Ok(())
+/// # }
+/// ```
+pub struct TopicMonitor {
+    count: watch::Receiver<usize>,
+    // Callbacks from on_publisher_count_change, each waiting on changes in its own task
+    callbacks: Mutex<Vec<ChildTask<()>>>,
+    _poll: ChildTask<()>,
+}
+
+impl TopicMonitor {
+    /// Starts following the publishers of `topic`, which is resolved relative to the node's
+    /// namespace if it isn't global. Must be called from within a tokio runtime.
+    pub fn new(node: NodeHandle, topic: &str) -> TopicMonitor {
+        let topic = match topic.starts_with('/') {
+            true => Namespace::new(topic),
+            false => node.namespace().join(topic),
+        };
+        TopicMonitor::polling(POLL_INTERVAL, move || {
+            let node = node.clone();
+            let topic = topic.clone();
+            async move {
+                let state = node.get_system_state().await?;
+                Ok(state.publishers_of(topic.as_str()).len())
+            }
+        })
+    }
+
+    // Calls `poll` every `interval` for the current publisher count
+    fn polling<F, Fut>(interval: Duration, mut poll: F) -> TopicMonitor
+    where
+        F: FnMut() -> Fut + Send + 'static,
+        Fut: Future<Output = Result<usize, Box<dyn std::error::Error + Send + Sync>>> + Send,
+    {
+        let (sender, count) = watch::channel(0);
+        let task = tokio::spawn(async move {
+            let mut interval = tokio::time::interval(interval);
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                interval.tick().await;
+                match poll().await {
+                    // Only wakes listeners when the count actually changes
+                    Ok(polled) => {
+                        sender.send_if_modified(|count| std::mem::replace(count, polled) != polled);
+                    }
+                    Err(e) => log::warn!("Failed to get publishers from the master: {e}"),
+                }
+            }
+        });
+        TopicMonitor {
+            count,
+            callbacks: Mutex::new(vec![]),
+            _poll: task.into(),
+        }
+    }
+
+    /// The number of nodes publishing the topic as of the last poll, 0 until the master has first
+    /// answered
+    pub fn publisher_count(&self) -> usize {
+        *self.count.borrow()
+    }
+
+    /// Resolves once at least one node publishes the topic, immediately if one already does
+    pub fn await_publisher(&self) -> impl Future<Output = ()> + Send + 'static {
+        let mut count = self.count.clone();
+        async move {
+            while *count.borrow_and_update() == 0 {
+                if count.changed().await.is_err() {
+                    // The monitor was dropped, so the count will never change again
+                    return std::future::pending().await;
+                }
+            }
+        }
+    }
+
+    /// Calls `cb` with the new count each time the number of publishers changes, for as long as
+    /// the monitor exists
+    pub fn on_publisher_count_change(&self, cb: impl Fn(usize) + Send + 'static) {
+        let mut count = self.count.clone();
+        count.borrow_and_update();
+        let task = tokio::spawn(async move {
+            while count.changed().await.is_ok() {
+                let current = *count.borrow_and_update();
+                cb(current);
+            }
+        });
+        self.callbacks.lock().unwrap().push(task.into());
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::TopicMonitor;
+    use std::{
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc, Mutex,
+        },
+        time::Duration,
+    };
+
+    const INTERVAL: Duration = Duration::from_millis(10);
+
+    // A monitor whose publisher count is whatever is stored in the returned counter
+    fn fake_monitor() -> (TopicMonitor, Arc<AtomicUsize>) {
+        let publishers = Arc::new(AtomicUsize::new(0));
+        let monitor = TopicMonitor::polling(INTERVAL, {
+            let publishers = publishers.clone();
+            move || {
+                let count = publishers.load(Ordering::SeqCst);
+                async move { Ok(count) }
+            }
+        });
+        (monitor, publishers)
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn first_publisher_appearing_is_awaited() {
+        let (monitor, publishers) = fake_monitor();
+        let waiting = tokio::spawn(monitor.await_publisher());
+        tokio::time::sleep(INTERVAL * 5).await;
+        assert!(!waiting.is_finished());
+        assert_eq!(monitor.publisher_count(), 0);
+
+        publishers.store(1, Ordering::SeqCst);
+        tokio::time::timeout(Duration::from_secs(1), waiting)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(monitor.publisher_count(), 1);
+
+        // Already has a publisher
+        tokio::time::timeout(Duration::from_secs(1), monitor.await_publisher())
+            .await
+            .unwrap();
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn count_changes_are_reported() {
+        let (monitor, publishers) = fake_monitor();
+        let changes = Arc::new(Mutex::new(vec![]));
+        monitor.on_publisher_count_change({
+            let changes = changes.clone();
+            move |count| changes.lock().unwrap().push(count)
+        });
+
+        // 0 -> 1 -> 2 -> 1 -> 0, with repeated polls of the same count not reported
+        for count in [0, 1, 2, 1, 0] {
+            publishers.store(count, Ordering::SeqCst);
+            tokio::time::sleep(INTERVAL * 5).await;
+            assert_eq!(monitor.publisher_count(), count);
+        }
+        assert_eq!(*changes.lock().unwrap(), [1, 2, 1, 0]);
+
+        // Callbacks stop with the monitor
+        drop(monitor);
+        publishers.store(3, Ordering::SeqCst);
+        tokio::time::sleep(INTERVAL * 5).await;
+        assert_eq!(*changes.lock().unwrap(), [1, 2, 1, 0]);
+    }
+}
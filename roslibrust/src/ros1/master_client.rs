@@ -14,7 +14,7 @@ pub enum RosMasterError {
     MasterError(String),
     #[error("Failure running xmlrpc server: {0}")]
     HostIoError(#[from] hyper::Error),
-    #[error("Failed to determine a valid ip address on which to host the nodes xmlrpc server, check that one of ROS_IP, ROS_HOSTNAME or the computer's hostname resolve to a valid Ipv4 address {0}")]
+    #[error("Failed to determine a valid ip address on which to host the nodes xmlrpc server, check that one of ROS_IP, ROS_HOSTNAME or the computer's hostname resolve to a valid IPv4 or IPv6 address {0}")]
     HostIpResolutionFailure(String),
 }
 
@@ -46,7 +46,24 @@ pub struct SystemState {
     service_providers: Vec<StateEntry>,
 }
 
+/// A topic with at least one publisher, as listed by the master's getPublishedTopics
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TopicDescription {
+    /// Fully resolved name of the topic, e.g. `/robot/odom`
+    pub name: String,
+    /// The type published on the topic, e.g. `nav_msgs/Odometry`
+    pub type_name: String,
+}
+
 impl SystemState {
+    /// Names of the nodes publishing a given topic, empty if nothing publishes it
+    pub fn publishers_of(&self, topic: &str) -> &[String] {
+        self.publishers
+            .iter()
+            .find(|entry| entry.topic.eq(topic))
+            .map_or(&[], |entry| &entry.nodes)
+    }
+
     /// Helper function for checking if a node is registered as a publisher of a given topic.
     /// Returns true iff the node is a publisher of that topic
     pub fn is_publishing(&self, topic: &str, node: &str) -> bool {
@@ -321,6 +338,38 @@ impl MasterClient {
         self.post(body).await
     }
 
+    /// Hits the [parameter server](http://wiki.ros.org/ROS/Parameter%20Server%20API)'s xmlrpc
+    /// endpoint "hasParam", returns true if the parameter is set.
+    /// Relative keys are resolved by the master relative to this client's id.
+    pub async fn has_param(&self, key: impl Into<String>) -> Result<bool, RosMasterError> {
+        let body = serde_xmlrpc::request_to_string(
+            "hasParam",
+            vec![self.id.clone().into(), key.into().into()],
+        )?;
+        self.post(body).await
+    }
+
+    /// Hits the parameter server's xmlrpc endpoint "getParam", returns the value of the parameter,
+    /// failing with [RosMasterError::MasterError] if it isn't set.
+    /// Relative keys are resolved by the master relative to this client's id.
+    pub async fn get_param<T: serde::de::DeserializeOwned + std::fmt::Debug>(
+        &self,
+        key: impl Into<String>,
+    ) -> Result<T, RosMasterError> {
+        let body = serde_xmlrpc::request_to_string(
+            "getParam",
+            vec![self.id.clone().into(), key.into().into()],
+        )?;
+        self.post(body).await
+    }
+
+    /// Hits the parameter server's xmlrpc endpoint "getParamNames", returns the fully resolved
+    /// names of every parameter that is set
+    pub async fn get_param_names(&self) -> Result<Vec<String>, RosMasterError> {
+        let body = serde_xmlrpc::request_to_string("getParamNames", vec![self.id.clone().into()])?;
+        self.post(body).await
+    }
+
     /// Returns where this client believes its own node's xmlrpc server is hosted at.
     /// This is simply a getter for the client_uri passed in while constructing this client.
     pub fn client_uri(&self) -> &str {
@@ -469,6 +518,21 @@ mod test {
         assert!(!node_uri.is_empty());
     }
 
+    #[test_log::test(tokio::test)]
+    async fn test_has_param() {
+        let client = test_client().await.unwrap();
+        // Set by roscore on startup
+        assert!(client.has_param("/run_id").await.unwrap());
+        assert!(!client.has_param("/no/such/param").await.unwrap());
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn test_get_param_names() {
+        let client = test_client().await.unwrap();
+        let names = client.get_param_names().await.unwrap();
+        assert!(names.iter().any(|name| name == "/run_id"));
+    }
+
     #[test_log::test(tokio::test)]
     async fn test_get_published_topics() {
         let client = test_client().await.unwrap();
@@ -1,17 +1,54 @@
 //! This module holds all content for directly working with ROS1 natively
 
+mod buffer_pool;
+pub use buffer_pool::{BufferPool, PooledBuffer};
+
 /// [master_client] module contains code for calling xmlrpc functions on the master
 mod master_client;
 pub use master_client::*;
 
-mod names;
+mod dynamic_message;
+pub use dynamic_message::{DynamicMessage, DynamicMessageDecoder, DynamicValue};
+
+use crate::names;
+pub use names::{validate_topic_name, validate_type_name, InvalidName, Namespace};
 
 /// [node] module contains the central Node and NodeHandle APIs
 mod node;
 pub use node::*;
 
+mod nodelet;
+pub use nodelet::{NodeletContainer, NodeletError, NodeletType, Nodelets};
+pub use roslibrust_codegen_macro::nodelets;
+// Used by code generated by the nodelets attribute
+#[doc(hidden)]
+pub use async_trait::async_trait;
+
 mod publisher;
-pub use publisher::Publisher;
+#[allow(deprecated)]
+pub use publisher::PublisherOptions;
+pub use publisher::{
+    AdvertiseOptions, BacklogEvent, DeliveryReport, LatchedPublisher, Md5Check, Publisher,
+    QueueFullPolicy, SubscriberQueueStatus,
+};
+mod protocol;
+pub use protocol::{ProtocolOffer, SelectedProtocol, UDPROS_PROTOCOL};
+mod rate_stats;
+pub use rate_stats::RateStats;
+mod service_client;
+pub use service_client::{ServiceClient, ServiceError, ServiceProxy};
+mod service_registry;
+pub use service_registry::{ServiceDefinition, ServiceTypeRegistry};
 mod subscriber;
-pub use subscriber::Subscriber;
+pub use subscriber::{DynamicSubscriber, Subscriber, SubscriberOptions};
 mod tcpros;
+pub use tcpros::{
+    Compression, ConnectionHeader, ConnectionHeaderDecoder, HeaderDiff, TcprosError,
+    TruncatedMessage, DEFAULT_MAX_FRAME_LENGTH, TCPROS_PROTOCOL,
+};
+pub mod bags;
+pub mod graph;
+pub mod testing;
+pub mod time;
+#[cfg(feature = "cli-tools")]
+pub mod topics;
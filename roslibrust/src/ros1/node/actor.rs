@@ -1,18 +1,28 @@
-use super::ProtocolParams;
+use super::NodeConfig;
 use crate::{
     ros1::{
-        names::Name,
+        names::{validate_topic_name, validate_type_name, Name},
         node::{XmlRpcServer, XmlRpcServerHandle},
-        publisher::Publication,
-        subscriber::Subscription,
-        MasterClient,
+        protocol::{self, ProtocolOffer, SelectedProtocol},
+        publisher::{AdvertiseOptions, Publication, PublicationHandle},
+        rate_stats::RateTracker,
+        subscriber::{RawMessage, SubscriberOptions, Subscription},
+        tcpros, ConnectionHeader, MasterClient, SystemState, TopicDescription,
     },
     ServiceCallback,
 };
 use abort_on_drop::ChildTask;
 use roslibrust_codegen::RosMessageType;
-use std::{collections::HashMap, net::Ipv4Addr, sync::Arc};
-use tokio::sync::{broadcast, mpsc, oneshot};
+use std::{collections::HashMap, net::IpAddr, sync::Arc};
+use tokio::sync::{broadcast, mpsc, oneshot, watch};
+
+/// The raw messages received by a subscription, along with the connection header of the
+/// publisher they most recently came from and the rate they are arriving at
+pub(crate) type SubscriberChannels = (
+    broadcast::Receiver<RawMessage>,
+    watch::Receiver<Option<ConnectionHeader>>,
+    RateTracker,
+);
 
 #[derive(Debug)]
 pub enum NodeMsg {
@@ -22,6 +32,9 @@ pub enum NodeMsg {
     GetClientUri {
         reply: oneshot::Sender<String>,
     },
+    GetNodeName {
+        reply: oneshot::Sender<String>,
+    },
     GetSubscriptions {
         reply: oneshot::Sender<Vec<(String, String)>>,
     },
@@ -34,26 +47,50 @@ pub enum NodeMsg {
     },
     Shutdown,
     RegisterPublisher {
-        reply: oneshot::Sender<Result<mpsc::Sender<Vec<u8>>, String>>,
-        topic: String,
+        reply: oneshot::Sender<Result<PublicationHandle, String>>,
         topic_type: String,
-        queue_size: usize,
         msg_definition: String,
         md5sum: String,
+        options: AdvertiseOptions,
     },
     RegisterSubscriber {
-        reply: oneshot::Sender<Result<broadcast::Receiver<Vec<u8>>, String>>,
+        reply: oneshot::Sender<Result<SubscriberChannels, String>>,
         topic: String,
         topic_type: String,
         queue_size: usize,
         msg_definition: String,
         md5sum: String,
+        options: SubscriberOptions,
     },
     RequestTopic {
-        reply: oneshot::Sender<Result<ProtocolParams, String>>,
+        reply: oneshot::Sender<Result<SelectedProtocol, String>>,
         caller_id: String,
         topic: String,
-        protocols: Vec<String>,
+        protocols: Vec<ProtocolOffer>,
+    },
+    HasParam {
+        reply: oneshot::Sender<Result<bool, String>>,
+        key: String,
+    },
+    GetParamNames {
+        reply: oneshot::Sender<Result<Vec<String>, String>>,
+    },
+    UseSimTime {
+        reply: oneshot::Sender<Result<bool, String>>,
+    },
+    GetSystemState {
+        reply: oneshot::Sender<Result<SystemState, String>>,
+    },
+    GetPublishedTopics {
+        reply: oneshot::Sender<Result<Vec<TopicDescription>, String>>,
+        subgraph: String,
+    },
+    GetTopicTypes {
+        reply: oneshot::Sender<Result<Vec<(String, String)>, String>>,
+    },
+    LookupService {
+        reply: oneshot::Sender<Result<String, String>>,
+        service: String,
     },
 }
 
@@ -90,6 +127,18 @@ impl NodeServerHandle {
         }
     }
 
+    /// Get the name of the node, which it uses as its caller id
+    pub async fn get_node_name(&self) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let (sender, receiver) = oneshot::channel();
+        match self
+            .node_server_sender
+            .send(NodeMsg::GetNodeName { reply: sender })
+        {
+            Ok(()) => Ok(receiver.await?),
+            Err(e) => Err(Box::new(e)),
+        }
+    }
+
     /// Gets the list of topics the node is currently subscribed to.
     /// Returns a tuple of (Topic Name, Topic Type) e.g. ("/rosout", "rosgraph_msgs/Log").
     pub async fn get_subscriptions(
@@ -142,17 +191,17 @@ impl NodeServerHandle {
 
     pub async fn register_publisher<T: RosMessageType>(
         &self,
-        topic: &str,
-        queue_size: usize,
-    ) -> Result<mpsc::Sender<Vec<u8>>, Box<dyn std::error::Error + Send + Sync>> {
+        options: AdvertiseOptions,
+    ) -> Result<PublicationHandle, Box<dyn std::error::Error + Send + Sync>> {
+        validate_topic_name(&options.topic)?;
+        validate_type_name(T::ROS_TYPE_NAME)?;
         let (sender, receiver) = oneshot::channel();
         match self.node_server_sender.send(NodeMsg::RegisterPublisher {
             reply: sender,
-            topic: topic.to_owned(),
             topic_type: T::ROS_TYPE_NAME.to_owned(),
-            queue_size,
             msg_definition: T::DEFINITION.to_owned(),
             md5sum: T::MD5SUM.to_owned(),
+            options,
         }) {
             Ok(()) => {
                 let received = receiver.await.map_err(|err| Box::new(err))?;
@@ -168,15 +217,60 @@ impl NodeServerHandle {
         &self,
         topic: &str,
         queue_size: usize,
-    ) -> Result<broadcast::Receiver<Vec<u8>>, Box<dyn std::error::Error + Send + Sync>> {
+        options: SubscriberOptions,
+    ) -> Result<SubscriberChannels, Box<dyn std::error::Error + Send + Sync>> {
+        self.register_subscriber_inner(
+            topic,
+            T::ROS_TYPE_NAME,
+            queue_size,
+            T::DEFINITION,
+            T::MD5SUM,
+            options,
+        )
+        .await
+    }
+
+    /// Registers a subscriber which accepts any message type, returning the connection header of
+    /// the publisher alongside the raw messages so they can be decoded
+    pub async fn register_dynamic_subscriber(
+        &self,
+        topic: &str,
+        queue_size: usize,
+    ) -> Result<SubscriberChannels, Box<dyn std::error::Error + Send + Sync>> {
+        self.register_subscriber_inner(
+            topic,
+            "*",
+            queue_size,
+            "",
+            "*",
+            SubscriberOptions::default(),
+        )
+        .await
+    }
+
+    async fn register_subscriber_inner(
+        &self,
+        topic: &str,
+        topic_type: &str,
+        queue_size: usize,
+        msg_definition: &str,
+        md5sum: &str,
+        options: SubscriberOptions,
+    ) -> Result<SubscriberChannels, Box<dyn std::error::Error + Send + Sync>> {
+        validate_topic_name(topic)?;
+        // Dynamic subscribers accept any type
+        if topic_type != "*" {
+            validate_type_name(topic_type)?;
+        }
         let (sender, receiver) = oneshot::channel();
         match self.node_server_sender.send(NodeMsg::RegisterSubscriber {
             reply: sender,
             topic: topic.to_owned(),
-            topic_type: T::ROS_TYPE_NAME.to_owned(),
+            topic_type: topic_type.to_owned(),
             queue_size,
-            msg_definition: T::DEFINITION.to_owned(),
-            md5sum: T::MD5SUM.to_owned(),
+            msg_definition: msg_definition.to_owned(),
+            md5sum: md5sum.to_owned(),
+            options,
         }) {
             Ok(()) => {
                 let received = receiver.await.map_err(|err| Box::new(err))?;
@@ -193,13 +287,13 @@ impl NodeServerHandle {
         &self,
         caller_id: &str,
         topic: &str,
-        protocols: &[String],
-    ) -> Result<ProtocolParams, Box<dyn std::error::Error>> {
+        protocols: &[ProtocolOffer],
+    ) -> Result<SelectedProtocol, Box<dyn std::error::Error>> {
         let (sender, receiver) = oneshot::channel();
         match self.node_server_sender.send(NodeMsg::RequestTopic {
             caller_id: caller_id.to_owned(),
             topic: topic.to_owned(),
-            protocols: protocols.into(),
+            protocols: protocols.to_vec(),
             reply: sender,
         }) {
             Ok(()) => {
@@ -214,6 +308,105 @@ impl NodeServerHandle {
             Err(e) => Err(Box::new(e)),
         }
     }
+
+    /// Checks with the parameter server whether the parameter `key` is set
+    pub async fn has_param(
+        &self,
+        key: &str,
+    ) -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
+        let (sender, receiver) = oneshot::channel();
+        match self.node_server_sender.send(NodeMsg::HasParam {
+            reply: sender,
+            key: key.to_owned(),
+        }) {
+            Ok(()) => Ok(receiver.await??),
+            Err(err) => Err(Box::new(err)),
+        }
+    }
+
+    /// Gets the names of every parameter set on the parameter server
+    pub async fn get_param_names(
+        &self,
+    ) -> Result<Vec<String>, Box<dyn std::error::Error + Send + Sync>> {
+        let (sender, receiver) = oneshot::channel();
+        match self
+            .node_server_sender
+            .send(NodeMsg::GetParamNames { reply: sender })
+        {
+            Ok(()) => Ok(receiver.await??),
+            Err(err) => Err(Box::new(err)),
+        }
+    }
+
+    /// Checks the `/use_sim_time` parameter, which is false if not set
+    pub async fn use_sim_time(&self) -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
+        let (sender, receiver) = oneshot::channel();
+        match self
+            .node_server_sender
+            .send(NodeMsg::UseSimTime { reply: sender })
+        {
+            Ok(()) => Ok(receiver.await??),
+            Err(err) => Err(Box::new(err)),
+        }
+    }
+
+    /// Gets every publisher, subscriber, and service provider registered with the master
+    pub async fn get_system_state(
+        &self,
+    ) -> Result<SystemState, Box<dyn std::error::Error + Send + Sync>> {
+        let (sender, receiver) = oneshot::channel();
+        match self
+            .node_server_sender
+            .send(NodeMsg::GetSystemState { reply: sender })
+        {
+            Ok(()) => Ok(receiver.await??),
+            Err(err) => Err(Box::new(err)),
+        }
+    }
+
+    /// Lists the topics within `subgraph` which have a publisher, every topic for `""`
+    pub async fn get_published_topics(
+        &self,
+        subgraph: &str,
+    ) -> Result<Vec<TopicDescription>, Box<dyn std::error::Error + Send + Sync>> {
+        let (sender, receiver) = oneshot::channel();
+        match self.node_server_sender.send(NodeMsg::GetPublishedTopics {
+            reply: sender,
+            subgraph: subgraph.to_owned(),
+        }) {
+            Ok(()) => Ok(receiver.await??),
+            Err(err) => Err(Box::new(err)),
+        }
+    }
+
+    /// Gets the name and type of every topic known to the master
+    pub async fn get_topic_types(
+        &self,
+    ) -> Result<Vec<(String, String)>, Box<dyn std::error::Error + Send + Sync>> {
+        let (sender, receiver) = oneshot::channel();
+        match self
+            .node_server_sender
+            .send(NodeMsg::GetTopicTypes { reply: sender })
+        {
+            Ok(()) => Ok(receiver.await??),
+            Err(err) => Err(Box::new(err)),
+        }
+    }
+
+    /// Asks the master for the uri of the node hosting `service`, e.g. "rosrpc://localhost:45123"
+    pub async fn lookup_service(
+        &self,
+        service: &str,
+    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let (sender, receiver) = oneshot::channel();
+        match self.node_server_sender.send(NodeMsg::LookupService {
+            reply: sender,
+            service: service.to_owned(),
+        }) {
+            Ok(()) => Ok(receiver.await??),
+            Err(err) => Err(Box::new(err)),
+        }
+    }
 }
 
 /// Represents a single "real" node, typically only one of these is expected per process
@@ -227,14 +420,17 @@ pub(crate) struct Node {
     node_msg_rx: mpsc::UnboundedReceiver<NodeMsg>,
     // Map of topic names to the publishing channels associated with the topic
     publishers: HashMap<String, Publication>,
-    // Record of subscriptions this node has
-    subscriptions: HashMap<String, Subscription>,
+    // Record of subscriptions this node has, keyed by topic and type. Dynamic subscriptions have
+    // the type "*" so they don't share a typed subscription's connections, or hand theirs to one.
+    subscriptions: HashMap<(String, String), Subscription>,
     // Record of what services this node is serving
     services: HashMap<String, ServiceCallback>,
     // TODO need signal to shutdown xmlrpc server when node is dropped
-    host_addr: Ipv4Addr,
+    host_addr: IpAddr,
+    /// Bare hostname or IP address which other nodes connect to, without brackets for IPv6
     hostname: String,
     node_name: String,
+    config: NodeConfig,
 }
 
 impl Node {
@@ -242,7 +438,8 @@ impl Node {
         master_uri: &str,
         hostname: &str,
         node_name: &str,
-        addr: Ipv4Addr,
+        addr: IpAddr,
+        config: NodeConfig,
     ) -> Result<NodeServerHandle, Box<dyn std::error::Error + Send + Sync>> {
         let (node_sender, node_receiver) = mpsc::unbounded_channel();
         let xml_server_handle = NodeServerHandle {
@@ -252,7 +449,10 @@ impl Node {
         };
         // Create our xmlrpc server and bind our socket so we know our port and can determine our local URI
         let xmlrpc_server = XmlRpcServer::new(addr, xml_server_handle)?;
-        let client_uri = format!("http://{hostname}:{}", xmlrpc_server.port());
+        let client_uri = format!(
+            "http://{}",
+            tcpros::host_port(hostname, xmlrpc_server.port())
+        );
 
         let _ = Name::new(node_name)?;
 
@@ -267,6 +467,7 @@ impl Node {
             host_addr: addr,
             hostname: hostname.to_owned(),
             node_name: node_name.to_owned(),
+            config,
         };
 
         let t = Arc::new(
@@ -304,11 +505,14 @@ impl Node {
             NodeMsg::GetClientUri { reply } => {
                 let _ = reply.send(self.client.client_uri().to_owned());
             }
+            NodeMsg::GetNodeName { reply } => {
+                let _ = reply.send(self.node_name.clone());
+            }
             NodeMsg::GetSubscriptions { reply } => {
                 let _ = reply.send(
                     self.subscriptions
                         .iter()
-                        .map(|(topic_name, subscription)| {
+                        .map(|((topic_name, _), subscription)| {
                             (topic_name.clone(), subscription.topic_type().to_owned())
                         })
                         .collect(),
@@ -323,15 +527,21 @@ impl Node {
                 );
             }
             NodeMsg::SetPeerPublishers { topic, publishers } => {
-                if let Some(subscription) = self.subscriptions.get_mut(&topic) {
-                    for publisher_uri in publishers {
-                        if let Err(err) = subscription.add_publisher_source(&publisher_uri).await {
+                let mut subscribed = false;
+                for ((topic_name, _), subscription) in self.subscriptions.iter_mut() {
+                    if *topic_name != topic {
+                        continue;
+                    }
+                    subscribed = true;
+                    for publisher_uri in &publishers {
+                        if let Err(err) = subscription.add_publisher_source(publisher_uri).await {
                             log::error!(
                                 "Unable to create subscribe stream for topic {topic}: {err}"
                             );
                         }
                     }
-                } else {
+                }
+                if !subscribed {
                     log::warn!(
                         "Got peer publisher update for topic we weren't subscribed to, ignoring"
                     );
@@ -339,14 +549,13 @@ impl Node {
             }
             NodeMsg::RegisterPublisher {
                 reply,
-                topic,
                 topic_type,
-                queue_size,
                 msg_definition,
                 md5sum,
+                options,
             } => {
                 let res = self
-                    .register_publisher(topic, &topic_type, queue_size, msg_definition, md5sum)
+                    .register_publisher(&topic_type, msg_definition, md5sum, options)
                     .await;
                 match res {
                     Ok(handle) => reply.send(Ok(handle)),
@@ -361,6 +570,7 @@ impl Node {
                 queue_size,
                 msg_definition,
                 md5sum,
+                options,
             } => {
                 let _ = reply.send(
                     self.register_subscriber(
@@ -369,6 +579,7 @@ impl Node {
                         queue_size,
                         &msg_definition,
                         &md5sum,
+                        options,
                     )
                     .await
                     .map_err(|err| err.to_string()),
@@ -381,20 +592,15 @@ impl Node {
                 ..
             } => {
                 // TODO: Should move the actual implementation similar to RegisterPublisher
-                if protocols
-                    .iter()
-                    .find(|proto| proto.as_str() == "TCPROS")
-                    .is_some()
-                {
+                if protocol::select_protocol(&protocols).is_some() {
                     if let Some((_key, publishing_channel)) =
                         self.publishers.iter().find(|(key, _pub)| *key == &topic)
                     {
-                        let protocol_params = ProtocolParams {
-                            hostname: self.hostname.clone(),
-                            protocol: String::from("TCPROS"), // Hardcoded as the only option for now
+                        let selected = SelectedProtocol::Tcpros {
+                            host: self.hostname.clone(),
                             port: publishing_channel.port(),
                         };
-                        let _ = reply.send(Ok(protocol_params));
+                        let _ = reply.send(Ok(selected));
                     } else {
                         let err_str = format!("Got request for topic {topic} from subscriber which this node does not publish");
                         log::warn!("{err_str}");
@@ -408,6 +614,67 @@ impl Node {
                     let _ = reply.send(Err(err_str));
                 }
             }
+            NodeMsg::HasParam { reply, key } => {
+                let _ = reply.send(
+                    self.client
+                        .has_param(key)
+                        .await
+                        .map_err(|err| err.to_string()),
+                );
+            }
+            NodeMsg::GetParamNames { reply } => {
+                let _ = reply.send(
+                    self.client
+                        .get_param_names()
+                        .await
+                        .map_err(|err| err.to_string()),
+                );
+            }
+            NodeMsg::UseSimTime { reply } => {
+                const USE_SIM_TIME: &str = "/use_sim_time";
+                let use_sim_time = match self.client.has_param(USE_SIM_TIME).await {
+                    Ok(true) => self.client.get_param::<bool>(USE_SIM_TIME).await,
+                    unset_or_err => unset_or_err,
+                };
+                let _ = reply.send(use_sim_time.map_err(|err| err.to_string()));
+            }
+            NodeMsg::GetSystemState { reply } => {
+                let _ = reply.send(
+                    self.client
+                        .get_system_state()
+                        .await
+                        .map_err(|err| err.to_string()),
+                );
+            }
+            NodeMsg::GetPublishedTopics { reply, subgraph } => {
+                let topics = self.client.get_published_topics(subgraph).await;
+                let _ = reply.send(
+                    topics
+                        .map(|topics| {
+                            topics
+                                .into_iter()
+                                .map(|(name, type_name)| TopicDescription { name, type_name })
+                                .collect()
+                        })
+                        .map_err(|err| err.to_string()),
+                );
+            }
+            NodeMsg::GetTopicTypes { reply } => {
+                let _ = reply.send(
+                    self.client
+                        .get_topic_types()
+                        .await
+                        .map_err(|err| err.to_string()),
+                );
+            }
+            NodeMsg::LookupService { reply, service } => {
+                let _ = reply.send(
+                    self.client
+                        .lookup_service(service)
+                        .await
+                        .map_err(|err| err.to_string()),
+                );
+            }
             NodeMsg::Shutdown => {
                 unreachable!("This node msg is handled in the wrapping handling code");
             }
@@ -421,10 +688,20 @@ impl Node {
         queue_size: usize,
         msg_definition: &str,
         md5sum: &str,
-    ) -> Result<broadcast::Receiver<Vec<u8>>, Box<dyn std::error::Error>> {
-        match self.subscriptions.iter().find(|(key, _)| *key == topic) {
-            Some((_topic, subscription)) => Ok(subscription.get_receiver()),
+        options: SubscriberOptions,
+    ) -> Result<SubscriberChannels, Box<dyn std::error::Error>> {
+        let key = (topic.to_owned(), topic_type.to_owned());
+        match self.subscriptions.get(&key) {
+            Some(subscription) => Ok((
+                subscription.get_receiver(),
+                subscription.get_publisher_header(),
+                subscription.get_rate_tracker(),
+            )),
             None => {
+                let options = SubscriberOptions {
+                    tcp_nodelay: options.tcp_nodelay.or(Some(self.config.tcp_nodelay)),
+                    ..options
+                };
                 let mut subscription = Subscription::new(
                     &self.node_name,
                     &topic,
@@ -432,6 +709,7 @@ impl Node {
                     queue_size,
                     msg_definition.to_owned(),
                     md5sum.to_owned(),
+                    options,
                 );
                 let current_publishers = self.client.register_subscriber(topic, topic_type).await?;
                 for publisher in current_publishers {
@@ -439,30 +717,40 @@ impl Node {
                         log::error!("Unable to create subscriber connection to {publisher} for {topic}: {err}");
                     }
                 }
-                let receiver = subscription.get_receiver();
-                self.subscriptions.insert(topic.to_owned(), subscription);
-                Ok(receiver)
+                let channels = (
+                    subscription.get_receiver(),
+                    subscription.get_publisher_header(),
+                    subscription.get_rate_tracker(),
+                );
+                self.subscriptions.insert(key, subscription);
+                Ok(channels)
             }
         }
     }
 
     async fn register_publisher(
         &mut self,
-        topic: String,
         topic_type: &str,
-        queue_size: usize,
         msg_definition: String,
         md5sum: String,
-    ) -> Result<mpsc::Sender<Vec<u8>>, Box<dyn std::error::Error>> {
+        options: AdvertiseOptions,
+    ) -> Result<PublicationHandle, Box<dyn std::error::Error>> {
+        let topic = options.topic.clone();
         let existing_entry = {
             self.publishers.iter().find_map(|(key, value)| {
                 if key.as_str() == &topic {
-                    if value.topic_type() == topic_type {
-                        Some(Ok(value.get_sender()))
-                    } else {
+                    if value.topic_type() != topic_type {
                         Some(Err(Box::new(std::io::Error::from(
                             std::io::ErrorKind::AddrInUse,
                         ))))
+                    } else if options.latching && !value.latching() {
+                        // Handing out the existing publication would silently not latch
+                        Some(Err(Box::new(std::io::Error::new(
+                            std::io::ErrorKind::AddrInUse,
+                            format!("{topic} is already advertised without latching"),
+                        ))))
+                    } else {
+                        Some(Ok(value.get_handle()))
                     }
                 } else {
                     None
@@ -475,20 +763,18 @@ impl Node {
         } else {
             let channel = Publication::new(
                 &self.node_name,
-                false,
-                &topic,
                 self.host_addr,
-                queue_size,
                 &msg_definition,
                 &md5sum,
                 topic_type,
+                options,
             )
             .await
             .map_err(|err| {
                 log::error!("Failed to create publishing channel: {err:?}");
                 err
             })?;
-            let handle = channel.get_sender();
+            let handle = channel.get_handle();
             self.publishers.insert(topic.clone(), channel);
             let _current_subscribers = self.client.register_publisher(&topic, topic_type).await?;
             Ok(handle)
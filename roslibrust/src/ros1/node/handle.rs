@@ -1,11 +1,24 @@
-use super::actor::{Node, NodeServerHandle};
-use crate::ros1::{publisher::Publisher, subscriber::Subscriber};
+use super::{
+    actor::{Node, NodeServerHandle},
+    NodeConfig,
+};
+use crate::ros1::{
+    names::Namespace,
+    publisher::{AdvertiseOptions, LatchedPublisher, Publisher},
+    service_client::{probe_service_type, ServiceError, ServiceProxy},
+    service_registry::{ServiceDefinition, ServiceTypeRegistry},
+    subscriber::{DynamicSubscriber, Subscriber, SubscriberOptions},
+    time::{Clock, SimClock, Timer, WallTimer},
+    SystemState, TopicDescription,
+};
+use std::time::Duration;
 
 /// Represents a handle to an underlying [Node]. NodeHandle's can be freely cloned, moved, copied, etc.
 /// This class provides the user facing API for interacting with ROS.
 #[derive(Clone)]
 pub struct NodeHandle {
     inner: NodeServerHandle,
+    namespace: Namespace,
 }
 
 impl NodeHandle {
@@ -16,16 +29,33 @@ impl NodeHandle {
     pub async fn new(
         master_uri: &str,
         name: &str,
+    ) -> Result<NodeHandle, Box<dyn std::error::Error + Send + Sync>> {
+        Self::with_config(master_uri, name, NodeConfig::default()).await
+    }
+
+    /// Creates a new node like [NodeHandle::new], with settings applying to all of its topics
+    pub async fn with_config(
+        master_uri: &str,
+        name: &str,
+        config: NodeConfig,
     ) -> Result<NodeHandle, Box<dyn std::error::Error + Send + Sync>> {
         // Follow ROS rules and determine our IP and hostname
         let (addr, hostname) = super::determine_addr().await?;
 
-        let node = Node::new(master_uri, &hostname, name, addr).await?;
-        let nh = NodeHandle { inner: node };
+        let node = Node::new(master_uri, &hostname, name, addr, config).await?;
+        let nh = NodeHandle {
+            inner: node,
+            namespace: Namespace::new(name).parent().unwrap_or_default(),
+        };
 
         Ok(nh)
     }
 
+    /// The namespace the node is in, e.g. `/robot` for a node named `/robot/driver`
+    pub fn namespace(&self) -> &Namespace {
+        &self.namespace
+    }
+
     pub fn is_ok(&self) -> bool {
         !self.inner.node_server_sender.is_closed()
     }
@@ -34,16 +64,67 @@ impl NodeHandle {
         self.inner.get_client_uri().await
     }
 
+    /// Checks whether the parameter `key` is set on the parameter server, without fetching it.
+    /// Relative keys are resolved relative to this node's namespace.
+    pub async fn has_param(
+        &self,
+        key: &str,
+    ) -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
+        self.inner.has_param(key).await
+    }
+
+    /// Lists the fully resolved names of all parameters set on the parameter server within
+    /// `namespace`, e.g. `list_params("/robot")` includes `/robot/max_speed` and
+    /// `/robot/arm/joints` but not `/robot_description`.
+    /// A relative `namespace` is treated as global, and `"/"` lists every parameter.
+    pub async fn list_params(
+        &self,
+        namespace: &str,
+    ) -> Result<Vec<String>, Box<dyn std::error::Error + Send + Sync>> {
+        let namespace = Namespace::new(namespace);
+        let names = self.inner.get_param_names().await?;
+        Ok(names
+            .into_iter()
+            .filter(|name| Namespace::new(name).is_child_of(&namespace))
+            .collect())
+    }
+
     pub async fn advertise<T: roslibrust_codegen::RosMessageType>(
         &self,
         topic_name: &str,
         queue_size: usize,
     ) -> Result<Publisher<T>, Box<dyn std::error::Error + Send + Sync>> {
-        let sender = self
-            .inner
-            .register_publisher::<T>(topic_name, queue_size)
-            .await?;
-        Ok(Publisher::new(topic_name, sender))
+        self.advertise_with_options(AdvertiseOptions::new(topic_name, queue_size))
+            .await
+    }
+
+    /// Advertises a topic like [NodeHandle::advertise], with additional control over how messages
+    /// are sent to subscribers.
+    ///
+    /// If this node already publishes the topic, the options of the existing publication are kept.
+    pub async fn advertise_with_options<T: roslibrust_codegen::RosMessageType>(
+        &self,
+        options: AdvertiseOptions,
+    ) -> Result<Publisher<T>, Box<dyn std::error::Error + Send + Sync>> {
+        let topic_name = options.topic.clone();
+        let handle = self.inner.register_publisher::<T>(options).await?;
+        Ok(Publisher::new(&topic_name, handle))
+    }
+
+    /// Advertises a latched topic, where the last message published is sent to each subscriber as
+    /// soon as it connects, like the current map or a robot's description.
+    ///
+    /// If this node already publishes the topic, the existing publication is reused when it was
+    /// also advertised as latched, otherwise an error is returned rather than a publisher which
+    /// wouldn't latch.
+    pub async fn advertise_latched<T: roslibrust_codegen::RosMessageType>(
+        &self,
+        topic_name: &str,
+        queue_size: usize,
+    ) -> Result<LatchedPublisher<T>, Box<dyn std::error::Error + Send + Sync>> {
+        let options = AdvertiseOptions::new(topic_name, queue_size).latching(true);
+        let handle = self.inner.register_publisher::<T>(options).await?;
+        Ok(LatchedPublisher::new(Publisher::new(topic_name, handle)))
     }
 
     pub async fn subscribe<T: roslibrust_codegen::RosMessageType>(
@@ -51,10 +132,271 @@ impl NodeHandle {
         topic_name: &str,
         queue_size: usize,
     ) -> Result<Subscriber<T>, Box<dyn std::error::Error + Send + Sync>> {
-        let receiver = self
+        self.subscribe_with_options(topic_name, queue_size, SubscriberOptions::default())
+            .await
+    }
+
+    /// Subscribes to a topic like [NodeHandle::subscribe], with additional control over how
+    /// messages are received, such as reading them into a [BufferPool](crate::ros1::BufferPool).
+    ///
+    /// If this node already subscribes to the topic, the options of the existing subscription are
+    /// kept.
+    pub async fn subscribe_with_options<T: roslibrust_codegen::RosMessageType>(
+        &self,
+        topic_name: &str,
+        queue_size: usize,
+        options: SubscriberOptions,
+    ) -> Result<Subscriber<T>, Box<dyn std::error::Error + Send + Sync>> {
+        let (receiver, _, rate) = self
+            .inner
+            .register_subscriber::<T>(topic_name, queue_size, options)
+            .await?;
+        Ok(Subscriber::new(receiver, rate))
+    }
+
+    /// Subscribes to a topic without knowing its type at compile time, decoding messages as
+    /// [DynamicMessage](crate::ros1::DynamicMessage)s using the message definition sent by each
+    /// publisher.
+    pub async fn subscribe_dynamic(
+        &self,
+        topic_name: &str,
+        queue_size: usize,
+    ) -> Result<DynamicSubscriber, Box<dyn std::error::Error + Send + Sync>> {
+        let (receiver, publisher_header, rate) = self
             .inner
-            .register_subscriber::<T>(topic_name, queue_size)
+            .register_dynamic_subscriber(topic_name, queue_size)
             .await?;
-        Ok(Subscriber::new(receiver))
+        Ok(DynamicSubscriber::new(receiver, publisher_header, rate))
+    }
+
+    /// Fetches every publisher, subscriber, and service provider currently registered with the
+    /// master, see [TopicMonitor](crate::ros1::graph::TopicMonitor) for following a single topic.
+    pub async fn get_system_state(
+        &self,
+    ) -> Result<SystemState, Box<dyn std::error::Error + Send + Sync>> {
+        self.inner.get_system_state().await
+    }
+
+    /// Lists the topics which currently have at least one publisher, with the type each is
+    /// published with. `subgraph` restricts the list to topics within a namespace, resolved by
+    /// the master relative to this node's namespace, and `""` lists every published topic.
+    pub async fn get_published_topics(
+        &self,
+        subgraph: &str,
+    ) -> Result<Vec<TopicDescription>, Box<dyn std::error::Error + Send + Sync>> {
+        self.inner.get_published_topics(subgraph).await
+    }
+
+    /// Lists the name and type of every topic known to the master, including topics which are
+    /// only subscribed to, unlike [NodeHandle::get_published_topics]
+    pub async fn get_topic_types(
+        &self,
+    ) -> Result<Vec<(String, String)>, Box<dyn std::error::Error + Send + Sync>> {
+        self.inner.get_topic_types().await
+    }
+
+    /// Subscribes to `/clock`, tracking the simulated ROS time published there.
+    ///
+    /// Only useful when `/use_sim_time` is set, otherwise nothing publishes `/clock`.
+    pub async fn sim_clock(&self) -> Result<SimClock, Box<dyn std::error::Error + Send + Sync>> {
+        let subscriber = self.subscribe::<Clock>("/clock", 1).await?;
+        Ok(SimClock::follow(subscriber))
+    }
+
+    /// Creates a [Timer] calling `cb` every `period` of ROS time.
+    ///
+    /// When `/use_sim_time` is set the timer follows [NodeHandle::sim_clock], firing at whatever
+    /// real-world rate the simulation runs at and not at all while it is paused or before the
+    /// first time is published. Otherwise it fires every `period` of wall-clock time like a
+    /// [WallTimer].
+    pub async fn create_timer_with_sim_time_support(
+        &self,
+        period: Duration,
+        cb: impl Fn() + Send + 'static,
+    ) -> Result<Timer, Box<dyn std::error::Error + Send + Sync>> {
+        if self.inner.use_sim_time().await? {
+            Ok(Timer::with_sim_clock(period, self.sim_clock().await?, cb))
+        } else {
+            Ok(WallTimer::new(period, cb).into())
+        }
+    }
+
+    /// Creates a [ServiceProxy] for calling `service_name`, which looks up the node hosting the
+    /// service with the master, reusing the uri found for calls made within `cache_ttl`.
+    /// A `cache_ttl` of zero looks the service up on every call.
+    ///
+    /// The service doesn't need to exist yet, see [ServiceProxy::wait_until_available].
+    pub async fn service_proxy<T: roslibrust_codegen::RosServiceType>(
+        &self,
+        service_name: &str,
+        cache_ttl: Duration,
+    ) -> Result<ServiceProxy<T>, Box<dyn std::error::Error + Send + Sync>> {
+        let caller_id = self.inner.get_node_name().await?;
+        Ok(ServiceProxy::new(
+            self.inner.clone(),
+            caller_id,
+            service_name,
+            cache_ttl,
+        ))
+    }
+
+    /// Finds the definition of the type of `service_name` in `registry`, for calling a service
+    /// whose type is only known at runtime.
+    ///
+    /// The master only knows which node hosts the service, so the node is asked for the type
+    /// directly, as `rosservice type` does.
+    pub async fn lookup_service_definition<'a>(
+        &self,
+        service_name: &str,
+        registry: &'a ServiceTypeRegistry,
+    ) -> Result<&'a ServiceDefinition, Box<dyn std::error::Error + Send + Sync>> {
+        let uri =
+            self.inner
+                .lookup_service(service_name)
+                .await
+                .map_err(|e| ServiceError::Lookup {
+                    service: service_name.to_owned(),
+                    reason: e.to_string(),
+                })?;
+        let caller_id = self.inner.get_node_name().await?;
+        let service_type = probe_service_type(&caller_id, service_name, &uri).await?;
+        registry.lookup(&service_type).ok_or_else(|| {
+            ServiceError::UnknownType {
+                service: service_name.to_owned(),
+                service_type,
+            }
+            .into()
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::NodeHandle;
+    use crate::ros1::{AdvertiseOptions, TopicDescription};
+    use hyper::{Body, Response};
+    use serde_xmlrpc::Value;
+    use std::{
+        convert::Infallible,
+        net::{Ipv4Addr, SocketAddr},
+    };
+
+    #[derive(::serde::Deserialize, ::serde::Serialize, Debug, Clone, PartialEq)]
+    struct TestMsg {
+        data: String,
+    }
+
+    impl roslibrust_codegen::RosMessageType for TestMsg {
+        const ROS_TYPE_NAME: &'static str = "std_msgs/String";
+        const MD5SUM: &'static str = "992ce8a1687cec8c8bd883ec73ca41d1";
+        const DEFINITION: &'static str = "string data";
+    }
+
+    /// A rosmaster answering getPublishedTopics and getTopicTypes with canned topics, only those
+    /// in the requested subgraph for getPublishedTopics, and accepting publishers without any
+    /// subscribers
+    fn mock_master() -> (String, abort_on_drop::ChildTask<()>) {
+        let make_svc = hyper::service::make_service_fn(|_| async {
+            Ok::<_, Infallible>(hyper::service::service_fn(|req| async {
+                let body = hyper::body::to_bytes(req).await.unwrap();
+                let (method, args) =
+                    serde_xmlrpc::request_from_str(std::str::from_utf8(&body).unwrap()).unwrap();
+                let topics = |topics: &[(&str, &str)]| {
+                    Value::Array(
+                        topics
+                            .iter()
+                            .map(|(name, topic_type)| {
+                                Value::Array(vec![(*name).into(), (*topic_type).into()])
+                            })
+                            .collect(),
+                    )
+                };
+                let value = match method.as_str() {
+                    "getUri" => "http://localhost:11311".into(),
+                    "getPublishedTopics" => {
+                        let subgraph = args[1].as_str().unwrap().to_owned();
+                        let published = [
+                            ("/robot/odom", "nav_msgs/Odometry"),
+                            ("/rosout", "rosgraph_msgs/Log"),
+                        ];
+                        topics(
+                            &published
+                                .into_iter()
+                                .filter(|(name, _)| name.starts_with(&subgraph))
+                                .collect::<Vec<_>>(),
+                        )
+                    }
+                    "getTopicTypes" => topics(&[
+                        ("/robot/odom", "nav_msgs/Odometry"),
+                        ("/robot/cmd_vel", "geometry_msgs/Twist"),
+                        ("/rosout", "rosgraph_msgs/Log"),
+                    ]),
+                    "registerPublisher" => Value::Array(vec![]),
+                    "unregisterPublisher" => 1.into(),
+                    _ => panic!("Unexpected call to mock master: {method}"),
+                };
+                let body = serde_xmlrpc::response_to_string(
+                    vec![Value::Array(vec![1.into(), "".into(), value])].into_iter(),
+                )
+                .unwrap();
+                Ok::<_, Infallible>(Response::new(Body::from(body)))
+            }))
+        });
+        let server = hyper::server::Server::bind(&SocketAddr::from((Ipv4Addr::LOCALHOST, 0)))
+            .serve(make_svc);
+        let uri = format!("http://{}", server.local_addr());
+        (
+            uri,
+            tokio::spawn(async move { server.await.unwrap() }).into(),
+        )
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn topics_are_listed_without_subscribing() {
+        let (uri, _master) = mock_master();
+        let nh = NodeHandle::new(&uri, "/topic_lister").await.unwrap();
+
+        let topic = |name: &str, type_name: &str| TopicDescription {
+            name: name.to_owned(),
+            type_name: type_name.to_owned(),
+        };
+        assert_eq!(
+            nh.get_published_topics("").await.unwrap(),
+            [
+                topic("/robot/odom", "nav_msgs/Odometry"),
+                topic("/rosout", "rosgraph_msgs/Log")
+            ]
+        );
+        assert_eq!(
+            nh.get_published_topics("/robot").await.unwrap(),
+            [topic("/robot/odom", "nav_msgs/Odometry")]
+        );
+
+        let types = nh.get_topic_types().await.unwrap();
+        assert_eq!(types.len(), 3);
+        assert!(types.contains(&(
+            "/robot/cmd_vel".to_owned(),
+            "geometry_msgs/Twist".to_owned()
+        )));
+    }
+    #[test_log::test(tokio::test)]
+    async fn latched_advertise_refuses_unlatched_publication() {
+        let (uri, _master) = mock_master();
+        let nh = NodeHandle::new(&uri, "/latcher").await.unwrap();
+
+        let _plain = nh.advertise::<TestMsg>("/plain", 1).await.unwrap();
+        assert!(nh.advertise_latched::<TestMsg>("/plain", 1).await.is_err());
+
+        let _latched = nh
+            .advertise_latched::<TestMsg>("/latched", 1)
+            .await
+            .unwrap();
+        nh.advertise_latched::<TestMsg>("/latched", 1)
+            .await
+            .unwrap();
+        // Only asking for latching is refused, advertising without it can share the publication
+        nh.advertise_with_options::<TestMsg>(AdvertiseOptions::new("/latched", 1))
+            .await
+            .unwrap();
     }
 }
@@ -1,10 +1,11 @@
 use super::NodeServerHandle;
+use crate::ros1::{ProtocolOffer, SelectedProtocol};
 use abort_on_drop::ChildTask;
 use hyper::{Body, Response, StatusCode};
 use log::*;
 use std::{
     convert::Infallible,
-    net::{Ipv4Addr, SocketAddr},
+    net::{IpAddr, SocketAddr},
 };
 
 #[allow(unused)]
@@ -45,7 +46,7 @@ impl XmlRpcServerHandle {
 
 impl XmlRpcServer {
     pub fn new(
-        host_addr: Ipv4Addr,
+        host_addr: IpAddr,
         node_server: NodeServerHandle,
     ) -> Result<XmlRpcServerHandle, Box<dyn std::error::Error + Send + Sync>> {
         let make_svc = hyper::service::make_service_fn(move |connection| {
@@ -186,7 +187,7 @@ impl XmlRpcServer {
             }
             "requestTopic" => {
                 debug!("requestTopic called by {args:?}");
-                let (caller_id, topic, protocols): (String, String, Vec<Vec<String>>) =
+                let (caller_id, topic, protocols): (String, String, Vec<ProtocolOffer>) =
                     serde_xmlrpc::from_values(args).map_err(|e| {
                         Self::make_error_response(
                             e,
@@ -194,7 +195,6 @@ impl XmlRpcServer {
                             StatusCode::BAD_REQUEST,
                         )
                     })?;
-                let protocols = protocols.iter().flatten().cloned().collect::<Vec<_>>();
                 debug!("Request for topic {topic} from {caller_id} via protocols {protocols:?}");
                 let params = node_server
                     .request_topic(&caller_id, &topic, &protocols)
@@ -207,11 +207,16 @@ impl XmlRpcServer {
                         )
                     })?;
 
+                let description = match &params {
+                    SelectedProtocol::Tcpros { host, port }
+                    | SelectedProtocol::Udpros { host, port, .. } => {
+                        format!("ready on {host}:{port}")
+                    }
+                };
                 let response = Self::make_success_response(
                     RosXmlStatusCode::Success,
-                    format!("ready on {}:{}", params.hostname.clone(), params.port).as_str(),
-                    serde_xmlrpc::to_value((params.protocol, params.hostname, params.port))
-                        .unwrap(),
+                    &description,
+                    params.to_xmlrpc(),
                 );
 
                 log::debug!("Sending response for requested topic {response:?}");
@@ -0,0 +1,293 @@
+use crate::ros1::NodeHandle;
+use async_trait::async_trait;
+use std::collections::HashMap;
+
+/// A unit of functionality which shares a process, and therefore a [NodeHandle], with other
+/// nodelets in a [NodeletContainer], mirroring ROS's nodelets.
+///
+/// Implementations are typically annotated with [nodelets](crate::ros1::nodelets), which handles
+/// the async trait plumbing and allows the type to be registered with a container.
+#[async_trait]
+pub trait Nodelets: Send + 'static {
+    /// Names of other nodelets in the same container which must be initialized before this one
+    fn dependencies(&self) -> Vec<String> {
+        vec![]
+    }
+
+    /// Called once when the container is initialized, after all of this nodelet's dependencies.
+    /// Nodelets should create their publishers, subscribers, and any tasks here.
+    async fn on_init(
+        &mut self,
+        handle: NodeHandle,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>>;
+
+    /// Called when the container is shutdown, in the reverse order of initialization
+    async fn on_shutdown(&mut self) {}
+}
+
+/// A [Nodelets] implementation which can be created by name from a [NodeletContainer]'s type map.
+/// This is implemented by the [nodelets](crate::ros1::nodelets) attribute, and should not need to
+/// be implemented manually.
+pub trait NodeletType: Nodelets {
+    /// Name the type is registered under, defaults to the name of the type itself
+    const NODELET_TYPE: &'static str;
+    fn create() -> Box<dyn Nodelets>;
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum NodeletError {
+    #[error("No nodelet type named {0} has been registered with the container")]
+    UnknownType(String),
+    #[error("A nodelet named {0} has already been added to the container")]
+    DuplicateName(String),
+    #[error("Nodelet {nodelet} depends on {dependency} which is not in the container")]
+    MissingDependency { nodelet: String, dependency: String },
+    #[error("Nodelets have circular dependencies: {0:?}")]
+    DependencyCycle(Vec<String>),
+    #[error("Nodelet {nodelet} failed to initialize: {source}")]
+    InitFailed {
+        nodelet: String,
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+}
+
+/// Holds a set of nodelets running within the same process and manages their lifecycle.
+///
+/// ```ignore
+/// let mut container = NodeletContainer::new();
+/// container.register::<Talker>().register::<Listener>();
+/// container.load("talker", "Talker")?;
+/// container.load("listener", "Listener")?;
+/// container.init(&nh).await?;
+/// ```
+#[derive(Default)]
+pub struct NodeletContainer {
+    types: HashMap<&'static str, fn() -> Box<dyn Nodelets>>,
+    nodelets: Vec<(String, Box<dyn Nodelets>)>,
+    // Indices into nodelets of those which have been initialized, in the order they were initialized
+    initialized: Vec<usize>,
+}
+
+impl NodeletContainer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a nodelet type to the container's type map so it can be created with [NodeletContainer::load]
+    pub fn register<T: NodeletType>(&mut self) -> &mut Self {
+        self.types.insert(T::NODELET_TYPE, T::create);
+        self
+    }
+
+    /// Creates an instance of a registered nodelet type and adds it to the container under `name`
+    pub fn load(&mut self, name: &str, nodelet_type: &str) -> Result<(), NodeletError> {
+        let create = self
+            .types
+            .get(nodelet_type)
+            .ok_or_else(|| NodeletError::UnknownType(nodelet_type.to_owned()))?;
+        let nodelet = create();
+        self.add(name, nodelet)
+    }
+
+    /// Adds an already constructed nodelet to the container under `name`
+    pub fn add(&mut self, name: &str, nodelet: Box<dyn Nodelets>) -> Result<(), NodeletError> {
+        if self.nodelets.iter().any(|(existing, _)| existing == name) {
+            return Err(NodeletError::DuplicateName(name.to_owned()));
+        }
+        self.nodelets.push((name.to_owned(), nodelet));
+        Ok(())
+    }
+
+    /// Names of the nodelets in the container, in the order they were added
+    pub fn names(&self) -> Vec<&str> {
+        self.nodelets
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect()
+    }
+
+    /// Calls [Nodelets::on_init] on every nodelet not yet initialized, such that each nodelet is
+    /// initialized after all of its dependencies. Each nodelet receives a clone of `handle`.
+    ///
+    /// Dependencies are validated before any nodelet is initialized. If a nodelet fails to
+    /// initialize, those initialized before it remain so and will be shutdown by
+    /// [NodeletContainer::shutdown].
+    pub async fn init(&mut self, handle: &NodeHandle) -> Result<(), NodeletError> {
+        for idx in self.initialization_order()? {
+            let (name, nodelet) = &mut self.nodelets[idx];
+            log::debug!("Initializing nodelet {name}");
+            nodelet
+                .on_init(handle.clone())
+                .await
+                .map_err(|source| NodeletError::InitFailed {
+                    nodelet: name.clone(),
+                    source,
+                })?;
+            self.initialized.push(idx);
+        }
+        Ok(())
+    }
+
+    /// Calls [Nodelets::on_shutdown] on every initialized nodelet, in reverse initialization order
+    pub async fn shutdown(&mut self) {
+        while let Some(idx) = self.initialized.pop() {
+            let (name, nodelet) = &mut self.nodelets[idx];
+            log::debug!("Shutting down nodelet {name}");
+            nodelet.on_shutdown().await;
+        }
+    }
+
+    /// Orders the uninitialized nodelets so that each comes after its dependencies, keeping the
+    /// order they were added in wherever dependencies allow
+    fn initialization_order(&self) -> Result<Vec<usize>, NodeletError> {
+        let dependencies = self
+            .nodelets
+            .iter()
+            .map(|(name, nodelet)| {
+                nodelet
+                    .dependencies()
+                    .into_iter()
+                    .map(|dependency| {
+                        self.nodelets
+                            .iter()
+                            .position(|(other, _)| *other == dependency)
+                            .ok_or_else(|| NodeletError::MissingDependency {
+                                nodelet: name.clone(),
+                                dependency,
+                            })
+                    })
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut done = self.initialized.clone();
+        let mut order = vec![];
+        let mut remaining = (0..self.nodelets.len())
+            .filter(|idx| !done.contains(idx))
+            .collect::<Vec<_>>();
+        while !remaining.is_empty() {
+            let ready = remaining
+                .iter()
+                .position(|idx| dependencies[*idx].iter().all(|dep| done.contains(dep)))
+                .ok_or_else(|| {
+                    NodeletError::DependencyCycle(
+                        remaining
+                            .iter()
+                            .map(|idx| self.nodelets[*idx].0.clone())
+                            .collect(),
+                    )
+                })?;
+            let idx = remaining.remove(ready);
+            done.push(idx);
+            order.push(idx);
+        }
+        Ok(order)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{NodeletContainer, NodeletError, NodeletType, Nodelets};
+    use crate::ros1::{nodelets, NodeHandle};
+
+    #[derive(Default)]
+    struct Camera;
+
+    #[nodelets]
+    impl Nodelets for Camera {
+        async fn on_init(
+            &mut self,
+            _handle: NodeHandle,
+        ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+            Ok(())
+        }
+    }
+
+    #[derive(Default)]
+    struct Detector;
+
+    #[nodelets("vision/Detector")]
+    impl Nodelets for Detector {
+        fn dependencies(&self) -> Vec<String> {
+            vec!["camera".to_owned()]
+        }
+
+        async fn on_init(
+            &mut self,
+            _handle: NodeHandle,
+        ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+            Ok(())
+        }
+    }
+
+    fn order(container: &NodeletContainer) -> Result<Vec<&str>, NodeletError> {
+        let names = container.names();
+        Ok(container
+            .initialization_order()?
+            .into_iter()
+            .map(|idx| names[idx])
+            .collect())
+    }
+
+    #[test_log::test]
+    fn nodelets_attribute_registers_types() {
+        assert_eq!(Camera::NODELET_TYPE, "Camera");
+        assert_eq!(Detector::NODELET_TYPE, "vision/Detector");
+
+        let mut container = NodeletContainer::new();
+        container.register::<Camera>().register::<Detector>();
+        container.load("detector", "vision/Detector").unwrap();
+        container.load("camera", "Camera").unwrap();
+        assert!(matches!(
+            container.load("lidar", "Lidar"),
+            Err(NodeletError::UnknownType(_))
+        ));
+        assert!(matches!(
+            container.load("camera", "Camera"),
+            Err(NodeletError::DuplicateName(_))
+        ));
+
+        assert_eq!(order(&container).unwrap(), vec!["camera", "detector"]);
+    }
+
+    #[test_log::test]
+    fn missing_dependencies_are_reported() {
+        let mut container = NodeletContainer::new();
+        container.register::<Detector>();
+        container.load("detector", "vision/Detector").unwrap();
+        assert!(matches!(
+            order(&container),
+            Err(NodeletError::MissingDependency { dependency, .. }) if dependency == "camera"
+        ));
+    }
+
+    #[test_log::test]
+    fn dependency_cycles_are_reported() {
+        // A camera that waits on the detector, which itself waits on the camera
+        #[derive(Default)]
+        struct Cyclic;
+
+        #[nodelets]
+        impl Nodelets for Cyclic {
+            fn dependencies(&self) -> Vec<String> {
+                vec!["detector".to_owned()]
+            }
+
+            async fn on_init(
+                &mut self,
+                _handle: NodeHandle,
+            ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+                Ok(())
+            }
+        }
+
+        let mut container = NodeletContainer::new();
+        container.register::<Detector>();
+        container.load("detector", "vision/Detector").unwrap();
+        container.add("camera", Box::new(Cyclic)).unwrap();
+        assert!(matches!(
+            order(&container),
+            Err(NodeletError::DependencyCycle(names)) if names.len() == 2
+        ));
+    }
+}
@@ -0,0 +1,302 @@
+//! The transport negotiation of `requestTopic`, where a subscriber lists the protocols it can
+//! receive a topic over in order of preference, and the publisher answers with the one it chose
+//! along with the parameters needed to connect with it.
+
+use super::TCPROS_PROTOCOL;
+use serde::de::{Deserialize, Deserializer, Error, IgnoredAny, SeqAccess, Visitor};
+use serde_xmlrpc::Value;
+
+/// Name of the UDPROS transport in `requestTopic` negotiation.
+///
+/// roslibrust can't send or receive UDPROS yet, but understands it being offered and selected.
+pub const UDPROS_PROTOCOL: &str = "UDPROS";
+
+/// A protocol a subscriber offers in its `requestTopic` call, see
+/// [SubscriberOptions::protocols](crate::ros1::SubscriberOptions::protocols)
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ProtocolOffer {
+    /// Sent as `["TCPROS"]`, the subscriber connects to the publisher
+    Tcpros,
+    /// Sent as `["UDPROS", header, host, port, max_datagram_size]`, the publisher sends datagrams
+    /// to the subscriber
+    Udpros {
+        /// The subscriber's connection header, as it would be sent over TCPROS
+        header: Vec<u8>,
+        /// Address the subscriber receives datagrams on
+        host: String,
+        port: u16,
+        /// Largest datagram the subscriber accepts, in bytes
+        max_datagram_size: u32,
+    },
+    /// A protocol roslibrust doesn't know, or whose parameters couldn't be understood, named as it
+    /// was offered
+    Other(String),
+}
+
+impl ProtocolOffer {
+    pub(crate) fn to_xmlrpc(&self) -> Value {
+        match self {
+            ProtocolOffer::Tcpros => Value::Array(vec![TCPROS_PROTOCOL.into()]),
+            ProtocolOffer::Udpros {
+                header,
+                host,
+                port,
+                max_datagram_size,
+            } => Value::Array(vec![
+                UDPROS_PROTOCOL.into(),
+                Value::Base64(header.clone()),
+                host.as_str().into(),
+                Value::Int(*port as i32),
+                Value::Int(*max_datagram_size as i32),
+            ]),
+            ProtocolOffer::Other(name) => Value::Array(vec![name.as_str().into()]),
+        }
+    }
+}
+
+/// The protocol a publisher selected in answer to `requestTopic`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SelectedProtocol {
+    /// Sent as `["TCPROS", host, port]`, the address the subscriber connects to
+    Tcpros { host: String, port: u16 },
+    /// Sent as `["UDPROS", host, port, connection_id, max_datagram_size, header]`
+    Udpros {
+        /// Address the publisher sends datagrams from
+        host: String,
+        port: u16,
+        /// Identifies the connection in the header of each datagram
+        connection_id: i32,
+        /// Largest datagram the publisher will send, in bytes
+        max_datagram_size: u32,
+        /// The publisher's connection header
+        header: Vec<u8>,
+    },
+}
+
+impl SelectedProtocol {
+    pub(crate) fn to_xmlrpc(&self) -> Value {
+        match self {
+            SelectedProtocol::Tcpros { host, port } => Value::Array(vec![
+                TCPROS_PROTOCOL.into(),
+                host.as_str().into(),
+                Value::Int(*port as i32),
+            ]),
+            SelectedProtocol::Udpros {
+                host,
+                port,
+                connection_id,
+                max_datagram_size,
+                header,
+            } => Value::Array(vec![
+                UDPROS_PROTOCOL.into(),
+                host.as_str().into(),
+                Value::Int(*port as i32),
+                Value::Int(*connection_id),
+                Value::Int(*max_datagram_size as i32),
+                Value::Base64(header.clone()),
+            ]),
+        }
+    }
+}
+
+/// Selects the first of `offers`, which are in the subscriber's order of preference, that a
+/// publisher can serve. Only TCPROS is supported, so any other protocol preferred over it is
+/// declined.
+pub(crate) fn select_protocol(offers: &[ProtocolOffer]) -> Option<&ProtocolOffer> {
+    offers
+        .iter()
+        .find(|offer| matches!(offer, ProtocolOffer::Tcpros))
+}
+
+impl<'de> Deserialize<'de> for ProtocolOffer {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct OfferVisitor;
+
+        impl<'de> Visitor<'de> for OfferVisitor {
+            type Value = ProtocolOffer;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                f.write_str("a list starting with a protocol name")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<ProtocolOffer, A::Error> {
+                let name: String = next(&mut seq)?;
+                let offer = match name.as_str() {
+                    TCPROS_PROTOCOL => ProtocolOffer::Tcpros,
+                    UDPROS_PROTOCOL => {
+                        let parsed = (|| -> Result<_, A::Error> {
+                            Ok(ProtocolOffer::Udpros {
+                                header: next::<_, Bytes>(&mut seq)?.0,
+                                host: next(&mut seq)?,
+                                port: port(next(&mut seq)?)?,
+                                max_datagram_size: size(next(&mut seq)?)?,
+                            })
+                        })();
+                        parsed.unwrap_or(ProtocolOffer::Other(name))
+                    }
+                    _ => ProtocolOffer::Other(name),
+                };
+                // Anything left over is for a protocol or version we don't understand
+                while seq.next_element::<IgnoredAny>()?.is_some() {}
+                Ok(offer)
+            }
+        }
+
+        deserializer.deserialize_seq(OfferVisitor)
+    }
+}
+
+impl<'de> Deserialize<'de> for SelectedProtocol {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct SelectionVisitor;
+
+        impl<'de> Visitor<'de> for SelectionVisitor {
+            type Value = SelectedProtocol;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                f.write_str("a TCPROS or UDPROS protocol selection")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(
+                self,
+                mut seq: A,
+            ) -> Result<SelectedProtocol, A::Error> {
+                let name: String = next(&mut seq)?;
+                let selection = match name.as_str() {
+                    TCPROS_PROTOCOL => SelectedProtocol::Tcpros {
+                        host: next(&mut seq)?,
+                        port: port(next(&mut seq)?)?,
+                    },
+                    UDPROS_PROTOCOL => SelectedProtocol::Udpros {
+                        host: next(&mut seq)?,
+                        port: port(next(&mut seq)?)?,
+                        connection_id: next(&mut seq)?,
+                        max_datagram_size: size(next(&mut seq)?)?,
+                        header: next::<_, Bytes>(&mut seq)?.0,
+                    },
+                    _ => {
+                        return Err(A::Error::custom(format!(
+                            "publisher selected unknown protocol {name}"
+                        )))
+                    }
+                };
+                while seq.next_element::<IgnoredAny>()?.is_some() {}
+                Ok(selection)
+            }
+        }
+
+        deserializer.deserialize_seq(SelectionVisitor)
+    }
+}
+
+fn next<'de, A: SeqAccess<'de>, T: Deserialize<'de>>(seq: &mut A) -> Result<T, A::Error> {
+    seq.next_element()?
+        .ok_or_else(|| A::Error::custom("protocol is missing parameters"))
+}
+
+fn port<E: Error>(port: i32) -> Result<u16, E> {
+    u16::try_from(port).map_err(|_| E::custom(format!("invalid port {port}")))
+}
+
+fn size<E: Error>(size: i32) -> Result<u32, E> {
+    u32::try_from(size).map_err(|_| E::custom(format!("invalid datagram size {size}")))
+}
+
+/// Base64 values, which xmlrpc deserializes as bytes rather than a sequence
+struct Bytes(Vec<u8>);
+
+impl<'de> Deserialize<'de> for Bytes {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct BytesVisitor;
+
+        impl<'de> Visitor<'de> for BytesVisitor {
+            type Value = Bytes;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                f.write_str("base64 data")
+            }
+
+            fn visit_bytes<E: Error>(self, v: &[u8]) -> Result<Bytes, E> {
+                Ok(Bytes(v.to_vec()))
+            }
+
+            fn visit_byte_buf<E: Error>(self, v: Vec<u8>) -> Result<Bytes, E> {
+                Ok(Bytes(v))
+            }
+        }
+
+        deserializer.deserialize_byte_buf(BytesVisitor)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{select_protocol, ProtocolOffer, SelectedProtocol};
+    use serde_xmlrpc::Value;
+
+    fn udpros_offer() -> ProtocolOffer {
+        ProtocolOffer::Udpros {
+            header: b"\x0a\x00\x00\x00topic=/foo".to_vec(),
+            host: "192.168.1.2".to_owned(),
+            port: 41000,
+            max_datagram_size: 1500,
+        }
+    }
+
+    #[test_log::test]
+    fn offers_round_trip_through_xmlrpc() {
+        let offers = vec![
+            udpros_offer(),
+            ProtocolOffer::Tcpros,
+            ProtocolOffer::Other("SHMROS".to_owned()),
+        ];
+        let value = Value::Array(offers.iter().map(ProtocolOffer::to_xmlrpc).collect());
+        let xml = serde_xmlrpc::value_to_string(value).unwrap();
+        let value = serde_xmlrpc::value_from_str(&xml).unwrap();
+        let parsed: Vec<ProtocolOffer> = serde_xmlrpc::from_value(value).unwrap();
+        assert_eq!(parsed, offers);
+
+        // A UDPROS offer we can't make sense of is still recognized as something to decline
+        let malformed = Value::Array(vec![Value::Array(vec!["UDPROS".into(), 5.into()])]);
+        let parsed: Vec<ProtocolOffer> = serde_xmlrpc::from_value(malformed).unwrap();
+        assert_eq!(parsed, vec![ProtocolOffer::Other("UDPROS".to_owned())]);
+    }
+
+    #[test_log::test]
+    fn selections_round_trip_through_xmlrpc() {
+        for selection in [
+            SelectedProtocol::Tcpros {
+                host: "fe80::1".to_owned(),
+                port: 45100,
+            },
+            SelectedProtocol::Udpros {
+                host: "192.168.1.3".to_owned(),
+                port: 41001,
+                connection_id: 7,
+                max_datagram_size: 1500,
+                header: b"\x0a\x00\x00\x00topic=/foo".to_vec(),
+            },
+        ] {
+            let response = serde_xmlrpc::response_to_string(
+                vec![Value::Array(vec![
+                    1.into(),
+                    "ready".into(),
+                    selection.to_xmlrpc(),
+                ])]
+                .into_iter(),
+            )
+            .unwrap();
+            let (_code, _description, parsed): (i32, String, SelectedProtocol) =
+                serde_xmlrpc::response_from_str(&response).unwrap();
+            assert_eq!(parsed, selection);
+        }
+    }
+
+    #[test_log::test]
+    fn publishers_decline_protocols_other_than_tcpros() {
+        let offers = [udpros_offer(), ProtocolOffer::Tcpros];
+        assert_eq!(select_protocol(&offers), Some(&ProtocolOffer::Tcpros));
+        assert_eq!(select_protocol(&[udpros_offer()]), None);
+        assert_eq!(select_protocol(&[]), None);
+    }
+}
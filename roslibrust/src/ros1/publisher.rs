@@ -1,122 +1,772 @@
-use crate::{ros1::tcpros::ConnectionHeader, RosLibRustError};
+use crate::{
+    ros1::tcpros::{self, Compression, ConnectionHeader},
+    RosLibRustError,
+};
 use abort_on_drop::ChildTask;
 use roslibrust_codegen::RosMessageType;
 use std::{
+    collections::VecDeque,
     marker::PhantomData,
-    net::{Ipv4Addr, SocketAddr},
-    sync::Arc,
+    net::{IpAddr, SocketAddr},
+    sync::{Arc, Mutex},
+    time::Duration,
 };
 use tokio::{
     io::{AsyncReadExt, AsyncWriteExt},
-    sync::{mpsc, RwLock},
+    net::tcp::OwnedWriteHalf,
+    sync::{broadcast, mpsc, oneshot, watch, Notify, RwLock},
 };
 
+/// Options for advertising a topic, see
+/// [NodeHandle::advertise_with_options](crate::ros1::NodeHandle::advertise_with_options)
+///
+/// ```
+/// use roslibrust::ros1::{AdvertiseOptions, Compression};
+/// let options = AdvertiseOptions::new("/scan", 10)
+///     .tcp_nodelay(true)
+///     .compression(Compression::Zstd);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct AdvertiseOptions {
+    /// Name of the topic to publish on
+    pub topic: String,
+    /// Number of published messages waiting to be handed to the subscribers' queues
+    pub queue_size: usize,
+    /// Sends the last message published to each subscriber as soon as it connects, see
+    /// [NodeHandle::advertise_latched](crate::ros1::NodeHandle::advertise_latched)
+    pub latching: bool,
+    /// Disables Nagle's algorithm for every subscriber, rather than only those which ask for it
+    /// in their connection header
+    pub tcp_nodelay: bool,
+    /// Compression to apply for subscribers which support it, other subscribers receive
+    /// uncompressed messages
+    pub compression: Compression,
+    /// Whether subscribers expecting a different message definition are refused
+    pub md5check: Md5Check,
+    /// Maximum number of messages waiting to be written to each subscriber, defaults to
+    /// [AdvertiseOptions::queue_size].
+    ///
+    /// Like roscpp, every subscriber has its own queue and is written to independently, so a slow
+    /// or stuck subscriber only loses its own messages rather than stalling the publisher.
+    pub subscriber_queue_size: Option<usize>,
+    /// What to do with a message for a subscriber whose queue is full
+    pub queue_full_policy: QueueFullPolicy,
+}
+
+/// Former name of [AdvertiseOptions], which now also holds the topic name and queue size
+#[deprecated(since = "0.8.0", note = "renamed to AdvertiseOptions")]
+pub type PublisherOptions = AdvertiseOptions;
+
+impl AdvertiseOptions {
+    pub fn new(topic: impl Into<String>, queue_size: usize) -> Self {
+        Self {
+            topic: topic.into(),
+            queue_size,
+            ..Default::default()
+        }
+    }
+
+    /// Sets [AdvertiseOptions::latching]
+    pub fn latching(mut self, latching: bool) -> Self {
+        self.latching = latching;
+        self
+    }
+
+    /// Sets [AdvertiseOptions::tcp_nodelay]
+    pub fn tcp_nodelay(mut self, tcp_nodelay: bool) -> Self {
+        self.tcp_nodelay = tcp_nodelay;
+        self
+    }
+
+    /// Sets [AdvertiseOptions::compression]
+    pub fn compression(mut self, compression: Compression) -> Self {
+        self.compression = compression;
+        self
+    }
+
+    /// Sets [AdvertiseOptions::md5check]
+    pub fn md5check(mut self, md5check: Md5Check) -> Self {
+        self.md5check = md5check;
+        self
+    }
+
+    /// Sets [AdvertiseOptions::subscriber_queue_size]
+    pub fn subscriber_queue_size(mut self, subscriber_queue_size: usize) -> Self {
+        self.subscriber_queue_size = Some(subscriber_queue_size);
+        self
+    }
+
+    /// Sets [AdvertiseOptions::queue_full_policy]
+    pub fn queue_full_policy(mut self, queue_full_policy: QueueFullPolicy) -> Self {
+        self.queue_full_policy = queue_full_policy;
+        self
+    }
+}
+
+/// Whether a publisher checks the md5sum subscribers send in their connection header against
+/// its own, see [AdvertiseOptions::md5check]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Md5Check {
+    /// Refuses subscribers whose md5sum differs, other than the `*` wildcard, as roscpp does
+    #[default]
+    Strict,
+    /// Accepts every subscriber whatever md5sum it sends, e.g. nodes built against an older but
+    /// still compatible definition of the message
+    Skip,
+}
+
+/// How a publisher handles a new message for a subscriber whose send queue is full, see
+/// [AdvertiseOptions::subscriber_queue_size]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum QueueFullPolicy {
+    /// Discard the oldest queued message to make room for the new one, matching roscpp
+    #[default]
+    DropOldest,
+    /// Discard the new message, keeping the messages already queued
+    DropNewest,
+}
+
+/// State of the send queue of one subscriber connected to a publisher, see
+/// [Publisher::subscriber_queues]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SubscriberQueueStatus {
+    /// Caller id of the subscribing node
+    pub caller_id: String,
+    /// Number of messages waiting to be written to the subscriber
+    pub queued: usize,
+    /// Maximum number of messages the queue holds, see [AdvertiseOptions::subscriber_queue_size]
+    pub capacity: usize,
+    /// Number of messages discarded because the queue was full
+    pub dropped: u64,
+    /// True from when the queue overflows until the subscriber catches up, emptying it
+    pub backlogged: bool,
+}
+
+/// Sent by [Publisher::backlog_events] when a subscriber falls behind, or catches back up
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BacklogEvent {
+    /// Caller id of the subscribing node
+    pub caller_id: String,
+    /// True when the subscriber's queue has overflowed, false once it has been emptied or the
+    /// subscriber disconnected while backlogged
+    pub backlogged: bool,
+    /// Number of messages discarded for this subscriber so far
+    pub dropped: u64,
+}
+
+/// Number of [BacklogEvent]s kept for receivers which haven't caught up
+const BACKLOG_EVENT_CAPACITY: usize = 64;
+
+/// Outcome of [Publisher::publish_and_wait].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DeliveryReport {
+    /// Number of subscribers which confirmed receipt of the message before the timeout
+    pub acknowledged: usize,
+    /// Caller ids of the subscribers which support acknowledgements, but did not confirm receipt
+    /// of the message before the timeout
+    pub timed_out: Vec<String>,
+    /// Number of subscribers the message was sent to which do not support acknowledgements
+    /// (e.g. roscpp or rospy nodes), delivery to these subscribers cannot be confirmed
+    pub unconfirmable: usize,
+}
+
+/// A serialized message queued for the publication task
+#[derive(Debug)]
+pub(crate) struct OutgoingMessage {
+    data: Vec<u8>,
+    /// If present the publication task replies with what needs to be awaited to confirm delivery
+    ack_reply: Option<oneshot::Sender<PendingDelivery>>,
+}
+
+/// Per subscriber acknowledgement state captured at the time a message was queued
+#[derive(Debug)]
+struct PendingAck {
+    subscriber: String,
+    /// Resolves to the sequence number the message was written with, or is dropped if the message
+    /// was discarded from the subscriber's queue
+    written: oneshot::Receiver<u32>,
+    acked: watch::Receiver<u32>,
+}
+
+#[derive(Debug)]
+struct PendingDelivery {
+    acks: Vec<PendingAck>,
+    unconfirmable: usize,
+}
+
+/// What a [Publisher] needs to reach its [Publication]
+#[derive(Clone)]
+pub(crate) struct PublicationHandle {
+    sender: mpsc::Sender<OutgoingMessage>,
+    subscriber_streams: Arc<RwLock<Vec<SubscriberStream>>>,
+    backlog_events: broadcast::Sender<BacklogEvent>,
+}
+
+// Subscriber streams hold tasks and sockets which aren't worth printing
+impl std::fmt::Debug for PublicationHandle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PublicationHandle")
+            .field("sender", &self.sender)
+            .finish_non_exhaustive()
+    }
+}
+
 pub struct Publisher<T> {
     topic_name: String,
-    sender: mpsc::Sender<Vec<u8>>,
+    sender: mpsc::Sender<OutgoingMessage>,
+    subscriber_streams: Arc<RwLock<Vec<SubscriberStream>>>,
+    backlog_events: broadcast::Sender<BacklogEvent>,
     phantom: PhantomData<T>,
 }
 
 impl<T: RosMessageType> Publisher<T> {
-    pub(crate) fn new(topic_name: &str, sender: mpsc::Sender<Vec<u8>>) -> Self {
+    pub(crate) fn new(topic_name: &str, handle: PublicationHandle) -> Self {
         Self {
             topic_name: topic_name.to_owned(),
-            sender,
+            sender: handle.sender,
+            subscriber_streams: handle.subscriber_streams,
+            backlog_events: handle.backlog_events,
             phantom: PhantomData,
         }
     }
 
+    /// The send queue of every subscriber currently connected, for spotting slow subscribers.
+    ///
+    /// Messages for a subscriber whose queue is full are dropped according to the
+    /// [QueueFullPolicy], so a subscriber which stays [SubscriberQueueStatus::backlogged] is not
+    /// keeping up with the rate messages are published at.
+    pub async fn subscriber_queues(&self) -> Vec<SubscriberQueueStatus> {
+        self.subscriber_streams
+            .read()
+            .await
+            .iter()
+            .map(|stream| stream.queue.status(&stream.caller_id))
+            .collect()
+    }
+
+    /// Receives an event each time a subscriber's queue overflows, and again once the subscriber
+    /// has caught up, so the application can adapt the rate it publishes at.
+    ///
+    /// Events are only kept for receivers which fall behind by a limited amount, after which the
+    /// oldest are skipped and the receiver gets [broadcast::error::RecvError::Lagged].
+    pub fn backlog_events(&self) -> broadcast::Receiver<BacklogEvent> {
+        self.backlog_events.subscribe()
+    }
+
     pub async fn publish(&self, data: &T) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-        let data = serde_rosmsg::to_vec(&data)
+        let data = serialize_message(data)
             // Gotta do some funny error mapping here as serde_rosmsg's error type is not sync
             .map_err(|e| RosLibRustError::Unexpected(anyhow::anyhow!("{e:?}")))?;
-        self.sender.send(data).await?;
+        self.sender
+            .send(OutgoingMessage {
+                data,
+                ack_reply: None,
+            })
+            .await?;
         log::debug!("Publishing data on topic {}", self.topic_name);
         Ok(())
     }
+
+    /// Publishes a message and waits up to `timeout` for every connected subscriber to confirm
+    /// it received the message.
+    ///
+    /// Confirmation relies on the `roslibrust_ack` connection header extension, which is only
+    /// negotiated with roslibrust subscribers that opt in with
+    /// [SubscriberOptions::acknowledge_delivery](crate::ros1::SubscriberOptions::acknowledge_delivery).
+    /// Other subscribers still receive the message, but are only counted in
+    /// [DeliveryReport::unconfirmable].
+    pub async fn publish_and_wait(
+        &self,
+        data: &T,
+        timeout: Duration,
+    ) -> Result<DeliveryReport, Box<dyn std::error::Error + Send + Sync>> {
+        let data = serialize_message(data)
+            .map_err(|e| RosLibRustError::Unexpected(anyhow::anyhow!("{e:?}")))?;
+        let (reply, pending) = oneshot::channel();
+        self.sender
+            .send(OutgoingMessage {
+                data,
+                ack_reply: Some(reply),
+            })
+            .await?;
+        log::debug!(
+            "Publishing data on topic {} and waiting for delivery",
+            self.topic_name
+        );
+        let pending = pending.await?;
+
+        let deadline = tokio::time::Instant::now() + timeout;
+        let mut report = DeliveryReport {
+            unconfirmable: pending.unconfirmable,
+            ..Default::default()
+        };
+        for mut ack in pending.acks {
+            let delivered = async {
+                let seq = ack.written.await.ok()?;
+                ack.acked.wait_for(|acked| *acked >= seq).await.ok()
+            };
+            match tokio::time::timeout_at(deadline, delivered).await {
+                Ok(Some(_)) => report.acknowledged += 1,
+                // Either the deadline passed, the message was dropped from the subscriber's queue,
+                // or the connection was dropped before acknowledging
+                _ => report.timed_out.push(ack.subscriber),
+            }
+        }
+        Ok(report)
+    }
+}
+
+/// Publishes on a latched topic, see
+/// [NodeHandle::advertise_latched](crate::ros1::NodeHandle::advertise_latched).
+///
+/// Only [LatchedPublisher::latch] is available, so every message on the topic is the one new
+/// subscribers receive when they connect.
+pub struct LatchedPublisher<T> {
+    publisher: Publisher<T>,
+}
+
+impl<T: RosMessageType> LatchedPublisher<T> {
+    pub(crate) fn new(publisher: Publisher<T>) -> Self {
+        Self { publisher }
+    }
+
+    /// Publishes `msg` to the current subscribers, and replaces the message sent to subscribers
+    /// which connect later
+    pub async fn latch(&mut self, msg: T) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        self.publisher.publish(&msg).await
+    }
+}
+
+/// A connected subscriber of a publication
+struct SubscriberStream {
+    caller_id: String,
+    /// Messages waiting to be written by `_writer_task`
+    queue: Arc<SendQueue>,
+    /// Compression negotiated with this subscriber
+    compression: Compression,
+    /// Present when the subscriber negotiated the `roslibrust_ack` extension
+    ack: Option<AckState>,
+    _writer_task: ChildTask<()>,
+}
+
+impl SubscriberStream {
+    fn new(
+        caller_id: String,
+        writer: OwnedWriteHalf,
+        compression: Compression,
+        ack: Option<AckState>,
+        queue: SendQueue,
+    ) -> Self {
+        let queue = Arc::new(queue);
+        let writer_task = tokio::spawn(write_queued_frames(
+            caller_id.clone(),
+            writer,
+            queue.clone(),
+        ));
+        Self {
+            caller_id,
+            queue,
+            compression,
+            ack,
+            _writer_task: writer_task.into(),
+        }
+    }
+}
+
+/// Writes frames from a subscriber's queue until its connection fails
+async fn write_queued_frames(caller_id: String, mut writer: OwnedWriteHalf, queue: Arc<SendQueue>) {
+    // Number of messages written to this subscriber, which is the sequence number of the last one
+    let mut sent: u32 = 0;
+    loop {
+        let frame = queue.pop().await;
+        if let Err(err) = writer.write_all(&frame.data[..]).await {
+            // TODO: A single failure between nodes that cross host boundaries is probably normal, should make this more robust perhaps
+            log::debug!("Failed to send data to subscriber {caller_id}: {err}, removing");
+            break;
+        }
+        sent = sent.wrapping_add(1);
+        if let Some(written) = frame.written {
+            // Publisher may have stopped waiting, nothing to do if so
+            let _ = written.send(sent);
+        }
+    }
+    queue.close();
+}
+
+/// A frame waiting in a subscriber's [SendQueue]
+struct QueuedFrame {
+    /// Shared between all subscribers receiving the same encoding of the message
+    data: Arc<Vec<u8>>,
+    /// Receives the sequence number of the frame once it has been written
+    written: Option<oneshot::Sender<u32>>,
+}
+
+/// Result of [SendQueue::push]
+#[derive(Debug, PartialEq, Eq)]
+enum Enqueued {
+    Queued,
+    /// The queue was full, and a message was discarded according to its [QueueFullPolicy]
+    Dropped,
+    /// The subscriber's connection has failed, so the frame was discarded
+    Closed,
+}
+
+/// Bounded queue of frames waiting to be written to a single subscriber
+struct SendQueue {
+    state: Mutex<SendQueueState>,
+    notify: Notify,
+    capacity: usize,
+    policy: QueueFullPolicy,
+    /// Where changes to whether the queue is backlogged are reported, with the subscriber's
+    /// caller id
+    backlog_events: Option<(String, broadcast::Sender<BacklogEvent>)>,
+}
+
+struct SendQueueState {
+    frames: VecDeque<QueuedFrame>,
+    closed: bool,
+    dropped: u64,
+    backlogged: bool,
+}
+
+impl SendQueue {
+    fn new(capacity: usize, policy: QueueFullPolicy) -> Self {
+        Self {
+            state: Mutex::new(SendQueueState {
+                frames: VecDeque::new(),
+                closed: false,
+                dropped: 0,
+                backlogged: false,
+            }),
+            notify: Notify::new(),
+            // A queue which can't hold anything would drop every message
+            capacity: capacity.max(1),
+            policy,
+            backlog_events: None,
+        }
+    }
+
+    /// Reports the queue overflowing and being emptied again on `events`
+    fn with_backlog_events(
+        mut self,
+        caller_id: String,
+        events: broadcast::Sender<BacklogEvent>,
+    ) -> Self {
+        self.backlog_events = Some((caller_id, events));
+        self
+    }
+
+    /// Records a change to whether the queue is backlogged, reporting it if it changed
+    fn set_backlogged(&self, state: &mut SendQueueState, backlogged: bool) {
+        if state.backlogged == backlogged {
+            return;
+        }
+        state.backlogged = backlogged;
+        if let Some((caller_id, events)) = &self.backlog_events {
+            // Nobody may be listening, nothing to do if so
+            let _ = events.send(BacklogEvent {
+                caller_id: caller_id.clone(),
+                backlogged,
+                dropped: state.dropped,
+            });
+        }
+    }
+
+    fn status(&self, caller_id: &str) -> SubscriberQueueStatus {
+        let state = self.state.lock().unwrap();
+        SubscriberQueueStatus {
+            caller_id: caller_id.to_owned(),
+            queued: state.frames.len(),
+            capacity: self.capacity,
+            dropped: state.dropped,
+            backlogged: state.backlogged,
+        }
+    }
+
+    /// Queues a frame without waiting, applying the queue's policy if it is full
+    fn push(&self, frame: QueuedFrame) -> Enqueued {
+        let mut state = self.state.lock().unwrap();
+        if state.closed {
+            return Enqueued::Closed;
+        }
+        let mut outcome = Enqueued::Queued;
+        if state.frames.len() >= self.capacity {
+            state.dropped += 1;
+            self.set_backlogged(&mut state, true);
+            match self.policy {
+                QueueFullPolicy::DropOldest => {
+                    state.frames.pop_front();
+                }
+                QueueFullPolicy::DropNewest => return Enqueued::Dropped,
+            }
+            outcome = Enqueued::Dropped;
+        }
+        state.frames.push_back(frame);
+        drop(state);
+        self.notify.notify_one();
+        outcome
+    }
+
+    /// Waits for the next frame to write
+    async fn pop(&self) -> QueuedFrame {
+        loop {
+            {
+                let mut state = self.state.lock().unwrap();
+                if let Some(frame) = state.frames.pop_front() {
+                    // The subscriber has caught up once nothing else is waiting for it
+                    if state.frames.is_empty() {
+                        self.set_backlogged(&mut state, false);
+                    }
+                    return frame;
+                }
+            }
+            self.notify.notified().await;
+        }
+    }
+
+    /// Discards any queued frames and rejects new ones
+    fn close(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.closed = true;
+        state.frames.clear();
+        self.set_backlogged(&mut state, false);
+    }
+}
+
+struct AckState {
+    acked: watch::Receiver<u32>,
+    _reader_task: ChildTask<()>,
+}
+
+impl AckState {
+    fn new(mut reader: tokio::net::tcp::OwnedReadHalf) -> Self {
+        let (sender, acked) = watch::channel(0);
+        let reader_task = tokio::spawn(async move {
+            // Acknowledgements are the little endian sequence number of each message received
+            while let Ok(seq) = reader.read_u32_le().await {
+                sender.send_replace(seq);
+            }
+        });
+        Self {
+            acked,
+            _reader_task: reader_task.into(),
+        }
+    }
+}
+
+/// Serializes a message with its length prefix, sizing the buffer up front when the message
+/// type is known to be fixed length
+fn serialize_message<T: RosMessageType>(data: &T) -> Result<Vec<u8>, serde_rosmsg::Error> {
+    let Some(size) = T::FIXED_SIZE else {
+        return serde_rosmsg::to_vec(data);
+    };
+    let mut frame = Vec::with_capacity(size + 4);
+    frame.extend_from_slice(&[0; 4]);
+    serde::Serialize::serialize(data, &mut serde_rosmsg::Serializer::new(&mut frame))?;
+    // Write the length we actually produced rather than trusting FIXED_SIZE
+    let len = (frame.len() - 4) as u32;
+    frame[..4].copy_from_slice(&len.to_le_bytes());
+    Ok(frame)
+}
+
+/// Compresses a serialized message into a new length-prefixed frame, the subscriber decompresses
+/// the frame's payload back into the original serialized message (which has its own length prefix)
+fn compress_frame(compression: Compression, data: &[u8]) -> std::io::Result<Vec<u8>> {
+    let compressed = compression.compress(data)?;
+    let mut frame = Vec::with_capacity(compressed.len() + 4);
+    frame.extend_from_slice(&(compressed.len() as u32).to_le_bytes());
+    frame.extend_from_slice(&compressed);
+    Ok(frame)
 }
 
 pub struct Publication {
     topic_type: String,
+    latching: bool,
     listener_port: u16,
     _channel_task: ChildTask<()>,
     _publish_task: ChildTask<()>,
-    publish_sender: mpsc::Sender<Vec<u8>>,
+    handle: PublicationHandle,
 }
 
 impl Publication {
     pub async fn new(
         node_name: &str,
-        latching: bool,
-        topic_name: &str,
-        host_addr: Ipv4Addr,
-        queue_size: usize,
+        host_addr: IpAddr,
         msg_definition: &str,
         md5sum: &str,
         topic_type: &str,
+        options: AdvertiseOptions,
     ) -> Result<Self, std::io::Error> {
+        let AdvertiseOptions {
+            topic,
+            queue_size,
+            latching,
+            tcp_nodelay,
+            compression,
+            md5check,
+            subscriber_queue_size,
+            queue_full_policy,
+        } = options;
+        // Channels can't be empty
+        let queue_size = queue_size.max(1);
+        let subscriber_queue_size = subscriber_queue_size.unwrap_or(queue_size);
         let host_addr = SocketAddr::from((host_addr, 0));
         let tcp_listener = tokio::net::TcpListener::bind(host_addr).await?;
         let listener_port = tcp_listener.local_addr().unwrap().port();
 
-        let (sender, mut receiver) = mpsc::channel::<Vec<u8>>(queue_size);
+        let (sender, mut receiver) = mpsc::channel::<OutgoingMessage>(queue_size);
 
         let responding_conn_header = ConnectionHeader {
             caller_id: node_name.to_owned(),
             latching,
             msg_definition: msg_definition.to_owned(),
             md5sum: md5sum.to_owned(),
-            topic: topic_name.to_owned(),
+            topic,
             topic_type: topic_type.to_owned(),
             tcp_nodelay: false,
+            roslibrust_ack: false,
+            compression: vec![],
+            error: None,
         };
 
         let subscriber_streams = Arc::new(RwLock::new(Vec::new()));
+        let (backlog_events, _) = broadcast::channel(BACKLOG_EVENT_CAPACITY);
+        let handle = PublicationHandle {
+            sender,
+            subscriber_streams: subscriber_streams.clone(),
+            backlog_events: backlog_events.clone(),
+        };
+        // The last message published when latching, only changed while holding the streams lock
+        // so every subscriber receives either the replayed message or the live publish
+        let latched_msg: Arc<Mutex<Option<Arc<Vec<u8>>>>> = Arc::new(Mutex::new(None));
 
         let subscriber_streams_copy = subscriber_streams.clone();
+        let latched_msg_copy = latched_msg.clone();
         let listener_handle = tokio::spawn(async move {
             let subscriber_streams = subscriber_streams_copy;
+            // Reused for the header we send to each subscriber
+            let mut response_header_bytes = Vec::with_capacity(1024);
             loop {
                 if let Ok((mut stream, peer_addr)) = tcp_listener.accept().await {
                     let topic_name = responding_conn_header.topic.as_str();
                     log::info!(
                         "Received connection from subscriber at {peer_addr} for topic {topic_name}"
                     );
-                    let mut connection_header = Vec::with_capacity(16 * 1024);
-                    if let Ok(bytes) = stream.read_buf(&mut connection_header).await {
-                        if let Ok(connection_header) =
-                            ConnectionHeader::from_bytes(&connection_header[..bytes])
+                    let connection_header = match tcpros::receive_frame(&mut stream).await {
+                        Ok(connection_header) => connection_header,
+                        Err(err) => {
+                            log::error!("Failed to read connection header from {peer_addr}: {err}");
+                            continue;
+                        }
+                    };
+                    if let Ok(connection_header) = ConnectionHeader::from_bytes(&connection_header)
+                    {
+                        // Subscribers which don't know the type ahead of time send a md5sum of "*"
+                        if md5check == Md5Check::Skip
+                            || connection_header.md5sum == "*"
+                            || connection_header.md5sum == responding_conn_header.md5sum
                         {
-                            if connection_header.md5sum == responding_conn_header.md5sum {
-                                log::debug!(
-                                    "Received subscribe request for {}",
-                                    connection_header.topic
-                                );
-                                // Write our own connection header in response
-                                let response_header_bytes = responding_conn_header
-                                    .to_bytes(false)
-                                    .expect("Couldn't serialize connection header");
-                                stream
-                                    .write(&response_header_bytes[..])
-                                    .await
-                                    .expect("Unable to respond on tcpstream");
-                                let mut wlock = subscriber_streams.write().await;
-                                wlock.push(stream);
-                                log::debug!(
-                                    "Added stream for topic {} to subscriber {}",
-                                    connection_header.topic,
-                                    peer_addr
+                            log::debug!(
+                                "Received subscribe request for {}",
+                                connection_header.topic
+                            );
+                            // Honor the subscriber's request to send each message immediately
+                            let nodelay = tcp_nodelay || connection_header.tcp_nodelay;
+                            if let Err(err) = stream.set_nodelay(nodelay) {
+                                log::warn!("Failed to set tcp_nodelay for {peer_addr}: {err}");
+                            }
+                            // Only compress if the subscriber told us it can decompress
+                            let compression =
+                                if connection_header.compression.contains(&compression) {
+                                    compression
+                                } else {
+                                    Compression::None
+                                };
+                            // Write our own connection header in response, agreeing to send
+                            // acknowledgements and compress only if the subscriber offered them
+                            response_header_bytes.clear();
+                            ConnectionHeader {
+                                roslibrust_ack: connection_header.roslibrust_ack,
+                                compression: match compression {
+                                    Compression::None => vec![],
+                                    compression => vec![compression],
+                                },
+                                ..responding_conn_header.clone()
+                            }
+                            .to_bytes_into(false, &mut response_header_bytes)
+                            .expect("Couldn't serialize connection header");
+                            stream
+                                .write_all(&response_header_bytes[..])
+                                .await
+                                .expect("Unable to respond on tcpstream");
+                            let (reader, writer) = stream.into_split();
+                            let ack = connection_header
+                                .roslibrust_ack
+                                .then(|| AckState::new(reader));
+                            let queue = SendQueue::new(subscriber_queue_size, queue_full_policy)
+                                .with_backlog_events(
+                                    connection_header.caller_id.clone(),
+                                    backlog_events.clone(),
                                 );
+                            let mut wlock = subscriber_streams.write().await;
+                            let latched = latched_msg_copy.lock().unwrap().clone();
+                            if let Some(data) = latched {
+                                let data = match compression {
+                                    Compression::None => Some(data),
+                                    compression => compress_frame(compression, &data)
+                                        .map_err(|err| log::error!("Failed to compress latched message for {peer_addr}: {err}"))
+                                        .ok()
+                                        .map(Arc::new),
+                                };
+                                if let Some(data) = data {
+                                    queue.push(QueuedFrame {
+                                        data,
+                                        written: None,
+                                    });
+                                }
                             }
+                            wlock.push(SubscriberStream::new(
+                                connection_header.caller_id,
+                                writer,
+                                compression,
+                                ack,
+                                queue,
+                            ));
+                            log::debug!(
+                                "Added stream for topic {} to subscriber {}",
+                                connection_header.topic,
+                                peer_addr
+                            );
                         } else {
-                            let header_str = connection_header[..bytes]
-                                .into_iter()
-                                .map(|ch| if *ch < 128 { *ch as char } else { '.' })
-                                .collect::<String>();
-                            log::error!(
-                                "Failed to parse connection header: ({bytes} bytes) {header_str}",
-                            )
+                            // Tell the subscriber why it was refused rather than just hanging up
+                            let reason = format!(
+                                "topic types do not match: [{}/{}] vs. [{}/{}]",
+                                connection_header.topic_type,
+                                connection_header.md5sum,
+                                responding_conn_header.topic_type,
+                                responding_conn_header.md5sum
+                            );
+                            log::warn!("Refusing subscriber at {peer_addr} for topic {topic_name}: {reason}");
+                            response_header_bytes.clear();
+                            ConnectionHeader {
+                                error: Some(reason),
+                                ..responding_conn_header.clone()
+                            }
+                            .to_bytes_into(false, &mut response_header_bytes)
+                            .expect("Couldn't serialize connection header");
+                            if let Err(err) = stream.write_all(&response_header_bytes[..]).await {
+                                log::debug!("Failed to send refusal to {peer_addr}: {err}");
+                            }
                         }
+                    } else {
+                        let header_str = connection_header
+                            .iter()
+                            .map(|ch| if *ch < 128 { *ch as char } else { '.' })
+                            .collect::<String>();
+                        log::error!(
+                            "Failed to parse connection header: ({} bytes) {header_str}",
+                            connection_header.len()
+                        )
                     }
                 }
             }
@@ -127,22 +777,66 @@ impl Publication {
                 match receiver.recv().await {
                     Some(msg_to_publish) => {
                         let mut streams = subscriber_streams.write().await;
-                        let mut streams_to_remove = vec![];
-                        for (stream_idx, stream) in streams.iter_mut().enumerate() {
-                            if let Err(err) = stream.write(&msg_to_publish[..]).await {
-                                // TODO: A single failure between nodes that cross host boundaries is probably normal, should make this more robust perhaps
-                                log::debug!("Failed to send data to subscriber: {err}, removing");
-                                streams_to_remove.push(stream_idx);
+                        let mut pending = PendingDelivery {
+                            acks: vec![],
+                            unconfirmable: 0,
+                        };
+                        let wants_reply = msg_to_publish.ack_reply.is_some();
+                        let data = Arc::new(msg_to_publish.data);
+                        if latching {
+                            *latched_msg.lock().unwrap() = Some(data.clone());
+                        }
+                        // Compressed once on demand, as all subscribers negotiate the same format
+                        let mut compressed: Option<Arc<Vec<u8>>> = None;
+                        // Queueing never waits on the subscriber, so one which isn't reading can't
+                        // hold up the others; its connection is dropped once writing to it fails
+                        streams.retain(|stream| {
+                            let data = match stream.compression {
+                                Compression::None => data.clone(),
+                                _ => {
+                                    if compressed.is_none() {
+                                        match compress_frame(stream.compression, &data) {
+                                            Ok(frame) => compressed = Some(Arc::new(frame)),
+                                            Err(err) => {
+                                                log::error!("Failed to compress message, it will not be sent to {}: {err}", stream.caller_id);
+                                                return true;
+                                            }
+                                        }
+                                    }
+                                    compressed.clone().unwrap()
+                                }
+                            };
+                            let (written, pending_ack) = match &stream.ack {
+                                Some(ack) if wants_reply => {
+                                    let (written, written_reply) = oneshot::channel();
+                                    let pending_ack = PendingAck {
+                                        subscriber: stream.caller_id.clone(),
+                                        written: written_reply,
+                                        acked: ack.acked.clone(),
+                                    };
+                                    (Some(written), Some(pending_ack))
+                                }
+                                _ => (None, None),
+                            };
+                            match stream.queue.push(QueuedFrame { data, written }) {
+                                Enqueued::Closed => return false,
+                                Enqueued::Dropped => log::debug!(
+                                    "Send queue for subscriber {} is full, dropping a message",
+                                    stream.caller_id
+                                ),
+                                Enqueued::Queued => {}
+                            }
+                            match pending_ack {
+                                Some(pending_ack) => pending.acks.push(pending_ack),
+                                None if stream.ack.is_none() => pending.unconfirmable += 1,
+                                None => {}
                             }
+                            true
+                        });
+                        if let Some(reply) = msg_to_publish.ack_reply {
+                            // Publisher may have stopped waiting, nothing to do if so
+                            let _ = reply.send(pending);
                         }
-                        // Subtract the removed count to account for shifting indices after each
-                        // remove, only works if they're sorted which should be the case given how
-                        // it's being populated (forward enumeration)
-                        streams_to_remove.into_iter().enumerate().for_each(
-                            |(removed_cnt, stream_idx)| {
-                                streams.remove(stream_idx - removed_cnt);
-                            },
-                        );
                     }
                     None => {
                         log::debug!("No more senders for the publisher channel, exiting...");
@@ -154,15 +848,16 @@ impl Publication {
 
         Ok(Self {
             topic_type: topic_type.to_owned(),
+            latching,
             _channel_task: listener_handle.into(),
             listener_port,
-            publish_sender: sender,
+            handle,
             _publish_task: publish_task.into(),
         })
     }
 
-    pub fn get_sender(&self) -> mpsc::Sender<Vec<u8>> {
-        self.publish_sender.clone()
+    pub(crate) fn get_handle(&self) -> PublicationHandle {
+        self.handle.clone()
     }
 
     pub fn port(&self) -> u16 {
@@ -172,4 +867,541 @@ impl Publication {
     pub fn topic_type(&self) -> &str {
         &self.topic_type
     }
+
+    /// Whether the last message published is sent to subscribers when they connect
+    pub fn latching(&self) -> bool {
+        self.latching
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        serialize_message, AdvertiseOptions, BacklogEvent, Enqueued, LatchedPublisher, Md5Check,
+        Publication, Publisher, QueueFullPolicy, QueuedFrame, SendQueue, SubscriberQueueStatus,
+    };
+    use crate::ros1::tcpros::{self, Compression, ConnectionHeader};
+    use std::{
+        net::{Ipv4Addr, Ipv6Addr},
+        time::Duration,
+    };
+    use tokio::{io::AsyncWriteExt, net::TcpStream};
+
+    #[derive(::serde::Deserialize, ::serde::Serialize, Debug, Clone, PartialEq)]
+    struct TestMsg {
+        data: String,
+    }
+
+    impl roslibrust_codegen::RosMessageType for TestMsg {
+        const ROS_TYPE_NAME: &'static str = "std_msgs/String";
+        const MD5SUM: &'static str = "992ce8a1687cec8c8bd883ec73ca41d1";
+        const DEFINITION: &'static str = "string data";
+    }
+
+    #[derive(::serde::Deserialize, ::serde::Serialize, Debug, Clone, PartialEq)]
+    struct FixedMsg {
+        x: f64,
+        y: f64,
+        z: f64,
+    }
+
+    impl roslibrust_codegen::RosMessageType for FixedMsg {
+        const ROS_TYPE_NAME: &'static str = "geometry_msgs/Point";
+        const MD5SUM: &'static str = "4a842b65f413084dc2b10fb484ea7f17";
+        const DEFINITION: &'static str = "float64 x\nfloat64 y\nfloat64 z";
+        const FIXED_SIZE: Option<usize> = Some(24);
+    }
+
+    async fn new_publication(compression: Compression) -> Publication {
+        new_publication_with_options(AdvertiseOptions::new("/chatter", 10).compression(compression))
+            .await
+    }
+
+    async fn new_publication_with_options(options: AdvertiseOptions) -> Publication {
+        Publication::new(
+            "/talker",
+            Ipv4Addr::LOCALHOST.into(),
+            "string data",
+            "992ce8a1687cec8c8bd883ec73ca41d1",
+            "std_msgs/String",
+            options,
+        )
+        .await
+        .unwrap()
+    }
+
+    async fn connect(port: u16, caller_id: &str, roslibrust_ack: bool) -> TcpStream {
+        connect_with_compression(port, caller_id, roslibrust_ack, vec![])
+            .await
+            .0
+    }
+
+    /// Returns the stream along with the compression selected by the publisher
+    async fn connect_with_compression(
+        port: u16,
+        caller_id: &str,
+        roslibrust_ack: bool,
+        compression: Vec<Compression>,
+    ) -> (TcpStream, Vec<Compression>) {
+        let mut stream = TcpStream::connect((Ipv4Addr::LOCALHOST, port))
+            .await
+            .unwrap();
+        let header = subscriber_header(caller_id, roslibrust_ack, compression);
+        stream
+            .write_all(&header.to_bytes(true).unwrap())
+            .await
+            .unwrap();
+        let response = tcpros::receive_frame(&mut stream).await.unwrap();
+        let response = ConnectionHeader::from_bytes(&response).unwrap();
+        assert_eq!(response.roslibrust_ack, roslibrust_ack);
+        (stream, response.compression)
+    }
+
+    fn subscriber_header(
+        caller_id: &str,
+        roslibrust_ack: bool,
+        compression: Vec<Compression>,
+    ) -> ConnectionHeader {
+        ConnectionHeader {
+            caller_id: caller_id.to_owned(),
+            latching: false,
+            msg_definition: "string data".to_owned(),
+            md5sum: "992ce8a1687cec8c8bd883ec73ca41d1".to_owned(),
+            topic: "/chatter".to_owned(),
+            topic_type: "std_msgs/String".to_owned(),
+            tcp_nodelay: false,
+            roslibrust_ack,
+            compression,
+            error: None,
+        }
+    }
+
+    #[test_log::test]
+    fn fixed_size_messages_serialize_into_exact_buffer() {
+        let msg = FixedMsg {
+            x: 1.0,
+            y: 2.0,
+            z: 3.0,
+        };
+        let frame = serialize_message(&msg).unwrap();
+        assert_eq!(frame, serde_rosmsg::to_vec(&msg).unwrap());
+        assert_eq!(frame.len(), 28);
+        assert_eq!(frame.capacity(), 28);
+
+        let msg = TestMsg {
+            data: "hello".to_owned(),
+        };
+        assert_eq!(
+            serialize_message(&msg).unwrap(),
+            serde_rosmsg::to_vec(&msg).unwrap()
+        );
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn publish_and_wait_reports_delivery() {
+        let publication = new_publication(Compression::None).await;
+        let publisher = Publisher::<TestMsg>::new("/chatter", publication.get_handle());
+
+        let mut acking = connect(publication.port(), "/acking", true).await;
+        let mut silent = connect(publication.port(), "/silent", true).await;
+        let mut plain = connect(publication.port(), "/plain", false).await;
+        // Give the listener a chance to register the connections
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        let msg = TestMsg {
+            data: "hello".to_owned(),
+        };
+        let ack_task = tokio::spawn(async move {
+            let frame = tcpros::receive_frame(&mut acking).await.unwrap();
+            acking.write_all(&1u32.to_le_bytes()).await.unwrap();
+            (acking, frame)
+        });
+        let report = publisher
+            .publish_and_wait(&msg, Duration::from_millis(200))
+            .await
+            .unwrap();
+        assert_eq!(report.acknowledged, 1);
+        assert_eq!(report.timed_out, vec!["/silent".to_owned()]);
+        assert_eq!(report.unconfirmable, 1);
+
+        let (_acking, frame) = ack_task.await.unwrap();
+        assert_eq!(serde_rosmsg::from_slice::<TestMsg>(&frame).unwrap(), msg);
+        for stream in [&mut silent, &mut plain] {
+            let frame = tcpros::receive_frame(stream).await.unwrap();
+            assert_eq!(serde_rosmsg::from_slice::<TestMsg>(&frame).unwrap(), msg);
+        }
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn compression_negotiated_per_subscriber() {
+        let publication = new_publication(Compression::Zstd).await;
+        let publisher = Publisher::<TestMsg>::new("/chatter", publication.get_handle());
+
+        let (mut compressed, selected) = connect_with_compression(
+            publication.port(),
+            "/compressed",
+            false,
+            vec![Compression::Lz4, Compression::Zstd],
+        )
+        .await;
+        assert_eq!(selected, vec![Compression::Zstd]);
+        let (mut lz4_only, selected) = connect_with_compression(
+            publication.port(),
+            "/lz4_only",
+            false,
+            vec![Compression::Lz4],
+        )
+        .await;
+        assert!(selected.is_empty());
+        let mut plain = connect(publication.port(), "/plain", false).await;
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        let msg = TestMsg {
+            data: "hello ".repeat(100),
+        };
+        publisher.publish(&msg).await.unwrap();
+
+        let frame = tcpros::receive_frame(&mut compressed).await.unwrap();
+        let decompressed = Compression::Zstd
+            .decompress(&frame[4..], tcpros::DEFAULT_MAX_FRAME_LENGTH)
+            .unwrap();
+        assert!(frame.len() < decompressed.len());
+        assert_eq!(
+            serde_rosmsg::from_slice::<TestMsg>(&decompressed).unwrap(),
+            msg
+        );
+        // Subscribers which can't decode the publisher's format fall back to uncompressed
+        for stream in [&mut lz4_only, &mut plain] {
+            let frame = tcpros::receive_frame(stream).await.unwrap();
+            assert_eq!(serde_rosmsg::from_slice::<TestMsg>(&frame).unwrap(), msg);
+        }
+    }
+
+    #[test_log::test]
+    fn advertise_options_builder_sets_each_option() {
+        let options = AdvertiseOptions::new("/scan", 5);
+        assert_eq!(options.topic, "/scan");
+        assert_eq!(options.queue_size, 5);
+        assert!(!options.latching);
+        assert!(!options.tcp_nodelay);
+        assert_eq!(options.compression, Compression::None);
+        assert_eq!(options.md5check, Md5Check::Strict);
+
+        let options = options
+            .latching(true)
+            .tcp_nodelay(true)
+            .compression(Compression::Lz4)
+            .md5check(Md5Check::Skip)
+            .subscriber_queue_size(2)
+            .queue_full_policy(QueueFullPolicy::DropNewest);
+        assert!(options.latching);
+        assert!(options.tcp_nodelay);
+        assert_eq!(options.compression, Compression::Lz4);
+        assert_eq!(options.md5check, Md5Check::Skip);
+        assert_eq!(options.subscriber_queue_size, Some(2));
+        assert_eq!(options.queue_full_policy, QueueFullPolicy::DropNewest);
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn md5check_decides_whether_mismatched_subscribers_are_refused() {
+        let stale = ConnectionHeader {
+            md5sum: "0123456789abcdef0123456789abcdef".to_owned(),
+            ..subscriber_header("/stale", false, vec![])
+        };
+        let subscribe = |port: u16| {
+            let stale = stale.clone();
+            async move {
+                let mut stream = TcpStream::connect((Ipv4Addr::LOCALHOST, port))
+                    .await
+                    .unwrap();
+                stream
+                    .write_all(&stale.to_bytes(true).unwrap())
+                    .await
+                    .unwrap();
+                let response = tcpros::receive_frame(&mut stream).await.unwrap();
+                (stream, ConnectionHeader::from_bytes(&response).unwrap())
+            }
+        };
+
+        let strict = new_publication_with_options(AdvertiseOptions::new("/chatter", 10)).await;
+        let (_stream, response) = subscribe(strict.port()).await;
+        assert!(response.error.unwrap().contains("topic types do not match"));
+
+        let skip = new_publication_with_options(
+            AdvertiseOptions::new("/chatter", 10)
+                .tcp_nodelay(true)
+                .md5check(Md5Check::Skip),
+        )
+        .await;
+        let publisher = Publisher::<TestMsg>::new("/chatter", skip.get_handle());
+        let (mut stream, response) = subscribe(skip.port()).await;
+        assert_eq!(response.error, None);
+        // Give the listener a chance to register the connection
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        let msg = TestMsg {
+            data: "hello".to_owned(),
+        };
+        publisher.publish(&msg).await.unwrap();
+        let frame = tcpros::receive_frame(&mut stream).await.unwrap();
+        assert_eq!(serde_rosmsg::from_slice::<TestMsg>(&frame).unwrap(), msg);
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn latched_message_replayed_to_new_subscribers() {
+        let publication = Publication::new(
+            "/talker",
+            Ipv4Addr::LOCALHOST.into(),
+            "string data",
+            "992ce8a1687cec8c8bd883ec73ca41d1",
+            "std_msgs/String",
+            AdvertiseOptions::new("/chatter", 10)
+                .latching(true)
+                .compression(Compression::Lz4),
+        )
+        .await
+        .unwrap();
+        let mut publisher = LatchedPublisher::new(Publisher::<TestMsg>::new(
+            "/chatter",
+            publication.get_handle(),
+        ));
+        let msg = |data: &str| TestMsg {
+            data: data.to_owned(),
+        };
+        publisher.latch(msg("first")).await.unwrap();
+        publisher.latch(msg("second")).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        let mut plain = TcpStream::connect((Ipv4Addr::LOCALHOST, publication.port()))
+            .await
+            .unwrap();
+        let header = subscriber_header("/plain", false, vec![]);
+        plain
+            .write_all(&header.to_bytes(true).unwrap())
+            .await
+            .unwrap();
+        let response = tcpros::receive_frame(&mut plain).await.unwrap();
+        assert!(ConnectionHeader::from_bytes(&response).unwrap().latching);
+        // Only the most recent message is replayed
+        let frame = tcpros::receive_frame(&mut plain).await.unwrap();
+        assert_eq!(
+            serde_rosmsg::from_slice::<TestMsg>(&frame).unwrap(),
+            msg("second")
+        );
+
+        let (mut compressed, _) = connect_with_compression(
+            publication.port(),
+            "/compressed",
+            false,
+            vec![Compression::Lz4],
+        )
+        .await;
+        let frame = tcpros::receive_frame(&mut compressed).await.unwrap();
+        let decompressed = Compression::Lz4
+            .decompress(&frame[4..], tcpros::DEFAULT_MAX_FRAME_LENGTH)
+            .unwrap();
+        assert_eq!(
+            serde_rosmsg::from_slice::<TestMsg>(&decompressed).unwrap(),
+            msg("second")
+        );
+
+        publisher.latch(msg("third")).await.unwrap();
+        let frame = tcpros::receive_frame(&mut plain).await.unwrap();
+        assert_eq!(
+            serde_rosmsg::from_slice::<TestMsg>(&frame).unwrap(),
+            msg("third")
+        );
+    }
+
+    fn frame(byte: u8) -> QueuedFrame {
+        QueuedFrame {
+            data: std::sync::Arc::new(vec![byte]),
+            written: None,
+        }
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn send_queue_applies_policy_when_full() {
+        let queue = SendQueue::new(2, QueueFullPolicy::DropOldest);
+        assert_eq!(queue.push(frame(1)), Enqueued::Queued);
+        assert_eq!(queue.push(frame(2)), Enqueued::Queued);
+        assert_eq!(queue.push(frame(3)), Enqueued::Dropped);
+        assert_eq!(*queue.pop().await.data, vec![2]);
+        assert_eq!(*queue.pop().await.data, vec![3]);
+
+        let queue = SendQueue::new(2, QueueFullPolicy::DropNewest);
+        assert_eq!(queue.push(frame(1)), Enqueued::Queued);
+        assert_eq!(queue.push(frame(2)), Enqueued::Queued);
+        assert_eq!(queue.push(frame(3)), Enqueued::Dropped);
+        assert_eq!(*queue.pop().await.data, vec![1]);
+        assert_eq!(*queue.pop().await.data, vec![2]);
+
+        queue.push(frame(4));
+        queue.close();
+        assert_eq!(queue.push(frame(5)), Enqueued::Closed);
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn send_queue_reports_backlog() {
+        let (events, mut received) = tokio::sync::broadcast::channel(8);
+        let queue = SendQueue::new(2, QueueFullPolicy::DropOldest)
+            .with_backlog_events("/slow".to_owned(), events);
+        let event = |backlogged, dropped| BacklogEvent {
+            caller_id: "/slow".to_owned(),
+            backlogged,
+            dropped,
+        };
+        queue.push(frame(1));
+        queue.push(frame(2));
+        assert!(received.try_recv().is_err());
+        queue.push(frame(3));
+        queue.push(frame(4));
+        // Only the change is reported, not every dropped message
+        assert_eq!(received.try_recv().unwrap(), event(true, 1));
+        assert!(received.try_recv().is_err());
+        assert_eq!(
+            queue.status("/slow"),
+            SubscriberQueueStatus {
+                caller_id: "/slow".to_owned(),
+                queued: 2,
+                capacity: 2,
+                dropped: 2,
+                backlogged: true,
+            }
+        );
+
+        queue.pop().await;
+        assert!(received.try_recv().is_err());
+        queue.pop().await;
+        assert_eq!(received.try_recv().unwrap(), event(false, 2));
+        assert!(!queue.status("/slow").backlogged);
+
+        // Disconnecting while backlogged also ends the backlog
+        for byte in 5..8 {
+            queue.push(frame(byte));
+        }
+        assert_eq!(received.try_recv().unwrap(), event(true, 3));
+        queue.close();
+        assert_eq!(received.try_recv().unwrap(), event(false, 3));
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn stuck_subscriber_does_not_stall_others() {
+        let publication = new_publication_with_options(
+            AdvertiseOptions::new("/chatter", 10).subscriber_queue_size(4),
+        )
+        .await;
+        let publisher = Publisher::<TestMsg>::new("/chatter", publication.get_handle());
+
+        // Never reads, so once the socket buffers fill its messages can only be queued
+        let _stuck = connect(publication.port(), "/stuck", false).await;
+        let mut reader = connect(publication.port(), "/reader", false).await;
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        let msg = TestMsg {
+            data: "x".repeat(100_000),
+        };
+        // Far more data than the socket buffers of the stuck subscriber can absorb
+        tokio::time::timeout(Duration::from_secs(10), async {
+            for _ in 0..200 {
+                publisher.publish(&msg).await.unwrap();
+                let frame = tcpros::receive_frame(&mut reader).await.unwrap();
+                assert_eq!(serde_rosmsg::from_slice::<TestMsg>(&frame).unwrap(), msg);
+            }
+        })
+        .await
+        .expect("Publishing stalled on the stuck subscriber");
+
+        // The publisher can tell which subscriber isn't keeping up
+        let queues = publisher.subscriber_queues().await;
+        let stuck = queues.iter().find(|q| q.caller_id == "/stuck").unwrap();
+        assert!(stuck.backlogged);
+        assert_eq!(stuck.queued, 4);
+        assert!(stuck.dropped > 0);
+        let reader = queues.iter().find(|q| q.caller_id == "/reader").unwrap();
+        assert!(!reader.backlogged);
+        assert_eq!(reader.dropped, 0);
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn backlog_events_report_slow_subscribers() {
+        let publication = new_publication_with_options(
+            AdvertiseOptions::new("/chatter", 10).subscriber_queue_size(2),
+        )
+        .await;
+        let publisher = Publisher::<TestMsg>::new("/chatter", publication.get_handle());
+        let mut events = publisher.backlog_events();
+
+        let mut slow = connect(publication.port(), "/slow", false).await;
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        let msg = TestMsg {
+            data: "x".repeat(100_000),
+        };
+        // Publish until the socket buffers and then the queue are full
+        let event = tokio::time::timeout(Duration::from_secs(10), async {
+            loop {
+                publisher.publish(&msg).await.unwrap();
+                if let Ok(event) = events.try_recv() {
+                    return event;
+                }
+                tokio::task::yield_now().await;
+            }
+        })
+        .await
+        .expect("Subscriber never became backlogged");
+        assert_eq!(event.caller_id, "/slow");
+        assert!(event.backlogged);
+        assert_eq!(event.dropped, 1);
+
+        // Reading everything sent lets the subscriber catch up
+        let caught_up = tokio::time::timeout(Duration::from_secs(10), async {
+            loop {
+                tokio::select! {
+                    frame = tcpros::receive_frame(&mut slow) => { frame.unwrap(); }
+                    event = events.recv() => return event.unwrap(),
+                }
+            }
+        })
+        .await
+        .expect("Subscriber never caught up");
+        assert_eq!(caught_up.caller_id, "/slow");
+        assert!(!caught_up.backlogged);
+        assert!(caught_up.dropped >= 1);
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn publishes_over_ipv6() {
+        let publication = Publication::new(
+            "/talker",
+            Ipv6Addr::LOCALHOST.into(),
+            "string data",
+            "992ce8a1687cec8c8bd883ec73ca41d1",
+            "std_msgs/String",
+            AdvertiseOptions::new("/chatter", 10),
+        )
+        .await
+        .unwrap();
+        let publisher = Publisher::<TestMsg>::new("/chatter", publication.get_handle());
+
+        // Connect the way a subscriber given ["TCPROS", "::1", port] would
+        let endpoint = tcpros::host_port("::1", publication.port());
+        let mut stream = TcpStream::connect(endpoint.as_str()).await.unwrap();
+        stream
+            .write_all(
+                &subscriber_header("/listener", false, vec![])
+                    .to_bytes(true)
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        tcpros::receive_frame(&mut stream).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        let msg = TestMsg {
+            data: "hello".to_owned(),
+        };
+        publisher.publish(&msg).await.unwrap();
+        let frame = tcpros::receive_frame(&mut stream).await.unwrap();
+        assert_eq!(serde_rosmsg::from_slice::<TestMsg>(&frame).unwrap(), msg);
+    }
 }
@@ -0,0 +1,186 @@
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+/// Number of message arrivals [RateStats] are computed over when
+/// [SubscriberOptions::rate_window](crate::ros1::SubscriberOptions::rate_window) isn't set
+pub(crate) const DEFAULT_RATE_WINDOW: usize = 100;
+
+/// How often messages have been arriving on a subscription, computed over the intervals between
+/// its most recent messages, see [Subscriber::rate_stats](crate::ros1::Subscriber::rate_stats).
+///
+/// All statistics are zero until at least two messages have arrived.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct RateStats {
+    samples: usize,
+    /// Mean interval in seconds
+    mean: f64,
+    /// Variance of the intervals in seconds squared
+    variance: f64,
+    min_interval: Duration,
+    max_interval: Duration,
+}
+
+impl RateStats {
+    /// Number of intervals between arrivals the statistics were computed over
+    pub fn samples(&self) -> usize {
+        self.samples
+    }
+
+    /// Average rate messages arrived at, the inverse of the mean interval between them
+    pub fn mean_hz(&self) -> f64 {
+        if self.mean > 0.0 {
+            1.0 / self.mean
+        } else {
+            0.0
+        }
+    }
+
+    /// Standard deviation of the interval between messages expressed as a rate, i.e. scaled by
+    /// the square of [RateStats::mean_hz], which is how far the rate typically strays from the mean
+    pub fn std_dev_hz(&self) -> f64 {
+        self.variance.sqrt() * self.mean_hz() * self.mean_hz()
+    }
+
+    /// Shortest interval between two consecutive messages
+    pub fn min_interval(&self) -> Duration {
+        self.min_interval
+    }
+
+    /// Longest interval between two consecutive messages
+    pub fn max_interval(&self) -> Duration {
+        self.max_interval
+    }
+}
+
+/// Tracks the arrival times of a subscription's messages, shared by its connections to
+/// publishers which record arrivals and its subscribers which read the statistics
+#[derive(Clone, Debug)]
+pub(crate) struct RateTracker {
+    inner: Arc<Mutex<RateWindow>>,
+}
+
+/// Welford's online mean and variance of the intervals in a sliding window, removing the oldest
+/// interval from the running totals as each new one arrives once the window is full
+#[derive(Debug)]
+struct RateWindow {
+    last_arrival: Option<Instant>,
+    /// Intervals in the window, oldest first
+    intervals: VecDeque<Duration>,
+    max_intervals: usize,
+    mean: f64,
+    /// Sum of squared differences from the mean
+    m2: f64,
+}
+
+impl RateTracker {
+    /// Tracks the last `window` arrivals, which is one fewer intervals between them
+    pub(crate) fn new(window: usize) -> Self {
+        let max_intervals = window.max(2) - 1;
+        Self {
+            inner: Arc::new(Mutex::new(RateWindow {
+                last_arrival: None,
+                intervals: VecDeque::with_capacity(max_intervals),
+                max_intervals,
+                mean: 0.0,
+                m2: 0.0,
+            })),
+        }
+    }
+
+    /// Records a message arriving at `at`
+    pub(crate) fn record(&self, at: Instant) {
+        let mut window = self.inner.lock().unwrap();
+        let Some(last_arrival) = window.last_arrival.replace(at) else {
+            return;
+        };
+        let interval = at.saturating_duration_since(last_arrival);
+        if window.intervals.len() == window.max_intervals {
+            let oldest = window.intervals.pop_front().unwrap();
+            window.remove(oldest.as_secs_f64());
+        }
+        window.intervals.push_back(interval);
+        window.add(interval.as_secs_f64());
+    }
+
+    pub(crate) fn stats(&self) -> RateStats {
+        let window = self.inner.lock().unwrap();
+        let samples = window.intervals.len();
+        if samples == 0 {
+            return RateStats::default();
+        }
+        RateStats {
+            samples,
+            mean: window.mean,
+            // Rounding can leave m2 slightly negative after removals
+            variance: (window.m2 / samples as f64).max(0.0),
+            min_interval: window.intervals.iter().min().copied().unwrap_or_default(),
+            max_interval: window.intervals.iter().max().copied().unwrap_or_default(),
+        }
+    }
+}
+
+impl RateWindow {
+    /// Adds a sample to the running totals, after it has been pushed to `intervals`
+    fn add(&mut self, x: f64) {
+        let n = self.intervals.len() as f64;
+        let delta = x - self.mean;
+        self.mean += delta / n;
+        self.m2 += delta * (x - self.mean);
+    }
+
+    /// Removes a sample from the running totals, after it has been popped from `intervals`
+    fn remove(&mut self, x: f64) {
+        let n = self.intervals.len() as f64;
+        if n == 0.0 {
+            self.mean = 0.0;
+            self.m2 = 0.0;
+            return;
+        }
+        let delta = x - self.mean;
+        self.mean -= delta / n;
+        self.m2 -= delta * (x - self.mean);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::RateTracker;
+    use std::time::{Duration, Instant};
+
+    #[test_log::test]
+    fn stats_cover_a_sliding_window() {
+        let tracker = RateTracker::new(4);
+        let start = Instant::now();
+        let at = |ms: u64| start + Duration::from_millis(ms);
+        assert_eq!(tracker.stats().mean_hz(), 0.0);
+        tracker.record(at(0));
+        assert_eq!(tracker.stats().samples(), 0);
+
+        // Intervals of 100, 300 and 200ms
+        for ms in [100, 400, 600] {
+            tracker.record(at(ms));
+        }
+        let stats = tracker.stats();
+        assert_eq!(stats.samples(), 3);
+        assert!((stats.mean_hz() - 5.0).abs() < 1e-9, "{stats:?}");
+        // The intervals' standard deviation is sqrt(2/3) * 100ms, scaled by 5Hz squared
+        let std_dev = (2.0f64 / 3.0).sqrt() * 0.1 * 25.0;
+        assert!((stats.std_dev_hz() - std_dev).abs() < 1e-9, "{stats:?}");
+        assert_eq!(stats.min_interval(), Duration::from_millis(100));
+        assert_eq!(stats.max_interval(), Duration::from_millis(300));
+
+        // Steady 50ms intervals push the earlier ones out of the window
+        for ms in [650, 700, 750] {
+            tracker.record(at(ms));
+        }
+        let stats = tracker.stats();
+        assert_eq!(stats.samples(), 3);
+        assert!((stats.mean_hz() - 20.0).abs() < 1e-6, "{stats:?}");
+        assert!(stats.std_dev_hz() < 1e-3, "{stats:?}");
+        assert_eq!(stats.min_interval(), Duration::from_millis(50));
+        assert_eq!(stats.max_interval(), Duration::from_millis(50));
+    }
+}
@@ -0,0 +1,551 @@
+use crate::ros1::{
+    node::NodeServerHandle,
+    tcpros::{self, ConnectionHeader, TcprosError},
+};
+use roslibrust_codegen::RosServiceType;
+use std::{
+    marker::PhantomData,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpStream,
+};
+
+/// How often [ServiceProxy::wait_until_available] asks the master whether the service exists
+const AVAILABILITY_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Errors calling a ROS1 service
+#[derive(thiserror::Error, Debug)]
+pub enum ServiceError {
+    #[error("Failed to look up service {service}: {reason}")]
+    Lookup { service: String, reason: String },
+    #[error("Service {service} did not become available within {timeout:?}")]
+    NotAvailable { service: String, timeout: Duration },
+    #[error("Invalid service uri {0}, expected rosrpc://host:port")]
+    InvalidUri(String),
+    /// Connecting to the service server failed, or it refused the connection
+    #[error(transparent)]
+    Connection(#[from] TcprosError),
+    /// The connection failed after the request was sent, so it may have been handled
+    #[error("Service call failed: {0}")]
+    Io(std::io::Error),
+    #[error("Failed to serialize the request or deserialize the response: {0}")]
+    Serialization(String),
+    /// The service server's callback failed, with the message it responded with
+    #[error("Service server responded with an error: {0}")]
+    Failed(String),
+    /// The service's type isn't in the [ServiceTypeRegistry](crate::ros1::ServiceTypeRegistry)
+    /// it was looked up in
+    #[error("Service {service} has type {service_type}, which is not in the registry")]
+    UnknownType {
+        service: String,
+        service_type: String,
+    },
+}
+
+/// Calls a service hosted by another node at a known uri, as given by the master's
+/// `lookupService`. Each call is made on its own connection.
+///
+/// Prefer [ServiceProxy], which looks the uri up itself and follows the service if it moves.
+pub struct ServiceClient<T> {
+    caller_id: String,
+    service: String,
+    uri: String,
+    _phantom: PhantomData<T>,
+}
+
+impl<T: RosServiceType> ServiceClient<T> {
+    /// - caller_id: Name of the calling node, e.g. "/my_node"
+    /// - service: Name of the service being called, e.g. "/add_two_ints"
+    /// - uri: Where the service is hosted, e.g. "rosrpc://localhost:45123"
+    pub fn new(caller_id: &str, service: &str, uri: &str) -> Self {
+        Self {
+            caller_id: caller_id.to_owned(),
+            service: service.to_owned(),
+            uri: uri.to_owned(),
+            _phantom: PhantomData,
+        }
+    }
+
+    pub fn uri(&self) -> &str {
+        &self.uri
+    }
+
+    /// Calls the service, failing with [ServiceError::Failed] if the server's callback failed
+    pub async fn call(&self, request: &T::Request) -> Result<T::Response, ServiceError> {
+        let mut stream = self.connect().await?;
+        let request = serde_rosmsg::to_vec(request)
+            .map_err(|e| ServiceError::Serialization(format!("{e:?}")))?;
+        stream.write_all(&request).await.map_err(ServiceError::Io)?;
+
+        // The response is a byte indicating success, followed by either the response message or
+        // the server's error message
+        let ok = stream.read_u8().await.map_err(ServiceError::Io)?;
+        let frame = tcpros::receive_frame(&mut stream)
+            .await
+            .map_err(ServiceError::Io)?;
+        if ok == 0 {
+            return Err(ServiceError::Failed(
+                String::from_utf8_lossy(&frame[4..]).into_owned(),
+            ));
+        }
+        serde_rosmsg::from_slice(&frame).map_err(|e| ServiceError::Serialization(format!("{e:?}")))
+    }
+
+    /// Connects to the service server and exchanges connection headers
+    async fn connect(&self) -> Result<TcpStream, ServiceError> {
+        let (stream, _header) = connect(
+            &self.uri,
+            &[
+                ("callerid", self.caller_id.as_str()),
+                ("md5sum", T::MD5SUM),
+                ("persistent", "0"),
+                ("service", self.service.as_str()),
+            ],
+        )
+        .await?;
+        Ok(stream)
+    }
+}
+
+/// Connects to the service server at `uri`, sending a connection header with the given fields and
+/// returning the server's header
+async fn connect(
+    uri: &str,
+    fields: &[(&str, &str)],
+) -> Result<(TcpStream, ConnectionHeader), ServiceError> {
+    let addr = uri
+        .strip_prefix("rosrpc://")
+        .map(|addr| addr.trim_end_matches('/'))
+        .ok_or_else(|| ServiceError::InvalidUri(uri.to_owned()))?;
+    let mut stream = TcpStream::connect(addr).await.map_err(TcprosError::from)?;
+
+    let header = tcpros::encode_fields(fields).map_err(TcprosError::from)?;
+    stream.write_all(&header).await.map_err(TcprosError::from)?;
+    let response = tcpros::receive_frame(&mut stream)
+        .await
+        .map_err(TcprosError::from)?;
+    let response = ConnectionHeader::from_bytes(&response).map_err(TcprosError::from)?;
+    if let Some(reason) = response.error {
+        return Err(TcprosError::ConnectionRefused(reason).into());
+    }
+    Ok((stream, response))
+}
+
+/// Asks the server hosting `service` at `uri` for the service's type, as `rosservice type` does.
+/// A server receiving a header with `probe=1` answers with its own header, including the type,
+/// without waiting for a request.
+pub(crate) async fn probe_service_type(
+    caller_id: &str,
+    service: &str,
+    uri: &str,
+) -> Result<String, ServiceError> {
+    let (_stream, header) = connect(
+        uri,
+        &[
+            ("callerid", caller_id),
+            ("md5sum", "*"),
+            ("probe", "1"),
+            ("service", service),
+        ],
+    )
+    .await?;
+    Ok(header.topic_type)
+}
+
+/// Calls a service by name, looking up which node hosts it with the master, see
+/// [NodeHandle::service_proxy](crate::ros1::NodeHandle::service_proxy).
+///
+/// The uri looked up is reused for calls made within the proxy's cache ttl. If connecting to a
+/// reused uri fails, as it will once the service restarts elsewhere, the service is looked up
+/// again and the call retried once.
+pub struct ServiceProxy<T> {
+    node: NodeServerHandle,
+    caller_id: String,
+    service: String,
+    cache_ttl: Duration,
+    // The last uri looked up, and when
+    cached_uri: Mutex<Option<(String, Instant)>>,
+    _phantom: PhantomData<T>,
+}
+
+impl<T: RosServiceType> ServiceProxy<T> {
+    pub(crate) fn new(
+        node: NodeServerHandle,
+        caller_id: String,
+        service: &str,
+        cache_ttl: Duration,
+    ) -> Self {
+        Self {
+            node,
+            caller_id,
+            service: service.to_owned(),
+            cache_ttl,
+            cached_uri: Mutex::new(None),
+            _phantom: PhantomData,
+        }
+    }
+
+    /// The name of the service this proxy calls
+    pub fn service(&self) -> &str {
+        &self.service
+    }
+
+    /// Calls the service, see [ServiceClient::call]
+    pub async fn call(&self, request: &T::Request) -> Result<T::Response, ServiceError> {
+        let (uri, cached) = match self.cached() {
+            Some(uri) => (uri, true),
+            None => (self.lookup().await?, false),
+        };
+        match ServiceClient::<T>::new(&self.caller_id, &self.service, &uri)
+            .call(request)
+            .await
+        {
+            Err(ServiceError::Connection(e)) if cached => {
+                log::debug!(
+                    "Failed to connect to {} at {uri}: {e}, looking it up again",
+                    self.service
+                );
+                let uri = self.lookup().await?;
+                ServiceClient::<T>::new(&self.caller_id, &self.service, &uri)
+                    .call(request)
+                    .await
+            }
+            result => result,
+        }
+    }
+
+    /// Waits until the master knows of a node hosting the service, failing with
+    /// [ServiceError::NotAvailable] if none does within `timeout`.
+    pub async fn wait_until_available(&self, timeout: Duration) -> Result<(), ServiceError> {
+        let wait = async {
+            loop {
+                match self.lookup().await {
+                    Ok(_) => return,
+                    Err(e) => log::trace!("Waiting for {} to become available: {e}", self.service),
+                }
+                tokio::time::sleep(AVAILABILITY_POLL_INTERVAL).await;
+            }
+        };
+        tokio::time::timeout(timeout, wait)
+            .await
+            .map_err(|_| ServiceError::NotAvailable {
+                service: self.service.clone(),
+                timeout,
+            })
+    }
+
+    /// The cached uri, if it was looked up within the cache ttl
+    fn cached(&self) -> Option<String> {
+        match &*self.cached_uri.lock().unwrap() {
+            Some((uri, looked_up)) if looked_up.elapsed() < self.cache_ttl => Some(uri.clone()),
+            _ => None,
+        }
+    }
+
+    /// Asks the master where the service is hosted, replacing the cached uri
+    async fn lookup(&self) -> Result<String, ServiceError> {
+        let uri = self.node.lookup_service(&self.service).await;
+        let mut cached_uri = self.cached_uri.lock().unwrap();
+        match uri {
+            Ok(uri) => {
+                *cached_uri = Some((uri.clone(), Instant::now()));
+                Ok(uri)
+            }
+            Err(e) => {
+                *cached_uri = None;
+                Err(ServiceError::Lookup {
+                    service: self.service.clone(),
+                    reason: e.to_string(),
+                })
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{ServiceClient, ServiceError};
+    use crate::ros1::{tcpros, NodeHandle, ServiceTypeRegistry};
+    use hyper::{Body, Response};
+    use std::{
+        convert::Infallible,
+        net::{Ipv4Addr, SocketAddr},
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc, Mutex,
+        },
+        time::Duration,
+    };
+    use tokio::{io::AsyncWriteExt, net::TcpListener};
+
+    #[derive(::serde::Deserialize, ::serde::Serialize, Debug, Clone, PartialEq)]
+    struct AddTwoIntsRequest {
+        a: i64,
+        b: i64,
+    }
+
+    impl roslibrust_codegen::RosMessageType for AddTwoIntsRequest {
+        const ROS_TYPE_NAME: &'static str = "test_srvs/AddTwoIntsRequest";
+        const MD5SUM: &'static str = "36d09b846be0b371c5f190354dd3153e";
+        const DEFINITION: &'static str = "int64 a\nint64 b";
+    }
+
+    #[derive(::serde::Deserialize, ::serde::Serialize, Debug, Clone, PartialEq)]
+    struct AddTwoIntsResponse {
+        sum: i64,
+    }
+
+    impl roslibrust_codegen::RosMessageType for AddTwoIntsResponse {
+        const ROS_TYPE_NAME: &'static str = "test_srvs/AddTwoIntsResponse";
+        const MD5SUM: &'static str = "b88405221c77b1878a3cbbfff53428d7";
+        const DEFINITION: &'static str = "int64 sum";
+    }
+
+    struct AddTwoInts;
+
+    impl roslibrust_codegen::RosServiceType for AddTwoInts {
+        const ROS_SERVICE_NAME: &'static str = "test_srvs/AddTwoInts";
+        const MD5SUM: &'static str = "6a2e34150c00229791cc89ff309fff21";
+        type Request = AddTwoIntsRequest;
+        type Response = AddTwoIntsResponse;
+    }
+
+    /// A rosmaster which only answers getUri and lookupService, for the service uri it is given
+    struct MockMaster {
+        uri: String,
+        service_uri: Arc<Mutex<Option<String>>>,
+        lookups: Arc<AtomicUsize>,
+        _task: abort_on_drop::ChildTask<()>,
+    }
+
+    impl MockMaster {
+        fn new() -> Self {
+            let service_uri = Arc::new(Mutex::new(None::<String>));
+            let lookups = Arc::new(AtomicUsize::new(0));
+            let (state, counter) = (service_uri.clone(), lookups.clone());
+            let make_svc = hyper::service::make_service_fn(move |_| {
+                let (state, counter) = (state.clone(), counter.clone());
+                async move {
+                    Ok::<_, Infallible>(hyper::service::service_fn(move |req| {
+                        let (state, counter) = (state.clone(), counter.clone());
+                        async move {
+                            let body = hyper::body::to_bytes(req).await.unwrap();
+                            let (method, _args) =
+                                serde_xmlrpc::request_from_str(std::str::from_utf8(&body).unwrap())
+                                    .unwrap();
+                            let (code, value) = match method.as_str() {
+                                "getUri" => (1, "http://localhost:11311".to_owned()),
+                                "lookupService" => {
+                                    counter.fetch_add(1, Ordering::SeqCst);
+                                    match state.lock().unwrap().clone() {
+                                        Some(uri) => (1, uri),
+                                        None => (-1, String::new()),
+                                    }
+                                }
+                                _ => panic!("Unexpected call to mock master: {method}"),
+                            };
+                            let body = serde_xmlrpc::response_to_string(
+                                vec![serde_xmlrpc::Value::Array(vec![
+                                    code.into(),
+                                    "".into(),
+                                    value.into(),
+                                ])]
+                                .into_iter(),
+                            )
+                            .unwrap();
+                            Ok::<_, Infallible>(Response::new(Body::from(body)))
+                        }
+                    }))
+                }
+            });
+            let server = hyper::server::Server::bind(&SocketAddr::from((Ipv4Addr::LOCALHOST, 0)))
+                .serve(make_svc);
+            let uri = format!("http://{}", server.local_addr());
+            Self {
+                uri,
+                service_uri,
+                lookups,
+                _task: tokio::spawn(async move { server.await.unwrap() }).into(),
+            }
+        }
+
+        fn set_service_uri(&self, uri: Option<&str>) {
+            *self.service_uri.lock().unwrap() = uri.map(str::to_owned);
+        }
+    }
+
+    /// Serves AddTwoInts calls, responding with an error when the sum is negative. Each
+    /// connection's header is sent on the returned channel.
+    async fn serve_add_two_ints() -> (
+        String,
+        tokio::sync::mpsc::UnboundedReceiver<Vec<u8>>,
+        abort_on_drop::ChildTask<()>,
+    ) {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).await.unwrap();
+        let uri = format!("rosrpc://{}", listener.local_addr().unwrap());
+        let (headers, received) = tokio::sync::mpsc::unbounded_channel();
+        let task = tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let header = tcpros::receive_frame(&mut stream).await.unwrap();
+                let _ = headers.send(header);
+                let response = tcpros::encode_fields(&[("callerid", "/adder")]).unwrap();
+                stream.write_all(&response).await.unwrap();
+
+                let request = tcpros::receive_frame(&mut stream).await.unwrap();
+                let request: AddTwoIntsRequest = serde_rosmsg::from_slice(&request).unwrap();
+                let sum = request.a + request.b;
+                if sum < 0 {
+                    stream.write_u8(0).await.unwrap();
+                    // Errors are sent as bare strings, with only the frame's length prefix
+                    let error = b"negative sums are not allowed";
+                    stream.write_u32_le(error.len() as u32).await.unwrap();
+                    stream.write_all(error).await.unwrap();
+                } else {
+                    stream.write_u8(1).await.unwrap();
+                    let response = serde_rosmsg::to_vec(&AddTwoIntsResponse { sum }).unwrap();
+                    stream.write_all(&response).await.unwrap();
+                }
+            }
+        });
+        (uri, received, task.into())
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn service_client_reports_server_errors() {
+        let (uri, mut headers, _server) = serve_add_two_ints().await;
+        let client = ServiceClient::<AddTwoInts>::new("/caller", "/add_two_ints", &uri);
+
+        let response = client.call(&AddTwoIntsRequest { a: 1, b: 2 }).await;
+        assert_eq!(response.unwrap(), AddTwoIntsResponse { sum: 3 });
+        let header = String::from_utf8_lossy(&headers.recv().await.unwrap()).into_owned();
+        for field in [
+            "callerid=/caller",
+            "service=/add_two_ints",
+            "md5sum=6a2e34150c00229791cc89ff309fff21",
+        ] {
+            assert!(header.contains(field), "{field} not in {header:?}");
+        }
+
+        match client.call(&AddTwoIntsRequest { a: 1, b: -2 }).await {
+            Err(ServiceError::Failed(message)) => {
+                assert_eq!(message, "negative sums are not allowed")
+            }
+            other => panic!("Expected the server's error, got {other:?}"),
+        }
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn wait_until_available_polls_until_service_is_registered() {
+        let master = MockMaster::new();
+        let nh = NodeHandle::new(&master.uri, "/service_proxy_test")
+            .await
+            .unwrap();
+        let proxy = nh
+            .service_proxy::<AddTwoInts>("/add_two_ints", Duration::from_secs(60))
+            .await
+            .unwrap();
+
+        let result = proxy.wait_until_available(Duration::from_millis(50)).await;
+        assert!(
+            matches!(result, Err(ServiceError::NotAvailable { .. })),
+            "{result:?}"
+        );
+
+        let (uri, _headers, _server) = serve_add_two_ints().await;
+        let registered = tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            master.set_service_uri(Some(&uri));
+            master
+        });
+        proxy
+            .wait_until_available(Duration::from_secs(2))
+            .await
+            .unwrap();
+        let master = registered.await.unwrap();
+
+        // The uri found while waiting is reused
+        let lookups = master.lookups.load(Ordering::SeqCst);
+        let response = proxy.call(&AddTwoIntsRequest { a: 2, b: 3 }).await;
+        assert_eq!(response.unwrap(), AddTwoIntsResponse { sum: 5 });
+        assert_eq!(master.lookups.load(Ordering::SeqCst), lookups);
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn call_looks_service_up_again_when_cached_uri_fails() {
+        let master = MockMaster::new();
+        let nh = NodeHandle::new(&master.uri, "/service_proxy_test")
+            .await
+            .unwrap();
+        let proxy = nh
+            .service_proxy::<AddTwoInts>("/add_two_ints", Duration::from_secs(60))
+            .await
+            .unwrap();
+
+        let (uri, _headers, server) = serve_add_two_ints().await;
+        master.set_service_uri(Some(&uri));
+        let response = proxy.call(&AddTwoIntsRequest { a: 1, b: 1 }).await;
+        assert_eq!(response.unwrap(), AddTwoIntsResponse { sum: 2 });
+        assert_eq!(master.lookups.load(Ordering::SeqCst), 1);
+
+        // The service restarts elsewhere, leaving the cached uri refusing connections
+        drop(server);
+        let (uri, _headers, _server) = serve_add_two_ints().await;
+        master.set_service_uri(Some(&uri));
+        let response = proxy.call(&AddTwoIntsRequest { a: 2, b: 2 }).await;
+        assert_eq!(response.unwrap(), AddTwoIntsResponse { sum: 4 });
+        assert_eq!(master.lookups.load(Ordering::SeqCst), 2);
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn service_definition_is_found_by_probing_server() {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).await.unwrap();
+        let uri = format!("rosrpc://{}", listener.local_addr().unwrap());
+        let (headers, mut received) = tokio::sync::mpsc::unbounded_channel();
+        let _server: abort_on_drop::ChildTask<()> = tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let header = tcpros::receive_frame(&mut stream).await.unwrap();
+                let _ = headers.send(String::from_utf8_lossy(&header).into_owned());
+                let response = tcpros::encode_fields(&[
+                    ("callerid", "/rosapi"),
+                    ("md5sum", "0d30b3f53a0fd5036523a7141e524ddf"),
+                    ("type", "rosapi/TopicType"),
+                ])
+                .unwrap();
+                stream.write_all(&response).await.unwrap();
+            }
+        })
+        .into();
+
+        let master = MockMaster::new();
+        master.set_service_uri(Some(&uri));
+        let nh = NodeHandle::new(&master.uri, "/probe_test").await.unwrap();
+        let registry = ServiceTypeRegistry::new(&[std::path::Path::new(
+            "../assets/ros1_common_interfaces/rosapi",
+        )])
+        .unwrap();
+        let definition = nh
+            .lookup_service_definition("/rosapi/topic_type", &registry)
+            .await
+            .unwrap();
+        assert_eq!(definition.service_type(), "rosapi/TopicType");
+        let header = received.recv().await.unwrap();
+        for field in ["probe=1", "md5sum=*", "service=/rosapi/topic_type"] {
+            assert!(header.contains(field), "{field} not in {header:?}");
+        }
+
+        let error = nh
+            .lookup_service_definition("/rosapi/topic_type", &ServiceTypeRegistry::default())
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(
+                error.downcast_ref::<ServiceError>(),
+                Some(ServiceError::UnknownType { service_type, .. }) if service_type == "rosapi/TopicType"
+            ),
+            "{error:?}"
+        );
+    }
+}
@@ -0,0 +1,221 @@
+use super::DynamicMessageDecoder;
+use roslibrust_codegen::{FieldInfo, MessageFile};
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::{Path, PathBuf},
+};
+
+/// Separates the definitions of nested types in a full message definition, as generated by
+/// genmsg and sent in the `message_definition` field of TCPROS connection headers
+const DEFINITION_SEPARATOR: &str =
+    "================================================================================";
+
+/// The request and response of a service type found by a [ServiceTypeRegistry]
+#[derive(Clone, Debug)]
+pub struct ServiceDefinition {
+    service_type: String,
+    md5sum: String,
+    request_fields: Vec<FieldInfo>,
+    response_fields: Vec<FieldInfo>,
+    request_definition: String,
+    response_definition: String,
+}
+
+impl ServiceDefinition {
+    /// The combination pkg_name/type_name of the service, e.g. `std_srvs/SetBool`
+    pub fn service_type(&self) -> &str {
+        &self.service_type
+    }
+
+    pub fn md5sum(&self) -> &str {
+        &self.md5sum
+    }
+
+    pub fn request_fields(&self) -> &[FieldInfo] {
+        &self.request_fields
+    }
+
+    pub fn response_fields(&self) -> &[FieldInfo] {
+        &self.response_fields
+    }
+
+    /// The full definition of the request, including the definitions of any message types it uses
+    /// in the format expected by [DynamicMessageDecoder::new]
+    pub fn request_definition(&self) -> &str {
+        &self.request_definition
+    }
+
+    /// The full definition of the response, see [ServiceDefinition::request_definition]
+    pub fn response_definition(&self) -> &str {
+        &self.response_definition
+    }
+
+    /// Creates a decoder for requests of this service, e.g. `std_srvs/SetBoolRequest`
+    pub fn request_decoder(&self) -> std::io::Result<DynamicMessageDecoder> {
+        DynamicMessageDecoder::new(
+            &format!("{}Request", self.service_type),
+            &self.request_definition,
+        )
+    }
+
+    /// Creates a decoder for responses of this service, e.g. `std_srvs/SetBoolResponse`
+    pub fn response_decoder(&self) -> std::io::Result<DynamicMessageDecoder> {
+        DynamicMessageDecoder::new(
+            &format!("{}Response", self.service_type),
+            &self.response_definition,
+        )
+    }
+}
+
+/// Definitions of every service type found in a set of ROS packages, for working with services
+/// whose types are only known at runtime, see
+/// [NodeHandle::lookup_service_definition](super::NodeHandle::lookup_service_definition).
+///
+/// ```no_run
+/// # fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+/// use roslibrust::ros1::ServiceTypeRegistry;
+/// let registry = ServiceTypeRegistry::new(&["/opt/ros/noetic/share".as_ref()])?;
+/// if let Some(set_bool) = registry.lookup("std_srvs/SetBool") {
+///     println!("{}", set_bool.request_definition());
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct ServiceTypeRegistry {
+    services: HashMap<String, ServiceDefinition>,
+}
+
+impl ServiceTypeRegistry {
+    /// Searches `search_paths` for ROS packages, like the codegen macros do, parsing the `.srv` and
+    /// `.msg` files found. Fails if a message used by any of them can't be found.
+    pub fn new(search_paths: &[&Path]) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        let search_paths: Vec<PathBuf> = search_paths.iter().map(|p| p.to_path_buf()).collect();
+        let (messages, services, _actions) =
+            roslibrust_codegen::find_and_parse_ros_messages(&search_paths)?;
+        let (messages, services) =
+            roslibrust_codegen::resolve_dependency_graph(messages, services)?;
+        let messages: BTreeMap<String, MessageFile> = messages
+            .into_iter()
+            .map(|msg| (msg.get_full_name(), msg))
+            .collect();
+
+        let services = services
+            .into_iter()
+            .map(|srv| {
+                let definition = ServiceDefinition {
+                    service_type: srv.get_full_name(),
+                    md5sum: srv.get_md5sum(),
+                    request_fields: srv.request().get_fields().to_vec(),
+                    response_fields: srv.response().get_fields().to_vec(),
+                    request_definition: full_definition(srv.request(), &messages),
+                    response_definition: full_definition(srv.response(), &messages),
+                };
+                (definition.service_type.clone(), definition)
+            })
+            .collect();
+        Ok(Self { services })
+    }
+
+    /// Finds the definition of a service type, e.g. `std_srvs/SetBool`
+    pub fn lookup(&self, service_type: &str) -> Option<&ServiceDefinition> {
+        self.services.get(service_type)
+    }
+
+    /// Every service type found, in no particular order
+    pub fn service_types(&self) -> impl Iterator<Item = &str> {
+        self.services.keys().map(String::as_str)
+    }
+}
+
+/// Appends the definitions of every message type `msg` depends on to its own definition, each
+/// introduced by a `MSG: pkg/Type` line
+fn full_definition(msg: &MessageFile, messages: &BTreeMap<String, MessageFile>) -> String {
+    let mut dependencies = vec![];
+    collect_dependencies(msg, messages, &mut dependencies);
+    let mut definition = msg.get_definition().trim().to_owned();
+    for dependency in dependencies {
+        definition.push_str(&format!(
+            "\n{DEFINITION_SEPARATOR}\nMSG: {}\n{}",
+            dependency.get_full_name(),
+            dependency.get_definition().trim()
+        ));
+    }
+    definition
+}
+
+/// Collects the message types used by `msg` depth first, each only once
+fn collect_dependencies<'a>(
+    msg: &MessageFile,
+    messages: &'a BTreeMap<String, MessageFile>,
+    dependencies: &mut Vec<&'a MessageFile>,
+) {
+    for field in msg.get_fields() {
+        // Only primitive types have no package
+        if field.field_type.package_name.is_none() {
+            continue;
+        }
+        let Some(dependency) = messages.get(&field.get_full_name()) else {
+            continue;
+        };
+        if dependencies
+            .iter()
+            .any(|known| std::ptr::eq(*known, dependency))
+        {
+            continue;
+        }
+        dependencies.push(dependency);
+        collect_dependencies(dependency, messages, dependencies);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::ServiceTypeRegistry;
+    use crate::ros1::DynamicValue;
+    use std::path::Path;
+
+    fn rosapi() -> ServiceTypeRegistry {
+        ServiceTypeRegistry::new(&[Path::new("../assets/ros1_common_interfaces/rosapi")]).unwrap()
+    }
+
+    #[test_log::test]
+    fn services_are_found_by_type() {
+        let registry = rosapi();
+        let topic_type = registry.lookup("rosapi/TopicType").unwrap();
+        assert_eq!(topic_type.service_type(), "rosapi/TopicType");
+        assert_eq!(topic_type.md5sum(), "0d30b3f53a0fd5036523a7141e524ddf");
+        assert_eq!(topic_type.request_fields()[0].field_name, "topic");
+        assert_eq!(topic_type.response_fields()[0].field_name, "type");
+        assert_eq!(topic_type.request_definition(), "string topic");
+        assert!(registry.lookup("rosapi/Missing").is_none());
+        assert!(registry.service_types().any(|srv| srv == "rosapi/Nodes"));
+    }
+
+    #[test_log::test]
+    fn definitions_include_nested_types() {
+        let registry = rosapi();
+        let details = registry.lookup("rosapi/MessageDetails").unwrap();
+        let definition = details.response_definition();
+        assert!(definition.starts_with("TypeDef[] typedefs\n====="));
+        assert!(definition.contains("\nMSG: rosapi/TypeDef\nstring type\n"));
+
+        // A response holding one TypeDef with type "a" and otherwise empty arrays
+        let mut data = vec![];
+        data.extend(1u32.to_le_bytes());
+        data.extend(1u32.to_le_bytes());
+        data.push(b'a');
+        for _ in 0..6 {
+            data.extend(0u32.to_le_bytes());
+        }
+        let response = details.response_decoder().unwrap().decode(&data).unwrap();
+        assert_eq!(response.msg_type, "rosapi/MessageDetailsResponse");
+        let Some(DynamicValue::Array(typedefs)) = response.get("typedefs") else {
+            panic!("Expected typedefs array in {response:?}");
+        };
+        let DynamicValue::Message(typedef) = &typedefs[0] else {
+            panic!("Expected a TypeDef in {typedefs:?}");
+        };
+        assert_eq!(typedef.get("type"), Some(&DynamicValue::String("a".into())));
+    }
+}
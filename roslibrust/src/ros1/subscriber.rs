@@ -1,38 +1,145 @@
-use crate::ros1::tcpros::ConnectionHeader;
+use crate::ros1::{
+    protocol::{ProtocolOffer, SelectedProtocol},
+    rate_stats::{RateTracker, DEFAULT_RATE_WINDOW},
+    tcpros::{self, Compression, ConnectionHeader, TcprosError, TruncatedMessage},
+    BufferPool, DynamicMessage, DynamicMessageDecoder, PooledBuffer, RateStats,
+};
 use abort_on_drop::ChildTask;
 use roslibrust_codegen::RosMessageType;
-use std::{marker::PhantomData, sync::Arc};
+use std::{marker::PhantomData, sync::Arc, time::Instant};
 use tokio::{
-    io::{AsyncReadExt, AsyncWriteExt},
+    io::AsyncWriteExt,
     net::TcpStream,
-    sync::{broadcast, RwLock},
+    sync::{broadcast, watch, RwLock},
 };
 
+/// A raw message received by a subscription, shared between all of the topic's subscribers
+pub(crate) type RawMessage = Arc<PooledBuffer>;
+
+/// Options which control how a subscription receives messages, see
+/// [NodeHandle::subscribe_with_options](crate::ros1::NodeHandle::subscribe_with_options)
+#[derive(Clone, Debug, Default)]
+pub struct SubscriberOptions {
+    /// Pool to read incoming messages into rather than allocating a buffer for each message,
+    /// which is worthwhile for high rate topics. Messages received compressed are still
+    /// decompressed into a new buffer.
+    pub buffer_pool: Option<BufferPool>,
+    /// Whether to ask publishers to disable Nagle's algorithm for this subscription, None uses the
+    /// node's [NodeConfig::tcp_nodelay](crate::ros1::NodeConfig::tcp_nodelay)
+    pub tcp_nodelay: Option<bool>,
+    /// Number of most recent message arrivals [Subscriber::rate_stats] are computed over,
+    /// defaults to 100
+    pub rate_window: Option<usize>,
+    /// Whether to acknowledge each message received to publishers which support the
+    /// `roslibrust_ack` connection header extension, which only roslibrust publishers do, so
+    /// their [Publisher::publish_and_wait](crate::ros1::Publisher::publish_and_wait) can confirm
+    /// delivery. Defaults to false, leaving the connection plain TCPROS.
+    pub acknowledge_delivery: bool,
+    /// Longest message accepted from a publisher in bytes, before or after decompression, the
+    /// connection is closed if one sends a longer message. Defaults to [DEFAULT_MAX_FRAME_LENGTH](crate::ros1::DEFAULT_MAX_FRAME_LENGTH).
+    pub max_message_length: Option<usize>,
+}
+
 pub struct Subscriber<T> {
-    receiver: broadcast::Receiver<Vec<u8>>,
+    receiver: broadcast::Receiver<RawMessage>,
+    rate: RateTracker,
     _phantom: PhantomData<T>,
 }
 
 impl<T: RosMessageType> Subscriber<T> {
-    pub(crate) fn new(receiver: broadcast::Receiver<Vec<u8>>) -> Self {
+    pub(crate) fn new(receiver: broadcast::Receiver<RawMessage>, rate: RateTracker) -> Self {
         Self {
             receiver,
+            rate,
             _phantom: PhantomData,
         }
     }
 
+    /// How often messages have recently been arriving on the topic, for checking a sensor is
+    /// publishing at the expected rate.
+    ///
+    /// Arrivals are recorded as messages are read from publishers, so the statistics aren't
+    /// affected by how promptly [Subscriber::next] is called, and are shared by every subscriber
+    /// to the topic within this node.
+    pub fn rate_stats(&self) -> RateStats {
+        self.rate.stats()
+    }
+
     pub async fn next(&mut self) -> Result<T, Box<dyn std::error::Error>> {
         let data = self.receiver.recv().await.map_err(|err| Box::new(err))?;
         Ok(serde_rosmsg::from_slice(&data[..]).map_err(|err| Box::new(err))?)
     }
 }
 
+/// Subscribes to a topic without knowing its type at compile time, see
+/// [NodeHandle::subscribe_dynamic](crate::ros1::NodeHandle::subscribe_dynamic).
+///
+/// Messages are decoded using the message definition the publisher sends when connecting, so
+/// publishers must send the full definition including nested types, as roscpp and rospy do.
+pub struct DynamicSubscriber {
+    receiver: broadcast::Receiver<RawMessage>,
+    publisher_header: watch::Receiver<Option<ConnectionHeader>>,
+    rate: RateTracker,
+    decoder: Option<DynamicMessageDecoder>,
+}
+
+impl DynamicSubscriber {
+    pub(crate) fn new(
+        receiver: broadcast::Receiver<RawMessage>,
+        publisher_header: watch::Receiver<Option<ConnectionHeader>>,
+        rate: RateTracker,
+    ) -> Self {
+        Self {
+            receiver,
+            publisher_header,
+            rate,
+            decoder: None,
+        }
+    }
+
+    /// How often messages have recently been arriving on the topic, see [Subscriber::rate_stats]
+    pub fn rate_stats(&self) -> RateStats {
+        self.rate.stats()
+    }
+
+    pub async fn next(&mut self) -> Result<DynamicMessage, Box<dyn std::error::Error>> {
+        let data = self.receiver.recv().await?;
+        // A publisher's header is always published before any of its messages
+        if self.publisher_header.has_changed().unwrap_or(false) || self.decoder.is_none() {
+            let header = self.publisher_header.borrow_and_update().clone();
+            let header = header.ok_or_else(|| {
+                std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    "Received a message before the publisher's connection header",
+                )
+            })?;
+            let is_current = self
+                .decoder
+                .as_ref()
+                .is_some_and(|decoder| decoder.msg_type() == header.topic_type);
+            if !is_current {
+                self.decoder = Some(DynamicMessageDecoder::new(
+                    &header.topic_type,
+                    &header.msg_definition,
+                )?);
+            }
+        }
+        let decoder = self.decoder.as_ref().expect("Decoder was just created");
+        Ok(decoder.decode(data.get(4..).unwrap_or_default())?)
+    }
+}
+
 pub struct Subscription {
     subscription_tasks: Vec<ChildTask<()>>,
-    _msg_receiver: broadcast::Receiver<Vec<u8>>,
-    msg_sender: broadcast::Sender<Vec<u8>>,
+    _msg_receiver: broadcast::Receiver<RawMessage>,
+    msg_sender: broadcast::Sender<RawMessage>,
     connection_header: ConnectionHeader,
+    buffer_pool: Option<BufferPool>,
     known_publishers: Arc<RwLock<Vec<String>>>,
+    /// The connection header of the most recently connected publisher
+    publisher_header: Arc<watch::Sender<Option<ConnectionHeader>>>,
+    rate: RateTracker,
+    max_message_length: usize,
 }
 
 impl Subscription {
@@ -43,6 +150,7 @@ impl Subscription {
         queue_size: usize,
         msg_definition: String,
         md5sum: String,
+        options: SubscriberOptions,
     ) -> Self {
         let (sender, receiver) = broadcast::channel(queue_size);
         let connection_header = ConnectionHeader {
@@ -52,7 +160,11 @@ impl Subscription {
             md5sum,
             topic: topic_name.to_owned(),
             topic_type: topic_type.to_owned(),
-            tcp_nodelay: false,
+            tcp_nodelay: options.tcp_nodelay.unwrap_or_default(),
+            // Only takes effect if the publisher is also roslibrust
+            roslibrust_ack: options.acknowledge_delivery,
+            compression: vec![Compression::Lz4, Compression::Zstd],
+            error: None,
         };
 
         Self {
@@ -60,7 +172,13 @@ impl Subscription {
             _msg_receiver: receiver,
             msg_sender: sender,
             connection_header,
+            buffer_pool: options.buffer_pool,
             known_publishers: Arc::new(RwLock::new(vec![])),
+            publisher_header: Arc::new(watch::channel(None).0),
+            rate: RateTracker::new(options.rate_window.unwrap_or(DEFAULT_RATE_WINDOW)),
+            max_message_length: options
+                .max_message_length
+                .unwrap_or(tcpros::DEFAULT_MAX_FRAME_LENGTH),
         }
     }
 
@@ -68,10 +186,18 @@ impl Subscription {
         self.connection_header.topic_type.as_str()
     }
 
-    pub fn get_receiver(&self) -> broadcast::Receiver<Vec<u8>> {
+    pub fn get_receiver(&self) -> broadcast::Receiver<RawMessage> {
         self.msg_sender.subscribe()
     }
 
+    pub fn get_publisher_header(&self) -> watch::Receiver<Option<ConnectionHeader>> {
+        self.publisher_header.subscribe()
+    }
+
+    pub(crate) fn get_rate_tracker(&self) -> RateTracker {
+        self.rate.clone()
+    }
+
     pub async fn add_publisher_source(
         &mut self,
         publisher_uri: &str,
@@ -91,35 +217,36 @@ impl Subscription {
             let connection_header = self.connection_header.clone();
             let sender = self.msg_sender.clone();
             let publisher_list = self.known_publishers.clone();
+            let publisher_header = self.publisher_header.clone();
             let publisher_uri = publisher_uri.to_owned();
+            let buffer_pool = self.buffer_pool.clone();
+            let rate = self.rate.clone();
+            let max_message_length = self.max_message_length;
 
             let handle = tokio::spawn(async move {
-                if let Ok(mut stream) = establish_publisher_connection(
+                let connection = establish_publisher_connection(
                     &node_name,
                     &topic_name,
                     &publisher_uri,
                     connection_header,
                 )
-                .await
-                {
-                    publisher_list.write().await.push(publisher_uri.to_owned());
-                    // Repeatedly read from the stream until its dry
-                    let mut read_buffer = Vec::with_capacity(4 * 1024);
-                    loop {
-                        if let Ok(bytes_read) = stream.read_buf(&mut read_buffer).await {
-                            if bytes_read == 0 {
-                                log::debug!("Got a message with 0 bytes, probably an EOF, closing connection");
-                                break;
-                            }
-                            log::debug!("Read {bytes_read} bytes from the publisher connection");
-                            if let Err(err) = sender.send(Vec::from(&read_buffer[..bytes_read])) {
-                                log::error!("Unable to send message data due to dropped channel, closing connection: {err}");
-                                break;
-                            }
-                            read_buffer.clear();
-                        } else {
-                            log::warn!("Got an error reading from the publisher connection on topic {topic_name}, closing");
-                        }
+                .await;
+                match connection {
+                    Err(err) => log::error!(
+                        "Failed to connect to publisher {publisher_uri} for topic {topic_name}: {err}"
+                    ),
+                    Ok(connection) => {
+                        publisher_list.write().await.push(publisher_uri.to_owned());
+                        receive_messages(
+                            connection,
+                            &topic_name,
+                            buffer_pool,
+                            sender,
+                            publisher_header,
+                            rate,
+                            max_message_length,
+                        )
+                        .await;
                     }
                 }
             });
@@ -130,55 +257,176 @@ impl Subscription {
     }
 }
 
+/// Repeatedly reads messages from a publisher connection until it's dry, sending each to the
+/// subscription's subscribers
+async fn receive_messages(
+    connection: PublisherConnection,
+    topic_name: &str,
+    buffer_pool: Option<BufferPool>,
+    sender: broadcast::Sender<RawMessage>,
+    publisher_header: Arc<watch::Sender<Option<ConnectionHeader>>>,
+    rate: RateTracker,
+    max_message_length: usize,
+) {
+    let PublisherConnection {
+        mut stream,
+        header,
+        send_acks,
+        compression,
+    } = connection;
+    let publisher = header.caller_id.clone();
+    publisher_header.send_replace(Some(header));
+    let mut seq: u32 = 0;
+    loop {
+        let mut frame = match &buffer_pool {
+            Some(pool) => pool.get(),
+            None => PooledBuffer::default(),
+        };
+        let message =
+            match tcpros::receive_frame_into(&mut stream, &mut frame, max_message_length).await {
+                Ok(()) => match compression {
+                    Compression::None => Ok(frame),
+                    compression => compression
+                        .decompress(&frame[4..], max_message_length)
+                        .map(PooledBuffer::from),
+                },
+                Err(err) => Err(err),
+            };
+        match message {
+            Ok(message) => {
+                rate.record(Instant::now());
+                log::debug!("Read {} bytes from the publisher connection", message.len());
+                if let Err(err) = sender.send(Arc::new(message)) {
+                    log::error!("Unable to send message data due to dropped channel, closing connection: {err}");
+                    break;
+                }
+                if send_acks {
+                    seq = seq.wrapping_add(1);
+                    if let Err(err) = stream.write_all(&seq.to_le_bytes()).await {
+                        log::warn!(
+                            "Unable to acknowledge message on topic {topic_name}, closing: {err}"
+                        );
+                        break;
+                    }
+                }
+            }
+            Err(err) if TruncatedMessage::from_io_error(&err).is_some() => {
+                log::warn!(
+                    "Publisher {publisher} disconnected mid-message on topic {topic_name}, the partial message was discarded: {err}"
+                );
+                break;
+            }
+            Err(err) if err.kind() == std::io::ErrorKind::UnexpectedEof => {
+                log::debug!(
+                    "Publisher connection on topic {topic_name} reached EOF, closing connection"
+                );
+                break;
+            }
+            Err(err) => {
+                log::warn!("Got an error reading from the publisher connection on topic {topic_name}, closing: {err}");
+                break;
+            }
+        }
+    }
+}
+
+/// A connection to a publisher along with the roslibrust extensions negotiated for it
+struct PublisherConnection {
+    stream: TcpStream,
+    header: ConnectionHeader,
+    send_acks: bool,
+    compression: Compression,
+}
+
 async fn establish_publisher_connection(
     node_name: &str,
     topic_name: &str,
     publisher_uri: &str,
     conn_header: ConnectionHeader,
-) -> Result<TcpStream, std::io::Error> {
-    let publisher_channel_uri = send_topic_request(node_name, topic_name, publisher_uri).await?;
-    let mut stream = TcpStream::connect(publisher_channel_uri).await?;
+) -> Result<PublisherConnection, TcprosError> {
+    // Only TCPROS can be received, so it is the only protocol offered
+    let offers = [ProtocolOffer::Tcpros];
+    let selected = send_topic_request(node_name, topic_name, publisher_uri, &offers).await?;
+    let SelectedProtocol::Tcpros { host, port } = selected else {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::Unsupported,
+            format!("publisher selected {selected:?}, which wasn't offered"),
+        )
+        .into());
+    };
+    log::debug!(
+        "Got a TCPROS publisher endpoint at {}",
+        tcpros::host_port(&host, port)
+    );
+    // Connecting with the host and port apart also resolves IPv6 hosts with a zone ID
+    let stream = TcpStream::connect((host.as_str(), port)).await?;
+    // Our own acknowledgements should be just as prompt
+    stream.set_nodelay(conn_header.tcp_nodelay)?;
+    exchange_connection_headers(stream, conn_header).await
+}
 
+/// Performs the subscriber's half of the TCPROS handshake over a newly opened connection
+async fn exchange_connection_headers(
+    mut stream: TcpStream,
+    conn_header: ConnectionHeader,
+) -> Result<PublisherConnection, TcprosError> {
     let conn_header_bytes = conn_header.to_bytes(true)?;
     stream.write_all(&conn_header_bytes[..]).await?;
 
-    let mut responded_header_bytes = Vec::with_capacity(16 * 1024);
-    let bytes = stream.read_buf(&mut responded_header_bytes).await?;
-    if let Ok(responded_header) = ConnectionHeader::from_bytes(&responded_header_bytes[..bytes]) {
-        if conn_header.md5sum == responded_header.md5sum {
-            log::debug!(
-                "Established connection with publisher for {}",
-                conn_header.topic
-            );
-            Ok(stream)
-        } else {
-            log::error!(
-                "Tried to subscribe to {}, but md5sums do not match. Expected {}, received {}",
-                topic_name,
-                conn_header.md5sum,
-                responded_header.md5sum
-            );
-            Err(std::io::ErrorKind::InvalidData)
-        }
-    } else {
+    let responded_header_bytes = tcpros::receive_frame(&mut stream).await?;
+    let Ok(responded_header) = ConnectionHeader::from_bytes(&responded_header_bytes) else {
         log::error!("Could not parse connection header data sent by publisher");
-        Err(std::io::ErrorKind::InvalidData)
+        return Err(std::io::Error::from(std::io::ErrorKind::InvalidData).into());
+    };
+    if let Some(reason) = responded_header.error {
+        return Err(TcprosError::ConnectionRefused(reason));
+    }
+    // A md5sum of "*" subscribes to whatever type the publisher is sending
+    if conn_header.md5sum == "*" || conn_header.md5sum == responded_header.md5sum {
+        log::debug!(
+            "Established connection with publisher for {}",
+            conn_header.topic
+        );
+        // Acknowledgements are only sent if both sides agreed to them
+        let send_acks = conn_header.roslibrust_ack && responded_header.roslibrust_ack;
+        // Publishers only ever select one of the formats we offered
+        let compression = responded_header
+            .compression
+            .first()
+            .copied()
+            .filter(|compression| conn_header.compression.contains(compression))
+            .unwrap_or_default();
+        Ok(PublisherConnection {
+            stream,
+            header: responded_header,
+            send_acks,
+            compression,
+        })
+    } else {
+        log::error!(
+            "Tried to subscribe to {}, but md5sums do not match. Expected {}, received {}",
+            conn_header.topic,
+            conn_header.md5sum,
+            responded_header.md5sum
+        );
+        Err(std::io::Error::from(std::io::ErrorKind::InvalidData).into())
     }
-    .map_err(std::io::Error::from)
 }
 
+/// Asks the publisher at `publisher_uri` which of `protocols` it will send `topic_name` over
 async fn send_topic_request(
     node_name: &str,
     topic_name: &str,
     publisher_uri: &str,
-) -> Result<String, std::io::Error> {
+    protocols: &[ProtocolOffer],
+) -> Result<SelectedProtocol, std::io::Error> {
     let xmlrpc_client = reqwest::Client::new();
     let body = serde_xmlrpc::request_to_string(
         "requestTopic",
         vec![
             node_name.into(),
             topic_name.into(),
-            serde_xmlrpc::Value::Array(vec![serde_xmlrpc::Value::Array(vec!["TCPROS".into()])]),
+            serde_xmlrpc::Value::Array(protocols.iter().map(ProtocolOffer::to_xmlrpc).collect()),
         ],
     )
     .unwrap();
@@ -194,19 +442,10 @@ async fn send_topic_request(
         })?;
     if response.status().is_success() {
         if let Ok(response_data) = response.text().await {
-            if let Ok((_code, _description, (protocol, hostname, port))) =
-                serde_xmlrpc::response_from_str::<(i8, String, (String, String, u16))>(
-                    &response_data,
-                )
+            if let Ok((_code, _description, selected)) =
+                serde_xmlrpc::response_from_str::<(i8, String, SelectedProtocol)>(&response_data)
             {
-                if protocol == "TCPROS" {
-                    let tcpros_endpoint = format!("{hostname}:{port}");
-                    log::debug!("Got a TCPROS publisher endpoint at {tcpros_endpoint}");
-                    Ok(tcpros_endpoint)
-                } else {
-                    log::error!("Got unsupported protocol {protocol}");
-                    Err(std::io::ErrorKind::Unsupported.into())
-                }
+                Ok(selected)
             } else {
                 log::error!("Failed to deserialize requestTopic response {response_data}");
                 Err(std::io::ErrorKind::InvalidData.into())
@@ -222,3 +461,272 @@ async fn send_topic_request(
         Err(std::io::ErrorKind::ConnectionRefused.into())
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::{
+        establish_publisher_connection, exchange_connection_headers, receive_messages,
+        PublisherConnection, RateTracker, Subscriber, SubscriberOptions, Subscription,
+        DEFAULT_RATE_WINDOW,
+    };
+    use crate::ros1::{
+        protocol::SelectedProtocol,
+        publisher::{AdvertiseOptions, Publication},
+        tcpros::{Compression, ConnectionHeader, TcprosError, DEFAULT_MAX_FRAME_LENGTH},
+        BufferPool,
+    };
+    use std::{net::Ipv4Addr, sync::Arc, time::Duration};
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::{TcpListener, TcpStream},
+        sync::{broadcast, watch},
+    };
+
+    #[test_log::test(tokio::test)]
+    async fn pooled_buffers_are_reused_for_later_messages() {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).await.unwrap();
+        let stream = TcpStream::connect(listener.local_addr().unwrap())
+            .await
+            .unwrap();
+        let (mut publisher, _) = listener.accept().await.unwrap();
+
+        let pool = BufferPool::new(4);
+        // A single slot, so each message leaves the queue as soon as the next one arrives
+        let (sender, mut receiver) = broadcast::channel(1);
+        let connection = PublisherConnection {
+            stream,
+            header: ConnectionHeader::default(),
+            send_acks: false,
+            compression: Compression::None,
+        };
+        let _task = abort_on_drop::ChildTask::from(tokio::spawn(receive_messages(
+            connection,
+            "/chatter",
+            Some(pool.clone()),
+            sender,
+            Arc::new(watch::channel(None).0),
+            RateTracker::new(DEFAULT_RATE_WINDOW),
+            DEFAULT_MAX_FRAME_LENGTH,
+        )));
+
+        let mut allocations = vec![];
+        for data in [b"one", b"two", b"six"] {
+            publisher.write_all(&3u32.to_le_bytes()).await.unwrap();
+            publisher.write_all(data).await.unwrap();
+            let message = receiver.recv().await.unwrap();
+            assert_eq!(&message[4..], data);
+            allocations.push(message.as_ptr());
+        }
+        // The first buffer was returned when the second message replaced it, and reused
+        assert_eq!(allocations[2], allocations[0]);
+        assert_eq!(pool.available(), 1);
+    }
+
+    #[derive(::serde::Deserialize, ::serde::Serialize, Debug, Clone, PartialEq)]
+    struct TestMsg {
+        data: String,
+    }
+
+    impl roslibrust_codegen::RosMessageType for TestMsg {
+        const ROS_TYPE_NAME: &'static str = "std_msgs/String";
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn rate_stats_measure_publish_rate() {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).await.unwrap();
+        let stream = TcpStream::connect(listener.local_addr().unwrap())
+            .await
+            .unwrap();
+        let (mut publisher, _) = listener.accept().await.unwrap();
+
+        let (sender, mut receiver) = broadcast::channel(16);
+        let connection = PublisherConnection {
+            stream,
+            header: ConnectionHeader::default(),
+            send_acks: false,
+            compression: Compression::None,
+        };
+        let rate = RateTracker::new(DEFAULT_RATE_WINDOW);
+        let subscriber = Subscriber::<TestMsg>::new(receiver.resubscribe(), rate.clone());
+        let _task = abort_on_drop::ChildTask::from(tokio::spawn(receive_messages(
+            connection,
+            "/chatter",
+            None,
+            sender,
+            Arc::new(watch::channel(None).0),
+            rate,
+            DEFAULT_MAX_FRAME_LENGTH,
+        )));
+
+        // Publish at 10Hz for a second
+        let mut interval = tokio::time::interval(Duration::from_millis(100));
+        for _ in 0..11 {
+            interval.tick().await;
+            // A std_msgs/String holding an empty string
+            publisher
+                .write_all(&[4, 0, 0, 0, 0, 0, 0, 0])
+                .await
+                .unwrap();
+            receiver.recv().await.unwrap();
+        }
+        let stats = subscriber.rate_stats();
+        assert_eq!(stats.samples(), 10);
+        assert!((stats.mean_hz() - 10.0).abs() < 0.5, "{stats:?}");
+        assert!(
+            stats.min_interval() <= Duration::from_millis(100),
+            "{stats:?}"
+        );
+        assert!(
+            stats.max_interval() >= Duration::from_millis(100),
+            "{stats:?}"
+        );
+    }
+
+    #[test_log::test]
+    fn subscriber_header_requests_tcp_nodelay() {
+        let subscription = |tcp_nodelay| {
+            let options = SubscriberOptions {
+                tcp_nodelay,
+                ..Default::default()
+            };
+            let subscription = Subscription::new(
+                "/listener",
+                "/cmd_vel",
+                "std_msgs/String",
+                1,
+                "string data".to_owned(),
+                "992ce8a1687cec8c8bd883ec73ca41d1".to_owned(),
+                options,
+            );
+            subscription.connection_header.tcp_nodelay
+        };
+        assert!(subscription(Some(true)));
+        assert!(!subscription(Some(false)));
+        // The node fills in its default before creating subscriptions
+        assert!(!subscription(None));
+    }
+
+    #[test_log::test]
+    fn subscriber_header_offers_acks_only_when_asked() {
+        let subscription = |acknowledge_delivery| {
+            let options = SubscriberOptions {
+                acknowledge_delivery,
+                ..Default::default()
+            };
+            let subscription = Subscription::new(
+                "/listener",
+                "/chatter",
+                "std_msgs/String",
+                1,
+                "string data".to_owned(),
+                "992ce8a1687cec8c8bd883ec73ca41d1".to_owned(),
+                options,
+            );
+            subscription.connection_header.roslibrust_ack
+        };
+        assert!(!subscription(false));
+        assert!(subscription(true));
+        assert!(!SubscriberOptions::default().acknowledge_delivery);
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn refused_connection_reports_reason() {
+        let publication = Publication::new(
+            "/talker",
+            Ipv4Addr::LOCALHOST.into(),
+            "string data",
+            "992ce8a1687cec8c8bd883ec73ca41d1",
+            "std_msgs/String",
+            AdvertiseOptions::new("/chatter", 10),
+        )
+        .await
+        .unwrap();
+        let stream = TcpStream::connect((Ipv4Addr::LOCALHOST, publication.port()))
+            .await
+            .unwrap();
+        let header = ConnectionHeader {
+            caller_id: "/listener".to_owned(),
+            latching: false,
+            msg_definition: "int32 data".to_owned(),
+            md5sum: "da5909fbe378aeaf85e547e830cc1bb7".to_owned(),
+            topic: "/chatter".to_owned(),
+            topic_type: "std_msgs/Int32".to_owned(),
+            tcp_nodelay: false,
+            roslibrust_ack: false,
+            compression: vec![],
+            error: None,
+        };
+
+        match exchange_connection_headers(stream, header).await {
+            Err(TcprosError::ConnectionRefused(reason)) => {
+                assert!(reason.contains("std_msgs/Int32"), "{reason}");
+                assert!(reason.contains("std_msgs/String"), "{reason}");
+            }
+            Err(err) => panic!("Expected connection to be refused, got {err}"),
+            Ok(_) => panic!("Expected connection to be refused"),
+        }
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn only_tcpros_is_offered_to_publishers() {
+        // Stands in for the publisher's xmlrpc server, which selects UDPROS regardless
+        let xmlrpc = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).await.unwrap();
+        let publisher_uri = format!("http://{}", xmlrpc.local_addr().unwrap());
+        let server = tokio::spawn(async move {
+            let mut requests = vec![];
+            {
+                let (mut stream, _) = xmlrpc.accept().await.unwrap();
+                let mut request = vec![];
+                while !String::from_utf8_lossy(&request).contains("</methodCall>") {
+                    let mut buf = [0; 4096];
+                    let read = stream.read(&mut buf).await.unwrap();
+                    request.extend_from_slice(&buf[..read]);
+                }
+                let request = String::from_utf8(request).unwrap();
+                let selected = SelectedProtocol::Udpros {
+                    host: "127.0.0.1".to_owned(),
+                    port: 41000,
+                    connection_id: 1,
+                    max_datagram_size: 1500,
+                    header: vec![],
+                };
+                let body = serde_xmlrpc::response_to_string(
+                    vec![serde_xmlrpc::Value::Array(vec![
+                        1.into(),
+                        "ready".into(),
+                        selected.to_xmlrpc(),
+                    ])]
+                    .into_iter(),
+                )
+                .unwrap();
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: text/xml\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                );
+                stream.write_all(response.as_bytes()).await.unwrap();
+                requests.push(request);
+            }
+            requests
+        });
+
+        let header = Subscription::new(
+            "/listener",
+            "/chatter",
+            "std_msgs/String",
+            1,
+            "string data".to_owned(),
+            "992ce8a1687cec8c8bd883ec73ca41d1".to_owned(),
+            SubscriberOptions::default(),
+        )
+        .connection_header;
+        // Selecting a protocol that wasn't offered fails the connection, rather than asking again
+        // and leaving the publisher sending to a transport nobody reads
+        let connection =
+            establish_publisher_connection("/listener", "/chatter", &publisher_uri, header).await;
+        assert!(connection.is_err());
+
+        let requests = server.await.unwrap();
+        assert_eq!(requests.len(), 1);
+        assert!(!requests[0].contains("UDPROS") && requests[0].contains("TCPROS"));
+    }
+}
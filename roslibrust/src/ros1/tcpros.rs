@@ -1,9 +1,29 @@
 use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
-use std::io::{Cursor, Read, Write};
+use std::{
+    collections::BTreeMap,
+    io::{Cursor, Read, Write},
+};
+use tokio::io::AsyncRead;
+
+/// Name of the TCPROS transport in the `[protocol, host, port]` lists exchanged by `requestTopic`.
+///
+/// It is the only transport roslibrust supports, see [ProtocolOffer](crate::ros1::ProtocolOffer).
+/// Publishers listen on a port assigned by the OS when they are created, which is the port given
+/// to subscribers in the negotiation.
+pub const TCPROS_PROTOCOL: &str = "TCPROS";
+
+/// Fields sent when connecting to a service, which [ConnectionHeader] doesn't represent
+const SERVICE_FIELDS: [&str; 4] = ["service=", "persistent=", "request_type=", "response_type="];
 
 // Implementation of ConnectionHeader is based off of ROS documentation here:
 // wiki.ros.org/ROS/Connection%20Header
-#[derive(Clone, Debug)]
+/// Fields of a TCPROS connection header.
+///
+/// Serializes with serde using the struct's field names and native types, e.g. `latching` is a json
+/// `true` rather than the `"1"` sent on the wire, which makes it convenient for structured logging.
+/// Missing fields deserialize to their defaults.
+#[derive(Clone, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
 pub struct ConnectionHeader {
     pub caller_id: String,
     pub latching: bool,
@@ -12,13 +32,179 @@ pub struct ConnectionHeader {
     pub topic: String,
     pub topic_type: String,
     pub tcp_nodelay: bool,
+    /// roslibrust specific extension: when set on both sides of a connection the subscriber
+    /// writes back the sequence number of every message it receives, see
+    /// [Publisher::publish_and_wait](crate::ros1::Publisher::publish_and_wait).
+    /// Other ROS implementations ignore the field, so acknowledgements are never negotiated with
+    /// them.
+    pub roslibrust_ack: bool,
+    /// roslibrust specific extension: subscribers list every compression format they can decode,
+    /// publishers respond with the single format they will use. Empty means uncompressed, which is
+    /// always the case when talking to other ROS implementations.
+    pub compression: Vec<Compression>,
+    /// Set by a node refusing a connection, giving the reason it was refused. Per the TCPROS spec
+    /// a header with an error contains no other fields.
+    pub error: Option<String>,
+}
+
+/// One field of a comparison between two connection headers, see [ConnectionHeader::diff].
+/// Keys are the field names used on the wire, e.g. `callerid` or `type`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum HeaderDiff {
+    Equal {
+        key: String,
+    },
+    Changed {
+        key: String,
+        old: String,
+        new: String,
+    },
+    /// The field is only present in the header `diff` was called on
+    OnlyInSelf {
+        key: String,
+        value: String,
+    },
+    /// The field is only present in the header passed to `diff`
+    OnlyInOther {
+        key: String,
+        value: String,
+    },
+}
+
+/// Errors establishing a TCPROS connection
+#[derive(thiserror::Error, Debug)]
+pub enum TcprosError {
+    #[error("Connection refused by the remote node: {0}")]
+    ConnectionRefused(String),
+    #[error("TCPROS connection failed: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+/// A connection which ended partway through a frame, as when a publisher dies while sending a
+/// message. Frames are read as an [std::io::Error] of kind
+/// [UnexpectedEof](std::io::ErrorKind::UnexpectedEof) both when the connection ends cleanly
+/// between frames and when it is truncated, with this as the error's inner error only when
+/// truncated, see [TruncatedMessage::from_io_error].
+#[derive(thiserror::Error, Clone, Copy, Debug, PartialEq, Eq)]
+#[error("Connection ended partway through a message, after {received} of its {expected} bytes")]
+pub struct TruncatedMessage {
+    /// Length of the frame including its 4 byte length prefix, or just the prefix's 4 bytes if
+    /// the connection ended before the whole prefix arrived
+    pub expected: usize,
+    /// Bytes of the frame received before the connection ended
+    pub received: usize,
+}
+
+impl TruncatedMessage {
+    /// The truncation `err` was caused by, None if it wasn't caused by a truncated frame
+    pub fn from_io_error(err: &std::io::Error) -> Option<&TruncatedMessage> {
+        err.get_ref()?.downcast_ref()
+    }
+
+    fn into_io_error(self) -> std::io::Error {
+        std::io::Error::new(std::io::ErrorKind::UnexpectedEof, self)
+    }
+}
+
+/// Compression applied to each message sent over a TCPROS connection.
+///
+/// Compression is negotiated per connection, so subscribers which don't support the requested
+/// format (including all non-roslibrust nodes) still receive uncompressed messages.
+///
+/// Which to pick is a throughput vs. CPU trade-off, run
+/// `cargo bench --features ros1 --bench ros1_compression` to measure it on your own hardware:
+///  - [Compression::None] costs no CPU, and is the right choice for small messages and for
+///    local or otherwise fast connections
+///  - [Compression::Lz4] is fast but shrinks messages less, and pays off once the link is slower
+///    than the publisher's CPU
+///  - [Compression::Zstd] shrinks messages further at a higher CPU cost, so is best reserved for
+///    bandwidth constrained links (e.g. wifi)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Compression {
+    #[default]
+    None,
+    Lz4,
+    Zstd,
+}
+
+impl Compression {
+    /// Name used for this format in the `compression` connection header field
+    fn as_str(&self) -> &'static str {
+        match self {
+            Compression::None => "none",
+            Compression::Lz4 => "lz4",
+            Compression::Zstd => "zstd",
+        }
+    }
+
+    fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "none" => Some(Compression::None),
+            "lz4" => Some(Compression::Lz4),
+            "zstd" => Some(Compression::Zstd),
+            _ => None,
+        }
+    }
+
+    /// Compresses a serialized message
+    pub fn compress(&self, data: &[u8]) -> std::io::Result<Vec<u8>> {
+        match self {
+            Compression::None => Ok(data.to_vec()),
+            Compression::Lz4 => Ok(lz4_flex::compress_prepend_size(data)),
+            Compression::Zstd => zstd::bulk::compress(data, zstd::DEFAULT_COMPRESSION_LEVEL),
+        }
+    }
+
+    /// Reverses [Compression::compress], failing with
+    /// [InvalidData](std::io::ErrorKind::InvalidData) rather than decompressing more than
+    /// `max_length` bytes
+    pub fn decompress(&self, data: &[u8], max_length: usize) -> std::io::Result<Vec<u8>> {
+        let too_long = || {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("Decompressed message is longer than the limit of {max_length} bytes"),
+            )
+        };
+        let invalid = |e| std::io::Error::new(std::io::ErrorKind::InvalidData, e);
+        match self {
+            Compression::None if data.len() > max_length => Err(too_long()),
+            Compression::None => Ok(data.to_vec()),
+            Compression::Lz4 => {
+                let (length, _) = lz4_flex::block::uncompressed_size(data).map_err(invalid)?;
+                if length > max_length {
+                    return Err(too_long());
+                }
+                lz4_flex::decompress_size_prepended(data).map_err(invalid)
+            }
+            Compression::Zstd => {
+                let mut decompressed = vec![];
+                zstd::stream::read::Decoder::new(data)?
+                    .take((max_length as u64).saturating_add(1))
+                    .read_to_end(&mut decompressed)?;
+                if decompressed.len() > max_length {
+                    return Err(too_long());
+                }
+                Ok(decompressed)
+            }
+        }
+    }
 }
 
 impl ConnectionHeader {
     pub fn from_bytes(header_data: &[u8]) -> std::io::Result<ConnectionHeader> {
+        Self::parse(header_data).map(|(_remaining, header)| header)
+    }
+
+    /// Parses a header like [ConnectionHeader::from_bytes], also returning the bytes following it.
+    /// Lets a caller holding several length prefixed frames in one buffer, e.g. a header followed
+    /// by messages, carry on parsing from where the header ends without copying.
+    pub fn parse(header_data: &[u8]) -> std::io::Result<(&[u8], ConnectionHeader)> {
         let mut cursor = Cursor::new(header_data);
         let header_length = cursor.read_u32::<LittleEndian>()?;
-        if header_length as usize > header_data.len() {
+        // Fields are only read up to the length given, which may be zero, ignoring anything after
+        let header_end = header_length as u64 + 4;
+        if header_end > header_data.len() as u64 {
             return Err(std::io::ErrorKind::InvalidInput.into());
         }
 
@@ -29,21 +215,31 @@ impl ConnectionHeader {
         let mut topic = String::new();
         let mut topic_type = String::new();
         let mut tcp_nodelay = false;
+        let mut roslibrust_ack = false;
+        let mut compression = vec![];
+        let mut error = None;
 
-        // TODO: Unhandled: error, persistent
+        // TODO: Unhandled: persistent
 
-        while cursor.position() < header_data.len() as u64 {
+        while cursor.position() < header_end {
             let field_length = cursor.read_u32::<LittleEndian>()? as usize;
+            if cursor.position() + field_length as u64 > header_end {
+                return Err(std::io::ErrorKind::InvalidData.into());
+            }
             let mut field = vec![0u8; field_length];
             cursor.read_exact(&mut field)?;
-            let field = String::from_utf8(field).unwrap();
+            // The definition is checked before utf8 validation as some tools send it compressed
+            if let Some(value) = field.strip_prefix(b"message_definition=") {
+                msg_definition = parse_msg_definition(value);
+                continue;
+            }
+            let field = String::from_utf8(field)
+                .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
             let equals_pos = match field.find('=') {
                 Some(pos) => pos,
                 None => continue,
             };
-            if field.starts_with("message_definition=") {
-                field[equals_pos + 1..].clone_into(&mut msg_definition);
-            } else if field.starts_with("callerid=") {
+            if field.starts_with("callerid=") {
                 field[equals_pos + 1..].clone_into(&mut caller_id);
             } else if field.starts_with("latching=") {
                 let mut latching_str = String::new();
@@ -59,12 +255,25 @@ impl ConnectionHeader {
                 let mut tcp_nodelay_str = String::new();
                 field[equals_pos + 1..].clone_into(&mut tcp_nodelay_str);
                 tcp_nodelay = &tcp_nodelay_str != "0";
+            } else if field.starts_with("roslibrust_ack=") {
+                roslibrust_ack = &field[equals_pos + 1..] != "0";
+            } else if field.starts_with("error=") {
+                error = Some(field[equals_pos + 1..].to_owned());
+            } else if SERVICE_FIELDS.iter().any(|key| field.starts_with(key)) {
+                // Only used on service connections, which check them before parsing the header
+            } else if field.starts_with("compression=") {
+                for format in field[equals_pos + 1..].split(',') {
+                    match Compression::from_str(format) {
+                        Some(format) => compression.push(format),
+                        None => log::debug!("Ignoring unknown compression format: {format}"),
+                    }
+                }
             } else {
                 log::warn!("Encountered unhandled field in connection header: {field}");
             }
         }
 
-        Ok(ConnectionHeader {
+        let header = ConnectionHeader {
             caller_id,
             latching,
             msg_definition,
@@ -72,49 +281,1076 @@ impl ConnectionHeader {
             topic,
             topic_type,
             tcp_nodelay,
-        })
+            roslibrust_ack,
+            compression,
+            error,
+        };
+        Ok((&header_data[header_end as usize..], header))
+    }
+
+    /// Decodes a message definition which was base64 encoded without the `base64:` prefix
+    /// [ConnectionHeader::from_bytes] looks for, replacing [ConnectionHeader::msg_definition] with
+    /// the decoded text. Base64 is not distinguishable from a plain definition, so this is
+    /// opt-in for callers which know their peers encode definitions this way.
+    /// The decoded definition is gunzipped if it is gzip compressed.
+    pub fn decode_base64_msg_definition(&mut self) -> std::io::Result<()> {
+        let encoded = self
+            .msg_definition
+            .strip_prefix(BASE64_DEFINITION_PREFIX)
+            .unwrap_or(&self.msg_definition);
+        self.msg_definition = decode_base64_definition(encoded.as_bytes())?;
+        Ok(())
     }
 
     pub fn to_bytes(&self, to_publisher: bool) -> std::io::Result<Vec<u8>> {
         let mut header_data = Vec::with_capacity(1024);
+        self.to_bytes_into(to_publisher, &mut header_data)?;
+        Ok(header_data)
+    }
+
+    /// Serializes the header like [ConnectionHeader::to_bytes], but appends to `buf` instead of
+    /// allocating a new buffer, allowing one buffer to be reused across many connections.
+    pub fn to_bytes_into(&self, to_publisher: bool, buf: &mut Vec<u8>) -> std::io::Result<()> {
+        let start = buf.len();
         // Start by skipping the length header since we don't know yet
-        header_data.write_u32::<LittleEndian>(0)?;
+        buf.write_u32::<LittleEndian>(0)?;
+
+        if let Some(error) = &self.error {
+            write_field(buf, "error", error.as_bytes())?;
+            let total_length = (buf.len() - start - 4) as u32;
+            buf[start..start + 4].copy_from_slice(&total_length.to_le_bytes());
+            return Ok(());
+        }
 
-        let caller_id_str = format!("callerid={}", self.caller_id);
-        header_data.write_u32::<LittleEndian>(caller_id_str.len() as u32)?;
-        header_data.write(caller_id_str.as_bytes())?;
+        write_field(buf, "callerid", self.caller_id.as_bytes())?;
+        write_field(buf, "latching", if self.latching { b"1" } else { b"0" })?;
+        write_field(buf, "md5sum", self.md5sum.as_bytes())?;
+        write_field(buf, "message_definition", self.msg_definition.as_bytes())?;
+        if to_publisher {
+            write_field(
+                buf,
+                "tcp_nodelay",
+                if self.tcp_nodelay { b"1" } else { b"0" },
+            )?;
+        }
+        write_field(buf, "topic", self.topic.as_bytes())?;
+        write_field(buf, "type", self.topic_type.as_bytes())?;
 
-        let latching_str = format!("latching={}", if self.latching { 1 } else { 0 });
-        header_data.write_u32::<LittleEndian>(latching_str.len() as u32)?;
-        header_data.write(latching_str.as_bytes())?;
+        // Only written when set so that headers sent to other ROS implementations are unchanged
+        if self.roslibrust_ack {
+            write_field(buf, "roslibrust_ack", b"1")?;
+        }
 
-        let md5sum = format!("md5sum={}", self.md5sum);
-        header_data.write_u32::<LittleEndian>(md5sum.len() as u32)?;
-        header_data.write(md5sum.as_bytes())?;
+        if !self.compression.is_empty() {
+            let field_start = buf.len();
+            buf.write_u32::<LittleEndian>(0)?;
+            buf.write_all(b"compression=")?;
+            for (idx, compression) in self.compression.iter().enumerate() {
+                if idx > 0 {
+                    buf.write_all(b",")?;
+                }
+                buf.write_all(compression.as_str().as_bytes())?;
+            }
+            let field_length = (buf.len() - field_start - 4) as u32;
+            buf[field_start..field_start + 4].copy_from_slice(&field_length.to_le_bytes());
+        }
 
-        let msg_definition = format!("message_definition={}", self.msg_definition);
-        header_data.write_u32::<LittleEndian>(msg_definition.len() as u32)?;
-        header_data.write(msg_definition.as_bytes())?;
+        let total_length = (buf.len() - start - 4) as u32;
+        buf[start..start + 4].copy_from_slice(&total_length.to_le_bytes());
+        Ok(())
+    }
 
-        if to_publisher {
-            let tcp_nodelay = format!("tcp_nodelay={}", if self.tcp_nodelay { 1 } else { 0 });
-            header_data.write_u32::<LittleEndian>(tcp_nodelay.len() as u32)?;
-            header_data.write(tcp_nodelay.as_bytes())?;
+    /// Formats the header as one `key = value` line per field, in alphabetical order, for
+    /// comparing the headers of the two sides of a connection while debugging.
+    ///
+    /// Fields are named and included as [ConnectionHeader::to_bytes] would send them to a
+    /// publisher. Lines after the first of a multi-line value, i.e. `message_definition`, are
+    /// indented.
+    pub fn to_pretty_string(&self) -> String {
+        let mut pretty = String::new();
+        for (key, value) in self.fields() {
+            pretty.push_str(key);
+            pretty.push_str(" = ");
+            pretty.push_str(&value.replace('\n', "\n    "));
+            pretty.push('\n');
+        }
+        pretty
+    }
+
+    /// Formats the header as [ConnectionHeader::to_bytes] would send it to a publisher, as a hex
+    /// dump annotated with what each part of it is, for comparing against a packet capture.
+    ///
+    /// Each line starts with the offset in hex, so the dump can be imported by Wireshark's
+    /// "Import from Hex Dump" or `text2pcap`. Length prefixes get a line of their own, and each
+    /// field's `key=value` bytes follow 16 to a line, annotated with those bytes as ascii.
+    ///
+    /// ```text
+    /// 00000000: b0 00 00 00                                     | total_length=176
+    /// 00000004: 0e 00 00 00                                     | field_length=14 (callerid)
+    /// 00000008: 63 61 6c 6c 65 72 69 64 3d 2f 6e 6f 64 65       | callerid=/node
+    /// ```
+    #[cfg(feature = "debug-output")]
+    pub fn to_annotated_hex(&self) -> String {
+        let bytes = self.to_bytes(true).expect("Writing to a Vec can't fail");
+        let mut dump = String::new();
+        let mut push_line = |offset: usize, row: &[u8], annotation: &str| {
+            let hex: Vec<String> = row.iter().map(|byte| format!("{byte:02x}")).collect();
+            dump.push_str(&format!(
+                "{offset:08x}: {:<47} | {annotation}\n",
+                hex.join(" ")
+            ));
+        };
+        push_line(0, &bytes[..4], &format!("total_length={}", bytes.len() - 4));
+        let mut offset = 4;
+        while offset + 4 <= bytes.len() {
+            let length_bytes = &bytes[offset..offset + 4];
+            let length = u32::from_le_bytes(length_bytes.try_into().unwrap()) as usize;
+            let field = &bytes[offset + 4..(offset + 4 + length).min(bytes.len())];
+            let key = field.split(|byte| *byte == b'=').next().unwrap_or_default();
+            let key = String::from_utf8_lossy(key);
+            push_line(
+                offset,
+                length_bytes,
+                &format!("field_length={length} ({key})"),
+            );
+            offset += 4;
+            for row in field.chunks(16) {
+                let ascii: String = row
+                    .iter()
+                    .map(|byte| match byte {
+                        b' '..=b'~' => *byte as char,
+                        _ => '.',
+                    })
+                    .collect();
+                push_line(offset, row, &ascii);
+                offset += row.len();
+            }
+        }
+        dump
+    }
+
+    /// Lists the header's fields as `(key, value)` pairs sorted by key, using the same names and
+    /// values as [ConnectionHeader::to_pretty_string], without any of the binary encoding.
+    /// Useful for building and comparing headers in tests.
+    ///
+    /// ```
+    /// # use roslibrust::ros1::ConnectionHeader;
+    /// let header = ConnectionHeader {
+    ///     caller_id: "/talker".to_owned(),
+    ///     topic: "/chatter".to_owned(),
+    ///     ..Default::default()
+    /// };
+    /// let fields = header.to_fields();
+    /// assert_eq!(fields[0], ("callerid".to_owned(), "/talker".to_owned()));
+    /// assert!(fields.contains(&("topic".to_owned(), "/chatter".to_owned())));
+    /// ```
+    pub fn to_fields(&self) -> Vec<(String, String)> {
+        self.fields()
+            .into_iter()
+            .map(|(key, value)| (key.to_owned(), value))
+            .collect()
+    }
+
+    /// The inverse of [ConnectionHeader::to_fields], parses `(key, value)` pairs exactly as
+    /// [ConnectionHeader::from_bytes] would parse them from the wire.
+    ///
+    /// ```
+    /// # use roslibrust::ros1::ConnectionHeader;
+    /// let fields = [("callerid", "/talker"), ("latching", "1"), ("type", "std_msgs/String")]
+    ///     .map(|(key, value)| (key.to_owned(), value.to_owned()));
+    /// let header = ConnectionHeader::from_fields_list(&fields).unwrap();
+    /// assert_eq!(header.caller_id, "/talker");
+    /// assert!(header.latching);
+    /// assert_eq!(header.topic_type, "std_msgs/String");
+    /// ```
+    pub fn from_fields_list(fields: &[(String, String)]) -> std::io::Result<ConnectionHeader> {
+        ConnectionHeader::from_bytes(&encode_fields(fields)?)
+    }
+
+    /// Compares each field of this header against `other`, in alphabetical order of field name,
+    /// e.g. to find why a publisher refused a subscriber's header.
+    pub fn diff(&self, other: &ConnectionHeader) -> Vec<HeaderDiff> {
+        let mut other_fields = other.fields();
+        let mut diffs = vec![];
+        for (key, value) in self.fields() {
+            let diff = match other_fields.remove(key) {
+                Some(other_value) if other_value == value => HeaderDiff::Equal {
+                    key: key.to_owned(),
+                },
+                Some(other_value) => HeaderDiff::Changed {
+                    key: key.to_owned(),
+                    old: value,
+                    new: other_value,
+                },
+                None => HeaderDiff::OnlyInSelf {
+                    key: key.to_owned(),
+                    value,
+                },
+            };
+            diffs.push(diff);
         }
+        diffs.extend(
+            other_fields
+                .into_iter()
+                .map(|(key, value)| HeaderDiff::OnlyInOther {
+                    key: key.to_owned(),
+                    value,
+                }),
+        );
+        diffs.sort_by(|a, b| a.key().cmp(b.key()));
+        diffs
+    }
 
-        let topic = format!("topic={}", self.topic);
-        header_data.write_u32::<LittleEndian>(topic.len() as u32)?;
-        header_data.write(topic.as_bytes())?;
+    /// The fields [ConnectionHeader::to_bytes] writes for a publisher, keyed by their wire names
+    fn fields(&self) -> BTreeMap<&'static str, String> {
+        let mut fields = BTreeMap::new();
+        if let Some(error) = &self.error {
+            fields.insert("error", error.clone());
+            return fields;
+        }
+        let flag = |set: bool| if set { "1" } else { "0" }.to_owned();
+        fields.insert("callerid", self.caller_id.clone());
+        fields.insert("latching", flag(self.latching));
+        fields.insert("md5sum", self.md5sum.clone());
+        fields.insert("message_definition", self.msg_definition.clone());
+        fields.insert("tcp_nodelay", flag(self.tcp_nodelay));
+        fields.insert("topic", self.topic.clone());
+        fields.insert("type", self.topic_type.clone());
+        if self.roslibrust_ack {
+            fields.insert("roslibrust_ack", flag(true));
+        }
+        if !self.compression.is_empty() {
+            let formats: Vec<_> = self.compression.iter().map(|c| c.as_str()).collect();
+            fields.insert("compression", formats.join(","));
+        }
+        fields
+    }
+}
 
-        let topic_type = format!("type={}", self.topic_type);
-        header_data.write_u32::<LittleEndian>(topic_type.len() as u32)?;
-        header_data.write(topic_type.as_bytes())?;
+impl HeaderDiff {
+    /// Name of the field this entry compares
+    pub fn key(&self) -> &str {
+        match self {
+            HeaderDiff::Equal { key }
+            | HeaderDiff::Changed { key, .. }
+            | HeaderDiff::OnlyInSelf { key, .. }
+            | HeaderDiff::OnlyInOther { key, .. } => key,
+        }
+    }
+}
+
+/// Prefix marking a base64 encoded message definition, as sent by some non-standard bridges
+const BASE64_DEFINITION_PREFIX: &str = "base64:";
+
+/// Magic bytes at the start of any gzip stream
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
 
-        let total_length = (header_data.len() - 4) as u32;
-        for (idx, byte) in total_length.to_le_bytes().iter().enumerate() {
-            header_data[idx] = *byte;
+/// Parses the value of the `message_definition` field.
+///
+/// Definitions which are gzip compressed or prefixed with `base64:` are decoded, neither of which
+/// can be mistaken for a plain text definition. Compressed definitions may decompress to at most
+/// the 16 MiB [ConnectionHeaderDecoder] allows for a whole header by default. A definition which
+/// fails to decode is logged and kept as (lossy) text rather than failing the whole header, as
+/// nothing in the connection handshake depends on it.
+fn parse_msg_definition(value: &[u8]) -> String {
+    let decoded = if value.starts_with(&GZIP_MAGIC) {
+        gunzip(value, DEFAULT_MAX_HEADER_LENGTH)
+    } else if let Some(encoded) = value.strip_prefix(BASE64_DEFINITION_PREFIX.as_bytes()) {
+        decode_base64_definition(encoded)
+    } else {
+        return String::from_utf8_lossy(value).into_owned();
+    };
+    match decoded {
+        Ok(definition) => definition,
+        Err(e) => {
+            log::warn!("Failed to decode message_definition in connection header: {e}");
+            String::from_utf8_lossy(value).into_owned()
         }
+    }
+}
 
-        Ok(header_data)
+/// Decodes a base64 message definition, which may itself be gzip compressed
+fn decode_base64_definition(encoded: &[u8]) -> std::io::Result<String> {
+    use base64::Engine;
+    let encoded: Vec<u8> = encoded
+        .iter()
+        .copied()
+        .filter(|b| !b.is_ascii_whitespace())
+        .collect();
+    let bytes = base64::engine::general_purpose::STANDARD
+        .decode(encoded)
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+    if bytes.starts_with(&GZIP_MAGIC) {
+        gunzip(&bytes, DEFAULT_MAX_HEADER_LENGTH)
+    } else {
+        String::from_utf8(bytes)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+    }
+}
+
+/// Decompresses a gzip compressed definition, failing with
+/// [InvalidData](std::io::ErrorKind::InvalidData) once more than `max_length` bytes come out
+fn gunzip(data: &[u8], max_length: usize) -> std::io::Result<String> {
+    let mut definition = String::new();
+    flate2::read::GzDecoder::new(data)
+        .take(max_length as u64 + 1)
+        .read_to_string(&mut definition)?;
+    if definition.len() > max_length {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!(
+                "Decompressed message definition is longer than the limit of {max_length} bytes"
+            ),
+        ));
+    }
+    Ok(definition)
+}
+
+/// Encodes `(key, value)` pairs as a connection header, in the order given, for headers which
+/// can't be represented by a [ConnectionHeader] such as those of service connections
+pub(crate) fn encode_fields(
+    fields: &[(impl AsRef<str>, impl AsRef<str>)],
+) -> std::io::Result<Vec<u8>> {
+    let mut header_data = vec![0; 4];
+    for (key, value) in fields {
+        write_field(&mut header_data, key.as_ref(), value.as_ref().as_bytes())?;
+    }
+    let total_length = (header_data.len() - 4) as u32;
+    header_data[..4].copy_from_slice(&total_length.to_le_bytes());
+    Ok(header_data)
+}
+
+/// Writes a single length-prefixed `key=value` header field
+fn write_field(buf: &mut Vec<u8>, key: &str, value: &[u8]) -> std::io::Result<()> {
+    buf.write_u32::<LittleEndian>((key.len() + 1 + value.len()) as u32)?;
+    buf.write_all(key.as_bytes())?;
+    buf.write_all(b"=")?;
+    buf.write_all(value)
+}
+
+/// Longest frame [receive_frame] accepts, in bytes, matching roscpp's refusal of messages over a
+/// gigabyte
+pub const DEFAULT_MAX_FRAME_LENGTH: usize = 1_000_000_000;
+
+/// Reads exactly one length-prefixed frame (connection header or message) from the stream.
+/// The returned buffer includes the 4 byte length prefix, which is what both
+/// [ConnectionHeader::from_bytes] and serde_rosmsg expect.
+///
+/// Frames longer than [DEFAULT_MAX_FRAME_LENGTH] are rejected, see [receive_frame_into].
+pub async fn receive_frame(stream: &mut (impl AsyncRead + Unpin)) -> std::io::Result<Vec<u8>> {
+    let mut frame = vec![];
+    receive_frame_into(stream, &mut frame, DEFAULT_MAX_FRAME_LENGTH).await?;
+    Ok(frame)
+}
+
+/// Reads a frame like [receive_frame], but into `frame` instead of a newly allocated buffer,
+/// replacing its contents and only growing it if the frame doesn't fit.
+///
+/// Fails with [InvalidData](std::io::ErrorKind::InvalidData) without allocating if the length
+/// prefix is over `max_length` bytes, and with [UnexpectedEof](std::io::ErrorKind::UnexpectedEof)
+/// if the stream ends, carrying a [TruncatedMessage] if it ended partway through the frame rather
+/// than before it.
+pub async fn receive_frame_into(
+    stream: &mut (impl AsyncRead + Unpin),
+    frame: &mut Vec<u8>,
+    max_length: usize,
+) -> std::io::Result<()> {
+    let mut prefix = [0u8; 4];
+    match read_until_eof(stream, &mut prefix).await? {
+        0 => return Err(std::io::ErrorKind::UnexpectedEof.into()),
+        4 => {}
+        received => {
+            return Err(TruncatedMessage {
+                expected: 4,
+                received,
+            }
+            .into_io_error())
+        }
+    }
+    let length = u32::from_le_bytes(prefix) as usize;
+    if length > max_length {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!("Frame of {length} bytes is longer than the limit of {max_length} bytes"),
+        ));
+    }
+    frame.clear();
+    frame.reserve(length + 4);
+    frame.extend_from_slice(&prefix);
+    frame.resize(length + 4, 0);
+    let received = read_until_eof(stream, &mut frame[4..]).await?;
+    if received < length {
+        return Err(TruncatedMessage {
+            expected: length + 4,
+            received: received + 4,
+        }
+        .into_io_error());
+    }
+    Ok(())
+}
+
+/// Fills `buf` from the stream, returning how many bytes were read before it ended
+async fn read_until_eof(
+    stream: &mut (impl AsyncRead + Unpin),
+    buf: &mut [u8],
+) -> std::io::Result<usize> {
+    use tokio::io::AsyncReadExt;
+    let mut filled = 0;
+    while filled < buf.len() {
+        match stream.read(&mut buf[filled..]).await? {
+            0 => break,
+            read => filled += read,
+        }
+    }
+    Ok(filled)
+}
+
+/// Longest connection header [ConnectionHeaderDecoder] accepts by default, in bytes
+const DEFAULT_MAX_HEADER_LENGTH: usize = 16 * 1024 * 1024;
+
+/// Decodes [ConnectionHeader]s from a byte stream with [tokio_util::codec], collecting reads until
+/// a whole header has arrived. Once the header is decoded, a `Framed` stream can carry on with the
+/// rest of the TCPROS session by swapping in a decoder for messages with `map_decoder`.
+///
+/// ```no_run
+/// # async fn example(stream: tokio::net::TcpStream) -> std::io::Result<()> {
+/// use futures::StreamExt;
+/// use roslibrust::ros1::ConnectionHeaderDecoder;
+/// let mut headers = tokio_util::codec::FramedRead::new(stream, ConnectionHeaderDecoder::new());
+/// if let Some(header) = headers.next().await {
+///     println!("Connection from {}", header?.caller_id);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct ConnectionHeaderDecoder {
+    max_length: usize,
+}
+
+impl ConnectionHeaderDecoder {
+    pub fn new() -> Self {
+        Self {
+            max_length: DEFAULT_MAX_HEADER_LENGTH,
+        }
+    }
+
+    /// Fails decoding headers whose length prefix is over `max_length` bytes rather than waiting
+    /// for them to arrive. Defaults to 16 MiB.
+    pub fn with_max_length(max_length: usize) -> Self {
+        Self { max_length }
+    }
+}
+
+impl Default for ConnectionHeaderDecoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl tokio_util::codec::Decoder for ConnectionHeaderDecoder {
+    type Item = ConnectionHeader;
+    type Error = std::io::Error;
+
+    fn decode(&mut self, src: &mut bytes::BytesMut) -> std::io::Result<Option<ConnectionHeader>> {
+        let Some(prefix) = src.get(..4) else {
+            return Ok(None);
+        };
+        let length = u32::from_le_bytes(prefix.try_into().unwrap()) as usize;
+        if length > self.max_length {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!(
+                    "Connection header of {length} bytes is longer than the limit of {} bytes",
+                    self.max_length
+                ),
+            ));
+        }
+        let frame_length = length + 4;
+        if src.len() < frame_length {
+            src.reserve(frame_length - src.len());
+            return Ok(None);
+        }
+        let frame = src.split_to(frame_length);
+        ConnectionHeader::from_bytes(&frame).map(Some)
+    }
+}
+
+/// Joins a host and port into an address for use in a URI, wrapping IPv6 addresses in brackets,
+/// e.g. `[fe80::1]:11311`. The `%` before an IPv6 zone ID is encoded as `%25`, as RFC 6874
+/// requires, e.g. `[fe80::1%25eth0]:11311`.
+///
+/// Hosts exchanged in ROS's `["TCPROS", host, port]` negotiation are bare, so this is needed
+/// whenever one is combined with a port. To connect, pass the host and port as a pair instead,
+/// which also resolves hosts with zone IDs.
+pub(crate) fn host_port(host: &str, port: u16) -> String {
+    let (addr, zone) = match host.split_once('%') {
+        Some((addr, zone)) => (addr, Some(zone)),
+        None => (host, None),
+    };
+    if addr.parse::<std::net::Ipv6Addr>().is_err() {
+        return format!("{host}:{port}");
+    }
+    match zone {
+        Some(zone) => format!("[{addr}%25{zone}]:{port}"),
+        None => format!("[{addr}]:{port}"),
+    }
+}
+
+/// Removes the brackets from a bracketed IPv6 address, e.g. one given as `ROS_IP`, leaving other
+/// hosts unchanged
+pub(crate) fn unbracket_host(host: &str) -> &str {
+    host.strip_prefix('[')
+        .and_then(|host| host.strip_suffix(']'))
+        .unwrap_or(host)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        host_port, unbracket_host, Compression, ConnectionHeader, ConnectionHeaderDecoder,
+        HeaderDiff, TruncatedMessage,
+    };
+    use std::net::{IpAddr, SocketAddr};
+    use tokio_util::codec::Decoder;
+
+    fn header(roslibrust_ack: bool) -> ConnectionHeader {
+        ConnectionHeader {
+            caller_id: "/node".to_owned(),
+            latching: false,
+            msg_definition: "string data".to_owned(),
+            md5sum: "992ce8a1687cec8c8bd883ec73ca41d1".to_owned(),
+            topic: "/chatter".to_owned(),
+            topic_type: "std_msgs/String".to_owned(),
+            tcp_nodelay: false,
+            roslibrust_ack,
+            compression: vec![],
+            error: None,
+        }
+    }
+
+    #[test_log::test]
+    fn roslibrust_ack_round_trips() {
+        let bytes = header(true).to_bytes(true).unwrap();
+        assert!(ConnectionHeader::from_bytes(&bytes).unwrap().roslibrust_ack);
+    }
+
+    #[test_log::test]
+    fn decoder_waits_for_whole_header() {
+        let first = header(false);
+        let second = header(true);
+        let mut stream = first.to_bytes(true).unwrap();
+        stream.extend(second.to_bytes(true).unwrap());
+
+        // Fed a byte at a time, nothing is decoded until each header is complete
+        let mut decoder = ConnectionHeaderDecoder::new();
+        let mut buf = bytes::BytesMut::new();
+        let mut decoded = vec![];
+        for byte in stream {
+            buf.extend_from_slice(&[byte]);
+            if let Some(header) = decoder.decode(&mut buf).unwrap() {
+                decoded.push(header);
+            }
+        }
+        assert_eq!(decoded, vec![first, second]);
+        assert!(buf.is_empty());
+        assert!(decoder.decode(&mut buf).unwrap().is_none());
+    }
+
+    #[test_log::test]
+    fn decoder_refuses_headers_over_its_limit() {
+        let bytes = header(false).to_bytes(true).unwrap();
+        let mut buf = bytes::BytesMut::from(&bytes[..4]);
+        let err = ConnectionHeaderDecoder::with_max_length(16)
+            .decode(&mut buf)
+            .unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn framed_read_yields_headers() {
+        use futures::StreamExt;
+        let (mut writer, reader) = tokio::io::duplex(16);
+        let bytes = header(true).to_bytes(true).unwrap();
+        // The duplex's small buffer splits the header over many reads
+        let write = tokio::spawn(async move {
+            use tokio::io::AsyncWriteExt;
+            writer.write_all(&bytes).await.unwrap();
+        });
+        let mut headers =
+            tokio_util::codec::FramedRead::new(reader, ConnectionHeaderDecoder::new());
+        assert_eq!(headers.next().await.unwrap().unwrap(), header(true));
+        write.await.unwrap();
+        assert!(headers.next().await.is_none());
+    }
+
+    #[test_log::test]
+    fn error_header_contains_only_error() {
+        let mut refusal = header(true);
+        refusal.error = Some("topic types do not match".to_owned());
+        let bytes = refusal.to_bytes(false).unwrap();
+        let as_str = String::from_utf8_lossy(&bytes);
+        assert!(!as_str.contains("md5sum"));
+        let parsed = ConnectionHeader::from_bytes(&bytes).unwrap();
+        assert_eq!(parsed.error, refusal.error);
+        assert!(parsed.caller_id.is_empty());
+        assert_eq!(
+            ConnectionHeader::from_bytes(&header(true).to_bytes(false).unwrap())
+                .unwrap()
+                .error,
+            None
+        );
+    }
+
+    #[test_log::test]
+    fn roslibrust_ack_omitted_when_unset() {
+        let bytes = header(false).to_bytes(true).unwrap();
+        let as_str = String::from_utf8_lossy(&bytes);
+        assert!(!as_str.contains("roslibrust_ack"));
+        assert!(!ConnectionHeader::from_bytes(&bytes).unwrap().roslibrust_ack);
+    }
+
+    #[test_log::test]
+    #[cfg(feature = "debug-output")]
+    fn annotated_hex_labels_every_field() {
+        let dump = header(true).to_annotated_hex();
+        let bytes = header(true).to_bytes(true).unwrap();
+        assert!(dump.starts_with(&format!(
+            "00000000: {:02x} {:02x} 00 00{}| total_length={}\n",
+            bytes[0],
+            bytes[1],
+            " ".repeat(37),
+            bytes.len() - 4
+        )));
+        for key in [
+            "callerid",
+            "latching",
+            "md5sum",
+            "message_definition",
+            "tcp_nodelay",
+            "topic",
+            "type",
+            "roslibrust_ack",
+        ] {
+            assert!(
+                dump.contains(&format!(" ({key})\n")),
+                "{key} missing from\n{dump}"
+            );
+        }
+        assert!(dump.contains("| callerid=/node\n"), "{dump}");
+        assert!(dump.contains("00000004: 0e 00 00 00"), "{dump}");
+
+        // Every byte is dumped once, with offsets counting them
+        let dumped: Vec<u8> = dump
+            .lines()
+            .flat_map(|line| {
+                let hex = line[10..].split(" | ").next().unwrap();
+                hex.split_whitespace()
+                    .map(|byte| u8::from_str_radix(byte, 16).unwrap())
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        assert_eq!(dumped, bytes);
+        let last = dump.lines().last().unwrap();
+        let offset = usize::from_str_radix(&last[..8], 16).unwrap();
+        assert_eq!(
+            offset
+                + last[10..]
+                    .split(" | ")
+                    .next()
+                    .unwrap()
+                    .split_whitespace()
+                    .count(),
+            bytes.len()
+        );
+    }
+
+    #[test_log::test]
+    fn header_serializes_to_json() {
+        let mut header = header(true);
+        header.latching = true;
+        header.compression = vec![Compression::Lz4];
+        let json = serde_json::to_value(&header).unwrap();
+        assert_eq!(json["caller_id"], "/node");
+        assert_eq!(json["latching"], true);
+        assert_eq!(json["tcp_nodelay"], false);
+        assert_eq!(json["roslibrust_ack"], true);
+        assert_eq!(json["compression"], serde_json::json!(["lz4"]));
+        assert_eq!(json["error"], serde_json::Value::Null);
+        assert_eq!(
+            serde_json::from_value::<ConnectionHeader>(json).unwrap(),
+            header
+        );
+
+        let partial: ConnectionHeader =
+            serde_json::from_str(r#"{"topic": "/chatter", "latching": true}"#).unwrap();
+        assert_eq!(partial.topic, "/chatter");
+        assert!(partial.latching);
+        assert!(!partial.tcp_nodelay);
+    }
+
+    #[test_log::test]
+    fn compression_round_trips() {
+        let mut offered = header(false);
+        offered.compression = vec![Compression::Lz4, Compression::Zstd];
+        let bytes = offered.to_bytes(true).unwrap();
+        assert!(String::from_utf8_lossy(&bytes).contains("compression=lz4,zstd"));
+        assert_eq!(
+            ConnectionHeader::from_bytes(&bytes).unwrap().compression,
+            offered.compression
+        );
+        // Headers from nodes that don't know about compression parse as uncompressed
+        let bytes = header(false).to_bytes(true).unwrap();
+        assert!(ConnectionHeader::from_bytes(&bytes)
+            .unwrap()
+            .compression
+            .is_empty());
+    }
+
+    #[test_log::test]
+    fn compress_decompress() {
+        let data = "compressible ".repeat(1000).into_bytes();
+        for format in [Compression::None, Compression::Lz4, Compression::Zstd] {
+            let compressed = format.compress(&data).unwrap();
+            if format != Compression::None {
+                assert!(compressed.len() < data.len());
+            }
+            assert_eq!(format.decompress(&compressed, data.len()).unwrap(), data);
+        }
+    }
+
+    #[test_log::test]
+    fn decompression_stops_at_the_limit() {
+        let data = vec![0u8; 64 * 1024];
+        for format in [Compression::None, Compression::Lz4, Compression::Zstd] {
+            let compressed = format.compress(&data).unwrap();
+            let err = format.decompress(&compressed, data.len() - 1).unwrap_err();
+            assert_eq!(err.kind(), std::io::ErrorKind::InvalidData, "{format:?}");
+        }
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn receive_frame_reads_one_frame() {
+        let bytes = header(false).to_bytes(false).unwrap();
+        let mut data = bytes.clone();
+        data.extend_from_slice(&[1, 2, 3]);
+        let frame = super::receive_frame(&mut &data[..]).await.unwrap();
+        assert_eq!(frame, bytes);
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn frames_over_the_limit_are_rejected_before_reading() {
+        let mut frame = vec![];
+        let data = [0xff, 0xff, 0xff, 0xff, 1, 2, 3];
+        let err = super::receive_frame_into(&mut &data[..], &mut frame, 1024)
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+        assert_eq!(frame.capacity(), 0);
+
+        let data = [3, 0, 0, 0, 1, 2, 3];
+        super::receive_frame_into(&mut &data[..], &mut frame, 3)
+            .await
+            .unwrap();
+        assert_eq!(frame, data);
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn truncated_frames_are_told_apart_from_the_end_of_the_stream() {
+        let err = super::receive_frame(&mut &[][..]).await.unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::UnexpectedEof);
+        assert_eq!(TruncatedMessage::from_io_error(&err), None);
+
+        let err = super::receive_frame(&mut &[10, 0][..]).await.unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::UnexpectedEof);
+        assert_eq!(
+            TruncatedMessage::from_io_error(&err),
+            Some(&TruncatedMessage {
+                expected: 4,
+                received: 2
+            })
+        );
+
+        let err = super::receive_frame(&mut &[10, 0, 0, 0, 1, 2, 3][..])
+            .await
+            .unwrap_err();
+        assert_eq!(
+            TruncatedMessage::from_io_error(&err),
+            Some(&TruncatedMessage {
+                expected: 14,
+                received: 7
+            })
+        );
+        assert_eq!(
+            err.to_string(),
+            "Connection ended partway through a message, after 7 of its 14 bytes"
+        );
+
+        // Any other io error is left as it is
+        let other = std::io::Error::new(std::io::ErrorKind::UnexpectedEof, "other");
+        assert_eq!(TruncatedMessage::from_io_error(&other), None);
+    }
+
+    #[test_log::test]
+    fn to_bytes_into_appends_to_buffer() {
+        let mut offered = header(true);
+        offered.compression = vec![Compression::Lz4, Compression::Zstd];
+        let expected = offered.to_bytes(true).unwrap();
+
+        let mut buf = vec![0xAB];
+        offered.to_bytes_into(true, &mut buf).unwrap();
+        assert_eq!(buf[0], 0xAB);
+        assert_eq!(&buf[1..], &expected[..]);
+
+        // Reusing the buffer after clearing it produces the same bytes again
+        buf.clear();
+        offered.to_bytes_into(true, &mut buf).unwrap();
+        assert_eq!(buf, expected);
+        let parsed = ConnectionHeader::from_bytes(&buf).unwrap();
+        assert_eq!(parsed.caller_id, "/node");
+        assert_eq!(parsed.md5sum, offered.md5sum);
+        assert_eq!(parsed.compression, offered.compression);
+    }
+
+    #[test_log::test]
+    fn zero_field_header_parses_to_default() {
+        let header = ConnectionHeader::from_bytes(&[0, 0, 0, 0]).unwrap();
+        assert_eq!(header, ConnectionHeader::default());
+
+        // Anything following an empty header isn't part of it
+        let header = ConnectionHeader::from_bytes(&[0, 0, 0, 0, 5, 0, 0, 0]).unwrap();
+        assert_eq!(header, ConnectionHeader::default());
+
+        // But a header claiming more data than was given is rejected, as is no data at all
+        assert!(ConnectionHeader::from_bytes(&[4, 0, 0, 0]).is_err());
+        assert!(ConnectionHeader::from_bytes(&[]).is_err());
+    }
+
+    #[test_log::test]
+    fn parse_returns_bytes_after_header() {
+        let mut buf = header(false).to_bytes(false).unwrap();
+        // A message frame pipelined after the header, as a publisher sends it
+        buf.extend_from_slice(&[3, 0, 0, 0, 1, 2, 3]);
+
+        let (remaining, parsed) = ConnectionHeader::parse(&buf).unwrap();
+        assert_eq!(parsed, header(false));
+        assert_eq!(remaining, &[3, 0, 0, 0, 1, 2, 3]);
+
+        let (remaining, parsed) = ConnectionHeader::parse(&[0, 0, 0, 0]).unwrap();
+        assert_eq!(parsed, ConnectionHeader::default());
+        assert!(remaining.is_empty());
+    }
+
+    #[test_log::test]
+    fn default_header_round_trips() {
+        for to_publisher in [true, false] {
+            let bytes = ConnectionHeader::default().to_bytes(to_publisher).unwrap();
+            assert_eq!(
+                ConnectionHeader::from_bytes(&bytes).unwrap(),
+                ConnectionHeader::default()
+            );
+        }
+    }
+
+    /// Builds a header whose message_definition field holds exactly `definition`
+    fn header_with_definition(definition: &[u8]) -> Vec<u8> {
+        let mut bytes = vec![0u8; 4];
+        super::write_field(&mut bytes, "callerid", b"/node").unwrap();
+        super::write_field(&mut bytes, "message_definition", definition).unwrap();
+        let length = (bytes.len() - 4) as u32;
+        bytes[..4].copy_from_slice(&length.to_le_bytes());
+        bytes
+    }
+
+    fn gzip(data: &[u8]) -> Vec<u8> {
+        use std::io::Write;
+        let mut encoder = flate2::write::GzEncoder::new(vec![], flate2::Compression::default());
+        encoder.write_all(data).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    #[test_log::test]
+    fn encoded_msg_definitions_are_decoded() {
+        use base64::Engine;
+        let definition = "string data\n".repeat(10_000);
+        let base64 = base64::engine::general_purpose::STANDARD;
+
+        let gzipped = header_with_definition(&gzip(definition.as_bytes()));
+        let prefixed = format!("base64:{}", base64.encode(&definition));
+        let prefixed_gzip = format!("base64:{}", base64.encode(gzip(definition.as_bytes())));
+        for bytes in [
+            gzipped,
+            header_with_definition(prefixed.as_bytes()),
+            header_with_definition(prefixed_gzip.as_bytes()),
+        ] {
+            let header = ConnectionHeader::from_bytes(&bytes).unwrap();
+            assert_eq!(header.msg_definition, definition);
+            assert_eq!(header.caller_id, "/node");
+        }
+    }
+
+    #[test_log::test]
+    fn gunzip_stops_at_the_limit() {
+        let definition = "string data\n".repeat(100);
+        let gzipped = gzip(definition.as_bytes());
+        assert_eq!(
+            super::gunzip(&gzipped, definition.len()).unwrap(),
+            definition
+        );
+        let err = super::gunzip(&gzipped, definition.len() - 1).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+    }
+
+    #[test_log::test]
+    fn undecodable_msg_definition_does_not_fail_header() {
+        let bytes = header_with_definition(b"base64:not base64!");
+        let header = ConnectionHeader::from_bytes(&bytes).unwrap();
+        assert_eq!(header.msg_definition, "base64:not base64!");
+        assert_eq!(header.caller_id, "/node");
+
+        let bytes = header_with_definition(&[0x1f, 0x8b, 0xff, 0xfe]);
+        let header = ConnectionHeader::from_bytes(&bytes).unwrap();
+        assert_eq!(header.caller_id, "/node");
+    }
+
+    #[test_log::test]
+    fn decode_base64_msg_definition_is_opt_in() {
+        use base64::Engine;
+        let encoded = base64::engine::general_purpose::STANDARD.encode("string data");
+        let bytes = header_with_definition(encoded.as_bytes());
+        let mut header = ConnectionHeader::from_bytes(&bytes).unwrap();
+        assert_eq!(header.msg_definition, encoded);
+        header.decode_base64_msg_definition().unwrap();
+        assert_eq!(header.msg_definition, "string data");
+
+        header.msg_definition = "string data".to_owned();
+        assert!(header.decode_base64_msg_definition().is_err());
+    }
+
+    #[test_log::test]
+    fn non_utf8_field_is_an_error() {
+        let mut bytes = vec![0u8; 4];
+        super::write_field(&mut bytes, "callerid", &[0xff, 0xfe]).unwrap();
+        let length = (bytes.len() - 4) as u32;
+        bytes[..4].copy_from_slice(&length.to_le_bytes());
+        assert!(ConnectionHeader::from_bytes(&bytes).is_err());
+    }
+
+    #[test_log::test]
+    fn host_port_round_trips_v4_and_v6_hosts() {
+        for (host, expected) in [
+            ("192.168.1.2", "192.168.1.2:11311"),
+            ("127.0.0.1", "127.0.0.1:11311"),
+            ("fe80::1", "[fe80::1]:11311"),
+            ("::1", "[::1]:11311"),
+            ("2001:db8::8a2e:370:7334", "[2001:db8::8a2e:370:7334]:11311"),
+        ] {
+            let addr = host_port(host, 11311);
+            assert_eq!(addr, expected);
+            let parsed: SocketAddr = addr.parse().unwrap();
+            assert_eq!(parsed.ip(), host.parse::<IpAddr>().unwrap());
+            assert_eq!(parsed.port(), 11311);
+            assert_eq!(parsed.ip().to_string(), host);
+        }
+        assert_eq!(host_port("robot.local", 80), "robot.local:80");
+        // Link local addresses name the interface they're reached through
+        assert_eq!(host_port("fe80::1%eth0", 11311), "[fe80::1%25eth0]:11311");
+        assert_eq!(host_port("fe80::1%3", 80), "[fe80::1%253]:80");
+        assert_eq!(host_port("robot%local", 80), "robot%local:80");
+    }
+
+    #[test_log::test]
+    fn unbracket_host_only_strips_brackets() {
+        assert_eq!(unbracket_host("[fe80::1]"), "fe80::1");
+        assert_eq!(unbracket_host("fe80::1"), "fe80::1");
+        assert_eq!(unbracket_host("10.0.0.1"), "10.0.0.1");
+        assert_eq!(unbracket_host("[broken"), "[broken");
+        // A bracketed host given by the user round trips to the same address
+        assert_eq!(host_port(unbracket_host("[::1]"), 1), "[::1]:1");
+    }
+
+    #[test_log::test]
+    fn pretty_string_lists_fields_alphabetically() {
+        let mut header = header(true);
+        header.msg_definition = "string data\nint32 count".to_owned();
+        assert_eq!(
+            header.to_pretty_string(),
+            "callerid = /node
+latching = 0
+md5sum = 992ce8a1687cec8c8bd883ec73ca41d1
+message_definition = string data
+    int32 count
+roslibrust_ack = 1
+tcp_nodelay = 0
+topic = /chatter
+type = std_msgs/String
+"
+        );
+    }
+
+    #[test_log::test]
+    fn fields_round_trip() {
+        let mut header = header(true);
+        header.compression = vec![Compression::Zstd];
+        let fields = header.to_fields();
+        let mut keys: Vec<_> = fields.iter().map(|(key, _)| key.as_str()).collect();
+        keys.sort();
+        assert_eq!(keys, fields.iter().map(|(key, _)| key).collect::<Vec<_>>());
+        assert_eq!(ConnectionHeader::from_fields_list(&fields).unwrap(), header);
+
+        let error = ConnectionHeader {
+            error: Some("topic does not exist".to_owned()),
+            ..Default::default()
+        };
+        assert_eq!(
+            error.to_fields(),
+            vec![("error".to_owned(), "topic does not exist".to_owned())]
+        );
+        assert_eq!(
+            ConnectionHeader::from_fields_list(&error.to_fields()).unwrap(),
+            error
+        );
+    }
+
+    #[test_log::test]
+    fn diff_reports_each_field() {
+        let expected = header(true);
+        let actual = ConnectionHeader {
+            md5sum: "*".to_owned(),
+            roslibrust_ack: false,
+            compression: vec![Compression::Lz4, Compression::Zstd],
+            ..header(false)
+        };
+        let equal = |key: &str| HeaderDiff::Equal {
+            key: key.to_owned(),
+        };
+        assert_eq!(
+            expected.diff(&actual),
+            vec![
+                equal("callerid"),
+                HeaderDiff::OnlyInOther {
+                    key: "compression".to_owned(),
+                    value: "lz4,zstd".to_owned()
+                },
+                equal("latching"),
+                HeaderDiff::Changed {
+                    key: "md5sum".to_owned(),
+                    old: "992ce8a1687cec8c8bd883ec73ca41d1".to_owned(),
+                    new: "*".to_owned()
+                },
+                equal("message_definition"),
+                HeaderDiff::OnlyInSelf {
+                    key: "roslibrust_ack".to_owned(),
+                    value: "1".to_owned()
+                },
+                equal("tcp_nodelay"),
+                equal("topic"),
+                equal("type"),
+            ]
+        );
+        assert!(expected
+            .diff(&expected)
+            .iter()
+            .all(|diff| matches!(diff, HeaderDiff::Equal { .. })));
     }
 }
@@ -0,0 +1,232 @@
+//! Stand-ins for a ROS1 node, to unit test code which subscribes to topics without a rosmaster or
+//! any publishers running.
+//!
+//! A [MockNodeHandle] hands out real [Subscriber]s, which receive messages injected by the test
+//! rather than sent by publishers. Recorded data can be replayed to them from a bag file with a
+//! [BagFixture]:
+//! ```no_run
+//! # roslibrust_codegen_macro::find_and_generate_ros_messages!("assets/ros1_common_interfaces/std_msgs");
+//! # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+//! use roslibrust::ros1::testing::{BagFixture, MockNodeHandle};
+//!
+//! let mut node = MockNodeHandle::new();
+//! let mut chatter = node.subscribe::<std_msgs::String>("/chatter", 1000)?;
+//! let fixture = BagFixture::load("tests/chatter.bag".as_ref())?;
+//! fixture.inject_into(&mut node);
+//! let first = chatter.next().await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::ros1::{
+    bags::{BagError, BagIndex, BagMessage},
+    rate_stats::{RateTracker, DEFAULT_RATE_WINDOW},
+    subscriber::RawMessage,
+    PooledBuffer, Subscriber,
+};
+use roslibrust_codegen::{integral_types::Time, RosMessageType};
+use std::{collections::HashMap, path::Path, sync::Arc, time::Instant};
+use tokio::sync::broadcast;
+
+/// A node whose subscriptions receive messages injected by tests, see the [module docs](self)
+#[derive(Debug, Default)]
+pub struct MockNodeHandle {
+    topics: HashMap<String, MockTopic>,
+}
+
+#[derive(Debug)]
+struct MockTopic {
+    topic_type: String,
+    md5sum: String,
+    sender: broadcast::Sender<RawMessage>,
+    rate: RateTracker,
+}
+
+impl MockNodeHandle {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Subscribes to `topic` like [NodeHandle::subscribe](crate::ros1::NodeHandle::subscribe).
+    ///
+    /// Every subscriber to a topic shares a queue of the size given by the first. Injected
+    /// messages are delivered immediately rather than at the rate they were published, so the
+    /// queue must be large enough to hold all of them until the test reads them.
+    ///
+    /// Fails if the topic was already subscribed to with a different type.
+    pub fn subscribe<T: RosMessageType>(
+        &mut self,
+        topic: &str,
+        queue_size: usize,
+    ) -> Result<Subscriber<T>, Box<dyn std::error::Error + Send + Sync>> {
+        let mock = self
+            .topics
+            .entry(topic.to_owned())
+            .or_insert_with(|| MockTopic {
+                topic_type: T::ROS_TYPE_NAME.to_owned(),
+                md5sum: T::MD5SUM.to_owned(),
+                sender: broadcast::channel(queue_size.max(1)).0,
+                rate: RateTracker::new(DEFAULT_RATE_WINDOW),
+            });
+        if mock.topic_type != T::ROS_TYPE_NAME {
+            return Err(format!(
+                "Topic {topic} is already subscribed to with type {}, not {}",
+                mock.topic_type,
+                T::ROS_TYPE_NAME
+            )
+            .into());
+        }
+        Ok(Subscriber::new(mock.sender.subscribe(), mock.rate.clone()))
+    }
+
+    /// Delivers `message` to the subscribers of `topic`, returning whether there were any
+    pub fn inject<T: RosMessageType>(&mut self, topic: &str, message: &T) -> bool {
+        // Serialized with its length prefix, as messages are received over TCPROS
+        let data = serde_rosmsg::to_vec(message).expect("Failed to serialize message");
+        self.send(topic, data)
+    }
+
+    /// Delivers an already serialized message, without its length prefix, to the subscribers of
+    /// `topic`, returning whether there were any
+    pub fn inject_raw(&mut self, topic: &str, data: &[u8]) -> bool {
+        let mut frame = Vec::with_capacity(data.len() + 4);
+        frame.extend((data.len() as u32).to_le_bytes());
+        frame.extend(data);
+        self.send(topic, frame)
+    }
+
+    fn send(&mut self, topic: &str, frame: Vec<u8>) -> bool {
+        let Some(mock) = self.topics.get(topic) else {
+            return false;
+        };
+        mock.rate.record(Instant::now());
+        mock.sender
+            .send(Arc::new(PooledBuffer::from(frame)))
+            .is_ok()
+    }
+}
+
+/// The messages recorded in a bag file, loaded to replay to the subscribers of a
+/// [MockNodeHandle], see the [module docs](self)
+#[derive(Debug)]
+pub struct BagFixture {
+    index: BagIndex,
+    /// In the order they were recorded
+    messages: Vec<BagMessage>,
+}
+
+impl BagFixture {
+    /// Reads every message in the bag file at `path` into memory
+    pub fn load(path: &Path) -> Result<BagFixture, BagError> {
+        let index = BagIndex::open(path)?;
+        let messages = index.read_messages()?;
+        Ok(BagFixture { index, messages })
+    }
+
+    /// Names of the topics with messages in the bag, sorted
+    pub fn topics(&self) -> Vec<&str> {
+        let mut topics: Vec<&str> = self.messages.iter().map(|m| m.topic.as_str()).collect();
+        topics.sort_unstable();
+        topics.dedup();
+        topics
+    }
+
+    /// Times of the first and last messages in the bag, both zero if it has none
+    pub fn time_range(&self) -> (Time, Time) {
+        self.index.time_range()
+    }
+
+    /// The messages in the bag, in the order they were recorded
+    pub fn messages(&self) -> &[BagMessage] {
+        &self.messages
+    }
+
+    /// Delivers every message in the bag to the subscribers of its topic in `node`, in the order
+    /// they were recorded. Messages on topics nothing subscribes to are dropped, as they would
+    /// be by ROS. Returns the number of messages delivered.
+    ///
+    /// Panics if a topic is subscribed to with a type whose md5sum differs from the one recorded,
+    /// as its messages can't be deserialized.
+    pub fn inject_into(&self, node: &mut MockNodeHandle) -> usize {
+        for topic in self.index.topics() {
+            let Some(mock) = node.topics.get(&topic.name) else {
+                continue;
+            };
+            let matches = mock.md5sum.is_empty()
+                || mock.md5sum == "*"
+                || topic.md5sum == "*"
+                || mock.md5sum == topic.md5sum;
+            assert!(
+                matches,
+                "Bag records {} as {} with md5sum {}, but it is subscribed to as {} with md5sum {}",
+                topic.name, topic.topic_type, topic.md5sum, mock.topic_type, mock.md5sum
+            );
+        }
+        self.messages
+            .iter()
+            .filter(|message| node.inject_raw(&message.topic, &message.data))
+            .count()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{BagFixture, MockNodeHandle};
+    use crate::ros1::bags::test::{message_chunk, write_bag, TempBag};
+    use roslibrust_codegen::integral_types::Time;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+    struct StringMsg {
+        data: String,
+    }
+
+    impl roslibrust_codegen::RosMessageType for StringMsg {
+        const ROS_TYPE_NAME: &'static str = "std_msgs/String";
+        const MD5SUM: &'static str = "992ce8a1687cec8c8bd883ec73ca41d1";
+    }
+
+    /// A std_msgs/String serialized as it is recorded in a bag
+    fn recorded(data: &str) -> Vec<u8> {
+        [&(data.len() as u32).to_le_bytes(), data.as_bytes()].concat()
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn bag_is_replayed_to_mock_subscribers() {
+        let bag = TempBag::new("replayed_to_mock_subscribers");
+        let (hello, world, other) = (recorded("hello"), recorded("world"), recorded("other"));
+        write_bag(
+            &bag.0,
+            &[
+                ("/chatter", "std_msgs/String"),
+                ("/unheard", "std_msgs/String"),
+            ],
+            &[
+                message_chunk(0, &[(20, &world)]),
+                message_chunk(1, &[(15, &other)]),
+                message_chunk(0, &[(10, &hello)]),
+            ],
+        );
+
+        let fixture = BagFixture::load(&bag.0).unwrap();
+        assert_eq!(fixture.topics(), ["/chatter", "/unheard"]);
+        assert_eq!(
+            fixture.time_range(),
+            (Time { secs: 10, nsecs: 0 }, Time { secs: 20, nsecs: 0 })
+        );
+
+        let mut node = MockNodeHandle::new();
+        let mut chatter = node.subscribe::<StringMsg>("/chatter", 10).unwrap();
+        assert_eq!(fixture.inject_into(&mut node), 2);
+        assert_eq!(chatter.next().await.unwrap().data, "hello");
+        assert_eq!(chatter.next().await.unwrap().data, "world");
+
+        // Messages can also be injected directly
+        let message = StringMsg {
+            data: "direct".to_owned(),
+        };
+        assert!(node.inject("/chatter", &message));
+        assert!(!node.inject("/unheard", &message));
+        assert_eq!(chatter.next().await.unwrap(), message);
+    }
+}
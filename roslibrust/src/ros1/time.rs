@@ -0,0 +1,356 @@
+//! Timers for running callbacks periodically, and following simulated time published on `/clock`
+
+use abort_on_drop::ChildTask;
+use roslibrust_codegen::{integral_types::Time, RosMessageType};
+use std::{future::Future, sync::Arc, time::Duration};
+use tokio::{
+    sync::{broadcast::error::RecvError, watch, Notify},
+    time::{Instant, MissedTickBehavior},
+};
+
+use super::Subscriber;
+
+/// Calls a callback periodically based on wall-clock time.
+///
+/// Unlike timers driven by ROS time, a WallTimer keeps firing at the same real-world rate when
+/// `use_sim_time` is set and the simulation runs slower or faster than real time, or is paused.
+/// This makes it suitable for watchdogs and diagnostics which must keep running regardless.
+///
+/// The first call happens one period after the timer is created. If a callback takes longer
+/// than the period, the next call is delayed rather than fired in a burst to catch up.
+/// The timer stops when dropped.
+pub struct WallTimer {
+    reset: Arc<Notify>,
+    _task: ChildTask<()>,
+}
+
+impl WallTimer {
+    /// Starts a timer calling `cb` every `period`, must be called from within a tokio runtime
+    pub fn new(period: Duration, cb: impl Fn() + Send + 'static) -> WallTimer {
+        let reset = Arc::new(Notify::new());
+        let task = tokio::spawn({
+            let reset = reset.clone();
+            async move {
+                let mut interval = tokio::time::interval_at(Instant::now() + period, period);
+                interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+                loop {
+                    tokio::select! {
+                        _ = interval.tick() => cb(),
+                        _ = reset.notified() => interval.reset(),
+                    }
+                }
+            }
+        });
+        WallTimer {
+            reset,
+            _task: task.into(),
+        }
+    }
+
+    /// Restarts the current period, so the next call happens one full period from now
+    pub fn reset(&self) {
+        self.reset.notify_one();
+    }
+}
+
+/// rosgraph_msgs/Clock, published on `/clock` by simulators when `use_sim_time` is set
+#[derive(serde::Deserialize, serde::Serialize, Debug, Default, Clone, PartialEq)]
+pub(crate) struct Clock {
+    pub clock: Time,
+}
+
+impl RosMessageType for Clock {
+    const ROS_TYPE_NAME: &'static str = "rosgraph_msgs/Clock";
+    const MD5SUM: &'static str = "a9c97c1d230cfc112e270351a944ee47";
+    const DEFINITION: &'static str = "time clock";
+}
+
+/// Follows the simulated ROS time published on `/clock`, see [NodeHandle::sim_clock](super::NodeHandle::sim_clock).
+///
+/// Clones share the same subscription, which ends once every clone and every future from
+/// [SimClock::wait_until] has been dropped.
+#[derive(Clone)]
+pub struct SimClock {
+    time: watch::Receiver<Option<Time>>,
+    _task: Arc<ChildTask<()>>,
+}
+
+impl SimClock {
+    /// Tracks the times received by `subscriber`
+    pub(crate) fn follow(mut subscriber: Subscriber<Clock>) -> SimClock {
+        let (sender, time) = watch::channel(None);
+        let task = tokio::spawn(async move {
+            loop {
+                let clock = match subscriber.next().await {
+                    Ok(clock) => clock,
+                    Err(e) if matches!(e.downcast_ref(), Some(RecvError::Closed)) => break,
+                    // Missing a time because we fell behind is fine, the next one is newer
+                    Err(e) => {
+                        log::debug!("Skipping /clock message: {e}");
+                        continue;
+                    }
+                };
+                sender.send_replace(Some(clock.clock));
+            }
+        });
+        SimClock::new(time, task.into())
+    }
+
+    fn new(time: watch::Receiver<Option<Time>>, task: ChildTask<()>) -> SimClock {
+        SimClock {
+            time,
+            _task: Arc::new(task),
+        }
+    }
+
+    /// The latest time published on `/clock`, None until the first one is received
+    pub fn now(&self) -> Option<Time> {
+        *self.time.borrow()
+    }
+
+    /// Resolves once the time published on `/clock` reaches `ros_time`, immediately if it already
+    /// has. Never resolves if the simulation stops publishing before then.
+    pub fn wait_until(&self, ros_time: Time) -> impl Future<Output = ()> + Send + 'static {
+        let mut clock = self.clone();
+        async move {
+            let target = since_epoch(ros_time);
+            loop {
+                let now = *clock.time.borrow_and_update();
+                if now.is_some_and(|now| since_epoch(now) >= target) {
+                    return;
+                }
+                if clock.time.changed().await.is_err() {
+                    // The subscription ended, so the time will never change again
+                    return std::future::pending().await;
+                }
+            }
+        }
+    }
+}
+
+fn since_epoch(time: Time) -> Duration {
+    Duration::new(time.secs.into(), time.nsecs)
+}
+
+/// Calls a callback periodically based on ROS time, see
+/// [NodeHandle::create_timer_with_sim_time_support](super::NodeHandle::create_timer_with_sim_time_support).
+///
+/// Following a [SimClock], the first call happens one period after the first time published on
+/// `/clock`. If the time jumps ahead by more than a period, the timer fires once and the next
+/// call is one period after the jump rather than a burst to catch up, as a [WallTimer] does.
+/// If the time jumps backwards, as when a simulation restarts, the next call is one period after
+/// the new time. The timer stops when dropped.
+pub struct Timer {
+    _inner: TimerInner,
+}
+
+// Only held so the timer stops when dropped
+enum TimerInner {
+    Wall { _timer: WallTimer },
+    Sim { _task: ChildTask<()> },
+}
+
+impl From<WallTimer> for Timer {
+    fn from(timer: WallTimer) -> Self {
+        Timer {
+            _inner: TimerInner::Wall { _timer: timer },
+        }
+    }
+}
+
+impl Timer {
+    /// Starts a timer calling `cb` every `period` of the time published on `/clock`, must be
+    /// called from within a tokio runtime
+    pub fn with_sim_clock(
+        period: Duration,
+        mut clock: SimClock,
+        cb: impl Fn() + Send + 'static,
+    ) -> Timer {
+        let task = tokio::spawn(async move {
+            // Starts from the first time published
+            let start = loop {
+                if let Some(now) = *clock.time.borrow_and_update() {
+                    break since_epoch(now);
+                }
+                if clock.time.changed().await.is_err() {
+                    return;
+                }
+            };
+            let mut next = start + period;
+            let mut last = start;
+            while clock.time.changed().await.is_ok() {
+                let Some(now) = *clock.time.borrow_and_update() else {
+                    continue;
+                };
+                let now = since_epoch(now);
+                if now < last {
+                    // The time jumped backwards, e.g. the simulation restarted, so waiting for
+                    // the old deadline could take arbitrarily long
+                    next = now + period;
+                } else if now >= next {
+                    cb();
+                    next += period;
+                    if next <= now {
+                        next = now + period;
+                    }
+                }
+                last = now;
+            }
+        });
+        Timer {
+            _inner: TimerInner::Sim { _task: task.into() },
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{SimClock, Time, Timer, WallTimer};
+    use std::{
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+        time::Duration,
+    };
+    use tokio::sync::watch;
+
+    fn counting_timer(period: Duration) -> (WallTimer, Arc<AtomicUsize>) {
+        let count = Arc::new(AtomicUsize::new(0));
+        let timer = WallTimer::new(period, {
+            let count = count.clone();
+            move || {
+                count.fetch_add(1, Ordering::SeqCst);
+            }
+        });
+        (timer, count)
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn fires_at_wall_clock_rate() {
+        let (_timer, count) = counting_timer(Duration::from_millis(50));
+        tokio::time::sleep(Duration::from_millis(25)).await;
+        assert_eq!(count.load(Ordering::SeqCst), 0);
+        tokio::time::sleep(Duration::from_millis(250)).await;
+        assert_eq!(count.load(Ordering::SeqCst), 5);
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn reset_restarts_period() {
+        let (timer, count) = counting_timer(Duration::from_millis(100));
+        tokio::time::sleep(Duration::from_millis(60)).await;
+        timer.reset();
+        // Would have fired at 100ms without the reset, now fires at 160ms
+        tokio::time::sleep(Duration::from_millis(70)).await;
+        assert_eq!(count.load(Ordering::SeqCst), 0);
+        tokio::time::sleep(Duration::from_millis(70)).await;
+        assert_eq!(count.load(Ordering::SeqCst), 1);
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn stops_when_dropped() {
+        let (timer, count) = counting_timer(Duration::from_millis(20));
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        drop(timer);
+        let fired = count.load(Ordering::SeqCst);
+        tokio::time::sleep(Duration::from_millis(60)).await;
+        assert_eq!(count.load(Ordering::SeqCst), fired);
+    }
+
+    fn manual_clock() -> (watch::Sender<Option<Time>>, SimClock) {
+        let (sender, time) = watch::channel(None);
+        let task = tokio::spawn(std::future::pending());
+        (sender, SimClock::new(time, task.into()))
+    }
+
+    fn at(secs: u32, millis: u32) -> Option<Time> {
+        Some(Time {
+            secs,
+            nsecs: millis * 1_000_000,
+        })
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn wait_until_resolves_once_clock_passes_target() {
+        let (sender, clock) = manual_clock();
+        let target = at(10, 0).unwrap();
+        let waiting = tokio::spawn(clock.wait_until(target));
+
+        for time in [None, at(9, 0), at(9, 999)] {
+            sender.send_replace(time);
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            assert!(!waiting.is_finished());
+        }
+        sender.send_replace(at(10, 500));
+        tokio::time::timeout(Duration::from_secs(1), waiting)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(clock.now(), at(10, 500));
+
+        // Already passed
+        tokio::time::timeout(Duration::from_secs(1), clock.wait_until(target))
+            .await
+            .unwrap();
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn sim_timer_follows_clock() {
+        let (sender, clock) = manual_clock();
+        let count = Arc::new(AtomicUsize::new(0));
+        let _timer = Timer::with_sim_clock(Duration::from_secs(1), clock, {
+            let count = count.clone();
+            move || {
+                count.fetch_add(1, Ordering::SeqCst);
+            }
+        });
+
+        // Nothing fires until the clock is published, however much wall time passes
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        // Expected calls after each time is published: starting at 100s, fires at 101s and 102s,
+        // then once for the jump to 105.2s after which the next call is at 106.2s
+        let steps = [
+            (at(100, 0), 0),
+            (at(100, 500), 0),
+            (at(101, 0), 1),
+            (at(101, 999), 1),
+            (at(102, 100), 2),
+            (at(105, 200), 3),
+            (at(106, 0), 3),
+            (at(106, 200), 4),
+        ];
+        for (time, expected) in steps {
+            sender.send_replace(time);
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            assert_eq!(count.load(Ordering::SeqCst), expected, "at {time:?}");
+        }
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn sim_timer_restarts_when_clock_jumps_backwards() {
+        let (sender, clock) = manual_clock();
+        let count = Arc::new(AtomicUsize::new(0));
+        let _timer = Timer::with_sim_clock(Duration::from_secs(1), clock, {
+            let count = count.clone();
+            move || {
+                count.fetch_add(1, Ordering::SeqCst);
+            }
+        });
+
+        // Fires at 201s, then the simulation restarts from 0s and the timer fires again at 1s
+        // rather than waiting for the clock to get back to 202s
+        let steps = [
+            (at(200, 0), 0),
+            (at(201, 0), 1),
+            (at(0, 0), 1),
+            (at(0, 500), 1),
+            (at(1, 0), 2),
+            (at(2, 0), 3),
+        ];
+        for (time, expected) in steps {
+            sender.send_replace(time);
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            assert_eq!(count.load(Ordering::SeqCst), expected, "at {time:?}");
+        }
+    }
+}
@@ -0,0 +1,236 @@
+//! Command line style utilities for inspecting topics, enabled with the `cli-tools` feature
+
+use crate::ros1::{DynamicMessage, DynamicValue, NodeHandle};
+use std::fmt::Write;
+use tokio::sync::broadcast::error::RecvError;
+
+/// Prints every message arriving on a topic to stdout in the same format as `rostopic echo`,
+/// without needing to know the topic's type at compile time.
+///
+/// ```no_run
+/// # async fn echo(nh: roslibrust::ros1::NodeHandle) {
+/// use roslibrust::ros1::topics::TopicEcho;
+/// // Print the next 10 messages then return
+/// TopicEcho::new(nh, "/chatter", Some(10)).run().await;
+/// # }
+/// ```
+pub struct TopicEcho {
+    node: NodeHandle,
+    topic: String,
+    count: Option<u32>,
+}
+
+impl TopicEcho {
+    /// Creates an echo of `topic`, which prints `count` messages or runs until the node shuts
+    /// down if `count` is None. Nothing is subscribed until [TopicEcho::run] is called.
+    pub fn new(node: NodeHandle, topic: &str, count: Option<u32>) -> TopicEcho {
+        TopicEcho {
+            node,
+            topic: topic.to_owned(),
+            count,
+        }
+    }
+
+    /// Subscribes to the topic and prints messages as they arrive, each followed by `---`.
+    /// Messages which fail to decode are logged and skipped.
+    pub async fn run(self) {
+        let mut subscriber = match self.node.subscribe_dynamic(&self.topic, 10).await {
+            Ok(subscriber) => subscriber,
+            Err(err) => {
+                log::error!("Failed to subscribe to {}: {err}", self.topic);
+                return;
+            }
+        };
+        let mut printed = 0;
+        while self.count.map_or(true, |count| printed < count) {
+            match subscriber.next().await {
+                Ok(msg) => {
+                    println!("{}---", format_message(&msg));
+                    printed += 1;
+                }
+                Err(err) => match err.downcast_ref::<RecvError>() {
+                    Some(RecvError::Closed) => {
+                        log::debug!("Subscription to {} closed, stopping echo", self.topic);
+                        return;
+                    }
+                    Some(RecvError::Lagged(skipped)) => {
+                        log::warn!(
+                            "Echo of {} fell behind, skipped {skipped} messages",
+                            self.topic
+                        );
+                    }
+                    None => {
+                        log::warn!("Failed to decode message on {}: {err}", self.topic);
+                    }
+                },
+            }
+        }
+    }
+}
+
+/// Formats a message as `rostopic echo` would, one field per line with nested messages indented
+fn format_message(msg: &DynamicMessage) -> String {
+    let mut out = String::new();
+    write_fields(&mut out, msg, 0);
+    out
+}
+
+fn write_fields(out: &mut String, msg: &DynamicMessage, indent: usize) {
+    for (name, value) in &msg.fields {
+        let _ = write!(out, "{:indent$}{name}:", "");
+        write_value(out, value, indent);
+    }
+}
+
+/// Writes a value following its field name, either inline or as an indented block
+fn write_value(out: &mut String, value: &DynamicValue, indent: usize) {
+    match value {
+        DynamicValue::Message(msg) => {
+            out.push('\n');
+            write_fields(out, msg, indent + 2);
+        }
+        DynamicValue::Time(time) => {
+            let _ = writeln!(
+                out,
+                "\n{:width$}secs: {}\n{:width$}nsecs: {}",
+                "",
+                time.secs,
+                "",
+                time.nsecs,
+                width = indent + 2
+            );
+        }
+        DynamicValue::Duration(duration) => {
+            let _ = writeln!(
+                out,
+                "\n{:width$}secs: {}\n{:width$}nsecs: {}",
+                "",
+                duration.sec,
+                "",
+                duration.nsec,
+                width = indent + 2
+            );
+        }
+        DynamicValue::Array(items) if items.iter().any(is_block) => {
+            out.push('\n');
+            for item in items {
+                let _ = write!(out, "{:width$}-", "", width = indent + 2);
+                write_value(out, item, indent + 2);
+            }
+        }
+        value => {
+            out.push(' ');
+            write_inline(out, value);
+            out.push('\n');
+        }
+    }
+}
+
+fn is_block(value: &DynamicValue) -> bool {
+    matches!(
+        value,
+        DynamicValue::Message(_) | DynamicValue::Time(_) | DynamicValue::Duration(_)
+    )
+}
+
+fn write_inline(out: &mut String, value: &DynamicValue) {
+    let _ = match value {
+        DynamicValue::Bool(true) => write!(out, "True"),
+        DynamicValue::Bool(false) => write!(out, "False"),
+        DynamicValue::I8(v) => write!(out, "{v}"),
+        DynamicValue::U8(v) => write!(out, "{v}"),
+        DynamicValue::I16(v) => write!(out, "{v}"),
+        DynamicValue::U16(v) => write!(out, "{v}"),
+        DynamicValue::I32(v) => write!(out, "{v}"),
+        DynamicValue::U32(v) => write!(out, "{v}"),
+        DynamicValue::I64(v) => write!(out, "{v}"),
+        DynamicValue::U64(v) => write!(out, "{v}"),
+        // Debug formatting always includes a decimal point, like python does
+        DynamicValue::F32(v) => write!(out, "{v:?}"),
+        DynamicValue::F64(v) => write!(out, "{v:?}"),
+        DynamicValue::String(v) => write!(out, "{v:?}"),
+        DynamicValue::Array(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push_str(", ");
+                }
+                write_inline(out, item);
+            }
+            write!(out, "]")
+        }
+        // Only reachable for nested arrays, which ROS messages can't contain
+        block => write!(out, "{block:?}"),
+    };
+}
+
+#[cfg(test)]
+mod test {
+    use super::format_message;
+    use crate::ros1::{DynamicMessage, DynamicValue};
+    use roslibrust_codegen::integral_types::Time;
+
+    fn msg(msg_type: &str, fields: Vec<(&str, DynamicValue)>) -> DynamicMessage {
+        DynamicMessage {
+            msg_type: msg_type.to_owned(),
+            fields: fields
+                .into_iter()
+                .map(|(name, value)| (name.to_owned(), value))
+                .collect(),
+        }
+    }
+
+    #[test_log::test]
+    fn formats_like_rostopic_echo() {
+        let header = msg(
+            "std_msgs/Header",
+            vec![
+                ("seq", DynamicValue::U32(7)),
+                ("stamp", DynamicValue::Time(Time { secs: 1, nsecs: 2 })),
+                ("frame_id", DynamicValue::String("map".to_owned())),
+            ],
+        );
+        let point = |x: f64| {
+            DynamicValue::Message(msg(
+                "geometry_msgs/Point32",
+                vec![
+                    ("x", DynamicValue::F64(x)),
+                    ("valid", DynamicValue::Bool(true)),
+                ],
+            ))
+        };
+        let cloud = msg(
+            "pkg/Cloud",
+            vec![
+                ("header", DynamicValue::Message(header)),
+                ("points", DynamicValue::Array(vec![point(1.0), point(0.5)])),
+                (
+                    "data",
+                    DynamicValue::Array(vec![DynamicValue::U8(1), DynamicValue::U8(2)]),
+                ),
+                ("empty", DynamicValue::Array(vec![])),
+            ],
+        );
+
+        assert_eq!(
+            format_message(&cloud),
+            "\
+header:
+  seq: 7
+  stamp:
+    secs: 1
+    nsecs: 2
+  frame_id: \"map\"
+points:
+  -
+    x: 1.0
+    valid: True
+  -
+    x: 0.5
+    valid: True
+data: [1, 2]
+empty: []
+"
+        );
+    }
+}
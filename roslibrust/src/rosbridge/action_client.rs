@@ -0,0 +1,630 @@
+// This file / module covers the client side of ROS1 actionlib implemented on top of rosbridge topics
+// An action is made up of five topics under a shared namespace:
+//   <ns>/goal, <ns>/cancel (client -> server)
+//   <ns>/status, <ns>/feedback, <ns>/result (server -> client)
+
+use crate::{ClientHandle, Publisher, RosLibRustResult, Subscriber};
+use abort_on_drop::ChildTask;
+use dashmap::DashMap;
+use log::{debug, warn};
+use roslibrust_codegen::{RosActionType, RosMessageType, Time};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::sync::Arc;
+use tokio::sync::{mpsc, watch};
+use tokio::time::Duration;
+
+/// How long a goal may go without being acknowledged by the server before [GoalHandle::result] gives up
+const DEFAULT_ACK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Maximum number of feedback messages buffered per goal, oldest feedback is dropped once full
+const FEEDBACK_QUEUE_SIZE: usize = 100;
+
+// Local mirrors of the actionlib_msgs types, rosbridge only needs the JSON representation to match
+#[derive(Deserialize, Serialize, Debug, Default, Clone, PartialEq)]
+struct Header {
+    seq: u32,
+    stamp: Time,
+    frame_id: String,
+}
+
+#[derive(Deserialize, Serialize, Debug, Default, Clone, PartialEq)]
+struct GoalID {
+    stamp: Time,
+    id: String,
+}
+
+impl RosMessageType for GoalID {
+    const ROS_TYPE_NAME: &'static str = "actionlib_msgs/GoalID";
+}
+
+#[derive(Deserialize, Serialize, Debug, Default, Clone, PartialEq)]
+struct GoalStatus {
+    goal_id: GoalID,
+    status: u8,
+    text: String,
+}
+
+#[derive(Deserialize, Serialize, Debug, Default, Clone, PartialEq)]
+struct GoalStatusArray {
+    header: Header,
+    status_list: Vec<GoalStatus>,
+}
+
+impl RosMessageType for GoalStatusArray {
+    const ROS_TYPE_NAME: &'static str = "actionlib_msgs/GoalStatusArray";
+}
+
+/// Matches the status constants of actionlib_msgs/GoalStatus
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GoalState {
+    Pending,
+    Active,
+    Preempted,
+    Succeeded,
+    Aborted,
+    Rejected,
+    Preempting,
+    Recalling,
+    Recalled,
+    /// The goal disappeared from the server's status without a result being received
+    Lost,
+}
+
+impl GoalState {
+    /// True if the server will not send further updates for a goal in this state
+    pub fn is_terminal(&self) -> bool {
+        matches!(
+            self,
+            GoalState::Preempted
+                | GoalState::Succeeded
+                | GoalState::Aborted
+                | GoalState::Rejected
+                | GoalState::Recalled
+                | GoalState::Lost
+        )
+    }
+}
+
+impl From<u8> for GoalState {
+    fn from(status: u8) -> Self {
+        match status {
+            0 => GoalState::Pending,
+            1 => GoalState::Active,
+            2 => GoalState::Preempted,
+            3 => GoalState::Succeeded,
+            4 => GoalState::Aborted,
+            5 => GoalState::Rejected,
+            6 => GoalState::Preempting,
+            7 => GoalState::Recalling,
+            8 => GoalState::Recalled,
+            _ => GoalState::Lost,
+        }
+    }
+}
+
+/// The final outcome of a goal as returned by [GoalHandle::result]
+#[derive(Debug, Clone, PartialEq)]
+pub enum GoalOutcome<R> {
+    Succeeded(R),
+    Aborted(R),
+    /// The goal was canceled after the server started executing it
+    Preempted(R),
+    Rejected(R),
+    /// The goal was canceled before the server started executing it
+    Recalled(R),
+    /// The server stopped reporting on the goal without ever sending a result
+    Lost,
+}
+
+/// State shared between the dispatch task and a goal handle
+#[derive(Debug, Clone)]
+struct GoalProgress<R> {
+    state: GoalState,
+    /// Set once the server has shown any sign of having received the goal
+    acknowledged: bool,
+    /// Set once the goal has been seen in the server's status list, after which disappearing
+    /// from the list means the goal was lost
+    seen_in_status: bool,
+    result: Option<R>,
+}
+
+struct GoalTracker<A: RosActionType> {
+    progress: watch::Sender<GoalProgress<A::Result>>,
+    feedback: mpsc::Sender<A::Feedback>,
+}
+
+type GoalMap<A> = Arc<DashMap<String, GoalTracker<A>>>;
+
+/// A client for a single ROS1 action server, reached through rosbridge.
+///
+/// ```no_run
+/// # async fn doc<A: roslibrust_codegen::RosActionType>(goal: A::Goal) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+///   let client = roslibrust::ClientHandle::new("ws://localhost:9090").await?;
+///   let action_client = roslibrust::ActionClient::<A>::new(&client, "/fibonacci").await?;
+///   let goal_handle = action_client.send_goal(goal).await?;
+///   let outcome = goal_handle.result().await?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// Dropping the action client stops all tracking of its goals, but does not cancel them.
+pub struct ActionClient<A: RosActionType> {
+    goal_publisher: Publisher<A::ActionGoal>,
+    // Shared with goal handles so they can cancel themselves
+    cancel_publisher: Arc<Publisher<GoalID>>,
+    goals: GoalMap<A>,
+    ack_timeout: Duration,
+    _dispatch_task: ChildTask<()>,
+}
+
+impl<A: RosActionType> ActionClient<A> {
+    /// Advertises the goal and cancel topics and subscribes to the status, feedback, and result
+    /// topics under `action_ns`.
+    pub async fn new(client: &ClientHandle, action_ns: &str) -> RosLibRustResult<Self> {
+        let action_ns = client.resolve_name(action_ns.trim_end_matches('/')).await?;
+        let goal_publisher = client.advertise(&format!("{action_ns}/goal")).await?;
+        let cancel_publisher = Arc::new(client.advertise(&format!("{action_ns}/cancel")).await?);
+        let status = client.subscribe(&format!("{action_ns}/status")).await?;
+        let feedback = client.subscribe(&format!("{action_ns}/feedback")).await?;
+        let result = client.subscribe(&format!("{action_ns}/result")).await?;
+
+        let goals: GoalMap<A> = Arc::new(DashMap::new());
+        let dispatch_task = tokio::spawn(dispatch::<A>(goals.clone(), status, feedback, result));
+
+        Ok(Self {
+            goal_publisher,
+            cancel_publisher,
+            goals,
+            ack_timeout: DEFAULT_ACK_TIMEOUT,
+            _dispatch_task: dispatch_task.into(),
+        })
+    }
+
+    /// Sets how long goals may go without being acknowledged by the server before
+    /// [GoalHandle::result] fails with a timeout. Defaults to 5 seconds.
+    pub fn ack_timeout<T: Into<Duration>>(mut self, duration: T) -> Self {
+        self.ack_timeout = duration.into();
+        self
+    }
+
+    /// Publishes a new goal to the action server and returns a handle to track it.
+    pub async fn send_goal(&self, goal: A::Goal) -> RosLibRustResult<GoalHandle<A>> {
+        let stamp: Time = std::time::SystemTime::now().into();
+        let goal_id = GoalID {
+            stamp,
+            id: format!("roslibrust-{}", uuid::Uuid::new_v4()),
+        };
+        let action_goal = serde_json::from_value::<A::ActionGoal>(json!({
+            "header": Header { stamp, ..Default::default() },
+            "goal_id": goal_id,
+            "goal": goal,
+        }))?;
+
+        // Start tracking before publishing so that a fast server can't beat us
+        let (progress, progress_rx) = watch::channel(GoalProgress {
+            state: GoalState::Pending,
+            acknowledged: false,
+            seen_in_status: false,
+            result: None,
+        });
+        let (feedback, feedback_rx) = mpsc::channel(FEEDBACK_QUEUE_SIZE);
+        self.goals
+            .insert(goal_id.id.clone(), GoalTracker { progress, feedback });
+
+        if let Err(e) = self.goal_publisher.publish(action_goal).await {
+            self.goals.remove(&goal_id.id);
+            return Err(e);
+        }
+        debug!("Sent goal {} to {}", goal_id.id, A::ROS_ACTION_NAME);
+
+        Ok(GoalHandle {
+            id: goal_id.id,
+            progress: progress_rx,
+            feedback: feedback_rx,
+            cancel_publisher: self.cancel_publisher.clone(),
+            goals: self.goals.clone(),
+            ack_timeout: self.ack_timeout,
+        })
+    }
+
+    /// Requests that the server cancel every goal it is currently tracking, including goals
+    /// sent by other clients.
+    pub async fn cancel_all(&self) -> RosLibRustResult<()> {
+        self.cancel_publisher.publish(GoalID::default()).await
+    }
+}
+
+/// Tracks a single goal sent with [ActionClient::send_goal].
+///
+/// Dropping the handle stops tracking the goal, but does not cancel it.
+pub struct GoalHandle<A: RosActionType> {
+    id: String,
+    progress: watch::Receiver<GoalProgress<A::Result>>,
+    feedback: mpsc::Receiver<A::Feedback>,
+    cancel_publisher: Arc<Publisher<GoalID>>,
+    goals: GoalMap<A>,
+    ack_timeout: Duration,
+}
+
+impl<A: RosActionType> GoalHandle<A> {
+    /// The unique id this goal was sent with
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// The most recent state reported by the server for this goal
+    pub fn state(&self) -> GoalState {
+        self.progress.borrow().state
+    }
+
+    /// Returns the next feedback message for this goal, or None once the goal has finished
+    /// and all buffered feedback has been consumed.
+    pub async fn next_feedback(&mut self) -> Option<A::Feedback> {
+        self.feedback.recv().await
+    }
+
+    /// A stream of the feedback for this goal, which ends once the goal has finished.
+    pub fn feedback(&mut self) -> impl futures::Stream<Item = A::Feedback> + Unpin + '_ {
+        futures::stream::poll_fn(move |cx| self.feedback.poll_recv(cx))
+    }
+
+    /// Waits for the goal to finish and returns its outcome.
+    ///
+    /// Fails with [RosLibRustError::Timeout](crate::RosLibRustError::Timeout) if the server does
+    /// not acknowledge the goal within the client's configured ack timeout.
+    pub async fn result(&self) -> RosLibRustResult<GoalOutcome<A::Result>> {
+        let mut progress = self.progress.clone();
+        tokio::time::timeout(
+            self.ack_timeout,
+            progress.wait_for(|progress| progress.acknowledged),
+        )
+        .await?
+        .map_err(|_| goal_dropped())?;
+        let progress = progress
+            .wait_for(|progress| progress.state.is_terminal())
+            .await
+            .map_err(|_| goal_dropped())?
+            .clone();
+
+        Ok(match (progress.state, progress.result) {
+            (GoalState::Succeeded, Some(result)) => GoalOutcome::Succeeded(result),
+            (GoalState::Aborted, Some(result)) => GoalOutcome::Aborted(result),
+            (GoalState::Preempted, Some(result)) => GoalOutcome::Preempted(result),
+            (GoalState::Rejected, Some(result)) => GoalOutcome::Rejected(result),
+            (GoalState::Recalled, Some(result)) => GoalOutcome::Recalled(result),
+            _ => GoalOutcome::Lost,
+        })
+    }
+
+    /// Requests that the server cancel this goal, the outcome of the cancellation is reported
+    /// through [GoalHandle::result].
+    pub async fn cancel(&self) -> RosLibRustResult<()> {
+        self.cancel_publisher
+            .publish(GoalID {
+                stamp: Time::default(),
+                id: self.id.clone(),
+            })
+            .await
+    }
+}
+
+impl<A: RosActionType> Drop for GoalHandle<A> {
+    fn drop(&mut self) {
+        self.goals.remove(&self.id);
+    }
+}
+
+// The dispatch task is the only thing that ever drops the progress sender while the handle is alive
+fn goal_dropped() -> crate::RosLibRustError {
+    crate::RosLibRustError::Unexpected(anyhow::anyhow!(
+        "Action client was dropped while goal was in progress"
+    ))
+}
+
+/// Routes messages from the server-side topics to the handles of the goals they refer to
+async fn dispatch<A: RosActionType>(
+    goals: GoalMap<A>,
+    status: Subscriber<GoalStatusArray>,
+    feedback: Subscriber<A::ActionFeedback>,
+    result: Subscriber<A::ActionResult>,
+) {
+    loop {
+        tokio::select! {
+            msg = status.next() => handle_status(&goals, msg),
+            msg = feedback.next() => handle_feedback(&goals, msg),
+            msg = result.next() => handle_result(&goals, msg),
+        }
+    }
+}
+
+fn handle_status<A: RosActionType>(goals: &GoalMap<A>, msg: GoalStatusArray) {
+    for tracker in goals.iter() {
+        let status = msg
+            .status_list
+            .iter()
+            .find(|status| &status.goal_id.id == tracker.key());
+        tracker.progress.send_if_modified(|progress| {
+            if progress.state.is_terminal() {
+                return false;
+            }
+            match status {
+                Some(status) => {
+                    let state = GoalState::from(status.status);
+                    progress.acknowledged = true;
+                    progress.seen_in_status = true;
+                    // Terminal states are only applied once the result arrives with them
+                    if !state.is_terminal() {
+                        progress.state = state;
+                    }
+                    true
+                }
+                None if progress.seen_in_status => {
+                    warn!(
+                        "Goal {} disappeared from server status without a result, marking lost",
+                        tracker.key()
+                    );
+                    progress.state = GoalState::Lost;
+                    true
+                }
+                None => false,
+            }
+        });
+    }
+}
+
+fn handle_feedback<A: RosActionType>(goals: &GoalMap<A>, msg: A::ActionFeedback) {
+    let (status, feedback) = match split_action_msg::<A::Feedback>(msg, "feedback") {
+        Some(split) => split,
+        None => return,
+    };
+    if let Some(tracker) = goals.get(&status.goal_id.id) {
+        tracker.progress.send_modify(|progress| {
+            progress.acknowledged = true;
+        });
+        if tracker.feedback.try_send(feedback).is_err() {
+            warn!(
+                "Feedback queue for goal {} is full, dropping feedback",
+                status.goal_id.id
+            );
+        }
+    }
+}
+
+fn handle_result<A: RosActionType>(goals: &GoalMap<A>, msg: A::ActionResult) {
+    let (status, result) = match split_action_msg::<A::Result>(msg, "result") {
+        Some(split) => split,
+        None => return,
+    };
+    // Removing the tracker closes the feedback stream, the handle keeps the final progress
+    if let Some((_id, tracker)) = goals.remove(&status.goal_id.id) {
+        tracker.progress.send_modify(|progress| {
+            progress.acknowledged = true;
+            progress.state = GoalState::from(status.status);
+            progress.result = Some(result);
+        });
+    }
+}
+
+/// Splits an ActionFeedback or ActionResult message into its status and inner payload
+fn split_action_msg<T: RosMessageType>(
+    msg: impl Serialize,
+    payload_field: &str,
+) -> Option<(GoalStatus, T)> {
+    let mut value = match serde_json::to_value(msg) {
+        Ok(value) => value,
+        Err(e) => {
+            warn!("Failed to convert action {payload_field} message: {e:?}");
+            return None;
+        }
+    };
+    let status = serde_json::from_value(value["status"].take());
+    let payload = serde_json::from_value(value[payload_field].take());
+    match (status, payload) {
+        (Ok(status), Ok(payload)) => Some((status, payload)),
+        (status, payload) => {
+            warn!(
+                "Failed to extract status and {payload_field} from action message: {:?} {:?}",
+                status.err(),
+                payload.err()
+            );
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::rosbridge::mock_bridge::MockBridge;
+    use crate::RosLibRustError;
+    use futures::StreamExt;
+    use serde_json::Value;
+
+    #[derive(Deserialize, Serialize, Debug, Default, Clone, PartialEq)]
+    struct FibonacciGoal {
+        order: i32,
+    }
+    impl RosMessageType for FibonacciGoal {
+        const ROS_TYPE_NAME: &'static str = "actionlib_tutorials/FibonacciGoal";
+    }
+
+    #[derive(Deserialize, Serialize, Debug, Default, Clone, PartialEq)]
+    struct FibonacciSequence {
+        sequence: Vec<i32>,
+    }
+    impl RosMessageType for FibonacciSequence {
+        const ROS_TYPE_NAME: &'static str = "actionlib_tutorials/FibonacciResult";
+    }
+
+    #[derive(Deserialize, Serialize, Debug, Default, Clone, PartialEq)]
+    struct FibonacciActionGoal {
+        header: Header,
+        goal_id: GoalID,
+        goal: FibonacciGoal,
+    }
+    impl RosMessageType for FibonacciActionGoal {
+        const ROS_TYPE_NAME: &'static str = "actionlib_tutorials/FibonacciActionGoal";
+    }
+
+    #[derive(Deserialize, Serialize, Debug, Default, Clone, PartialEq)]
+    struct FibonacciActionFeedback {
+        header: Header,
+        status: GoalStatus,
+        feedback: FibonacciSequence,
+    }
+    impl RosMessageType for FibonacciActionFeedback {
+        const ROS_TYPE_NAME: &'static str = "actionlib_tutorials/FibonacciActionFeedback";
+    }
+
+    #[derive(Deserialize, Serialize, Debug, Default, Clone, PartialEq)]
+    struct FibonacciActionResult {
+        header: Header,
+        status: GoalStatus,
+        result: FibonacciSequence,
+    }
+    impl RosMessageType for FibonacciActionResult {
+        const ROS_TYPE_NAME: &'static str = "actionlib_tutorials/FibonacciActionResult";
+    }
+
+    struct FibonacciAction;
+    impl RosActionType for FibonacciAction {
+        const ROS_ACTION_NAME: &'static str = "actionlib_tutorials/Fibonacci";
+        type Goal = FibonacciGoal;
+        type Feedback = FibonacciSequence;
+        type Result = FibonacciSequence;
+        type ActionGoal = FibonacciActionGoal;
+        type ActionFeedback = FibonacciActionFeedback;
+        type ActionResult = FibonacciActionResult;
+    }
+
+    const TIMEOUT: Duration = Duration::from_secs(2);
+
+    async fn setup() -> (MockBridge, ClientHandle, ActionClient<FibonacciAction>) {
+        let mut bridge = MockBridge::new().await;
+        let client = ClientHandle::new(bridge.url()).await.unwrap();
+        let action_client = ActionClient::<FibonacciAction>::new(&client, "/fibonacci")
+            .await
+            .unwrap();
+        // Wait for the last subscription so we know the client is ready to receive
+        bridge.next_op("subscribe", "/fibonacci/result").await;
+        (bridge, client, action_client)
+    }
+
+    /// Sends a goal and returns its handle along with the goal id received by the server
+    async fn send_goal(
+        bridge: &mut MockBridge,
+        action_client: &ActionClient<FibonacciAction>,
+    ) -> (GoalHandle<FibonacciAction>, Value) {
+        let handle = action_client
+            .send_goal(FibonacciGoal { order: 3 })
+            .await
+            .unwrap();
+        let goal = bridge.next_op("publish", "/fibonacci/goal").await;
+        assert_eq!(goal["msg"]["goal"]["order"], 3);
+        assert_eq!(goal["msg"]["goal_id"]["id"], handle.id());
+        (handle, goal["msg"]["goal_id"].clone())
+    }
+
+    fn status(goal_id: &Value, status: u8) -> Value {
+        json!({"goal_id": goal_id, "status": status, "text": ""})
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn goal_lifecycle_succeeds() {
+        let (mut bridge, _client, action_client) = setup().await;
+        let (mut handle, goal_id) = send_goal(&mut bridge, &action_client).await;
+
+        bridge.publish(
+            "/fibonacci/status",
+            json!({"header": Header::default(), "status_list": [status(&goal_id, 1)]}),
+        );
+        bridge.publish(
+            "/fibonacci/feedback",
+            json!({"header": Header::default(), "status": status(&goal_id, 1), "feedback": {"sequence": [0, 1]}}),
+        );
+        let feedback = tokio::time::timeout(TIMEOUT, handle.feedback().next())
+            .await
+            .unwrap();
+        assert_eq!(
+            feedback,
+            Some(FibonacciSequence {
+                sequence: vec![0, 1]
+            })
+        );
+        assert_eq!(handle.state(), GoalState::Active);
+
+        bridge.publish(
+            "/fibonacci/result",
+            json!({"header": Header::default(), "status": status(&goal_id, 3), "result": {"sequence": [0, 1, 1]}}),
+        );
+        let outcome = tokio::time::timeout(TIMEOUT, handle.result())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            outcome,
+            GoalOutcome::Succeeded(FibonacciSequence {
+                sequence: vec![0, 1, 1]
+            })
+        );
+        // Feedback stream ends once the goal is finished
+        assert_eq!(handle.next_feedback().await, None);
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn cancel_preempts_goal() {
+        let (mut bridge, _client, action_client) = setup().await;
+        let (handle, goal_id) = send_goal(&mut bridge, &action_client).await;
+
+        handle.cancel().await.unwrap();
+        let cancel = bridge.next_op("publish", "/fibonacci/cancel").await;
+        assert_eq!(cancel["msg"]["id"], handle.id());
+
+        bridge.publish(
+            "/fibonacci/result",
+            json!({"header": Header::default(), "status": status(&goal_id, 2), "result": {"sequence": [0]}}),
+        );
+        let outcome = tokio::time::timeout(TIMEOUT, handle.result())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            outcome,
+            GoalOutcome::Preempted(FibonacciSequence { sequence: vec![0] })
+        );
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn goal_missing_from_status_is_lost() {
+        let (mut bridge, _client, action_client) = setup().await;
+        let (handle, goal_id) = send_goal(&mut bridge, &action_client).await;
+
+        bridge.publish(
+            "/fibonacci/status",
+            json!({"header": Header::default(), "status_list": [status(&goal_id, 1)]}),
+        );
+        bridge.publish(
+            "/fibonacci/status",
+            json!({"header": Header::default(), "status_list": []}),
+        );
+        let outcome = tokio::time::timeout(TIMEOUT, handle.result())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(outcome, GoalOutcome::Lost);
+        assert_eq!(handle.state(), GoalState::Lost);
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn unacknowledged_goal_times_out() {
+        let (mut bridge, _client, action_client) = setup().await;
+        let action_client = action_client.ack_timeout(Duration::from_millis(100));
+        let (handle, _goal_id) = send_goal(&mut bridge, &action_client).await;
+
+        let result = tokio::time::timeout(TIMEOUT, handle.result())
+            .await
+            .unwrap();
+        assert!(matches!(result, Err(RosLibRustError::Timeout(_))));
+    }
+}
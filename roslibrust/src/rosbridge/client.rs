@@ -1,48 +1,938 @@
 use crate::rosbridge::comm;
 use crate::{rosbridge::comm::RosBridgeComm, RosLibRustError};
-use crate::{Publisher, ServiceHandle, Subscriber};
+use crate::{InvalidName, Namespace, Publisher, ServiceHandle, SubscribeOnceError, Subscriber};
 use anyhow::anyhow;
+use bytes::Bytes;
 use dashmap::DashMap;
-use futures::StreamExt;
 use log::*;
-use roslibrust_codegen::{RosMessageType, RosServiceType};
+use roslibrust_codegen::{utils::RosVersion, RosMessageType, RosServiceType};
 use serde_json::Value;
 use std::collections::HashMap;
+use std::ops::Range;
 use std::str::FromStr;
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use tokio::sync::{
+    broadcast, watch, Notify, OwnedSemaphorePermit, RwLock, RwLockReadGuard, Semaphore,
+};
 use tokio::time::Duration;
-use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::tungstenite::protocol::WebSocketConfig;
 
+use super::message_details::{MessageDetailsRequest, MessageDetailsResponse};
+use super::metrics::{Gauge, Metrics, MetricsSnapshot, TopicCounters};
+use super::transport::{self, Fragmentation, Frame, ServerClose, TcpFraming};
 use super::{
-    MessageQueue, PublisherHandle, Reader, RosLibRustResult, ServiceCallback, Socket, Subscription,
-    Writer, QUEUE_SIZE,
+    Callback, MessageQueue, PublisherHandle, Reader, ReceivedMsg, RosLibRustResult, ServiceServer,
+    Subscription, Writer, QUEUE_SIZE,
 };
 
+/// Number of connection events buffered for each receiver of [ClientHandle::connection_events]
+const CONNECTION_EVENT_QUEUE_SIZE: usize = 16;
+
+/// Default for [ClientHandleOptions::max_pending_operations]
+const DEFAULT_MAX_PENDING_OPERATIONS: usize = 1000;
+
+/// How long operations wait for a lost connection to be re-established when neither
+/// [ClientHandleOptions::pending_operation_timeout] nor [ClientHandleOptions::timeout] is set
+const DEFAULT_PENDING_OPERATION_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Default for [ClientHandleOptions::write_queue_size]
+const DEFAULT_WRITE_QUEUE_SIZE: usize = 1000;
+
+/// Default for [ClientHandleOptions::handshake_timeout]
+const DEFAULT_HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Default for [ClientHandleOptions::service_poll_interval]
+const DEFAULT_SERVICE_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// How long to wait for the reply to a server's close frame to be sent before reconnecting
+const CLOSE_REPLY_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Default for [ClientHandleOptions::max_message_size], room for dense point clouds and images
+/// after JSON encoding has inflated them
+const DEFAULT_MAX_MESSAGE_SIZE: usize = 256 << 20;
+
+/// Default for [ClientHandleOptions::max_frame_size], the same as for messages as rosbridge sends
+/// each message as a single frame
+const DEFAULT_MAX_FRAME_SIZE: usize = 256 << 20;
+
+/// How long to wait between attempts to connect to rosbridge, used both when first connecting and
+/// when reconnecting after the connection is lost. Also how long to wait between the attempts of
+/// a service call, see [RetryPolicy::backoff].
+#[derive(Clone, Debug, PartialEq)]
+pub enum ReconnectPolicy {
+    /// Waits the same amount of time after every failed attempt
+    Fixed(Duration),
+    /// Waits `initial` after the first failed attempt, doubling the wait after each further
+    /// failure up to at most `max`
+    ExponentialBackoff { initial: Duration, max: Duration },
+    /// Backs off with a configurable multiplier and optional jitter, and can give up after a
+    /// number of attempts
+    Backoff(Backoff),
+}
+
+impl Default for ReconnectPolicy {
+    fn default() -> Self {
+        ReconnectPolicy::Fixed(Duration::from_millis(200))
+    }
+}
+
+impl ReconnectPolicy {
+    /// How long to wait after `failures` consecutive failed attempts, before any jitter
+    fn delay(&self, failures: u32) -> Duration {
+        match self {
+            ReconnectPolicy::Fixed(delay) => *delay,
+            ReconnectPolicy::ExponentialBackoff { initial, max } => initial
+                .checked_mul(2u32.saturating_pow(failures.saturating_sub(1)))
+                .map_or(*max, |delay| delay.min(*max)),
+            ReconnectPolicy::Backoff(backoff) => {
+                let exponent = failures.saturating_sub(1).min(i32::MAX as u32) as i32;
+                let delay = backoff.initial.as_secs_f64() * backoff.multiplier.powi(exponent);
+                // Overflowing, negative, or NaN delays fall back to the maximum
+                Duration::try_from_secs_f64(delay)
+                    .map_or(backoff.max, |delay| delay.min(backoff.max))
+            }
+        }
+    }
+
+    /// How long to actually wait after `failures` consecutive failed attempts, applying jitter
+    fn jittered_delay(&self, failures: u32, rng: &mut impl rand::Rng) -> Duration {
+        let delay = self.delay(failures);
+        match self {
+            ReconnectPolicy::Backoff(Backoff { jitter: true, .. }) => {
+                rng.gen_range(Duration::ZERO..=delay)
+            }
+            _ => delay,
+        }
+    }
+
+    /// How many consecutive failed attempts to make before giving up, if limited
+    fn max_attempts(&self) -> Option<u32> {
+        match self {
+            ReconnectPolicy::Backoff(backoff) => backoff.max_attempts,
+            _ => None,
+        }
+    }
+}
+
+/// Exponential backoff between connection attempts, see [ReconnectPolicy::Backoff].
+///
+/// When many clients lose their connection to the same rosbridge at once, jitter keeps them from
+/// all retrying in lockstep.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Backoff {
+    /// Wait after the first failed attempt
+    pub initial: Duration,
+    /// Factor the wait is multiplied by after each further failed attempt
+    pub multiplier: f64,
+    /// Longest wait between attempts
+    pub max: Duration,
+    /// Waits a random duration between zero and the backed off wait instead ("full jitter")
+    pub jitter: bool,
+    /// Gives up after this many consecutive failed attempts, after which the client's
+    /// [ConnectionState] is [ConnectionState::Failed]. Retries forever if not set.
+    pub max_attempts: Option<u32>,
+}
+
+impl Default for Backoff {
+    /// Starts at 200 milliseconds, doubling up to 30 seconds with full jitter, retrying forever
+    fn default() -> Self {
+        Backoff {
+            initial: Duration::from_millis(200),
+            multiplier: 2.0,
+            max: Duration::from_secs(30),
+            jitter: true,
+            max_attempts: None,
+        }
+    }
+}
+
+/// Compression rosbridge applies to messages it sends for our subscriptions
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Compression {
+    /// Messages are sent as json text
+    #[default]
+    None,
+    /// Messages are sent as binary [CBOR](https://cbor.io), which is considerably smaller and
+    /// faster to decode for messages containing large numeric arrays such as images and point
+    /// clouds. Requires rosbridge 0.11.6 or newer.
+    Cbor,
+}
+
+impl Compression {
+    /// Name used for this format in the `compression` field of subscribe operations
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            Compression::None => "none",
+            Compression::Cbor => "cbor",
+        }
+    }
+}
+
 /// Builder options for creating a client
+///
+/// Prefer configuring clients with [ClientHandle::builder], which accepts all of these options.
 #[derive(Clone)]
 pub struct ClientHandleOptions {
     url: String,
     timeout: Option<Duration>,
+    connect_timeout: Option<Duration>,
+    handshake_timeout: Option<Duration>,
+    default_service_call_timeout: Option<Duration>,
+    publish_flush_timeout: Option<Duration>,
+    pending_operation_timeout: Option<Duration>,
+    max_pending_operations: usize,
+    write_queue_size: usize,
+    reconnect: ReconnectPolicy,
+    fatal_close_codes: Vec<u16>,
+    default_compression: Compression,
+    subscriber_queue_size: usize,
+    ros_version: RosVersion,
+    collect_metrics: bool,
+    tcp_framing: TcpFraming,
+    websocket_config: WebSocketConfig,
+    service_poll_interval: Duration,
+    namespace: Option<Namespace>,
+    fragmentation: Option<Fragmentation>,
 }
 
 impl ClientHandleOptions {
-    /// Expects a fully describe websocket url, e.g. 'ws://localhost:9090'
+    /// Expects a fully describe websocket url, e.g. 'ws://localhost:9090', or a rosbridge_tcp url,
+    /// e.g. 'tcp://localhost:9090'
     pub fn new<S: Into<String>>(url: S) -> ClientHandleOptions {
         ClientHandleOptions {
             url: url.into(),
             timeout: None,
+            connect_timeout: None,
+            handshake_timeout: Some(DEFAULT_HANDSHAKE_TIMEOUT),
+            default_service_call_timeout: None,
+            publish_flush_timeout: None,
+            pending_operation_timeout: None,
+            max_pending_operations: DEFAULT_MAX_PENDING_OPERATIONS,
+            write_queue_size: DEFAULT_WRITE_QUEUE_SIZE,
+            reconnect: ReconnectPolicy::default(),
+            fatal_close_codes: vec![1008],
+            default_compression: Compression::None,
+            subscriber_queue_size: QUEUE_SIZE,
+            ros_version: RosVersion::ROS1,
+            collect_metrics: true,
+            tcp_framing: TcpFraming::default(),
+            websocket_config: WebSocketConfig {
+                max_message_size: Some(DEFAULT_MAX_MESSAGE_SIZE),
+                max_frame_size: Some(DEFAULT_MAX_FRAME_SIZE),
+                ..WebSocketConfig::default()
+            },
+            service_poll_interval: DEFAULT_SERVICE_POLL_INTERVAL,
+            namespace: None,
+            fragmentation: None,
         }
     }
 
     /// Configures a default timeout for all operations.
     /// Underlying communication implementations may define their own timeouts, this options does
     /// not affect those timeouts, but adds an additional on top to preempt any operations.
+    ///
+    /// The more specific timeouts below take precedence over this one where they are set.
     pub fn timeout<T: Into<Duration>>(mut self, duration: T) -> ClientHandleOptions {
         self.timeout = Some(duration.into());
         self
     }
+
+    /// Limits how long [ClientHandle::new_with_options] waits for the first connection to be
+    /// established, failing with [RosLibRustError::ConnectTimeout].
+    /// Connection attempts are retried until then, as rosbridge may still be starting up.
+    ///
+    /// Defaults to the general [ClientHandleOptions::timeout], or waiting forever if that isn't set.
+    pub fn connect_timeout<T: Into<Duration>>(mut self, duration: T) -> ClientHandleOptions {
+        self.connect_timeout = Some(duration.into());
+        self
+    }
+
+    /// Limits how long each connection attempt may take to open the connection and complete the
+    /// websocket handshake. When the first connection times out
+    /// [ClientHandle::new_with_options] fails with [RosLibRustError::HandshakeTimeout], as a
+    /// server which accepts connections but never completes the handshake is not rosbridge.
+    /// Reconnection attempts which time out are retried.
+    ///
+    /// Defaults to 10 seconds.
+    pub fn handshake_timeout<T: Into<Duration>>(mut self, duration: T) -> ClientHandleOptions {
+        self.handshake_timeout = Some(duration.into());
+        self
+    }
+
+    /// Limits how long [ClientHandle::call_service] waits for a response, failing with
+    /// [RosLibRustError::ServiceCallTimeout].
+    /// Can be overridden per call with [ClientHandle::call_service_with_timeout].
+    ///
+    /// Defaults to the general [ClientHandleOptions::timeout], or waiting forever if that isn't set.
+    pub fn default_service_call_timeout<T: Into<Duration>>(
+        mut self,
+        duration: T,
+    ) -> ClientHandleOptions {
+        self.default_service_call_timeout = Some(duration.into());
+        self
+    }
+
+    /// Limits how long publishing waits for the message to be written to the websocket, failing
+    /// with [RosLibRustError::PublishFlushTimeout]. Writes only block when rosbridge isn't keeping
+    /// up with the messages being sent.
+    ///
+    /// Defaults to the general [ClientHandleOptions::timeout], or waiting forever if that isn't set.
+    pub fn publish_flush_timeout<T: Into<Duration>>(mut self, duration: T) -> ClientHandleOptions {
+        self.publish_flush_timeout = Some(duration.into());
+        self
+    }
+
+    /// Limits how long operations issued while the client is reconnecting wait for the connection
+    /// to be re-established, failing with [RosLibRustError::Disconnected] if it isn't. Waiting for
+    /// a client from [ClientHandleBuilder::build_unconnected] to first connect is instead limited
+    /// by [ClientHandleOptions::connect_timeout].
+    ///
+    /// Defaults to the general [ClientHandleOptions::timeout], or 30 seconds if that isn't set.
+    pub fn pending_operation_timeout<T: Into<Duration>>(
+        mut self,
+        duration: T,
+    ) -> ClientHandleOptions {
+        self.pending_operation_timeout = Some(duration.into());
+        self
+    }
+
+    /// Configures how many operations can be waiting for the client to connect or reconnect at
+    /// once, further operations fail immediately with [RosLibRustError::TooManyPendingOperations].
+    ///
+    /// Defaults to 1000.
+    pub fn max_pending_operations(mut self, max: usize) -> ClientHandleOptions {
+        self.max_pending_operations = max;
+        self
+    }
+
+    /// Configures how many operations can be queued to be written to rosbridge. Once the queue is
+    /// full, e.g. because rosbridge isn't keeping up with publishes, further operations wait for
+    /// room, which counts against their timeouts such as [ClientHandleOptions::publish_flush_timeout].
+    /// An operation which times out while waiting for room is never sent, but one which was
+    /// already queued is still sent after its caller has given up on it.
+    ///
+    /// Defaults to 1000.
+    pub fn write_queue_size(mut self, size: usize) -> ClientHandleOptions {
+        self.write_queue_size = size;
+        self
+    }
+
+    /// Configures how long to wait between attempts to connect to rosbridge, and whether to give
+    /// up after a number of attempts. Can be replaced later with [ClientHandle::set_reconnect_policy].
+    ///
+    /// Defaults to retrying every 200 milliseconds.
+    pub fn reconnect(mut self, policy: ReconnectPolicy) -> ClientHandleOptions {
+        self.reconnect = policy;
+        self
+    }
+
+    /// Configures the websocket close codes after which the client gives up rather than
+    /// reconnecting, when rosbridge or a proxy in front of it closes the connection with one.
+    /// The client's [ConnectionState] is then [ConnectionState::Failed], and operations fail
+    /// with the error for the close code.
+    ///
+    /// Defaults to 1008 (policy violation), which servers send after failing authentication, as
+    /// reconnecting with the same credentials would fail the same way.
+    pub fn fatal_close_codes(
+        mut self,
+        codes: impl IntoIterator<Item = u16>,
+    ) -> ClientHandleOptions {
+        self.fatal_close_codes = codes.into_iter().collect();
+        self
+    }
+
+    /// Configures the compression rosbridge is asked to use for every subscription.
+    ///
+    /// Defaults to [Compression::None].
+    pub fn default_compression(mut self, compression: Compression) -> ClientHandleOptions {
+        self.default_compression = compression;
+        self
+    }
+
+    /// Configures how many messages each subscriber buffers before the oldest are dropped.
+    ///
+    /// Defaults to 1000.
+    pub fn subscriber_queue_size(mut self, size: usize) -> ClientHandleOptions {
+        self.subscriber_queue_size = size;
+        self
+    }
+
+    /// Configures which version of ROS the rosbridge server is running.
+    ///
+    /// Generated code always names types ROS 1 style, e.g. `std_msgs/String`. With
+    /// [RosVersion::ROS2] type names are converted to ROS 2 style, e.g. `std_msgs/msg/String` or
+    /// `std_srvs/srv/SetBool`, when subscribing, advertising, and publishing, and the fields of
+    /// time and duration values are renamed to `sec` and `nanosec` in outgoing messages.
+    ///
+    /// Defaults to [RosVersion::ROS1].
+    pub fn ros_version(mut self, version: RosVersion) -> ClientHandleOptions {
+        self.ros_version = version;
+        self
+    }
+
+    /// Configures whether the client counts its traffic, service calls, and reconnects, see
+    /// [ClientHandle::metrics]. Collection is cheap, but can be disabled to avoid it entirely.
+    ///
+    /// Defaults to true.
+    pub fn collect_metrics(mut self, enabled: bool) -> ClientHandleOptions {
+        self.collect_metrics = enabled;
+        self
+    }
+
+    /// Configures how operations are delimited when connecting to rosbridge_tcp with a `tcp://`
+    /// url, which must match the server's configuration.
+    ///
+    /// Defaults to [TcpFraming::Json], rosbridge_tcp's default.
+    pub fn tcp_framing(mut self, framing: TcpFraming) -> ClientHandleOptions {
+        self.tcp_framing = framing;
+        self
+    }
+
+    /// Limits the size in bytes of messages received over websockets, `None` removes the limit.
+    /// Messages over the limit end the connection with [RosLibRustError::MessageTooLarge], after
+    /// which the client reconnects.
+    ///
+    /// The limit applies to messages as sent, since websocket level compression
+    /// (permessage-deflate) is not available: tungstenite does not implement the extension and
+    /// rejects the compressed frames a server would send. See [Compression] for rosbridge's own
+    /// compression instead.
+    ///
+    /// Defaults to 256 MiB.
+    pub fn max_message_size(mut self, size: Option<usize>) -> ClientHandleOptions {
+        self.websocket_config.max_message_size = size;
+        self
+    }
+
+    /// Limits the size in bytes of the individual frames messages received over websockets are
+    /// split into, `None` removes the limit. Rosbridge doesn't split messages, so this should be at
+    /// least [ClientHandleOptions::max_message_size].
+    ///
+    /// Over rosbridge_tcp, where operations aren't framed, this limits how much of an operation is
+    /// buffered while waiting for the rest of it, failing the connection with
+    /// [RosLibRustError::MessageTooLarge] once it's exceeded.
+    ///
+    /// Defaults to 256 MiB.
+    pub fn max_frame_size(mut self, size: Option<usize>) -> ClientHandleOptions {
+        self.websocket_config.max_frame_size = size;
+        self
+    }
+
+    /// Splits operations sent to rosbridge which are longer than `threshold` bytes into
+    /// `fragment` operations carrying at most `fragment_size` bytes of the operation each, which
+    /// rosbridge reassembles. Useful when a proxy between the client and rosbridge limits the size
+    /// of websocket frames, e.g. when publishing large maps. The pieces are escaped within each
+    /// fragment, so fragments are somewhat larger than `fragment_size`.
+    ///
+    /// Defaults to sending every operation whole.
+    pub fn fragment_outgoing(
+        mut self,
+        threshold: usize,
+        fragment_size: usize,
+    ) -> ClientHandleOptions {
+        self.fragmentation = Some(Fragmentation {
+            threshold,
+            fragment_size,
+        });
+        self
+    }
+
+    /// How long [ClientHandle::wait_for_service] waits before first checking again for a service
+    /// which isn't available yet. The wait doubles with each check, up to four times this.
+    ///
+    /// Defaults to 500 milliseconds.
+    pub fn service_poll_interval<T: Into<Duration>>(mut self, interval: T) -> ClientHandleOptions {
+        self.service_poll_interval = interval.into();
+        self
+    }
+
+    /// Resolves relative topic, service, and action names within `namespace`, so with a namespace
+    /// of `/robot1` subscribing to `cmd_vel` subscribes to `/robot1/cmd_vel`. Global names are
+    /// used as they are, and private names (`~name`) are rejected.
+    ///
+    /// Without a namespace relative names are sent as they are, and rosbridge resolves them
+    /// within its own namespace. Either way names are checked against ROS's naming rules before
+    /// anything is sent, failing with [RosLibRustError::NameRuleViolation].
+    pub fn namespace(mut self, namespace: impl AsRef<str>) -> ClientHandleOptions {
+        self.namespace = Some(Namespace::new(namespace));
+        self
+    }
+
+    /// Validates a topic, service, or action name, resolving it within the namespace if there is one
+    fn resolve_name(&self, name: &str) -> Result<String, InvalidName> {
+        match &self.namespace {
+            Some(namespace) => crate::resolve_name(name, namespace),
+            None => crate::validate_topic_name(name).map(|()| name.to_owned()),
+        }
+    }
+
+    /// Checks the url is a websocket or TCP url, which otherwise would only be discovered by connecting
+    fn validate(&self) -> RosLibRustResult<()> {
+        use tokio_tungstenite::tungstenite::http::Uri;
+        let uri: Uri = self
+            .url
+            .parse()
+            .map_err(|e| RosLibRustError::InvalidUrl(format!("{}: {e}", self.url)))?;
+        match uri.scheme_str() {
+            Some("ws") | Some("wss") | Some("tcp") => {}
+            _ => {
+                return Err(RosLibRustError::InvalidUrl(format!(
+                    "{}: scheme must be ws, wss, or tcp",
+                    self.url
+                )))
+            }
+        }
+        if uri.host().map_or(true, str::is_empty) {
+            return Err(RosLibRustError::InvalidUrl(format!(
+                "{}: missing host",
+                self.url
+            )));
+        }
+        if let Some(namespace) = self.namespace.as_ref().filter(|ns| !ns.is_root()) {
+            crate::validate_topic_name(namespace.as_str())?;
+        }
+        Ok(())
+    }
+}
+
+/// Configures and creates a [ClientHandle], see [ClientHandle::builder]
+#[derive(Clone)]
+pub struct ClientHandleBuilder {
+    opts: ClientHandleOptions,
+}
+
+impl From<ClientHandleOptions> for ClientHandleBuilder {
+    fn from(opts: ClientHandleOptions) -> Self {
+        ClientHandleBuilder { opts }
+    }
+}
+
+impl ClientHandleBuilder {
+    /// See [ClientHandleOptions::timeout]
+    pub fn timeout<T: Into<Duration>>(self, duration: T) -> Self {
+        self.opts.timeout(duration).into()
+    }
+
+    /// See [ClientHandleOptions::connect_timeout]
+    pub fn connect_timeout<T: Into<Duration>>(self, duration: T) -> Self {
+        self.opts.connect_timeout(duration).into()
+    }
+
+    /// See [ClientHandleOptions::handshake_timeout]
+    pub fn handshake_timeout<T: Into<Duration>>(self, duration: T) -> Self {
+        self.opts.handshake_timeout(duration).into()
+    }
+
+    /// See [ClientHandleOptions::default_service_call_timeout]
+    pub fn default_service_call_timeout<T: Into<Duration>>(self, duration: T) -> Self {
+        self.opts.default_service_call_timeout(duration).into()
+    }
+
+    /// See [ClientHandleOptions::publish_flush_timeout]
+    pub fn publish_flush_timeout<T: Into<Duration>>(self, duration: T) -> Self {
+        self.opts.publish_flush_timeout(duration).into()
+    }
+
+    /// See [ClientHandleOptions::pending_operation_timeout]
+    pub fn pending_operation_timeout<T: Into<Duration>>(self, duration: T) -> Self {
+        self.opts.pending_operation_timeout(duration).into()
+    }
+
+    /// See [ClientHandleOptions::max_pending_operations]
+    pub fn max_pending_operations(self, max: usize) -> Self {
+        self.opts.max_pending_operations(max).into()
+    }
+
+    /// See [ClientHandleOptions::write_queue_size]
+    pub fn write_queue_size(self, size: usize) -> Self {
+        self.opts.write_queue_size(size).into()
+    }
+
+    /// See [ClientHandleOptions::reconnect]
+    pub fn reconnect(self, policy: ReconnectPolicy) -> Self {
+        self.opts.reconnect(policy).into()
+    }
+
+    /// See [ClientHandleOptions::fatal_close_codes]
+    pub fn fatal_close_codes(self, codes: impl IntoIterator<Item = u16>) -> Self {
+        self.opts.fatal_close_codes(codes).into()
+    }
+
+    /// See [ClientHandleOptions::default_compression]
+    pub fn default_compression(self, compression: Compression) -> Self {
+        self.opts.default_compression(compression).into()
+    }
+
+    /// See [ClientHandleOptions::subscriber_queue_size]
+    pub fn subscriber_queue_size(self, size: usize) -> Self {
+        self.opts.subscriber_queue_size(size).into()
+    }
+
+    /// See [ClientHandleOptions::ros_version]
+    pub fn ros_version(self, version: RosVersion) -> Self {
+        self.opts.ros_version(version).into()
+    }
+
+    /// See [ClientHandleOptions::collect_metrics]
+    pub fn collect_metrics(self, enabled: bool) -> Self {
+        self.opts.collect_metrics(enabled).into()
+    }
+
+    /// See [ClientHandleOptions::tcp_framing]
+    pub fn tcp_framing(self, framing: TcpFraming) -> Self {
+        self.opts.tcp_framing(framing).into()
+    }
+
+    /// See [ClientHandleOptions::max_message_size]
+    pub fn max_message_size(self, size: Option<usize>) -> Self {
+        self.opts.max_message_size(size).into()
+    }
+
+    /// See [ClientHandleOptions::max_frame_size]
+    pub fn max_frame_size(self, size: Option<usize>) -> Self {
+        self.opts.max_frame_size(size).into()
+    }
+
+    /// See [ClientHandleOptions::fragment_outgoing]
+    pub fn fragment_outgoing(self, threshold: usize, fragment_size: usize) -> Self {
+        self.opts.fragment_outgoing(threshold, fragment_size).into()
+    }
+
+    /// See [ClientHandleOptions::service_poll_interval]
+    pub fn service_poll_interval<T: Into<Duration>>(self, interval: T) -> Self {
+        self.opts.service_poll_interval(interval).into()
+    }
+
+    /// See [ClientHandleOptions::namespace]
+    pub fn namespace(self, namespace: impl AsRef<str>) -> Self {
+        self.opts.namespace(namespace).into()
+    }
+
+    /// Creates the client, resolving once connected to rosbridge.
+    ///
+    /// Fails with [RosLibRustError::InvalidUrl] without attempting to connect if the url is not a
+    /// `ws://`, `wss://`, or `tcp://` url.
+    pub async fn build(self) -> RosLibRustResult<ClientHandle> {
+        let handle = self.build_unconnected()?;
+        handle.inner.read().await.connection().await?;
+        Ok(handle)
+    }
+
+    /// Creates the client without connecting, the connection is instead made by the first
+    /// operation performed with the client, which waits for it within the connect timeout.
+    /// If connecting fails the operation fails, and the next operation tries again.
+    ///
+    /// Must be called from within a tokio runtime.
+    pub fn build_unconnected(self) -> RosLibRustResult<ClientHandle> {
+        self.opts.validate()?;
+        let pending_operations = Arc::new(Semaphore::new(self.opts.max_pending_operations));
+        let client = Client::new(self.opts);
+        let is_closed = client.is_closed.clone();
+        let has_connected = client.has_connected.clone();
+        let events = client.events.clone();
+        let metrics = client.metrics.clone();
+        let reconnect = client.reconnect.clone();
+        let inner = Arc::new(RwLock::new(client));
+        let inner_weak = Arc::downgrade(&inner);
+
+        let is_disconnected = Arc::new(AtomicBool::new(false));
+        let reconnected = Arc::new(Notify::new());
+
+        // Spawn the spin task, which waits for the connection to be made before reading from it
+        // The internal stubborn spin task continues to try to reconnect on failure
+        let _ = tokio::task::spawn(stubborn_spin(
+            inner_weak,
+            is_disconnected.clone(),
+            is_closed.clone(),
+            reconnected.clone(),
+            events.clone(),
+        ));
+
+        Ok(ClientHandle {
+            inner,
+            is_disconnected,
+            is_closed,
+            has_connected,
+            reconnected,
+            pending_operations,
+            events,
+            metrics,
+            reconnect,
+        })
+    }
+}
+
+// Request and response of rosapi's topic_type service, used to discover types for subscribe_any
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq)]
+struct TopicTypeRequest {
+    topic: String,
+}
+impl RosMessageType for TopicTypeRequest {
+    const ROS_TYPE_NAME: &'static str = "rosapi/TopicTypeRequest";
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq)]
+struct TopicTypeResponse {
+    r#type: String,
+}
+impl RosMessageType for TopicTypeResponse {
+    const ROS_TYPE_NAME: &'static str = "rosapi/TopicTypeResponse";
+}
+
+// Request and response of rosapi's service_type service, used by wait_for_service to check a
+// service exists
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq)]
+struct ServiceTypeRequest {
+    service: String,
+}
+impl RosMessageType for ServiceTypeRequest {
+    const ROS_TYPE_NAME: &'static str = "rosapi/ServiceTypeRequest";
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq)]
+struct ServiceTypeResponse {
+    r#type: String,
+}
+impl RosMessageType for ServiceTypeResponse {
+    const ROS_TYPE_NAME: &'static str = "rosapi/ServiceTypeResponse";
+}
+
+// Response of rosapi's ros_version service, called by ClientHandle::ping
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq)]
+struct RosVersionResponse {
+    version: i8,
+    distro: String,
+}
+impl RosMessageType for RosVersionResponse {
+    const ROS_TYPE_NAME: &'static str = "rosapi/GetROSVersionResponse";
+}
+
+/// Options controlling how a topic is advertised, see [ClientHandle::advertise_with_options]
+#[derive(Clone, Debug, Default)]
+pub struct AdvertiseOptions {
+    /// Requests that rosbridge latch the topic, so that the last message published is delivered
+    /// to subscribers which connect after it was sent.
+    ///
+    /// The latched message is also cached by the client and republished once after reconnecting
+    /// to rosbridge, as rosbridge forgets it along with the old connection.
+    pub latch: bool,
+}
+
+/// Options controlling how calls to a service advertised by the client are handled, see
+/// [ClientHandle::advertise_service_with_options]
+///
+/// Each call is handled on tokio's blocking thread pool. By default calls are handled one at a
+/// time, with the rest waiting their turn, so handlers sharing a resource like a database
+/// connection needn't expect concurrent calls. [ClientHandle::metrics] reports each service's
+/// queue and how many calls were rejected or timed out.
+#[derive(Clone, Debug)]
+pub struct ServiceOptions {
+    /// The most calls handled at once, 1 by default. Further calls wait in the service's queue
+    /// for a handler to finish. A limit of 0 is treated as 1.
+    pub max_concurrent: usize,
+    /// The most calls which wait for a handler with [ServiceOptions::max_concurrent] already
+    /// running, unbounded if None. Calls arriving while the queue is full are rejected straight
+    /// away, answered with `result: false` so the caller isn't left waiting. 0 rejects every call
+    /// over the limit.
+    pub queue_size: Option<usize>,
+    /// How long a call may take, including time spent queued, before it is answered with
+    /// `result: false`. A handler which has already started can't be stopped, so runs to
+    /// completion with its response discarded, and counts towards
+    /// [ServiceOptions::max_concurrent] until it does.
+    pub timeout: Option<Duration>,
+}
+
+impl Default for ServiceOptions {
+    fn default() -> Self {
+        Self {
+            max_concurrent: 1,
+            queue_size: None,
+            timeout: None,
+        }
+    }
+}
+
+/// Options controlling a single service call, see [ClientHandle::call_service_with_options]
+#[derive(Clone, Debug, Default)]
+pub struct ServiceCallOptions {
+    /// How long to wait for the response to each attempt, defaulting to the client's
+    /// [default service call timeout](ClientHandleOptions::default_service_call_timeout)
+    pub timeout: Option<Duration>,
+    /// Bounds the whole call, including every retry and the backoff between them, failing with
+    /// [RosLibRustError::ServiceCallTimeout] once passed. Unbounded if not set, in which case
+    /// only [RetryPolicy::max_attempts] limits how long the call takes.
+    pub deadline: Option<Duration>,
+    /// Retries failed attempts, for services which briefly disappear while their node restarts.
+    /// Each attempt is made only once if not set.
+    pub retry: Option<RetryPolicy>,
+}
+
+/// How a service call is retried after a failed attempt, see [ServiceCallOptions::retry]
+#[derive(Clone, Debug)]
+pub struct RetryPolicy {
+    /// Most attempts made in total, including the first. 0 is treated as 1.
+    pub max_attempts: u32,
+    /// How long to wait after each failed attempt before retrying
+    pub backoff: ReconnectPolicy,
+    /// Decides whether a failed attempt is worth retrying, by default
+    /// [RetryPolicy::is_transient]
+    pub retry_if: fn(&RosLibRustError) -> bool,
+    /// Before each retry, also waits until the service is advertised with
+    /// [ClientHandle::wait_for_service], bounded by what's left of
+    /// [ServiceCallOptions::deadline]. rosapi must then be running alongside rosbridge.
+    pub wait_for_service: bool,
+}
+
+impl Default for RetryPolicy {
+    /// Three attempts, 100 milliseconds apart doubling up to a second, retrying transient errors
+    fn default() -> Self {
+        RetryPolicy {
+            max_attempts: 3,
+            backoff: ReconnectPolicy::ExponentialBackoff {
+                initial: Duration::from_millis(100),
+                max: Duration::from_secs(1),
+            },
+            retry_if: RetryPolicy::is_transient,
+            wait_for_service: false,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// True for failures which may resolve themselves: no response in time, the connection to
+    /// rosbridge being lost, or the service not existing, as while its node restarts. Errors
+    /// reported by the service itself are never transient, as retrying would only repeat them,
+    /// even if they also say something does not exist.
+    pub fn is_transient(error: &RosLibRustError) -> bool {
+        match error {
+            RosLibRustError::Timeout(_)
+            | RosLibRustError::ServiceCallTimeout { .. }
+            | RosLibRustError::ServiceUnavailable { .. }
+            | RosLibRustError::Disconnected
+            | RosLibRustError::ConnectionClosed { .. }
+            | RosLibRustError::TryAgainLater { .. } => true,
+            // rosbridge's message for calls to services which aren't advertised
+            RosLibRustError::ServerError(message) => missing_service(message).is_some(),
+            _ => false,
+        }
+    }
+}
+
+/// What [ClientHandle::subscribe_with_options] does when [SubscribeOptions::type_check] finds
+/// the topic doesn't exist yet
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MissingTopicPolicy {
+    /// Subscribe anyway, trusting that the topic will be published with the expected type
+    #[default]
+    Proceed,
+    /// Fail with [RosLibRustError::TopicNotFound]
+    Fail,
+}
+
+/// Options controlling how a topic is subscribed to, see [ClientHandle::subscribe_with_options]
+#[derive(Clone, Debug, Default)]
+pub struct SubscribeOptions {
+    /// Asks rosapi for the topic's type before subscribing, failing with
+    /// [RosLibRustError::TypeMismatch] if it isn't the type being subscribed with.
+    /// Rosbridge itself would otherwise only log the mismatch, and messages would fail to
+    /// deserialize or be silently misread.
+    pub type_check: bool,
+    /// With [SubscribeOptions::type_check], also compares the message's fields against the
+    /// definition rosapi has for the type, failing with [RosLibRustError::DefinitionMismatch] if
+    /// they differ. This catches a type which has changed since the code was generated.
+    ///
+    /// Skipped for types without a definition, such as hand written messages.
+    pub check_definition: bool,
+    /// What to do when [SubscribeOptions::type_check] finds that nothing has advertised the topic
+    pub missing_topic: MissingTopicPolicy,
+    /// Where the subscriber's messages are deserialized
+    pub dispatch: SubscriberDispatch,
+}
+
+/// Where a subscriber's messages are deserialized and queued, see [SubscribeOptions::dispatch].
+///
+/// Either way each subscriber receives a topic's messages in the order rosbridge sent them.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SubscriberDispatch {
+    /// Directly in the task reading from rosbridge, which saves handing each message to another
+    /// task. Anything slow while deserializing, such as a hand written `Deserialize` doing
+    /// blocking work, holds up every other subscription and service call until it is done.
+    Inline,
+    /// On a task of the subscriber's own, which deserializes on tokio's blocking thread pool.
+    /// Up to [ClientHandleOptions::subscriber_queue_size] messages wait for the task, further
+    /// messages are dropped until it catches up.
+    #[default]
+    Spawned,
+}
+
+/// Changes in the state of a client's connection to rosbridge, see [ClientHandle::connection_events]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ConnectionEvent {
+    /// The client connected to rosbridge, either for the first time or after losing its
+    /// connection. When reconnecting this is only sent once the client's subscriptions and
+    /// publishers have been restored, so handlers can publish straight away.
+    Connected {
+        /// Number of subscriptions re-established on the new connection, always 0 for the first
+        /// connection
+        resubscribed: usize,
+    },
+    /// The connection to rosbridge was lost, the client will now try to reconnect
+    Disconnected {
+        /// The error which ended the connection
+        reason: String,
+    },
+    /// The client is about to make an attempt to reconnect, counting from 1 for the first attempt
+    /// after each disconnect
+    Reconnecting { attempt: u32 },
+    /// The client gave up reconnecting after [Backoff::max_attempts] failed attempts, or without
+    /// any attempts after a fatal close code, see [ConnectionState::Failed]
+    Failed { attempts: u32 },
+}
+
+/// The current state of a client's connection to rosbridge, see [ClientHandle::connection_state]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConnectionState {
+    /// The client is trying to connect to rosbridge, either for the first time or after losing
+    /// its connection. Clients from [ClientHandleBuilder::build_unconnected] start in this state
+    /// before connecting on first use.
+    Connecting {
+        /// The attempt being made, or made after `next_delay`, counting from 1 after each
+        /// disconnect
+        attempt: u32,
+        /// How long until the next attempt after one has failed, None while an attempt is being
+        /// made
+        next_delay: Option<Duration>,
+    },
+    /// The client is connected to rosbridge
+    Connected,
+    /// The client gave up connecting after [Backoff::max_attempts] failed attempts. This is
+    /// terminal after losing a connection, operations then fail with
+    /// [RosLibRustError::Disconnected]. When first connecting the next operation tries again.
+    ///
+    /// Also entered with zero attempts when rosbridge closes the connection with one of the
+    /// [ClientHandleOptions::fatal_close_codes], after which operations fail with the error for
+    /// the close code.
+    Failed { attempts: u32 },
+}
+
+/// Options controlling how a client is shutdown, see [ClientHandle::close_with_options]
+#[derive(Clone, Debug)]
+pub struct CloseOptions {
+    /// Sends rosbridge an unsubscribe, unadvertise, or unadvertise_service for every active
+    /// subscription, publisher, and service before closing the connection. Rosbridge cleans these
+    /// up itself when a connection closes, so this is only needed to have them removed promptly.
+    pub unregister: bool,
+    /// How long to wait for the websocket close handshake to complete
+    pub timeout: Duration,
+}
+
+impl Default for CloseOptions {
+    fn default() -> Self {
+        Self {
+            unregister: false,
+            timeout: Duration::from_secs(5),
+        }
+    }
 }
 
 /// The ClientHandle is the fundamental object through which users of this library are expected to interact with it.
@@ -51,6 +941,11 @@ impl ClientHandleOptions {
 /// which is responsible for continuously managing that connection and attempts to re-establish the connection if it goes down.
 ///
 /// ClientHandle is clone and multiple handles can be clone()'d from the original and passed throughout your application.
+/// Handles are Send + Sync and cheap to clone, everything sent to rosbridge is queued to a single task which owns
+/// the write half of the connection, so no lock is held while waiting for a write.
+///
+/// Operations sent from one task reach rosbridge in the order that task sent them, e.g. an advertise before the
+/// publishes following it. Operations sent concurrently from different tasks have no guaranteed order between them.
 /// ```no_run
 /// # roslibrust_codegen_macro::find_and_generate_ros_messages!(
 /// #    "assets/ros1_common_interfaces/std_msgs"
@@ -76,86 +971,515 @@ impl ClientHandleOptions {
 pub struct ClientHandle {
     pub(crate) inner: Arc<RwLock<Client>>,
     pub(crate) is_disconnected: Arc<AtomicBool>,
+    // Shared with the client, set once the client is closed and never cleared
+    pub(crate) is_closed: Arc<AtomicBool>,
+    // Shared with the client, set once it first connects and never cleared
+    has_connected: Arc<AtomicBool>,
+    // Notified once a lost connection has been re-established, or the client is closed
+    reconnected: Arc<Notify>,
+    // Each operation waiting for the client to connect holds a permit, limiting how many can wait
+    pending_operations: Arc<Semaphore>,
+    // Shared with the client, which sends on it as the connection changes state
+    events: broadcast::Sender<ConnectionEvent>,
+    // Shared with the client, which records its activity in it
+    metrics: Arc<Metrics>,
+    // Shared with the client, which reconnects according to its policy
+    reconnect: Arc<ReconnectState>,
 }
 
 impl ClientHandle {
+    /// Starts configuring a client for the rosbridge instance at the given url, which is expected
+    /// to be a fully described websocket url, e.g. 'ws://localhost:9090'.
+    /// ```no_run
+    /// # use std::time::Duration;
+    /// # use roslibrust::{ClientHandle, Compression, ReconnectPolicy};
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    ///   let handle = ClientHandle::builder("ws://localhost:9090")
+    ///     .connect_timeout(Duration::from_secs(30))
+    ///     .reconnect(ReconnectPolicy::ExponentialBackoff {
+    ///         initial: Duration::from_millis(100),
+    ///         max: Duration::from_secs(5),
+    ///     })
+    ///     .default_compression(Compression::Cbor)
+    ///     .build()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn builder<S: Into<String>>(url: S) -> ClientHandleBuilder {
+        ClientHandleOptions::new(url).into()
+    }
+
     /// Creates a new client handle with configurable options.
     ///
-    /// Use this method if you need more control than [ClientHandle::new] provides.
+    /// Equivalent to building a client with [ClientHandle::builder] configured with the same options.
     /// Like [ClientHandle::new] this function does not resolve until the connection is established for the first time.
-    /// This function respects the [ClientHandleOptions] timeout and will return with an error if a connection is not
-    /// established within the timeout.
+    /// This function respects the [ClientHandleOptions] connect timeout and will return with an error if a connection
+    /// is not established within the timeout.
     pub async fn new_with_options(opts: ClientHandleOptions) -> RosLibRustResult<Self> {
-        let inner = Arc::new(RwLock::new(timeout(opts.timeout, Client::new(opts)).await?));
-        let inner_weak = Arc::downgrade(&inner);
-
-        // We connect when we create Client
-        let is_disconnected = Arc::new(AtomicBool::new(false));
-
-        // Spawn the spin task
-        // The internal stubborn spin task continues to try to reconnect on failure
-        let _ = tokio::task::spawn(stubborn_spin(inner_weak, is_disconnected.clone()));
-
-        Ok(ClientHandle {
-            inner,
-            is_disconnected,
-        })
+        ClientHandleBuilder::from(opts).build().await
     }
 
     /// Connects a rosbridge instance at the given url
-    /// Expects a fully describe websocket url, e.g. 'ws://localhost:9090'
+    /// Expects a fully describe websocket url, e.g. 'ws://localhost:9090', or a rosbridge_tcp url,
+    /// e.g. 'tcp://localhost:9090'
     /// When awaited will not resolve until connection is successfully made.
+    ///
+    /// Shorthand for `ClientHandle::builder(url).build()`.
     pub async fn new<S: Into<String>>(url: S) -> RosLibRustResult<Self> {
-        Self::new_with_options(ClientHandleOptions::new(url)).await
+        Self::builder(url).build().await
+    }
+
+    /// Connects to rosbridge at the given url like [ClientHandle::new], but fails with
+    /// [RosLibRustError::ConnectTimeout] if the connection, including the websocket handshake, isn't
+    /// established within `timeout`. An unreachable host or a wrong port then fails fast instead of
+    /// retrying forever.
+    ///
+    /// Shorthand for `ClientHandle::builder(url).connect_timeout(timeout).build()`.
+    /// ```no_run
+    /// # use std::time::Duration;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    ///   let candidates = ["ws://robot.local:9090", "ws://192.168.1.10:9090", "ws://localhost:9090"];
+    ///   for url in candidates {
+    ///     match roslibrust::ClientHandle::connect_with_timeout(url, Duration::from_secs(2)).await {
+    ///       Ok(_handle) => return Ok(()),
+    ///       Err(e) => eprintln!("Couldn't connect to {url}: {e}"),
+    ///     }
+    ///   }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn connect_with_timeout<S: Into<String>>(
+        url: S,
+        timeout: Duration,
+    ) -> RosLibRustResult<Self> {
+        Self::builder(url).connect_timeout(timeout).build().await
+    }
+
+    /// Returns a receiver of events for each time the client connects, loses its connection, or
+    /// attempts to reconnect to rosbridge.
+    ///
+    /// Subscriptions and publishers are restored automatically after reconnecting, this is for
+    /// re-syncing any other state, e.g. re-fetching parameters, once [ConnectionEvent::Connected]
+    /// is received. Only events which happen after this is called are received, and a receiver
+    /// which falls far behind skips the oldest events.
+    /// ```no_run
+    /// # use roslibrust::{ClientHandle, ConnectionEvent};
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    ///   let handle = ClientHandle::new("ws://localhost:9090").await?;
+    ///   let mut events = handle.connection_events();
+    ///   while let Ok(event) = events.recv().await {
+    ///     if let ConnectionEvent::Connected { .. } = event {
+    ///       // Re-fetch params, alert an operator, etc.
+    ///     }
+    ///   }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn connection_events(&self) -> broadcast::Receiver<ConnectionEvent> {
+        self.events.subscribe()
+    }
+
+    /// Returns a receiver of the current [ConnectionState], including which attempt is being
+    /// made and how long until the next one while reconnecting, e.g. for showing in a UI.
+    /// ```no_run
+    /// # use roslibrust::{ClientHandle, ConnectionState};
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    ///   let handle = ClientHandle::new("ws://localhost:9090").await?;
+    ///   let mut state = handle.connection_state();
+    ///   while state.changed().await.is_ok() {
+    ///     match *state.borrow() {
+    ///       ConnectionState::Connecting { attempt, next_delay: Some(delay) } => {
+    ///         println!("Connection attempt {attempt} in {delay:?}")
+    ///       }
+    ///       ConnectionState::Failed { .. } => break,
+    ///       _ => {}
+    ///     }
+    ///   }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn connection_state(&self) -> watch::Receiver<ConnectionState> {
+        self.reconnect.state.subscribe()
+    }
+
+    /// Replaces the policy configured with [ClientHandleOptions::reconnect]. Takes effect from the
+    /// next failed attempt, including while already reconnecting.
+    pub fn set_reconnect_policy(&self, policy: ReconnectPolicy) {
+        *self.reconnect.policy.lock().unwrap() = policy;
+    }
+
+    /// Returns the client's counters of messages and bytes sent and received per topic, messages
+    /// dropped by subscribers, service calls, and reconnects, along with the current connection's
+    /// uptime, e.g. for exporting to a monitoring system.
+    ///
+    /// The snapshot is empty if metrics were disabled with [ClientHandleOptions::collect_metrics].
+    pub fn metrics(&self) -> MetricsSnapshot {
+        self.metrics.snapshot()
+    }
+
+    /// Returns true if the client currently has a connection to rosbridge, without doing any IO.
+    ///
+    /// This is false before a client from [ClientHandleBuilder::build_unconnected] first connects,
+    /// while reconnecting, and once closed. Use [ClientHandle::ping] to check that rosbridge is
+    /// actually responding.
+    pub fn is_connected(&self) -> bool {
+        self.has_connected.load(Ordering::Relaxed)
+            && !self.is_disconnected.load(Ordering::Relaxed)
+            && !self.is_closed.load(Ordering::Relaxed)
+    }
+
+    /// Checks that rosbridge is alive and responding by calling rosapi's `/rosapi/ros_version`
+    /// service, returning the round trip time.
+    ///
+    /// Fails with [RosLibRustError::ServiceCallTimeout] if no response arrives within `timeout`,
+    /// which includes any time spent waiting for the client to connect or reconnect.
+    /// Requires the rosapi node to be running alongside rosbridge.
+    /// ```no_run
+    /// # use std::time::Duration;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    ///   let handle = roslibrust::ClientHandle::new("ws://localhost:9090").await?;
+    ///   let rtt = handle.ping(Duration::from_secs(1)).await?;
+    ///   log::info!("Rosbridge responded in {rtt:?}");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn ping(&self, timeout: Duration) -> RosLibRustResult<Duration> {
+        const SERVICE: &str = "/rosapi/ros_version";
+        let started = tokio::time::Instant::now();
+        let call = self.call_service_inner::<(), RosVersionResponse>(SERVICE, (), Some(timeout));
+        tokio::time::timeout(timeout, call).await.map_err(|_| {
+            RosLibRustError::ServiceCallTimeout {
+                service: SERVICE.to_owned(),
+                timeout,
+            }
+        })??;
+        Ok(started.elapsed())
+    }
+
+    /// Waits until `service` is advertised, like rospy's `wait_for_service`, for calling services
+    /// whose provider may still be starting up. Checks with rosapi's `/rosapi/service_type`
+    /// service, waiting [ClientHandleOptions::service_poll_interval] between checks, doubling up
+    /// to four times that.
+    ///
+    /// Fails with [RosLibRustError::ServiceUnavailable] if the service hasn't appeared within
+    /// `timeout`, which includes any time spent waiting for the client to connect or reconnect,
+    /// and with [RosLibRustError::RosapiUnavailable] if rosapi isn't running alongside rosbridge.
+    /// ```no_run
+    /// # use std::time::Duration;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    ///   let handle = roslibrust::ClientHandle::new("ws://localhost:9090").await?;
+    ///   handle.wait_for_service("/controller/switch", Duration::from_secs(30)).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn wait_for_service(&self, service: &str, timeout: Duration) -> RosLibRustResult<()> {
+        const SERVICE: &str = "/rosapi/service_type";
+        let service = &self.resolve_name(service).await?;
+        let interval = self.inner.read().await.opts.service_poll_interval;
+        let backoff = ReconnectPolicy::ExponentialBackoff {
+            initial: interval,
+            max: interval * 4,
+        };
+        let poll = async {
+            let mut polls = 0;
+            loop {
+                polls += 1;
+                let request = ServiceTypeRequest {
+                    service: service.to_owned(),
+                };
+                // Bounded by the overall timeout, so it needs none of its own
+                let response = self
+                    .call_rosapi::<_, ServiceTypeResponse>(SERVICE, request)
+                    .await;
+                match response {
+                    // rosapi reports an empty type for services which don't exist
+                    Ok(response) if !response.r#type.is_empty() => return Ok(()),
+                    Ok(_) => debug!("Service {service} is not available yet"),
+                    Err(e) => return Err(e),
+                }
+                tokio::time::sleep(backoff.delay(polls)).await;
+            }
+        };
+        tokio::time::timeout(timeout, poll).await.map_err(|_| {
+            RosLibRustError::ServiceUnavailable {
+                service: service.to_owned(),
+                timeout,
+            }
+        })?
+    }
+
+    /// Calls a service once it is available, combining [ClientHandle::wait_for_service] with
+    /// [ClientHandle::call_service]. Only the wait is limited by `timeout`, the call itself is
+    /// limited by the [default service call timeout](ClientHandleOptions::default_service_call_timeout).
+    pub async fn call_service_when_available<Req: RosMessageType, Res: RosMessageType>(
+        &self,
+        service: &str,
+        req: Req,
+        timeout: Duration,
+    ) -> RosLibRustResult<Res> {
+        self.wait_for_service(service, timeout).await?;
+        self.call_service(service, req).await
+    }
+
+    /// Calls `callback` with every [ConnectionEvent] which happens after this is called, see
+    /// [ClientHandle::connection_events].
+    ///
+    /// Callbacks run one event at a time on their own spawned task, so they are free to use the
+    /// client, e.g. to publish once [ConnectionEvent::Connected] is received, without holding up
+    /// the client's connection. They stop being called once every handle to the client is dropped.
+    /// ```no_run
+    /// # use roslibrust::{ClientHandle, ConnectionEvent};
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    ///   let handle = ClientHandle::new("ws://localhost:9090").await?;
+    ///   handle.on_connection_event(|event| async move {
+    ///     if let ConnectionEvent::Disconnected { reason } = event {
+    ///       log::warn!("Lost connection to rosbridge: {reason}");
+    ///     }
+    ///   });
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn on_connection_event<F, Fut>(&self, mut callback: F)
+    where
+        F: FnMut(ConnectionEvent) -> Fut + Send + 'static,
+        Fut: futures::Future<Output = ()> + Send,
+    {
+        let mut events = self.connection_events();
+        tokio::spawn(async move {
+            loop {
+                match events.recv().await {
+                    Ok(event) => callback(event).await,
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        warn!("Connection event callback fell behind, skipped {skipped} events")
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        });
+    }
+
+    /// Returns the connection to use for an operation, connecting first if this is the first use of
+    /// a lazily connected client. Returns None while the client is reconnecting, in which case the
+    /// operation should wait for the reconnect with [ClientHandle::wait_for_reconnect].
+    async fn current_connection<'a>(
+        &self,
+        client: &'a Client,
+    ) -> RosLibRustResult<Option<&'a Connection>> {
+        if self.is_closed.load(Ordering::Relaxed) {
+            return Err(self.closed_error());
+        }
+        if self.is_disconnected.load(Ordering::Relaxed) {
+            return Ok(None);
+        }
+        if client.connection.get().is_some() {
+            return client.connection().await.map(Some);
+        }
+        let _permit = self.reserve_pending_operation()?;
+        client.connection().await.map(Some)
+    }
+
+    /// Locks the client once it is connected, waiting for it to reconnect first if necessary
+    async fn connected_client(&self) -> RosLibRustResult<RwLockReadGuard<'_, Client>> {
+        loop {
+            let client = self.inner.read().await;
+            if self.current_connection(&client).await?.is_some() {
+                return Ok(client);
+            }
+            let wait = client.pending_operation_timeout();
+            drop(client);
+            self.wait_for_reconnect(wait).await?;
+        }
     }
 
-    fn check_for_disconnect(&self) -> RosLibRustResult<()> {
-        match self.is_disconnected.load(Ordering::Relaxed) {
+    /// Waits up to `wait` for a lost connection to be re-established. The client must not be
+    /// locked while waiting, as reconnecting needs to lock it.
+    async fn wait_for_reconnect(&self, wait: Duration) -> RosLibRustResult<()> {
+        let _permit = self.reserve_pending_operation()?;
+        let reconnected = async {
+            loop {
+                // Registered before checking so a reconnect in between isn't missed
+                let notified = self.reconnected.notified();
+                tokio::pin!(notified);
+                notified.as_mut().enable();
+                if !self.is_disconnected.load(Ordering::Relaxed)
+                    || self.is_closed.load(Ordering::Relaxed)
+                {
+                    return;
+                }
+                notified.await;
+            }
+        };
+        tokio::time::timeout(wait, reconnected)
+            .await
+            .map_err(|_| RosLibRustError::Disconnected)?;
+        match self.is_closed.load(Ordering::Relaxed) {
             false => Ok(()),
-            true => Err(RosLibRustError::Disconnected),
+            true => Err(self.closed_error()),
+        }
+    }
+
+    /// The error for operations on a closed client, which names the close code if rosbridge
+    /// closing the connection is why the client gave up
+    fn closed_error(&self) -> RosLibRustError {
+        let fatal_close = self.reconnect.fatal_close.lock().unwrap();
+        fatal_close
+            .as_ref()
+            .map_or(RosLibRustError::Disconnected, ServerClose::to_error)
+    }
+
+    /// The code and reason of the close frame rosbridge, or a proxy in front of it, most recently
+    /// ended the client's connection with. None if the connection was never closed by the server,
+    /// including when it was lost without a close frame.
+    pub fn last_close(&self) -> Option<ServerClose> {
+        self.reconnect.last_close.lock().unwrap().clone()
+    }
+
+    fn reserve_pending_operation(&self) -> RosLibRustResult<tokio::sync::SemaphorePermit<'_>> {
+        self.pending_operations
+            .try_acquire()
+            .map_err(|_| RosLibRustError::TooManyPendingOperations)
+    }
+
+    /// Shuts down the connection to rosbridge, see [ClientHandle::close_with_options]
+    pub async fn close(&self) -> RosLibRustResult<()> {
+        self.close_with_options(CloseOptions::default()).await
+    }
+
+    /// Shuts down the connection to rosbridge, resolving once the websocket close handshake has
+    /// completed or `options.timeout` has elapsed.
+    ///
+    /// Once called, every handle to this client fails new operations with
+    /// [RosLibRustError::Disconnected], and outstanding service calls fail immediately with the
+    /// same error. Operations which were already sending, such as a publish started just before
+    /// calling close, are sent before the connection is closed. The client will not reconnect
+    /// after being closed, and closing an already closed client does nothing.
+    ///
+    /// If the last handle to a client is dropped without calling close, a close is still attempted
+    /// but without waiting for anything to be sent.
+    pub async fn close_with_options(&self, options: CloseOptions) -> RosLibRustResult<()> {
+        if self.is_closed.swap(true, Ordering::Relaxed) {
+            return Ok(());
         }
+        self.reconnected.notify_waiters();
+        let client = self.inner.read().await;
+        client.service_calls.clear();
+        // A lazily connected client which was never used has nothing to shut down
+        let Some(connection) = client.connection.get() else {
+            return Ok(());
+        };
+
+        tokio::time::timeout(options.timeout, async {
+            // The close is queued behind any operation already queued, letting them be sent first
+            if options.unregister {
+                client.unregister_all(&connection.writer).await;
+            }
+            connection.writer.close().await?;
+            // Rosbridge ends the handshake by replying with a close of its own, then closing the stream
+            let mut reader = connection.reader.write().await;
+            while let Some(Ok(frame)) = reader.receive().await {
+                if let Frame::Close(_) = frame {
+                    break;
+                }
+            }
+            Ok(())
+        })
+        .await?
     }
 
     // Internal implementation of subscribe
-    async fn _subscribe<Msg>(&self, topic_name: &str) -> RosLibRustResult<Subscriber<Msg>>
+    async fn _subscribe<Msg>(
+        &self,
+        topic_name: &str,
+        dispatch: SubscriberDispatch,
+    ) -> RosLibRustResult<Subscriber<Msg>>
     where
         Msg: RosMessageType,
+    {
+        self.subscribe_with(topic_name, Msg::ROS_TYPE_NAME, dispatch, |msg| {
+            match serde_json::from_str::<Msg>(msg.json()) {
+                Ok(msg) => Some(msg),
+                Err(e) => {
+                    // TODO makes sense for callback to return Result<>, instead of this handling
+                    // Should do better error propogation
+                    error!(
+                        "Failed to deserialize ros message: {:?}. Message will be skipped!",
+                        e
+                    );
+                    None
+                }
+            }
+        })
+        .await
+    }
+
+    // Shared implementation of typed and untyped subscribes, registers a callback which converts
+    // each incoming message with `convert` and queues it for the returned subscriber
+    async fn subscribe_with<T, F>(
+        &self,
+        topic_name: &str,
+        topic_type: &str,
+        dispatch: SubscriberDispatch,
+        convert: F,
+    ) -> RosLibRustResult<Subscriber<T>>
+    where
+        T: std::fmt::Debug + Send + Sync + 'static,
+        F: Fn(&ReceivedMsg) -> Option<T> + Send + Sync + 'static,
     {
         // Lookup / create a subscription entry for tracking
         let client = self.inner.read().await;
-        let mut cbs = client
+        let connection = self.current_connection(&client).await?;
+        let topic_type = &comm::wire_type_name(client.opts.ros_version, topic_type, "msg");
+        let changing = client.subscription_changes.lock().await;
+        let topic_metrics = client
             .subscriptions
             .entry(topic_name.to_string())
-            .or_insert(Subscription {
+            .or_insert_with(|| Subscription {
                 handles: HashMap::new(),
-                topic_type: Msg::ROS_TYPE_NAME.to_string(),
+                topic_type: topic_type.to_string(),
                 known_publishers: vec![],
-            });
+                metrics: client.metrics.topic(topic_name),
+            })
+            .metrics
+            .clone();
 
         // TODO Possible bug here? We send a subscribe message each time even if already subscribed
         // Send subscribe message to rosbridge to initiate it sending us messages
-        let mut stream = client.writer.write().await;
-        stream.subscribe(topic_name, Msg::ROS_TYPE_NAME).await?;
+        // While reconnecting the entry is enough, the reconnect subscribes to every entry
+        if let Some(connection) = connection {
+            let subscribed = connection
+                .writer
+                .subscribe(topic_name, topic_type, client.opts.default_compression)
+                .await;
+            if let Err(e) = subscribed {
+                client
+                    .subscriptions
+                    .remove_if(topic_name, |_, subscription| {
+                        subscription.handles.is_empty()
+                    });
+                return Err(e);
+            }
+        }
 
         // Create a new watch channel for this topic
-        let queue = Arc::new(MessageQueue::new(QUEUE_SIZE));
+        let queue = Arc::new(MessageQueue::new(client.opts.subscriber_queue_size));
 
-        // Move the tx into a callback that takes raw string data
+        // Move the tx into a callback that takes the raw message
         // This allows us to store the callbacks generic on type, Msg conversion is embedded here
         let topic_name_copy = topic_name.to_string();
         let queue_copy = queue.clone();
-        let send_cb = Box::new(move |data: &str| {
-            let converted = match serde_json::from_str::<Msg>(data) {
-                Err(e) => {
-                    // TODO makes sense for callback to return Result<>, instead of this handling
-                    // Should do better error propogation
-                    error!(
-                        "Failed to deserialize ros message: {:?}. Message will be skipped!",
-                        e
-                    );
-                    return;
-                }
-                Ok(t) => t,
+        let dropped_metrics = topic_metrics.clone();
+        let deliver = move |msg: &ReceivedMsg| {
+            let converted = match convert(msg) {
+                Some(t) => t,
+                None => return,
             };
 
             match queue_copy.try_push(converted) {
@@ -168,6 +1492,9 @@ impl ClientHandle {
                         &topic_name_copy
                     );
                     let _dropped = queue_copy.try_pop();
+                    if let Some(topic_metrics) = &dropped_metrics {
+                        topic_metrics.record_dropped();
+                    }
                     // Retry pushing into queue
                     match queue_copy.try_push(msg) {
                         Ok(()) => {
@@ -186,14 +1513,35 @@ impl ClientHandle {
                     }
                 }
             }
-        });
+        };
+        let send_cb: Callback = match dispatch {
+            SubscriberDispatch::Inline => Box::new(deliver),
+            SubscriberDispatch::Spawned => spawn_subscriber_task(
+                topic_name,
+                client.opts.subscriber_queue_size,
+                topic_metrics,
+                deliver,
+            ),
+        };
 
         // Create subscriber
         let sub = Subscriber::new(self.clone(), queue, topic_name.to_string());
 
         // Store callback in map under the subscriber's id
-        cbs.handles.insert(*sub.get_id(), send_cb);
+        client
+            .subscriptions
+            .get_mut(topic_name)
+            .expect("Subscriptions are only removed while holding subscription_changes")
+            .handles
+            .insert(*sub.get_id(), send_cb);
+        drop(changing);
 
+        if connection.is_none() {
+            let wait = client.pending_operation_timeout();
+            drop(client);
+            // Dropping the subscriber on failure removes its entry again
+            self.wait_for_reconnect(wait).await?;
+        }
         Ok(sub)
     }
 
@@ -213,8 +1561,10 @@ impl ClientHandle {
     ///   # Ok(())
     /// # }
     /// ```
-    /// This function returns after a subscribe message has been sent to rosbridge, it will
-    /// return immediately with an error if call while currently disconnected.
+    /// This function returns after a subscribe message has been sent to rosbridge. If called while
+    /// the client is reconnecting it waits for the reconnect, up to the
+    /// [pending operation timeout](ClientHandleOptions::pending_operation_timeout), before failing
+    /// with [RosLibRustError::Disconnected].
     ///
     /// It does not error if subscribed type does not match the topic type or check this in anyway.
     /// If a type different that what is expected on the topic is published the deserialization of that message will fail,
@@ -254,171 +1604,831 @@ impl ClientHandle {
     where
         Msg: RosMessageType,
     {
-        self.check_for_disconnect()?;
+        let topic_name = &self.resolve_name(topic_name).await?;
+        // Read separately so the client isn't locked while subscribing, which may wait on a reconnect
+        let subscribe_timeout = self.inner.read().await.opts.timeout;
         timeout(
-            self.inner.read().await.opts.timeout,
-            self._subscribe(topic_name),
+            subscribe_timeout,
+            self._subscribe(topic_name, SubscriberDispatch::default()),
         )
         .await
     }
 
-    // Publishes a message
-    // Fails immediately(ish) if disconnected
-    // Returns success when message is put on websocket (no confirmation of receipt)
-    pub(crate) async fn publish<T>(&self, topic: &str, msg: T) -> RosLibRustResult<()>
-    where
-        T: RosMessageType,
-    {
-        self.check_for_disconnect()?;
-        let client = self.inner.read().await;
-        let mut stream = client.writer.write().await;
-        debug!("Publish got write lock on comm");
-        stream.publish(topic, msg).await?;
-        Ok(())
-    }
-
-    /// Advertises a topic to be published to and returns a type specific publisher to use.
-    ///
-    /// Dropping the publisher will automatically un-advertise the topic. Publisher is not clone,
-    /// and calling advertise multiple times targeting the same topic is not currently supported and
-    /// will result in an error.
-    ///
-    /// This function returns with a failure if currently disconnected when called.
-    ///
-    /// No type checking of the advertised type is performed. If the serialization of T is not
-    /// accepted by rosbridge as compatible with rosmaster's type, that information will only be
-    /// available in rosbridge's logs.
+    /// Subscribes to a topic like [ClientHandle::subscribe], with extra checks before subscribing.
     ///
+    /// The client's timeout covers both the checks and subscribing. The checks call rosapi, so
+    /// fail with [RosLibRustError::RosapiUnavailable] if it isn't running alongside rosbridge.
     /// ```no_run
     /// # roslibrust_codegen_macro::find_and_generate_ros_messages!(
     /// #    "assets/ros1_common_interfaces/std_msgs"
     /// # );
     /// # #[tokio::main]
     /// # async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    ///   // Create a new client
-    ///   let mut handle = roslibrust::ClientHandle::new("ws://localhost:9090").await?;
-    ///   // Advertise using ::<T> style
-    ///   let mut publisher = handle.advertise::<std_msgs::Header>("/topic").await?;
-    ///   // Advertise using explicit type
-    ///   let mut publisher2: roslibrust::Publisher<std_msgs::Header> = handle.advertise("/other_topic").await?;
-    ///   # Ok(())
+    ///   let handle = roslibrust::ClientHandle::new("ws://localhost:9090").await?;
+    ///   let options = roslibrust::SubscribeOptions {
+    ///     type_check: true,
+    ///     missing_topic: roslibrust::MissingTopicPolicy::Fail,
+    ///     ..Default::default()
+    ///   };
+    ///   let subscriber = handle
+    ///     .subscribe_with_options::<std_msgs::Header>("/topic", options)
+    ///     .await?;
+    /// # Ok(())
     /// # }
     /// ```
-    pub async fn advertise<T>(&self, topic: &str) -> RosLibRustResult<Publisher<T>>
+    pub async fn subscribe_with_options<Msg>(
+        &self,
+        topic_name: &str,
+        options: SubscribeOptions,
+    ) -> RosLibRustResult<Subscriber<Msg>>
     where
-        T: RosMessageType,
+        Msg: RosMessageType,
     {
-        self.check_for_disconnect()?;
-        let client = self.inner.read().await;
-        if client.publishers.contains_key(topic) {
-            // TODO if we ever remove this restriction we should still check types match
-            return Err(RosLibRustError::Unexpected(anyhow!(
-                "Attempted to create two publisher to same topic, this is not supported"
-            )));
-        } else {
-            client.publishers.insert(
-                topic.to_string(),
-                PublisherHandle {
-                    topic_type: T::ROS_TYPE_NAME.to_string(),
-                },
-            );
+        let topic_name = &self.resolve_name(topic_name).await?;
+        let subscribe_timeout = self.inner.read().await.opts.timeout;
+        timeout(subscribe_timeout, async {
+            if options.type_check {
+                self.check_topic_type::<Msg>(topic_name, &options).await?;
+            }
+            self._subscribe(topic_name, options.dispatch).await
+        })
+        .await
+    }
+
+    // Checks with rosapi that topic_name has the type being subscribed with, see
+    // SubscribeOptions::type_check
+    async fn check_topic_type<Msg: RosMessageType>(
+        &self,
+        topic_name: &str,
+        options: &SubscribeOptions,
+    ) -> RosLibRustResult<()> {
+        let ros_version = self.inner.read().await.opts.ros_version;
+        let requested = comm::wire_type_name(ros_version, Msg::ROS_TYPE_NAME, "msg");
+        let request = TopicTypeRequest {
+            topic: topic_name.to_string(),
+        };
+        let response: TopicTypeResponse = self.call_rosapi("/rosapi/topic_type", request).await?;
+        // rosapi reports an empty type for topics which don't exist
+        if response.r#type.is_empty() {
+            return match options.missing_topic {
+                MissingTopicPolicy::Proceed => {
+                    debug!(
+                        "Topic {topic_name} doesn't exist yet, subscribing without a type check"
+                    );
+                    Ok(())
+                }
+                MissingTopicPolicy::Fail => {
+                    Err(RosLibRustError::TopicNotFound(topic_name.to_string()))
+                }
+            };
+        }
+        if response.r#type != requested {
+            return Err(RosLibRustError::TypeMismatch {
+                topic: topic_name.to_string(),
+                actual: response.r#type,
+                requested,
+            });
         }
 
-        {
-            let mut stream = client.writer.write().await;
-            debug!("Advertise got lock on comm");
-            stream.advertise::<T>(topic).await?;
+        let expected_fields = definition_field_names(Msg::DEFINITION);
+        if !options.check_definition || expected_fields.is_empty() {
+            return Ok(());
+        }
+        let request = MessageDetailsRequest {
+            r#type: requested.clone(),
+        };
+        let details: MessageDetailsResponse =
+            self.call_rosapi("/rosapi/message_details", request).await?;
+        match details.typedefs.iter().find(|def| def.r#type == requested) {
+            Some(def) if def.fieldnames != expected_fields => {
+                Err(RosLibRustError::DefinitionMismatch {
+                    topic: topic_name.to_string(),
+                    topic_type: requested,
+                })
+            }
+            Some(_) => Ok(()),
+            None => {
+                debug!("rosapi has no definition for {requested}, skipping definition check");
+                Ok(())
+            }
         }
-        Ok(Publisher::new(topic.to_string(), self.clone()))
     }
 
-    /// Calls a ros service and returns the response
-    ///
-    /// Service calls can fail if communication is interrupted.
-    /// This method is currently unaffected by the clients Timeout configuration.
-    ///
-    /// Roadmap:
-    ///   - Provide better error information when a service call fails
-    ///   - Integrate with ClientHandle's timeout better
+    // Calls a rosapi service, reporting rosapi being missing as RosapiUnavailable. Other errors,
+    // such as rosapi failing to handle the call, are returned as they are.
+    pub(crate) async fn call_rosapi<Req: RosMessageType, Res: RosMessageType>(
+        &self,
+        service: &str,
+        request: Req,
+    ) -> RosLibRustResult<Res> {
+        match self.call_service_inner(service, request, None).await {
+            Err(RosLibRustError::ServerError(e)) if missing_service(&e) == Some(service) => {
+                Err(RosLibRustError::RosapiUnavailable(e))
+            }
+            result => result,
+        }
+    }
+
+    /// Waits for a single message on a topic, such as the current map or the first GPS fix, and
+    /// unsubscribes again.
     ///
+    /// `timeout` covers both subscribing and receiving the message. Other subscribers to the same
+    /// topic are unaffected, the topic is only unsubscribed from rosbridge if this was the last one.
     /// ```no_run
     /// # roslibrust_codegen_macro::find_and_generate_ros_messages!(
-    /// #    "assets/ros1_common_interfaces/rosapi"
+    /// #    "assets/ros1_common_interfaces/std_msgs"
     /// # );
     /// # #[tokio::main]
     /// # async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    ///   // Create a new client
-    ///   let mut handle = roslibrust::ClientHandle::new("ws://localhost:9090").await?;
-    ///   // Call service using implied types
-    ///   // Note: () "the empty type" has an explicit definition as a RosMessageType and can be used in place of naming an empty message
-    ///   let response: rosapi::GetTimeResponse  = handle.call_service("/rosapi/get_time", ()).await?;
-    ///   // Call service using explicit types
-    ///   let response = handle.call_service::<rosapi::GetTimeRequest, rosapi::GetTimeResponse>("/rosapi/get_time", rosapi::GetTimeRequest{}).await?;
+    ///   let handle = roslibrust::ClientHandle::new("ws://localhost:9090").await?;
+    ///   let header = handle
+    ///     .subscribe_once::<std_msgs::Header>("/topic", std::time::Duration::from_secs(5))
+    ///     .await?;
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn call_service<Req: RosMessageType, Res: RosMessageType>(
+    pub async fn subscribe_once<Msg>(
         &self,
-        service: &str,
-        req: Req,
-    ) -> RosLibRustResult<Res> {
-        self.check_for_disconnect()?;
-        let (tx, rx) = tokio::sync::oneshot::channel();
-        let rand_string: String = uuid::Uuid::new_v4().to_string();
-        let client = self.inner.read().await;
-        {
-            if client
-                .service_calls
-                .insert(rand_string.clone(), tx)
-                .is_some()
-            {
-                error!("ID collision encountered in call_service");
-            }
-        }
-        {
-            let mut comm = client.writer.write().await;
-            timeout(
-                client.opts.timeout,
-                comm.call_service(service, &rand_string, req),
-            )
-            .await?;
-        }
-
-        // Having to do manual timeout logic here because of error types
-        let recv = if let Some(timeout) = client.opts.timeout {
-            tokio::time::timeout(timeout, rx).await?
-        } else {
-            rx.await
-        };
-
-        // Attempt to actually pull data out
-        let msg = match recv {
-            Ok(msg) => msg,
-            Err(e) =>
-            // TODO remove panic! here, this could result from dropping communication, need to handle disconnect better
-            panic!("The sender end of a service channel was dropped while rx was being awaited, this should not be possible: {}", e),
+        topic_name: &str,
+        timeout: Duration,
+    ) -> Result<Msg, SubscribeOnceError>
+    where
+        Msg: RosMessageType,
+    {
+        let start = tokio::time::Instant::now();
+        let topic_name = &self.resolve_name(topic_name).await?;
+        let timed_out = |subscribed| SubscribeOnceError::Timeout {
+            topic: topic_name.to_string(),
+            waited: timeout,
+            subscribed,
         };
+        let subscribe = self._subscribe::<Msg>(topic_name, SubscriberDispatch::default());
+        let subscriber = tokio::time::timeout(timeout, subscribe)
+            .await
+            .map_err(|_| timed_out(false))??;
+        // Dropping the subscriber on return unsubscribes
+        subscriber
+            .next_timeout(timeout.saturating_sub(start.elapsed()))
+            .await
+            .map_err(|_| timed_out(true))
+    }
 
-        // Attempt to convert data to response type
-        match serde_json::from_value(msg.clone()) {
-            Ok(val) => Ok(val),
-            Err(e) => {
-                // We failed to parse the value as an expected type, before just giving up, try to parse as string
-                // if we got a string it indicates a server side error, otherwise we got the wrong datatype back
-                match serde_json::from_value(msg) {
-                    Ok(s) => return Err(RosLibRustError::ServerError(s)),
-                    Err(_) => {
-                        // Return the error from the origional parse
-                        return Err(RosLibRustError::InvalidMessage(e));
+    /// Subscribes to several topics of the same type, such as every robot's `/odom`, merging
+    /// their messages into one stream of the topic each message arrived on and the message.
+    ///
+    /// Topics listed more than once are only subscribed to once. Each topic is a separate
+    /// subscription like one made by [ClientHandle::subscribe], and is resubscribed when the
+    /// client reconnects. The client's timeout covers subscribing to all of them, and if any
+    /// fails the topics already subscribed to are unsubscribed again. Dropping the returned
+    /// stream unsubscribes from every topic. Messages are labelled with their topic's name as
+    /// resolved within [ClientHandleOptions::namespace].
+    /// ```no_run
+    /// # roslibrust_codegen_macro::find_and_generate_ros_messages!(
+    /// #    "assets/ros1_common_interfaces/std_msgs"
+    /// # );
+    /// # use futures::StreamExt;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    ///   let handle = roslibrust::ClientHandle::new("ws://localhost:9090").await?;
+    ///   let mut headers = handle
+    ///     .subscribe_many::<std_msgs::Header>(&["/robot1/header", "/robot2/header"])
+    ///     .await?;
+    ///   while let Some((topic, header)) = headers.next().await {
+    ///     println!("{topic}: {}", header.seq);
+    ///   }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn subscribe_many<Msg>(
+        &self,
+        topic_names: &[&str],
+    ) -> RosLibRustResult<impl futures::Stream<Item = (String, Msg)> + Send + Unpin>
+    where
+        Msg: RosMessageType,
+    {
+        let mut topics: Vec<String> = vec![];
+        for topic in topic_names {
+            let topic = self.resolve_name(topic).await?;
+            if !topics.contains(&topic) {
+                topics.push(topic);
+            }
+        }
+        let subscribe_timeout = self.inner.read().await.opts.timeout;
+        let subscribers = timeout(subscribe_timeout, async {
+            let mut subscribers = Vec::with_capacity(topics.len());
+            for topic in topics {
+                // Subscribers made so far are dropped, and so unsubscribed, if this fails
+                let subscriber = self
+                    ._subscribe::<Msg>(&topic, SubscriberDispatch::default())
+                    .await?;
+                subscribers.push((topic, subscriber));
+            }
+            Ok(subscribers)
+        })
+        .await?;
+        Ok(futures::stream::select_all(subscribers.into_iter().map(
+            |(topic, subscriber)| {
+                Box::pin(futures::stream::unfold(
+                    (topic, subscriber),
+                    |(topic, subscriber)| async move {
+                        let msg = subscriber.next().await;
+                        Some(((topic.clone(), msg), (topic, subscriber)))
+                    },
+                ))
+            },
+        )))
+    }
+
+    /// Subscribes to a topic without knowing its type at compile time, yielding the type name of
+    /// the topic along with each message as raw json.
+    ///
+    /// If the type of the topic isn't already known from another subscription, it is looked up
+    /// via rosapi's `/rosapi/topic_type` service, and will be empty if rosapi doesn't know the topic.
+    /// Untyped and typed subscriptions to the same topic share a single subscription with
+    /// rosbridge, and each receive every message.
+    ///
+    /// Like [ClientHandle::subscribe], dropping the returned stream unsubscribes.
+    /// ```no_run
+    /// # use futures::StreamExt;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    ///   let handle = roslibrust::ClientHandle::new("ws://localhost:9090").await?;
+    ///   let mut messages = handle.subscribe_any("/some/topic").await?;
+    ///   while let Some((topic_type, msg)) = messages.next().await {
+    ///     println!("{topic_type}: {msg}");
+    ///   }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn subscribe_any(
+        &self,
+        topic_name: &str,
+    ) -> RosLibRustResult<impl futures::Stream<Item = (String, Value)> + Send + Unpin> {
+        let topic_name = &self.resolve_name(topic_name).await?;
+        let topic_type = self.topic_type_of(topic_name).await?;
+        let type_copy = topic_type.clone();
+        let subscribe_timeout = self.inner.read().await.opts.timeout;
+        let subscriber = timeout(
+            subscribe_timeout,
+            self.subscribe_with(
+                topic_name,
+                &topic_type,
+                SubscriberDispatch::default(),
+                move |msg| match serde_json::from_str::<Value>(msg.json()) {
+                    Ok(msg) => Some((type_copy.clone(), msg)),
+                    Err(e) => {
+                        error!(
+                            "Failed to parse ros message as json: {e:?}. Message will be skipped!"
+                        );
+                        None
                     }
+                },
+            ),
+        )
+        .await?;
+        Ok(Box::pin(futures::stream::unfold(
+            subscriber,
+            |subscriber| async move {
+                let msg = subscriber.next().await;
+                Some((msg, subscriber))
+            },
+        )))
+    }
+
+    /// Subscribes to a topic of any type like [ClientHandle::subscribe_any], yielding the json of
+    /// each message exactly as rosbridge sent it, for forwarding messages on to other systems.
+    ///
+    /// Messages are never parsed for these subscribers, each is a slice of the websocket frame it
+    /// arrived in rather than a copy. With [Compression::Cbor] messages arrive as CBOR, and are
+    /// converted to json first.
+    ///
+    /// Like [ClientHandle::subscribe], dropping the returned stream unsubscribes.
+    /// ```no_run
+    /// # use futures::StreamExt;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    ///   let handle = roslibrust::ClientHandle::new("ws://localhost:9090").await?;
+    ///   let mut messages = handle.subscribe_raw_json("/some/topic").await?;
+    ///   while let Some(json) = messages.next().await {
+    ///     println!("{}", String::from_utf8_lossy(&json));
+    ///   }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn subscribe_raw_json(
+        &self,
+        topic_name: &str,
+    ) -> RosLibRustResult<impl futures::Stream<Item = Bytes> + Send + Unpin> {
+        let topic_name = &self.resolve_name(topic_name).await?;
+        let topic_type = self.topic_type_of(topic_name).await?;
+        let subscribe_timeout = self.inner.read().await.opts.timeout;
+        let subscriber = timeout(
+            subscribe_timeout,
+            // Nothing is parsed, so there's no work worth moving off the read loop
+            self.subscribe_with(topic_name, &topic_type, SubscriberDispatch::Inline, |msg| {
+                Some(msg.bytes())
+            }),
+        )
+        .await?;
+        Ok(Box::pin(futures::stream::unfold(
+            subscriber,
+            |subscriber| async move {
+                let msg = subscriber.next().await;
+                Some((msg, subscriber))
+            },
+        )))
+    }
+
+    /// Validates a topic, service, or action name, resolving it within the client's namespace if
+    /// it has one, see [ClientHandleOptions::namespace]
+    pub(crate) async fn resolve_name(&self, name: &str) -> RosLibRustResult<String> {
+        Ok(self.inner.read().await.opts.resolve_name(name)?)
+    }
+
+    /// Converts a type name to the form rosbridge expects for the client's ROS version, e.g.
+    /// `std_msgs/msg/Header` for ROS 2
+    pub(crate) async fn wire_type_name(&self, type_name: &str, interface: &str) -> String {
+        let ros_version = self.inner.read().await.opts.ros_version;
+        comm::wire_type_name(ros_version, type_name, interface)
+    }
+
+    /// The type of a topic, from an existing subscription to it or else asking rosapi
+    async fn topic_type_of(&self, topic_name: &str) -> RosLibRustResult<String> {
+        let known_type = self
+            .inner
+            .read()
+            .await
+            .subscriptions
+            .get(topic_name)
+            .map(|subscription| subscription.topic_type.clone());
+        match known_type {
+            Some(topic_type) => Ok(topic_type),
+            None => {
+                let response: TopicTypeResponse = self
+                    .call_service(
+                        "/rosapi/topic_type",
+                        TopicTypeRequest {
+                            topic: topic_name.to_string(),
+                        },
+                    )
+                    .await?;
+                Ok(response.r#type)
+            }
+        }
+    }
+
+    // Publishes a message
+    // Waits for the client to reconnect if disconnected
+    // Returns success when message is put on websocket (no confirmation of receipt)
+    pub(crate) async fn publish<T>(&self, topic: &str, msg: T) -> RosLibRustResult<()>
+    where
+        T: RosMessageType,
+    {
+        // Publishers hold names which are already resolved
+        self.publish_resolved(topic, T::ROS_TYPE_NAME, serde_json::to_value(&msg)?)
+            .await
+    }
+
+    /// Publishes a message given as raw json on a topic of the given type, for when the type is
+    /// not known at compile time.
+    ///
+    /// Unlike [Publisher::publish] the topic does not need to be advertised first, rosbridge will
+    /// advertise it automatically. No checking is done that `msg` matches `topic_type`.
+    pub async fn publish_any(
+        &self,
+        topic: &str,
+        topic_type: &str,
+        msg: Value,
+    ) -> RosLibRustResult<()> {
+        let topic = &self.resolve_name(topic).await?;
+        self.publish_resolved(topic, topic_type, msg).await
+    }
+
+    async fn publish_resolved(
+        &self,
+        topic: &str,
+        topic_type: &str,
+        mut msg: Value,
+    ) -> RosLibRustResult<()> {
+        let client = self.connected_client().await?;
+        let topic_type = &comm::wire_type_name(client.opts.ros_version, topic_type, "msg");
+        if client.opts.ros_version == RosVersion::ROS2 {
+            comm::ros2_time_fields(&mut msg);
+        }
+        // Connecting isn't counted against the flush timeout
+        let connection = client.connection().await?;
+        let send = connection.writer.publish_value(topic, topic_type, &msg);
+        let size = match client.opts.publish_flush_timeout.or(client.opts.timeout) {
+            Some(t) => tokio::time::timeout(t, send)
+                .await
+                .map_err(|_| RosLibRustError::PublishFlushTimeout(t))??,
+            None => send.await?,
+        };
+        match client.publishers.get_mut(topic) {
+            Some(mut publisher) => {
+                if let Some(topic_metrics) = &publisher.metrics {
+                    topic_metrics.record_sent(size);
+                }
+                if publisher.latch {
+                    publisher.latched_msg = Some(msg);
+                }
+            }
+            // Publishing without advertising is rare enough to look the counters up each time
+            None => {
+                if let Some(topic_metrics) = client.metrics.topic(topic) {
+                    topic_metrics.record_sent(size);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Advertises a topic to be published to and returns a type specific publisher to use.
+    ///
+    /// Advertising a topic which this client already advertises returns another publisher sharing
+    /// the existing advertisement, whose options are kept. The type must match the existing
+    /// advertisement, otherwise this fails with [RosLibRustError::TopicTypeMismatch]. The topic is
+    /// automatically un-advertised once every publisher of it, including clones, is dropped.
+    ///
+    /// If called while the client is reconnecting this waits for the reconnect, like
+    /// [ClientHandle::subscribe].
+    ///
+    /// No type checking of the advertised type is performed. If the serialization of T is not
+    /// accepted by rosbridge as compatible with rosmaster's type, that information will only be
+    /// available in rosbridge's logs.
+    ///
+    /// ```no_run
+    /// # roslibrust_codegen_macro::find_and_generate_ros_messages!(
+    /// #    "assets/ros1_common_interfaces/std_msgs"
+    /// # );
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    ///   // Create a new client
+    ///   let mut handle = roslibrust::ClientHandle::new("ws://localhost:9090").await?;
+    ///   // Advertise using ::<T> style
+    ///   let mut publisher = handle.advertise::<std_msgs::Header>("/topic").await?;
+    ///   // Advertise using explicit type
+    ///   let mut publisher2: roslibrust::Publisher<std_msgs::Header> = handle.advertise("/other_topic").await?;
+    ///   # Ok(())
+    /// # }
+    /// ```
+    pub async fn advertise<T>(&self, topic: &str) -> RosLibRustResult<Publisher<T>>
+    where
+        T: RosMessageType,
+    {
+        self.advertise_with_options(topic, AdvertiseOptions::default())
+            .await
+    }
+
+    /// Advertises a topic like [ClientHandle::advertise], with additional control over how the
+    /// topic is advertised.
+    ///
+    /// ```no_run
+    /// # roslibrust_codegen_macro::find_and_generate_ros_messages!(
+    /// #    "assets/ros1_common_interfaces/std_msgs"
+    /// # );
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    ///   let handle = roslibrust::ClientHandle::new("ws://localhost:9090").await?;
+    ///   // Subscribers connecting later will still receive the last message published
+    ///   let options = roslibrust::AdvertiseOptions { latch: true };
+    ///   let publisher = handle.advertise_with_options::<std_msgs::String>("/config", options).await?;
+    ///   # Ok(())
+    /// # }
+    /// ```
+    pub async fn advertise_with_options<T>(
+        &self,
+        topic: &str,
+        options: AdvertiseOptions,
+    ) -> RosLibRustResult<Publisher<T>>
+    where
+        T: RosMessageType,
+    {
+        let topic = &self.resolve_name(topic).await?;
+        let client = self.inner.read().await;
+        let connection = self.current_connection(&client).await?;
+        let topic_type = comm::wire_type_name(client.opts.ros_version, T::ROS_TYPE_NAME, "msg");
+        // Only the first publisher of a topic advertises it, later ones share its advertisement
+        let (publishers, advertised) = match client.publishers.entry(topic.to_string()) {
+            dashmap::mapref::entry::Entry::Occupied(existing) => {
+                let existing = existing.get();
+                if existing.topic_type != topic_type {
+                    return Err(RosLibRustError::TopicTypeMismatch {
+                        topic: topic.to_string(),
+                        existing: existing.topic_type.clone(),
+                        requested: topic_type,
+                    });
+                }
+                existing.publishers.fetch_add(1, Ordering::SeqCst);
+                return Ok(Publisher::new(
+                    topic.to_string(),
+                    self.clone(),
+                    existing.publishers.clone(),
+                ));
+            }
+            dashmap::mapref::entry::Entry::Vacant(vacant) => {
+                let publishers = Arc::new(AtomicUsize::new(1));
+                let _entry = vacant.insert(PublisherHandle {
+                    topic_type: topic_type.clone(),
+                    latch: options.latch,
+                    latched_msg: None,
+                    publishers: publishers.clone(),
+                    metrics: client.metrics.topic(topic),
+                });
+                // Queued while the entry is still locked, so an unadvertise of the topic by its
+                // previous publishers is always queued before this
+                let advertised = connection.map(|connection| {
+                    connection
+                        .writer
+                        .advertise(topic, &topic_type, options.latch)
+                });
+                (publishers, advertised)
+            }
+        };
+        // Dropping the publisher on failure removes its entry again
+        let publisher = Publisher::new(topic.to_string(), self.clone(), publishers);
+
+        // While reconnecting the entry is enough, the reconnect advertises every entry
+        let Some(advertised) = advertised else {
+            let wait = client.pending_operation_timeout();
+            drop(client);
+            self.wait_for_reconnect(wait).await?;
+            return Ok(publisher);
+        };
+        advertised.await?;
+        Ok(publisher)
+    }
+
+    /// Calls a ros service and returns the response
+    ///
+    /// Service calls can fail if communication is interrupted, or with
+    /// [RosLibRustError::ServiceCallTimeout] if no response arrives within the client's
+    /// [default service call timeout](ClientHandleOptions::default_service_call_timeout).
+    ///
+    /// Roadmap:
+    ///   - Provide better error information when a service call fails
+    ///
+    /// ```no_run
+    /// # roslibrust_codegen_macro::find_and_generate_ros_messages!(
+    /// #    "assets/ros1_common_interfaces/rosapi"
+    /// # );
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    ///   // Create a new client
+    ///   let mut handle = roslibrust::ClientHandle::new("ws://localhost:9090").await?;
+    ///   // Call service using implied types
+    ///   // Note: () "the empty type" has an explicit definition as a RosMessageType and can be used in place of naming an empty message
+    ///   let response: rosapi::GetTimeResponse  = handle.call_service("/rosapi/get_time", ()).await?;
+    ///   // Call service using explicit types
+    ///   let response = handle.call_service::<rosapi::GetTimeRequest, rosapi::GetTimeResponse>("/rosapi/get_time", rosapi::GetTimeRequest{}).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn call_service<Req: RosMessageType, Res: RosMessageType>(
+        &self,
+        service: &str,
+        req: Req,
+    ) -> RosLibRustResult<Res> {
+        let call_timeout = {
+            let client = self.inner.read().await;
+            client
+                .opts
+                .default_service_call_timeout
+                .or(client.opts.timeout)
+        };
+        self.call_service_inner(service, req, call_timeout).await
+    }
+
+    /// Calls a ros service like [ClientHandle::call_service], but waits up to `timeout` for the
+    /// response instead of the client's default service call timeout.
+    pub async fn call_service_with_timeout<Req: RosMessageType, Res: RosMessageType>(
+        &self,
+        service: &str,
+        req: Req,
+        timeout: Duration,
+    ) -> RosLibRustResult<Res> {
+        self.call_service_inner(service, req, Some(timeout)).await
+    }
+
+    /// Calls a ros service like [ClientHandle::call_service], optionally retrying failed attempts
+    /// and bounding the whole call with a deadline. Each attempt is a separate call with its own
+    /// id, so a late response to an attempt which timed out is ignored.
+    /// ```no_run
+    /// # roslibrust_codegen_macro::find_and_generate_ros_messages!(
+    /// #    "assets/ros1_common_interfaces/std_srvs"
+    /// # );
+    /// # use std::time::Duration;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    ///   let handle = roslibrust::ClientHandle::new("ws://localhost:9090").await?;
+    ///   let options = roslibrust::ServiceCallOptions {
+    ///     timeout: Some(Duration::from_secs(1)),
+    ///     deadline: Some(Duration::from_secs(10)),
+    ///     retry: Some(roslibrust::RetryPolicy {
+    ///       max_attempts: 5,
+    ///       ..Default::default()
+    ///     }),
+    ///   };
+    ///   let response: std_srvs::TriggerResponse = handle
+    ///     .call_service_with_options("/arm/home", std_srvs::TriggerRequest {}, options)
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn call_service_with_options<Req: RosMessageType, Res: RosMessageType>(
+        &self,
+        service: &str,
+        req: Req,
+        options: ServiceCallOptions,
+    ) -> RosLibRustResult<Res> {
+        let service = &self.resolve_name(service).await?;
+        let call_timeout = match options.timeout {
+            Some(timeout) => Some(timeout),
+            None => {
+                let client = self.inner.read().await;
+                client
+                    .opts
+                    .default_service_call_timeout
+                    .or(client.opts.timeout)
+            }
+        };
+        let args = self.service_args(&req).await?;
+        let started = tokio::time::Instant::now();
+        let attempts = async {
+            let mut failures = 0;
+            loop {
+                let attempt_started = tokio::time::Instant::now();
+                let result = self
+                    .call_service_value(service, args.clone(), call_timeout, Compression::None)
+                    .await;
+                self.metrics
+                    .record_service_call(attempt_started.elapsed(), result.is_ok());
+                let error = match result {
+                    Ok(msg) => return Ok(serde_json::from_value(msg)?),
+                    Err(error) => error,
+                };
+                failures += 1;
+                let Some(retry) = &options.retry else {
+                    return Err(error);
+                };
+                if failures >= retry.max_attempts || !(retry.retry_if)(&error) {
+                    return Err(error);
+                }
+                let delay = retry
+                    .backoff
+                    .jittered_delay(failures, &mut rand::thread_rng());
+                debug!("Service call to {service} failed, retrying in {delay:?}: {error}");
+                tokio::time::sleep(delay).await;
+                if retry.wait_for_service {
+                    let remaining = options.deadline.map_or(Duration::MAX, |deadline| {
+                        deadline.saturating_sub(started.elapsed())
+                    });
+                    self.wait_for_service(service, remaining).await?;
                 }
             }
+        };
+        match options.deadline {
+            Some(deadline) => tokio::time::timeout(deadline, attempts)
+                .await
+                .map_err(|_| RosLibRustError::ServiceCallTimeout {
+                    service: service.to_owned(),
+                    timeout: deadline,
+                })?,
+            None => attempts.await,
+        }
+    }
+
+    async fn call_service_inner<Req: RosMessageType, Res: RosMessageType>(
+        &self,
+        service: &str,
+        req: Req,
+        call_timeout: Option<Duration>,
+    ) -> RosLibRustResult<Res> {
+        let service = &self.resolve_name(service).await?;
+        let started = tokio::time::Instant::now();
+        let result = self
+            .call_service_unrecorded(service, req, call_timeout)
+            .await;
+        self.metrics
+            .record_service_call(started.elapsed(), result.is_ok());
+        result
+    }
+
+    async fn call_service_unrecorded<Req: RosMessageType, Res: RosMessageType>(
+        &self,
+        service: &str,
+        req: Req,
+        call_timeout: Option<Duration>,
+    ) -> RosLibRustResult<Res> {
+        let args = self.service_args(&req).await?;
+        let msg = self
+            .call_service_value(service, args, call_timeout, Compression::None)
+            .await?;
+        Ok(serde_json::from_value(msg)?)
+    }
+
+    /// Calls a service with a request given as raw json, returning the raw json of the response,
+    /// for when the service's type is not known at compile time, e.g. when bridging or in tooling.
+    ///
+    /// The request is sent exactly as given, without the renaming of time fields typed calls do
+    /// for ROS2, and no checking is done that it matches the service's type. Fails with
+    /// [RosLibRustError::ServiceCallTimeout] if there is no response within `timeout`.
+    pub async fn call_service_raw(
+        &self,
+        service: &str,
+        args: Value,
+        timeout: Duration,
+    ) -> RosLibRustResult<Value> {
+        self.call_service_raw_with(service, args, timeout, Compression::None)
+            .await
+    }
+
+    /// Calls a service with raw json like [ClientHandle::call_service_raw], but has rosbridge send
+    /// the response as a binary CBOR frame, which is considerably smaller for responses with large
+    /// numeric arrays such as maps. The call itself is sent as json like any other operation.
+    ///
+    /// The response is decoded to the same json as messages of subscriptions using
+    /// [Compression::Cbor], in which `uint8[]` fields are arrays of numbers rather than base64.
+    pub async fn call_service_cbor_raw(
+        &self,
+        service: &str,
+        args: Value,
+        timeout: Duration,
+    ) -> RosLibRustResult<Value> {
+        self.call_service_raw_with(service, args, timeout, Compression::Cbor)
+            .await
+    }
+
+    async fn call_service_raw_with(
+        &self,
+        service: &str,
+        args: Value,
+        timeout: Duration,
+        compression: Compression,
+    ) -> RosLibRustResult<Value> {
+        let service = &self.resolve_name(service).await?;
+        let started = tokio::time::Instant::now();
+        let result = self
+            .call_service_value(service, args, Some(timeout), compression)
+            .await;
+        self.metrics
+            .record_service_call(started.elapsed(), result.is_ok());
+        result
+    }
+
+    // Serializes a typed service request, renaming its time fields when talking to ROS2
+    async fn service_args<Req: RosMessageType>(&self, req: &Req) -> RosLibRustResult<Value> {
+        let mut args = serde_json::to_value(req)?;
+        if self.inner.read().await.opts.ros_version == RosVersion::ROS2 {
+            comm::ros2_time_fields(&mut args);
         }
+        Ok(args)
+    }
+
+    // Makes a single call with the request already serialized, returning the response's values
+    async fn call_service_value(
+        &self,
+        service: &str,
+        args: Value,
+        call_timeout: Option<Duration>,
+        compression: Compression,
+    ) -> RosLibRustResult<Value> {
+        // Connecting isn't counted against the service call timeout
+        drop(self.connected_client().await?);
+        let call = async {
+            let (tx, rx) = tokio::sync::oneshot::channel();
+            // The client lock is released before waiting on the response so a reconnect isn't blocked
+            let _pending = {
+                let client = self.inner.read().await;
+                let pending = client.register_service_call(tx);
+                let connection = client.connection().await?;
+                connection
+                    .writer
+                    .call_service(service, &pending.id, args, compression)
+                    .await?;
+                pending
+            };
+            // The sender is only dropped without a response if the connection was lost or the client shutdown
+            rx.await.map_err(|_| RosLibRustError::Disconnected)?
+        };
+        let msg = match call_timeout {
+            Some(t) => tokio::time::timeout(t, call).await.map_err(|_| {
+                RosLibRustError::ServiceCallTimeout {
+                    service: service.to_owned(),
+                    timeout: t,
+                }
+            })??,
+            None => call.await?,
+        };
+        Ok(msg)
     }
 
     /// Advertises a service and returns a handle that manages the lifetime of the service.
     /// Service will be active until the handle is dropped!
     ///
+    /// Calls are handled one at a time, see [ClientHandle::advertise_service_with_options] to
+    /// handle them concurrently.
+    ///
     /// See examples/service_server.rs for usage.
     pub async fn advertise_service<T, F>(
         &self,
@@ -435,44 +2445,103 @@ impl ClientHandle {
             + Sync
             + 'static,
     {
-        self.check_for_disconnect()?;
-        {
-            let client = self.inner.read().await;
-            let mut writer = client.writer.write().await;
-            // Before proceeding check we don't already have an active service_server for this topic
-            if client.services.contains_key(topic) {
-                error!(
-                    "Re-registering a server for the pre-existing topic: {topic} This will fail!"
-                );
-                return Err(RosLibRustError::Unexpected(anyhow!("roslibrust does not support re-advertising a service without first dropping the previous Service")));
-            }
-
-            // We need to do type erasure and hide the request by wrapping their closure in a generic closure
-            let erased_closure = move |message: &str| -> Result<
-                serde_json::Value,
-                Box<dyn std::error::Error + Send + Sync>,
-            > {
-                // Type erase the incoming type
-                let parsed_msg = serde_json::from_str(message)?;
-                let response = server(parsed_msg)?;
-                // Type erase the outgoing type
-                let response_string = serde_json::json!(response);
-                Ok(response_string)
-            };
-
-            let res = client
-                .services
-                .insert(topic.to_string(), Box::new(erased_closure));
-            if let Some(_previous_server) = res {
-                error!("This should not be possible, but somehow you managed to double advertise a service despite the guard...");
-            }
-            // Don't advertise the service until we've reached this point, otherwise we'll double advertise
-            writer.advertise_service(topic, T::ROS_SERVICE_NAME).await?;
-        } // Drop client lock here so we can clone without creating an issue
+        self.advertise_service_with_options::<T, F>(topic, server, ServiceOptions::default())
+            .await
+    }
 
-        Ok(ServiceHandle {
-            client: self.clone(),
-            topic: topic.to_string(),
+    /// Advertises a service like [ClientHandle::advertise_service], limiting how many calls are
+    /// handled at once and how long each may take.
+    /// ```no_run
+    /// # roslibrust_codegen_macro::find_and_generate_ros_messages!(
+    /// #    "assets/ros1_common_interfaces/std_srvs"
+    /// # );
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    ///   let handle = roslibrust::ClientHandle::new("ws://localhost:9090").await?;
+    ///   let options = roslibrust::ServiceOptions {
+    ///     max_concurrent: 4,
+    ///     queue_size: Some(16),
+    ///     timeout: Some(std::time::Duration::from_secs(5)),
+    ///   };
+    ///   let _service = handle
+    ///     .advertise_service_with_options::<std_srvs::Trigger, _>(
+    ///       "/query",
+    ///       |_request| Ok(Default::default()),
+    ///       options,
+    ///     )
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn advertise_service_with_options<T, F>(
+        &self,
+        topic: &str,
+        server: F,
+        options: ServiceOptions,
+    ) -> RosLibRustResult<ServiceHandle>
+    where
+        T: RosServiceType,
+        F: Fn(
+                T::Request,
+            )
+                -> Result<T::Response, Box<dyn std::error::Error + 'static + Send + Sync>>
+            + Send
+            + Sync
+            + 'static,
+    {
+        let topic = &self.resolve_name(topic).await?;
+        {
+            let client = self.connected_client().await?;
+            let connection = client.connection().await?;
+
+            let ros_version = client.opts.ros_version;
+            // We need to do type erasure and hide the request by wrapping their closure in a generic closure
+            let erased_closure = move |message: &str| -> Result<
+                serde_json::Value,
+                Box<dyn std::error::Error + Send + Sync>,
+            > {
+                // Type erase the incoming type
+                let parsed_msg = serde_json::from_str(message)?;
+                let response = server(parsed_msg)?;
+                // Type erase the outgoing type
+                let mut response_string = serde_json::json!(response);
+                if ros_version == RosVersion::ROS2 {
+                    comm::ros2_time_fields(&mut response_string);
+                }
+                Ok(response_string)
+            };
+
+            // Checking for an active service_server for this topic and taking its place happen
+            // under the entry's lock, so two servers racing to advertise can't both succeed
+            let advertised = match client.services.entry(topic.to_string()) {
+                dashmap::mapref::entry::Entry::Occupied(_) => {
+                    error!(
+                        "Re-registering a server for the pre-existing topic: {topic} This will fail!"
+                    );
+                    return Err(RosLibRustError::Unexpected(anyhow!("roslibrust does not support re-advertising a service without first dropping the previous Service")));
+                }
+                dashmap::mapref::entry::Entry::Vacant(vacant) => {
+                    let permits = Arc::new(Semaphore::new(options.max_concurrent.max(1)));
+                    let _entry = vacant.insert(Arc::new(ServiceServer {
+                        callback: Arc::new(erased_closure),
+                        options,
+                        permits,
+                        counters: client.metrics.service(topic),
+                    }));
+                    // Queued while the entry is locked, so the unadvertise of a previous server
+                    // of this service is always queued before it
+                    connection.writer.advertise_service(
+                        topic,
+                        &comm::wire_type_name(ros_version, T::ROS_SERVICE_NAME, "srv"),
+                    )
+                }
+            };
+            advertised.await?;
+        } // Drop client lock here so we can clone without creating an issue
+
+        Ok(ServiceHandle {
+            client: self.clone(),
+            topic: topic.to_string(),
         })
     }
 
@@ -493,8 +2562,14 @@ impl ClientHandle {
             }
 
             // Regardless of whether we found an entry we should still send he unadvertise_service message to rosbridge
-            let mut writer = client.writer.write().await;
-            let res = writer.unadvertise_service(&topic).await;
+            // Services can only have been advertised once connected
+            let Some(connection) = client.connection.get() else {
+                return;
+            };
+            if client.is_closed.load(Ordering::Relaxed) {
+                return;
+            }
+            let res = connection.writer.unadvertise_service(&topic).await;
             if let Err(e) = res {
                 error!("Failed to send unadvertise_service message when service handle was dropped for `{topic}`: {e}");
             }
@@ -503,319 +2578,3371 @@ impl ClientHandle {
 
     // This function is not async specifically so it can be called from drop
     // same reason why it doesn't return anything
-    // Called automatically when Publisher is dropped
+    // Called automatically when the last Publisher of a topic is dropped
     pub(crate) fn unadvertise(&self, topic_name: &str) {
         let copy = self.clone();
         let topic_name_copy = topic_name.to_string();
         tokio::spawn(async move {
-            // Remove publisher from our records
             let client = copy.inner.read().await;
-            client.publishers.remove(&topic_name_copy);
+            // The unadvertise is queued while the entry is locked, so an advertise of the same
+            // topic either joins the record before it is removed, or is queued after our unadvertise
+            let unadvertised = match client.publishers.entry(topic_name_copy.clone()) {
+                dashmap::mapref::entry::Entry::Occupied(entry)
+                    if entry.get().publishers.load(Ordering::SeqCst) == 0 =>
+                {
+                    // Publishers can only have been advertised once connected
+                    let unadvertised = client
+                        .connection
+                        .get()
+                        .filter(|_| !client.is_closed.load(Ordering::Relaxed))
+                        .map(|connection| connection.writer.unadvertise(&topic_name_copy));
+                    entry.remove();
+                    unadvertised
+                }
+                _ => {
+                    debug!(
+                        "{topic_name_copy} was advertised again before it could be unadvertised"
+                    );
+                    return;
+                }
+            };
 
-            // Send unadvertise message
-            {
-                debug!("Unadvertise waiting for comm lock");
-                let mut comm = client.writer.write().await;
-                debug!("Unadvertise got comm lock");
-                if let Err(e) = comm.unadvertise(&topic_name_copy).await {
+            if let Some(unadvertised) = unadvertised {
+                if let Err(e) = unadvertised.await {
                     error!("Failed to send unadvertise in comm layer: {:?}", e);
                 }
             }
         });
     }
 
-    // This function removes the entry for a subscriber in from the client, and if it is the last
-    // subscriber for a given topic then dispatches an unsubscribe message to the master/bridge
-    pub(crate) fn unsubscribe(&self, topic_name: &str, id: &uuid::Uuid) -> RosLibRustResult<()> {
-        // Copy so we can move into closure
-        let client = self.clone();
-        let topic_name = topic_name.to_string();
-        let id = *id;
-        // Actually send the unsubscribe message in a task so subscriber::Drop can call this function
-        tokio::spawn(async move {
-            // Identify the subscription entry for the subscriber
-            let client = client.inner.read().await;
-            let mut subscription = match client.subscriptions.get_mut(&topic_name) {
-                Some(subscription) => subscription,
-                None => {
-                    error!("Topic not found in subscriptions upon dropping. This should be impossible and indicates a bug in the roslibrust crate. Topic: {topic_name} UUID: {id:?}");
-                    return;
-                }
+    // This function removes the entry for a subscriber in from the client, and if it is the last
+    // subscriber for a given topic then dispatches an unsubscribe message to the master/bridge
+    pub(crate) fn unsubscribe(&self, topic_name: &str, id: &uuid::Uuid) -> RosLibRustResult<()> {
+        // Copy so we can move into closure
+        let client = self.clone();
+        let topic_name = topic_name.to_string();
+        let id = *id;
+        // Actually send the unsubscribe message in a task so subscriber::Drop can call this function
+        tokio::spawn(async move {
+            // Identify the subscription entry for the subscriber
+            let client = client.inner.read().await;
+            let _changing = client.subscription_changes.lock().await;
+            let is_last = {
+                let mut subscription = match client.subscriptions.get_mut(&topic_name) {
+                    Some(subscription) => subscription,
+                    None => {
+                        error!("Topic not found in subscriptions upon dropping. This should be impossible and indicates a bug in the roslibrust crate. Topic: {topic_name} UUID: {id:?}");
+                        return;
+                    }
+                };
+                if subscription.value_mut().handles.remove(&id).is_none() {
+                    error!("Subscriber id {id:?} was not found in handles list for topic {topic_name:?} while unsubscribing");
+                    return;
+                }
+                subscription.handles.is_empty()
+            };
+            if !is_last {
+                return;
+            }
+            // Forgotten so it isn't resubscribed to after reconnecting
+            client.subscriptions.remove(&topic_name);
+
+            // Subscriptions can only have been made once connected
+            let Some(connection) = client.connection.get() else {
+                return;
+            };
+            if !client.is_closed.load(Ordering::Relaxed) {
+                // This is the last subscriber for that topic and we need to unsubscribe now
+                match connection.writer.unsubscribe(&topic_name).await {
+                    Ok(_) => {}
+                    Err(e) => error!(
+                        "Failed to send unsubscribe while dropping subscriber: {:?}",
+                        e
+                    ),
+                }
+            }
+        });
+        Ok(())
+    }
+}
+
+/// Runs `deliver` for a subscriber on a task of its own, see [SubscriberDispatch::Spawned],
+/// returning the callback which feeds it. The task ends once the callback is dropped.
+fn spawn_subscriber_task(
+    topic: &str,
+    queue_size: usize,
+    topic_metrics: Option<Arc<TopicCounters>>,
+    deliver: impl Fn(&ReceivedMsg) + Send + Sync + 'static,
+) -> Callback {
+    let (tx, mut rx) = tokio::sync::mpsc::channel::<Bytes>(queue_size.max(1));
+    let deliver = Arc::new(deliver);
+    tokio::spawn(async move {
+        while let Some(first) = rx.recv().await {
+            // Everything received by the time the blocking pool picks this up is handled with it,
+            // and the next batch isn't started until this one is done, keeping messages in order
+            let deliver = deliver.clone();
+            let handled = tokio::task::spawn_blocking(move || {
+                let mut next = Some(first);
+                while let Some(json) = next {
+                    // Already checked to be utf8 when it was received
+                    if let Ok(msg) = ReceivedMsg::new(&json, 0..json.len()) {
+                        deliver(&msg);
+                    }
+                    next = rx.try_recv().ok();
+                }
+                rx
+            })
+            .await;
+            rx = match handled {
+                Ok(rx) => rx,
+                Err(e) => {
+                    error!("A subscriber's task panicked while deserializing: {e}");
+                    return;
+                }
+            };
+        }
+    });
+    let topic = topic.to_string();
+    Box::new(move |msg: &ReceivedMsg| {
+        if tx.try_send(msg.bytes()).is_err() {
+            info!("Subscriber to {topic} is too far behind, dropping message");
+            if let Some(topic_metrics) = &topic_metrics {
+                topic_metrics.record_dropped();
+            }
+        }
+    })
+}
+
+/// The service named by rosbridge's failure for a call to a service which isn't advertised,
+/// "Service <name> does not exist"
+fn missing_service(message: &str) -> Option<&str> {
+    let name = message
+        .strip_prefix("Service ")?
+        .strip_suffix(" does not exist")?;
+    (!name.is_empty() && !name.contains(char::is_whitespace)).then_some(name)
+}
+
+/// A call received for a service advertised by the client, see [serve_call]
+struct ServiceCall {
+    service: String,
+    id: Option<String>,
+    request: String,
+    /// The call's permit to run, None if it must wait for one
+    permit: Option<OwnedSemaphorePermit>,
+    /// Counts the call as queued while it waits for a permit
+    queued: Option<Gauge>,
+}
+
+/// Handles a call on the blocking thread pool once a handler is free, then sends the response
+async fn serve_call(server: Arc<ServiceServer>, call: ServiceCall, writer: Writer) {
+    let ServiceCall {
+        service,
+        id,
+        request,
+        permit,
+        queued,
+    } = call;
+    let handled = async {
+        let permit = match permit {
+            Some(permit) => permit,
+            None => server
+                .permits
+                .clone()
+                .acquire_owned()
+                .await
+                .expect("Service permits are never closed"),
+        };
+        drop(queued);
+        let callback = server.callback.clone();
+        let counters = server.counters.clone();
+        tokio::task::spawn_blocking(move || {
+            // Held until the handler returns, even if the call has timed out by then
+            let _permit = permit;
+            let _in_flight = counters.handle();
+            callback(&request)
+        })
+        .await
+    };
+    let handled = match server.options.timeout {
+        // Dropping the future on timeout also removes a queued call from the queue
+        Some(timeout) => match tokio::time::timeout(timeout, handled).await {
+            Ok(handled) => handled,
+            Err(_) => {
+                server.counters.record_timed_out();
+                warn!("A call to {service} timed out after {timeout:?}, sending response false");
+                let message = format!("Service {service} did not respond within {timeout:?}");
+                if let Err(e) = writer
+                    .service_response(&service, id, false, Value::String(message))
+                    .await
+                {
+                    error!("Failed to send service_response for timed out call to {service}: {e}");
+                }
+                return;
+            }
+        },
+        None => handled.await,
+    };
+    let (is_success, response) = match handled {
+        Ok(Ok(response)) => (true, response),
+        Ok(Err(e)) => {
+            error!("A service callback on topic {service:?} failed with {e:?} sending response false in service_response");
+            (false, serde_json::json!(format!("{e}")))
+        }
+        Err(e) => {
+            error!("A service callback on topic {service:?} panicked: {e}");
+            let message = format!("Service {service} failed while handling the call");
+            (false, Value::String(message))
+        }
+    };
+    if let Err(e) = writer
+        .service_response(&service, id, is_success, response)
+        .await
+    {
+        error!("Failed to send service_response for {service}: {e}");
+    }
+}
+
+/// A client connection to the rosbridge_server that allows for publishing and subscribing to topics
+pub(crate) struct Client {
+    // Set once connected, which for clients from ClientHandleBuilder::build_unconnected is on first use
+    connection: tokio::sync::OnceCell<Connection>,
+    // Stores a record of the publishers we've handed out
+    publishers: DashMap<String, PublisherHandle>,
+    subscriptions: DashMap<String, Subscription>,
+    // Held while subscribing or unsubscribing, from updating the subscription's entry through to
+    // sending rosbridge the operation, so the subscribes and unsubscribes for a topic are sent in
+    // the same order its subscriptions changed
+    subscription_changes: tokio::sync::Mutex<()>,
+    services: DashMap<String, Arc<ServiceServer>>,
+    // Contains any outstanding service calls we're waiting for a response on
+    // Map key will be a uniquely generated id for each call
+    service_calls:
+        Arc<DashMap<String, tokio::sync::oneshot::Sender<Result<Value, RosLibRustError>>>>,
+    // Source of service call ids, never reset so ids are not reused even across reconnects
+    next_call_id: AtomicU64,
+    // Set by ClientHandle::close, after which the connection is not used or reconnected
+    is_closed: Arc<AtomicBool>,
+    // Set once first connected, see ClientHandle::is_connected
+    has_connected: Arc<AtomicBool>,
+    // Notifies receivers from ClientHandle::connection_events of changes in the connection
+    events: broadcast::Sender<ConnectionEvent>,
+    metrics: Arc<Metrics>,
+    reconnect: Arc<ReconnectState>,
+    opts: ClientHandleOptions,
+}
+
+/// A best effort attempt to let rosbridge know we're leaving when the client is dropped without
+/// being closed, the close is queued behind anything still waiting to be sent
+impl Drop for Client {
+    fn drop(&mut self) {
+        if !self.is_closed.load(Ordering::Relaxed) {
+            if let Some(connection) = self.connection.get_mut() {
+                // Nothing waits on the close, the writer task finishes it in the background
+                drop(connection.writer.close());
+            }
+        }
+    }
+}
+
+/// The connection to rosbridge, the reader is locked by whichever task is reading while the writer
+/// is shared, so reading doesn't block writing
+struct Connection {
+    reader: RwLock<Reader>,
+    writer: Writer,
+}
+
+/// How the client reconnects and how far it has got, shared with its handles so the policy can be
+/// replaced and the state watched without locking the client
+struct ReconnectState {
+    // Starts as ClientHandleOptions::reconnect, replaced by ClientHandle::set_reconnect_policy
+    policy: std::sync::Mutex<ReconnectPolicy>,
+    state: watch::Sender<ConnectionState>,
+    // The close frame which most recently ended the connection, see ClientHandle::last_close
+    last_close: std::sync::Mutex<Option<ServerClose>>,
+    // Set when the client gave up because the close code was one of the fatal_close_codes
+    fatal_close: std::sync::Mutex<Option<ServerClose>>,
+}
+
+impl ReconnectState {
+    fn new(policy: ReconnectPolicy) -> Self {
+        let initial = ConnectionState::Connecting {
+            attempt: 1,
+            next_delay: None,
+        };
+        Self {
+            policy: std::sync::Mutex::new(policy),
+            state: watch::channel(initial).0,
+            last_close: std::sync::Mutex::new(None),
+            fatal_close: std::sync::Mutex::new(None),
+        }
+    }
+
+    fn policy(&self) -> ReconnectPolicy {
+        self.policy.lock().unwrap().clone()
+    }
+}
+
+/// An outstanding service call, which removes its entry from [Client::service_calls] when dropped
+/// so calls which time out or are cancelled by the caller don't leave their entry behind
+struct PendingServiceCall {
+    id: String,
+    service_calls:
+        Arc<DashMap<String, tokio::sync::oneshot::Sender<Result<Value, RosLibRustError>>>>,
+}
+
+impl Drop for PendingServiceCall {
+    fn drop(&mut self) {
+        self.service_calls.remove(&self.id);
+    }
+}
+
+impl Client {
+    // internal implementation of new, the client connects on first use of Client::connection
+    fn new(opts: ClientHandleOptions) -> Self {
+        Self {
+            connection: tokio::sync::OnceCell::new(),
+            publishers: DashMap::new(),
+            services: DashMap::new(),
+            subscriptions: DashMap::new(),
+            subscription_changes: tokio::sync::Mutex::new(()),
+            service_calls: Arc::new(DashMap::new()),
+            next_call_id: AtomicU64::new(0),
+            is_closed: Arc::new(AtomicBool::new(false)),
+            has_connected: Arc::new(AtomicBool::new(false)),
+            events: broadcast::channel(CONNECTION_EVENT_QUEUE_SIZE).0,
+            metrics: Arc::new(Metrics::new(opts.collect_metrics)),
+            reconnect: Arc::new(ReconnectState::new(opts.reconnect.clone())),
+            opts,
+        }
+    }
+
+    /// How long operations issued while reconnecting wait, see [ClientHandleOptions::pending_operation_timeout]
+    fn pending_operation_timeout(&self) -> Duration {
+        self.opts
+            .pending_operation_timeout
+            .or(self.opts.timeout)
+            .unwrap_or(DEFAULT_PENDING_OPERATION_TIMEOUT)
+    }
+
+    /// Returns the connection to rosbridge, first connecting if this is the first use of the client
+    async fn connection(&self) -> RosLibRustResult<&Connection> {
+        self.connection
+            .get_or_try_init(|| async {
+                // A server which accepts connections but never completes the handshake isn't
+                // rosbridge, so handshake timeouts aren't retried here
+                let connect = stubborn_connect(&self.opts, &self.reconnect, false, None);
+                let connection = match self.opts.connect_timeout.or(self.opts.timeout) {
+                    Some(t) => tokio::time::timeout(t, connect)
+                        .await
+                        .map_err(|_| RosLibRustError::ConnectTimeout(t))?,
+                    None => connect.await,
+                }?;
+                self.metrics.record_connected(false);
+                self.has_connected.store(true, Ordering::Relaxed);
+                self.reconnect
+                    .state
+                    .send_replace(ConnectionState::Connected);
+                // Only fails if nobody is listening for events
+                let _ = self
+                    .events
+                    .send(ConnectionEvent::Connected { resubscribed: 0 });
+                Ok(connection)
+            })
+            .await
+    }
+
+    async fn handle_message(&self, frame: Frame) -> RosLibRustResult<()> {
+        match frame {
+            Frame::Text(text) => {
+                debug!("got message: {}", text);
+                // Publishes are handed to subscribers as a slice of the frame, the message is
+                // only parsed by typed subscribers
+                if let Some((topic, range)) = comm::publish_msg_range(&text) {
+                    let size = text.len();
+                    self.handle_publish(&topic, Bytes::from(text), range, size)
+                        .await;
+                    return Ok(());
+                }
+                // TODO better error handling here serde_json::Error not send
+                let parsed: serde_json::Value = serde_json::from_str(text.as_str()).unwrap();
+                self.handle_op(parsed, text.len()).await?;
+            }
+            // Rosbridge sends messages for subscriptions using cbor compression as binary
+            Frame::Binary(data) => match comm::cbor_to_json(&data) {
+                Ok(parsed) => self.handle_op(parsed, data.len()).await?,
+                Err(e) => error!("Failed to decode binary message, it will be skipped: {e}"),
+            },
+            Frame::Close(_) if self.is_closed.load(Ordering::Relaxed) => {
+                debug!("Close handshake completed");
+            }
+            Frame::Close(close) => {
+                let close = close.unwrap_or(ServerClose {
+                    code: ServerClose::NO_STATUS,
+                    reason: String::new(),
+                });
+                warn!("Rosbridge closed the connection: {}", close.to_error());
+                // Calls waiting on a response learn why it will never arrive, rather than only
+                // that the connection was lost
+                let ids: Vec<String> = self.service_calls.iter().map(|c| c.key().clone()).collect();
+                for id in ids {
+                    if let Some((_, call)) = self.service_calls.remove(&id) {
+                        let _ = call.send(Err(close.to_error()));
+                    }
+                }
+                *self.reconnect.last_close.lock().unwrap() = Some(close.clone());
+                return Err(close.to_error());
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Completes the close handshake after the server closed the connection. Tungstenite sends its
+    /// reply to the close frame the next time the connection is read from, and servers may wait
+    /// for it before accepting a new connection.
+    async fn reply_to_close(&self) {
+        let Some(connection) = self.connection.get() else {
+            return;
+        };
+        let mut reader = connection.reader.write().await;
+        let _ = tokio::time::timeout(CLOSE_REPLY_TIMEOUT, async {
+            while let Some(Ok(_)) = reader.receive().await {}
+        })
+        .await;
+    }
+
+    /// Handles an operation received from rosbridge, `size` is the number of bytes it was sent as
+    async fn handle_op(&self, parsed: Value, size: usize) -> RosLibRustResult<()> {
+        let parsed_object = parsed
+            .as_object()
+            .expect("Recieved non-object json response");
+        let op = parsed_object
+            .get("op")
+            .expect("Op field not present on returned object.")
+            .as_str()
+            .expect("Op field was not of string type.");
+        let op = comm::Ops::from_str(op)?;
+        match op {
+            comm::Ops::Publish => {
+                trace!("handling publish for {:?}", &parsed);
+                let topic = parsed.get("topic").unwrap().as_str().unwrap();
+                // TODO possible bug here if "msg" isn't defined remove this unwrap
+                let msg = Bytes::from(serde_json::to_string(parsed.get("msg").unwrap()).unwrap());
+                let range = 0..msg.len();
+                self.handle_publish(topic, msg, range, size).await;
+            }
+            comm::Ops::ServiceResponse => {
+                trace!("handling service response for {:?}", &parsed);
+                self.handle_response(parsed).await;
+            }
+            comm::Ops::CallService => {
+                trace!("handling call_service for {:?}", &parsed);
+                self.handle_service(parsed).await;
+            }
+            _ => {
+                warn!("Unhandled op type {}", op)
+            }
+        }
+        Ok(())
+    }
+
+    /// Removes all of our subscriptions, publishers, and services from rosbridge, used when closing
+    async fn unregister_all(&self, writer: &Writer) {
+        let subscriptions = self.subscriptions.iter().map(|sub| sub.key().clone());
+        for topic in subscriptions.collect::<Vec<_>>() {
+            if let Err(e) = writer.unsubscribe(&topic).await {
+                warn!("Failed to unsubscribe from {topic} while closing: {e}");
+            }
+        }
+        let publishers = self
+            .publishers
+            .iter()
+            .map(|publisher| publisher.key().clone());
+        for topic in publishers.collect::<Vec<_>>() {
+            if let Err(e) = writer.unadvertise(&topic).await {
+                warn!("Failed to unadvertise {topic} while closing: {e}");
+            }
+        }
+        let services = self.services.iter().map(|service| service.key().clone());
+        for topic in services.collect::<Vec<_>>() {
+            if let Err(e) = writer.unadvertise_service(&topic).await {
+                warn!("Failed to unadvertise service {topic} while closing: {e}");
+            }
+        }
+    }
+
+    /// Assigns a new id to a service call and stores where its response should be sent
+    fn register_service_call(
+        &self,
+        tx: tokio::sync::oneshot::Sender<Result<Value, RosLibRustError>>,
+    ) -> PendingServiceCall {
+        let id = format!(
+            "call_service:{}",
+            self.next_call_id.fetch_add(1, Ordering::Relaxed)
+        );
+        self.service_calls.insert(id.clone(), tx);
+        PendingServiceCall {
+            id,
+            service_calls: self.service_calls.clone(),
+        }
+    }
+
+    async fn handle_response(&self, data: Value) {
+        let Some(id) = data.get("id").and_then(Value::as_str) else {
+            warn!("Received service_response without an id, ignoring it: {data}");
+            return;
+        };
+        // Calls which were cancelled, timed out, or already answered no longer have an entry
+        let Some((_id, call)) = self.service_calls.remove(id) else {
+            warn!("Received service_response for unknown call id {id}, ignoring it");
+            return;
+        };
+        let values = data.get("values").cloned().unwrap_or(Value::Null);
+        // A failed call's values are the server's description of what went wrong, not a response
+        let res = match data.get("result").and_then(Value::as_bool) {
+            // Only successful responses are expected to be of the response type
+            Some(false) => Err(RosLibRustError::ServerError(match values {
+                Value::String(message) => message,
+                Value::Null => "Service call failed without a message".to_owned(),
+                values => values.to_string(),
+            })),
+            _ => Ok(values),
+        };
+        // The caller may have stopped waiting after the entry was removed, which is fine
+        let _ = call.send(res);
+    }
+
+    /// Response handler for receiving a service call looks up if we have a service
+    /// registered for the incoming topic and if so dispatches to the callback
+    async fn handle_service(&self, data: Value) {
+        // Unwrap is okay, field is fully required and strictly type
+        let topic = data.get("service").unwrap().as_str().unwrap();
+        // Unwrap is okay, field is strictly typed to string
+        let id = data.get("id").map(|id| id.as_str().unwrap().to_string());
+
+        // Service calls are only received once connected
+        let Some(connection) = self.connection.get() else {
+            return;
+        };
+        // Lookup if we have a service for the message
+        let Some(server) = self.services.get(topic).map(|server| server.clone()) else {
+            // The service may have just been dropped, the caller is told rather than left waiting
+            warn!("Received call_service for unadvertised service {topic}");
+            let message =
+                Value::String(format!("Service {topic} is not advertised by this client"));
+            if let Err(e) = connection
+                .writer
+                .service_response(topic, id, false, message)
+                .await
+            {
+                error!("Failed to send service_response for unadvertised service {topic}: {e}");
+            }
+            return;
+        };
+        // TODO likely bugs here remove this unwrap. Unclear what we are expected to get for empty service
+        let request = data.get("args").unwrap().to_string();
+
+        // Calls over the limit are queued here rather than in their task, so that a burst of calls
+        // received before any of their tasks run still can't overfill the queue
+        let has_room = server
+            .options
+            .queue_size
+            .map_or(true, |size| server.counters.queued() < size as u64);
+        let (permit, queued) = match server.permits.clone().try_acquire_owned() {
+            Ok(permit) => (Some(permit), None),
+            Err(_) if has_room => (None, Some(server.counters.enqueue())),
+            Err(_) => {
+                server.counters.record_rejected();
+                warn!("Rejecting call to {topic}, its queue is full");
+                let message = Value::String(format!(
+                    "Service {topic} is busy, too many calls are already waiting"
+                ));
+                if let Err(e) = connection
+                    .writer
+                    .service_response(topic, id, false, message)
+                    .await
+                {
+                    error!("Failed to send service_response rejecting call to {topic}: {e}");
+                }
+                return;
+            }
+        };
+        let call = ServiceCall {
+            service: topic.to_string(),
+            id,
+            request,
+            permit,
+            queued,
+        };
+        tokio::spawn(serve_call(server, call, connection.writer.clone()));
+    }
+
+    async fn spin_once(&self) -> RosLibRustResult<()> {
+        // Lazily connected clients have nothing to read until first used, so we wait for the spin
+        // loop's timeout to bring us back around to check again
+        let Some(connection) = self.connection.get() else {
+            return std::future::pending().await;
+        };
+        let read = {
+            let mut stream = connection.reader.write().await;
+            match stream.receive().await {
+                Some(Ok(msg)) => msg,
+                Some(Err(RosLibRustError::MessageTooLarge {
+                    size,
+                    limit,
+                    topic: None,
+                })) => {
+                    // The message is discarded unread, but with only one subscription and nothing
+                    // else we're waiting on it can only have been published on that topic
+                    let topic = match self.subscriptions.len() {
+                        1 if self.service_calls.is_empty() && self.services.is_empty() => self
+                            .subscriptions
+                            .iter()
+                            .next()
+                            .map(|sub| sub.key().clone()),
+                        _ => None,
+                    };
+                    return Err(RosLibRustError::MessageTooLarge { size, limit, topic });
+                }
+                Some(Err(e)) => {
+                    return Err(e);
+                }
+                None => {
+                    return Err(RosLibRustError::Unexpected(anyhow!(
+                        "Wtf does none mean here?"
+                    )));
+                }
+            }
+        };
+        debug!("Got message: {:?}", read);
+        self.handle_message(read).await
+    }
+
+    /// Response handler for received publish messages
+    /// Converts the return message to the subscribed type and calls any callbacks
+    /// Panics if publish is received for unexpected topic
+    /// Hands a message to every subscriber of `topic`, `range` is where its json is in `frame`
+    async fn handle_publish(&self, topic: &str, frame: Bytes, range: Range<usize>, size: usize) {
+        // TODO lots of error handling!
+        let callbacks = self.subscriptions.get(topic);
+        let callbacks = match callbacks {
+            Some(callbacks) => callbacks,
+            _ => panic!("Received publish message for unsubscribed topic!"), // TODO probably shouldn't be a panic?
+        };
+        if let Some(topic_metrics) = &callbacks.metrics {
+            topic_metrics.record_received(size);
+        }
+        let msg = match ReceivedMsg::new(&frame, range) {
+            Ok(msg) => msg,
+            Err(e) => {
+                error!(
+                    "Received message on {topic} which isn't valid utf8, it will be skipped: {e}"
+                );
+                return;
+            }
+        };
+        for callback in callbacks.handles.values() {
+            callback(&msg)
+        }
+    }
+
+    /// Switches to a newly made connection, and re-establishes the subscriptions and publishers
+    /// the client has on it. This includes any subscribe or advertise requested while reconnecting,
+    /// which were recorded but waited for the reconnect to be sent.
+    ///
+    /// Returns the number of subscriptions re-established.
+    async fn reconnect(&mut self, connection: Connection) -> RosLibRustResult<usize> {
+        self.connection = tokio::sync::OnceCell::from(connection);
+
+        // Responses to calls made on the old connection will never arrive, dropping their senders
+        // fails them with Disconnected
+        self.service_calls.clear();
+
+        // Resend rosbridge our subscription requests to re-establish inflight subscriptions
+        // Clone here is dumb, but required due to async
+        let mut subs: Vec<(String, String)> = vec![];
+        {
+            for sub in self.subscriptions.iter() {
+                subs.push((sub.key().clone(), sub.value().topic_type.clone()))
+            }
+        }
+        let stream = &self.connection().await?.writer;
+        for (topic, topic_type) in &subs {
+            stream
+                .subscribe(topic, topic_type, self.opts.default_compression)
+                .await?;
+        }
+
+        // Re-advertise our publishers, and restore the latched message of any latched topics
+        let pubs: Vec<(String, String, bool, Option<Value>)> = self
+            .publishers
+            .iter()
+            .map(|publisher| {
+                (
+                    publisher.key().clone(),
+                    publisher.topic_type.clone(),
+                    publisher.latch,
+                    publisher.latched_msg.clone(),
+                )
+            })
+            .collect();
+        for (topic, topic_type, latch, latched_msg) in &pubs {
+            stream.advertise(topic, topic_type, *latch).await?;
+            if let Some(msg) = latched_msg {
+                stream.publish_value(topic, topic_type, msg).await?;
+            }
+        }
+
+        Ok(subs.len())
+    }
+}
+
+/// Wraps spin in retry logic to handle reconnection attempts automagically
+async fn stubborn_spin(
+    client: std::sync::Weak<RwLock<Client>>,
+    is_disconnected: Arc<AtomicBool>,
+    is_closed: Arc<AtomicBool>,
+    reconnected: Arc<Notify>,
+    events: broadcast::Sender<ConnectionEvent>,
+) -> RosLibRustResult<()> {
+    debug!("Starting stubborn_spin");
+    while let Some(client) = client.upgrade() {
+        // Once closed the connection ends with the close handshake, which close() waits on itself
+        if is_closed.load(Ordering::Relaxed) {
+            break;
+        }
+        const SPIN_DURATION: Duration = Duration::from_millis(10);
+
+        // Bound separately so the read lock is released before we need to write lock to reconnect
+        let spin_result =
+            tokio::time::timeout(SPIN_DURATION, client.read().await.spin_once()).await;
+        match spin_result {
+            Ok(Ok(())) => {}
+            Ok(Err(_)) if is_closed.load(Ordering::Relaxed) => break,
+            Ok(Err(err)) => {
+                is_disconnected.store(true, Ordering::Relaxed);
+                let (opts, metrics, reconnect) = {
+                    let client = client.read().await;
+                    let reconnect = client.reconnect.clone();
+                    (client.opts.clone(), client.metrics.clone(), reconnect)
+                };
+                metrics.record_disconnected();
+                if err.close_code().is_some() {
+                    client.read().await.reply_to_close().await;
+                }
+                let _ = events.send(ConnectionEvent::Disconnected {
+                    reason: err.to_string(),
+                });
+                if let Some(code) = err
+                    .close_code()
+                    .filter(|code| opts.fatal_close_codes.contains(code))
+                {
+                    warn!("Rosbridge closed the connection with code {code}, not reconnecting");
+                    let close = reconnect.last_close.lock().unwrap().clone();
+                    *reconnect.fatal_close.lock().unwrap() = close;
+                    is_closed.store(true, Ordering::Relaxed);
+                    reconnect
+                        .state
+                        .send_replace(ConnectionState::Failed { attempts: 0 });
+                    let _ = events.send(ConnectionEvent::Failed { attempts: 0 });
+                    reconnected.notify_waiters();
+                    return Err(err);
+                }
+                warn!("Spin failed with error: {err}, attempting to reconnect");
+                // The client isn't locked while connecting so operations issued meanwhile can
+                // record themselves and wait instead of blocking
+                let reconnecting = Some((&events, metrics.as_ref()));
+                let connection = match stubborn_connect(&opts, &reconnect, true, reconnecting).await
+                {
+                    Ok(connection) => connection,
+                    Err(e) => {
+                        // Nothing more will be attempted, so operations waiting on the reconnect
+                        // fail instead of waiting out their timeout
+                        is_closed.store(true, Ordering::Relaxed);
+                        reconnected.notify_waiters();
+                        return Err(e);
+                    }
+                };
+                let resubscribed = {
+                    let mut client = client.write().await;
+                    let resubscribed = client.reconnect(connection).await?;
+                    // Cleared while still locked, so every operation either recorded itself before
+                    // the reconnect replayed the client's state, or sees the new connection
+                    is_disconnected.store(false, Ordering::Relaxed);
+                    resubscribed
+                };
+                metrics.record_connected(true);
+                reconnect.state.send_replace(ConnectionState::Connected);
+                reconnected.notify_waiters();
+                // Only sent once the client's state is restored, so handlers can use it right away
+                let _ = events.send(ConnectionEvent::Connected { resubscribed });
+            }
+            Err(_) => {
+                // Time out occurred, so we'll check on our weak pointer again
+            }
+        }
+    }
+
+    Ok(())
+}
+
+// Names of the fields of a message definition in order, skipping constants and the definitions
+// of nested types which follow it
+fn definition_field_names(definition: &str) -> Vec<String> {
+    definition
+        .lines()
+        .take_while(|line| !line.starts_with("==="))
+        .map(|line| line.split('#').next().unwrap_or_default().trim())
+        .filter(|line| !line.is_empty() && !line.contains('='))
+        .filter_map(|line| line.split_whitespace().nth(1))
+        .map(str::to_owned)
+        .collect()
+}
+
+// Implementation of timeout that is a no-op if timeout is 0 or un-configured
+// Only works on functions that already return our result type
+// This might not be needed but reading tokio::timeout docs I couldn't confirm this
+async fn timeout<F, T>(timeout: Option<Duration>, future: F) -> RosLibRustResult<T>
+where
+    F: futures::Future<Output = RosLibRustResult<T>>,
+{
+    if let Some(t) = timeout {
+        tokio::time::timeout(t, future).await?
+    } else {
+        future.await
+    }
+}
+
+// Connects to rosbridge at the configured URL, retrying according to the reconnect policy, which
+// is re-read after every failure so it can be replaced meanwhile. Handshake timeouts are returned
+// instead of retried unless `retry_handshake_timeouts`.
+// When reconnecting, each attempt is reported to `reconnecting`'s events and metrics
+async fn stubborn_connect(
+    opts: &ClientHandleOptions,
+    reconnect: &ReconnectState,
+    retry_handshake_timeouts: bool,
+    reconnecting: Option<(&broadcast::Sender<ConnectionEvent>, &Metrics)>,
+) -> RosLibRustResult<Connection> {
+    use rand::SeedableRng;
+    // Jitter must differ between clients, so isn't seeded from anything they share
+    let mut rng = rand::rngs::StdRng::from_entropy();
+    let mut failures = 0;
+    loop {
+        reconnect.state.send_replace(ConnectionState::Connecting {
+            attempt: failures + 1,
+            next_delay: None,
+        });
+        if let Some((events, metrics)) = reconnecting {
+            metrics.record_reconnect_attempt();
+            let _ = events.send(ConnectionEvent::Reconnecting {
+                attempt: failures + 1,
+            });
+        }
+        match transport::connect(
+            &opts.url,
+            opts.handshake_timeout,
+            opts.tcp_framing,
+            opts.websocket_config,
+            opts.fragmentation,
+        )
+        .await
+        {
+            Err(e @ RosLibRustError::HandshakeTimeout(_)) if !retry_handshake_timeouts => {
+                return Err(e)
+            }
+            Err(e) => {
+                failures += 1;
+                let policy = reconnect.policy();
+                if policy.max_attempts().is_some_and(|max| failures >= max) {
+                    warn!("Failed to connect: {e:?}, giving up after {failures} attempts");
+                    reconnect
+                        .state
+                        .send_replace(ConnectionState::Failed { attempts: failures });
+                    if let Some((events, _)) = reconnecting {
+                        let _ = events.send(ConnectionEvent::Failed { attempts: failures });
+                    }
+                    return Err(RosLibRustError::ConnectAttemptsExhausted { attempts: failures });
+                }
+                let delay = policy.jittered_delay(failures, &mut rng);
+                reconnect.state.send_replace(ConnectionState::Connecting {
+                    attempt: failures + 1,
+                    next_delay: Some(delay),
+                });
+                warn!("Failed to connect: {:?}, retrying in {:?}", e, delay);
+                tokio::time::sleep(delay).await;
+            }
+            Ok((reader, writer)) => {
+                return Ok(Connection {
+                    reader: RwLock::new(reader),
+                    writer: Writer::spawn(writer, opts.write_queue_size),
+                });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::rosbridge::{mock_bridge::MockBridge, ServiceMetrics, TopicMetrics};
+    use futures::StreamExt;
+    use serde::{Deserialize, Serialize};
+    use serde_json::json;
+
+    #[derive(Deserialize, Serialize, Debug, Default, Clone, PartialEq)]
+    struct MapInfo {
+        resolution: f64,
+    }
+    impl RosMessageType for MapInfo {
+        const ROS_TYPE_NAME: &'static str = "nav_msgs/MapMetaData";
+    }
+
+    #[derive(Deserialize, Serialize, Debug, Default, Clone, PartialEq)]
+    struct Echo {
+        data: u32,
+    }
+    impl RosMessageType for Echo {
+        const ROS_TYPE_NAME: &'static str = "test_msgs/Echo";
+    }
+
+    /// An [Echo] which takes a while to deserialize, standing in for a subscriber doing
+    /// blocking work
+    #[derive(Serialize, Debug, Clone, PartialEq)]
+    struct SlowEcho {
+        data: u32,
+    }
+    impl<'de> Deserialize<'de> for SlowEcho {
+        fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let Echo { data } = Echo::deserialize(deserializer)?;
+            std::thread::sleep(Duration::from_millis(100));
+            Ok(SlowEcho { data })
+        }
+    }
+    impl RosMessageType for SlowEcho {
+        const ROS_TYPE_NAME: &'static str = "test_msgs/Echo";
+    }
+
+    #[derive(Deserialize, Serialize, Debug, Default, Clone, PartialEq)]
+    struct Stamped {
+        stamp: roslibrust_codegen::integral_types::Time,
+    }
+    impl RosMessageType for Stamped {
+        const ROS_TYPE_NAME: &'static str = "test_msgs/Stamped";
+    }
+
+    struct EchoService;
+    impl RosServiceType for EchoService {
+        const ROS_SERVICE_NAME: &'static str = "test_msgs/Echo";
+        const MD5SUM: &'static str = "";
+        type Request = Echo;
+        type Response = Echo;
+    }
+
+    const TIMEOUT: Duration = Duration::from_secs(2);
+
+    #[test_log::test(tokio::test(flavor = "multi_thread", worker_threads = 4))]
+    async fn concurrent_publishers_keep_per_task_order() {
+        fn assert_shareable<T: Clone + Send + Sync + 'static>(_: &T) {}
+
+        const TASKS: u32 = 16;
+        const MESSAGES: u32 = 200;
+        let mut bridge = MockBridge::new().await;
+        let client = ClientHandle::new(bridge.url()).await.unwrap();
+        let publisher = client.advertise::<Echo>("/echo").await.unwrap();
+        assert_shareable(&client);
+        assert_shareable(&publisher);
+        bridge.next_op("advertise", "/echo").await;
+
+        let tasks: Vec<_> = (0..TASKS)
+            .map(|task| {
+                let publisher = publisher.clone();
+                tokio::spawn(async move {
+                    for i in 0..MESSAGES {
+                        publisher
+                            .publish(Echo {
+                                data: task * 1000 + i,
+                            })
+                            .await
+                            .unwrap();
+                    }
+                })
+            })
+            .collect();
+
+        // Every publish arrives intact, and each task's publishes arrive in the order it sent them
+        let mut next = vec![0; TASKS as usize];
+        for _ in 0..TASKS * MESSAGES {
+            let publish = tokio::time::timeout(TIMEOUT, bridge.next_op("publish", "/echo"))
+                .await
+                .unwrap();
+            let data = publish["msg"]["data"].as_u64().unwrap() as u32;
+            let (task, i) = ((data / 1000) as usize, data % 1000);
+            assert_eq!(
+                i, next[task],
+                "publishes from task {task} arrived out of order"
+            );
+            next[task] += 1;
+        }
+        for task in tasks {
+            task.await.unwrap();
+        }
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn advertise_sends_latch() {
+        let mut bridge = MockBridge::new().await;
+        let client = ClientHandle::new(bridge.url()).await.unwrap();
+
+        let _latched = client
+            .advertise_with_options::<MapInfo>("/map", AdvertiseOptions { latch: true })
+            .await
+            .unwrap();
+        let advertise = bridge.next_op("advertise", "/map").await;
+        assert_eq!(advertise["latch"], true);
+        assert_eq!(advertise["type"], "nav_msgs/MapMetaData");
+
+        let _unlatched = client.advertise::<MapInfo>("/map_updates").await.unwrap();
+        let advertise = bridge.next_op("advertise", "/map_updates").await;
+        assert_eq!(advertise["latch"], false);
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn publishers_share_advertisement_until_last_is_dropped() {
+        let mut bridge = MockBridge::new().await;
+        let client = ClientHandle::new(bridge.url()).await.unwrap();
+
+        let first = client.advertise::<MapInfo>("/diagnostics").await.unwrap();
+        let second = client.advertise::<MapInfo>("/diagnostics").await.unwrap();
+        let cloned = second.clone();
+        let msg = bridge.next_message().await;
+        assert_eq!(
+            (msg["op"].as_str(), msg["topic"].as_str()),
+            (Some("advertise"), Some("/diagnostics"))
+        );
+
+        for (idx, publisher) in [&first, &second, &cloned, &first].into_iter().enumerate() {
+            publisher
+                .publish(MapInfo {
+                    resolution: idx as f64,
+                })
+                .await
+                .unwrap();
+        }
+        for idx in 0..4 {
+            let msg = bridge.next_message().await;
+            assert_eq!(msg["op"], "publish");
+            assert_eq!(msg["msg"]["resolution"], idx as f64);
+        }
+
+        let mismatch = client.advertise::<Echo>("/diagnostics").await;
+        assert!(matches!(
+            mismatch,
+            Err(RosLibRustError::TopicTypeMismatch { existing, requested, .. })
+                if existing == "nav_msgs/MapMetaData" && requested == "test_msgs/Echo"
+        ));
+
+        // The topic stays advertised while any publisher remains
+        drop(first);
+        drop(cloned);
+        second.publish(MapInfo { resolution: 4.0 }).await.unwrap();
+        let msg = bridge.next_message().await;
+        assert_eq!(msg["op"], "publish");
+        assert_eq!(msg["msg"]["resolution"], 4.0);
+
+        drop(second);
+        let msg = tokio::time::timeout(TIMEOUT, bridge.next_message())
+            .await
+            .unwrap();
+        assert_eq!(
+            (msg["op"].as_str(), msg["topic"].as_str()),
+            (Some("unadvertise"), Some("/diagnostics"))
+        );
+
+        // Once unadvertised the topic can be advertised again, with any type
+        let _echo = client.advertise::<Echo>("/diagnostics").await.unwrap();
+        let msg = bridge.next_message().await;
+        assert_eq!(msg["op"], "advertise");
+        assert_eq!(msg["type"], "test_msgs/Echo");
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn metrics_count_traffic_calls_and_reconnects() {
+        const N: u64 = 5;
+        let mut bridge = MockBridge::new().await;
+        let client = ClientHandle::builder(bridge.url())
+            .timeout(TIMEOUT)
+            .subscriber_queue_size(2)
+            .build()
+            .await
+            .unwrap();
+
+        let publisher = client.advertise::<MapInfo>("/map_out").await.unwrap();
+        for _ in 0..N {
+            publisher
+                .publish(MapInfo { resolution: 0.5 })
+                .await
+                .unwrap();
+        }
+        let sent_size = json!({
+            "op": "publish",
+            "topic": "/map_out",
+            "type": "nav_msgs/MapMetaData",
+            "msg": {"resolution": 0.5},
+        })
+        .to_string()
+        .len() as u64;
+
+        // Never read from, so all but the last two messages are dropped from its queue. Inline so
+        // every message has been queued by the time the service response below is handled
+        let options = SubscribeOptions {
+            dispatch: SubscriberDispatch::Inline,
+            ..Default::default()
+        };
+        let _subscriber = client
+            .subscribe_with_options::<MapInfo>("/map", options)
+            .await
+            .unwrap();
+        bridge.next_op("subscribe", "/map").await;
+        for _ in 0..N {
+            bridge.publish("/map", json!({"resolution": 0.5}));
+        }
+        let received_size = json!({"op": "publish", "topic": "/map", "msg": {"resolution": 0.5}})
+            .to_string()
+            .len() as u64;
+
+        let (response, _) = tokio::join!(
+            client.call_service::<Echo, Echo>("/echo", Echo { data: 7 }),
+            async {
+                let call = bridge.next_service_call("/echo").await;
+                bridge.respond(&call, call["args"].clone());
+            }
+        );
+        response.unwrap();
+        // Service responses are handled after the publishes sent before them
+        let metrics = client.metrics();
+        assert_eq!(
+            metrics.topics["/map_out"],
+            TopicMetrics {
+                messages_sent: N,
+                bytes_sent: N * sent_size,
+                ..Default::default()
+            }
+        );
+        assert_eq!(
+            metrics.topics["/map"],
+            TopicMetrics {
+                messages_received: N,
+                bytes_received: N * received_size,
+                messages_dropped: N - 2,
+                ..Default::default()
+            }
+        );
+        assert_eq!(metrics.service_calls, 1);
+        assert_eq!(metrics.service_call_failures, 0);
+        assert!(metrics.service_call_latency_max >= metrics.service_call_latency_mean);
+        assert!(metrics.service_call_latency_mean.is_some());
+        assert!(metrics.connection_uptime.is_some());
+        assert_eq!((metrics.reconnect_attempts, metrics.reconnects), (0, 0));
+
+        let mut events = client.connection_events();
+        bridge.disconnect();
+        while !matches!(
+            tokio::time::timeout(TIMEOUT, events.recv())
+                .await
+                .unwrap()
+                .unwrap(),
+            ConnectionEvent::Connected { .. }
+        ) {}
+        let metrics = client.metrics();
+        assert_eq!((metrics.reconnect_attempts, metrics.reconnects), (1, 1));
+        assert!(metrics.connection_uptime.is_some());
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn metrics_can_be_disabled() {
+        let mut bridge = MockBridge::new().await;
+        let client = ClientHandle::builder(bridge.url())
+            .collect_metrics(false)
+            .build()
+            .await
+            .unwrap();
+        let publisher = client.advertise::<MapInfo>("/map_out").await.unwrap();
+        publisher
+            .publish(MapInfo { resolution: 0.5 })
+            .await
+            .unwrap();
+        bridge.next_op("publish", "/map_out").await;
+        assert_eq!(client.metrics(), MetricsSnapshot::default());
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn ping_measures_round_trip_to_rosapi() {
+        let mut bridge = MockBridge::new().await;
+        let client = ClientHandle::builder(bridge.url())
+            .build_unconnected()
+            .unwrap();
+        assert!(!client.is_connected());
+
+        let (rtt, _) = tokio::join!(client.ping(TIMEOUT), async {
+            let call = bridge.next_service_call("/rosapi/ros_version").await;
+            bridge.respond(&call, json!({"version": 1, "distro": "noetic"}));
+        });
+        assert!(rtt.unwrap() < TIMEOUT);
+        assert!(client.is_connected());
+
+        // An unanswered ping times out
+        let timeout = Duration::from_millis(50);
+        let ping = client.ping(timeout).await;
+        assert!(
+            matches!(ping, Err(RosLibRustError::ServiceCallTimeout { timeout: t, .. }) if t == timeout),
+            "{ping:?}"
+        );
+
+        client.close().await.unwrap();
+        assert!(!client.is_connected());
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn wait_for_service_polls_until_service_appears() {
+        let mut bridge = MockBridge::new().await;
+        let client = ClientHandle::builder(bridge.url())
+            .service_poll_interval(Duration::from_millis(10))
+            .build()
+            .await
+            .unwrap();
+
+        // The service is only reported after the third poll
+        let (echoed, _) = tokio::join!(
+            client.call_service_when_available::<Echo, Echo>("/echo", Echo { data: 3 }, TIMEOUT),
+            async {
+                for _ in 0..3 {
+                    let call = bridge.next_service_call("/rosapi/service_type").await;
+                    assert_eq!(call["args"]["service"], "/echo");
+                    bridge.respond(&call, json!({"type": ""}));
+                }
+                let call = bridge.next_service_call("/rosapi/service_type").await;
+                bridge.respond(&call, json!({"type": "test_msgs/Echo"}));
+                let call = bridge.next_service_call("/echo").await;
+                bridge.respond(&call, call["args"].clone());
+            }
+        );
+        assert_eq!(echoed.unwrap(), Echo { data: 3 });
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn wait_for_service_times_out_or_reports_missing_rosapi() {
+        let mut bridge = MockBridge::new().await;
+        let client = ClientHandle::builder(bridge.url())
+            .service_poll_interval(Duration::from_millis(10))
+            .build()
+            .await
+            .unwrap();
+
+        // A service which never appears
+        let timeout = Duration::from_millis(100);
+        let waited = tokio::select! {
+            waited = client.wait_for_service("/missing", timeout) => waited,
+            _ = async {
+                loop {
+                    let call = bridge.next_service_call("/rosapi/service_type").await;
+                    bridge.respond(&call, json!({"type": ""}));
+                }
+            } => unreachable!(),
+        };
+        assert!(
+            matches!(&waited, Err(RosLibRustError::ServiceUnavailable { service, timeout: t })
+                if service == "/missing" && *t == timeout),
+            "{waited:?}"
+        );
+
+        // Rosbridge fails calls to rosapi when it isn't running
+        let waited = tokio::select! {
+            waited = client.wait_for_service("/echo", TIMEOUT) => waited,
+            _ = async {
+                loop {
+                    let call = bridge.next_service_call("/rosapi/service_type").await;
+                    bridge.fail(&call, "Service /rosapi/service_type does not exist");
+                }
+            } => unreachable!(),
+        };
+        assert!(
+            matches!(&waited, Err(RosLibRustError::RosapiUnavailable(_))),
+            "{waited:?}"
+        );
+
+        // Other failures are rosapi's own, not a sign of it missing
+        let waited = tokio::select! {
+            waited = client.wait_for_service("/echo", TIMEOUT) => waited,
+            _ = async {
+                loop {
+                    let call = bridge.next_service_call("/rosapi/service_type").await;
+                    bridge.fail(&call, "Service /echo does not exist");
+                }
+            } => unreachable!(),
+        };
+        assert!(
+            matches!(&waited, Err(RosLibRustError::ServerError(message))
+                if message == "Service /echo does not exist"),
+            "{waited:?}"
+        );
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn raw_service_calls_send_json_as_is() {
+        let mut bridge = MockBridge::new().await;
+        // Typed calls would rename the time fields for ROS2
+        let client = ClientHandle::builder(bridge.url())
+            .ros_version(RosVersion::ROS2)
+            .build()
+            .await
+            .unwrap();
+        let args = json!({"stamp": {"secs": 1, "nsecs": 2}, "names": ["a", "b"]});
+
+        let (response, _) = tokio::join!(
+            client.call_service_raw("/echo_any", args.clone(), TIMEOUT),
+            async {
+                let call = bridge.next_service_call("/echo_any").await;
+                assert_eq!(call["args"], args);
+                assert_eq!(call.get("compression"), None);
+                bridge.respond(&call, call["args"].clone());
+            }
+        );
+        assert_eq!(response.unwrap(), args);
+        assert_eq!(bridge.binary_ops(), 0);
+
+        let (response, _) = tokio::join!(
+            client.call_service_raw("/echo_any", json!({}), Duration::from_millis(50)),
+            bridge.next_service_call("/echo_any")
+        );
+        assert!(
+            matches!(response, Err(RosLibRustError::ServiceCallTimeout { .. })),
+            "{response:?}"
+        );
+        assert_eq!(client.metrics().service_calls, 2);
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn cbor_raw_service_calls_are_answered_as_binary() {
+        let mut bridge = MockBridge::new().await;
+        let client = ClientHandle::new(bridge.url()).await.unwrap();
+        let args = json!({"data": [1.5, -2.0, 3.25], "label": "scan"});
+
+        let (response, _) = tokio::join!(
+            client.call_service_cbor_raw("/echo_any", args.clone(), TIMEOUT),
+            async {
+                let call = bridge.next_service_call("/echo_any").await;
+                assert_eq!(call["compression"], "cbor");
+                bridge.respond_cbor(&call, call["args"].clone());
+            }
+        );
+        assert_eq!(response.unwrap(), args);
+        // rosbridge only parses text frames, so the call must not be binary
+        assert_eq!(bridge.binary_ops(), 0);
+
+        let (response, _) = tokio::join!(
+            client.call_service_cbor_raw("/echo_any", json!({}), TIMEOUT),
+            async {
+                let call = bridge.next_service_call("/echo_any").await;
+                bridge.fail(&call, "Service /echo_any does not exist");
+            }
+        );
+        assert!(
+            matches!(response, Err(RosLibRustError::ServerError(_))),
+            "{response:?}"
+        );
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn failed_service_calls_return_the_server_message() {
+        let mut bridge = MockBridge::new().await;
+        let client = ClientHandle::new(bridge.url()).await.unwrap();
+
+        let (response, _) = tokio::join!(
+            client.call_service::<Echo, Echo>("/echo", Echo { data: 1 }),
+            async {
+                let call = bridge.next_service_call("/echo").await;
+                bridge.fail(&call, "Service /echo does not exist");
+            }
+        );
+        match response {
+            Err(RosLibRustError::ServerError(message)) => {
+                assert_eq!(message, "Service /echo does not exist")
+            }
+            other => panic!("Expected a server error, got {other:?}"),
+        }
+
+        // Successful responses of the wrong type are still reported as invalid
+        let (response, _) = tokio::join!(
+            client.call_service::<Echo, Echo>("/echo", Echo { data: 1 }),
+            async {
+                let call = bridge.next_service_call("/echo").await;
+                bridge.respond(&call, json!({"data": "seven"}));
+            }
+        );
+        assert!(
+            matches!(response, Err(RosLibRustError::InvalidMessage(_))),
+            "{response:?}"
+        );
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn failed_service_callbacks_respond_with_the_error() {
+        let mut bridge = MockBridge::new().await;
+        let client = ClientHandle::new(bridge.url()).await.unwrap();
+        let _service = client
+            .advertise_service::<EchoService, _>("/echo", |request: Echo| match request.data {
+                0 => Err("data must be non-zero".into()),
+                _ => Ok(request),
+            })
+            .await
+            .unwrap();
+
+        for (service, data, result, values) in [
+            ("/echo", 0, false, json!("data must be non-zero")),
+            (
+                "/missing",
+                1,
+                false,
+                json!("Service /missing is not advertised by this client"),
+            ),
+            ("/echo", 3, true, json!({"data": 3})),
+        ] {
+            bridge.call_service(service, "call", json!({ "data": data }));
+            let response = bridge.next_service_response().await;
+            assert_eq!(response["service"], service);
+            assert_eq!(response["id"], "call");
+            assert_eq!(response["result"], result);
+            assert_eq!(response["values"], values);
+        }
+    }
+
+    /// Waits until the service's metrics satisfy `condition`, as handlers start on other threads
+    async fn wait_for_service_metrics(
+        client: &ClientHandle,
+        service: &str,
+        condition: impl Fn(&ServiceMetrics) -> bool,
+    ) -> ServiceMetrics {
+        tokio::time::timeout(TIMEOUT, async {
+            loop {
+                let metrics = client.metrics().services[service].clone();
+                if condition(&metrics) {
+                    return metrics;
+                }
+                tokio::time::sleep(Duration::from_millis(5)).await;
+            }
+        })
+        .await
+        .unwrap()
+    }
+
+    /// Handlers calling the returned closure block until the returned sender is dropped, giving up
+    /// after [TIMEOUT] so a failing test can't hang
+    fn release_gate() -> (
+        std::sync::mpsc::Sender<()>,
+        impl Fn() + Send + Sync + 'static,
+    ) {
+        let (release, released) = std::sync::mpsc::channel::<()>();
+        let released = std::sync::Mutex::new(released);
+        let wait = move || {
+            let _ = released.lock().unwrap().recv_timeout(TIMEOUT);
+        };
+        (release, wait)
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn service_calls_are_handled_one_at_a_time_by_default() {
+        let mut bridge = MockBridge::new().await;
+        let client = ClientHandle::new(bridge.url()).await.unwrap();
+        let (release, wait_for_release) = release_gate();
+        let _service = client
+            .advertise_service::<EchoService, _>("/echo", move |request: Echo| {
+                wait_for_release();
+                Ok(request)
+            })
+            .await
+            .unwrap();
+
+        for data in 0..4 {
+            bridge.call_service("/echo", &format!("call{data}"), json!({ "data": data }));
+        }
+        // Nothing is rejected, the calls wait for the one handler running
+        let metrics =
+            wait_for_service_metrics(&client, "/echo", |m| m.in_flight + m.queued == 4).await;
+        assert_eq!(
+            metrics,
+            ServiceMetrics {
+                queued: 3,
+                in_flight: 1,
+                rejected: 0,
+                timed_out: 0,
+            }
+        );
+
+        drop(release);
+        let mut responses = vec![];
+        for _ in 0..4 {
+            let response = tokio::time::timeout(TIMEOUT, bridge.next_service_response())
+                .await
+                .unwrap();
+            assert_eq!(response["result"], true);
+            let data = response["values"]["data"].as_u64().unwrap();
+            assert_eq!(response["id"], format!("call{data}"));
+            responses.push(data);
+        }
+        responses.sort();
+        assert_eq!(responses, [0, 1, 2, 3]);
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn service_calls_over_the_limit_are_queued_then_rejected() {
+        let mut bridge = MockBridge::new().await;
+        let client = ClientHandle::new(bridge.url()).await.unwrap();
+        let (release, wait_for_release) = release_gate();
+        let options = ServiceOptions {
+            max_concurrent: 2,
+            queue_size: Some(1),
+            timeout: None,
+        };
+        let _service = client
+            .advertise_service_with_options::<EchoService, _>(
+                "/echo",
+                move |request: Echo| {
+                    wait_for_release();
+                    Ok(request)
+                },
+                options,
+            )
+            .await
+            .unwrap();
+
+        for data in 0..5 {
+            bridge.call_service("/echo", &format!("call{data}"), json!({ "data": data }));
+        }
+        // Two calls are handled, one queued, and the rest answered straight away
+        for id in ["call3", "call4"] {
+            let response = bridge.next_service_response().await;
+            assert_eq!(response["id"], id);
+            assert_eq!(response["result"], false);
+            assert_eq!(
+                response["values"],
+                "Service /echo is busy, too many calls are already waiting"
+            );
+        }
+        let metrics = wait_for_service_metrics(&client, "/echo", |m| m.in_flight == 2).await;
+        assert_eq!(
+            metrics,
+            ServiceMetrics {
+                queued: 1,
+                in_flight: 2,
+                rejected: 2,
+                timed_out: 0,
+            }
+        );
+
+        drop(release);
+        let mut responses = vec![];
+        for _ in 0..3 {
+            let response = bridge.next_service_response().await;
+            assert_eq!(response["result"], true);
+            let data = response["values"]["data"].as_u64().unwrap();
+            assert_eq!(response["id"], format!("call{data}"));
+            responses.push(data);
+        }
+        responses.sort();
+        assert_eq!(responses, [0, 1, 2]);
+        wait_for_service_metrics(&client, "/echo", |m| m.in_flight == 0 && m.queued == 0).await;
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn service_calls_time_out_while_handled_or_queued() {
+        let mut bridge = MockBridge::new().await;
+        let client = ClientHandle::new(bridge.url()).await.unwrap();
+        let (release, wait_for_release) = release_gate();
+        let options = ServiceOptions {
+            max_concurrent: 1,
+            queue_size: Some(1),
+            timeout: Some(Duration::from_millis(200)),
+        };
+        let _service = client
+            .advertise_service_with_options::<EchoService, _>(
+                "/echo",
+                move |request: Echo| {
+                    wait_for_release();
+                    Ok(request)
+                },
+                options,
+            )
+            .await
+            .unwrap();
+
+        bridge.call_service("/echo", "handled", json!({ "data": 1 }));
+        bridge.call_service("/echo", "queued", json!({ "data": 2 }));
+        for id in ["handled", "queued"] {
+            let response = tokio::time::timeout(TIMEOUT, bridge.next_service_response())
+                .await
+                .unwrap();
+            assert_eq!(response["id"], id);
+            assert_eq!(response["result"], false);
+            assert_eq!(
+                response["values"],
+                "Service /echo did not respond within 200ms"
+            );
+        }
+        // The abandoned handler still holds its permit until it returns
+        let metrics = client.metrics().services["/echo"].clone();
+        assert_eq!((metrics.queued, metrics.in_flight), (0, 1));
+        assert_eq!(metrics.timed_out, 2);
+
+        // Once it does its response is discarded, and the next call is handled
+        drop(release);
+        wait_for_service_metrics(&client, "/echo", |m| m.in_flight == 0).await;
+        bridge.call_service("/echo", "next", json!({ "data": 3 }));
+        let response = tokio::time::timeout(TIMEOUT, bridge.next_service_response())
+            .await
+            .unwrap();
+        assert_eq!(response["id"], "next");
+        assert_eq!(response["result"], true);
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn reconnect_republishes_latched_message_once() {
+        let mut bridge = MockBridge::new().await;
+        let client = ClientHandle::new(bridge.url()).await.unwrap();
+
+        let publisher = client
+            .advertise_with_options::<MapInfo>("/map", AdvertiseOptions { latch: true })
+            .await
+            .unwrap();
+        bridge.next_op("advertise", "/map").await;
+        publisher
+            .publish(MapInfo { resolution: 0.1 })
+            .await
+            .unwrap();
+        publisher
+            .publish(MapInfo { resolution: 0.05 })
+            .await
+            .unwrap();
+        bridge.next_op("publish", "/map").await;
+        bridge.next_op("publish", "/map").await;
+
+        bridge.disconnect();
+
+        let advertise = tokio::time::timeout(TIMEOUT, bridge.next_op("advertise", "/map"))
+            .await
+            .unwrap();
+        assert_eq!(advertise["latch"], true);
+        let republish = tokio::time::timeout(TIMEOUT, bridge.next_op("publish", "/map"))
+            .await
+            .unwrap();
+        assert_eq!(republish["msg"]["resolution"], 0.05);
+        assert!(
+            tokio::time::timeout(
+                Duration::from_millis(300),
+                bridge.next_op("publish", "/map")
+            )
+            .await
+            .is_err(),
+            "Latched message should only be republished once"
+        );
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn subscribe_any_discovers_type_and_receives_json() {
+        let mut bridge = MockBridge::new().await;
+        let client = ClientHandle::new(bridge.url()).await.unwrap();
+
+        let (subscriber, _) = tokio::join!(client.subscribe_any("/map_metadata"), async {
+            let call = bridge.next_service_call("/rosapi/topic_type").await;
+            assert_eq!(call["args"]["topic"], "/map_metadata");
+            bridge.respond(&call, json!({"type": "nav_msgs/MapMetaData"}));
+        });
+        let mut subscriber = subscriber.unwrap();
+        let subscribe = bridge.next_op("subscribe", "/map_metadata").await;
+        assert_eq!(subscribe["type"], "nav_msgs/MapMetaData");
+
+        bridge.publish(
+            "/map_metadata",
+            json!({"resolution": 0.05, "origin": {"position": {"x": 1.5}}}),
+        );
+        let (topic_type, msg) = tokio::time::timeout(TIMEOUT, subscriber.next())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(topic_type, "nav_msgs/MapMetaData");
+        assert_eq!(msg["resolution"], 0.05);
+        assert_eq!(msg["origin"]["position"]["x"], 1.5);
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn typed_and_untyped_subscribers_coexist() {
+        let mut bridge = MockBridge::new().await;
+        let client = ClientHandle::new(bridge.url()).await.unwrap();
+
+        let typed = client.subscribe::<MapInfo>("/map_metadata").await.unwrap();
+        // The type is already known, so no rosapi lookup is needed
+        let mut untyped = client.subscribe_any("/map_metadata").await.unwrap();
+        bridge.next_op("subscribe", "/map_metadata").await;
+        bridge.next_op("subscribe", "/map_metadata").await;
+
+        bridge.publish("/map_metadata", json!({"resolution": 0.05}));
+        let typed_msg = tokio::time::timeout(TIMEOUT, typed.next()).await.unwrap();
+        assert_eq!(typed_msg, MapInfo { resolution: 0.05 });
+        let (topic_type, msg) = tokio::time::timeout(TIMEOUT, untyped.next())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(topic_type, "nav_msgs/MapMetaData");
+        assert_eq!(msg["resolution"], 0.05);
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn subscribe_once_returns_first_message_and_unsubscribes() {
+        let mut bridge = MockBridge::new().await;
+        let client = ClientHandle::new(bridge.url()).await.unwrap();
+
+        let (msg, _) = tokio::join!(client.subscribe_once::<Echo>("/echo", TIMEOUT), async {
+            bridge.next_op("subscribe", "/echo").await;
+            bridge.publish("/echo", json!({"data": 1}));
+            bridge.publish("/echo", json!({"data": 2}));
+        });
+        assert_eq!(msg.unwrap(), Echo { data: 1 });
+        bridge.next_op("unsubscribe", "/echo").await;
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn subscribe_once_times_out() {
+        let mut bridge = MockBridge::new().await;
+        let client = ClientHandle::new(bridge.url()).await.unwrap();
+
+        let wait = Duration::from_millis(100);
+        let err = client.subscribe_once::<Echo>("/echo", wait).await;
+        assert!(matches!(
+            err,
+            Err(SubscribeOnceError::Timeout { waited, subscribed: true, .. }) if waited == wait
+        ));
+        bridge.next_op("subscribe", "/echo").await;
+        bridge.next_op("unsubscribe", "/echo").await;
+
+        // Never connects in time, so the subscribe is never sent
+        let bridge = MockBridge::with_accept_delay(Duration::from_millis(500)).await;
+        let client = ClientHandle::builder(bridge.url())
+            .timeout(TIMEOUT)
+            .build_unconnected()
+            .unwrap();
+        let err = client.subscribe_once::<Echo>("/echo", wait).await;
+        assert!(matches!(
+            err,
+            Err(SubscribeOnceError::Timeout {
+                subscribed: false,
+                ..
+            })
+        ));
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn subscribe_once_leaves_existing_subscriber_subscribed() {
+        let mut bridge = MockBridge::new().await;
+        let client = ClientHandle::new(bridge.url()).await.unwrap();
+        let existing = client.subscribe::<Echo>("/echo").await.unwrap();
+        bridge.next_op("subscribe", "/echo").await;
+
+        let (msg, _) = tokio::join!(client.subscribe_once::<Echo>("/echo", TIMEOUT), async {
+            bridge.next_op("subscribe", "/echo").await;
+            bridge.publish("/echo", json!({"data": 1}));
+        });
+        assert_eq!(msg.unwrap(), Echo { data: 1 });
+        assert_eq!(
+            existing.next_timeout(TIMEOUT).await.unwrap(),
+            Echo { data: 1 }
+        );
+
+        // The next operation the bridge sees is this publish rather than an unsubscribe
+        client
+            .publish_any("/marker", "test_msgs/Echo", json!({"data": 0}))
+            .await
+            .unwrap();
+        assert_eq!(bridge.next_message().await["op"], "publish");
+        bridge.publish("/echo", json!({"data": 2}));
+        assert_eq!(
+            existing.next_timeout(TIMEOUT).await.unwrap(),
+            Echo { data: 2 }
+        );
+        assert!(matches!(
+            existing.next_timeout(Duration::from_millis(50)).await,
+            Err(RosLibRustError::Timeout(_))
+        ));
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn publish_any_sends_json() {
+        let mut bridge = MockBridge::new().await;
+        let client = ClientHandle::new(bridge.url()).await.unwrap();
+
+        client
+            .publish_any(
+                "/map_metadata",
+                "nav_msgs/MapMetaData",
+                json!({"resolution": 0.05}),
+            )
+            .await
+            .unwrap();
+        let publish = bridge.next_op("publish", "/map_metadata").await;
+        assert_eq!(publish["type"], "nav_msgs/MapMetaData");
+        assert_eq!(publish["msg"]["resolution"], 0.05);
+    }
+
+    /// Receives fragments until an operation is complete, checking each is at most `size` bytes
+    async fn reassemble(bridge: &mut MockBridge, size: usize) -> Value {
+        let first = bridge.next_message().await;
+        assert_eq!(first["op"], "fragment");
+        let total = first["total"].as_u64().unwrap();
+        let mut data = String::new();
+        let mut fragment = first.clone();
+        for num in 0..total {
+            assert_eq!(fragment["op"], "fragment");
+            assert_eq!(fragment["id"], first["id"]);
+            assert_eq!(fragment["num"], num);
+            assert_eq!(fragment["total"], total);
+            let piece = fragment["data"].as_str().unwrap();
+            assert!(piece.len() <= size);
+            data.push_str(piece);
+            if num + 1 < total {
+                fragment = bridge.next_message().await;
+            }
+        }
+        serde_json::from_str(&data).unwrap()
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn large_operations_are_fragmented() {
+        let mut bridge = MockBridge::new().await;
+        let client = ClientHandle::builder(bridge.url())
+            .timeout(TIMEOUT)
+            .fragment_outgoing(1000, 256)
+            .build()
+            .await
+            .unwrap();
+
+        // Small enough to be sent whole
+        let small = json!({"resolution": 0.05});
+        client
+            .publish_any("/map_metadata", "nav_msgs/MapMetaData", small.clone())
+            .await
+            .unwrap();
+        let publish = bridge.next_message().await;
+        assert_eq!(publish["op"], "publish");
+        assert_eq!(publish["msg"], small);
+
+        let grid = json!({"data": vec![100u8; 2000], "info": {"width": 40}});
+        client
+            .publish_any("/map", "nav_msgs/OccupancyGrid", grid.clone())
+            .await
+            .unwrap();
+        assert_eq!(
+            reassemble(&mut bridge, 256).await,
+            json!({"op": "publish", "topic": "/map", "type": "nav_msgs/OccupancyGrid", "msg": grid})
+        );
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn concurrent_service_calls_resolve_with_their_own_response() {
+        const CALLS: u32 = 500;
+        let mut bridge = MockBridge::new().await;
+        let client = ClientHandle::new(bridge.url()).await.unwrap();
+
+        let calls = futures::future::join_all(
+            (0..CALLS).map(|data| client.call_service::<Echo, Echo>("/echo", Echo { data })),
+        );
+        let (responses, _) = tokio::join!(calls, async {
+            let mut received = vec![];
+            for _ in 0..CALLS {
+                received.push(bridge.next_service_call("/echo").await);
+            }
+            // Responses for ids the client never used are dropped
+            bridge.respond(
+                &json!({"service": "/echo", "id": "not_a_call"}),
+                json!({"data": CALLS}),
+            );
+            // Reply in reverse order, answering the first call twice
+            for call in received.iter().rev() {
+                bridge.respond(call, call["args"].clone());
+            }
+            bridge.respond(&received[0], json!({"data": CALLS}));
+        });
+        for (data, response) in responses.into_iter().enumerate() {
+            assert_eq!(response.unwrap(), Echo { data: data as u32 });
+        }
+        assert!(client.inner.read().await.service_calls.is_empty());
+
+        // The client is still usable after the unknown and duplicate responses
+        let (response, _) = tokio::join!(
+            client.call_service::<Echo, Echo>("/echo", Echo { data: 7 }),
+            async {
+                let call = bridge.next_service_call("/echo").await;
+                bridge.respond(&call, call["args"].clone());
+            }
+        );
+        assert_eq!(response.unwrap(), Echo { data: 7 });
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn cancelled_service_calls_are_removed() {
+        let mut bridge = MockBridge::new().await;
+        let client = ClientHandle::new(bridge.url()).await.unwrap();
+
+        let cancelled = tokio::time::timeout(
+            Duration::from_millis(100),
+            client.call_service::<Echo, Echo>("/echo", Echo { data: 1 }),
+        );
+        let (cancelled, call) = tokio::join!(cancelled, bridge.next_service_call("/echo"));
+        assert!(cancelled.is_err());
+        assert!(client.inner.read().await.service_calls.is_empty());
+
+        // A late response to the cancelled call is ignored
+        bridge.respond(&call, call["args"].clone());
+        let (response, _) = tokio::join!(
+            client.call_service::<Echo, Echo>("/echo", Echo { data: 2 }),
+            async {
+                let call = bridge.next_service_call("/echo").await;
+                bridge.respond(&call, call["args"].clone());
+            }
+        );
+        assert_eq!(response.unwrap(), Echo { data: 2 });
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn pending_service_calls_fail_on_disconnect() {
+        let mut bridge = MockBridge::new().await;
+        let client = ClientHandle::new(bridge.url()).await.unwrap();
+
+        let (response, _) = tokio::join!(
+            tokio::time::timeout(
+                TIMEOUT,
+                client.call_service::<Echo, Echo>("/echo", Echo { data: 1 })
+            ),
+            async {
+                bridge.next_service_call("/echo").await;
+                bridge.disconnect();
+            }
+        );
+        assert!(matches!(
+            response.unwrap(),
+            Err(RosLibRustError::Disconnected)
+        ));
+        assert!(client.inner.read().await.service_calls.is_empty());
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn close_sends_publish_started_before_it() {
+        let mut bridge = MockBridge::new().await;
+        let client = ClientHandle::new(bridge.url()).await.unwrap();
+        let publisher = client.advertise::<MapInfo>("/status").await.unwrap();
+        bridge.next_op("advertise", "/status").await;
+
+        let (published, closed) = tokio::join!(
+            publisher.publish(MapInfo { resolution: 0.5 }),
+            client.close()
+        );
+        published.unwrap();
+        closed.unwrap();
+        let publish = bridge.next_op("publish", "/status").await;
+        assert_eq!(publish["msg"]["resolution"], 0.5);
+
+        // Nothing is sent after closing, including when the publisher is dropped
+        assert!(matches!(
+            publisher.publish(MapInfo { resolution: 1.0 }).await,
+            Err(RosLibRustError::Disconnected)
+        ));
+        assert!(matches!(
+            client.subscribe::<MapInfo>("/map").await,
+            Err(RosLibRustError::Disconnected)
+        ));
+        drop(publisher);
+        // Closing again is a no-op
+        client.close().await.unwrap();
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn close_fails_pending_service_calls() {
+        let mut bridge = MockBridge::new().await;
+        let client = ClientHandle::new(bridge.url()).await.unwrap();
+
+        let (response, _) = tokio::join!(
+            tokio::time::timeout(
+                TIMEOUT,
+                client.call_service::<Echo, Echo>("/echo", Echo { data: 1 })
+            ),
+            async {
+                bridge.next_service_call("/echo").await;
+                client.close().await.unwrap();
+            }
+        );
+        assert!(matches!(
+            response.unwrap(),
+            Err(RosLibRustError::Disconnected)
+        ));
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn close_can_unregister_everything() {
+        let mut bridge = MockBridge::new().await;
+        let client = ClientHandle::new(bridge.url()).await.unwrap();
+        let _subscriber = client.subscribe::<MapInfo>("/map").await.unwrap();
+        let _publisher = client.advertise::<MapInfo>("/status").await.unwrap();
+
+        client
+            .close_with_options(CloseOptions {
+                unregister: true,
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        bridge.next_op("unsubscribe", "/map").await;
+        bridge.next_op("unadvertise", "/status").await;
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn connect_timeout_fires_when_nothing_is_listening() {
+        let url = {
+            let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+            format!("ws://{}", listener.local_addr().unwrap())
+        };
+        let opts = ClientHandleOptions::new(url).connect_timeout(Duration::from_millis(500));
+        let res = ClientHandle::new_with_options(opts).await;
+        assert!(matches!(res, Err(RosLibRustError::ConnectTimeout(_))));
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn connect_with_timeout_moves_on_from_dead_candidates() {
+        let closed = {
+            let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+            format!("ws://{}", listener.local_addr().unwrap())
+        };
+        // Accepts connections but never completes the handshake
+        let (unresponsive, _unresponsive) =
+            crate::rosbridge::mock_bridge::unresponsive_bridge(false).await;
+        let bridge = MockBridge::new().await;
+
+        let started = tokio::time::Instant::now();
+        let mut errors = vec![];
+        let mut client = None;
+        for url in [closed, unresponsive, bridge.url().to_owned()] {
+            match ClientHandle::connect_with_timeout(url, Duration::from_millis(200)).await {
+                Ok(connected) => client = Some(connected),
+                Err(e) => errors.push(e),
+            }
+        }
+        assert!(client.unwrap().is_connected());
+        assert!(
+            errors
+                .iter()
+                .all(|e| matches!(e, RosLibRustError::ConnectTimeout(_))),
+            "{errors:?}"
+        );
+        assert_eq!(errors.len(), 2);
+        assert!(started.elapsed() < TIMEOUT);
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn handshake_timeout_fires_when_server_never_responds() {
+        let (url, _bridge) = crate::rosbridge::mock_bridge::unresponsive_bridge(false).await;
+        let opts = ClientHandleOptions::new(url)
+            .connect_timeout(Duration::from_secs(5))
+            .handshake_timeout(Duration::from_millis(200));
+        let res = ClientHandle::new_with_options(opts).await;
+        assert!(matches!(res, Err(RosLibRustError::HandshakeTimeout(_))));
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn service_call_timeout_fires_without_response() {
+        let mut bridge = MockBridge::new().await;
+        let opts = ClientHandleOptions::new(bridge.url())
+            .timeout(TIMEOUT)
+            .default_service_call_timeout(Duration::from_millis(100));
+        let client = ClientHandle::new_with_options(opts).await.unwrap();
+
+        let res = client
+            .call_service::<Echo, Echo>("/echo", Echo { data: 1 })
+            .await;
+        assert!(matches!(
+            res,
+            Err(RosLibRustError::ServiceCallTimeout { ref service, .. }) if service == "/echo"
+        ));
+        assert!(client.inner.read().await.service_calls.is_empty());
+
+        // A per call timeout takes precedence over the client's default
+        let call =
+            client.call_service_with_timeout::<Echo, Echo>("/echo", Echo { data: 2 }, TIMEOUT);
+        let respond = async {
+            // Skipping the call which already timed out
+            bridge.next_service_call("/echo").await;
+            let request = bridge.next_service_call("/echo").await;
+            tokio::time::sleep(Duration::from_millis(300)).await;
+            bridge.respond(&request, json!({"data": 2}));
+        };
+        let (res, ()) = tokio::join!(call, respond);
+        assert_eq!(res.unwrap(), Echo { data: 2 });
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn publish_flush_timeout_fires_when_bridge_stops_reading() {
+        let (url, _bridge) = crate::rosbridge::mock_bridge::unresponsive_bridge(true).await;
+        let opts = ClientHandleOptions::new(url)
+            .timeout(TIMEOUT)
+            .publish_flush_timeout(Duration::from_millis(200));
+        let client = ClientHandle::new_with_options(opts).await.unwrap();
+
+        // Eventually the socket's buffers fill up and writes stop completing
+        let msg = json!({"data": "x".repeat(1 << 20)});
+        for _ in 0..1000 {
+            match client
+                .publish_any("/big", "std_msgs/String", msg.clone())
+                .await
+            {
+                Ok(()) => {}
+                Err(RosLibRustError::PublishFlushTimeout(_)) => return,
+                Err(e) => panic!("Unexpected error publishing: {e}"),
+            }
+        }
+        panic!("Publishing never timed out");
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn builder_rejects_invalid_urls() {
+        for url in [
+            "localhost:9090",
+            "http://localhost:9090",
+            "ws://",
+            "not a url",
+        ] {
+            let res = ClientHandle::builder(url).build_unconnected();
+            assert!(
+                matches!(res, Err(RosLibRustError::InvalidUrl(_))),
+                "{url} was accepted"
+            );
+            let res = ClientHandle::builder(url).build().await;
+            assert!(matches!(res, Err(RosLibRustError::InvalidUrl(_))));
+        }
+        assert!(ClientHandle::builder("wss://example.com:9090/path")
+            .build_unconnected()
+            .is_ok());
+        assert!(ClientHandle::builder("tcp://example.com:9090")
+            .build_unconnected()
+            .is_ok());
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn tcp_transport_handles_operations_with_each_framing() {
+        for framing in [TcpFraming::Json, TcpFraming::NullDelimited] {
+            let mut bridge = MockBridge::tcp(framing).await;
+            let client = ClientHandle::builder(bridge.url())
+                .timeout(TIMEOUT)
+                .tcp_framing(framing)
+                .build()
+                .await
+                .unwrap();
+
+            let subscriber = client.subscribe::<Echo>("/echo").await.unwrap();
+            let subscribe = bridge.next_op("subscribe", "/echo").await;
+            assert_eq!(subscribe["type"], "test_msgs/Echo");
+            // Sent back to back, so they are likely to arrive together
+            bridge.publish("/echo", json!({"data": 1}));
+            bridge.publish("/echo", json!({"data": 2}));
+            assert_eq!(subscriber.next_timeout(TIMEOUT).await.unwrap().data, 1);
+            assert_eq!(subscriber.next_timeout(TIMEOUT).await.unwrap().data, 2);
+
+            let publisher = client.advertise::<Echo>("/out").await.unwrap();
+            publisher.publish(Echo { data: 3 }).await.unwrap();
+            let publish = bridge.next_op("publish", "/out").await;
+            assert_eq!(publish["msg"]["data"], 3);
+
+            let (response, _) = tokio::join!(
+                client.call_service::<Echo, Echo>("/echo_service", Echo { data: 4 }),
+                async {
+                    let call = bridge.next_service_call("/echo_service").await;
+                    bridge.respond(&call, call["args"].clone());
+                }
+            );
+            assert_eq!(response.unwrap(), Echo { data: 4 });
+        }
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn tcp_transport_reconnects_and_resubscribes() {
+        let mut bridge = MockBridge::tcp(TcpFraming::Json).await;
+        let client = ClientHandle::builder(bridge.url())
+            .timeout(TIMEOUT)
+            .build()
+            .await
+            .unwrap();
+        let subscriber = client.subscribe::<Echo>("/echo").await.unwrap();
+        bridge.next_op("subscribe", "/echo").await;
+
+        bridge.disconnect();
+        bridge.next_op("subscribe", "/echo").await;
+        bridge.publish("/echo", json!({"data": 1}));
+        assert_eq!(subscriber.next_timeout(TIMEOUT).await.unwrap().data, 1);
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn oversized_messages_are_reported_with_limit_and_topic() {
+        let mut bridge = MockBridge::new().await;
+        let client = ClientHandle::builder(bridge.url())
+            .timeout(TIMEOUT)
+            .max_message_size(Some(1024))
+            .build()
+            .await
+            .unwrap();
+        let mut events = client.connection_events();
+        let subscriber = client.subscribe::<Echo>("/echo").await.unwrap();
+        bridge.next_op("subscribe", "/echo").await;
+
+        // Reconnecting must keep the limit, so the second oversized message is rejected too
+        for _ in 0..2 {
+            bridge.publish("/echo", json!({"data": 1, "padding": "x".repeat(2048)}));
+            let reason = loop {
+                let event = tokio::time::timeout(TIMEOUT, events.recv())
+                    .await
+                    .unwrap()
+                    .unwrap();
+                if let ConnectionEvent::Disconnected { reason } = event {
+                    break reason;
+                }
+            };
+            assert!(reason.contains("over the limit of 1024 bytes"), "{reason}");
+            assert!(reason.contains(" on /echo "), "{reason}");
+            // The mock serves one connection at a time, so it has to let go of the abandoned one
+            bridge.disconnect();
+            bridge.next_op("subscribe", "/echo").await;
+        }
+
+        bridge.publish("/echo", json!({"data": 2}));
+        assert_eq!(subscriber.next_timeout(TIMEOUT).await.unwrap().data, 2);
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn build_unconnected_connects_on_first_use() {
+        let mut bridge = MockBridge::new().await;
+        let client = ClientHandle::builder(bridge.url())
+            .timeout(TIMEOUT)
+            .build_unconnected()
+            .unwrap();
+        assert!(client.inner.read().await.connection.get().is_none());
+
+        let _subscriber = client.subscribe::<MapInfo>("/map").await.unwrap();
+        tokio::time::timeout(TIMEOUT, bridge.next_op("subscribe", "/map"))
+            .await
+            .unwrap();
+        assert!(client.inner.read().await.connection.get().is_some());
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn connection_events_report_connect_and_reconnect() {
+        let mut bridge = MockBridge::new().await;
+        let client = ClientHandle::builder(bridge.url())
+            .timeout(TIMEOUT)
+            .build_unconnected()
+            .unwrap();
+        let mut events = client.connection_events();
+
+        let _subscriber = client.subscribe::<MapInfo>("/map").await.unwrap();
+        bridge.next_op("subscribe", "/map").await;
+        assert_eq!(
+            tokio::time::timeout(TIMEOUT, events.recv())
+                .await
+                .unwrap()
+                .unwrap(),
+            ConnectionEvent::Connected { resubscribed: 0 }
+        );
+
+        for _ in 0..2 {
+            bridge.disconnect();
+            let mut cycle = vec![];
+            for _ in 0..3 {
+                let event = tokio::time::timeout(TIMEOUT, events.recv())
+                    .await
+                    .unwrap()
+                    .unwrap();
+                cycle.push(event);
+            }
+            assert!(matches!(cycle[0], ConnectionEvent::Disconnected { .. }));
+            assert_eq!(
+                cycle[1..],
+                [
+                    ConnectionEvent::Reconnecting { attempt: 1 },
+                    ConnectionEvent::Connected { resubscribed: 1 }
+                ]
+            );
+            // The subscription is restored by the time Connected is received
+            tokio::time::timeout(TIMEOUT, bridge.next_op("subscribe", "/map"))
+                .await
+                .unwrap();
+        }
+        assert!(events.try_recv().is_err());
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn connection_event_callbacks_can_use_client() {
+        let mut bridge = MockBridge::new().await;
+        let client = ClientHandle::builder(bridge.url())
+            .timeout(TIMEOUT)
+            .build()
+            .await
+            .unwrap();
+        let publisher = Arc::new(client.advertise::<MapInfo>("/status").await.unwrap());
+        bridge.next_op("advertise", "/status").await;
+
+        let (counts, mut counted) = tokio::sync::mpsc::unbounded_channel();
+        client.on_connection_event(move |event| {
+            let publisher = publisher.clone();
+            let counts = counts.clone();
+            async move {
+                if let ConnectionEvent::Connected { .. } = event {
+                    // Calling back into the client mustn't deadlock its reconnect
+                    publisher
+                        .publish(MapInfo { resolution: 1.0 })
+                        .await
+                        .unwrap();
+                }
+                counts.send(event).unwrap();
+            }
+        });
+
+        bridge.disconnect();
+        bridge.next_op("advertise", "/status").await;
+        tokio::time::timeout(TIMEOUT, bridge.next_op("publish", "/status"))
+            .await
+            .unwrap();
+        let mut received = vec![];
+        for _ in 0..3 {
+            let event = tokio::time::timeout(TIMEOUT, counted.recv())
+                .await
+                .unwrap()
+                .unwrap();
+            received.push(event);
+        }
+        assert!(matches!(received[0], ConnectionEvent::Disconnected { .. }));
+        assert_eq!(
+            received[1..],
+            [
+                ConnectionEvent::Reconnecting { attempt: 1 },
+                ConnectionEvent::Connected { resubscribed: 0 }
+            ]
+        );
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn subscribe_before_bridge_accepts_receives_first_message() {
+        let mut bridge = MockBridge::with_accept_delay(Duration::from_millis(200)).await;
+        let client = ClientHandle::builder(bridge.url())
+            .timeout(TIMEOUT)
+            .build_unconnected()
+            .unwrap();
+
+        let subscriber = client.subscribe::<MapInfo>("/map").await.unwrap();
+        bridge.next_op("subscribe", "/map").await;
+        bridge.publish("/map", json!({"resolution": 0.5}));
+        let msg = tokio::time::timeout(TIMEOUT, subscriber.next())
+            .await
+            .unwrap();
+        assert_eq!(msg, MapInfo { resolution: 0.5 });
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn operations_during_reconnect_are_sent_once_reconnected() {
+        let mut bridge = MockBridge::with_accept_delay(Duration::from_millis(200)).await;
+        let client = ClientHandle::builder(bridge.url())
+            .timeout(TIMEOUT)
+            .build()
+            .await
+            .unwrap();
+        let mut events = client.connection_events();
+
+        bridge.disconnect();
+        assert!(matches!(
+            tokio::time::timeout(TIMEOUT, events.recv())
+                .await
+                .unwrap()
+                .unwrap(),
+            ConnectionEvent::Disconnected { .. }
+        ));
+        // Issued while the bridge is still refusing to accept the new connection
+        let (subscriber, publisher) = tokio::join!(
+            client.subscribe::<MapInfo>("/map"),
+            client.advertise::<MapInfo>("/map_out")
+        );
+        let (subscriber, publisher) = (subscriber.unwrap(), publisher.unwrap());
+        assert_eq!(
+            events.try_recv().unwrap(),
+            ConnectionEvent::Reconnecting { attempt: 1 }
+        );
+        // The subscribe issued while reconnecting is restored along with the client's state
+        assert_eq!(
+            events.try_recv().unwrap(),
+            ConnectionEvent::Connected { resubscribed: 1 }
+        );
+
+        bridge.next_op("subscribe", "/map").await;
+        bridge.next_op("advertise", "/map_out").await;
+        bridge.publish("/map", json!({"resolution": 0.5}));
+        let msg = tokio::time::timeout(TIMEOUT, subscriber.next())
+            .await
+            .unwrap();
+        assert_eq!(msg, MapInfo { resolution: 0.5 });
+        publisher
+            .publish(MapInfo { resolution: 0.1 })
+            .await
+            .unwrap();
+        bridge.next_op("publish", "/map_out").await;
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn pending_operations_are_limited() {
+        let bridge = MockBridge::new().await;
+        let client = ClientHandle::builder(bridge.url())
+            .pending_operation_timeout(Duration::from_millis(200))
+            .max_pending_operations(1)
+            .build()
+            .await
+            .unwrap();
+        let mut events = client.connection_events();
+
+        // The bridge never comes back
+        drop(bridge);
+        events.recv().await.unwrap();
+        let waiting = tokio::spawn({
+            let client = client.clone();
+            async move {
+                client
+                    .publish_any("/map", "nav_msgs/MapMetaData", json!({}))
+                    .await
+            }
+        });
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(matches!(
+            client.advertise::<MapInfo>("/map").await,
+            Err(RosLibRustError::TooManyPendingOperations)
+        ));
+        assert!(matches!(
+            waiting.await.unwrap(),
+            Err(RosLibRustError::Disconnected)
+        ));
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn failed_lazy_connection_fails_operation() {
+        let url = {
+            let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+            format!("ws://{}", listener.local_addr().unwrap())
+        };
+        let client = ClientHandle::builder(url)
+            .connect_timeout(Duration::from_millis(300))
+            .build_unconnected()
+            .unwrap();
+        let res = client.subscribe::<MapInfo>("/map").await;
+        assert!(matches!(res, Err(RosLibRustError::ConnectTimeout(_))));
+        // Never having connected, closing has nothing to wait for
+        client.close().await.unwrap();
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn cbor_compressed_subscription() {
+        use ciborium::value::Value as Cbor;
+        let mut bridge = MockBridge::new().await;
+        let client = ClientHandle::builder(bridge.url())
+            .timeout(TIMEOUT)
+            .default_compression(Compression::Cbor)
+            .build()
+            .await
+            .unwrap();
+
+        let subscriber = client.subscribe::<Echo>("/echo").await.unwrap();
+        let subscribe = tokio::time::timeout(TIMEOUT, bridge.next_op("subscribe", "/echo"))
+            .await
+            .unwrap();
+        assert_eq!(subscribe["compression"], "cbor");
+
+        bridge.publish_cbor(
+            "/echo",
+            Cbor::Map(vec![(Cbor::Text("data".into()), Cbor::Integer(7.into()))]),
+        );
+        let msg = tokio::time::timeout(TIMEOUT, subscriber.next())
+            .await
+            .unwrap();
+        assert_eq!(msg, Echo { data: 7 });
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn raw_and_typed_subscribers_share_a_topic() {
+        let mut bridge = MockBridge::new().await;
+        let client = ClientHandle::builder(bridge.url())
+            .timeout(TIMEOUT)
+            .build()
+            .await
+            .unwrap();
+
+        let typed = client.subscribe::<Echo>("/echo").await.unwrap();
+        bridge.next_op("subscribe", "/echo").await;
+        // The type is already known from the typed subscription, so rosapi isn't asked
+        let mut raw = client.subscribe_raw_json("/echo").await.unwrap();
+
+        // The json is passed on exactly as sent, whitespace and all
+        bridge.send_text(r#"{"op": "publish", "topic": "/echo", "msg": {"data" :  7 }}"#);
+        let json = tokio::time::timeout(TIMEOUT, raw.next())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(&json[..], br#"{"data" :  7 }"#);
+        let msg = tokio::time::timeout(TIMEOUT, typed.next()).await.unwrap();
+        assert_eq!(msg, Echo { data: 7 });
+
+        // Dropping the raw subscriber leaves the typed one subscribed
+        drop(raw);
+        bridge.publish("/echo", json!({"data": 8}));
+        let msg = tokio::time::timeout(TIMEOUT, typed.next()).await.unwrap();
+        assert_eq!(msg, Echo { data: 8 });
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn slow_subscribers_only_hold_up_other_topics_when_inline() {
+        for dispatch in [SubscriberDispatch::Spawned, SubscriberDispatch::Inline] {
+            let mut bridge = MockBridge::new().await;
+            let client = ClientHandle::builder(bridge.url())
+                .timeout(TIMEOUT)
+                .build()
+                .await
+                .unwrap();
+            let options = SubscribeOptions {
+                dispatch,
+                ..Default::default()
             };
-            if subscription.value_mut().handles.remove(&id).is_none() {
-                error!("Subscriber id {id:?} was not found in handles list for topic {topic_name:?} while unsubscribing");
-                return;
+            let slow = client
+                .subscribe_with_options::<SlowEcho>("/slow", options)
+                .await
+                .unwrap();
+            let fast = client.subscribe::<Echo>("/fast").await.unwrap();
+            bridge.next_op("subscribe", "/fast").await;
+
+            // Five messages take the slow subscriber at least half a second
+            for data in 0..5 {
+                bridge.publish("/slow", json!({ "data": data }));
             }
+            let start = tokio::time::Instant::now();
+            bridge.publish("/fast", json!({"data": 7}));
+            let msg = tokio::time::timeout(TIMEOUT, fast.next()).await.unwrap();
+            assert_eq!(msg, Echo { data: 7 });
+            let held_up = start.elapsed() >= Duration::from_millis(300);
+            assert_eq!(
+                held_up,
+                dispatch == SubscriberDispatch::Inline,
+                "{dispatch:?} took {:?}",
+                start.elapsed()
+            );
 
-            if subscription.handles.is_empty() {
-                // This is the last subscriber for that topic and we need to unsubscribe now
-                let mut stream = client.writer.write().await;
-                match stream.unsubscribe(&topic_name).await {
-                    Ok(_) => {}
-                    Err(e) => error!(
-                        "Failed to send unsubscribe while dropping subscriber: {:?}",
-                        e
-                    ),
-                }
+            // Either way the slow subscriber receives its messages in order
+            for data in 0..5 {
+                let msg = tokio::time::timeout(TIMEOUT, slow.next()).await.unwrap();
+                assert_eq!(msg, SlowEcho { data });
             }
-        });
-        Ok(())
+        }
     }
-}
 
-/// A client connection to the rosbridge_server that allows for publishing and subscribing to topics
-pub(crate) struct Client {
-    reader: RwLock<Reader>,
-    writer: RwLock<Writer>,
-    // Stores a record of the publishers we've handed out
-    publishers: DashMap<String, PublisherHandle>,
-    subscriptions: DashMap<String, Subscription>,
-    services: DashMap<String, ServiceCallback>,
-    // Contains any outstanding service calls we're waiting for a response on
-    // Map key will be a uniquely generated id for each call
-    service_calls: DashMap<String, tokio::sync::oneshot::Sender<Value>>,
-    opts: ClientHandleOptions,
-}
+    #[test_log::test(tokio::test)]
+    async fn relative_names_resolve_within_namespace() {
+        let mut bridge = MockBridge::new().await;
+        let client = ClientHandle::builder(bridge.url())
+            .timeout(TIMEOUT)
+            .namespace("robot1")
+            .build()
+            .await
+            .unwrap();
 
-impl Client {
-    // internal implementation of new
-    async fn new(opts: ClientHandleOptions) -> RosLibRustResult<Self> {
-        let (writer, reader) = stubborn_connect(&opts.url).await;
-        let client = Self {
-            reader: RwLock::new(reader),
-            writer: RwLock::new(writer),
-            publishers: DashMap::new(),
-            services: DashMap::new(),
-            subscriptions: DashMap::new(),
-            service_calls: DashMap::new(),
-            opts,
+        let _subscriber = client.subscribe::<Echo>("cmd_vel").await.unwrap();
+        bridge.next_op("subscribe", "/robot1/cmd_vel").await;
+        let publisher = client.advertise::<Echo>("arm/status").await.unwrap();
+        bridge.next_op("advertise", "/robot1/arm/status").await;
+        publisher.publish(Echo { data: 1 }).await.unwrap();
+        bridge.next_op("publish", "/robot1/arm/status").await;
+        // Global names are left alone
+        let _clock = client.subscribe::<Echo>("/clock").await.unwrap();
+        bridge.next_op("subscribe", "/clock").await;
+
+        let (response, _) = tokio::join!(
+            client.call_service::<Echo, Echo>("reset", Echo { data: 2 }),
+            async {
+                let call = bridge.next_service_call("/robot1/reset").await;
+                bridge.respond(&call, call["args"].clone());
+            }
+        );
+        assert_eq!(response.unwrap(), Echo { data: 2 });
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn invalid_names_fail_before_sending() {
+        let mut bridge = MockBridge::new().await;
+        let client = ClientHandle::builder(bridge.url())
+            .timeout(TIMEOUT)
+            .namespace("/robot1")
+            .build()
+            .await
+            .unwrap();
+        let reason = |result: RosLibRustResult<_>| match result {
+            Err(RosLibRustError::NameRuleViolation(invalid)) => invalid.reason,
+            Err(e) => panic!("Expected an invalid name, got {e}"),
+            Ok(_) => panic!("Expected an invalid name"),
         };
 
-        Ok(client)
+        assert_eq!(
+            reason(client.subscribe::<Echo>("/chatter ").await.map(|_| ())),
+            "names can't contain whitespace"
+        );
+        assert_eq!(
+            reason(client.advertise::<Echo>("/cmd-vel").await.map(|_| ())),
+            "names may only contain letters, digits, underscores, and /"
+        );
+        assert_eq!(
+            reason(
+                client
+                    .call_service::<Echo, Echo>("/2fast", Echo { data: 1 })
+                    .await
+                    .map(|_| ())
+            ),
+            "names must start with a letter, or with / or ~ followed by a letter or underscore"
+        );
+        assert_eq!(
+            reason(client.subscribe::<Echo>("~status").await.map(|_| ())),
+            "private names can only be resolved within a node's name"
+        );
+        // Nothing reached rosbridge
+        assert!(
+            tokio::time::timeout(Duration::from_millis(100), bridge.next_message())
+                .await
+                .is_err()
+        );
+
+        // Without a namespace relative names are left for rosbridge to resolve
+        let mut bridge = MockBridge::new().await;
+        let client = ClientHandle::builder(bridge.url())
+            .timeout(TIMEOUT)
+            .build()
+            .await
+            .unwrap();
+        let _subscriber = client.subscribe::<Echo>("chatter").await.unwrap();
+        bridge.next_op("subscribe", "chatter").await;
+
+        // Nor can the namespace itself be invalid
+        let invalid = ClientHandle::builder(bridge.url())
+            .namespace("robot 1")
+            .build_unconnected();
+        assert_eq!(
+            reason(invalid.map(|_| ())),
+            "names can't contain whitespace"
+        );
     }
 
-    async fn handle_message(&self, msg: Message) -> RosLibRustResult<()> {
-        match msg {
-            Message::Text(text) => {
-                debug!("got message: {}", text);
-                // TODO better error handling here serde_json::Error not send
-                let parsed: serde_json::Value = serde_json::from_str(text.as_str()).unwrap();
-                let parsed_object = parsed
-                    .as_object()
-                    .expect("Recieved non-object json response");
-                let op = parsed_object
-                    .get("op")
-                    .expect("Op field not present on returned object.")
-                    .as_str()
-                    .expect("Op field was not of string type.");
-                let op = comm::Ops::from_str(op)?;
-                match op {
-                    comm::Ops::Publish => {
-                        trace!("handling publish for {:?}", &parsed);
-                        self.handle_publish(parsed).await;
-                    }
-                    comm::Ops::ServiceResponse => {
-                        trace!("handling service response for {:?}", &parsed);
-                        self.handle_response(parsed).await;
-                    }
-                    comm::Ops::CallService => {
-                        trace!("handling call_service for {:?}", &parsed);
-                        self.handle_service(parsed).await;
-                    }
-                    _ => {
-                        warn!("Unhandled op type {}", op)
-                    }
-                }
-            }
-            Message::Close(close) => {
-                // TODO how should we respond to this?
-                // How do we represent connection status via our API well?
-                panic!("Close requested from server: {:?}", close);
-            }
-            Message::Ping(ping) => {
-                debug!("Ping received: {:?}", ping);
+    #[test_log::test(tokio::test)]
+    async fn subscribing_while_the_last_subscriber_drops_stays_subscribed() {
+        let mut bridge = MockBridge::new().await;
+        let client = ClientHandle::builder(bridge.url())
+            .timeout(TIMEOUT)
+            .build()
+            .await
+            .unwrap();
+
+        let mut subscribers = vec![];
+        for round in 0..10 {
+            let topic = format!("/echo{round}");
+            let first = client.subscribe::<Echo>(&topic).await.unwrap();
+            bridge.next_op("subscribe", &topic).await;
+            drop(first);
+            subscribers.push(client.subscribe::<Echo>(&topic).await.unwrap());
+
+            // The first's unsubscribe, if it's sent at all, must come before the second's subscribe
+            let mut ops = vec![];
+            while let Ok(op) =
+                tokio::time::timeout(Duration::from_millis(100), bridge.next_message()).await
+            {
+                assert_eq!(op["topic"], topic.as_str(), "{op}");
+                ops.push(op["op"].as_str().unwrap().to_string());
             }
-            Message::Pong(pong) => {
-                debug!("Pong received {:?}", pong);
+            assert_eq!(ops.last().map(String::as_str), Some("subscribe"), "{ops:?}");
+        }
+
+        // Topics whose last subscriber was dropped aren't resubscribed to after reconnecting
+        let dropped = client.subscribe::<Echo>("/dropped").await.unwrap();
+        bridge.next_op("subscribe", "/dropped").await;
+        drop(dropped);
+        bridge.next_op("unsubscribe", "/dropped").await;
+        bridge.disconnect();
+        let mut resubscribed = vec![];
+        while resubscribed.len() < 10 {
+            let op = tokio::time::timeout(TIMEOUT, bridge.next_message())
+                .await
+                .unwrap();
+            assert_ne!(op["topic"], "/dropped", "{op}");
+            if op["op"] == "subscribe" {
+                resubscribed.push(op["topic"].as_str().unwrap().to_string());
             }
-            _ => {
-                panic!("Non-text response received");
+        }
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn subscribe_many_merges_topics() {
+        let mut bridge = MockBridge::new().await;
+        let client = ClientHandle::builder(bridge.url())
+            .timeout(TIMEOUT)
+            .build()
+            .await
+            .unwrap();
+
+        let mut merged = client
+            .subscribe_many::<Echo>(&["/a/echo", "/b/echo", "/a/echo"])
+            .await
+            .unwrap();
+        bridge.next_op("subscribe", "/a/echo").await;
+        bridge.next_op("subscribe", "/b/echo").await;
+
+        bridge.publish("/b/echo", json!({"data": 1}));
+        bridge.publish("/a/echo", json!({"data": 2}));
+        let mut received = vec![];
+        for _ in 0..2 {
+            received.push(
+                tokio::time::timeout(TIMEOUT, merged.next())
+                    .await
+                    .unwrap()
+                    .unwrap(),
+            );
+        }
+        received.sort_by_key(|(_, msg)| msg.data);
+        assert_eq!(
+            received,
+            vec![
+                ("/b/echo".to_string(), Echo { data: 1 }),
+                ("/a/echo".to_string(), Echo { data: 2 })
+            ]
+        );
+        // Listed twice but only subscribed once, so each message is received once
+        assert!(
+            tokio::time::timeout(Duration::from_millis(100), merged.next())
+                .await
+                .is_err()
+        );
+
+        // Both topics are resubscribed after reconnecting
+        bridge.disconnect();
+        let mut resubscribed = vec![];
+        while resubscribed.len() < 2 {
+            let op = tokio::time::timeout(TIMEOUT, bridge.next_message())
+                .await
+                .unwrap();
+            if op["op"] == "subscribe" {
+                resubscribed.push(op["topic"].as_str().unwrap().to_string());
             }
         }
+        resubscribed.sort();
+        assert_eq!(resubscribed, ["/a/echo", "/b/echo"]);
+        bridge.publish("/a/echo", json!({"data": 3}));
+        let (topic, msg) = tokio::time::timeout(TIMEOUT, merged.next())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!((topic.as_str(), msg.data), ("/a/echo", 3));
 
-        Ok(())
+        // Dropping the stream unsubscribes every topic
+        drop(merged);
+        let mut unsubscribed = vec![];
+        while unsubscribed.len() < 2 {
+            let op = tokio::time::timeout(TIMEOUT, bridge.next_message())
+                .await
+                .unwrap();
+            if op["op"] == "unsubscribe" {
+                unsubscribed.push(op["topic"].as_str().unwrap().to_string());
+            }
+        }
+        unsubscribed.sort();
+        assert_eq!(unsubscribed, ["/a/echo", "/b/echo"]);
     }
 
-    async fn handle_response(&self, data: Value) {
-        // TODO lots of error handling!
-        let id = data.get("id").unwrap().as_str().unwrap();
-        let (_id, call) = self.service_calls.remove(id).unwrap();
-        let res = data.get("values").unwrap();
-        call.send(res.clone()).unwrap();
+    #[test_log::test]
+    fn reconnect_policy_delays() {
+        let fixed = ReconnectPolicy::default();
+        assert_eq!(fixed.delay(1), Duration::from_millis(200));
+        assert_eq!(fixed.delay(100), Duration::from_millis(200));
+
+        let backoff = ReconnectPolicy::ExponentialBackoff {
+            initial: Duration::from_millis(100),
+            max: Duration::from_secs(1),
+        };
+        let delays: Vec<_> = (1..=6).map(|failures| backoff.delay(failures)).collect();
+        assert_eq!(
+            delays,
+            [100, 200, 400, 800, 1000, 1000].map(Duration::from_millis)
+        );
+        assert_eq!(backoff.delay(u32::MAX), Duration::from_secs(1));
     }
 
-    /// Response handler for receiving a service call looks up if we have a service
-    /// registered for the incoming topic and if so dispatches to the callback
-    async fn handle_service(&self, data: Value) {
-        // Unwrap is okay, field is fully required and strictly type
-        let topic = data.get("service").unwrap().as_str().unwrap();
-        // Unwrap is okay, field is strictly typed to string
-        let id = data.get("id").map(|id| id.as_str().unwrap().to_string());
+    #[test_log::test]
+    fn backoff_policy_delays() {
+        use rand::SeedableRng;
+        let backoff = Backoff {
+            initial: Duration::from_millis(100),
+            multiplier: 3.0,
+            max: Duration::from_secs(2),
+            jitter: false,
+            max_attempts: Some(5),
+        };
+        let policy = ReconnectPolicy::Backoff(backoff.clone());
+        let mut rng = rand::rngs::StdRng::seed_from_u64(42);
+        let delays: Vec<_> = (1..=5)
+            .map(|failures| policy.jittered_delay(failures, &mut rng))
+            .collect();
+        assert_eq!(
+            delays,
+            [100, 300, 900, 2000, 2000].map(Duration::from_millis)
+        );
+        assert_eq!(policy.delay(u32::MAX), Duration::from_secs(2));
+        assert_eq!(policy.max_attempts(), Some(5));
+        assert_eq!(ReconnectPolicy::default().max_attempts(), None);
 
-        // Lookup if we have a service for the message
-        let callback = self.services.get(topic);
-        let callback = match callback {
-            Some(callback) => callback,
-            _ => panic!("Received call_service for unadvertised service!"),
+        // Full jitter stays within the backed off delay, and is reproducible from the seed
+        let jittered = ReconnectPolicy::Backoff(Backoff {
+            jitter: true,
+            ..backoff
+        });
+        let sample = |seed| {
+            let mut rng = rand::rngs::StdRng::seed_from_u64(seed);
+            (1..=20)
+                .map(|failures| jittered.jittered_delay(failures, &mut rng))
+                .collect::<Vec<_>>()
         };
-        // TODO likely bugs here remove this unwrap. Unclear what we are expected to get for empty service
-        let request = data.get("args").unwrap().to_string();
-        let mut writer = self.writer.write().await;
-        match callback(&request) {
-            Ok(res) => {
-                // TODO unwrap here is probably bad... Failure to write means disconnected?
-                writer.service_response(topic, id, true, res).await.unwrap();
-            }
-            Err(e) => {
-                error!("A service callback on topic {:?} failed with {:?} sending response false in service_response", data.get("service"), e);
-                writer
-                    .service_response(topic, id, false, serde_json::json!(format!("{e}")))
-                    .await
-                    .unwrap();
+        let delays = sample(42);
+        assert_eq!(delays, sample(42));
+        assert_ne!(delays, sample(43));
+        for (failures, delay) in (1..).zip(&delays) {
+            assert!(*delay <= jittered.delay(failures), "{failures}: {delay:?}");
+        }
+        assert!(delays[4..]
+            .iter()
+            .any(|delay| *delay < Duration::from_secs(2)));
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn reconnect_gives_up_after_max_attempts() {
+        let bridge = MockBridge::new().await;
+        let client = ClientHandle::new(bridge.url()).await.unwrap();
+        let mut events = client.connection_events();
+        let mut state = client.connection_state();
+        assert_eq!(*state.borrow(), ConnectionState::Connected);
+
+        // Replaced after connecting, the default policy would retry forever
+        client.set_reconnect_policy(ReconnectPolicy::Backoff(Backoff {
+            initial: Duration::from_millis(10),
+            max: Duration::from_millis(50),
+            jitter: false,
+            max_attempts: Some(3),
+            ..Default::default()
+        }));
+        drop(bridge);
+
+        let mut seen = vec![];
+        tokio::time::timeout(TIMEOUT, async {
+            while !matches!(*state.borrow(), ConnectionState::Failed { .. }) {
+                state.changed().await.unwrap();
+                seen.push(*state.borrow());
             }
-        };
+        })
+        .await
+        .unwrap();
+        assert_eq!(seen.last(), Some(&ConnectionState::Failed { attempts: 3 }));
+        assert!(seen.contains(&ConnectionState::Connecting {
+            attempt: 3,
+            next_delay: Some(Duration::from_millis(20))
+        }));
+
+        let mut received = vec![];
+        while let Ok(event) = events.try_recv() {
+            received.push(event);
+        }
+        assert!(matches!(received[0], ConnectionEvent::Disconnected { .. }));
+        assert_eq!(
+            received[1..],
+            [
+                ConnectionEvent::Reconnecting { attempt: 1 },
+                ConnectionEvent::Reconnecting { attempt: 2 },
+                ConnectionEvent::Reconnecting { attempt: 3 },
+                ConnectionEvent::Failed { attempts: 3 },
+            ]
+        );
 
-        // Now we need to send the service_response back
+        // Nothing will reconnect, so operations fail straight away
+        let subscribed = tokio::time::timeout(TIMEOUT, client.subscribe::<Echo>("/echo"))
+            .await
+            .unwrap();
+        assert!(matches!(subscribed, Err(RosLibRustError::Disconnected)));
     }
 
-    async fn spin_once(&self) -> RosLibRustResult<()> {
-        let read = {
-            let mut stream = self.reader.write().await;
-            match stream.next().await {
-                Some(Ok(msg)) => msg,
-                Some(Err(e)) => {
-                    return Err(e.into());
+    #[test_log::test(tokio::test)]
+    async fn server_close_codes_are_reported() {
+        let mut bridge = MockBridge::new().await;
+        let client = ClientHandle::builder(bridge.url())
+            .reconnect(ReconnectPolicy::Fixed(Duration::from_millis(10)))
+            .build()
+            .await
+            .unwrap();
+        let mut events = client.connection_events();
+        assert_eq!(client.last_close(), None);
+
+        let closes = [
+            (1001, "server shutting down"),
+            (1009, "message too big"),
+            (1013, "idle timeout, try again later"),
+        ];
+        for (code, reason) in closes {
+            // A call waiting on a response fails with the reason the connection was closed
+            let (response, _) = tokio::join!(
+                tokio::time::timeout(
+                    TIMEOUT,
+                    client.call_service::<Echo, Echo>("/echo", Echo { data: 1 })
+                ),
+                async {
+                    bridge.next_service_call("/echo").await;
+                    bridge.close(code, reason);
                 }
-                None => {
-                    return Err(RosLibRustError::Unexpected(anyhow!(
-                        "Wtf does none mean here?"
-                    )));
+            );
+            let err = response.unwrap().unwrap_err();
+            let is_expected = match code {
+                1009 => matches!(err, RosLibRustError::MessageTooBigForServer { .. }),
+                1013 => matches!(err, RosLibRustError::TryAgainLater { .. }),
+                _ => matches!(err, RosLibRustError::ConnectionClosed { code: 1001, .. }),
+            };
+            assert!(is_expected, "{code}: {err:?}");
+            assert_eq!(err.close_code(), Some(code));
+            assert!(err.to_string().contains(reason), "{err}");
+
+            let event = tokio::time::timeout(TIMEOUT, events.recv())
+                .await
+                .unwrap()
+                .unwrap();
+            let ConnectionEvent::Disconnected {
+                reason: disconnected,
+            } = event
+            else {
+                panic!("Expected a disconnect, got {event:?}");
+            };
+            assert!(disconnected.contains(reason), "{disconnected}");
+            assert_eq!(
+                client.last_close(),
+                Some(ServerClose {
+                    code,
+                    reason: reason.to_owned()
+                })
+            );
+
+            // None of these are fatal, so the client reconnects
+            loop {
+                let event = tokio::time::timeout(TIMEOUT, events.recv())
+                    .await
+                    .unwrap()
+                    .unwrap();
+                if let ConnectionEvent::Connected { .. } = event {
+                    break;
                 }
             }
-        };
-        debug!("Got message: {:?}", read);
-        self.handle_message(read).await
+        }
+        assert!(RetryPolicy::is_transient(&RosLibRustError::TryAgainLater {
+            reason: String::new()
+        }));
     }
 
-    /// Response handler for received publish messages
-    /// Converts the return message to the subscribed type and calls any callbacks
-    /// Panics if publish is received for unexpected topic
-    async fn handle_publish(&self, data: Value) {
-        // TODO lots of error handling!
-        let callbacks = self
-            .subscriptions
-            .get(data.get("topic").unwrap().as_str().unwrap());
-        let callbacks = match callbacks {
-            Some(callbacks) => callbacks,
-            _ => panic!("Received publish message for unsubscribed topic!"), // TODO probably shouldn't be a panic?
-        };
-        for callback in callbacks.handles.values() {
-            callback(
-                // TODO possible bug here if "msg" isn't defined remove this unwrap
-                serde_json::to_string(data.get("msg").unwrap())
-                    .unwrap()
-                    .as_str(),
-            )
+    #[test_log::test(tokio::test)]
+    async fn fatal_close_codes_are_not_retried() {
+        let bridge = MockBridge::new().await;
+        let client = ClientHandle::builder(bridge.url())
+            .reconnect(ReconnectPolicy::Fixed(Duration::from_millis(10)))
+            .build()
+            .await
+            .unwrap();
+        let mut events = client.connection_events();
+        let mut state = client.connection_state();
+
+        bridge.close(1008, "authentication failed");
+        tokio::time::timeout(TIMEOUT, async {
+            while !matches!(*state.borrow(), ConnectionState::Failed { .. }) {
+                state.changed().await.unwrap();
+            }
+        })
+        .await
+        .unwrap();
+        assert_eq!(*state.borrow(), ConnectionState::Failed { attempts: 0 });
+
+        let mut received = vec![];
+        while let Ok(event) = events.try_recv() {
+            received.push(event);
+        }
+        assert_eq!(
+            received,
+            [
+                ConnectionEvent::Disconnected {
+                    reason: RosLibRustError::PolicyViolation {
+                        reason: "authentication failed".to_owned()
+                    }
+                    .to_string()
+                },
+                ConnectionEvent::Failed { attempts: 0 },
+            ]
+        );
+
+        // Operations fail with why the client gave up
+        let subscribed = tokio::time::timeout(TIMEOUT, client.subscribe::<Echo>("/echo"))
+            .await
+            .unwrap();
+        match subscribed {
+            Err(RosLibRustError::PolicyViolation { reason }) => {
+                assert_eq!(reason, "authentication failed")
+            }
+            other => panic!("Expected a policy violation, got {:?}", other.err()),
         }
+        assert!(!RetryPolicy::is_transient(
+            &RosLibRustError::PolicyViolation {
+                reason: String::new()
+            }
+        ));
     }
 
-    async fn reconnect(&mut self) -> RosLibRustResult<()> {
-        // Reconnect stream
-        let (writer, reader) = stubborn_connect(&self.opts.url).await;
-        self.reader = RwLock::new(reader);
-        self.writer = RwLock::new(writer);
+    #[test_log::test(tokio::test)]
+    async fn ros_version_controls_wire_format() {
+        use roslibrust_codegen::integral_types::Time;
+        let cases = [
+            (
+                RosVersion::ROS1,
+                "test_msgs/Stamped",
+                json!({"secs": 1, "nsecs": 2}),
+            ),
+            (
+                RosVersion::ROS2,
+                "test_msgs/msg/Stamped",
+                json!({"sec": 1, "nanosec": 2}),
+            ),
+        ];
+        for (version, expected_type, expected_stamp) in cases {
+            let mut bridge = MockBridge::new().await;
+            let client = ClientHandle::builder(bridge.url())
+                .ros_version(version)
+                .build()
+                .await
+                .unwrap();
 
-        // TODO re-advertise!
-        // Resend rosbridge our subscription requests to re-establish inflight subscriptions
-        // Clone here is dumb, but required due to async
-        let mut subs: Vec<(String, String)> = vec![];
-        {
-            for sub in self.subscriptions.iter() {
-                subs.push((sub.key().clone(), sub.value().topic_type.clone()))
-            }
+            let subscriber = client.subscribe::<Stamped>("/stamped_in").await.unwrap();
+            let subscribe = bridge.next_op("subscribe", "/stamped_in").await;
+            assert_eq!(subscribe["type"], expected_type);
+
+            let publisher = client.advertise::<Stamped>("/stamped_out").await.unwrap();
+            let advertise = bridge.next_op("advertise", "/stamped_out").await;
+            assert_eq!(advertise["type"], expected_type);
+            let msg = Stamped {
+                stamp: Time { secs: 1, nsecs: 2 },
+            };
+            publisher.publish(msg.clone()).await.unwrap();
+            let publish = bridge.next_op("publish", "/stamped_out").await;
+            assert_eq!(publish["type"], expected_type);
+            assert_eq!(publish["msg"]["stamp"], expected_stamp);
+
+            // Incoming messages in either form are accepted
+            bridge.publish("/stamped_in", json!({ "stamp": expected_stamp }));
+            let received = tokio::time::timeout(TIMEOUT, subscriber.next())
+                .await
+                .unwrap();
+            assert_eq!(received, msg);
+
+            // The conversion still applies after reconnecting
+            bridge.disconnect();
+            let subscribe =
+                tokio::time::timeout(TIMEOUT, bridge.next_op("subscribe", "/stamped_in"))
+                    .await
+                    .unwrap();
+            assert_eq!(subscribe["type"], expected_type);
+            let advertise =
+                tokio::time::timeout(TIMEOUT, bridge.next_op("advertise", "/stamped_out"))
+                    .await
+                    .unwrap();
+            assert_eq!(advertise["type"], expected_type);
         }
-        let mut stream = self.writer.write().await;
-        for (topic, topic_type) in &subs {
-            stream.subscribe(topic, topic_type).await?;
+    }
+
+    // Subscribes to /echo with a type check, answering rosapi's topic_type with `reported_type`
+    async fn subscribe_checked(
+        client: &ClientHandle,
+        bridge: &mut MockBridge,
+        missing_topic: MissingTopicPolicy,
+        reported_type: &str,
+    ) -> RosLibRustResult<Subscriber<Echo>> {
+        let options = SubscribeOptions {
+            type_check: true,
+            missing_topic,
+            ..Default::default()
+        };
+        let (subscribed, _) = tokio::join!(
+            client.subscribe_with_options::<Echo>("/echo", options),
+            async {
+                let call = bridge.next_service_call("/rosapi/topic_type").await;
+                assert_eq!(call["args"]["topic"], "/echo");
+                bridge.respond(&call, json!({ "type": reported_type }));
+            }
+        );
+        subscribed
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn type_check_subscribes_when_types_match() {
+        let mut bridge = MockBridge::new().await;
+        let client = ClientHandle::new(bridge.url()).await.unwrap();
+
+        let subscriber = subscribe_checked(
+            &client,
+            &mut bridge,
+            MissingTopicPolicy::Fail,
+            "test_msgs/Echo",
+        )
+        .await
+        .unwrap();
+        bridge.next_op("subscribe", "/echo").await;
+        bridge.publish("/echo", json!({"data": 5}));
+        let received = tokio::time::timeout(TIMEOUT, subscriber.next())
+            .await
+            .unwrap();
+        assert_eq!(received, Echo { data: 5 });
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn type_check_rejects_mismatched_type() {
+        let mut bridge = MockBridge::new().await;
+        let client = ClientHandle::new(bridge.url()).await.unwrap();
+
+        let subscribed = subscribe_checked(
+            &client,
+            &mut bridge,
+            MissingTopicPolicy::Proceed,
+            "std_msgs/String",
+        )
+        .await;
+        let error = subscribed.err().unwrap();
+        assert_eq!(
+            error.to_string(),
+            "Topic /echo has type std_msgs/String, it can't be subscribed to with type test_msgs/Echo"
+        );
+        assert!(matches!(
+            error,
+            RosLibRustError::TypeMismatch { ref actual, ref requested, .. }
+                if actual == "std_msgs/String" && requested == "test_msgs/Echo"
+        ));
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn type_check_of_unpublished_topic_follows_policy() {
+        let mut bridge = MockBridge::new().await;
+        let client = ClientHandle::new(bridge.url()).await.unwrap();
+
+        let subscribed =
+            subscribe_checked(&client, &mut bridge, MissingTopicPolicy::Fail, "").await;
+        assert!(
+            matches!(&subscribed, Err(RosLibRustError::TopicNotFound(topic)) if topic == "/echo"),
+            "{:?}",
+            subscribed.err()
+        );
+
+        let _subscriber = subscribe_checked(&client, &mut bridge, MissingTopicPolicy::Proceed, "")
+            .await
+            .unwrap();
+        let subscribe = bridge.next_op("subscribe", "/echo").await;
+        assert_eq!(subscribe["type"], "test_msgs/Echo");
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn type_check_reports_missing_rosapi_apart_from_its_failures() {
+        async fn subscribe_failing_with(
+            client: &ClientHandle,
+            bridge: &mut MockBridge,
+            failure: &str,
+        ) -> RosLibRustError {
+            let options = SubscribeOptions {
+                type_check: true,
+                ..Default::default()
+            };
+            let (subscribed, _) = tokio::join!(
+                client.subscribe_with_options::<Echo>("/echo", options),
+                async {
+                    let call = bridge.next_service_call("/rosapi/topic_type").await;
+                    bridge.fail(&call, failure);
+                }
+            );
+            subscribed.err().unwrap()
         }
+        let mut bridge = MockBridge::new().await;
+        let client = ClientHandle::new(bridge.url()).await.unwrap();
 
-        Ok(())
+        let missing = "Service /rosapi/topic_type does not exist";
+        let error = subscribe_failing_with(&client, &mut bridge, missing).await;
+        assert!(
+            matches!(&error, RosLibRustError::RosapiUnavailable(message) if message == missing),
+            "{error:?}"
+        );
+
+        let failed = "TypeError: topic_type() got an unexpected argument";
+        let error = subscribe_failing_with(&client, &mut bridge, failed).await;
+        assert!(
+            matches!(&error, RosLibRustError::ServerError(message) if message == failed),
+            "{error:?}"
+        );
     }
-}
 
-/// Wraps spin in retry logic to handle reconnection attempts automagically
-async fn stubborn_spin(
-    client: std::sync::Weak<RwLock<Client>>,
-    is_disconnected: Arc<AtomicBool>,
-) -> RosLibRustResult<()> {
-    debug!("Starting stubborn_spin");
-    while let Some(client) = client.upgrade() {
-        const SPIN_DURATION: Duration = Duration::from_millis(10);
+    #[test_log::test(tokio::test)]
+    async fn type_check_compares_definitions() {
+        #[derive(Deserialize, Serialize, Debug, Default, Clone, PartialEq)]
+        struct Labelled {
+            data: u32,
+            label: String,
+        }
+        impl RosMessageType for Labelled {
+            const ROS_TYPE_NAME: &'static str = "test_msgs/Labelled";
+            const DEFINITION: &'static str =
+                "# A labelled value\nuint8 MAX_LABEL=16\nuint32 data\nstring label # shown to users";
+        }
 
-        match tokio::time::timeout(SPIN_DURATION, client.read().await.spin_once()).await {
-            Ok(Ok(())) => {}
-            Ok(Err(err)) => {
-                is_disconnected.store(true, Ordering::Relaxed);
-                warn!("Spin failed with error: {err}, attempting to reconnect");
-                client.write().await.reconnect().await?;
-                is_disconnected.store(false, Ordering::Relaxed);
-            }
-            Err(_) => {
-                // Time out occurred, so we'll check on our weak pointer again
+        let mut bridge = MockBridge::new().await;
+        let client = ClientHandle::new(bridge.url()).await.unwrap();
+        let options = SubscribeOptions {
+            type_check: true,
+            check_definition: true,
+            ..Default::default()
+        };
+
+        for (fieldnames, matches) in [(json!(["data", "label"]), true), (json!(["data"]), false)] {
+            let (subscribed, _) = tokio::join!(
+                client.subscribe_with_options::<Labelled>("/labelled", options.clone()),
+                async {
+                    let call = bridge.next_service_call("/rosapi/topic_type").await;
+                    bridge.respond(&call, json!({"type": "test_msgs/Labelled"}));
+                    let call = bridge.next_service_call("/rosapi/message_details").await;
+                    assert_eq!(call["args"]["type"], "test_msgs/Labelled");
+                    bridge.respond(
+                        &call,
+                        json!({"typedefs": [{
+                            "type": "test_msgs/Labelled",
+                            "fieldnames": fieldnames,
+                            "fieldtypes": ["uint32", "string"],
+                        }]}),
+                    );
+                }
+            );
+            if matches {
+                subscribed.unwrap();
+            } else {
+                assert!(
+                    matches!(&subscribed, Err(RosLibRustError::DefinitionMismatch { topic_type, .. }) if topic_type == "test_msgs/Labelled"),
+                    "{:?}",
+                    subscribed.err()
+                );
             }
         }
     }
 
-    Ok(())
-}
+    #[test_log::test(tokio::test)]
+    async fn service_calls_are_retried_until_they_succeed() {
+        let mut bridge = MockBridge::new().await;
+        let client = ClientHandle::new(bridge.url()).await.unwrap();
+        let options = ServiceCallOptions {
+            retry: Some(RetryPolicy {
+                backoff: ReconnectPolicy::Fixed(Duration::from_millis(10)),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
 
-// Implementation of timeout that is a no-op if timeout is 0 or un-configured
-// Only works on functions that already return our result type
-// This might not be needed but reading tokio::timeout docs I couldn't confirm this
-async fn timeout<F, T>(timeout: Option<Duration>, future: F) -> RosLibRustResult<T>
-where
-    F: futures::Future<Output = RosLibRustResult<T>>,
-{
-    if let Some(t) = timeout {
-        tokio::time::timeout(t, future).await?
-    } else {
-        future.await
-    }
-}
+        let (response, ids) = tokio::join!(
+            client.call_service_with_options::<Echo, Echo>(
+                "/echo",
+                Echo { data: 3 },
+                options.clone()
+            ),
+            async {
+                let mut ids = vec![];
+                for _ in 0..2 {
+                    let call = bridge.next_service_call("/echo").await;
+                    bridge.fail(&call, "Service /echo does not exist");
+                    ids.push(call["id"].clone());
+                }
+                let call = bridge.next_service_call("/echo").await;
+                assert_eq!(call["args"], json!({"data": 3}));
+                bridge.respond(&call, json!({"data": 4}));
+                ids.push(call["id"].clone());
+                ids
+            }
+        );
+        assert_eq!(response.unwrap(), Echo { data: 4 });
+        assert!(ids[0] != ids[1] && ids[1] != ids[2], "{ids:?}");
 
-// Connects to websocket at specified URL, retries indefinitely
-async fn stubborn_connect(url: &str) -> (Writer, Reader) {
-    loop {
-        match connect(url).await {
-            Err(e) => {
-                warn!("Failed to reconnect: {:?}", e);
-                // TODO configurable rate?
-                tokio::time::sleep(tokio::time::Duration::from_millis(200)).await;
-                continue;
+        // The service's own errors would only happen again
+        let (response, _) = tokio::join!(
+            client.call_service_with_options::<Echo, Echo>(
+                "/echo",
+                Echo { data: 3 },
+                options.clone()
+            ),
+            async {
+                let call = bridge.next_service_call("/echo").await;
+                bridge.fail(&call, "data must be even");
             }
-            Ok(stream) => {
-                let (writer, reader) = stream.split();
-                return (writer, reader);
+        );
+        assert!(
+            matches!(&response, Err(RosLibRustError::ServerError(message)) if message == "data must be even"),
+            "{response:?}"
+        );
+        // Only rosbridge's own message for a missing service is retried
+        let (response, _) = tokio::join!(
+            client.call_service_with_options::<Echo, Echo>("/echo", Echo { data: 3 }, options),
+            async {
+                let call = bridge.next_service_call("/echo").await;
+                bridge.fail(&call, "Calibration file does not exist");
             }
-        }
+        );
+        assert!(
+            matches!(&response, Err(RosLibRustError::ServerError(message)) if message == "Calibration file does not exist"),
+            "{response:?}"
+        );
+        // Every attempt counts as a call
+        let metrics = client.metrics();
+        assert_eq!(metrics.service_calls, 5);
+        assert_eq!(metrics.service_call_failures, 4);
     }
-}
 
-// Basic connection attempt and error wrapping
-async fn connect(url: &str) -> RosLibRustResult<Socket> {
-    let attempt = tokio_tungstenite::connect_async(url).await;
-    match attempt {
-        Ok((stream, _response)) => Ok(stream),
-        Err(e) => Err(e.into()),
+    #[test_log::test(tokio::test)]
+    async fn service_call_deadline_covers_retries() {
+        let mut bridge = MockBridge::new().await;
+        let client = ClientHandle::new(bridge.url()).await.unwrap();
+        let deadline = Duration::from_millis(200);
+        let options = ServiceCallOptions {
+            deadline: Some(deadline),
+            retry: Some(RetryPolicy {
+                max_attempts: 10,
+                backoff: ReconnectPolicy::Fixed(Duration::from_secs(5)),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let started = tokio::time::Instant::now();
+        let (response, _) = tokio::join!(
+            client.call_service_with_options::<Echo, Echo>("/echo", Echo { data: 3 }, options),
+            async {
+                let call = bridge.next_service_call("/echo").await;
+                bridge.fail(&call, "Service /echo does not exist");
+            }
+        );
+        // Gives up while backing off rather than waiting to retry
+        assert!(started.elapsed() < Duration::from_secs(2));
+        assert!(
+            matches!(&response, Err(RosLibRustError::ServiceCallTimeout { timeout, .. }) if *timeout == deadline),
+            "{response:?}"
+        );
     }
 }
@@ -1,14 +1,12 @@
-use std::{fmt::Display, str::FromStr, string::ToString};
+use std::{borrow::Cow, fmt::Display, ops::Range, str::FromStr, string::ToString};
 
 use anyhow::bail;
-use async_trait::async_trait;
-use futures_util::SinkExt;
+use futures::FutureExt;
 use log::debug;
 use serde_json::json;
-use tokio_tungstenite::tungstenite::Message;
 
-use crate::{rosbridge::RosLibRustResult, rosbridge::Writer};
-use roslibrust_codegen::RosMessageType;
+use crate::{rosbridge::transport::Written, rosbridge::Compression, rosbridge::Writer};
+use roslibrust_codegen::utils::RosVersion;
 
 /// Describes all documented rosbridge server operations
 pub(crate) enum Ops {
@@ -86,113 +84,130 @@ impl FromStr for Ops {
 }
 
 /// Describes the low level comm capabilities of talking to a rosbridge server
-/// This trait exists because we haven't wrapped Writer in our own type
-/// So we're defining this trait on a foregin type, since we didn't end up
-/// using this trait for mocking. I'm inclined to replace it, and move the
-/// impls directly into some wrapper around [Writer]
-#[async_trait]
+/// Implemented for [Writer], so the operations are the same whichever transport is in use.
+/// Each operation is queued when called, so operations are sent in the order they are called,
+/// and the returned future resolves once it has been written.
 pub(crate) trait RosBridgeComm {
-    async fn subscribe(&mut self, topic: &str, msg_type: &str) -> RosLibRustResult<()>;
-    async fn unsubscribe(&mut self, topic: &str) -> RosLibRustResult<()>;
-    async fn publish<T: RosMessageType>(&mut self, topic: &str, msg: T) -> RosLibRustResult<()>;
-    async fn advertise<T: RosMessageType>(&mut self, topic: &str) -> RosLibRustResult<()>;
-    async fn call_service<Req: RosMessageType>(
-        &mut self,
+    fn subscribe(&self, topic: &str, msg_type: &str, compression: Compression) -> Written;
+    fn unsubscribe(&self, topic: &str) -> Written;
+    /// Returns the size of the publish operation sent, in bytes
+    fn publish_value(&self, topic: &str, msg_type: &str, msg: &serde_json::Value)
+        -> Written<usize>;
+    fn advertise(&self, topic: &str, msg_type: &str, latch: bool) -> Written;
+    /// With [Compression::Cbor] rosbridge is asked to send the response as a binary CBOR frame
+    fn call_service(
+        &self,
         service: &str,
         id: &str,
-        req: Req,
-    ) -> RosLibRustResult<()>;
-    async fn unadvertise(&mut self, topic: &str) -> RosLibRustResult<()>;
-    async fn advertise_service(&mut self, topic: &str, srv_type: &str) -> RosLibRustResult<()>;
-    async fn unadvertise_service(&mut self, topic: &str) -> RosLibRustResult<()>;
-    async fn service_response(
-        &mut self,
+        args: serde_json::Value,
+        compression: Compression,
+    ) -> Written;
+    fn unadvertise(&self, topic: &str) -> Written;
+    fn advertise_service(&self, topic: &str, srv_type: &str) -> Written;
+    fn unadvertise_service(&self, topic: &str) -> Written;
+    fn service_response(
+        &self,
         topic: &str,
         id: Option<String>,
         is_success: bool,
         response: serde_json::Value,
-    ) -> RosLibRustResult<()>;
+    ) -> Written;
 }
 
-#[async_trait]
 impl RosBridgeComm for Writer {
-    async fn subscribe(&mut self, topic: &str, msg_type: &str) -> RosLibRustResult<()> {
-        let msg = json!(
+    fn subscribe(&self, topic: &str, msg_type: &str, compression: Compression) -> Written {
+        let mut msg = json!(
         {
         "op": Ops::Subscribe.to_string(),
         "topic": topic,
         "type": msg_type,
         }
         );
-        let msg = Message::Text(msg.to_string());
+        // Only sent when compressing so subscribes are unchanged for older rosbridge versions
+        if compression != Compression::None {
+            msg["compression"] = compression.as_str().into();
+        }
+        let msg = msg.to_string();
         debug!("Sending subscribe: {:?}", &msg);
-        self.send(msg).await?;
-        Ok(())
+        self.send(msg)
     }
 
-    async fn unsubscribe(&mut self, topic: &str) -> RosLibRustResult<()> {
+    fn unsubscribe(&self, topic: &str) -> Written {
         let msg = json!(
         {
         "op": Ops::Unsubscribe.to_string(),
         "topic": topic,
         }
         );
-        let msg = Message::Text(msg.to_string());
+        let msg = msg.to_string();
         debug!("Sending unsubscribe: {:?}", &msg);
-        self.send(msg).await?;
-        Ok(())
+        self.send(msg)
     }
 
-    async fn publish<T: RosMessageType>(&mut self, topic: &str, msg: T) -> RosLibRustResult<()> {
+    fn publish_value(
+        &self,
+        topic: &str,
+        msg_type: &str,
+        msg: &serde_json::Value,
+    ) -> Written<usize> {
         let msg = json!(
             {
                 "op": Ops::Publish.to_string(),
                 "topic": topic,
-                "type": T::ROS_TYPE_NAME,
-                "msg": &msg,
+                "type": msg_type,
+                "msg": msg,
             }
         );
-        let msg = Message::Text(msg.to_string());
+        let msg = msg.to_string();
+        let size = msg.len();
         debug!("Sending publish: {:?}", &msg);
-        self.send(msg).await?;
-        Ok(())
+        let sent = self.send(msg);
+        async move {
+            sent.await?;
+            Ok(size)
+        }
+        .boxed()
     }
 
-    async fn advertise<T: RosMessageType>(&mut self, topic: &str) -> RosLibRustResult<()> {
+    fn advertise(&self, topic: &str, msg_type: &str, latch: bool) -> Written {
         let msg = json!(
             {
                 "op": Ops::Advertise.to_string(),
                 "topic": topic.to_string(),
-                "type": T::ROS_TYPE_NAME,
+                "type": msg_type,
+                "latch": latch,
             }
         );
-        let msg = Message::Text(msg.to_string());
+        let msg = msg.to_string();
         debug!("Sending advertise: {:?}", &msg);
-        self.send(msg).await?;
-        Ok(())
+        self.send(msg)
     }
 
-    async fn call_service<Req: RosMessageType>(
-        &mut self,
+    fn call_service(
+        &self,
         service: &str,
         id: &str,
-        req: Req,
-    ) -> RosLibRustResult<()> {
-        let msg = json!(
+        args: serde_json::Value,
+        compression: Compression,
+    ) -> Written {
+        let mut msg = json!(
             {
                 "op": Ops::CallService.to_string(),
                 "service": service,
                 "id": id,
-                "args": req,
+                "args": args,
             }
         );
-        let msg = Message::Text(msg.to_string());
+        // Only sent when compressing so calls are unchanged for older rosbridge versions
+        if compression != Compression::None {
+            msg["compression"] = compression.as_str().into();
+        }
+        let msg = msg.to_string();
         debug!("Sending call_service: {:?}", &msg);
-        self.send(msg).await?;
-        Ok(())
+        self.send(msg)
     }
 
-    async fn unadvertise(&mut self, topic: &str) -> RosLibRustResult<()> {
+    fn unadvertise(&self, topic: &str) -> Written {
         debug!("Sending unadvertise on {}", topic);
         let msg = json! {
             {
@@ -200,13 +215,12 @@ impl RosBridgeComm for Writer {
                 "topic": topic
             }
         };
-        let msg = Message::Text(msg.to_string());
+        let msg = msg.to_string();
         debug!("Sending unadvertise: {:?}", &msg);
-        self.send(msg).await?;
-        Ok(())
+        self.send(msg)
     }
 
-    async fn advertise_service(&mut self, srv_name: &str, srv_type: &str) -> RosLibRustResult<()> {
+    fn advertise_service(&self, srv_name: &str, srv_type: &str) -> Written {
         debug!("Sending advertise service on {} w/ {}", srv_name, srv_type);
         let msg = json! {
             {
@@ -215,12 +229,11 @@ impl RosBridgeComm for Writer {
                 "service": srv_name
             }
         };
-        let msg = Message::Text(msg.to_string());
-        self.send(msg).await?;
-        Ok(())
+        let msg = msg.to_string();
+        self.send(msg)
     }
 
-    async fn unadvertise_service(&mut self, topic: &str) -> RosLibRustResult<()> {
+    fn unadvertise_service(&self, topic: &str) -> Written {
         debug!("Sending unadvertise service on {topic}");
         let msg = json! {
             {
@@ -228,18 +241,17 @@ impl RosBridgeComm for Writer {
                 "service": &topic
             }
         };
-        let msg = Message::Text(msg.to_string());
-        self.send(msg).await?;
-        Ok(())
+        let msg = msg.to_string();
+        self.send(msg)
     }
 
-    async fn service_response(
-        &mut self,
+    fn service_response(
+        &self,
         topic: &str,
         id: Option<String>,
         is_success: bool,
         response: serde_json::Value,
-    ) -> RosLibRustResult<()> {
+    ) -> Written {
         debug!(
             "Sending service response on {:?} with {:?}, {:?}, {:?}",
             topic, id, is_success, response
@@ -253,9 +265,278 @@ impl RosBridgeComm for Writer {
                 "values": response,
             }
         };
-        let msg = Message::Text(msg.to_string());
+        let msg = msg.to_string();
         debug!("Sending service_response: {:?}", &msg);
-        self.send(msg).await?;
-        Ok(())
+        self.send(msg)
+    }
+}
+
+/// Converts a ros1 style type name, as used for `ROS_TYPE_NAME`, into the form rosbridge expects
+/// for `version`. ROS 2 type names include the kind of interface, e.g. `std_msgs/msg/String` or
+/// `std_srvs/srv/SetBool`, so `interface` is inserted for ROS 2. Names which already have the
+/// interface included are left unchanged.
+pub(crate) fn wire_type_name(version: RosVersion, type_name: &str, interface: &str) -> String {
+    match (version, type_name.split_once('/')) {
+        (RosVersion::ROS2, Some((package, name))) if !name.contains('/') => {
+            format!("{package}/{interface}/{name}")
+        }
+        _ => type_name.to_owned(),
+    }
+}
+
+/// Renames the fields of time and duration values within a message to their ROS 2 names, as
+/// generated code always serializes them with their ROS 1 names.
+/// Values are recognized as any object with exactly the fields `secs` and `nsecs` (time) or `sec`
+/// and `nsec` (duration). Deserializing accepts both forms, so no conversion is needed for incoming messages.
+pub(crate) fn ros2_time_fields(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::Object(fields) => {
+            let renames = if fields.len() != 2 {
+                None
+            } else if fields.contains_key("secs") && fields.contains_key("nsecs") {
+                Some(("secs", "nsecs"))
+            } else if fields.contains_key("sec") && fields.contains_key("nsec") {
+                Some(("sec", "nsec"))
+            } else {
+                None
+            };
+            match renames {
+                Some((sec, nsec)) => {
+                    let sec = fields.remove(sec).expect("Key was just checked");
+                    let nsec = fields.remove(nsec).expect("Key was just checked");
+                    fields.insert("sec".to_owned(), sec);
+                    fields.insert("nanosec".to_owned(), nsec);
+                }
+                None => fields.values_mut().for_each(ros2_time_fields),
+            }
+        }
+        serde_json::Value::Array(items) => items.iter_mut().for_each(ros2_time_fields),
+        _ => {}
+    }
+}
+
+/// The fields of an operation needed to dispatch a publish, borrowed from the frame
+#[derive(serde::Deserialize)]
+struct PublishEnvelope<'a> {
+    #[serde(borrow)]
+    op: Cow<'a, str>,
+    #[serde(borrow, default)]
+    topic: Option<Cow<'a, str>>,
+    #[serde(borrow, default)]
+    msg: Option<&'a serde_json::value::RawValue>,
+}
+
+/// If `text` is a publish operation, returns its topic and the range of `text` holding the json of
+/// its `msg` field. This skips over the message without building a [serde_json::Value] of it.
+pub(crate) fn publish_msg_range(text: &str) -> Option<(String, Range<usize>)> {
+    let envelope: PublishEnvelope = serde_json::from_str(text).ok()?;
+    if envelope.op != "publish" {
+        return None;
+    }
+    let msg = envelope.msg?.get();
+    // The raw value borrows from text, so its position is the offset between the two
+    let start = msg.as_ptr() as usize - text.as_ptr() as usize;
+    Some((envelope.topic?.into_owned(), start..start + msg.len()))
+}
+
+/// Decodes a binary message sent by rosbridge for subscriptions using [Compression::Cbor] into
+/// the same json representation used for uncompressed messages
+pub(crate) fn cbor_to_json(data: &[u8]) -> anyhow::Result<serde_json::Value> {
+    let value: ciborium::value::Value = ciborium::de::from_reader(data)
+        .map_err(|e| anyhow::anyhow!("Failed to decode CBOR message: {e}"))?;
+    cbor_value_to_json(value)
+}
+
+fn cbor_value_to_json(value: ciborium::value::Value) -> anyhow::Result<serde_json::Value> {
+    use ciborium::value::Value;
+    Ok(match value {
+        Value::Null => serde_json::Value::Null,
+        Value::Bool(b) => b.into(),
+        Value::Integer(i) => {
+            let i = i128::from(i);
+            match i64::try_from(i) {
+                Ok(i) => i.into(),
+                Err(_) => u64::try_from(i)?.into(),
+            }
+        }
+        Value::Float(f) => f.into(),
+        Value::Text(s) => s.into(),
+        // rosbridge sends uint8[] and char[] fields as byte strings
+        Value::Bytes(bytes) => bytes.into(),
+        Value::Array(values) => values
+            .into_iter()
+            .map(cbor_value_to_json)
+            .collect::<anyhow::Result<Vec<_>>>()?
+            .into(),
+        Value::Map(entries) => {
+            let mut map = serde_json::Map::new();
+            for (key, value) in entries {
+                let Value::Text(key) = key else {
+                    bail!("CBOR map key was not a string: {key:?}");
+                };
+                map.insert(key, cbor_value_to_json(value)?);
+            }
+            map.into()
+        }
+        Value::Tag(tag, value) => match *value {
+            Value::Bytes(bytes) => typed_array_to_json(tag, &bytes)?,
+            // Other tags (e.g. dates) carry no meaning for ROS messages, so only the value is kept
+            value => cbor_value_to_json(value)?,
+        },
+        value => bail!("Unsupported CBOR value: {value:?}"),
+    })
+}
+
+/// Decodes an RFC 8746 typed array, which rosbridge uses for numeric arrays other than uint8[]
+fn typed_array_to_json(tag: u64, bytes: &[u8]) -> anyhow::Result<serde_json::Value> {
+    macro_rules! decode {
+        ($t:ty, $from_bytes:ident) => {{
+            const WIDTH: usize = std::mem::size_of::<$t>();
+            let chunks = bytes.chunks_exact(WIDTH);
+            if !chunks.remainder().is_empty() {
+                bail!("Typed array with tag {tag} has a length which isn't a multiple of {WIDTH}");
+            }
+            chunks
+                .map(|chunk| <$t>::$from_bytes(chunk.try_into().unwrap()).into())
+                .collect::<Vec<serde_json::Value>>()
+        }};
+    }
+    Ok(match tag {
+        64 | 68 => decode!(u8, from_be_bytes),
+        65 => decode!(u16, from_be_bytes),
+        66 => decode!(u32, from_be_bytes),
+        67 => decode!(u64, from_be_bytes),
+        69 => decode!(u16, from_le_bytes),
+        70 => decode!(u32, from_le_bytes),
+        71 => decode!(u64, from_le_bytes),
+        72 => decode!(i8, from_be_bytes),
+        73 => decode!(i16, from_be_bytes),
+        74 => decode!(i32, from_be_bytes),
+        75 => decode!(i64, from_be_bytes),
+        77 => decode!(i16, from_le_bytes),
+        78 => decode!(i32, from_le_bytes),
+        79 => decode!(i64, from_le_bytes),
+        81 => decode!(f32, from_be_bytes),
+        82 => decode!(f64, from_be_bytes),
+        85 => decode!(f32, from_le_bytes),
+        86 => decode!(f64, from_le_bytes),
+        _ => bail!("Unsupported CBOR tag {tag} on byte string"),
+    }
+    .into())
+}
+
+#[cfg(test)]
+mod test {
+    use super::{cbor_to_json, publish_msg_range, ros2_time_fields, wire_type_name, RosVersion};
+    use ciborium::value::Value;
+    use serde_json::json;
+
+    fn encode(value: Value) -> Vec<u8> {
+        let mut data = vec![];
+        ciborium::ser::into_writer(&value, &mut data).unwrap();
+        data
+    }
+
+    #[test_log::test]
+    fn publish_msg_is_sliced_from_frame() {
+        let text = r#"{"op": "publish", "topic": "/a\/b", "msg": {"data": "x}"}, "id": 3}"#;
+        let (topic, range) = publish_msg_range(text).unwrap();
+        assert_eq!(topic, "/a/b");
+        assert_eq!(&text[range], r#"{"data": "x}"}"#);
+
+        // The msg may come before the op
+        let text = r#"{"msg":[1,2],"topic":"/t","op":"publish"}"#;
+        let (_, range) = publish_msg_range(text).unwrap();
+        assert_eq!(&text[range], "[1,2]");
+
+        assert!(publish_msg_range(r#"{"op": "service_response", "msg": 1}"#).is_none());
+        assert!(publish_msg_range(r#"{"op": "publish", "topic": "/t"}"#).is_none());
+        assert!(publish_msg_range("not json").is_none());
+    }
+
+    #[test_log::test]
+    fn cbor_publish_decodes_to_json() {
+        let floats: Vec<u8> = [1.5f32, -2.0]
+            .iter()
+            .flat_map(|f| f.to_le_bytes())
+            .collect();
+        let ints: Vec<u8> = [-1i16, 300].iter().flat_map(|i| i.to_le_bytes()).collect();
+        let msg = Value::Map(vec![
+            (Value::Text("data".into()), Value::Bytes(vec![1, 2, 3])),
+            (
+                Value::Text("floats".into()),
+                Value::Tag(85, Box::new(Value::Bytes(floats))),
+            ),
+            (
+                Value::Text("ints".into()),
+                Value::Tag(77, Box::new(Value::Bytes(ints))),
+            ),
+            (Value::Text("big".into()), Value::Integer(u64::MAX.into())),
+            (Value::Text("name".into()), Value::Text("map".into())),
+        ]);
+        let op = Value::Map(vec![
+            (Value::Text("op".into()), Value::Text("publish".into())),
+            (Value::Text("topic".into()), Value::Text("/map".into())),
+            (Value::Text("msg".into()), msg),
+        ]);
+        assert_eq!(
+            cbor_to_json(&encode(op)).unwrap(),
+            json!({
+                "op": "publish",
+                "topic": "/map",
+                "msg": {
+                    "data": [1, 2, 3],
+                    "floats": [1.5, -2.0],
+                    "ints": [-1, 300],
+                    "big": u64::MAX,
+                    "name": "map",
+                },
+            })
+        );
+    }
+
+    #[test_log::test]
+    fn invalid_cbor_is_an_error() {
+        assert!(cbor_to_json(&[0xff, 0x00]).is_err());
+        let truncated = Value::Tag(70, Box::new(Value::Bytes(vec![1, 2, 3])));
+        assert!(cbor_to_json(&encode(truncated)).is_err());
+    }
+
+    #[test_log::test]
+    fn converts_type_names_for_ros2() {
+        assert_eq!(
+            wire_type_name(RosVersion::ROS1, "std_msgs/String", "msg"),
+            "std_msgs/String"
+        );
+        assert_eq!(
+            wire_type_name(RosVersion::ROS2, "std_msgs/String", "msg"),
+            "std_msgs/msg/String"
+        );
+        assert_eq!(
+            wire_type_name(RosVersion::ROS2, "std_srvs/SetBool", "srv"),
+            "std_srvs/srv/SetBool"
+        );
+        assert_eq!(
+            wire_type_name(RosVersion::ROS2, "std_msgs/msg/String", "msg"),
+            "std_msgs/msg/String"
+        );
+    }
+
+    #[test_log::test]
+    fn renames_time_fields_for_ros2() {
+        let mut msg = json!({
+            "header": {"stamp": {"secs": 1, "nsecs": 2}, "frame_id": "map"},
+            "timeouts": [{"sec": 3, "nsec": 4}],
+            "secs": 5,
+        });
+        ros2_time_fields(&mut msg);
+        assert_eq!(
+            msg,
+            json!({
+                "header": {"stamp": {"sec": 1, "nanosec": 2}, "frame_id": "map"},
+                "timeouts": [{"sec": 3, "nanosec": 4}],
+                "secs": 5,
+            })
+        );
     }
 }
@@ -0,0 +1,171 @@
+use super::{ClientHandle, RosLibRustResult};
+use futures::StreamExt;
+use serde_json::Value;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Options controlling what [ClientHandle::echo_with_options] prints
+#[derive(Clone, Debug, Default)]
+pub struct EchoOptions {
+    /// Ends the stream after this many messages, like `rostopic echo -n`
+    pub count: Option<usize>,
+    /// Only prints the field at this dotted path, e.g. `pose.position` or `poses[0].position.x`,
+    /// instead of the whole message. Messages without the field print `null`.
+    pub filter: Option<String>,
+}
+
+impl ClientHandle {
+    /// Subscribes to a topic of any type, yielding each message pretty printed as json for
+    /// debugging, similar to `rostopic echo`, see [ClientHandle::echo_with_options].
+    /// ```no_run
+    /// # use futures::StreamExt;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    ///   let handle = roslibrust::ClientHandle::new("ws://localhost:9090").await?;
+    ///   let mut echo = handle.echo("/odom").await?;
+    ///   while let Some(output) = echo.next().await {
+    ///     println!("{output}");
+    ///   }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn echo(
+        &self,
+        topic: &str,
+    ) -> RosLibRustResult<impl futures::Stream<Item = String> + Send + Unpin> {
+        self.echo_with_options(topic, EchoOptions::default()).await
+    }
+
+    /// Subscribes to a topic like [ClientHandle::subscribe_any], yielding each message pretty
+    /// printed as json. Each is preceded by a header line with the message's number, counting
+    /// from 1, and the time it was received in seconds since the unix epoch, e.g.
+    /// `--- /odom #3 (nav_msgs/Odometry) received at 1700000000.250000000`.
+    ///
+    /// Like [ClientHandle::subscribe], dropping the returned stream unsubscribes.
+    pub async fn echo_with_options(
+        &self,
+        topic: &str,
+        options: EchoOptions,
+    ) -> RosLibRustResult<impl futures::Stream<Item = String> + Send + Unpin> {
+        let messages = self.subscribe_any(topic).await?;
+        let topic = topic.to_owned();
+        let echoed = messages
+            .enumerate()
+            .map(move |(index, (topic_type, msg))| {
+                let msg = match &options.filter {
+                    Some(path) => select_field(&msg, path).cloned().unwrap_or(Value::Null),
+                    None => msg,
+                };
+                format_echo(&topic, &topic_type, index + 1, SystemTime::now(), &msg)
+            })
+            .take(options.count.unwrap_or(usize::MAX));
+        Ok(Box::pin(echoed))
+    }
+}
+
+/// Formats one message with its header line, see [ClientHandle::echo_with_options]
+fn format_echo(
+    topic: &str,
+    topic_type: &str,
+    number: usize,
+    received: SystemTime,
+    msg: &Value,
+) -> String {
+    let received = received.duration_since(UNIX_EPOCH).unwrap_or_default();
+    let msg = serde_json::to_string_pretty(msg).expect("Json values always serialize");
+    format!(
+        "--- {topic} #{number} ({topic_type}) received at {}.{:09}\n{msg}",
+        received.as_secs(),
+        received.subsec_nanos()
+    )
+}
+
+/// Finds the field at a dotted path like `poses[0].position`, None if it doesn't exist
+fn select_field<'a>(msg: &'a Value, path: &str) -> Option<&'a Value> {
+    path.split('.').try_fold(msg, |value, component| {
+        let mut indices = component.split('[');
+        let field = indices.next()?;
+        let mut value = match field {
+            "" => value,
+            field => value.get(field)?,
+        };
+        for index in indices {
+            value = value.get(index.strip_suffix(']')?.parse::<usize>().ok()?)?;
+        }
+        Some(value)
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::rosbridge::mock_bridge::MockBridge;
+    use serde_json::json;
+    use std::time::Duration;
+
+    const TIMEOUT: Duration = Duration::from_secs(2);
+
+    fn pose(x: f64) -> Value {
+        json!({
+            "header": {"frame_id": "map"},
+            "poses": [{"position": {"x": x, "y": 2.0}}],
+        })
+    }
+
+    #[test_log::test]
+    fn messages_are_formatted_with_header_line() {
+        let received = UNIX_EPOCH + Duration::new(1_700_000_000, 250_000_000);
+        assert_eq!(
+            format_echo("/data", "std_msgs/UInt8", 3, received, &json!({"data": 7})),
+            "--- /data #3 (std_msgs/UInt8) received at 1700000000.250000000\n{\n  \"data\": 7\n}"
+        );
+    }
+
+    #[test_log::test]
+    fn fields_are_selected_by_path() {
+        let msg = pose(1.5);
+        assert_eq!(select_field(&msg, "header.frame_id"), Some(&json!("map")));
+        assert_eq!(
+            select_field(&msg, "poses[0].position"),
+            Some(&json!({"x": 1.5, "y": 2.0}))
+        );
+        assert_eq!(select_field(&msg, "poses[0].position.x"), Some(&json!(1.5)));
+        assert_eq!(select_field(&msg, "poses[1]"), None);
+        assert_eq!(select_field(&msg, "header.seq"), None);
+        assert_eq!(select_field(&msg, "poses[x]"), None);
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn echo_is_filtered_and_limited() {
+        let mut bridge = MockBridge::new().await;
+        let client = ClientHandle::new(bridge.url()).await.unwrap();
+
+        let options = EchoOptions {
+            count: Some(2),
+            filter: Some("poses[0].position".to_owned()),
+        };
+        let (echo, _) = tokio::join!(client.echo_with_options("/path", options), async {
+            let call = bridge.next_service_call("/rosapi/topic_type").await;
+            bridge.respond(&call, json!({"type": "nav_msgs/Path"}));
+        });
+        let mut echo = echo.unwrap();
+        bridge.next_op("subscribe", "/path").await;
+        for x in [1.0, 2.0, 3.0] {
+            bridge.publish("/path", pose(x));
+        }
+
+        let mut output = vec![];
+        while let Some(echoed) = tokio::time::timeout(TIMEOUT, echo.next()).await.unwrap() {
+            // The receive time differs every run
+            let (header, msg) = echoed.split_once('\n').unwrap();
+            let (header, _time) = header.rsplit_once(' ').unwrap();
+            output.push(format!("{header}\n{msg}"));
+        }
+        assert_eq!(
+            output,
+            [
+                "--- /path #1 (nav_msgs/Path) received at\n{\n  \"x\": 1.0,\n  \"y\": 2.0\n}",
+                "--- /path #2 (nav_msgs/Path) received at\n{\n  \"x\": 2.0,\n  \"y\": 2.0\n}",
+            ]
+        );
+    }
+}
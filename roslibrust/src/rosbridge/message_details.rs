@@ -0,0 +1,475 @@
+use super::{ClientHandle, RosLibRustError, RosLibRustResult};
+use anyhow::anyhow;
+use roslibrust_codegen::RosMessageType;
+use serde_json::{json, Map, Value};
+
+/// The structure of a message type as reported by rosapi, each nested message type expanded into
+/// its own fields, see [ClientHandle::message_type_tree]
+#[derive(Clone, Debug, PartialEq)]
+pub struct MessageTypeTree {
+    /// The type's name as rosapi reported it, e.g. `nav_msgs/Odometry`
+    pub type_name: String,
+    pub fields: Vec<FieldNode>,
+}
+
+/// A field of a [MessageTypeTree]
+#[derive(Clone, Debug, PartialEq)]
+pub struct FieldNode {
+    pub name: String,
+    pub kind: FieldKind,
+    /// None unless the field is an array
+    pub array: Option<ArrayBound>,
+}
+
+/// What a [FieldNode] holds, or each element of it holds if it is an array
+#[derive(Clone, Debug, PartialEq)]
+pub enum FieldKind {
+    /// A primitive type, named as in ROS 1 message definitions, e.g. `float64`, `string` or `time`
+    Builtin(String),
+    /// Another message type
+    Nested(MessageTypeTree),
+}
+
+/// How many elements an array field holds
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ArrayBound {
+    /// Any number, e.g. `float64[]`
+    Unbounded,
+    /// Up to this many, e.g. ROS 2's `float64[<=4]`
+    AtMost(usize),
+    /// Exactly this many, e.g. `float64[36]`
+    Exactly(usize),
+}
+
+impl MessageTypeTree {
+    /// A message of this type with every field at its default, zero for numbers, empty for strings
+    /// and unbounded arrays, and fixed size arrays filled with default elements. Useful as a
+    /// starting point for building a message to publish with [ClientHandle::publish_any].
+    pub fn default_value(&self) -> Value {
+        let fields = self
+            .fields
+            .iter()
+            .map(|field| (field.name.clone(), field.default_value()))
+            .collect::<Map<_, _>>();
+        Value::Object(fields)
+    }
+}
+
+impl FieldNode {
+    /// The default of this field, see [MessageTypeTree::default_value]
+    pub fn default_value(&self) -> Value {
+        let element = || match &self.kind {
+            FieldKind::Builtin(name) => builtin_default(name),
+            FieldKind::Nested(tree) => tree.default_value(),
+        };
+        match self.array {
+            None => element(),
+            Some(ArrayBound::Exactly(len)) => Value::Array((0..len).map(|_| element()).collect()),
+            Some(ArrayBound::Unbounded | ArrayBound::AtMost(_)) => Value::Array(vec![]),
+        }
+    }
+}
+
+fn builtin_default(name: &str) -> Value {
+    match name {
+        "bool" => json!(false),
+        "float32" | "float64" => json!(0.0),
+        "string" | "wstring" => json!(""),
+        "time" | "duration" => json!({"secs": 0, "nsecs": 0}),
+        _ => json!(0),
+    }
+}
+
+// Request and response of rosapi's message_details service, also used to compare a topic's
+// definition when subscribing with SubscribeOptions::check_definition
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq)]
+pub(crate) struct MessageDetailsRequest {
+    pub(crate) r#type: String,
+}
+impl RosMessageType for MessageDetailsRequest {
+    const ROS_TYPE_NAME: &'static str = "rosapi/MessageDetailsRequest";
+}
+
+// Only the parts of rosapi's TypeDef describing fields, the examples and constants are ignored
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq)]
+pub(crate) struct TypeDef {
+    pub(crate) r#type: String,
+    pub(crate) fieldnames: Vec<String>,
+    #[serde(default)]
+    pub(crate) fieldtypes: Vec<String>,
+    /// -1 for fields which aren't arrays, otherwise the array's length or 0 if it is unbounded
+    #[serde(default)]
+    pub(crate) fieldarraylen: Vec<i32>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq)]
+pub(crate) struct MessageDetailsResponse {
+    pub(crate) typedefs: Vec<TypeDef>,
+}
+impl RosMessageType for MessageDetailsResponse {
+    const ROS_TYPE_NAME: &'static str = "rosapi/MessageDetailsResponse";
+}
+
+impl ClientHandle {
+    /// Looks up the structure of a message type with rosapi's `/rosapi/message_details` service,
+    /// for working with types only known at runtime, such as building a generic message editor.
+    ///
+    /// Fails with [RosLibRustError::RosapiUnavailable] if rosapi isn't running alongside rosbridge.
+    /// ```no_run
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    ///   let handle = roslibrust::ClientHandle::new("ws://localhost:9090").await?;
+    ///   let odometry = handle.message_type_tree("nav_msgs/Odometry").await?;
+    ///   let mut msg = odometry.default_value();
+    ///   msg["child_frame_id"] = "base_link".into();
+    ///   handle.publish_any("/odom", "nav_msgs/Odometry", msg).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn message_type_tree(&self, type_name: &str) -> RosLibRustResult<MessageTypeTree> {
+        let request = MessageDetailsRequest {
+            r#type: self.wire_type_name(type_name, "msg").await,
+        };
+        let details: MessageDetailsResponse =
+            self.call_rosapi("/rosapi/message_details", request).await?;
+        build_tree(type_name, &details.typedefs, &mut vec![])
+    }
+}
+
+/// Builds the tree of `type_name` from rosapi's flat list of the typedefs it uses. `parents` are
+/// the canonical names of the types being built which contain this one, to refuse typedefs that
+/// contain themselves.
+fn build_tree(
+    type_name: &str,
+    typedefs: &[TypeDef],
+    parents: &mut Vec<String>,
+) -> RosLibRustResult<MessageTypeTree> {
+    let canonical_name = canonical_type_name(type_name);
+    let typedef = typedefs
+        .iter()
+        .find(|def| canonical_type_name(&def.r#type) == canonical_name)
+        .ok_or_else(|| {
+            RosLibRustError::Unexpected(anyhow!("rosapi returned no definition for {type_name}"))
+        })?;
+    if parents.contains(&canonical_name) {
+        return Err(RosLibRustError::Unexpected(anyhow!(
+            "rosapi's definition of {type_name} contains itself"
+        )));
+    }
+    parents.push(canonical_name);
+    let mut fields = vec![];
+    for (index, name) in typedef.fieldnames.iter().enumerate() {
+        let field_type = typedef.fieldtypes.get(index).ok_or_else(|| {
+            RosLibRustError::Unexpected(anyhow!(
+                "rosapi's definition of {type_name} has no type for field {name}"
+            ))
+        })?;
+        let (element_type, mut array) = split_array(field_type);
+        // rosapi reports array lengths separately, with the element type left bare
+        match typedef.fieldarraylen.get(index) {
+            Some(0) if array.is_none() => array = Some(ArrayBound::Unbounded),
+            Some(&len) if len > 0 && array.is_none() => {
+                array = Some(ArrayBound::Exactly(len as usize))
+            }
+            _ => {}
+        }
+        let kind = match builtin_name(element_type) {
+            Some(builtin) => FieldKind::Builtin(builtin.to_owned()),
+            None => FieldKind::Nested(build_tree(element_type, typedefs, parents)?),
+        };
+        fields.push(FieldNode {
+            name: name.clone(),
+            kind,
+            array,
+        });
+    }
+    parents.pop();
+    Ok(MessageTypeTree {
+        type_name: typedef.r#type.clone(),
+        fields,
+    })
+}
+
+/// ROS 2's rosapi includes the `msg` namespace in type names, e.g. `std_msgs/msg/Header`
+fn canonical_type_name(type_name: &str) -> String {
+    type_name.replacen("/msg/", "/", 1)
+}
+
+/// Splits any array notation off a field type, both ROS 1's `float64[36]` and ROS 2's
+/// `sequence<float64>`, `sequence<float64, 4>` and `float64[<=4]`
+fn split_array(field_type: &str) -> (&str, Option<ArrayBound>) {
+    if let Some(inner) = field_type
+        .strip_prefix("sequence<")
+        .and_then(|rest| rest.strip_suffix('>'))
+    {
+        return match inner.split_once(',') {
+            Some((element, bound)) => match bound.trim().parse() {
+                Ok(bound) => (element.trim(), Some(ArrayBound::AtMost(bound))),
+                Err(_) => (element.trim(), Some(ArrayBound::Unbounded)),
+            },
+            None => (inner.trim(), Some(ArrayBound::Unbounded)),
+        };
+    }
+    let Some((element, bound)) = field_type
+        .strip_suffix(']')
+        .and_then(|rest| rest.split_once('['))
+    else {
+        return (field_type, None);
+    };
+    let array = if let Some(bound) = bound.strip_prefix("<=") {
+        bound.parse().map(ArrayBound::AtMost).ok()
+    } else {
+        bound.parse().map(ArrayBound::Exactly).ok()
+    };
+    (element, Some(array.unwrap_or(ArrayBound::Unbounded)))
+}
+
+/// The ROS 1 name of a primitive type, also accepting ROS 2's names for primitives and bounded
+/// strings like `string<=10`, or None for message types
+fn builtin_name(field_type: &str) -> Option<&'static str> {
+    let field_type = field_type
+        .split_once("<=")
+        .map_or(field_type, |(unbounded, _)| unbounded);
+    Some(match field_type {
+        "bool" | "boolean" => "bool",
+        "byte" | "octet" => "byte",
+        "char" => "char",
+        "int8" => "int8",
+        "uint8" => "uint8",
+        "int16" => "int16",
+        "uint16" => "uint16",
+        "int32" => "int32",
+        "uint32" => "uint32",
+        "int64" => "int64",
+        "uint64" => "uint64",
+        "float32" | "float" => "float32",
+        "float64" | "double" => "float64",
+        "string" => "string",
+        "wstring" => "wstring",
+        "time" => "time",
+        "duration" => "duration",
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::rosbridge::mock_bridge::MockBridge;
+    use roslibrust_codegen::utils::RosVersion;
+
+    /// A cut down nav_msgs/Odometry, as ROS 1's rosapi describes it
+    fn ros1_odometry() -> Value {
+        json!({"typedefs": [
+            {
+                "type": "nav_msgs/Odometry",
+                "fieldnames": ["header", "child_frame_id", "pose"],
+                "fieldtypes": ["std_msgs/Header", "string", "geometry_msgs/PoseWithCovariance"],
+                "fieldarraylen": [-1, -1, -1],
+                "examples": ["{}", "", "{}"],
+                "constnames": [],
+                "constvalues": [],
+            },
+            {
+                "type": "std_msgs/Header",
+                "fieldnames": ["seq", "stamp", "frame_id"],
+                "fieldtypes": ["uint32", "time", "string"],
+                "fieldarraylen": [-1, -1, -1],
+                "examples": ["0", "{}", ""],
+                "constnames": [],
+                "constvalues": [],
+            },
+            {
+                "type": "geometry_msgs/PoseWithCovariance",
+                "fieldnames": ["position", "covariance"],
+                "fieldtypes": ["geometry_msgs/Point", "float64"],
+                "fieldarraylen": [-1, 36],
+                "examples": ["{}", "[]"],
+                "constnames": [],
+                "constvalues": [],
+            },
+            {
+                "type": "geometry_msgs/Point",
+                "fieldnames": ["x", "y", "z"],
+                "fieldtypes": ["float64", "float64", "float64"],
+                "fieldarraylen": [-1, -1, -1],
+                "examples": ["0.0", "0.0", "0.0"],
+                "constnames": [],
+                "constvalues": [],
+            },
+        ]})
+    }
+
+    fn field(name: &str, kind: FieldKind, array: Option<ArrayBound>) -> FieldNode {
+        FieldNode {
+            name: name.to_owned(),
+            kind,
+            array,
+        }
+    }
+
+    fn builtin(name: &str) -> FieldKind {
+        FieldKind::Builtin(name.to_owned())
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn message_details_are_built_into_a_tree() {
+        let mut bridge = MockBridge::new().await;
+        let client = ClientHandle::new(bridge.url()).await.unwrap();
+
+        let (tree, _) = tokio::join!(client.message_type_tree("nav_msgs/Odometry"), async {
+            let call = bridge.next_service_call("/rosapi/message_details").await;
+            assert_eq!(call["args"]["type"], "nav_msgs/Odometry");
+            bridge.respond(&call, ros1_odometry());
+        });
+        let tree = tree.unwrap();
+
+        let point = MessageTypeTree {
+            type_name: "geometry_msgs/Point".to_owned(),
+            fields: vec![
+                field("x", builtin("float64"), None),
+                field("y", builtin("float64"), None),
+                field("z", builtin("float64"), None),
+            ],
+        };
+        let expected = MessageTypeTree {
+            type_name: "nav_msgs/Odometry".to_owned(),
+            fields: vec![
+                field(
+                    "header",
+                    FieldKind::Nested(MessageTypeTree {
+                        type_name: "std_msgs/Header".to_owned(),
+                        fields: vec![
+                            field("seq", builtin("uint32"), None),
+                            field("stamp", builtin("time"), None),
+                            field("frame_id", builtin("string"), None),
+                        ],
+                    }),
+                    None,
+                ),
+                field("child_frame_id", builtin("string"), None),
+                field(
+                    "pose",
+                    FieldKind::Nested(MessageTypeTree {
+                        type_name: "geometry_msgs/PoseWithCovariance".to_owned(),
+                        fields: vec![
+                            field("position", FieldKind::Nested(point), None),
+                            field(
+                                "covariance",
+                                builtin("float64"),
+                                Some(ArrayBound::Exactly(36)),
+                            ),
+                        ],
+                    }),
+                    None,
+                ),
+            ],
+        };
+        assert_eq!(tree, expected);
+
+        assert_eq!(
+            tree.default_value(),
+            json!({
+                "header": {"seq": 0, "stamp": {"secs": 0, "nsecs": 0}, "frame_id": ""},
+                "child_frame_id": "",
+                "pose": {
+                    "position": {"x": 0.0, "y": 0.0, "z": 0.0},
+                    "covariance": vec![0.0; 36],
+                },
+            })
+        );
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn ros2_message_details_are_understood() {
+        let mut bridge = MockBridge::new().await;
+        let client = ClientHandle::new_with_options(
+            crate::ClientHandleOptions::new(bridge.url()).ros_version(RosVersion::ROS2),
+        )
+        .await
+        .unwrap();
+
+        let (tree, _) = tokio::join!(client.message_type_tree("sensor_msgs/JointState"), async {
+            let call = bridge.next_service_call("/rosapi/message_details").await;
+            assert_eq!(call["args"]["type"], "sensor_msgs/msg/JointState");
+            bridge.respond(
+                &call,
+                json!({"typedefs": [
+                    {
+                        "type": "sensor_msgs/msg/JointState",
+                        "fieldnames": ["stamp", "name", "position", "enabled", "label"],
+                        "fieldtypes": [
+                            "builtin_interfaces/msg/Time",
+                            "sequence<string>",
+                            "double[<=4]",
+                            "boolean[2]",
+                            "string<=8",
+                        ],
+                        "fieldarraylen": [-1, -1, -1, -1, -1],
+                    },
+                    {
+                        "type": "builtin_interfaces/msg/Time",
+                        "fieldnames": ["sec", "nanosec"],
+                        "fieldtypes": ["int32", "uint32"],
+                        "fieldarraylen": [-1, -1],
+                    },
+                ]}),
+            );
+        });
+        let tree = tree.unwrap();
+
+        let fields: Vec<_> = tree
+            .fields
+            .iter()
+            .map(|field| (field.name.as_str(), &field.kind, field.array))
+            .collect();
+        assert_eq!(
+            fields[1],
+            ("name", &builtin("string"), Some(ArrayBound::Unbounded))
+        );
+        assert_eq!(
+            fields[2],
+            ("position", &builtin("float64"), Some(ArrayBound::AtMost(4)))
+        );
+        assert_eq!(
+            fields[3],
+            ("enabled", &builtin("bool"), Some(ArrayBound::Exactly(2)))
+        );
+        assert_eq!(fields[4], ("label", &builtin("string"), None));
+        assert_eq!(
+            tree.default_value(),
+            json!({
+                "stamp": {"sec": 0, "nanosec": 0},
+                "name": [],
+                "position": [],
+                "enabled": [false, false],
+                "label": "",
+            })
+        );
+    }
+
+    #[test_log::test]
+    fn missing_nested_definitions_fail() {
+        let typedefs = vec![TypeDef {
+            r#type: "test_msgs/Outer".to_owned(),
+            fieldnames: vec!["inner".to_owned()],
+            fieldtypes: vec!["test_msgs/Inner".to_owned()],
+            fieldarraylen: vec![-1],
+        }];
+        let error = build_tree("test_msgs/Outer", &typedefs, &mut vec![]).unwrap_err();
+        assert!(error.to_string().contains("test_msgs/Inner"), "{error}");
+    }
+
+    #[test_log::test]
+    fn self_containing_definitions_fail_however_they_are_named() {
+        // ROS 2's rosapi names the typedef with its msg namespace, and the field without it
+        let typedefs = vec![TypeDef {
+            r#type: "test_msgs/msg/Loop".to_owned(),
+            fieldnames: vec!["inner".to_owned()],
+            fieldtypes: vec!["test_msgs/Loop".to_owned()],
+            fieldarraylen: vec![-1],
+        }];
+        let error = build_tree("test_msgs/msg/Loop", &typedefs, &mut vec![]).unwrap_err();
+        assert!(error.to_string().contains("contains itself"), "{error}");
+    }
+}
@@ -0,0 +1,295 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex, RwLock,
+    },
+    time::{Duration, Instant},
+};
+
+/// Traffic on a single topic, see [MetricsSnapshot::topics]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TopicMetrics {
+    /// Messages published by this client
+    pub messages_sent: u64,
+    /// Size of the publish operations sent by this client, in bytes as written to the websocket
+    pub bytes_sent: u64,
+    /// Messages received for this client's subscriptions
+    pub messages_received: u64,
+    /// Size of the publish operations received, in bytes as read from the websocket
+    pub bytes_received: u64,
+    /// Messages discarded because a subscriber's queue was full, counted once per subscriber
+    pub messages_dropped: u64,
+}
+
+/// Load on a service advertised by this client, see [MetricsSnapshot::services] and
+/// [ServiceOptions](crate::ServiceOptions)
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ServiceMetrics {
+    /// Calls currently waiting for one of the service's handlers to become free
+    pub queued: u64,
+    /// Calls currently being handled
+    pub in_flight: u64,
+    /// Calls turned away because the service's queue was full
+    pub rejected: u64,
+    /// Calls which were answered with an error after taking longer than the service's timeout
+    pub timed_out: u64,
+}
+
+/// Counters describing a client's activity since it was created, see [ClientHandle::metrics](crate::ClientHandle::metrics).
+///
+/// Counters only ever increase, so rates can be derived from successive snapshots.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MetricsSnapshot {
+    /// Traffic on every topic the client has published or subscribed to
+    pub topics: BTreeMap<String, TopicMetrics>,
+    /// Load on every service the client has advertised
+    pub services: BTreeMap<String, ServiceMetrics>,
+    /// Service calls which completed, whether successfully or not
+    pub service_calls: u64,
+    /// Service calls which failed, including those which timed out
+    pub service_call_failures: u64,
+    /// Mean time taken by completed service calls, None if there haven't been any
+    pub service_call_latency_mean: Option<Duration>,
+    /// Longest time taken by a completed service call, None if there haven't been any
+    pub service_call_latency_max: Option<Duration>,
+    /// Attempts made to reconnect after losing the connection to rosbridge
+    pub reconnect_attempts: u64,
+    /// Times the client successfully reconnected
+    pub reconnects: u64,
+    /// How long the current connection to rosbridge has been up, None while not connected
+    pub connection_uptime: Option<Duration>,
+}
+
+/// Counters for a single topic, shared with the client's records of its subscription and publishers
+#[derive(Debug, Default)]
+pub(crate) struct TopicCounters {
+    messages_sent: AtomicU64,
+    bytes_sent: AtomicU64,
+    messages_received: AtomicU64,
+    bytes_received: AtomicU64,
+    messages_dropped: AtomicU64,
+}
+
+impl TopicCounters {
+    pub(crate) fn record_sent(&self, bytes: usize) {
+        self.messages_sent.fetch_add(1, Ordering::Relaxed);
+        self.bytes_sent.fetch_add(bytes as u64, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_received(&self, bytes: usize) {
+        self.messages_received.fetch_add(1, Ordering::Relaxed);
+        self.bytes_received
+            .fetch_add(bytes as u64, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_dropped(&self) {
+        self.messages_dropped.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn snapshot(&self) -> TopicMetrics {
+        TopicMetrics {
+            messages_sent: self.messages_sent.load(Ordering::Relaxed),
+            bytes_sent: self.bytes_sent.load(Ordering::Relaxed),
+            messages_received: self.messages_received.load(Ordering::Relaxed),
+            bytes_received: self.bytes_received.load(Ordering::Relaxed),
+            messages_dropped: self.messages_dropped.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Counters for a service advertised by the client, which it also uses to limit its queue
+#[derive(Debug, Default)]
+pub(crate) struct ServiceCounters {
+    queued: AtomicU64,
+    in_flight: AtomicU64,
+    rejected: AtomicU64,
+    timed_out: AtomicU64,
+}
+
+impl ServiceCounters {
+    pub(crate) fn queued(&self) -> u64 {
+        self.queued.load(Ordering::Relaxed)
+    }
+
+    /// Counts a call as queued until the returned guard is dropped
+    pub(crate) fn enqueue(self: &Arc<Self>) -> Gauge {
+        Gauge::enter(self.clone(), |counters| &counters.queued)
+    }
+
+    /// Counts a call as being handled until the returned guard is dropped
+    pub(crate) fn handle(self: &Arc<Self>) -> Gauge {
+        Gauge::enter(self.clone(), |counters| &counters.in_flight)
+    }
+
+    pub(crate) fn record_rejected(&self) {
+        self.rejected.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_timed_out(&self) {
+        self.timed_out.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn snapshot(&self) -> ServiceMetrics {
+        ServiceMetrics {
+            queued: self.queued.load(Ordering::Relaxed),
+            in_flight: self.in_flight.load(Ordering::Relaxed),
+            rejected: self.rejected.load(Ordering::Relaxed),
+            timed_out: self.timed_out.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Increments one of a service's counters while held, decrementing it again when dropped
+pub(crate) struct Gauge {
+    counters: Arc<ServiceCounters>,
+    counter: fn(&ServiceCounters) -> &AtomicU64,
+}
+
+impl Gauge {
+    fn enter(counters: Arc<ServiceCounters>, counter: fn(&ServiceCounters) -> &AtomicU64) -> Self {
+        counter(&counters).fetch_add(1, Ordering::Relaxed);
+        Gauge { counters, counter }
+    }
+}
+
+impl Drop for Gauge {
+    fn drop(&mut self) {
+        (self.counter)(&self.counters).fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// A client's metrics. When disabled nothing is recorded and snapshots are empty.
+#[derive(Debug)]
+pub(crate) struct Metrics {
+    enabled: bool,
+    // Only written when a topic is first published or subscribed to, the hot paths use the
+    // counters held by the client's subscription and publisher records
+    topics: RwLock<HashMap<String, Arc<TopicCounters>>>,
+    // Written when a service is advertised
+    services: RwLock<HashMap<String, Arc<ServiceCounters>>>,
+    service_calls: AtomicU64,
+    service_call_failures: AtomicU64,
+    service_call_latency_total_us: AtomicU64,
+    service_call_latency_max_us: AtomicU64,
+    reconnect_attempts: AtomicU64,
+    reconnects: AtomicU64,
+    connected_since: Mutex<Option<Instant>>,
+}
+
+impl Metrics {
+    pub(crate) fn new(enabled: bool) -> Self {
+        Self {
+            enabled,
+            topics: RwLock::new(HashMap::new()),
+            services: RwLock::new(HashMap::new()),
+            service_calls: AtomicU64::new(0),
+            service_call_failures: AtomicU64::new(0),
+            service_call_latency_total_us: AtomicU64::new(0),
+            service_call_latency_max_us: AtomicU64::new(0),
+            reconnect_attempts: AtomicU64::new(0),
+            reconnects: AtomicU64::new(0),
+            connected_since: Mutex::new(None),
+        }
+    }
+
+    /// Returns the counters for a topic, None if metrics are disabled
+    pub(crate) fn topic(&self, topic: &str) -> Option<Arc<TopicCounters>> {
+        if !self.enabled {
+            return None;
+        }
+        if let Some(counters) = self.topics.read().unwrap().get(topic) {
+            return Some(counters.clone());
+        }
+        let mut topics = self.topics.write().unwrap();
+        Some(topics.entry(topic.to_owned()).or_default().clone())
+    }
+
+    /// Returns the counters for a service advertised by the client. These are always needed to
+    /// enforce the service's queue size, so when metrics are disabled they are just not recorded.
+    pub(crate) fn service(&self, service: &str) -> Arc<ServiceCounters> {
+        if !self.enabled {
+            return Arc::default();
+        }
+        let mut services = self.services.write().unwrap();
+        services.entry(service.to_owned()).or_default().clone()
+    }
+
+    pub(crate) fn record_service_call(&self, latency: Duration, succeeded: bool) {
+        if !self.enabled {
+            return;
+        }
+        let latency_us = latency.as_micros() as u64;
+        self.service_calls.fetch_add(1, Ordering::Relaxed);
+        if !succeeded {
+            self.service_call_failures.fetch_add(1, Ordering::Relaxed);
+        }
+        self.service_call_latency_total_us
+            .fetch_add(latency_us, Ordering::Relaxed);
+        self.service_call_latency_max_us
+            .fetch_max(latency_us, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_reconnect_attempt(&self) {
+        if self.enabled {
+            self.reconnect_attempts.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    pub(crate) fn record_connected(&self, reconnect: bool) {
+        if !self.enabled {
+            return;
+        }
+        if reconnect {
+            self.reconnects.fetch_add(1, Ordering::Relaxed);
+        }
+        *self.connected_since.lock().unwrap() = Some(Instant::now());
+    }
+
+    pub(crate) fn record_disconnected(&self) {
+        if self.enabled {
+            *self.connected_since.lock().unwrap() = None;
+        }
+    }
+
+    pub(crate) fn snapshot(&self) -> MetricsSnapshot {
+        if !self.enabled {
+            return MetricsSnapshot::default();
+        }
+        let topics = self
+            .topics
+            .read()
+            .unwrap()
+            .iter()
+            .map(|(topic, counters)| (topic.clone(), counters.snapshot()))
+            .collect();
+        let services = self
+            .services
+            .read()
+            .unwrap()
+            .iter()
+            .map(|(service, counters)| (service.clone(), counters.snapshot()))
+            .collect();
+        let service_calls = self.service_calls.load(Ordering::Relaxed);
+        let latency = |us: u64| (service_calls > 0).then(|| Duration::from_micros(us));
+        MetricsSnapshot {
+            topics,
+            services,
+            service_calls,
+            service_call_failures: self.service_call_failures.load(Ordering::Relaxed),
+            service_call_latency_mean: latency(
+                self.service_call_latency_total_us.load(Ordering::Relaxed) / service_calls.max(1),
+            ),
+            service_call_latency_max: latency(
+                self.service_call_latency_max_us.load(Ordering::Relaxed),
+            ),
+            reconnect_attempts: self.reconnect_attempts.load(Ordering::Relaxed),
+            reconnects: self.reconnects.load(Ordering::Relaxed),
+            connection_uptime: self
+                .connected_since
+                .lock()
+                .unwrap()
+                .map(|since| since.elapsed()),
+        }
+    }
+}
@@ -0,0 +1,329 @@
+// A minimal stand-in for rosbridge_server used by tests that need to script the server side of
+// the protocol without a running ROS installation.
+
+use futures_util::{SinkExt, StreamExt};
+use serde_json::{json, Value};
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpListener,
+    sync::mpsc,
+};
+use tokio_tungstenite::tungstenite::{
+    protocol::{frame::coding::CloseCode, CloseFrame},
+    Message,
+};
+
+use crate::rosbridge::TcpFraming;
+
+/// Accepts websocket connections, or rosbridge_tcp connections for [MockBridge::tcp], one at a
+/// time and exposes the operations received from the client, while allowing the test to inject
+/// operations as if they were sent by rosbridge.
+pub(crate) struct MockBridge {
+    url: String,
+    incoming: mpsc::UnboundedReceiver<Value>,
+    outgoing: mpsc::UnboundedSender<Message>,
+    disconnect: mpsc::UnboundedSender<()>,
+    /// Number of operations the client sent as binary CBOR frames
+    binary_ops: Arc<AtomicUsize>,
+    _task: abort_on_drop::ChildTask<()>,
+}
+
+impl MockBridge {
+    pub(crate) async fn new() -> Self {
+        Self::with_accept_delay(std::time::Duration::ZERO).await
+    }
+
+    /// Creates a bridge which waits `delay` before accepting each connection, as a bridge which is
+    /// slow to start or restart would
+    pub(crate) async fn with_accept_delay(delay: std::time::Duration) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+        let (incoming_tx, incoming) = mpsc::unbounded_channel();
+        let (outgoing, mut outgoing_rx) = mpsc::unbounded_channel::<Message>();
+        let (disconnect, mut disconnect_rx) = mpsc::unbounded_channel();
+        let binary_ops = Arc::new(AtomicUsize::new(0));
+        let binary_count = binary_ops.clone();
+        let task = tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(delay).await;
+                let Ok((stream, _)) = listener.accept().await else {
+                    break;
+                };
+                let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+                loop {
+                    tokio::select! {
+                        msg = ws.next() => match msg {
+                            Some(Ok(Message::Text(text))) => {
+                                let _ = incoming_tx.send(serde_json::from_str(&text).unwrap());
+                            }
+                            // Decoded the way the client decodes what rosbridge sends
+                            Some(Ok(Message::Binary(data))) => {
+                                binary_count.fetch_add(1, Ordering::SeqCst);
+                                let op = crate::rosbridge::comm::cbor_to_json(&data).unwrap();
+                                let _ = incoming_tx.send(op);
+                            }
+                            Some(Ok(_)) => {}
+                            _ => break,
+                        },
+                        Some(msg) = outgoing_rx.recv() => {
+                            ws.send(msg).await.unwrap();
+                        }
+                        // Dropping the socket without a close handshake looks like a network failure
+                        Some(()) = disconnect_rx.recv() => break,
+                    }
+                }
+            }
+        });
+        Self {
+            url,
+            incoming,
+            outgoing,
+            disconnect,
+            binary_ops,
+            _task: task.into(),
+        }
+    }
+
+    /// Creates a bridge speaking rosbridge_tcp's protocol with the given framing instead of
+    /// websockets. Each operation is written in two halves, so the client has to reassemble them.
+    pub(crate) async fn tcp(framing: TcpFraming) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("tcp://{}", listener.local_addr().unwrap());
+        let (incoming_tx, incoming) = mpsc::unbounded_channel();
+        // rosbridge_tcp has no binary frames
+        let binary_ops = Arc::new(AtomicUsize::new(0));
+        let (outgoing, mut outgoing_rx) = mpsc::unbounded_channel::<Message>();
+        let (disconnect, mut disconnect_rx) = mpsc::unbounded_channel();
+        let task = tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let mut buffer = vec![];
+                loop {
+                    tokio::select! {
+                        read = stream.read_buf(&mut buffer) => match read {
+                            Ok(0) | Err(_) => break,
+                            Ok(_) => {
+                                for op in take_ops(&mut buffer, framing) {
+                                    let _ = incoming_tx.send(op);
+                                }
+                            }
+                        },
+                        Some(msg) = outgoing_rx.recv() => {
+                            let Message::Text(text) = msg else {
+                                panic!("Only text operations can be sent over rosbridge_tcp");
+                            };
+                            let mut data = text.into_bytes();
+                            if framing == TcpFraming::NullDelimited {
+                                data.push(0);
+                            }
+                            let (first, second) = data.split_at(data.len() / 2);
+                            stream.write_all(first).await.unwrap();
+                            stream.flush().await.unwrap();
+                            tokio::task::yield_now().await;
+                            stream.write_all(second).await.unwrap();
+                        }
+                        Some(()) = disconnect_rx.recv() => break,
+                    }
+                }
+            }
+        });
+        Self {
+            url,
+            incoming,
+            outgoing,
+            disconnect,
+            binary_ops,
+            _task: task.into(),
+        }
+    }
+
+    pub(crate) fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Number of operations received from the client as binary CBOR frames rather than text
+    pub(crate) fn binary_ops(&self) -> usize {
+        self.binary_ops.load(Ordering::SeqCst)
+    }
+
+    /// Returns the next operation received from the client matching the given op and topic,
+    /// discarding any others received in between
+    pub(crate) async fn next_op(&mut self, op: &str, topic: &str) -> Value {
+        loop {
+            let msg = self
+                .incoming
+                .recv()
+                .await
+                .expect("Client connection closed");
+            if msg["op"] == op && msg["topic"] == topic {
+                return msg;
+            }
+        }
+    }
+
+    /// Returns the next operation received from the client, whatever it is
+    pub(crate) async fn next_message(&mut self) -> Value {
+        self.incoming
+            .recv()
+            .await
+            .expect("Client connection closed")
+    }
+
+    /// Returns the next call to the given service received from the client, discarding any other
+    /// operations received in between
+    pub(crate) async fn next_service_call(&mut self, service: &str) -> Value {
+        loop {
+            let msg = self
+                .incoming
+                .recv()
+                .await
+                .expect("Client connection closed");
+            if msg["op"] == "call_service" && msg["service"] == service {
+                return msg;
+            }
+        }
+    }
+
+    /// Returns the next response the client sent for a service it advertised, discarding any
+    /// other operations received in between
+    pub(crate) async fn next_service_response(&mut self) -> Value {
+        loop {
+            let msg = self.next_message().await;
+            if msg["op"] == "service_response" {
+                return msg;
+            }
+        }
+    }
+
+    /// Responds successfully to a service call received via [MockBridge::next_service_call]
+    pub(crate) fn respond(&self, call: &Value, values: Value) {
+        self.send(json!({
+            "op": "service_response",
+            "service": call["service"],
+            "id": call["id"],
+            "values": values,
+            "result": true,
+        }));
+    }
+
+    /// Responds successfully to a service call as a binary CBOR frame, as rosbridge does for calls
+    /// made with cbor compression
+    pub(crate) fn respond_cbor(&self, call: &Value, values: Value) {
+        let op = json!({
+            "op": "service_response",
+            "service": call["service"],
+            "id": call["id"],
+            "values": values,
+            "result": true,
+        });
+        let mut data = vec![];
+        ciborium::ser::into_writer(&op, &mut data).unwrap();
+        self.outgoing.send(Message::Binary(data)).unwrap();
+    }
+
+    /// Responds to a service call received via [MockBridge::next_service_call] as rosbridge does
+    /// when the call failed, with the error message in place of the response values
+    pub(crate) fn fail(&self, call: &Value, message: &str) {
+        self.send(json!({
+            "op": "service_response",
+            "service": call["service"],
+            "id": call["id"],
+            "values": message,
+            "result": false,
+        }));
+    }
+
+    /// Calls a service the client has advertised, as rosbridge does on behalf of other nodes
+    pub(crate) fn call_service(&self, service: &str, id: &str, args: Value) {
+        self.send(json!({"op": "call_service", "service": service, "id": id, "args": args}));
+    }
+
+    /// Sends a message to the client as though it was published on the topic
+    pub(crate) fn publish(&self, topic: &str, msg: Value) {
+        self.send(json!({"op": "publish", "topic": topic, "msg": msg}));
+    }
+
+    /// Sends a message to the client as though it was published on a topic subscribed to with
+    /// cbor compression
+    pub(crate) fn publish_cbor(&self, topic: &str, msg: ciborium::value::Value) {
+        use ciborium::value::Value as Cbor;
+        let op = Cbor::Map(vec![
+            (Cbor::Text("op".into()), Cbor::Text("publish".into())),
+            (Cbor::Text("topic".into()), Cbor::Text(topic.into())),
+            (Cbor::Text("msg".into()), msg),
+        ]);
+        let mut data = vec![];
+        ciborium::ser::into_writer(&op, &mut data).unwrap();
+        self.outgoing.send(Message::Binary(data)).unwrap();
+    }
+
+    /// Sends a text frame exactly as given, for checking how the client handles formatting
+    pub(crate) fn send_text(&self, text: &str) {
+        self.outgoing.send(Message::Text(text.to_owned())).unwrap();
+    }
+
+    fn send(&self, op: Value) {
+        self.outgoing.send(Message::Text(op.to_string())).unwrap();
+    }
+
+    /// Closes the current connection with a close frame, as rosbridge or a proxy in front of it
+    /// would when shutting down, rejecting the client, or timing out an idle connection
+    pub(crate) fn close(&self, code: u16, reason: &str) {
+        let frame = CloseFrame {
+            code: CloseCode::from(code),
+            reason: reason.to_owned().into(),
+        };
+        self.outgoing.send(Message::Close(Some(frame))).unwrap();
+    }
+
+    /// Drops the current connection, the client is expected to reconnect
+    pub(crate) fn disconnect(&self) {
+        self.disconnect.send(()).unwrap();
+    }
+}
+
+/// Removes every complete operation from the data a [MockBridge::tcp] bridge has received
+fn take_ops(buffer: &mut Vec<u8>, framing: TcpFraming) -> Vec<Value> {
+    let mut ops = vec![];
+    match framing {
+        TcpFraming::NullDelimited => {
+            while let Some(end) = buffer.iter().position(|b| *b == 0) {
+                let op: Vec<u8> = buffer.drain(..=end).collect();
+                ops.push(serde_json::from_slice(&op[..end]).unwrap());
+            }
+        }
+        TcpFraming::Json => {
+            let mut values = serde_json::Deserializer::from_slice(buffer).into_iter::<Value>();
+            let mut consumed = 0;
+            while let Some(Ok(op)) = values.next() {
+                consumed = values.byte_offset();
+                ops.push(op);
+            }
+            buffer.drain(..consumed);
+        }
+    }
+    ops
+}
+
+/// Starts a deliberately unresponsive server, returning its url. Connections are accepted but
+/// never read from, and when `handshake` is false the websocket handshake is never completed.
+pub(crate) async fn unresponsive_bridge(handshake: bool) -> (String, abort_on_drop::ChildTask<()>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("ws://{}", listener.local_addr().unwrap());
+    let task = tokio::spawn(async move {
+        // Connections are held so they stay open without ever being read from
+        let mut streams = vec![];
+        let mut websockets = vec![];
+        while let Ok((stream, _)) = listener.accept().await {
+            if handshake {
+                websockets.push(tokio_tungstenite::accept_async(stream).await.unwrap());
+            } else {
+                streams.push(stream);
+            }
+        }
+    });
+    (url, task.into())
+}
@@ -13,9 +13,30 @@ pub use publisher::*;
 mod client;
 pub use client::*;
 
+// Action client is a transparent module, we directly expose internal types
+// Module exists only to organize source code
+mod action_client;
+pub use action_client::*;
+
+// Counters the client keeps about its own activity, see ClientHandle::metrics
+mod metrics;
+pub use metrics::{MetricsSnapshot, ServiceMetrics, TopicMetrics};
+use metrics::{ServiceCounters, TopicCounters};
+
+// Pretty printing any topic for debugging, see ClientHandle::echo
+mod echo;
+pub use echo::EchoOptions;
+
+// Runtime introspection of message types through rosapi, see ClientHandle::message_details
+mod message_details;
+pub use message_details::{ArrayBound, FieldKind, FieldNode, MessageTypeTree};
+
 // Tests are fully private module
 #[cfg(test)]
 mod integration_tests;
+// Scriptable stand-in for rosbridge_server used by tests that don't need a real bridge
+#[cfg(test)]
+mod mock_bridge;
 // Standard return type for all tests to use
 #[cfg(test)]
 #[allow(dead_code)]
@@ -29,17 +50,48 @@ mod topic_provider;
 /// Communication primitives for the rosbridge_suite protocol
 mod comm;
 
-use futures_util::stream::{SplitSink, SplitStream};
+/// The websocket and TCP connections rosbridge_suite's protocol can be carried over
+mod transport;
+pub use transport::{ServerClose, TcpFraming};
+
 use std::collections::HashMap;
-use tokio::net::TcpStream;
-use tokio_tungstenite::*;
-use tungstenite::Message;
+use std::sync::{atomic::AtomicUsize, Arc};
 
 // Doing this to maintain backwards compatibilities like `use roslibrust::rosbridge::RosLibRustError`
 pub use super::{RosLibRustError, RosLibRustResult};
+// Used to configure which version of ROS the rosbridge server is running
+pub use roslibrust_codegen::utils::RosVersion;
 
 /// Used for type erasure of message type so that we can store arbitrary handles
-type Callback = Box<dyn Fn(&str) + Send + Sync>;
+type Callback = Box<dyn Fn(&ReceivedMsg) + Send + Sync>;
+
+/// A message received for a subscription, the json of the publish operation's `msg` field as a
+/// slice of the frame it arrived in, so every subscriber of the topic can share it
+pub(crate) struct ReceivedMsg<'a> {
+    frame: &'a bytes::Bytes,
+    range: std::ops::Range<usize>,
+    json: &'a str,
+}
+
+impl<'a> ReceivedMsg<'a> {
+    /// Fails if the range is not valid utf8
+    fn new(
+        frame: &'a bytes::Bytes,
+        range: std::ops::Range<usize>,
+    ) -> Result<Self, std::str::Utf8Error> {
+        let json = std::str::from_utf8(&frame[range.clone()])?;
+        Ok(Self { frame, range, json })
+    }
+
+    pub(crate) fn json(&self) -> &str {
+        self.json
+    }
+
+    /// The json as bytes, sharing the frame's buffer rather than copying it
+    pub(crate) fn bytes(&self) -> bytes::Bytes {
+        self.frame.slice(self.range.clone())
+    }
+}
 
 /// Type erasure of callback for a service
 /// Internally this will covert the input string to the Request type
@@ -50,12 +102,22 @@ type Callback = Box<dyn Fn(&str) + Send + Sync>;
 // I can make a good argument for &str because that should be generic even if we switch
 // backends - Carter 2022-10-6
 // TODO move out of rosbridge and into "common"
-pub(crate) type ServiceCallback = Box<
+pub(crate) type ServiceCallback = Arc<
     dyn Fn(&str) -> Result<serde_json::Value, Box<dyn std::error::Error + Send + Sync>>
         + Send
         + Sync,
 >;
 
+/// Internal tracking structure for a service advertised by our client
+pub(crate) struct ServiceServer {
+    pub(crate) callback: ServiceCallback,
+    pub(crate) options: ServiceOptions,
+    /// One permit per handler allowed to run at once, see [ServiceOptions::max_concurrent]
+    pub(crate) permits: Arc<tokio::sync::Semaphore>,
+    /// Also used to tell when the service's queue is full, so kept even without metrics
+    pub(crate) counters: Arc<ServiceCounters>,
+}
+
 /// The handle returned to the caller of advertise_service this struct represents the lifetime
 /// of the service, and dropping this struct automatically unadvertises and removes the service.
 /// No interaction with this struct is expected beyond managing its lifetime.
@@ -74,16 +136,13 @@ impl Drop for ServiceHandle {
     }
 }
 
-/// Our underlying communication socket type (maybe move to comm?)
-type Socket = tokio_tungstenite::WebSocketStream<tokio_tungstenite::MaybeTlsStream<TcpStream>>;
-
-/// We split our underlying socket into two halves with separate locks on read and write.
-/// This is the read half.
-type Reader = SplitStream<Socket>;
+/// We split our underlying connection into two halves, the read half is locked by whichever task
+/// is currently reading from it.
+type Reader = Box<dyn transport::TransportReader>;
 
-/// We split our underlying socket into two halves with separate locks on read and write.
-/// This is the write half.
-type Writer = SplitSink<Socket, Message>;
+/// The write half of our underlying connection is owned by a single task, which everything sending
+/// on the connection queues operations to through this cheaply cloned handle.
+type Writer = transport::WriterHandle;
 
 /// Topics have a fundamental queue *per subscriber* this is te queue type used for each subscriber.
 type MessageQueue<T> = deadqueue::limited::Queue<T>;
@@ -107,9 +166,21 @@ pub(crate) struct Subscription {
     // Will contain the list of publishers of this topic as told to us by rosmaster
     // Currently only used / populated with ros1 native
     pub(crate) known_publishers: Vec<String>,
+    /// Traffic counters for the topic, None if the client isn't collecting metrics
+    pub(crate) metrics: Option<Arc<TopicCounters>>,
 }
 
 // TODO move out of rosbridge and into common
 pub(crate) struct PublisherHandle {
     pub(crate) topic_type: String,
+    /// Whether the topic was advertised as latched, used for re-advertises
+    pub(crate) latch: bool,
+    /// The last message published on a latched topic, republished after reconnecting as the
+    /// server's latched copy is lost with the old connection
+    pub(crate) latched_msg: Option<serde_json::Value>,
+    /// Number of [Publisher](crate::Publisher)s of the topic, shared with each of them. The topic
+    /// is unadvertised once the last one is dropped.
+    pub(crate) publishers: Arc<AtomicUsize>,
+    /// Traffic counters for the topic, None if the client isn't collecting metrics
+    pub(crate) metrics: Option<Arc<TopicCounters>>,
 }
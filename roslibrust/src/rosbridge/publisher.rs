@@ -1,15 +1,27 @@
 use crate::{rosbridge::RosLibRustResult, ClientHandle};
+use log::*;
 use roslibrust_codegen::RosMessageType;
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc, Weak,
+};
+use tokio::sync::Mutex;
+use tokio::time::{Duration, Instant};
 
 /// A handle given to the caller when they advertise a topic
 ///
 /// Publisher's have a single core function [publish](Publisher::publish) which provides the ability
-/// to send message on the associated topic. Publishers automatically un-advertise the topic when
-/// they are dropped. Currently, only one publisher is allowed to be created per topic, and
-/// subsequent calls to advertise() on the same topic will fail.
+/// to send message on the associated topic.
+///
+/// A topic can have any number of publishers, whether cloned or from advertising the topic again,
+/// which all share one advertisement with rosbridge. The topic is un-advertised once the last of
+/// them is dropped.
+///
+/// Publishers are Send + Sync, so clones can publish from any number of tasks at once. Messages
+/// published from one task are sent in order, messages published concurrently from different tasks
+/// are not ordered relative to each other.
 ///
 /// Roadmap for Publisher:
-///   - Support clone() / multiple advertise
 ///   - Ability for publish to by const
 // Instead of giving back a publisher should we give back a reference to one, and give back the
 // same reference when you advertise multiple times? Would require non-mut references?
@@ -24,21 +36,39 @@ pub struct Publisher<T: RosMessageType> {
     // seq: usize,
     // Stores a copy of the client so that we can de-register ourselves
     client: ClientHandle,
+    // Count of publishers on this topic, shared with the client's record of the topic
+    publishers: Arc<AtomicUsize>,
     _marker: std::marker::PhantomData<T>,
 }
 
-/// Publisher will un-advertise its topic automatically on drop
+/// The last Publisher of a topic will un-advertise it automatically on drop
 impl<T: RosMessageType> Drop for Publisher<T> {
     fn drop(&mut self) {
-        self.client.unadvertise(&self.topic);
+        if self.publishers.fetch_sub(1, Ordering::SeqCst) == 1 {
+            self.client.unadvertise(&self.topic);
+        }
+    }
+}
+
+impl<T: RosMessageType> Clone for Publisher<T> {
+    fn clone(&self) -> Self {
+        self.publishers.fetch_add(1, Ordering::SeqCst);
+        Publisher {
+            topic: self.topic.clone(),
+            client: self.client.clone(),
+            publishers: self.publishers.clone(),
+            _marker: Default::default(),
+        }
     }
 }
 
 impl<T: RosMessageType> Publisher<T> {
-    pub(crate) fn new(topic: String, client: ClientHandle) -> Self {
+    /// `publishers` must already count this publisher
+    pub(crate) fn new(topic: String, client: ClientHandle, publishers: Arc<AtomicUsize>) -> Self {
         Publisher {
             topic,
             client,
+            publishers,
             _marker: Default::default(),
         }
     }
@@ -51,4 +81,294 @@ impl<T: RosMessageType> Publisher<T> {
     pub async fn publish(&self, msg: T) -> RosLibRustResult<()> {
         self.client.publish(&self.topic, msg).await
     }
+
+    /// Limits this publisher to `hz` messages per second, see [RateLimitedPublisher].
+    ///
+    /// Panics if `hz` is not a positive finite number.
+    pub fn rate_limited(self, hz: f64, behavior: RateLimitBehavior) -> RateLimitedPublisher<T> {
+        RateLimitedPublisher::with_behavior(self, hz, behavior)
+    }
+}
+
+/// What a [RateLimitedPublisher] does with messages published faster than its rate allows
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum RateLimitBehavior {
+    /// Keeps only the most recent message, publishing it as soon as the rate allows. Also known as
+    /// conflating.
+    #[default]
+    Coalesce,
+    /// Discards the message
+    Drop,
+}
+
+/// What happened to a message given to [RateLimitedPublisher::publish]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PublishOutcome {
+    /// The message was published immediately
+    Sent,
+    /// The message is held, to be published once the rate allows
+    Queued,
+    /// The message is held to be published once the rate allows, replacing an earlier held message
+    /// which will now never be published
+    Replaced,
+    /// The message was discarded, see [RateLimitBehavior::Drop]
+    Dropped,
+}
+
+/// Wraps a [Publisher] to publish at no more than a fixed rate, e.g. when republishing a fast
+/// topic onto a slow one.
+///
+/// A message is published immediately if enough time has passed since the last one, otherwise it
+/// is handled according to the [RateLimitBehavior]. Each rate limited publisher has its own limit,
+/// independent of any other publishers of the topic. When coalescing, a message waiting for its
+/// turn is discarded if the rate limited publisher is dropped.
+pub struct RateLimitedPublisher<T: RosMessageType> {
+    shared: Arc<RateLimitShared<T>>,
+}
+
+// Sends a message, by publishing it with the wrapped Publisher outside of tests
+type PublishFn<T> =
+    Box<dyn Fn(T) -> futures::future::BoxFuture<'static, RosLibRustResult<()>> + Send + Sync>;
+
+struct RateLimitShared<T: RosMessageType> {
+    publish: PublishFn<T>,
+    period: Duration,
+    behavior: RateLimitBehavior,
+    state: Mutex<RateLimitState<T>>,
+}
+
+struct RateLimitState<T> {
+    last_sent: Option<Instant>,
+    // Message waiting to be published when coalescing
+    pending: Option<T>,
+}
+
+impl<T: RosMessageType> RateLimitedPublisher<T> {
+    /// Limits `publisher` to `hz` messages per second, coalescing any published faster than that.
+    ///
+    /// Panics if `hz` is not a positive finite number.
+    pub fn new(publisher: Publisher<T>, hz: f64) -> Self {
+        Self::with_behavior(publisher, hz, RateLimitBehavior::default())
+    }
+
+    /// Limits `publisher` to `hz` messages per second, handling any published faster than that
+    /// according to `behavior`.
+    ///
+    /// Panics if `hz` is not a positive finite number.
+    pub fn with_behavior(publisher: Publisher<T>, hz: f64, behavior: RateLimitBehavior) -> Self {
+        let publisher = Arc::new(publisher);
+        let publish: PublishFn<T> = Box::new(move |msg| {
+            let publisher = publisher.clone();
+            Box::pin(async move { publisher.publish(msg).await })
+        });
+        Self::with_publish_fn(publish, hz, behavior)
+    }
+
+    fn with_publish_fn(publish: PublishFn<T>, hz: f64, behavior: RateLimitBehavior) -> Self {
+        assert!(
+            hz.is_finite() && hz > 0.0,
+            "Rate limit must be a positive number of messages per second, got {hz}"
+        );
+        Self {
+            shared: Arc::new(RateLimitShared {
+                publish,
+                period: Duration::from_secs_f64(1.0 / hz),
+                behavior,
+                state: Mutex::new(RateLimitState {
+                    last_sent: None,
+                    pending: None,
+                }),
+            }),
+        }
+    }
+
+    /// Publishes the message now if the rate allows, otherwise drops or holds onto it without
+    /// waiting for the rate to allow it. Errors publishing a held message later are logged, as
+    /// there is no caller to return them to.
+    pub async fn publish(&self, msg: T) -> RosLibRustResult<PublishOutcome> {
+        let mut state = self.shared.state.lock().await;
+        let next_allowed = state.last_sent.map(|sent| sent + self.shared.period);
+        if next_allowed.map_or(true, |next| Instant::now() >= next) {
+            state.last_sent = Some(Instant::now());
+            state.pending = None;
+            (self.shared.publish)(msg).await?;
+            return Ok(PublishOutcome::Sent);
+        }
+        // Unwrap is safe, we can only be rate limited if something has been sent
+        let next_allowed = next_allowed.unwrap();
+        match self.shared.behavior {
+            RateLimitBehavior::Drop => {
+                trace!("Rate limit exceeded, dropping message");
+                Ok(PublishOutcome::Dropped)
+            }
+            // Only the first message held in a period needs to schedule its publish, any others
+            // replace it
+            RateLimitBehavior::Coalesce => match state.pending.replace(msg) {
+                Some(_) => Ok(PublishOutcome::Replaced),
+                None => {
+                    let shared = Arc::downgrade(&self.shared);
+                    tokio::spawn(Self::publish_pending(shared, next_allowed));
+                    Ok(PublishOutcome::Queued)
+                }
+            },
+        }
+    }
+
+    // Only holds a weak reference while waiting, so the held message is discarded if the rate
+    // limited publisher is dropped first
+    async fn publish_pending(shared: Weak<RateLimitShared<T>>, at: Instant) {
+        tokio::time::sleep_until(at).await;
+        let Some(shared) = shared.upgrade() else {
+            return;
+        };
+        let mut state = shared.state.lock().await;
+        // A publish after the deadline but before us may have already superseded the message
+        if let Some(msg) = state.pending.take() {
+            state.last_sent = Some(Instant::now());
+            if let Err(e) = (shared.publish)(msg).await {
+                error!("Failed to publish rate limited message: {e}");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{PublishFn, PublishOutcome, RateLimitBehavior, RateLimitedPublisher};
+    use crate::rosbridge::mock_bridge::MockBridge;
+    use crate::ClientHandle;
+    use roslibrust_codegen::RosMessageType;
+    use serde::{Deserialize, Serialize};
+    use std::sync::{Arc, Mutex};
+    use tokio::time::{Duration, Instant};
+
+    #[derive(Deserialize, Serialize, Debug, Default, Clone, PartialEq)]
+    struct Count {
+        data: u32,
+    }
+    impl RosMessageType for Count {
+        const ROS_TYPE_NAME: &'static str = "std_msgs/UInt32";
+    }
+
+    // Returns the data of each message the bridge receives on /count until none arrive for a while
+    async fn received(bridge: &mut MockBridge) -> Vec<u64> {
+        let mut data = vec![];
+        while let Ok(publish) = tokio::time::timeout(
+            Duration::from_millis(300),
+            bridge.next_op("publish", "/count"),
+        )
+        .await
+        {
+            data.push(publish["msg"]["data"].as_u64().unwrap());
+        }
+        data
+    }
+
+    // The data of each message sent and when
+    type Sent = Arc<Mutex<Vec<(u32, Instant)>>>;
+
+    // A rate limited publisher recording what it sends rather than needing a bridge, so its timing
+    // can be checked with a paused clock
+    fn recording(hz: f64, behavior: RateLimitBehavior) -> (RateLimitedPublisher<Count>, Sent) {
+        let sent = Arc::new(Mutex::new(vec![]));
+        let publish: PublishFn<Count> = Box::new({
+            let sent = sent.clone();
+            move |msg| {
+                sent.lock().unwrap().push((msg.data, Instant::now()));
+                Box::pin(async { Ok(()) })
+            }
+        });
+        (
+            RateLimitedPublisher::with_publish_fn(publish, hz, behavior),
+            sent,
+        )
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn rate_limit_coalesces_to_latest_message() {
+        let mut bridge = MockBridge::new().await;
+        let client = ClientHandle::new(bridge.url()).await.unwrap();
+        let publisher = client.advertise::<Count>("/count").await.unwrap();
+        let publisher = RateLimitedPublisher::new(publisher, 10.0);
+
+        for data in 0..5 {
+            publisher.publish(Count { data }).await.unwrap();
+        }
+        assert_eq!(received(&mut bridge).await, vec![0, 4]);
+    }
+
+    #[test_log::test(tokio::test(start_paused = true))]
+    async fn coalescing_reports_queued_and_replaced_messages() {
+        let (publisher, sent) = recording(10.0, RateLimitBehavior::Coalesce);
+        let start = Instant::now();
+
+        let mut outcomes = vec![];
+        for data in 0..5 {
+            outcomes.push(publisher.publish(Count { data }).await.unwrap());
+        }
+        use PublishOutcome::*;
+        assert_eq!(outcomes, [Sent, Queued, Replaced, Replaced, Replaced]);
+
+        // The newest message is sent at the next tick
+        tokio::time::sleep(Duration::from_millis(150)).await;
+        assert_eq!(
+            *sent.lock().unwrap(),
+            [(0, start), (4, start + Duration::from_millis(100))]
+        );
+    }
+
+    #[test_log::test(tokio::test(start_paused = true))]
+    async fn rate_limit_bounds_output_rate() {
+        let (publisher, sent) = recording(50.0, RateLimitBehavior::Coalesce);
+
+        // 1kHz for one second
+        let start = Instant::now();
+        for data in 0..1000 {
+            publisher.publish(Count { data }).await.unwrap();
+            tokio::time::sleep_until(start + Duration::from_millis(data as u64 + 1)).await;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        // One every 20ms from 0 to 1000ms, with the last held message sent at the final tick
+        let sent = sent.lock().unwrap();
+        assert_eq!(sent.len(), 51);
+        for pair in sent.windows(2) {
+            assert_eq!(pair[1].1 - pair[0].1, Duration::from_millis(20));
+        }
+        // Ends with the newest message rather than dropping it
+        assert_eq!(sent.last().unwrap().0, 999);
+    }
+
+    #[test_log::test(tokio::test(start_paused = true))]
+    async fn held_message_is_discarded_on_drop() {
+        let (publisher, sent) = recording(10.0, RateLimitBehavior::Coalesce);
+
+        publisher.publish(Count { data: 0 }).await.unwrap();
+        let outcome = publisher.publish(Count { data: 1 }).await.unwrap();
+        assert_eq!(outcome, PublishOutcome::Queued);
+        drop(publisher);
+
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        assert_eq!(sent.lock().unwrap().len(), 1);
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn rate_limit_drops_messages() {
+        let mut bridge = MockBridge::new().await;
+        let client = ClientHandle::new(bridge.url()).await.unwrap();
+        let publisher = client.advertise::<Count>("/count").await.unwrap();
+        let publisher =
+            RateLimitedPublisher::with_behavior(publisher, 10.0, RateLimitBehavior::Drop);
+
+        for data in 0..5 {
+            publisher.publish(Count { data }).await.unwrap();
+        }
+        tokio::time::sleep(Duration::from_millis(150)).await;
+        let outcomes = [
+            publisher.publish(Count { data: 5 }).await.unwrap(),
+            publisher.publish(Count { data: 6 }).await.unwrap(),
+        ];
+        assert_eq!(outcomes, [PublishOutcome::Sent, PublishOutcome::Dropped]);
+        assert_eq!(received(&mut bridge).await, vec![0, 5]);
+    }
 }
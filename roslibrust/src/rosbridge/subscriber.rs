@@ -4,9 +4,24 @@
 
 use log::error;
 use std::sync::Arc;
+use std::time::Duration;
 
-use crate::{rosbridge::MessageQueue, ClientHandle};
-use roslibrust_codegen::RosMessageType;
+use crate::{rosbridge::MessageQueue, ClientHandle, RosLibRustError, RosLibRustResult};
+
+/// Error returned by [ClientHandle::subscribe_once]
+#[derive(thiserror::Error, Debug)]
+pub enum SubscribeOnceError {
+    /// No message arrived in time. `subscribed` is false if the timeout elapsed before the
+    /// subscribe operation could be sent to rosbridge, for instance while waiting to reconnect.
+    #[error("No message received on {topic} within {waited:?} (subscribe sent to rosbridge: {subscribed})")]
+    Timeout {
+        topic: String,
+        waited: Duration,
+        subscribed: bool,
+    },
+    #[error(transparent)]
+    Client(#[from] RosLibRustError),
+}
 
 /// Represents a single instance of listening to a topic, and provides the ability to extract messages
 ///
@@ -21,7 +36,7 @@ use roslibrust_codegen::RosMessageType;
 ///  - Expose queue size control
 ///  - Provide unlimited queue (maybe?)
 ///  - Provide automatic alerting mechanism on queue growth / fullness
-pub struct Subscriber<T: RosMessageType> {
+pub struct Subscriber<T> {
     // Randomly generated unique id of the subscriber used to track its lifetime with the client
     id: uuid::Uuid,
     // ROS topic name this is subscribed to, currently only used in Drop impl to help client
@@ -31,7 +46,7 @@ pub struct Subscriber<T: RosMessageType> {
     queue: Arc<MessageQueue<T>>,
 }
 
-impl<T: RosMessageType> Subscriber<T> {
+impl<T> Subscriber<T> {
     // External API is accessed through ClientHandle::subscribe
     // This function is just a convenience wrapper for our internal API
     pub(crate) fn new(client: ClientHandle, queue: Arc<MessageQueue<T>>, topic: String) -> Self {
@@ -63,6 +78,12 @@ impl<T: RosMessageType> Subscriber<T> {
         self.queue.pop().await
     }
 
+    /// Like [Subscriber::next], but gives up with [RosLibRustError::Timeout] if no message is
+    /// received within `timeout`. Messages received afterwards remain queued for the next call.
+    pub async fn next_timeout(&self, timeout: Duration) -> RosLibRustResult<T> {
+        Ok(tokio::time::timeout(timeout, self.queue.pop()).await?)
+    }
+
     /// Returns the most recently received message, flushing all older message from the queue.
     /// Will ".await" if no message is available. This is the recommended method of subscribing and
     /// prevents the queue overfilling if processing messages too slowly.
@@ -81,7 +102,7 @@ impl<T: RosMessageType> Subscriber<T> {
 
 /// Informs the client that the subscriber is being dropped so that
 /// the client can track when the last subscriber for a topic is dropped
-impl<T: RosMessageType> Drop for Subscriber<T> {
+impl<T> Drop for Subscriber<T> {
     fn drop(&mut self) {
         match self.client.unsubscribe(&self.topic, &self.id) {
             Ok(_) => {}
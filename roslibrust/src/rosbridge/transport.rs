@@ -0,0 +1,630 @@
+//! The connections rosbridge operations are carried over, a websocket for rosbridge_websocket or a
+//! plain TCP socket for rosbridge_tcp. Everything above this module is independent of which is in use.
+
+use async_trait::async_trait;
+use futures::{future::BoxFuture, FutureExt, SinkExt, StreamExt};
+use futures_util::stream::{SplitSink, SplitStream};
+use log::debug;
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{
+        tcp::{OwnedReadHalf, OwnedWriteHalf},
+        TcpStream,
+    },
+    sync::{mpsc, oneshot},
+};
+use tokio_tungstenite::tungstenite::{
+    self, error::CapacityError, http::Uri, protocol::WebSocketConfig, Message,
+};
+
+use crate::{RosLibRustError, RosLibRustResult};
+
+/// Port rosbridge_tcp listens on by default, used for `tcp://` urls without a port
+const DEFAULT_TCP_PORT: u16 = 9090;
+
+/// How operations are delimited on a rosbridge_tcp connection, see
+/// [ClientHandleOptions::tcp_framing](crate::ClientHandleOptions::tcp_framing).
+/// Has no effect on websocket connections, where every operation is its own frame.
+///
+/// rosbridge_tcp's `bson_only_mode` is not supported.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TcpFraming {
+    /// Operations are sent back to back as JSON with nothing in between, as rosbridge_tcp does by
+    /// default. Each operation ends where its JSON value does.
+    #[default]
+    Json,
+    /// Each JSON operation is followed by a `\0` byte
+    NullDelimited,
+}
+
+/// Splits large operations into rosbridge `fragment` operations, see
+/// [ClientHandleOptions::fragment_outgoing](crate::ClientHandleOptions::fragment_outgoing)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct Fragmentation {
+    /// Operations longer than this many bytes are fragmented
+    pub(crate) threshold: usize,
+    /// The most bytes of the operation carried by each fragment
+    pub(crate) fragment_size: usize,
+}
+
+/// A unit of data received from rosbridge
+#[derive(Debug)]
+pub(crate) enum Frame {
+    /// A JSON encoded operation
+    Text(String),
+    /// A CBOR encoded operation, only sent over websockets for subscriptions using cbor compression
+    Binary(Vec<u8>),
+    /// The server has ended the connection, with the code and reason of its close frame if it
+    /// sent one
+    Close(Option<ServerClose>),
+}
+
+/// The close frame rosbridge, or a proxy in front of it, ended a websocket connection with, see
+/// [ClientHandle::last_close](crate::ClientHandle::last_close)
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ServerClose {
+    /// The websocket close code, e.g. 1001 when the server is going away
+    pub code: u16,
+    pub reason: String,
+}
+
+impl ServerClose {
+    /// Code for a close frame without a status, as defined by RFC 6455
+    pub(crate) const NO_STATUS: u16 = 1005;
+
+    /// The error for operations which failed because of the close, with specific errors for the
+    /// codes which call for a different response from the caller
+    pub(crate) fn to_error(&self) -> RosLibRustError {
+        let reason = self.reason.clone();
+        match self.code {
+            1008 => RosLibRustError::PolicyViolation { reason },
+            1009 => RosLibRustError::MessageTooBigForServer { reason },
+            1013 => RosLibRustError::TryAgainLater { reason },
+            code => RosLibRustError::ConnectionClosed { code, reason },
+        }
+    }
+}
+
+/// The half of a connection operations are sent on
+#[async_trait]
+pub(crate) trait TransportWriter: Send + Sync {
+    /// Sends one JSON encoded operation
+    async fn send(&mut self, op: String) -> RosLibRustResult<()>;
+    /// Begins closing the connection, the reader then receives anything still in flight followed
+    /// by the end of the connection
+    async fn close(&mut self) -> RosLibRustResult<()>;
+}
+
+/// The half of a connection operations are received on
+#[async_trait]
+pub(crate) trait TransportReader: Send + Sync {
+    /// Waits for the next frame, returning None once the connection has ended.
+    /// Must be cancel safe, the client regularly stops waiting to check on its own state.
+    async fn receive(&mut self) -> Option<RosLibRustResult<Frame>>;
+}
+
+type Socket = tokio_tungstenite::WebSocketStream<tokio_tungstenite::MaybeTlsStream<TcpStream>>;
+
+#[async_trait]
+impl TransportWriter for SplitSink<Socket, Message> {
+    async fn send(&mut self, op: String) -> RosLibRustResult<()> {
+        SinkExt::send(self, Message::Text(op)).await?;
+        Ok(())
+    }
+
+    async fn close(&mut self) -> RosLibRustResult<()> {
+        SinkExt::close(self).await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl TransportReader for SplitStream<Socket> {
+    async fn receive(&mut self) -> Option<RosLibRustResult<Frame>> {
+        loop {
+            let frame = match self.next().await? {
+                Ok(Message::Text(text)) => Frame::Text(text),
+                Ok(Message::Binary(data)) => Frame::Binary(data),
+                Ok(Message::Close(close)) => {
+                    debug!("Websocket closed: {close:?}");
+                    Frame::Close(close.map(|close| ServerClose {
+                        code: close.code.into(),
+                        reason: close.reason.into_owned(),
+                    }))
+                }
+                // Tungstenite answers pings itself
+                Ok(Message::Ping(_)) | Ok(Message::Pong(_)) => continue,
+                Ok(Message::Frame(_)) => {
+                    panic!("Unexpected websocket frame received");
+                }
+                Err(tungstenite::Error::Capacity(CapacityError::MessageTooLong {
+                    size,
+                    max_size,
+                })) => {
+                    return Some(Err(RosLibRustError::MessageTooLarge {
+                        size,
+                        limit: max_size,
+                        topic: None,
+                    }))
+                }
+                Err(e) => return Some(Err(e.into())),
+            };
+            return Some(Ok(frame));
+        }
+    }
+}
+
+struct TcpWriter {
+    stream: OwnedWriteHalf,
+    framing: TcpFraming,
+}
+
+#[async_trait]
+impl TransportWriter for TcpWriter {
+    async fn send(&mut self, op: String) -> RosLibRustResult<()> {
+        let mut data = op.into_bytes();
+        if self.framing == TcpFraming::NullDelimited {
+            data.push(0);
+        }
+        self.stream.write_all(&data).await?;
+        Ok(())
+    }
+
+    async fn close(&mut self) -> RosLibRustResult<()> {
+        self.stream.shutdown().await?;
+        Ok(())
+    }
+}
+
+struct TcpReader {
+    stream: OwnedReadHalf,
+    framing: TcpFraming,
+    // Bytes received but not yet split into frames, kept here so receive is cancel safe
+    buffer: Vec<u8>,
+    // How far into the buffer the end of the next operation has been looked for
+    scan: FrameScan,
+    /// Most bytes of a single operation to buffer, see
+    /// [ClientHandleOptions::max_frame_size](crate::ClientHandleOptions::max_frame_size)
+    max_frame_size: Option<usize>,
+}
+
+impl TcpReader {
+    /// Removes the first complete operation from the buffer, if one has been received
+    fn next_frame(&mut self) -> Option<RosLibRustResult<Frame>> {
+        let end = self.scan.find_end(&self.buffer, self.framing)?;
+        let mut op: Vec<u8> = self.buffer.drain(..end).collect();
+        if self.framing == TcpFraming::NullDelimited {
+            op.pop();
+        }
+        // The scan only matches up brackets, so the operation is checked once it has all arrived
+        if let Err(e) = serde_json::from_slice::<serde::de::IgnoredAny>(&op) {
+            return Some(Err(e.into()));
+        }
+        Some(
+            String::from_utf8(op)
+                .map(|op| Frame::Text(op.trim_start().to_owned()))
+                .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e).into()),
+        )
+    }
+}
+
+/// Finds where operations end in the bytes received over rosbridge_tcp, carrying on from where
+/// it left off as more arrive rather than going over the whole operation again on each read
+#[derive(Debug, Default)]
+struct FrameScan {
+    /// Number of bytes already looked at
+    scanned: usize,
+    /// How many JSON objects and arrays the scanned bytes are nested in
+    depth: usize,
+    in_string: bool,
+    escaped: bool,
+}
+
+impl FrameScan {
+    /// Returns the length of the first operation in `buffer` once all of it has been received,
+    /// after which scanning starts over for the operation following it
+    fn find_end(&mut self, buffer: &[u8], framing: TcpFraming) -> Option<usize> {
+        let start = self.scanned;
+        self.scanned = buffer.len();
+        let end = match framing {
+            TcpFraming::NullDelimited => start + buffer[start..].iter().position(|b| *b == 0)?,
+            TcpFraming::Json => start + self.find_json_end(&buffer[start..])?,
+        };
+        *self = FrameScan::default();
+        Some(end + 1)
+    }
+
+    /// Returns the index of the byte closing the JSON value, or None if it isn't in `bytes`
+    fn find_json_end(&mut self, bytes: &[u8]) -> Option<usize> {
+        for (i, b) in bytes.iter().enumerate() {
+            if self.in_string {
+                match b {
+                    _ if self.escaped => self.escaped = false,
+                    b'\\' => self.escaped = true,
+                    b'"' => self.in_string = false,
+                    _ => {}
+                }
+                continue;
+            }
+            match b {
+                b'"' => self.in_string = true,
+                b'{' | b'[' => self.depth += 1,
+                b'}' | b']' if self.depth > 0 => {
+                    self.depth -= 1;
+                    if self.depth == 0 {
+                        return Some(i);
+                    }
+                }
+                _ => {}
+            }
+        }
+        None
+    }
+}
+
+#[async_trait]
+impl TransportReader for TcpReader {
+    async fn receive(&mut self) -> Option<RosLibRustResult<Frame>> {
+        loop {
+            if let Some(frame) = self.next_frame() {
+                return Some(frame);
+            }
+            if let Some(limit) = self.max_frame_size {
+                if self.buffer.len() > limit {
+                    return Some(Err(RosLibRustError::MessageTooLarge {
+                        size: self.buffer.len(),
+                        limit,
+                        topic: None,
+                    }));
+                }
+            }
+            match self.stream.read_buf(&mut self.buffer).await {
+                Ok(0) => return None,
+                Ok(_) => {}
+                Err(e) => return Some(Err(e.into())),
+            }
+        }
+    }
+}
+
+/// Sends operations over [Fragmentation::threshold] as a series of `fragment` operations,
+/// which rosbridge reassembles before handling the original operation. Each fragment carries the
+/// next piece of the operation's text in `data`, with the `id` shared by all of them, its index
+/// in `num` and the number of fragments in `total`.
+struct FragmentingWriter {
+    inner: Box<dyn TransportWriter>,
+    fragmentation: Fragmentation,
+}
+
+#[async_trait]
+impl TransportWriter for FragmentingWriter {
+    async fn send(&mut self, op: String) -> RosLibRustResult<()> {
+        match op {
+            op if op.len() > self.fragmentation.threshold => {
+                let id = uuid::Uuid::new_v4().to_string();
+                let pieces = split_fragments(&op, self.fragmentation.fragment_size);
+                let total = pieces.len();
+                for (num, data) in pieces.into_iter().enumerate() {
+                    let fragment = serde_json::json!({
+                        "op": "fragment",
+                        "id": id,
+                        "data": data,
+                        "num": num,
+                        "total": total,
+                    });
+                    self.inner.send(fragment.to_string()).await?;
+                }
+                Ok(())
+            }
+            op => self.inner.send(op).await,
+        }
+    }
+
+    async fn close(&mut self) -> RosLibRustResult<()> {
+        self.inner.close().await
+    }
+}
+
+/// Splits `op` into pieces of at most `size` bytes, without splitting any characters. A piece
+/// holds at least one character, so may be larger when `size` is smaller than a character.
+fn split_fragments(op: &str, size: usize) -> Vec<&str> {
+    let mut pieces = vec![];
+    let mut rest = op;
+    while !rest.is_empty() {
+        let mut end = size.min(rest.len());
+        while !rest.is_char_boundary(end) {
+            end -= 1;
+        }
+        if end == 0 {
+            end = rest.chars().next().map_or(rest.len(), char::len_utf8);
+        }
+        let (piece, remainder) = rest.split_at(end);
+        pieces.push(piece);
+        rest = remainder;
+    }
+    pieces
+}
+
+/// The two halves of a connection to rosbridge
+pub(crate) type Transport = (Box<dyn TransportReader>, Box<dyn TransportWriter>);
+
+/// Resolves once a queued operation has been written to the connection, or failed to be.
+///
+/// The operation is queued when this is created if the queue has room, otherwise once this is
+/// polled and room frees up, so operations created while the queue is full aren't guaranteed to
+/// be sent in the order they were created. Dropping it, e.g. when a timeout gives up on it, only
+/// cancels the write while it is still waiting for room, an operation which made it into the
+/// queue is sent.
+pub(crate) type Written<T = ()> = BoxFuture<'static, RosLibRustResult<T>>;
+
+enum WriteRequest {
+    Send(String, oneshot::Sender<RosLibRustResult<()>>),
+    Close(oneshot::Sender<RosLibRustResult<()>>),
+}
+
+/// Queues operations to the task which owns the write half of a connection.
+/// Operations are written in the order they are queued, one at a time, so no lock is needed to
+/// share a connection between tasks. The task ends once every handle has been dropped.
+///
+/// The queue is bounded, so operations wait for room while rosbridge isn't keeping up rather
+/// than piling up in memory, see [Written].
+#[derive(Clone)]
+pub(crate) struct WriterHandle {
+    requests: mpsc::Sender<WriteRequest>,
+}
+
+impl WriterHandle {
+    /// Spawns the task writing to `writer` with room for `queue_size` operations waiting to be
+    /// written, must be called within a tokio runtime
+    pub(crate) fn spawn(mut writer: Box<dyn TransportWriter>, queue_size: usize) -> Self {
+        let (sender, mut requests) = mpsc::channel(queue_size.max(1));
+        tokio::spawn(async move {
+            while let Some(request) = requests.recv().await {
+                // Nobody waiting for the result is fine, the write still had to happen in order
+                match request {
+                    WriteRequest::Send(op, written) => {
+                        let _ = written.send(writer.send(op).await);
+                    }
+                    WriteRequest::Close(closed) => {
+                        let _ = closed.send(writer.close().await);
+                    }
+                }
+            }
+        });
+        Self { requests: sender }
+    }
+
+    /// Queues one JSON encoded operation to be sent after everything already queued
+    pub(crate) fn send(&self, op: String) -> Written {
+        let (sender, written) = oneshot::channel();
+        self.queue(WriteRequest::Send(op, sender), written)
+    }
+
+    /// Queues closing the connection after everything already queued has been sent
+    pub(crate) fn close(&self) -> Written {
+        let (sender, closed) = oneshot::channel();
+        self.queue(WriteRequest::Close(sender), closed)
+    }
+
+    fn queue(
+        &self,
+        request: WriteRequest,
+        result: oneshot::Receiver<RosLibRustResult<()>>,
+    ) -> Written {
+        // Queued straight away where possible, so operations keep the order they were created in
+        let waiting = match self.requests.try_send(request) {
+            Ok(()) => None,
+            Err(mpsc::error::TrySendError::Full(request)) => Some(request),
+            Err(mpsc::error::TrySendError::Closed(_)) => {
+                return async { Err(RosLibRustError::Disconnected) }.boxed()
+            }
+        };
+        let requests = self.requests.clone();
+        async move {
+            if let Some(request) = waiting {
+                requests
+                    .send(request)
+                    .await
+                    .map_err(|_| RosLibRustError::Disconnected)?;
+            }
+            // The task only stops early if the runtime it was spawned on shuts down
+            result.await.unwrap_or(Err(RosLibRustError::Disconnected))
+        }
+        .boxed()
+    }
+}
+
+/// Opens a connection to rosbridge at `url`, using TCP for `tcp://` urls and a websocket otherwise
+pub(crate) async fn connect(
+    url: &str,
+    handshake_timeout: Option<std::time::Duration>,
+    framing: TcpFraming,
+    websocket_config: WebSocketConfig,
+    fragmentation: Option<Fragmentation>,
+) -> RosLibRustResult<Transport> {
+    let (reader, writer) =
+        connect_unfragmented(url, handshake_timeout, framing, websocket_config).await?;
+    let writer = match fragmentation {
+        Some(fragmentation) => Box::new(FragmentingWriter {
+            inner: writer,
+            fragmentation,
+        }),
+        None => writer,
+    };
+    Ok((reader, writer))
+}
+
+async fn connect_unfragmented(
+    url: &str,
+    handshake_timeout: Option<std::time::Duration>,
+    framing: TcpFraming,
+    websocket_config: WebSocketConfig,
+) -> RosLibRustResult<Transport> {
+    if url.starts_with("tcp://") {
+        let uri: Uri = url
+            .parse()
+            .map_err(|e| RosLibRustError::InvalidUrl(format!("{url}: {e}")))?;
+        // IPv6 hosts are bracketed in urls, but not when connecting
+        let host = uri.host().unwrap_or_default();
+        let host = host.trim_start_matches('[').trim_end_matches(']');
+        let port = uri.port_u16().unwrap_or(DEFAULT_TCP_PORT);
+        let stream = TcpStream::connect((host, port)).await?;
+        let (reader, writer) = stream.into_split();
+        let reader = TcpReader {
+            stream: reader,
+            framing,
+            buffer: vec![],
+            scan: FrameScan::default(),
+            max_frame_size: websocket_config.max_frame_size,
+        };
+        let writer = TcpWriter {
+            stream: writer,
+            framing,
+        };
+        return Ok((Box::new(reader), Box::new(writer)));
+    }
+
+    let attempt = tokio_tungstenite::connect_async_with_config(url, Some(websocket_config));
+    let (socket, _response) = match handshake_timeout {
+        Some(t) => tokio::time::timeout(t, attempt)
+            .await
+            .map_err(|_| RosLibRustError::HandshakeTimeout(t))?,
+        None => attempt.await,
+    }?;
+    let (writer, reader) = socket.split();
+    Ok((Box::new(reader), Box::new(writer)))
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        split_fragments, FrameScan, TcpFraming, TcpReader, TransportReader, TransportWriter,
+        WriterHandle,
+    };
+    use crate::{RosLibRustError, RosLibRustResult};
+    use std::{
+        sync::{Arc, Mutex},
+        time::Duration,
+    };
+    use tokio::sync::Semaphore;
+
+    /// Records the operations sent, each waiting for a permit first
+    struct GatedWriter {
+        sent: Arc<Mutex<Vec<String>>>,
+        permits: Arc<Semaphore>,
+    }
+
+    #[async_trait::async_trait]
+    impl TransportWriter for GatedWriter {
+        async fn send(&mut self, op: String) -> RosLibRustResult<()> {
+            self.permits.acquire().await.unwrap().forget();
+            self.sent.lock().unwrap().push(op);
+            Ok(())
+        }
+
+        async fn close(&mut self) -> RosLibRustResult<()> {
+            Ok(())
+        }
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn full_write_queue_holds_back_operations_until_there_is_room() {
+        let sent = Arc::new(Mutex::new(vec![]));
+        let permits = Arc::new(Semaphore::new(0));
+        let writer = WriterHandle::spawn(
+            Box::new(GatedWriter {
+                sent: sent.clone(),
+                permits: permits.clone(),
+            }),
+            1,
+        );
+
+        // The first is being written and the second fills the queue
+        let first = writer.send("first".to_owned());
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        let second = writer.send("second".to_owned());
+        // So the third waits for room, and is never sent once its caller gives up on it
+        let third =
+            tokio::time::timeout(Duration::from_millis(50), writer.send("third".to_owned()));
+        assert!(third.await.is_err());
+
+        // Were the third still queued the fourth would be left without a permit
+        permits.add_permits(3);
+        let rest = async {
+            first.await.unwrap();
+            second.await.unwrap();
+            writer.send("fourth".to_owned()).await.unwrap();
+        };
+        tokio::time::timeout(Duration::from_secs(5), rest)
+            .await
+            .expect("Queued operations weren't written");
+        assert_eq!(*sent.lock().unwrap(), ["first", "second", "fourth"]);
+    }
+
+    #[test_log::test]
+    fn frame_scan_finds_operations_arriving_a_byte_at_a_time() {
+        let first = r#"{"op":"publish","msg":{"data":"}]\"{ ["}}"#;
+        let second = r#" [{"op":"status"}]"#;
+        let data = format!("{first}{second}");
+        let mut scan = FrameScan::default();
+        let mut ends = vec![];
+        let mut start = 0;
+        for received in 1..=data.len() {
+            // Everything but the byte which just arrived has already been looked at
+            assert_eq!(scan.scanned, received - 1 - start);
+            if let Some(end) = scan.find_end(&data.as_bytes()[start..received], TcpFraming::Json) {
+                start += end;
+                ends.push(start);
+            }
+        }
+        assert_eq!(ends, [first.len(), data.len()]);
+
+        let mut scan = FrameScan::default();
+        assert_eq!(scan.find_end(b"{\"op\"", TcpFraming::NullDelimited), None);
+        assert_eq!(
+            scan.find_end(b"{\"op\":1}\0{", TcpFraming::NullDelimited),
+            Some(9)
+        );
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn tcp_reader_fails_once_an_operation_is_over_the_limit() {
+        use tokio::io::AsyncWriteExt;
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let stream = tokio::net::TcpStream::connect(listener.local_addr().unwrap())
+            .await
+            .unwrap();
+        let (mut bridge, _) = listener.accept().await.unwrap();
+        let mut reader = TcpReader {
+            stream: stream.into_split().0,
+            framing: TcpFraming::Json,
+            buffer: vec![],
+            scan: FrameScan::default(),
+            max_frame_size: Some(1024),
+        };
+
+        bridge.write_all(br#"{"op":"status"}"#).await.unwrap();
+        assert!(reader.receive().await.unwrap().is_ok());
+        let unterminated = format!(r#"{{"op":"publish","msg":"{}"#, "x".repeat(2048));
+        bridge.write_all(unterminated.as_bytes()).await.unwrap();
+        let err = tokio::time::timeout(Duration::from_secs(5), reader.receive())
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap_err();
+        assert!(
+            matches!(err, RosLibRustError::MessageTooLarge { limit: 1024, .. }),
+            "{err:?}"
+        );
+    }
+
+    #[test_log::test]
+    fn fragments_split_between_characters() {
+        assert_eq!(split_fragments("abcdefg", 3), vec!["abc", "def", "g"]);
+        assert_eq!(split_fragments("abc", 3), vec!["abc"]);
+        // é is two bytes, so can't be split after the first
+        assert_eq!(split_fragments("aébc", 2), vec!["a", "é", "bc"]);
+        // Pieces smaller than a character still make progress
+        assert_eq!(split_fragments("éé", 1), vec!["é", "é"]);
+    }
+}
@@ -142,4 +142,82 @@ mod tests {
         assert!(!host.is_empty());
         assert!(port != 0);
     }
+
+    #[test_log::test(tokio::test)]
+    async fn verify_nodelets_communicate_in_process() {
+        use roslibrust::ros1::{nodelets, NodeletContainer, Nodelets};
+
+        struct Listener {
+            received: tokio::sync::mpsc::Sender<String>,
+        }
+
+        #[async_trait::async_trait]
+        impl Nodelets for Listener {
+            async fn on_init(
+                &mut self,
+                handle: NodeHandle,
+            ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+                let mut subscriber = handle
+                    .subscribe::<std_msgs::String>("/nodelet_chatter", 1)
+                    .await?;
+                let received = self.received.clone();
+                tokio::spawn(async move {
+                    loop {
+                        let data = match subscriber.next().await {
+                            Ok(msg) => msg.data,
+                            Err(_) => break,
+                        };
+                        let _ = received.send(data).await;
+                    }
+                });
+                Ok(())
+            }
+        }
+
+        #[derive(Default)]
+        struct Talker;
+
+        #[nodelets]
+        impl Nodelets for Talker {
+            fn dependencies(&self) -> Vec<String> {
+                vec!["listener".to_owned()]
+            }
+
+            async fn on_init(
+                &mut self,
+                handle: NodeHandle,
+            ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+                let publisher = handle
+                    .advertise::<std_msgs::String>("/nodelet_chatter", 1)
+                    .await?;
+                tokio::spawn(async move {
+                    let msg = std_msgs::String {
+                        data: "hello".to_owned(),
+                    };
+                    while publisher.publish(&msg).await.is_ok() {
+                        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+                    }
+                });
+                Ok(())
+            }
+        }
+
+        let node = NodeHandle::new("http://localhost:11311", "verify_nodelets")
+            .await
+            .unwrap();
+        let (tx, mut rx) = tokio::sync::mpsc::channel(1);
+        let mut container = NodeletContainer::new();
+        container.register::<Talker>();
+        container.load("talker", "Talker").unwrap();
+        container
+            .add("listener", Box::new(Listener { received: tx }))
+            .unwrap();
+        container.init(&node).await.unwrap();
+
+        let msg = tokio::time::timeout(std::time::Duration::from_secs(5), rx.recv())
+            .await
+            .unwrap();
+        assert_eq!(msg.as_deref(), Some("hello"));
+        container.shutdown().await;
+    }
 }
@@ -0,0 +1,94 @@
+//! Compares generating a large workspace on a single thread to generating it on rayon's default pool
+//! Run with `cargo bench -p roslibrust_codegen --features parallel-codegen --bench parallel_codegen`
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use roslibrust_codegen::find_and_generate_ros_messages_without_ros_package_path;
+use std::path::Path;
+
+const PACKAGES: usize = 100;
+const MESSAGES_PER_PACKAGE: usize = 8;
+
+fn write(path: &Path, contents: &str) {
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    std::fs::write(path, contents).unwrap();
+}
+
+fn write_package(root: &Path, name: &str, files: &[(String, String)]) {
+    write(
+        &root.join(name).join("package.xml"),
+        &format!(
+            "<package format=\"2\"><name>{name}</name>\
+             <buildtool_depend>catkin</buildtool_depend></package>"
+        ),
+    );
+    for (file, contents) in files {
+        write(&root.join(name).join(file), contents);
+    }
+}
+
+/// Lays out a catkin workspace of packages which all depend on the first, as many depend on common_msgs
+fn workspace(root: &Path) {
+    write_package(
+        root,
+        "std_msgs",
+        &[(
+            "msg/Header.msg".to_owned(),
+            "uint32 seq\ntime stamp\nstring frame_id".to_owned(),
+        )],
+    );
+    for pkg in 0..PACKAGES {
+        let mut files = vec![(
+            "msg/Point.msg".to_owned(),
+            "# A point in space\nfloat64 x\nfloat64 y\nfloat64 z".to_owned(),
+        )];
+        let upstream = match pkg {
+            0 => "Point".to_owned(),
+            _ => "bench_pkg_0/Sample0".to_owned(),
+        };
+        for msg in 0..MESSAGES_PER_PACKAGE {
+            files.push((
+                format!("msg/Sample{msg}.msg"),
+                format!(
+                    "uint8 IDLE=0\nuint8 ACTIVE=1\nstring NAME=\"sample\"\n\
+                     Header header\nPoint position\nPoint[] path\n{upstream} upstream\n\
+                     float32[9] covariance\nstring[] labels\nuint8 state\nduration age"
+                ),
+            ));
+        }
+        files.push((
+            "srv/Lookup.srv".to_owned(),
+            "string name\nPoint near\n---\nSample0[] samples\nbool found".to_owned(),
+        ));
+        write_package(root, &format!("bench_pkg_{pkg}"), &files);
+    }
+}
+
+fn generation(c: &mut Criterion) {
+    let root = tempfile::tempdir().unwrap();
+    workspace(root.path());
+    // Rendered to source as a build script would, token streams can't leave the pool's threads
+    let generate = || {
+        find_and_generate_ros_messages_without_ros_package_path(vec![root.path().to_owned()])
+            .unwrap()
+            .0
+            .to_string()
+    };
+
+    let mut group = c.benchmark_group("generate_100_packages");
+    group.sample_size(20);
+    let mut threads = vec![1, rayon::current_num_threads()];
+    threads.dedup();
+    for threads in threads {
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .build()
+            .unwrap();
+        group.bench_function(BenchmarkId::new("threads", threads), |b| {
+            b.iter(|| pool.install(generate))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, generation);
+criterion_main!(benches);
@@ -4,24 +4,38 @@ use serde::de::DeserializeOwned;
 use std::str::FromStr;
 use syn::parse_quote;
 
-use crate::parse::convert_ros_type_to_rust_type;
+use crate::parse::{convert_ros_type_to_rust_type, ParsedActionFile};
 use crate::utils::RosVersion;
 use crate::{bail, Error};
-use crate::{ConstantInfo, FieldInfo, MessageFile, RosLiteral, ServiceFile};
+use crate::{
+    ConstantInfo, FieldConstraint, FieldInfo, MessageFile, Prelude, RosLiteral, ServiceFile,
+};
 
-fn derive_attrs() -> Vec<syn::Attribute> {
+fn derive_attrs(is_copy: bool, is_ord: bool) -> Vec<syn::Attribute> {
     // TODO we should look into using $crate here...
     // The way we're currently doing it leaks a dependency on these crates to users...
     // However using $crate breaks the generated code in non-macro usage
     // Pass a flag in "if_macro"?
-    vec![
+    let mut attrs = vec![
         parse_quote! { #[derive(::serde::Deserialize)] },
         parse_quote! { #[derive(::serde::Serialize)] },
         parse_quote! { #[derive(::smart_default::SmartDefault)] },
         parse_quote! { #[derive(Debug)] },
         parse_quote! { #[derive(Clone)] },
         parse_quote! { #[derive(PartialEq)] },
-    ]
+    ];
+    // Messages with floating point fields can only be partially ordered
+    if is_ord {
+        attrs.push(parse_quote! { #[derive(Eq)] });
+    }
+    attrs.push(parse_quote! { #[derive(PartialOrd)] });
+    if is_ord {
+        attrs.push(parse_quote! { #[derive(Ord)] });
+    }
+    if is_copy {
+        attrs.push(parse_quote! { #[derive(Copy)] });
+    }
+    attrs
 }
 
 /// Generates the service for a given service file
@@ -53,9 +67,55 @@ pub fn generate_service(service: ServiceFile) -> Result<TokenStream, Error> {
     })
 }
 
+/// Generates the implementation of the RosActionType trait for a given action file
+/// The structs for the messages making up the action are generated as regular messages, this
+/// only ties them together on the generated `<Name>Action` struct
+pub fn generate_action(action: ParsedActionFile) -> TokenStream {
+    let action_type_name = format!("{}/{}", action.package, action.name);
+    let struct_name = format_ident!("{}", action.action_type.name);
+    let goal_name = format_ident!("{}", action.goal_type.name);
+    let feedback_name = format_ident!("{}", action.feedback_type.name);
+    let result_name = format_ident!("{}", action.result_type.name);
+    let action_goal_name = format_ident!("{}", action.action_goal_type.name);
+    let action_feedback_name = format_ident!("{}", action.action_feedback_type.name);
+    let action_result_name = format_ident!("{}", action.action_result_type.name);
+    quote! {
+        impl ::roslibrust_codegen::RosActionType for #struct_name {
+            const ROS_ACTION_NAME: &'static str = #action_type_name;
+            type Goal = #goal_name;
+            type Feedback = #feedback_name;
+            type Result = #result_name;
+            type ActionGoal = #action_goal_name;
+            type ActionFeedback = #action_feedback_name;
+            type ActionResult = #action_result_name;
+        }
+    }
+}
+
 pub fn generate_struct(msg: MessageFile) -> Result<TokenStream, Error> {
     let ros_type_name = msg.get_full_name();
-    let attrs = derive_attrs();
+    // Messages with a fixed size contain only primitives, time, fixed length arrays and other
+    // fixed size messages, all of which are Copy as well
+    let attrs = derive_attrs(
+        cfg!(feature = "derive-copy") && msg.fixed_size.is_some(),
+        msg.totally_ordered,
+    );
+    let struct_name = format_ident!("{}", msg.parsed.name);
+    let validator = if cfg!(feature = "validation") {
+        generate_validator(
+            &struct_name,
+            &msg.parsed.fields,
+            msg.parsed.version.unwrap_or(RosVersion::ROS1),
+        )
+        .map_err(|e| {
+            Error::new(format!(
+                "Invalid constraint annotation in {ros_type_name}: {e}"
+            ))
+        })?
+    } else {
+        None
+    };
+    let size_upper_bound = generate_size_upper_bound(&msg.parsed.fields, msg.fixed_size);
     let fields = msg
         .parsed
         .fields
@@ -81,9 +141,18 @@ pub fn generate_struct(msg: MessageFile) -> Result<TokenStream, Error> {
         })
         .collect::<Result<Vec<TokenStream>, _>>()?;
 
-    let struct_name = format_ident!("{}", msg.parsed.name);
     let md5sum = msg.md5sum;
     let definition = msg.parsed.source.trim();
+    // Sizes are computed for ros1 serialization, and the trait default of None covers variable length messages
+    let fixed_size = match (
+        msg.parsed.version.unwrap_or(RosVersion::ROS1),
+        msg.fixed_size,
+    ) {
+        (RosVersion::ROS1, Some(fixed_size)) => {
+            quote! { const FIXED_SIZE: ::std::option::Option<usize> = ::std::option::Option::Some(#fixed_size); }
+        }
+        _ => quote! {},
+    };
 
     let mut base = quote! {
         #[allow(non_snake_case)]
@@ -96,18 +165,250 @@ pub fn generate_struct(msg: MessageFile) -> Result<TokenStream, Error> {
             const ROS_TYPE_NAME: &'static str = #ros_type_name;
             const MD5SUM: &'static str = #md5sum;
             const DEFINITION: &'static str = #definition;
+            #fixed_size
+        }
+    };
+
+    base.extend(quote! {
+        impl #struct_name {
+            #(#constants )*
+            #size_upper_bound
         }
+    });
+    base.extend(validator);
+    Ok(base)
+}
+
+/// Generates `serialized_size_upper_bound`, the size of the message body in ros1 serialization.
+/// This is exact, except for bounded arrays of fixed size elements which are counted as at least
+/// as long as their bound, and is a constant for fixed size messages.
+fn generate_size_upper_bound(fields: &[FieldInfo], fixed_size: Option<usize>) -> TokenStream {
+    let doc = quote! {
+        /// An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix
+    };
+    if let Some(fixed_size) = fixed_size {
+        return if cfg!(feature = "fixed-array") {
+            quote! {
+                #doc
+                pub const fn serialized_size_upper_bound(&self) -> usize {
+                    #fixed_size
+                }
+            }
+        } else {
+            quote! {
+                #doc
+                pub fn serialized_size_upper_bound(&self) -> usize {
+                    #fixed_size
+                }
+            }
+        };
+    }
+    let sizes = fields.iter().map(generate_field_size_upper_bound);
+    // Deprecated fields still have to be counted
+    let allow = if fields.iter().any(|field| field.deprecated.is_some()) {
+        quote! { #[allow(deprecated)] }
+    } else {
+        quote! {}
     };
+    quote! {
+        #doc
+        #allow
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            #(#sizes)+*
+        }
+    }
+}
 
-    // Only if we have constants append the impl
-    if !constants.is_empty() {
-        base.extend(quote! {
-            impl #struct_name {
-                #(#constants )*
+/// Generates an expression for the serialized size of a field, see [generate_size_upper_bound]
+fn generate_field_size_upper_bound(field: &FieldInfo) -> TokenStream {
+    let field_name = format_ident!("r#{}", field.field_name);
+    let field_type = &field.field_type;
+    let element_size = match field_type.field_type.as_str() {
+        _ if field_type.package_name.is_some() => None,
+        "builtin_interfaces/Time" | "builtin_interfaces/Duration" => Some(8),
+        ros_type => MessageFile::primitive_size(ros_type),
+    };
+    // Strings and nested messages vary in size, so are counted element by element
+    let variable_size = |element: TokenStream| {
+        if field_type.package_name.is_none() {
+            // Strings are prefixed with their length
+            quote! { 4usize + #element.len() }
+        } else {
+            quote! { #element.serialized_size_upper_bound() }
+        }
+    };
+    let element_variable_size = variable_size(quote! { element });
+    match (field_type.array_info, element_size) {
+        (None, Some(size)) => quote! { #size },
+        // Fixed size arrays are serialized without a length prefix
+        (Some(Some(length)), Some(size)) => {
+            let size = length * size;
+            quote! { #size }
+        }
+        (Some(None), Some(size)) => {
+            let length = match field_type.array_bound {
+                Some(bound) => quote! { self.#field_name.len().max(#bound) },
+                None => quote! { self.#field_name.len() },
+            };
+            // Byte arrays are common enough to skip multiplying by 1
+            match size {
+                1 => quote! { 4usize + #length },
+                size => quote! { 4usize + #length * #size },
             }
-        });
+        }
+        (None, None) => variable_size(quote! { self.#field_name }),
+        (Some(Some(_)), None) => quote! {
+            self.#field_name.iter().map(|element| #element_variable_size).sum::<usize>()
+        },
+        (Some(None), None) => quote! {
+            4usize + self.#field_name.iter().map(|element| #element_variable_size).sum::<usize>()
+        },
+    }
+}
+
+/// Generates the implementation of MessageValidator checking each field's constraint annotations,
+/// returns None if no field has any
+fn generate_validator(
+    struct_name: &syn::Ident,
+    fields: &[FieldInfo],
+    version: RosVersion,
+) -> Result<Option<TokenStream>, Error> {
+    let mut checks = vec![];
+    for field in fields {
+        for constraint in field.constraints.iter() {
+            let check = generate_constraint_check(field, constraint, version)?;
+            let field_name = format_ident!("r#{}", field.field_name);
+            let name = field.field_name.as_str();
+            let constraint = constraint.to_string();
+            // Array fields check each element, naming the index of any which fail
+            checks.push(if field.field_type.array_info.is_some() {
+                quote! {
+                    for (index, value) in self.#field_name.iter().enumerate() {
+                        if !(#check) {
+                            errors.push(::roslibrust_codegen::ValidationError {
+                                field: format!("{}[{}]", #name, index),
+                                constraint: #constraint.to_owned(),
+                                value: format!("{:?}", value),
+                            });
+                        }
+                    }
+                }
+            } else {
+                quote! {
+                    let value = &self.#field_name;
+                    if !(#check) {
+                        errors.push(::roslibrust_codegen::ValidationError {
+                            field: #name.to_owned(),
+                            constraint: #constraint.to_owned(),
+                            value: format!("{:?}", value),
+                        });
+                    }
+                }
+            });
+        }
+    }
+    if checks.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(quote! {
+        impl ::roslibrust_codegen::MessageValidator for #struct_name {
+            // Bounds at the limits of the field's type, such as a minimum of 0 for unsigned
+            // fields, are written as given rather than special cased
+            #[allow(unused_comparisons, clippy::absurd_extreme_comparisons)]
+            fn validate(&self) -> ::std::result::Result<
+                ::std::vec::Vec<::roslibrust_codegen::ValidationError>,
+                ::std::vec::Vec<::roslibrust_codegen::ValidationError>,
+            > {
+                let mut errors = ::std::vec::Vec::new();
+                #(#checks )*
+                if errors.is_empty() {
+                    Ok(errors)
+                } else {
+                    Err(errors)
+                }
+            }
+        }
+    }))
+}
+
+/// Generates an expression which is true if `value`, a reference to the field or one of its
+/// elements, satisfies the constraint
+fn generate_constraint_check(
+    field: &FieldInfo,
+    constraint: &FieldConstraint,
+    version: RosVersion,
+) -> Result<TokenStream, Error> {
+    let ros_type = field.field_type.field_type.as_str();
+    let is_numeric = matches!(
+        ros_type,
+        "float64"
+            | "float32"
+            | "uint8"
+            | "char"
+            | "byte"
+            | "int8"
+            | "uint16"
+            | "int16"
+            | "uint32"
+            | "int32"
+            | "uint64"
+            | "int64"
+    );
+    if field.field_type.package_name.is_some()
+        || !(is_numeric || ros_type == "string" || ros_type == "bool")
+    {
+        bail!(
+            "constraints are not supported on field {} of type {ros_type}",
+            field.field_name
+        );
+    }
+    match constraint {
+        FieldConstraint::Range {
+            min,
+            max,
+            min_inclusive,
+            max_inclusive,
+        } => {
+            if !is_numeric {
+                bail!("range given for non-numeric field {}", field.field_name);
+            }
+            let min = parse_ros_value(ros_type, min, None, version)?;
+            let max = parse_ros_value(ros_type, max, None, version)?;
+            let above_min = if *min_inclusive {
+                quote! { *value >= #min }
+            } else {
+                quote! { *value > #min }
+            };
+            let below_max = if *max_inclusive {
+                quote! { *value <= #max }
+            } else {
+                quote! { *value < #max }
+            };
+            Ok(quote! { #above_min && #below_max })
+        }
+        FieldConstraint::Valid(values) if ros_type == "string" => {
+            // Quotes around each value are optional
+            let values = values.iter().map(|value| {
+                value
+                    .strip_prefix('"')
+                    .and_then(|value| value.strip_suffix('"'))
+                    .or_else(|| {
+                        value
+                            .strip_prefix('\'')
+                            .and_then(|value| value.strip_suffix('\''))
+                    })
+                    .unwrap_or(value)
+            });
+            Ok(quote! { [#(#values),*].contains(&value.as_str()) })
+        }
+        FieldConstraint::Valid(values) => {
+            let values = values
+                .iter()
+                .map(|value| parse_ros_value(ros_type, value, None, version))
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(quote! { [#(#values),*].contains(value) })
+        }
     }
-    Ok(base)
 }
 
 fn generate_field_definition(
@@ -127,7 +428,13 @@ fn generate_field_definition(
             .ok_or(Error::new(format!("No Rust type for {}", field.field_type)))?
             .to_owned(),
     };
+    // Byte buffers such as images are often shared, which Bytes makes cheap
+    let is_bytes = cfg!(feature = "bytes")
+        && field.field_type.package_name.is_none()
+        && field.field_type.array_info == Some(None)
+        && rust_field_type == "u8";
     let rust_field_type = match field.field_type.array_info {
+        Some(None) if is_bytes => "::roslibrust_codegen::Bytes".to_owned(),
         Some(None) => format!("::std::vec::Vec<{rust_field_type}>"),
         Some(Some(fixed_length)) => format!("[{rust_field_type}; {fixed_length}]"),
         None => rust_field_type,
@@ -137,6 +444,11 @@ fn generate_field_definition(
     );
 
     let field_name = format_ident!("r#{}", field.field_name);
+    let deprecated_line = match field.deprecated.as_deref() {
+        Some("") => quote! { #[deprecated] },
+        Some(note) => quote! { #[deprecated(note = #note)] },
+        None => quote! {},
+    };
     let property_line = quote! { pub #field_name: #rust_field_type, };
     let default_line = if let Some(ref default_val) = field.default {
         let default_val = ros_literal_to_rust_literal(
@@ -145,7 +457,16 @@ fn generate_field_definition(
             field.field_type.array_info,
             version,
         )?;
-        if field.field_type.array_info.is_some() {
+        if is_bytes {
+            // The default is generated as code building a Vec
+            let default_val = syn::parse2::<syn::LitStr>(default_val)
+                .map_err(|e| Error::with("Generated an invalid default for a byte array", e))?
+                .value();
+            let default_val = format!("::roslibrust_codegen::Bytes::from({default_val})");
+            quote! {
+                #[default(_code = #default_val)]
+            }
+        } else if field.field_type.array_info.is_some() {
             // For vectors use smart_defaults "dynamic" style
             quote! {
                 #[default(_code = #default_val)]
@@ -184,6 +505,7 @@ fn generate_field_definition(
         _ => quote! {},
     };
     Ok(quote! {
+        #deprecated_line
         #default_line
         #serde_line
         #property_line
@@ -217,7 +539,15 @@ fn generate_constant_field_definition(
         None,
         version,
     )?;
-    Ok(quote! { pub const #constant_name: #constant_rust_type = #constant_value; })
+    // The value as written in the message file, which is clearer than the generated literal
+    let doc = match &constant.comment {
+        Some(comment) => format!(" = {} ({comment})", constant.constant_value),
+        None => format!(" = {}", constant.constant_value),
+    };
+    Ok(quote! {
+        #[doc = #doc]
+        pub const #constant_name: #constant_rust_type = #constant_value;
+    })
 }
 
 pub fn generate_mod(
@@ -232,8 +562,9 @@ pub fn generate_mod(
         .map(|pkg| format_ident!("{}", pkg))
         .collect::<Vec<_>>();
 
+    // Deprecated fields are allowed within the module so only downstream uses of them warn
     quote! {
-        #[allow(unused_imports)]
+        #[allow(unused_imports, deprecated)]
         pub mod #mod_name {
             #(use super::#all_pkgs; )*
 
@@ -242,6 +573,53 @@ pub fn generate_mod(
     }
 }
 
+/// Generates the `prelude` module re-exporting the types selected by `prelude`, out of the
+/// generated `types` given as (package, name) pairs
+pub fn generate_prelude(
+    types: &[(String, String)],
+    prelude: &Prelude,
+) -> Result<TokenStream, Error> {
+    let selected: Vec<&(String, String)> = match prelude {
+        Prelude::All => {
+            let mut uses = std::collections::HashMap::new();
+            for (_, name) in types {
+                *uses.entry(name).or_insert(0) += 1;
+            }
+            // A name used by several packages can't be re-exported unambiguously
+            types.iter().filter(|(_, name)| uses[name] == 1).collect()
+        }
+        Prelude::Types(full_names) => {
+            let mut selected: Vec<&(String, String)> = vec![];
+            for full_name in full_names {
+                let Some(found) = types
+                    .iter()
+                    .find(|(pkg, name)| format!("{pkg}/{name}") == *full_name)
+                else {
+                    bail!("The prelude lists {full_name}, which is not one of the generated types");
+                };
+                if let Some((pkg, _)) = selected.iter().find(|(_, name)| *name == found.1) {
+                    bail!(
+                        "The prelude lists both {full_name} and {pkg}/{}, which can't be re-exported under the same name",
+                        found.1
+                    );
+                }
+                selected.push(found);
+            }
+            selected
+        }
+    };
+    let uses = selected.into_iter().map(|(pkg, name)| {
+        let pkg = format_ident!("{}", pkg);
+        let name = format_ident!("{}", name);
+        quote! { pub use super::#pkg::#name; }
+    });
+    Ok(quote! {
+        pub mod prelude {
+            #(#uses)*
+        }
+    })
+}
+
 fn ros_literal_to_rust_literal(
     ros_type: &str,
     literal: &RosLiteral,
@@ -257,14 +635,19 @@ fn ros_literal_to_rust_literal(
 // Wraps a serde_json deserialize call with our style of error handling.
 fn generic_parse_value<T: DeserializeOwned + ToTokens + std::fmt::Debug>(
     value: &str,
-    is_vec: bool,
+    array_info: Option<Option<usize>>,
 ) -> Result<TokenStream, Error> {
-    if is_vec {
+    if let Some(fixed_size) = array_info {
         let parsed: Vec<T> = serde_json::from_str(value).map_err(|e|
             Error::with(format!("Failed to parse a literal value in a message file to the corresponding rust type: {value} to {}", std::any::type_name::<T>()).as_str(), e)
         )?;
-        let vec_str = format!("vec!{parsed:?}");
-        Ok(quote! { #vec_str })
+        check_array_length(value, parsed.len(), fixed_size)?;
+        // Fixed size arrays are generated as [T; N], which the array literal is already
+        let array_str = match fixed_size {
+            Some(_) => format!("{parsed:?}"),
+            None => format!("vec!{parsed:?}"),
+        };
+        Ok(quote! { #array_str })
     } else {
         let parsed: T = serde_json::from_str(value).map_err(|e|
             Error::with(format!("Failed to parse a literal value in a message file to the corresponding rust type: {value} to {}", std::any::type_name::<T>()).as_str(), e)
@@ -291,28 +674,28 @@ fn parse_ros_value(
     array_info: Option<Option<usize>>,
     version: RosVersion,
 ) -> Result<TokenStream, Error> {
-    let is_vec = array_info.is_some();
     match ros_type {
-        "bool" => generic_parse_value::<bool>(value, is_vec),
-        "float64" => generic_parse_value::<f64>(value, is_vec),
-        "float32" => generic_parse_value::<f32>(value, is_vec),
-        "uint8" | "char" | "byte" => generic_parse_value::<u8>(value, is_vec),
-        "int8" => generic_parse_value::<i8>(value, is_vec),
-        "uint16" => generic_parse_value::<u16>(value, is_vec),
-        "int16" => generic_parse_value::<i16>(value, is_vec),
-        "uint32" => generic_parse_value::<u32>(value, is_vec),
-        "int32" => generic_parse_value::<i32>(value, is_vec),
-        "uint64" => generic_parse_value::<u64>(value, is_vec),
-        "int64" => generic_parse_value::<i64>(value, is_vec),
+        "bool" => generic_parse_value::<bool>(value, array_info),
+        "float64" => generic_parse_value::<f64>(value, array_info),
+        "float32" => generic_parse_value::<f32>(value, array_info),
+        "uint8" | "char" | "byte" => generic_parse_value::<u8>(value, array_info),
+        "int8" => generic_parse_value::<i8>(value, array_info),
+        "uint16" => generic_parse_value::<u16>(value, array_info),
+        "int16" => generic_parse_value::<i16>(value, array_info),
+        "uint32" => generic_parse_value::<u32>(value, array_info),
+        "int32" => generic_parse_value::<i32>(value, array_info),
+        "uint64" => generic_parse_value::<u64>(value, array_info),
+        "int64" => generic_parse_value::<i64>(value, array_info),
         "string" => {
             // String is a special case because of quotes and to_string()
-            if is_vec {
-                // TODO there is a bug here, no idea how I should be attempting to convert / escape single quotes here...
-                let parsed: Vec<String> = serde_json::from_str(value).map_err(|e|
-                    Error::with(format!("Failed to parse a literal value in a message file to the corresponding rust type: {value} to Vec<String>").as_str(), e)
-                )?;
-                let vec_str = format!("{parsed:?}.iter().map(|x| x.to_string()).collect()");
-                Ok(quote! { #vec_str })
+            if let Some(fixed_size) = array_info {
+                let parsed = parse_string_array(value)?;
+                check_array_length(value, parsed.len(), fixed_size)?;
+                let array_str = match fixed_size {
+                    Some(_) => format!("{parsed:?}.map(|x| x.to_string())"),
+                    None => format!("{parsed:?}.iter().map(|x| x.to_string()).collect()"),
+                };
+                Ok(quote! { #array_str })
             } else {
                 match version {
                     RosVersion::ROS1 => {
@@ -345,3 +728,43 @@ fn parse_ros_value(
         }
     }
 }
+
+/// Fails if an array value for a fixed size array field doesn't have exactly that many elements
+fn check_array_length(value: &str, len: usize, fixed_size: Option<usize>) -> Result<(), Error> {
+    match fixed_size {
+        Some(size) if size != len => bail!(
+            "Array value {value} has {len} elements, but the array it is for has a fixed size of {size}"
+        ),
+        _ => Ok(()),
+    }
+}
+
+/// Parses an array of strings, e.g. `["hello", 'world']`. Like ROS each element may be enclosed
+/// in either single or double quotes, and the contents of the quotes are not escaped.
+fn parse_string_array(value: &str) -> Result<Vec<String>, Error> {
+    let inner = value
+        .trim()
+        .strip_prefix('[')
+        .and_then(|v| v.strip_suffix(']'))
+        .ok_or(Error::new(format!(
+            "String array value must be enclosed in square brackets: {value}"
+        )))?;
+    let mut parsed = vec![];
+    let mut rest = inner.trim_start();
+    while let Some(quote) = rest.chars().next() {
+        if quote != '"' && quote != '\'' {
+            bail!("String array element was not enclosed in single or double quotes: {value}");
+        }
+        let end = rest[1..].find(quote).ok_or(Error::new(format!(
+            "Unterminated string in string array value: {value}"
+        )))?;
+        parsed.push(rest[1..end + 1].to_string());
+        rest = rest[end + 2..].trim_start();
+        rest = match rest.strip_prefix(',') {
+            Some(after_comma) => after_comma.trim_start(),
+            None if rest.is_empty() => rest,
+            None => bail!("Expected a comma between elements of string array value: {value}"),
+        };
+    }
+    Ok(parsed)
+}
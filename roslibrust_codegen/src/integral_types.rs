@@ -3,7 +3,18 @@ use crate::RosMessageType;
 /// Matches the integral ros1 type time, with extensions for ease of use
 /// NOTE: in ROS1 "Time" is not a message in and of itself and std_msgs/Time should be used.
 /// However, in ROS2 "Time" is a message and part of builtin_interfaces/Time.
-#[derive(:: serde :: Deserialize, :: serde :: Serialize, Debug, Default, Clone, PartialEq)]
+#[derive(
+    :: serde :: Deserialize,
+    :: serde :: Serialize,
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+)]
 pub struct Time {
     // Note: rosbridge appears to accept secs and nsecs in for time without issue?
     // Not sure we should actually rely on this behavior, but ok for now...
@@ -40,9 +51,22 @@ impl RosMessageType for Time {
 
 /// Matches the integral ros1 duration type, with extensions for ease of use
 /// NOTE: Is not a message in and of itself use std_msgs/Duration for that
-#[derive(:: serde :: Deserialize, :: serde :: Serialize, Debug, Default, Clone, PartialEq)]
+#[derive(
+    :: serde :: Deserialize,
+    :: serde :: Serialize,
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+)]
 pub struct Duration {
     pub sec: i32,
+    // This alias is required for ros2 where field has been renamed
+    #[serde(alias = "nanosec")]
     pub nsec: i32,
 }
 
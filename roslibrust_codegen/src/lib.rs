@@ -1,22 +1,33 @@
+#[cfg(feature = "parallel-codegen")]
+extern crate proc_macro;
+
 use log::*;
 use proc_macro2::TokenStream;
 use quote::quote;
 use serde::de::DeserializeOwned;
 use serde::Serialize;
 use simple_error::{bail, SimpleError as Error};
-use std::collections::{BTreeMap, VecDeque};
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
 use std::fmt::{Debug, Display};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use utils::Package;
 
 mod gen;
 use gen::*;
 mod parse;
 use parse::*;
+pub use parse::{
+    parse_message, parse_service, GenError, ParsedActionFile, ParsedMessageFile, ParsedServiceFile,
+};
+mod plan;
+pub use plan::{plan, GenerationPlan, UnresolvedReference};
 pub mod utils;
 use utils::RosVersion;
 
 pub mod integral_types;
+/// The type of unbounded `uint8[]` fields in generated messages when the `bytes` feature is enabled
+#[cfg(feature = "bytes")]
+pub use bytes::Bytes;
 pub use integral_types::*;
 
 /// Fundamental traits for message types this crate works with
@@ -35,8 +46,46 @@ pub trait RosMessageType:
     /// The definition from the msg, srv, or action file
     /// This field is optional, and only needed when using ros1 native communication
     const DEFINITION: &'static str = "";
+
+    /// The exact size in bytes of the message body when serialized with ros1 native encoding,
+    /// if every instance of the message has the same size (e.g. `geometry_msgs/Point` is always 24 bytes)
+    /// None for any message containing strings or variable length arrays
+    /// Serializers can use this to pre-size their buffers
+    const FIXED_SIZE: Option<usize> = None;
+}
+
+/// Checks a message against the constraints annotated in its definition with `# range:` or
+/// `# valid:` comments. Generated for any message with such annotations when the `validation`
+/// feature is enabled, allowing `msg.validate()?` before publishing.
+pub trait MessageValidator {
+    /// Returns `Err` listing every violated constraint. `Ok` carries any non-fatal findings,
+    /// which generated implementations do not currently produce.
+    fn validate(&self) -> Result<Vec<ValidationError>, Vec<ValidationError>>;
+}
+
+/// Describes a single field value which violates a constraint from the message definition
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ValidationError {
+    /// Name of the field, including the index for elements of array fields e.g. `cells[2]`
+    pub field: String,
+    /// The constraint that was violated, as written in the message definition e.g. `range: [0, 100]`
+    pub constraint: String,
+    /// Debug representation of the offending value
+    pub value: String,
+}
+
+impl Display for ValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} = {} violates {}",
+            self.field, self.value, self.constraint
+        )
+    }
 }
 
+impl std::error::Error for ValidationError {}
+
 // This special impl allows for services with no args / returns
 impl RosMessageType for () {
     const ROS_TYPE_NAME: &'static str = "";
@@ -57,21 +106,44 @@ pub trait RosServiceType {
     type Response: RosMessageType;
 }
 
+/// Fundamental trait for action types this crate works with
+/// This trait will be satisfied for the `<Name>Action` type generated from any action file with
+/// this crate's message_gen functionality
+pub trait RosActionType: 'static + Send + Sync {
+    /// Name of the ros action e.g. `actionlib_tutorials/Fibonacci`
+    const ROS_ACTION_NAME: &'static str;
+    /// The goal sent by the client
+    type Goal: RosMessageType;
+    /// The periodic feedback sent by the server while a goal is being executed
+    type Feedback: RosMessageType;
+    /// The final result sent by the server once a goal is finished
+    type Result: RosMessageType;
+    /// The message published on `<ns>/goal` wrapping [Self::Goal] with a header and goal id
+    type ActionGoal: RosMessageType;
+    /// The message published on `<ns>/feedback` wrapping [Self::Feedback] with a header and goal status
+    type ActionFeedback: RosMessageType;
+    /// The message published on `<ns>/result` wrapping [Self::Result] with a header and goal status
+    type ActionResult: RosMessageType;
+}
+
 #[derive(Clone, Debug)]
 pub struct MessageFile {
     pub(crate) parsed: ParsedMessageFile,
     pub(crate) md5sum: String,
-    pub(crate) is_fixed_length: bool,
+    pub(crate) fixed_size: Option<usize>,
+    pub(crate) totally_ordered: bool,
 }
 
 impl MessageFile {
     fn resolve(parsed: ParsedMessageFile, graph: &BTreeMap<String, MessageFile>) -> Option<Self> {
         let md5sum = Self::compute_md5sum(&parsed, graph)?;
-        let is_fixed_length = Self::determine_if_fixed_length(&parsed, graph)?;
+        let fixed_size = Self::compute_fixed_size(&parsed, graph)?;
+        let totally_ordered = Self::compute_totally_ordered(&parsed, graph)?;
         Some(MessageFile {
             parsed,
             md5sum,
-            is_fixed_length,
+            fixed_size,
+            totally_ordered,
         })
     }
 
@@ -100,13 +172,72 @@ impl MessageFile {
     }
 
     pub fn is_fixed_length(&self) -> bool {
-        self.is_fixed_length
+        self.fixed_size.is_some()
+    }
+
+    /// The serialized size in bytes of this message if it is fixed length, see [RosMessageType::FIXED_SIZE]
+    pub fn get_fixed_size(&self) -> Option<usize> {
+        self.fixed_size
+    }
+
+    /// True if every field of this message, including those of nested messages, can be totally
+    /// ordered, which is all but floating point fields. The generated struct then derives `Eq` and
+    /// `Ord` as well as `PartialOrd`.
+    pub fn is_totally_ordered(&self) -> bool {
+        self.totally_ordered
     }
 
     pub fn get_definition(&self) -> &str {
         &self.parsed.source
     }
 
+    /// Expands the definition of this message the way `rosmsg show pkg/Type` prints it: constants
+    /// then fields, one per line with comments and blank lines removed, each message typed field
+    /// followed by the fields of its type indented by two spaces.
+    ///
+    /// `messages` is searched for the types of nested fields, e.g. the output of
+    /// [resolve_dependency_graph]. Returns None if one of them can't be found.
+    pub fn rosmsg_show(&self, messages: &[MessageFile]) -> Option<String> {
+        let mut text = String::new();
+        self.write_rosmsg_show(messages, "", &mut text)?;
+        Some(text)
+    }
+
+    fn write_rosmsg_show(
+        &self,
+        messages: &[MessageFile],
+        indent: &str,
+        text: &mut String,
+    ) -> Option<()> {
+        for constant in self.get_constants() {
+            text.push_str(&format!(
+                "{indent}{} {}={}\n",
+                constant.constant_type, constant.constant_name, constant.constant_value
+            ));
+        }
+        for field in self.get_fields() {
+            if field.field_type.package_name.is_none() {
+                text.push_str(&format!(
+                    "{indent}{} {}\n",
+                    field.field_type, field.field_name
+                ));
+                continue;
+            }
+            let full_name = field.get_full_name();
+            // rosmsg names nested types in full, whether or not the definition did
+            let full_type = FieldType {
+                field_type: full_name.clone(),
+                ..field.field_type.clone()
+            };
+            text.push_str(&format!("{indent}{full_type} {}\n", field.field_name));
+            let nested = messages
+                .iter()
+                .find(|msg| msg.get_full_name() == full_name)?;
+            nested.write_rosmsg_show(messages, &format!("{indent}  "), text)?;
+        }
+        Some(())
+    }
+
     fn compute_md5sum(
         parsed: &ParsedMessageFile,
         graph: &BTreeMap<String, MessageFile>,
@@ -152,31 +283,61 @@ impl MessageFile {
         Some(md5sum_content)
     }
 
-    fn determine_if_fixed_length(
+    /// Returns None if a dependency could not be resolved, and Some(None) if the message
+    /// is not fixed length
+    fn compute_fixed_size(
+        parsed: &ParsedMessageFile,
+        graph: &BTreeMap<String, MessageFile>,
+    ) -> Option<Option<usize>> {
+        let mut size = 0;
+        for field in &parsed.fields {
+            let element_size = if field.field_type.package_name.is_none() {
+                Self::primitive_size(field.field_type.field_type.as_str())
+            } else {
+                let field_msg = graph.get(field.get_full_name().as_str())?;
+                Self::compute_fixed_size(&field_msg.parsed, graph)?
+            };
+            let field_size = match (field.field_type.array_info, element_size) {
+                (None, Some(element_size)) => element_size,
+                // Fixed length arrays are serialized without a length prefix
+                (Some(Some(len)), Some(element_size)) => len * element_size,
+                _ => return Some(None),
+            };
+            size += field_size;
+        }
+        Some(Some(size))
+    }
+
+    /// Returns None if a dependency could not be resolved, otherwise whether the message has no
+    /// floating point fields, directly or in nested messages, which would prevent deriving `Ord`
+    fn compute_totally_ordered(
         parsed: &ParsedMessageFile,
         graph: &BTreeMap<String, MessageFile>,
     ) -> Option<bool> {
         for field in &parsed.fields {
-            if matches!(field.field_type.array_info, Some(Some(_))) {
-                return Some(true);
-            } else if matches!(field.field_type.array_info, Some(None)) {
-                return Some(false);
-            }
-            if field.field_type.package_name.is_none() {
-                if field.field_type.field_type == "string" {
-                    return Some(false);
-                }
+            let ordered = if field.field_type.package_name.is_none() {
+                !matches!(field.field_type.field_type.as_str(), "float32" | "float64")
             } else {
                 let field_msg = graph.get(field.get_full_name().as_str())?;
-                let field_is_fixed_length =
-                    Self::determine_if_fixed_length(&field_msg.parsed, graph)?;
-                if !field_is_fixed_length {
-                    return Some(false);
-                }
+                Self::compute_totally_ordered(&field_msg.parsed, graph)?
+            };
+            if !ordered {
+                return Some(false);
             }
         }
         Some(true)
     }
+
+    /// Size in bytes of a primitive type in ros1 serialization, None for strings
+    fn primitive_size(ros_type: &str) -> Option<usize> {
+        match ros_type {
+            "bool" | "int8" | "uint8" | "byte" | "char" => Some(1),
+            "int16" | "uint16" => Some(2),
+            "int32" | "uint32" | "float32" => Some(4),
+            "int64" | "uint64" | "float64" | "time" | "duration" => Some(8),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -279,14 +440,27 @@ pub struct FieldType {
     // Is Some(None) if it's an array type of variable size or Some(Some(N))
     // if it's an array type of fixed size.
     pub array_info: Option<Option<usize>>,
+    // The maximum length of a bounded variable size array, written `int32[<=3]` in ROS2.
+    // Bounded arrays have an array_info of Some(None).
+    array_bound: Option<usize>,
+}
+
+impl FieldType {
+    /// The maximum length of a bounded array field, None for every other kind of field
+    pub fn array_bound(&self) -> Option<usize> {
+        self.array_bound
+    }
 }
 
 impl std::fmt::Display for FieldType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self.array_info {
-            Some(Some(n)) => f.write_fmt(format_args!("{}[{}]", self.field_type, n)),
-            Some(None) => f.write_fmt(format_args!("{}[]", self.field_type)),
-            None => f.write_fmt(format_args!("{}", self.field_type)),
+        match (self.array_info, self.array_bound) {
+            (Some(Some(n)), _) => f.write_fmt(format_args!("{}[{}]", self.field_type, n)),
+            (Some(None), Some(bound)) => {
+                f.write_fmt(format_args!("{}[<={}]", self.field_type, bound))
+            }
+            (Some(None), None) => f.write_fmt(format_args!("{}[]", self.field_type)),
+            (None, _) => f.write_fmt(format_args!("{}", self.field_type)),
         }
     }
 }
@@ -298,6 +472,44 @@ pub struct FieldInfo {
     pub field_name: String,
     // Exists if this is a ros2 message field with a default value
     pub default: Option<RosLiteral>,
+    /// Exists if the field was annotated with a `# DEPRECATED` comment, holding any explanation
+    /// which followed it (possibly empty)
+    pub deprecated: Option<String>,
+    /// Constraints on the field's value from `# range:` or `# valid:` comment annotations
+    pub constraints: Vec<FieldConstraint>,
+}
+
+/// A constraint on the value of a field, parsed from a comment annotation in the message
+/// definition. For array fields the constraint applies to every element.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FieldConstraint {
+    /// `# range: [min, max]`, a square bracket includes the bound and a parenthesis excludes it
+    Range {
+        min: String,
+        max: String,
+        min_inclusive: bool,
+        max_inclusive: bool,
+    },
+    /// `# valid: a, b, c`, the field must hold one of the listed values
+    Valid(Vec<String>),
+}
+
+impl Display for FieldConstraint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FieldConstraint::Range {
+                min,
+                max,
+                min_inclusive,
+                max_inclusive,
+            } => {
+                let open = if *min_inclusive { '[' } else { '(' };
+                let close = if *max_inclusive { ']' } else { ')' };
+                write!(f, "range: {open}{min}, {max}{close}")
+            }
+            FieldConstraint::Valid(values) => write!(f, "valid: {}", values.join(", ")),
+        }
+    }
 }
 
 // Because TokenStream doesn't impl PartialEq we have to do it manually for FieldInfo
@@ -326,6 +538,8 @@ pub struct ConstantInfo {
     pub constant_type: String,
     pub constant_name: String,
     pub constant_value: RosLiteral,
+    /// The comment following the constant on its line in the message file, if any
+    pub comment: Option<String>,
 }
 
 // Because TokenStream doesn't impl PartialEq we have to do it manually for ConstantInfo
@@ -336,19 +550,100 @@ impl PartialEq for ConstantInfo {
     }
 }
 
+/// Selects which ROS packages are generated by [find_and_generate_filtered_ros_messages] and
+/// [find_and_generate_filtered_ros_messages_without_ros_package_path].
+///
+/// The default filter selects every package found. Packages that a selected package depends on
+/// are always pulled in, so `PackageFilter::include(["geometry_msgs"])` also generates
+/// `std_msgs`. Excluded packages are never generated, even as a dependency, in which case
+/// generation fails on the unresolved reference.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PackageFilter {
+    include: Option<BTreeSet<String>>,
+    exclude: BTreeSet<String>,
+}
+
+impl PackageFilter {
+    /// Creates a filter selecting only the given packages and their dependencies
+    pub fn include<I, S>(packages: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        PackageFilter {
+            include: Some(packages.into_iter().map(Into::into).collect()),
+            exclude: BTreeSet::new(),
+        }
+    }
+
+    /// Skips the given packages in addition to anything this filter already skips
+    pub fn exclude<I, S>(mut self, packages: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.exclude.extend(packages.into_iter().map(Into::into));
+        self
+    }
+
+    /// True if the named package is selected directly, ignoring dependencies
+    pub fn selects(&self, package: &str) -> bool {
+        !self.exclude.contains(package)
+            && self
+                .include
+                .as_ref()
+                .map_or(true, |include| include.contains(package))
+    }
+}
+
+/// Selects the generated types re-exported from a `prelude` module generated alongside the module
+/// for each package, so they can be brought into scope with `use my_msgs::prelude::*` rather
+/// than named by their full paths, see [find_and_generate_ros_messages_with_prelude].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Prelude {
+    /// Every generated message and service type, except for names used by more than one package,
+    /// which would be ambiguous and are left out
+    All,
+    /// Only the listed types, given by their full ROS name e.g. `std_msgs/Header`. The request
+    /// and response of a service are listed separately, e.g. `std_srvs/TriggerRequest`.
+    /// Generation fails if a type listed isn't generated, or two types share a name.
+    Types(Vec<String>),
+}
+
 /// Searches a list of paths for ROS packages and generates struct definitions
 /// and implementations for message files and service files in packages it finds.
 /// Returns a tuple of the generated source code and list of file system paths that if
 /// modified would trigger re-generation of the source. This function is designed to
 /// be used either in a build.rs file or via the roslibrust_codegen_macro crate.
 /// * `additional_search_paths` - A list of additional paths to search beyond those
-/// found in ROS_PACKAGE_PATH environment variable.
+/// found in the environment, see [utils::get_search_paths].
 pub fn find_and_generate_ros_messages(
     additional_search_paths: Vec<PathBuf>,
+) -> Result<(TokenStream, Vec<PathBuf>), Error> {
+    find_and_generate_filtered_ros_messages(additional_search_paths, &PackageFilter::default())
+}
+
+/// Same as [find_and_generate_ros_messages], but only generates the packages selected by `filter`
+/// and the packages they depend on.
+pub fn find_and_generate_filtered_ros_messages(
+    additional_search_paths: Vec<PathBuf>,
+    filter: &PackageFilter,
 ) -> Result<(TokenStream, Vec<PathBuf>), Error> {
     let mut ros_package_paths = utils::get_search_paths();
     ros_package_paths.extend(additional_search_paths);
-    find_and_generate_ros_messages_without_ros_package_path(ros_package_paths)
+    find_and_generate_filtered_ros_messages_without_ros_package_path(ros_package_paths, filter)
+}
+
+/// Same as [find_and_generate_filtered_ros_messages], additionally generating a `prelude` module
+/// re-exporting the types selected by `prelude`.
+pub fn find_and_generate_ros_messages_with_prelude(
+    additional_search_paths: Vec<PathBuf>,
+    filter: &PackageFilter,
+    prelude: &Prelude,
+) -> Result<(TokenStream, Vec<PathBuf>), Error> {
+    let mut ros_package_paths = utils::get_search_paths();
+    ros_package_paths.extend(additional_search_paths);
+    generate_from_search_paths(ros_package_paths, filter, Some(prelude))
 }
 
 /// Searches a list of paths for ROS packages and generates struct definitions
@@ -361,13 +656,34 @@ pub fn find_and_generate_ros_messages(
 pub fn find_and_generate_ros_messages_without_ros_package_path(
     search_paths: Vec<PathBuf>,
 ) -> Result<(TokenStream, Vec<PathBuf>), Error> {
-    let (messages, services, actions) = find_and_parse_ros_messages(&search_paths)?;
+    find_and_generate_filtered_ros_messages_without_ros_package_path(
+        search_paths,
+        &PackageFilter::default(),
+    )
+}
+
+/// Same as [find_and_generate_ros_messages_without_ros_package_path], but only generates the
+/// packages selected by `filter` and the packages they depend on.
+pub fn find_and_generate_filtered_ros_messages_without_ros_package_path(
+    search_paths: Vec<PathBuf>,
+    filter: &PackageFilter,
+) -> Result<(TokenStream, Vec<PathBuf>), Error> {
+    generate_from_search_paths(search_paths, filter, None)
+}
+
+fn generate_from_search_paths(
+    search_paths: Vec<PathBuf>,
+    filter: &PackageFilter,
+    prelude: Option<&Prelude>,
+) -> Result<(TokenStream, Vec<PathBuf>), Error> {
+    let (messages, services, actions) =
+        find_and_parse_filtered_ros_messages(&search_paths, filter)?;
     if messages.is_empty() && services.is_empty() {
         // I'm considering this an error for now, but I could see this one being debateable
         // As it stands there is not good way for us to manually produce a warning, so I'd rather fail loud
         bail!("Failed to find any services or messages while generating ROS message definitions, paths searched: {search_paths:?}");
     }
-    tokenize_messages_and_services(messages, services, actions)
+    tokenize_messages_and_services(messages, services, actions, prelude)
 }
 
 /// Generates source code and list of depnendent file system paths
@@ -375,13 +691,14 @@ fn tokenize_messages_and_services(
     messages: Vec<ParsedMessageFile>,
     services: Vec<ParsedServiceFile>,
     actions: Vec<ParsedActionFile>,
+    prelude: Option<&Prelude>,
 ) -> Result<(TokenStream, Vec<PathBuf>), Error> {
     let (messages, services) = resolve_dependency_graph(messages, services)?;
     let msg_iter = messages.iter().map(|m| m.parsed.path.clone());
     let srv_iter = services.iter().map(|s| s.parsed.path.clone());
     let action_iter = actions.iter().map(|a| a.path.clone());
     let dependent_paths = msg_iter.chain(srv_iter).chain(action_iter).collect();
-    let source = generate_rust_ros_message_definitions(messages, services)?;
+    let source = generate_definitions(messages, services, actions, prelude)?;
     Ok((source, dependent_paths))
 }
 
@@ -405,9 +722,16 @@ pub fn generate_ros_messages_for_packages(
     if messages.is_empty() && services.is_empty() {
         bail!("Failed to find any services or messages while generating ROS message definitions, packages searched: {packages:?}")
     }
-    tokenize_messages_and_services(messages, services, actions)
+    tokenize_messages_and_services(messages, services, actions, None)
 }
 
+/// The messages, services, and actions found by [find_and_parse_ros_messages]
+type ParsedFiles = (
+    Vec<ParsedMessageFile>,
+    Vec<ParsedServiceFile>,
+    Vec<ParsedActionFile>,
+);
+
 /// Searches a list of paths for ROS packages to find their associated message
 /// and service files, parsing and performing dependency resolution on those
 /// it finds. Returns a map of PACKAGE_NAME/MESSAGE_NAME strings to message file
@@ -415,16 +739,84 @@ pub fn generate_ros_messages_for_packages(
 ///
 /// * `search_paths` - A list of paths to search.
 ///
-pub fn find_and_parse_ros_messages(
+pub fn find_and_parse_ros_messages(search_paths: &Vec<PathBuf>) -> Result<ParsedFiles, Error> {
+    let message_files = find_ros_files(search_paths)?;
+    parse_ros_files(message_files)
+}
+
+/// Same as [find_and_parse_ros_messages], but only parses the packages selected by `filter` and
+/// the packages they depend on.
+pub fn find_and_parse_filtered_ros_messages(
     search_paths: &Vec<PathBuf>,
-) -> Result<
-    (
-        Vec<ParsedMessageFile>,
-        Vec<ParsedServiceFile>,
-        Vec<ParsedActionFile>,
-    ),
-    Error,
-> {
+    filter: &PackageFilter,
+) -> Result<ParsedFiles, Error> {
+    let packages = find_packages(search_paths)?;
+    if let Some(include) = &filter.include {
+        for name in include {
+            if !packages.iter().any(|pkg| &pkg.name == name) {
+                warn!("Package {name} was selected for generation but was not found in {search_paths:?}");
+            }
+        }
+    }
+
+    // Parse the selected packages, then keep pulling in the packages they reference until
+    // nothing new is needed. Excluded packages are left for dependency resolution to report.
+    let mut wanted: BTreeSet<String> = packages
+        .iter()
+        .filter(|pkg| filter.selects(&pkg.name))
+        .map(|pkg| pkg.name.clone())
+        .collect();
+    let mut parsed = BTreeSet::new();
+    let (mut messages, mut services, mut actions) = (Vec::new(), Vec::new(), Vec::new());
+    loop {
+        let next: Vec<Package> = packages
+            .iter()
+            .filter(|pkg| wanted.contains(&pkg.name) && !parsed.contains(&pkg.name))
+            .cloned()
+            .collect();
+        if next.is_empty() {
+            break;
+        }
+        let (new_messages, new_services, new_actions) = parse_ros_files(message_files_for(&next)?)?;
+        // Parsing an action adds the messages generated for it to new_messages, so the
+        // actionlib_msgs and std_msgs/Header they use are followed like any other dependency
+        let fields = new_messages
+            .iter()
+            .chain(
+                new_services
+                    .iter()
+                    .flat_map(|srv| [&srv.request_type, &srv.response_type]),
+            )
+            .flat_map(|msg| msg.fields.iter());
+        for field in fields {
+            let dependency = match (
+                &field.field_type.package_name,
+                field.field_type.field_type.as_str(),
+            ) {
+                (_, "Header") => "std_msgs",
+                (Some(package), _) => package.as_str(),
+                (None, _) => continue,
+            };
+            if !filter.exclude.contains(dependency) {
+                wanted.insert(dependency.to_owned());
+            }
+        }
+        parsed.extend(next.into_iter().map(|pkg| pkg.name));
+        messages.extend(new_messages);
+        services.extend(new_services);
+        actions.extend(new_actions);
+    }
+    Ok((messages, services, actions))
+}
+
+/// Searches a list of paths for ROS packages and returns the paths of all message, service, and
+/// action files found in them, paired with the package they belong to.
+fn find_ros_files(search_paths: &Vec<PathBuf>) -> Result<Vec<(Package, PathBuf)>, Error> {
+    message_files_for(&find_packages(search_paths)?)
+}
+
+/// Searches a list of paths for ROS packages, failing if none are found
+fn find_packages(search_paths: &Vec<PathBuf>) -> Result<Vec<Package>, Error> {
     let search_paths  = search_paths
         .into_iter()
         .map(|path| {
@@ -447,7 +839,12 @@ pub fn find_and_parse_ros_messages(
             std::env::current_dir().unwrap()
         );
     }
+    Ok(packages)
+}
 
+/// Returns the paths of all message, service, and action files in the given packages, paired
+/// with the package they belong to.
+fn message_files_for(packages: &[Package]) -> Result<Vec<(Package, PathBuf)>, Error> {
     let message_files = packages
         .iter()
         .flat_map(|pkg| {
@@ -468,7 +865,7 @@ pub fn find_and_parse_ros_messages(
         })
         .collect::<Result<Vec<(Package, PathBuf)>, Error>>()?;
 
-    parse_ros_files(message_files)
+    Ok(message_files)
 }
 
 /// Takes in collections of ROS message and ROS service data and generates Rust
@@ -479,40 +876,85 @@ pub fn find_and_parse_ros_messages(
 ///
 /// * `messages` - Collection of ROS message definition data.
 /// * `services` - Collection of ROS service definition data.
+/// * `actions` - Collection of ROS action definition data, the messages making up each action are
+///   expected to already be part of `messages`.
 pub fn generate_rust_ros_message_definitions(
     messages: Vec<MessageFile>,
     services: Vec<ServiceFile>,
+    actions: Vec<ParsedActionFile>,
+) -> Result<TokenStream, Error> {
+    generate_definitions(messages, services, actions, None)
+}
+
+/// Same as [generate_rust_ros_message_definitions], additionally generating a `prelude` module
+/// re-exporting the types selected by `prelude`.
+pub fn generate_rust_ros_message_definitions_with_prelude(
+    messages: Vec<MessageFile>,
+    services: Vec<ServiceFile>,
+    actions: Vec<ParsedActionFile>,
+    prelude: &Prelude,
+) -> Result<TokenStream, Error> {
+    generate_definitions(messages, services, actions, Some(prelude))
+}
+
+fn generate_definitions(
+    messages: Vec<MessageFile>,
+    services: Vec<ServiceFile>,
+    actions: Vec<ParsedActionFile>,
+    prelude: Option<&Prelude>,
 ) -> Result<TokenStream, Error> {
+    // Every type generated, by package and name, for the prelude to choose from
+    let types: Vec<(String, String)> = messages
+        .iter()
+        .map(|msg| (msg.parsed.package.clone(), msg.parsed.name.clone()))
+        .chain(services.iter().flat_map(|srv| {
+            [
+                &srv.parsed.name,
+                &srv.parsed.request_type.name,
+                &srv.parsed.response_type.name,
+            ]
+            .map(|name| (srv.parsed.package.clone(), name.clone()))
+        }))
+        .collect();
+    let prelude = prelude
+        .map(|prelude| generate_prelude(&types, prelude))
+        .transpose()?;
+
     let mut modules_to_struct_definitions: BTreeMap<String, Vec<TokenStream>> = BTreeMap::new();
 
     // Convert messages files into rust token streams and insert them into BTree organized by package
-    messages
-        .into_iter()
-        .map(|message| {
-            let pkg_name = message.parsed.package.clone();
-            let definition = generate_struct(message)?;
-            if let Some(entry) = modules_to_struct_definitions.get_mut(&pkg_name) {
-                entry.push(definition);
-            } else {
-                modules_to_struct_definitions.insert(pkg_name, vec![definition]);
-            }
-            Ok(())
-        })
-        .collect::<Result<_, Error>>()?;
+    let message_pkgs = messages
+        .iter()
+        .map(|message| message.parsed.package.clone())
+        .collect::<Vec<_>>();
+    let message_definitions = generate_each(messages, generate_struct)?;
+    for (pkg_name, definition) in message_pkgs.into_iter().zip(message_definitions) {
+        modules_to_struct_definitions
+            .entry(pkg_name)
+            .or_default()
+            .push(definition);
+    }
     // Do the same for services
-    services
-        .into_iter()
-        .map(|service| {
-            let pkg_name = service.parsed.package.clone();
-            let definition = generate_service(service)?;
-            if let Some(entry) = modules_to_struct_definitions.get_mut(&pkg_name) {
-                entry.push(definition);
-            } else {
-                modules_to_struct_definitions.insert(pkg_name, vec![definition]);
-            }
-            Ok(())
-        })
-        .collect::<Result<_, Error>>()?;
+    let service_pkgs = services
+        .iter()
+        .map(|service| service.parsed.package.clone())
+        .collect::<Vec<_>>();
+    let service_definitions = generate_each(services, generate_service)?;
+    for (pkg_name, definition) in service_pkgs.into_iter().zip(service_definitions) {
+        modules_to_struct_definitions
+            .entry(pkg_name)
+            .or_default()
+            .push(definition);
+    }
+    // And for actions
+    for action in actions {
+        let pkg_name = action.package.clone();
+        let definition = generate_action(action);
+        modules_to_struct_definitions
+            .entry(pkg_name)
+            .or_default()
+            .push(definition);
+    }
     // Now generate modules to wrap all of the TokenStreams in a module for each package
     let all_pkgs = modules_to_struct_definitions
         .keys()
@@ -526,9 +968,74 @@ pub fn generate_rust_ros_message_definitions(
     Ok(quote! {
         #(#module_definitions)*
 
+        #prelude
     })
 }
 
+/// Generates a definition for each item, in the same order as the items
+#[cfg(not(feature = "parallel-codegen"))]
+fn generate_each<T>(
+    items: Vec<T>,
+    generate: fn(T) -> Result<TokenStream, Error>,
+) -> Result<Vec<TokenStream>, Error> {
+    items.into_iter().map(generate).collect()
+}
+
+/// Generates a definition for each item in parallel, in the same order as the items.
+///
+/// Inside a proc macro they're generated one at a time instead, as proc_macro2 can only be used
+/// on the thread the compiler invoked the macro on.
+#[cfg(feature = "parallel-codegen")]
+fn generate_each<T: Send>(
+    items: Vec<T>,
+    generate: fn(T) -> Result<TokenStream, Error>,
+) -> Result<Vec<TokenStream>, Error> {
+    use rayon::prelude::*;
+    use std::str::FromStr;
+    if proc_macro::is_available() {
+        return items.into_iter().map(generate).collect();
+    }
+    // Token streams can't be sent between threads, so they're passed back as source and re-parsed
+    let sources = items
+        .into_par_iter()
+        .map(|item| generate(item).map(|definition| definition.to_string()))
+        .collect::<Result<Vec<_>, Error>>()?;
+    sources
+        .iter()
+        .map(|source| {
+            TokenStream::from_str(source).map_err(|err| {
+                Error::new(format!(
+                    "Failed to re-parse generated definition: {err}\n{source}"
+                ))
+            })
+        })
+        .collect()
+}
+
+/// Lists every constant defined by `messages` as `(type_name, constant_name, value)`, in the order
+/// the messages are given and the constants appear in each, e.g. for building a table of the names
+/// of `diagnostic_msgs/DiagnosticStatus` levels.
+///
+/// Services' constants are in their request and response messages, which can be chained on:
+/// `messages.iter().chain(services.iter().flat_map(|srv| [srv.request(), srv.response()]))`.
+pub fn collect_constants<'a>(
+    messages: impl IntoIterator<Item = &'a MessageFile>,
+) -> Vec<(String, String, RosLiteral)> {
+    messages
+        .into_iter()
+        .flat_map(|msg| {
+            let type_name = msg.get_full_name();
+            msg.get_constants().iter().map(move |constant| {
+                (
+                    type_name.clone(),
+                    constant.constant_name.clone(),
+                    constant.constant_value.clone(),
+                )
+            })
+        })
+        .collect()
+}
+
 struct MessageMetadata {
     msg: ParsedMessageFile,
     seen_count: u32,
@@ -601,68 +1108,144 @@ pub fn resolve_dependency_graph(
 /// The returned collection will contain all messages files including those buried with the
 /// service or action files, and will have fully expanded and resolved referenced types in other packages.
 /// * `msg_paths` -- List of tuple (Package, Path to File) for each file to parse
-fn parse_ros_files(
-    msg_paths: Vec<(Package, PathBuf)>,
-) -> Result<
-    (
-        Vec<ParsedMessageFile>,
-        Vec<ParsedServiceFile>,
-        Vec<ParsedActionFile>,
-    ),
-    Error,
-> {
+fn parse_ros_files(msg_paths: Vec<(Package, PathBuf)>) -> Result<ParsedFiles, Error> {
+    // Each file is parsed on its own, then merged in order so the output doesn't depend on which
+    // file finished parsing first. Parsing creates no tokens, so unlike generation it can run in
+    // parallel inside a proc macro too.
+    let parse = |(pkg, path): (Package, PathBuf)| {
+        let (mut messages, mut services, mut actions) = (Vec::new(), Vec::new(), Vec::new());
+        parse_ros_file(&pkg, &path, &mut messages, &mut services, &mut actions)?;
+        Ok((messages, services, actions))
+    };
+    #[cfg(feature = "parallel-codegen")]
+    let parsed_files = {
+        use rayon::prelude::*;
+        msg_paths
+            .into_par_iter()
+            .map(parse)
+            .collect::<Result<Vec<_>, Error>>()?
+    };
+    #[cfg(not(feature = "parallel-codegen"))]
+    let parsed_files = msg_paths
+        .into_iter()
+        .map(parse)
+        .collect::<Result<Vec<_>, Error>>()?;
     let mut parsed_messages = Vec::new();
     let mut parsed_services = Vec::new();
     let mut parsed_actions = Vec::new();
-    for (pkg, path) in msg_paths {
-        let contents = std::fs::read_to_string(&path).map_err(|e| {
-            Error::with(
-                format!("Codgen failed while attempting to read file {path:?} from disk:").as_str(),
-                e,
-            )
-        })?;
-        // Probably being overly aggressive with error shit here, but I'm on a kick
-        let name = path
-            .file_stem()
-            .ok_or(Error::new(format!(
-                "Failed to extract valid file stem for file at {path:?}"
-            )))?
-            .to_str()
-            .ok_or(Error::new(format!(
-                "File stem for file at path {path:?} was not valid unicode?"
-            )))?;
-        match path.extension().unwrap().to_str().unwrap() {
-            "srv" => {
-                let srv_file = parse_ros_service_file(&contents, name, &pkg, &path)?;
-                parsed_services.push(srv_file);
-                // TODO ask shane, shouldn't we be pushing request and response to messages here?
-            }
-            "msg" => {
-                let msg = parse_ros_message_file(&contents, name, &pkg, &path)?;
-                parsed_messages.push(msg);
-            }
-            "action" => {
-                let action = parse_ros_action_file(&contents, name, &pkg, &path)?;
-                parsed_actions.push(action.clone());
-                parsed_messages.push(action.action_type);
-                parsed_messages.push(action.action_goal_type);
-                parsed_messages.push(action.goal_type);
-                parsed_messages.push(action.action_result_type);
-                parsed_messages.push(action.result_type);
-                parsed_messages.push(action.action_feedback_type);
-                parsed_messages.push(action.feedback_type);
-            }
-            _ => {
-                log::error!("File extension not recognized as a ROS file: {path:?}");
-            }
-        }
+    for (messages, services, actions) in parsed_files {
+        parsed_messages.extend(messages);
+        parsed_services.extend(services);
+        parsed_actions.extend(actions);
     }
+    parse::resolve_same_package_references(
+        &mut parsed_messages,
+        &mut parsed_services,
+        &mut parsed_actions,
+    );
     Ok((parsed_messages, parsed_services, parsed_actions))
 }
 
+/// Parses a single ROS file, adding the messages, services, and actions it defines to the given
+/// collections. Action files also add each of the messages generated for the action to `messages`.
+fn parse_ros_file(
+    pkg: &Package,
+    path: &Path,
+    messages: &mut Vec<ParsedMessageFile>,
+    services: &mut Vec<ParsedServiceFile>,
+    actions: &mut Vec<ParsedActionFile>,
+) -> Result<(), Error> {
+    let contents = std::fs::read_to_string(path).map_err(|e| {
+        Error::with(
+            format!("Codgen failed while attempting to read file {path:?} from disk:").as_str(),
+            e,
+        )
+    })?;
+    // Probably being overly aggressive with error shit here, but I'm on a kick
+    let name = path
+        .file_stem()
+        .ok_or(Error::new(format!(
+            "Failed to extract valid file stem for file at {path:?}"
+        )))?
+        .to_str()
+        .ok_or(Error::new(format!(
+            "File stem for file at path {path:?} was not valid unicode?"
+        )))?;
+    match path.extension().unwrap().to_str().unwrap() {
+        "srv" => {
+            let srv_file = parse_ros_service_file(&contents, name, pkg, path)?;
+            services.push(srv_file);
+            // TODO ask shane, shouldn't we be pushing request and response to messages here?
+        }
+        "msg" => {
+            let msg = parse_ros_message_file(&contents, name, pkg, path)?;
+            messages.push(msg);
+        }
+        "action" => {
+            let action = parse_ros_action_file(&contents, name, pkg, path)?;
+            actions.push(action.clone());
+            messages.push(action.action_type);
+            messages.push(action.action_goal_type);
+            messages.push(action.goal_type);
+            messages.push(action.action_result_type);
+            messages.push(action.result_type);
+            messages.push(action.action_feedback_type);
+            messages.push(action.feedback_type);
+        }
+        _ => {
+            log::error!("File extension not recognized as a ROS file: {path:?}");
+        }
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod test {
-    use crate::find_and_generate_ros_messages;
+    use crate::utils::{Package, RosVersion};
+    use crate::{
+        find_and_generate_filtered_ros_messages_without_ros_package_path,
+        find_and_generate_ros_messages, find_and_parse_filtered_ros_messages, PackageFilter,
+        Prelude,
+    };
+    use crate::{Error, ParsedMessageFile};
+
+    /// Parses `definition` as the message `package/name`, as if it were found in `./not_a_path`
+    fn parse_msg(
+        package: &str,
+        version: RosVersion,
+        name: &str,
+        definition: &str,
+    ) -> ParsedMessageFile {
+        let pkg = Package {
+            name: package.to_string(),
+            path: "./not_a_path".into(),
+            version: Some(version),
+        };
+        crate::parse_ros_message_file(
+            definition,
+            name,
+            &pkg,
+            &std::path::Path::new("./not_a_path/msg").join(format!("{name}.msg")),
+        )
+        .unwrap()
+    }
+
+    /// Generates the source for `msgs` after resolving their dependencies on each other
+    fn generate_msgs(msgs: Vec<ParsedMessageFile>) -> Result<String, Error> {
+        let (messages, services) = crate::resolve_dependency_graph(msgs, vec![])?;
+        crate::generate_rust_ros_message_definitions(messages, services, vec![])
+            .map(|source| source.to_string())
+    }
+
+    /// Generates the source for the single message `package/name` defined by `definition`
+    fn generate_from_str(
+        package: &str,
+        version: RosVersion,
+        name: &str,
+        definition: &str,
+    ) -> String {
+        generate_msgs(vec![parse_msg(package, version, name, definition)]).unwrap()
+    }
 
     /// Confirms we don't panic on ros1 parsing
     #[test_log::test]
@@ -679,6 +1262,31 @@ mod test {
         assert!(!paths.is_empty());
     }
 
+    /// Confirms generating on many threads gives exactly the same output as on one
+    #[test_log::test]
+    #[cfg(feature = "parallel-codegen")]
+    fn parallel_generation_is_deterministic() {
+        let assets_path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/../assets/ros1_common_interfaces"
+        );
+        let generate = |threads| {
+            let pool = rayon::ThreadPoolBuilder::new()
+                .num_threads(threads)
+                .build()
+                .unwrap();
+            pool.install(|| {
+                let (source, paths) =
+                    crate::find_and_generate_ros_messages_without_ros_package_path(vec![
+                        assets_path.into(),
+                    ])
+                    .unwrap();
+                (source.to_string(), paths)
+            })
+        };
+        assert_eq!(generate(1), generate(4));
+    }
+
     /// Confirms we don't panic on ros2 parsing
     #[test_log::test]
     fn generate_ok_on_ros2() {
@@ -722,4 +1330,571 @@ mod test {
         assert!(!source.is_empty());
         assert!(!paths.is_empty());
     }
+
+    /// Confirms the action trait implementation ties together the messages generated for an action
+    #[test_log::test]
+    fn generate_action_implements_action_trait() {
+        let pkg = Package {
+            name: "actionlib_tutorials".to_string(),
+            path: "./not_a_path".into(),
+            version: Some(RosVersion::ROS1),
+        };
+        let action = crate::parse_ros_action_file(
+            "int32 order\n---\nint32[] sequence\n---\nint32[] sequence\n",
+            "Fibonacci",
+            &pkg,
+            std::path::Path::new("./not_a_path/action/Fibonacci.action"),
+        )
+        .unwrap();
+        let source = crate::gen::generate_action(action).to_string();
+        assert!(source.contains("RosActionType for FibonacciAction"));
+        assert!(source.contains("\"actionlib_tutorials/Fibonacci\""));
+        assert!(source.contains("type Goal = FibonacciGoal"));
+        assert!(source.contains("type ActionFeedback = FibonacciActionFeedback"));
+    }
+
+    /// Confirms fields annotated as deprecated in the message definition are deprecated in rust
+    #[test_log::test]
+    fn generate_deprecated_fields() {
+        let source = generate_from_str(
+            "geometry_msgs",
+            RosVersion::ROS1,
+            "Point",
+            "float64 x # DEPRECATED: use position instead\n# DEPRECATED\nfloat64 y\nfloat64 position",
+        );
+        assert!(source.contains("# [deprecated (note = \"use position instead\")] pub r#x"));
+        assert!(source.contains("# [deprecated] pub r#y"));
+        assert!(source.contains("pub r#position"));
+        assert_eq!(source.matches("deprecated").count(), 3, "{source}");
+    }
+
+    #[test_log::test]
+    fn collect_constants_across_messages() {
+        let parse = |name: &str, definition: &str| {
+            parse_msg("diagnostic_msgs", RosVersion::ROS1, name, definition)
+        };
+        let status = parse(
+            "DiagnosticStatus",
+            "byte OK=0\nbyte WARN=1\nbyte ERROR=2\nbyte level\nstring name",
+        );
+        let key_value = parse("KeyValue", "string key\nstring value");
+        let mode = parse("Mode", "string DEFAULT_MODE=\"auto\"\nstring mode");
+        let (messages, _) =
+            crate::resolve_dependency_graph(vec![status, key_value, mode], vec![]).unwrap();
+
+        let constants: Vec<_> = crate::collect_constants(&messages)
+            .into_iter()
+            .map(|(type_name, name, value)| (type_name, name, value.to_string()))
+            .collect();
+        let expected = [
+            ("diagnostic_msgs/DiagnosticStatus", "OK", "0"),
+            ("diagnostic_msgs/DiagnosticStatus", "WARN", "1"),
+            ("diagnostic_msgs/DiagnosticStatus", "ERROR", "2"),
+            ("diagnostic_msgs/Mode", "DEFAULT_MODE", "\"auto\""),
+        ];
+        let expected: Vec<_> = expected
+            .into_iter()
+            .map(|(type_name, name, value)| {
+                (type_name.to_owned(), name.to_owned(), value.to_owned())
+            })
+            .collect();
+        assert_eq!(constants, expected);
+    }
+
+    /// Confirms constants are documented with their value and the comment following them
+    #[test_log::test]
+    fn generate_constant_docs() {
+        let msg = parse_msg(
+            "rosgraph_msgs",
+            RosVersion::ROS1,
+            "Log",
+            "byte DEBUG=1 # DEBUG level\nbyte INFO=2\nstring NAME=\"log\" #\nbyte level",
+        );
+        assert_eq!(msg.constants[0].comment.as_deref(), Some("DEBUG level"));
+        assert_eq!(msg.constants[2].comment, None);
+        let source = generate_msgs(vec![msg]).unwrap();
+        assert!(
+            source.contains("# [doc = \" = 1 (DEBUG level)\"] pub const r#DEBUG"),
+            "{source}"
+        );
+        assert!(source.contains("# [doc = \" = 2\"] pub const r#INFO"));
+        assert!(source.contains("# [doc = \" = \\\"log\\\"\"] pub const r#NAME"));
+    }
+
+    /// Confirms comments wrapped onto the following lines stay with their constant
+    #[test_log::test]
+    fn constant_comments_continue_on_indented_lines() {
+        let msg = parse_msg(
+            "actionlib_msgs",
+            RosVersion::ROS1,
+            "GoalStatus",
+            concat!(
+                "uint8 SUCCEEDED = 3 # The goal was achieved (Terminal State)\n",
+                "uint8 ABORTED   = 4 # The goal was aborted during execution due\n",
+                "                    #    to some failure (Terminal State)\n",
+                "uint8 LOST      = 9\n",
+                "                    # Not a continuation, LOST has no comment\n",
+                "uint8 RECALLED  = 8 # The goal was cancelled\n",
+                "# The status of the goal\n",
+                "uint8 status\n",
+            ),
+        );
+        let comments: Vec<_> = msg
+            .constants
+            .iter()
+            .map(|constant| constant.comment.as_deref())
+            .collect();
+        assert_eq!(
+            comments,
+            [
+                Some("The goal was achieved (Terminal State)"),
+                Some("The goal was aborted during execution due to some failure (Terminal State)"),
+                None,
+                Some("The goal was cancelled"),
+            ]
+        );
+    }
+
+    /// Confirms nested types are expanded in place as `rosmsg show` does, and that every nested
+    /// type must be given
+    #[test_log::test]
+    fn rosmsg_show_expands_nested_types() {
+        let parse = |package: &str, definition: &str, name: &str| {
+            parse_msg(package, RosVersion::ROS1, name, definition)
+        };
+        let msgs = vec![
+            parse(
+                "std_msgs",
+                "uint32 seq\ntime stamp\nstring frame_id",
+                "Header",
+            ),
+            parse("geometry_msgs", "float64 x\nfloat64 y\nfloat64 z", "Point"),
+            parse(
+                "nav_msgs",
+                "# A path for the robot to follow\n\nuint8 LOOSE=0 # Corners may be cut\nuint8 STRICT=1\n\nstd_msgs/Header header\ngeometry_msgs/Point[] poses\nfloat64[4] weights\nuint8 mode",
+                "Path",
+            ),
+        ];
+        let (messages, _services) = crate::resolve_dependency_graph(msgs, vec![]).unwrap();
+        let path = messages
+            .iter()
+            .find(|msg| msg.get_full_name() == "nav_msgs/Path")
+            .unwrap();
+        assert_eq!(
+            path.rosmsg_show(&messages).unwrap(),
+            "uint8 LOOSE=0
+uint8 STRICT=1
+std_msgs/Header header
+  uint32 seq
+  time stamp
+  string frame_id
+geometry_msgs/Point[] poses
+  float64 x
+  float64 y
+  float64 z
+float64[4] weights
+uint8 mode
+"
+        );
+
+        // Every nested type is needed
+        let header_less: Vec<_> = messages
+            .iter()
+            .filter(|msg| msg.get_short_name() != "Header")
+            .cloned()
+            .collect();
+        assert!(path.rosmsg_show(&header_less).is_none());
+    }
+
+    /// Confirms fixed sizes are computed through nested messages and fixed length arrays
+    #[test_log::test]
+    fn compute_fixed_size() {
+        let parse = |definition: &str, name: &str| {
+            parse_msg("geometry_msgs", RosVersion::ROS1, name, definition)
+        };
+        let msgs = vec![
+            parse("float64 x\nfloat64 y\nfloat64 z", "Point"),
+            parse("Point[2] ends\nuint8 flags\ntime stamp", "Segment"),
+            parse("uint8[4] tag\nstring label", "Labeled"),
+            parse("Segment segment\nint32[] ids", "Segments"),
+        ];
+        let source = generate_msgs(msgs.clone()).unwrap();
+        let (messages, _services) = crate::resolve_dependency_graph(msgs, vec![]).unwrap();
+        let size_of = |name: &str| {
+            messages
+                .iter()
+                .find(|msg| msg.get_short_name() == name)
+                .unwrap()
+                .get_fixed_size()
+        };
+        assert_eq!(size_of("Point"), Some(24));
+        assert_eq!(size_of("Segment"), Some(57));
+        assert_eq!(size_of("Labeled"), None);
+        assert_eq!(size_of("Segments"), None);
+        assert!(
+            source.contains("const FIXED_SIZE : :: std :: option :: Option < usize > = :: std :: option :: Option :: Some (57usize)"),
+            "{source}"
+        );
+        assert_eq!(source.matches("FIXED_SIZE").count(), 2);
+    }
+
+    /// Confirms Ord is only derived for messages without floats, directly or nested
+    #[test_log::test]
+    fn compute_totally_ordered() {
+        let parse = |definition: &str, name: &str| {
+            parse_msg("geometry_msgs", RosVersion::ROS1, name, definition)
+        };
+        let msgs = vec![
+            parse("float32 x\nfloat32 y", "Point"),
+            parse("Point[] points\nuint8 flags", "Polygon"),
+            parse("time stamp\nstring[] labels\nint64[3] ids", "Tag"),
+            parse("Tag tag\nbool visible", "Marker"),
+        ];
+        let source = generate_msgs(msgs.clone()).unwrap();
+        let (messages, _services) = crate::resolve_dependency_graph(msgs, vec![]).unwrap();
+        let ordered = |name: &str| {
+            messages
+                .iter()
+                .find(|msg| msg.get_short_name() == name)
+                .unwrap()
+                .is_totally_ordered()
+        };
+        assert!(!ordered("Point"));
+        assert!(!ordered("Polygon"));
+        assert!(ordered("Tag"));
+        assert!(ordered("Marker"));
+        assert_eq!(source.matches("PartialOrd").count(), 4, "{source}");
+        assert_eq!(source.matches("# [derive (Ord)]").count(), 2, "{source}");
+    }
+
+    /// Confirms size bounds are summed from each field, and constant for fixed size messages
+    #[test_log::test]
+    fn generate_size_upper_bound() {
+        let parse = |definition: &str, name: &str| {
+            parse_msg("shape_msgs", RosVersion::ROS2, name, definition)
+        };
+        let msgs = vec![
+            parse("float64 x\nfloat64 y", "Point"),
+            parse(
+                "Point[2] corners\nfloat64[<=3] dimensions\nstring label",
+                "Shape",
+            ),
+            parse("Shape[] shapes\nbyte[4] tag", "Shapes"),
+        ];
+        let source = generate_msgs(msgs).unwrap();
+
+        let fixed = match cfg!(feature = "fixed-array") {
+            true => "pub const fn serialized_size_upper_bound (& self) -> usize { 16usize }",
+            false => "pub fn serialized_size_upper_bound (& self) -> usize { 16usize }",
+        };
+        assert!(source.contains(fixed), "{source}");
+        // Bounded arrays of primitives are counted at their bound
+        assert!(
+            source.contains("self . r#corners . iter () . map (| element | element . serialized_size_upper_bound ()) . sum :: < usize > () + 4usize + self . r#dimensions . len () . max (3usize) * 8usize + 4usize + self . r#label . len ()"),
+            "{source}"
+        );
+        assert!(
+            source.contains("4usize + self . r#shapes . iter () . map (| element | element . serialized_size_upper_bound ()) . sum :: < usize > () + 4usize }"),
+            "{source}"
+        );
+    }
+
+    /// Confirms ROS2 default values generate for fixed size arrays and either style of string quotes
+    #[test_log::test]
+    fn generate_ros2_defaults() {
+        let generate = |definition: &str| {
+            generate_msgs(vec![parse_msg(
+                "test_msgs",
+                RosVersion::ROS2,
+                "Defaults",
+                definition,
+            )])
+        };
+
+        let source = generate(
+            "int32[3] fixed [1, 2, 3]\nstring[] names ['first', \"second\"]\nstring[2] pair ['a', 'b']",
+        )
+        .unwrap();
+        assert!(source.contains(r#"_code = "[1, 2, 3]""#), "{source}");
+        assert!(
+            source.contains(r#"[\"first\", \"second\"].iter().map(|x| x.to_string()).collect()"#),
+            "{source}"
+        );
+        assert!(
+            source.contains(r#"[\"a\", \"b\"].map(|x| x.to_string())"#),
+            "{source}"
+        );
+
+        // Values which don't fit the array's fixed size fail generation
+        assert!(generate("int32[2] fixed [1, 2, 3]").is_err());
+        assert!(generate("string[3] pair ['a', 'b']").is_err());
+        assert!(generate("string[] names ['unterminated]").is_err());
+    }
+
+    /// Confirms the prelude re-exports the selected types, leaving out ambiguous names
+    #[test_log::test]
+    fn generate_prelude() {
+        let parse = |package: &str, definition: &str, name: &str| {
+            parse_msg(package, RosVersion::ROS1, name, definition)
+        };
+        let generate = |prelude: &Prelude| {
+            let msgs = vec![
+                parse("geometry_msgs", "float64 x\nfloat64 y", "Point"),
+                parse("geometry_msgs", "Point position", "Pose"),
+                parse("turtlesim", "float32 x\nfloat32 y", "Pose"),
+            ];
+            let (messages, services) = crate::resolve_dependency_graph(msgs, vec![]).unwrap();
+            crate::generate_rust_ros_message_definitions_with_prelude(
+                messages,
+                services,
+                vec![],
+                prelude,
+            )
+            .map(|source| source.to_string())
+        };
+
+        let source = generate(&Prelude::All).unwrap();
+        assert!(
+            source.contains("pub mod prelude { pub use super :: geometry_msgs :: Point ; }"),
+            "{source}"
+        );
+
+        let source = generate(&Prelude::Types(vec!["turtlesim/Pose".to_owned()])).unwrap();
+        assert!(
+            source.contains("pub mod prelude { pub use super :: turtlesim :: Pose ; }"),
+            "{source}"
+        );
+
+        // Listing a type which isn't generated, or two with the same name, fails
+        assert!(generate(&Prelude::Types(vec!["std_msgs/Header".to_owned()])).is_err());
+        assert!(generate(&Prelude::Types(vec![
+            "geometry_msgs/Pose".to_owned(),
+            "turtlesim/Pose".to_owned()
+        ]))
+        .is_err());
+
+        // No prelude is generated unless asked for
+        let source = generate_from_str("std_msgs", RosVersion::ROS1, "String", "string data");
+        assert!(!source.contains("prelude"), "{source}");
+    }
+
+    /// Confirms unbounded byte arrays are generated as Bytes, which serializes the same as Vec<u8>
+    #[test_log::test]
+    #[cfg(feature = "bytes")]
+    fn generate_bytes_fields() {
+        let source = generate_from_str(
+            "test_msgs",
+            RosVersion::ROS2,
+            "Blob",
+            "uint8[] data [1, 2]\nbyte[] raw\nuint8[4] fixed\nint8[] signed",
+        );
+        for field in [
+            "pub r#data : :: roslibrust_codegen :: Bytes",
+            "pub r#raw : :: roslibrust_codegen :: Bytes",
+            "pub r#fixed : [u8 ; 4]",
+            "pub r#signed : :: std :: vec :: Vec < i8 >",
+        ] {
+            assert!(source.contains(field), "{field} not in {source}");
+        }
+        assert!(
+            source.contains(r#"_code = "::roslibrust_codegen::Bytes::from(vec![1, 2])""#),
+            "{source}"
+        );
+
+        let bytes = crate::Bytes::from(vec![1u8, 2]);
+        let json = serde_json::to_value(&bytes).unwrap();
+        assert_eq!(json, serde_json::to_value(vec![1u8, 2]).unwrap());
+        assert_eq!(serde_json::from_value::<crate::Bytes>(json).unwrap(), bytes);
+    }
+
+    /// Confirms only messages made up of fixed size fields derive Copy
+    #[test_log::test]
+    #[cfg(feature = "derive-copy")]
+    fn generate_copy_derives() {
+        let parse = |definition: &str, name: &str| {
+            parse_msg("geometry_msgs", RosVersion::ROS1, name, definition)
+        };
+        let msgs = vec![
+            parse("float64 x\nfloat64 y\nfloat64 z", "Point"),
+            parse("Point[2] ends\nuint8 flags\ntime stamp", "Segment"),
+            parse("uint8[4] tag\nstring label", "Labeled"),
+            parse("Segment segment\nint32[] ids", "Segments"),
+        ];
+        let source = generate_msgs(msgs).unwrap();
+        let is_copy =
+            |name: &str| source.contains(&format!("# [derive (Copy)] pub struct {name} {{"));
+        assert!(is_copy("Point"), "{source}");
+        assert!(is_copy("Segment"), "{source}");
+        assert!(!is_copy("Labeled"), "{source}");
+        assert!(!is_copy("Segments"), "{source}");
+    }
+
+    /// Confirms constraint annotations generate a MessageValidator impl, and invalid ones are rejected
+    #[cfg(feature = "validation")]
+    #[test_log::test]
+    fn generate_validator() {
+        let generate = |definition: &str| {
+            generate_msgs(vec![parse_msg(
+                "sensor_msgs",
+                RosVersion::ROS1,
+                "BatteryState",
+                definition,
+            )])
+        };
+
+        let source = generate(
+            "# range: [0, 100]\nuint8 percentage\nfloat32 voltage # range: (0, 60]\n\
+             string chemistry # valid: LiPo, NiMH\nuint8[] cells # valid: [0, 1]",
+        )
+        .unwrap();
+        assert!(source.contains("impl :: roslibrust_codegen :: MessageValidator for BatteryState"));
+        assert!(
+            source.contains("* value >= 0u8 && * value <= 100u8"),
+            "{source}"
+        );
+        assert!(
+            source.contains("* value > 0f32 && * value <= 60f32"),
+            "{source}"
+        );
+        assert!(source.contains("[\"LiPo\" , \"NiMH\"] . contains (& value . as_str ())"));
+        assert!(
+            source.contains("[0u8 , 1u8] . contains (value)"),
+            "{source}"
+        );
+
+        // Messages without annotations have no validator
+        let source = generate("uint8 percentage").unwrap();
+        assert!(!source.contains("MessageValidator"));
+
+        // Constraints which can't apply to the field's type fail generation
+        assert!(generate("string chemistry # range: [0, 1]").is_err());
+        assert!(generate("uint8 percentage # valid: full, empty").is_err());
+    }
+
+    /// Confirms a package filter limits generation to the selected packages and their dependencies
+    #[test_log::test]
+    fn package_filter_selects_packages_and_dependencies() {
+        let write = |root: &std::path::Path, package: &str, file: &str, contents: &str| {
+            let dir = root.join(package).join("msg");
+            std::fs::create_dir_all(&dir).unwrap();
+            std::fs::write(
+                root.join(package).join("package.xml"),
+                format!("<package format=\"2\"><name>{package}</name></package>"),
+            )
+            .unwrap();
+            std::fs::write(dir.join(file), contents).unwrap();
+        };
+        let root = tempfile::tempdir().unwrap();
+        write(
+            root.path(),
+            "std_msgs",
+            "Header.msg",
+            "uint32 seq\ntime stamp\nstring frame_id",
+        );
+        write(root.path(), "geo_msgs", "Point.msg", "float64 x\nfloat64 y");
+        write(
+            root.path(),
+            "nav_msgs",
+            "Path.msg",
+            "Header header\ngeo_msgs/Point[] points",
+        );
+        write(
+            root.path(),
+            "cam_msgs",
+            "Image.msg",
+            "Header header\nuint8[] data",
+        );
+        let search_paths = vec![root.path().to_owned()];
+        let packages = |filter: &PackageFilter| {
+            let (messages, _, _) =
+                find_and_parse_filtered_ros_messages(&search_paths, filter).unwrap();
+            messages
+                .into_iter()
+                .map(|msg| msg.package)
+                .collect::<std::collections::BTreeSet<_>>()
+        };
+
+        assert_eq!(packages(&PackageFilter::default()).len(), 4);
+        assert_eq!(
+            packages(&PackageFilter::include(["nav_msgs"])),
+            ["geo_msgs", "nav_msgs", "std_msgs"]
+                .map(String::from)
+                .into()
+        );
+        assert_eq!(
+            packages(&PackageFilter::default().exclude(["cam_msgs", "nav_msgs"])),
+            ["geo_msgs", "std_msgs"].map(String::from).into()
+        );
+
+        // Excluding a dependency of a selected package fails generation
+        let filter = PackageFilter::include(["nav_msgs"]).exclude(["geo_msgs"]);
+        assert!(
+            find_and_generate_filtered_ros_messages_without_ros_package_path(
+                search_paths.clone(),
+                &filter
+            )
+            .is_err()
+        );
+    }
+
+    /// Confirms the packages used by the messages generated for an action are pulled in too
+    #[test_log::test]
+    fn package_filter_selects_action_dependencies() {
+        let write = |root: &std::path::Path, package: &str, file: &str, contents: &str| {
+            let kind = file.rsplit('.').next().unwrap();
+            let dir = root.join(package).join(kind);
+            std::fs::create_dir_all(&dir).unwrap();
+            std::fs::write(
+                root.join(package).join("package.xml"),
+                format!("<package format=\"2\"><name>{package}</name></package>"),
+            )
+            .unwrap();
+            std::fs::write(dir.join(file), contents).unwrap();
+        };
+        let root = tempfile::tempdir().unwrap();
+        write(
+            root.path(),
+            "std_msgs",
+            "Header.msg",
+            "uint32 seq\ntime stamp\nstring frame_id",
+        );
+        write(
+            root.path(),
+            "actionlib_msgs",
+            "GoalID.msg",
+            "time stamp\nstring id",
+        );
+        write(
+            root.path(),
+            "actionlib_msgs",
+            "GoalStatus.msg",
+            "GoalID goal_id\nuint8 status\nstring text",
+        );
+        write(root.path(), "geo_msgs", "Point.msg", "float64 x\nfloat64 y");
+        write(
+            root.path(),
+            "nav_actions",
+            "Move.action",
+            "geo_msgs/Point target\n---\nbool reached\n---\nfloat64 distance",
+        );
+        let search_paths = vec![root.path().to_owned()];
+        let filter = PackageFilter::include(["nav_actions"]);
+
+        let (messages, _, actions) =
+            find_and_parse_filtered_ros_messages(&search_paths, &filter).unwrap();
+        assert_eq!(actions.len(), 1);
+        assert_eq!(
+            messages
+                .into_iter()
+                .map(|msg| msg.package)
+                .collect::<std::collections::BTreeSet<_>>(),
+            ["actionlib_msgs", "geo_msgs", "nav_actions", "std_msgs"]
+                .map(String::from)
+                .into()
+        );
+        assert!(
+            find_and_generate_filtered_ros_messages_without_ros_package_path(search_paths, &filter)
+                .is_ok()
+        );
+    }
 }
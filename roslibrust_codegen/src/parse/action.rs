@@ -2,7 +2,7 @@ use crate::parse::ParsedMessageFile;
 use crate::{bail, Error, Package};
 use std::path::{Path, PathBuf};
 
-use super::parse_ros_message_file;
+use super::{is_section_separator, parse_ros_message_file, GenError};
 
 #[derive(Clone, Debug)]
 pub struct ParsedActionFile {
@@ -27,32 +27,13 @@ pub fn parse_ros_action_file(
     package: &Package,
     path: &Path,
 ) -> Result<ParsedActionFile, Error> {
-    let mut dash_line_number_1 = None;
-    let mut dash_line_number_2 = None;
-
-    for (line_num, line) in data.lines().enumerate() {
-        match (line.find("---"), line.find('#')) {
-            (Some(dash_idx), Some(comment_idx)) => {
-                if dash_idx < comment_idx {
-                    if dash_line_number_1.is_none() {
-                        dash_line_number_1 = Some(line_num);
-                    } else {
-                        dash_line_number_2 = Some(line_num);
-                        break;
-                    }
-                }
-            }
-            (Some(_), None) => {
-                if dash_line_number_1.is_none() {
-                    dash_line_number_1 = Some(line_num);
-                } else {
-                    dash_line_number_2 = Some(line_num);
-                    break;
-                }
-            }
-            _ => continue,
-        }
-    }
+    let mut dash_line_numbers = data
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| is_section_separator(line))
+        .map(|(line_num, _)| line_num);
+    let dash_line_number_1 = dash_line_numbers.next();
+    let dash_line_number_2 = dash_line_numbers.next();
     let str_accumulator = |mut acc: String, line: &str| -> String {
         acc.push_str(line);
         acc.push('\n');
@@ -116,7 +97,7 @@ fn generate_action_msg(
     name: &str,
     package: &Package,
     path: &Path,
-) -> Result<ParsedMessageFile, Error> {
+) -> Result<ParsedMessageFile, GenError> {
     let source = format!(
         r#"
 {name}ActionGoal action_goal
@@ -132,7 +113,7 @@ fn generate_action_goal_msg(
     name: &str,
     package: &Package,
     path: &Path,
-) -> Result<ParsedMessageFile, Error> {
+) -> Result<ParsedMessageFile, GenError> {
     let source = format!(
         r#"
 Header header
@@ -148,7 +129,7 @@ fn generate_action_result_msg(
     name: &str,
     package: &Package,
     path: &Path,
-) -> Result<ParsedMessageFile, Error> {
+) -> Result<ParsedMessageFile, GenError> {
     let source = format!(
         r#"
 Header header
@@ -169,7 +150,7 @@ fn generate_action_feedback_msg(
     name: &str,
     package: &Package,
     path: &Path,
-) -> Result<ParsedMessageFile, Error> {
+) -> Result<ParsedMessageFile, GenError> {
     let source = format!(
         r#"
 Header header
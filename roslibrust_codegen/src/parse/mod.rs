@@ -1,7 +1,8 @@
 use crate::utils::{Package, RosVersion};
 use crate::{bail, Error};
-use crate::{ConstantInfo, FieldInfo, FieldType};
-use std::collections::HashMap;
+use crate::{ConstantInfo, FieldConstraint, FieldInfo, FieldType};
+use std::collections::{BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
 
 mod action;
 pub use action::{parse_ros_action_file, ParsedActionFile};
@@ -10,6 +11,61 @@ pub use msg::{parse_ros_message_file, ParsedMessageFile};
 mod srv;
 pub use srv::{parse_ros_service_file, ParsedServiceFile};
 
+/// Failures parsing a definition which callers may want to tell apart, anything else is
+/// [GenError::Other]
+#[derive(Debug)]
+pub enum GenError {
+    /// A message declares two fields with the same name, which would otherwise generate a struct
+    /// failing to compile far from the definition at fault
+    DuplicateField {
+        /// Full name of the message, e.g. `geometry_msgs/Point`
+        message: String,
+        /// Name of the repeated field
+        field: String,
+        /// Line of the repeated declaration, counting from 1
+        line: usize,
+        /// File the definition was read from, empty if it was parsed from text
+        path: PathBuf,
+    },
+    Other(Error),
+}
+
+impl std::fmt::Display for GenError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GenError::DuplicateField {
+                message,
+                field,
+                line,
+                path,
+            } => write!(
+                f,
+                "Duplicate field name '{field}' in {message} on line {line} of {}",
+                path.display()
+            ),
+            GenError::Other(e) => std::fmt::Display::fmt(e, f),
+        }
+    }
+}
+
+impl std::error::Error for GenError {}
+
+impl From<Error> for GenError {
+    fn from(value: Error) -> Self {
+        GenError::Other(value)
+    }
+}
+
+// Lets the rest of generation, which doesn't distinguish failures, use `?` on parse results
+impl From<GenError> for Error {
+    fn from(value: GenError) -> Self {
+        match value {
+            GenError::Other(e) => e,
+            e => Error::new(e.to_string()),
+        }
+    }
+}
+
 lazy_static::lazy_static! {
     pub static ref ROS_TYPE_TO_RUST_TYPE_MAP: HashMap<&'static str, &'static str> = vec![
         ("bool", "bool"),
@@ -76,12 +132,13 @@ fn parse_field(line: &str, pkg: &Package, msg_name: &str) -> Result<FieldInfo, E
         "Did not find field_name on line: {line} while parsing {pkg_name}/{msg_name}"
     )))?;
 
-    let sep = line.find(' ').unwrap();
+    // Field type and name may be separated by any run of whitespace (tabs, aligned spaces, etc.)
+    let sep = line.find(char::is_whitespace).unwrap();
     // Determine if there is a default value for this field
     let default = if matches!(pkg.version, Some(RosVersion::ROS2)) {
         // For ros2 packages only, check if there is a default value
         let line_after_sep = line[sep + 1..].trim();
-        match line_after_sep.find(' ') {
+        match line_after_sep.find(char::is_whitespace) {
             Some(def_start) => {
                 let remainder = line_after_sep[def_start..].trim();
                 if remainder.is_empty() {
@@ -103,11 +160,13 @@ fn parse_field(line: &str, pkg: &Package, msg_name: &str) -> Result<FieldInfo, E
         field_type,
         field_name: field_name.to_string(),
         default,
+        deprecated: None,
+        constraints: vec![],
     })
 }
 
 fn parse_constant_field(line: &str, pkg: &Package) -> Result<ConstantInfo, Error> {
-    let sep = line.find(' ').ok_or(
+    let sep = line.find(char::is_whitespace).ok_or(
         Error::new(format!("Failed to find white space seperator ' ' while parsing constant information one line {line} for package {pkg:?}"))
     )?;
     let equal_after_sep = line[sep..].find('=').ok_or(
@@ -126,6 +185,7 @@ fn parse_constant_field(line: &str, pkg: &Package) -> Result<ConstantInfo, Error
         constant_type,
         constant_name,
         constant_value: constant_value.into(),
+        comment: None,
     })
 }
 
@@ -137,6 +197,89 @@ fn strip_comments(line: &str) -> &str {
     line
 }
 
+/// The text of the comment on this line, if it has one that isn't empty
+fn trailing_comment(line: &str) -> Option<String> {
+    let comment = line[line.find('#')?..].trim_start_matches('#').trim();
+    (!comment.is_empty()).then(|| comment.to_owned())
+}
+
+/// True if this line separates the sections of a service or action file, i.e. it is `---` on its
+/// own, optionally followed by a comment. Dashes within comments or string values don't count.
+fn is_section_separator(line: &str) -> bool {
+    strip_comments(line).trim() == "---"
+}
+
+/// If the comment on this line marks a field as deprecated, e.g. `# DEPRECATED: use pose instead`,
+/// returns the explanation following the marker (which may be empty)
+fn deprecation_note(line: &str) -> Option<String> {
+    let comment = line[line.find('#')?..].trim_start_matches('#').trim();
+    let note = comment.strip_prefix("DEPRECATED")?;
+    Some(
+        note.trim_start_matches(|c: char| c == ':' || c == '-' || c.is_whitespace())
+            .to_owned(),
+    )
+}
+
+/// If the comment on this line is a constraint annotation, e.g. `# range: [0, 100]` or
+/// `# valid: 1, 2, 4`, parses the constraint. The values are kept as written and only checked
+/// against the field's type during generation.
+///
+/// Without the `validation` feature annotations are left as ordinary comments, so malformed ones
+/// don't fail parsing.
+fn field_constraint(line: &str) -> Result<Option<FieldConstraint>, Error> {
+    if !cfg!(feature = "validation") {
+        return Ok(None);
+    }
+    let Some(start) = line.find('#') else {
+        return Ok(None);
+    };
+    let comment = line[start..].trim_start_matches('#').trim();
+    if let Some(range) = comment.strip_prefix("range:") {
+        let range = range.trim();
+        let min_inclusive = match range.chars().next() {
+            Some('[') => true,
+            Some('(') => false,
+            _ => bail!("Range annotation must start with '[' or '(': {line}"),
+        };
+        let max_inclusive = match range.chars().last() {
+            Some(']') if range.len() > 1 => true,
+            Some(')') if range.len() > 1 => false,
+            _ => bail!("Range annotation must end with ']' or ')': {line}"),
+        };
+        let bounds = range[1..range.len() - 1]
+            .split(',')
+            .map(|bound| bound.trim().to_owned())
+            .collect::<Vec<_>>();
+        let [min, max] = <[String; 2]>::try_from(bounds)
+            .map_err(|_| Error::new(format!("Range annotation must have two bounds: {line}")))?;
+        if min.is_empty() || max.is_empty() {
+            bail!("Range annotation must have two bounds: {line}");
+        }
+        Ok(Some(FieldConstraint::Range {
+            min,
+            max,
+            min_inclusive,
+            max_inclusive,
+        }))
+    } else if let Some(values) = comment.strip_prefix("valid:") {
+        let values = values.trim();
+        let values = values
+            .strip_prefix('[')
+            .and_then(|values| values.strip_suffix(']'))
+            .unwrap_or(values);
+        let values = values
+            .split(',')
+            .map(|value| value.trim().to_owned())
+            .collect::<Vec<_>>();
+        if values.iter().any(|value| value.is_empty()) {
+            bail!("Valid annotation must be a comma separated list of values: {line}");
+        }
+        Ok(Some(FieldConstraint::Valid(values)))
+    } else {
+        Ok(None)
+    }
+}
+
 //TODO it is a little scary that this function appears infallible?
 fn parse_field_type(type_str: &str, array_info: Option<Option<usize>>, pkg: &Package) -> FieldType {
     let items = type_str.split('/').collect::<Vec<&str>>();
@@ -148,16 +291,20 @@ fn parse_field_type(type_str: &str, array_info: Option<Option<usize>>, pkg: &Pac
             package_name: if is_intrinsic_type(pkg_version, type_str) {
                 // If it is a fundamental type, no package
                 None
+            } else if type_str == "Header" && pkg_version == RosVersion::ROS1 {
+                // genmsg always resolves a bare Header to std_msgs/Header, even in a package
+                // defining its own, and md5sums must match what it computes
+                Some("std_msgs".to_owned())
             } else {
                 // Otherwise it is referencing another message in the same package
-                if type_str == "Header" {
-                    Some("std_msgs".to_owned())
-                } else {
-                    Some(pkg.name.clone())
-                }
+                // For ROS2, falling back to std_msgs/Header happens in
+                // resolve_same_package_references, once we know whether the package defines a
+                // Header of its own
+                Some(pkg.name.clone())
             },
             field_type: items[0].to_string(),
             array_info,
+            array_bound: None,
         }
     } else {
         // If there is more than one item there is a package redirect
@@ -168,12 +315,14 @@ fn parse_field_type(type_str: &str, array_info: Option<Option<usize>>, pkg: &Pac
                 package_name: None,
                 field_type: type_str.to_string(),
                 array_info,
+                array_bound: None,
             }
         } else {
             FieldType {
                 package_name: Some(items[0].to_string()),
                 field_type: items[1].to_string(),
                 array_info,
+                array_bound: None,
             }
         }
     }
@@ -189,19 +338,27 @@ fn parse_type(type_str: &str, pkg: &Package) -> Result<FieldType, Error> {
     match (open_bracket_idx, close_bracket_idx) {
         (Some(o), Some(c)) => {
             // After having stripped array information, parse the remainder of the type
+            let size_str = &type_str[(o + 1)..c];
+            if let Some(bound_str) = size_str.strip_prefix("<=") {
+                // Bounded arrays are variable size, up to the bound
+                let bound = bound_str.trim().parse::<usize>().map_err(|err| {
+                    Error::new(format!(
+                        "Unable to parse bound of the array: {type_str}: {err}"
+                    ))
+                })?;
+                let mut field_type = parse_field_type(&type_str[..o], Some(None), pkg);
+                field_type.array_bound = Some(bound);
+                return Ok(field_type);
+            }
             let array_size = if c - o == 1 {
                 // No size specified
                 None
             } else {
-                let fixed_size_str = &type_str[(o + 1)..c];
-                let fixed_size = fixed_size_str.parse::<usize>().map_err(|err| {
+                let fixed_size = size_str.parse::<usize>().map_err(|err| {
                     Error::new(format!(
                         "Unable to parse size of the array: {type_str}, defaulting to 0: {err}"
                     ))
                 });
-                // TODO we don't currently handle "array limits" in ROS2, so for now we're ejecting this error
-                // To make this function complete we need to handle clauses like '<=3'
-                // None of this really matters at current time, because we don't generate fixed size array types yet anyway
                 let fixed_size = fixed_size.unwrap_or(0);
                 Some(fixed_size)
             };
@@ -217,10 +374,98 @@ fn parse_type(type_str: &str, pkg: &Package) -> Result<FieldType, Error> {
     }
 }
 
+/// Unqualified field types are parsed as references into the package they appear in, except a
+/// ROS1 `Header`, which is always `std_msgs/Header`. A ROS2 package that doesn't define its own
+/// `Header` gets `std_msgs/Header`, this applies that fallback once the full set of messages is
+/// known.
+pub(crate) fn resolve_same_package_references(
+    messages: &mut [ParsedMessageFile],
+    services: &mut [ParsedServiceFile],
+    actions: &mut [ParsedActionFile],
+) {
+    let known = messages
+        .iter()
+        .map(|msg| msg.get_full_name())
+        .collect::<BTreeSet<_>>();
+    let fix = |msg: &mut ParsedMessageFile| {
+        for field in &mut msg.fields {
+            let field_type = &mut field.field_type;
+            if field_type.field_type == "Header"
+                && field_type.package_name.as_deref() == Some(msg.package.as_str())
+                && !known.contains(&format!("{}/Header", msg.package))
+            {
+                field_type.package_name = Some("std_msgs".to_owned());
+            }
+        }
+    };
+    messages.iter_mut().for_each(fix);
+    for srv in services {
+        fix(&mut srv.request_type);
+        fix(&mut srv.response_type);
+    }
+    for action in actions {
+        for msg in [
+            &mut action.action_type,
+            &mut action.goal_type,
+            &mut action.result_type,
+            &mut action.feedback_type,
+            &mut action.action_goal_type,
+            &mut action.action_result_type,
+            &mut action.action_feedback_type,
+        ] {
+            fix(msg);
+        }
+    }
+}
+
+/// Parses the text of a ROS1 message definition, e.g. one recorded in a bag file or fetched from
+/// rosapi, without reading anything from the filesystem. The returned message's `path` is empty.
+/// * `package` -- Name of the package the message belongs to, used for unqualified type names
+/// * `name` -- Name of the message, e.g. `Header`
+/// * `text` -- Contents of the message definition
+pub fn parse_message(package: &str, name: &str, text: &str) -> Result<ParsedMessageFile, GenError> {
+    let mut parsed = [parse_ros_message_file(
+        text,
+        name,
+        &in_memory_package(package),
+        Path::new(""),
+    )?];
+    resolve_same_package_references(&mut parsed, &mut [], &mut []);
+    let [parsed] = parsed;
+    Ok(parsed)
+}
+
+/// Parses the text of a ROS1 service definition without reading anything from the filesystem,
+/// see [parse_message].
+pub fn parse_service(package: &str, name: &str, text: &str) -> Result<ParsedServiceFile, GenError> {
+    let mut parsed = [parse_ros_service_file(
+        text,
+        name,
+        &in_memory_package(package),
+        Path::new(""),
+    )?];
+    resolve_same_package_references(&mut [], &mut parsed, &mut []);
+    let [parsed] = parsed;
+    Ok(parsed)
+}
+
+/// The package for definitions which weren't found on disk
+fn in_memory_package(name: &str) -> Package {
+    Package {
+        name: name.to_owned(),
+        path: PathBuf::new(),
+        version: Some(RosVersion::ROS1),
+    }
+}
+
 #[cfg(test)]
 mod test {
     use crate::{
-        parse::parse_type,
+        parse::{
+            in_memory_package, parse_constant_field, parse_field, parse_message,
+            parse_ros_action_file, parse_ros_message_file, parse_service, parse_type,
+            resolve_same_package_references, GenError,
+        },
         utils::{Package, RosVersion},
     };
 
@@ -236,4 +481,287 @@ mod test {
         let parsed = parse_type(line, &pkg).unwrap();
         assert_eq!(parsed.array_info, Some(Some(9)));
     }
+
+    // ROS2 bounded arrays are variable size, with the bound kept for estimating sizes
+    #[test_log::test]
+    fn parse_type_handles_bounded_arrays() {
+        let pkg = Package {
+            version: Some(RosVersion::ROS2),
+            ..in_memory_package("test_pkg")
+        };
+        let parsed = parse_type("float64[<=3]", &pkg).unwrap();
+        assert_eq!(parsed.field_type, "float64");
+        assert_eq!(parsed.array_info, Some(None));
+        assert_eq!(parsed.array_bound, Some(3));
+        assert_eq!(parsed.to_string(), "float64[<=3]");
+
+        let parsed = parse_type("float64[]", &pkg).unwrap();
+        assert_eq!(parsed.array_bound, None);
+        assert!(parse_type("float64[<=x]", &pkg).is_err());
+    }
+
+    // Fields and constants aligned with tabs or runs of spaces should tokenize the same as single spaces
+    #[test_log::test]
+    fn parse_field_handles_arbitrary_whitespace() {
+        let pkg = in_memory_package("test_pkg");
+        for line in ["float64\tx", "float64    x", "float64 \t  x"] {
+            let field = parse_field(line, &pkg, "Test").unwrap();
+            assert_eq!(field.field_type.field_type, "float64");
+            assert_eq!(field.field_name, "x");
+        }
+
+        let constant = parse_constant_field("uint8\tDEBUG\t= 1", &pkg).unwrap();
+        assert_eq!(constant.constant_type, "uint8");
+        assert_eq!(constant.constant_name, "DEBUG");
+        assert_eq!(constant.constant_value.inner, "1");
+    }
+
+    #[test_log::test]
+    fn parse_field_default_handles_arbitrary_whitespace() {
+        let pkg = Package {
+            version: Some(RosVersion::ROS2),
+            ..in_memory_package("test_pkg")
+        };
+        let field = parse_field("int32\t\tx\t\t42", &pkg, "Test").unwrap();
+        assert_eq!(field.field_name, "x");
+        assert_eq!(field.default.unwrap().inner, "42");
+    }
+
+    // Unqualified names resolve within the current package, with a ROS2 Header falling back to
+    // std_msgs only when the package doesn't define one itself
+    #[test_log::test]
+    fn unqualified_types_resolve_to_same_package_before_std_msgs_header() {
+        let pkg = |name: &str| Package {
+            version: Some(RosVersion::ROS2),
+            ..in_memory_package(name)
+        };
+        let parse = |data: &str, name: &str, pkg: &Package| {
+            parse_ros_message_file(data, name, pkg, std::path::Path::new("./not_a_path")).unwrap()
+        };
+        let custom = pkg("custom_msgs");
+        let plain = pkg("plain_msgs");
+        let mut messages = vec![
+            parse("uint32 id", "Header", &custom),
+            parse("Header header\nPoint point", "Stamped", &custom),
+            parse("float64 x", "Point", &plain),
+            parse("Header header\nPoint point", "Stamped", &plain),
+        ];
+        resolve_same_package_references(&mut messages, &mut [], &mut []);
+
+        let package_of = |msg: usize, field: usize| {
+            messages[msg].fields[field]
+                .field_type
+                .package_name
+                .clone()
+                .unwrap()
+        };
+        assert_eq!(package_of(1, 0), "custom_msgs");
+        assert_eq!(package_of(1, 1), "custom_msgs");
+        assert_eq!(package_of(3, 0), "std_msgs");
+        assert_eq!(package_of(3, 1), "plain_msgs");
+        assert!(messages[3].has_header());
+        assert!(!messages[1].has_header());
+    }
+
+    // genmsg resolves a bare ROS1 Header to std_msgs/Header even when the package defines its own,
+    // so the md5sum must be the one roscpp and rospy compute for geometry_msgs/PointStamped
+    #[test_log::test]
+    fn ros1_header_is_always_std_msgs() {
+        let parse = |data: &str, name: &str, pkg: &Package| {
+            parse_ros_message_file(data, name, pkg, std::path::Path::new("./not_a_path")).unwrap()
+        };
+        let std_msgs = in_memory_package("std_msgs");
+        let geometry_msgs = in_memory_package("geometry_msgs");
+        let mut messages = vec![
+            parse(
+                "uint32 seq\ntime stamp\nstring frame_id",
+                "Header",
+                &std_msgs,
+            ),
+            parse("uint32 id", "Header", &geometry_msgs),
+            parse("float64 x\nfloat64 y\nfloat64 z", "Point", &geometry_msgs),
+            parse("Header header\nPoint point", "PointStamped", &geometry_msgs),
+        ];
+        resolve_same_package_references(&mut messages, &mut [], &mut []);
+        assert_eq!(
+            messages[3].fields[0].field_type.package_name.as_deref(),
+            Some("std_msgs")
+        );
+
+        let (resolved, _) = crate::resolve_dependency_graph(messages, vec![]).unwrap();
+        let stamped = resolved
+            .iter()
+            .find(|msg| msg.get_full_name() == "geometry_msgs/PointStamped")
+            .unwrap();
+        assert_eq!(stamped.get_md5sum(), "c63aecb41bfdfd6b7e1fac37c7cbe7bf");
+    }
+
+    #[test_log::test]
+    fn parse_message_deprecated_fields() {
+        let pkg = in_memory_package("test_pkg");
+        let msg = parse_ros_message_file(
+            "# DEPRECATED - superseded by pose\n# Kept for compatibility\nfloat64 x\n\
+             float64 y  # DEPRECATED\n\
+             # DEPRECATED: applies to nothing\n\
+             \n\
+             float64 z # not deprecated",
+            "Test",
+            &pkg,
+            std::path::Path::new("./not_a_path"),
+        )
+        .unwrap();
+        let deprecated = msg
+            .fields
+            .iter()
+            .map(|field| field.deprecated.as_deref())
+            .collect::<Vec<_>>();
+        assert_eq!(deprecated, vec![Some("superseded by pose"), Some(""), None]);
+    }
+
+    #[test_log::test]
+    fn parse_message_rejects_duplicate_field_names() {
+        let pkg = in_memory_package("test_pkg");
+        let err = parse_ros_message_file(
+            "float64 x\nfloat64 y\n# merged twice\nfloat32  x",
+            "Point",
+            &pkg,
+            std::path::Path::new("msg/Point.msg"),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Duplicate field name 'x' in test_pkg/Point on line 4 of msg/Point.msg"
+        );
+        match err {
+            GenError::DuplicateField {
+                message,
+                field,
+                line,
+                path,
+            } => {
+                assert_eq!(message, "test_pkg/Point");
+                assert_eq!(field, "x");
+                assert_eq!(line, 4);
+                assert_eq!(path, std::path::Path::new("msg/Point.msg"));
+            }
+            other => panic!("Expected DuplicateField, got {other:?}"),
+        }
+
+        // Services check their request and response separately
+        parse_service("test_pkg", "Echo", "uint32 data\n---\nuint32 data").unwrap();
+    }
+
+    #[cfg(feature = "validation")]
+    #[test_log::test]
+    fn parse_message_field_constraints() {
+        use crate::FieldConstraint;
+        let pkg = in_memory_package("test_pkg");
+        let parse = |definition: &str| {
+            parse_ros_message_file(
+                definition,
+                "Test",
+                &pkg,
+                std::path::Path::new("./not_a_path"),
+            )
+        };
+        let msg = parse(
+            "# Charge remaining\n# range: [0, 100]\nuint8 percentage # valid: 0, 50, 100\n\
+             float32 voltage # range: (0.0, 60.5]\n\
+             # range: [1, 2]\n\
+             \n\
+             string chemistry # valid: [\"LiPo\", \"NiMH\"]",
+        )
+        .unwrap();
+        let constraints = msg
+            .fields
+            .iter()
+            .map(|field| field.constraints.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            constraints,
+            vec![
+                vec![
+                    FieldConstraint::Range {
+                        min: "0".to_owned(),
+                        max: "100".to_owned(),
+                        min_inclusive: true,
+                        max_inclusive: true,
+                    },
+                    FieldConstraint::Valid(vec!["0".to_owned(), "50".to_owned(), "100".to_owned()]),
+                ],
+                vec![FieldConstraint::Range {
+                    min: "0.0".to_owned(),
+                    max: "60.5".to_owned(),
+                    min_inclusive: false,
+                    max_inclusive: true,
+                }],
+                vec![FieldConstraint::Valid(vec![
+                    "\"LiPo\"".to_owned(),
+                    "\"NiMH\"".to_owned()
+                ])],
+            ]
+        );
+        assert_eq!(constraints[1][0].to_string(), "range: (0.0, 60.5]");
+
+        assert!(parse("uint8 x # range: 0, 100").is_err());
+        assert!(parse("uint8 x # range: [0]").is_err());
+        assert!(parse("uint8 x # valid: 1,,2").is_err());
+    }
+
+    #[test_log::test]
+    fn parse_message_and_service_from_text() {
+        let msg = parse_message(
+            "custom_msgs",
+            "Stamped",
+            "uint8 OK=0\nHeader header\nPoint[] points # trailing comment\n",
+        )
+        .unwrap();
+        assert_eq!(msg.get_full_name(), "custom_msgs/Stamped");
+        assert_eq!(msg.constants[0].constant_name, "OK");
+        assert!(msg.has_header());
+        assert_eq!(
+            msg.fields[1].field_type.package_name.as_deref(),
+            Some("custom_msgs")
+        );
+        assert_eq!(msg.fields[1].field_type.array_info, Some(None));
+
+        let srv = parse_service("custom_msgs", "Echo", "Header header\n---\nuint32 data").unwrap();
+        assert_eq!(srv.request_type.get_full_name(), "custom_msgs/EchoRequest");
+        assert!(srv.request_type.has_header());
+        assert_eq!(srv.response_type.fields[0].field_name, "data");
+
+        assert!(parse_service("custom_msgs", "Echo", "uint32 data").is_err());
+    }
+
+    // Only a line of exactly `---` separates sections, not dashes in comments or string defaults
+    #[test_log::test]
+    fn section_separators_ignore_dashes_elsewhere() {
+        let srv = parse_service(
+            "custom_msgs",
+            "Describe",
+            "# ----------------------\n\
+             # Request\n\
+             string separator \"---\"\n\
+             ---   # Response follows\n\
+             string description # e.g. ---\n",
+        )
+        .unwrap();
+        assert_eq!(srv.request_type.fields.len(), 1);
+        assert_eq!(srv.request_type.fields[0].field_name, "separator");
+        assert_eq!(srv.response_type.fields[0].field_name, "description");
+
+        let pkg = in_memory_package("custom_msgs");
+        let action = parse_ros_action_file(
+            "uint32 order\n#---\n---\nuint32[] sequence\n  ---  \nuint32 done # -------\n",
+            "Count",
+            &pkg,
+            std::path::Path::new(""),
+        )
+        .unwrap();
+        assert_eq!(action.goal_type.fields.len(), 1);
+        assert_eq!(action.result_type.fields[0].field_name, "sequence");
+        assert_eq!(action.feedback_type.fields[0].field_name, "done");
+
+        assert!(parse_service("custom_msgs", "Echo", "uint32 data # ---").is_err());
+    }
 }
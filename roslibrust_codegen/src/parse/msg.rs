@@ -1,4 +1,8 @@
-use crate::parse::{parse_constant_field, parse_field, strip_comments};
+use super::GenError;
+use crate::parse::{
+    deprecation_note, field_constraint, parse_constant_field, parse_field, strip_comments,
+    trailing_comment,
+};
 use crate::Error;
 use crate::{ConstantInfo, FieldInfo, Package, RosVersion};
 use std::path::{Path, PathBuf};
@@ -41,18 +45,45 @@ pub fn parse_ros_message_file(
     name: &str,
     package: &Package,
     path: &Path,
-) -> Result<ParsedMessageFile, Error> {
+) -> Result<ParsedMessageFile, GenError> {
     let mut fields = vec![];
-    let mut constants = vec![];
+    let mut constants: Vec<ConstantInfo> = vec![];
 
-    for line in data.lines() {
-        let line = strip_comments(line).trim();
+    // Deprecation and constraint comments on their own line apply to the field following them
+    let mut pending_deprecation = None;
+    let mut pending_constraints = vec![];
+    // Whether indented comment only lines continue the comment of the constant before them
+    let mut continuing_constant = false;
+    for (line_num, full_line) in data.lines().enumerate() {
+        let line = strip_comments(full_line).trim();
         if line.is_empty() {
             // Comment only line skip
+            if full_line.trim().is_empty() {
+                pending_deprecation = None;
+                pending_constraints.clear();
+                continuing_constant = false;
+                continue;
+            }
+            // Long comments on constants, e.g. actionlib_msgs/GoalStatus, wrap onto lines of their
+            // own indented to line up with the first
+            if continuing_constant && full_line.starts_with(char::is_whitespace) {
+                let comment = constants.last_mut().and_then(|c| c.comment.as_mut());
+                if let (Some(comment), Some(more)) = (comment, trailing_comment(full_line)) {
+                    comment.push(' ');
+                    comment.push_str(&more);
+                }
+                continue;
+            }
+            continuing_constant = false;
+            if let Some(note) = deprecation_note(full_line) {
+                pending_deprecation = Some(note);
+            } else if let Some(constraint) = field_constraint(full_line)? {
+                pending_constraints.push(constraint);
+            }
             continue;
         }
         // Determine if we're looking at a constant or a field
-        let sep = line.find(' ').ok_or(
+        let sep = line.find(char::is_whitespace).ok_or(
             Error::new(
                 format!("Found an invalid ros field line, no space delinting type from name: {line} in {}\n{data}",
                 path.display())
@@ -61,10 +92,32 @@ pub fn parse_ros_message_file(
         let equal_after_sep = line[sep..].find('=');
         if equal_after_sep.is_some() {
             // Since we found an equal sign after a space, this must be a constant
-            constants.push(parse_constant_field(line, package)?)
+            let mut constant = parse_constant_field(line, package)?;
+            constant.comment = trailing_comment(full_line);
+            continuing_constant = constant.comment.is_some();
+            constants.push(constant);
+            pending_deprecation = None;
+            pending_constraints.clear();
         } else {
             // Is regular field
-            fields.push(parse_field(line, package, name)?);
+            continuing_constant = false;
+            let mut field = parse_field(line, package, name)?;
+            // Would otherwise generate a struct which fails to compile far from the definition
+            if fields
+                .iter()
+                .any(|existing: &FieldInfo| existing.field_name == field.field_name)
+            {
+                return Err(GenError::DuplicateField {
+                    message: format!("{}/{name}", package.name),
+                    field: field.field_name,
+                    line: line_num + 1,
+                    path: path.to_owned(),
+                });
+            }
+            field.deprecated = deprecation_note(full_line).or(pending_deprecation.take());
+            field.constraints = std::mem::take(&mut pending_constraints);
+            field.constraints.extend(field_constraint(full_line)?);
+            fields.push(field);
         }
     }
     Ok(ParsedMessageFile {
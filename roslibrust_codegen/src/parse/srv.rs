@@ -1,5 +1,5 @@
 use crate::{
-    parse::{parse_ros_message_file, ParsedMessageFile},
+    parse::{is_section_separator, parse_ros_message_file, GenError, ParsedMessageFile},
     Error, Package,
 };
 use std::path::{Path, PathBuf};
@@ -34,24 +34,8 @@ pub fn parse_ros_service_file(
     name: &str,
     package: &Package,
     path: &Path,
-) -> Result<ParsedServiceFile, Error> {
-    let mut dash_line_number = None;
-    for (line_num, line) in data.lines().enumerate() {
-        match (line.find("---"), line.find('#')) {
-            (Some(dash_idx), Some(cmt_idx)) => {
-                if dash_idx < cmt_idx {
-                    // Comment appears after dash
-                    dash_line_number = Some(line_num);
-                    break;
-                }
-            }
-            (Some(_), None) => {
-                dash_line_number = Some(line_num);
-                break;
-            }
-            _ => continue,
-        }
-    }
+) -> Result<ParsedServiceFile, GenError> {
+    let dash_line_number = data.lines().position(is_section_separator);
     let str_accumulator = |mut acc: String, line: &str| -> String {
         acc.push_str(line);
         acc.push('\n');
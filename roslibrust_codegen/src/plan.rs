@@ -0,0 +1,264 @@
+use crate::parse::{ParsedMessageFile, ParsedServiceFile};
+use crate::{
+    find_ros_files, generate_rust_ros_message_definitions, parse, parse_ros_file,
+    resolve_dependency_graph, Error,
+};
+use std::collections::BTreeSet;
+use std::fmt::Display;
+use std::path::PathBuf;
+
+/// Report of what code generation would produce for a set of search paths, created by [plan].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct GenerationPlan {
+    /// Every ROS file that was found and parsed successfully, these are the files that generated
+    /// code would depend on
+    pub dependent_paths: Vec<PathBuf>,
+    /// Full names (`package/Name`) of the messages that would be generated
+    pub messages: Vec<String>,
+    /// Full names (`package/Name`) of the services that would be generated
+    pub services: Vec<String>,
+    /// Full names (`package/Name`) of the actions that would be generated
+    pub actions: Vec<String>,
+    /// Fields referencing messages which could not be found in any of the searched packages
+    pub unresolved: Vec<UnresolvedReference>,
+    /// Errors encountered while parsing individual files or generating code
+    pub errors: Vec<String>,
+}
+
+/// A message or service whose definition refers to a message that could not be found.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct UnresolvedReference {
+    /// Full name of the message or service containing the reference
+    pub referenced_by: String,
+    /// Full name of the message that could not be found
+    pub missing: String,
+}
+
+impl GenerationPlan {
+    /// True if generation would succeed with every found file resolved
+    pub fn is_ok(&self) -> bool {
+        self.unresolved.is_empty() && self.errors.is_empty()
+    }
+}
+
+impl Display for GenerationPlan {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "{} files, {} messages, {} services, {} actions would be generated",
+            self.dependent_paths.len(),
+            self.messages.len(),
+            self.services.len(),
+            self.actions.len()
+        )?;
+        for name in self.messages.iter() {
+            writeln!(f, "  message {name}")?;
+        }
+        for name in self.services.iter() {
+            writeln!(f, "  service {name}")?;
+        }
+        for name in self.actions.iter() {
+            writeln!(f, "  action {name}")?;
+        }
+        for UnresolvedReference {
+            referenced_by,
+            missing,
+        } in self.unresolved.iter()
+        {
+            writeln!(f, "unresolved: {referenced_by} references {missing}")?;
+        }
+        for error in self.errors.iter() {
+            writeln!(f, "error: {error}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Performs discovery, parsing, and dependency resolution for the given search paths exactly as
+/// [crate::find_and_generate_ros_messages_without_ros_package_path] would, but instead of
+/// returning generated source reports which files and types would be generated, along with any
+/// unresolved references or errors. Unlike generation this does not stop at the first problem, so
+/// a single call reports everything wrong with a message set.
+///
+/// Returns an error only if the search paths themselves are invalid or contain no packages.
+///
+/// * `search_paths` - A list of paths to search for ROS packages.
+pub fn plan(search_paths: Vec<PathBuf>) -> Result<GenerationPlan, Error> {
+    let files = find_ros_files(&search_paths)?;
+
+    let mut report = GenerationPlan::default();
+    let mut messages = vec![];
+    let mut services = vec![];
+    let mut actions = vec![];
+    for (pkg, path) in files {
+        match parse_ros_file(&pkg, &path, &mut messages, &mut services, &mut actions) {
+            Ok(()) => report.dependent_paths.push(path),
+            Err(e) => report.errors.push(format!("{path:?}: {e}")),
+        }
+    }
+    parse::resolve_same_package_references(&mut messages, &mut services, &mut actions);
+    if messages.is_empty() && services.is_empty() {
+        report.errors.push(format!(
+            "Failed to find any services or messages, paths searched: {search_paths:?}"
+        ));
+    }
+
+    let (messages, services, unresolved) = partition_resolvable(messages, services);
+    report.unresolved = unresolved;
+    let actions = actions
+        .into_iter()
+        .filter(|action| {
+            let goal = format!("{}/{}", action.package, action.action_goal_type.name);
+            messages.iter().any(|msg| msg.get_full_name() == goal)
+        })
+        .collect::<Vec<_>>();
+
+    match resolve_dependency_graph(messages, services) {
+        Ok((messages, services)) => {
+            report.messages = messages.iter().map(|msg| msg.get_full_name()).collect();
+            report.services = services.iter().map(|srv| srv.get_full_name()).collect();
+            report.actions = actions
+                .iter()
+                .map(|action| format!("{}/{}", action.package, action.name))
+                .collect();
+            if let Err(e) = generate_rust_ros_message_definitions(messages, services, actions) {
+                report.errors.push(e.to_string());
+            }
+        }
+        Err(e) => report.errors.push(e.to_string()),
+    }
+    Ok(report)
+}
+
+/// Splits out the messages and services which depend, directly or indirectly, on a message that
+/// isn't present, so the remainder can be resolved as normal.
+fn partition_resolvable(
+    messages: Vec<ParsedMessageFile>,
+    services: Vec<ParsedServiceFile>,
+) -> (
+    Vec<ParsedMessageFile>,
+    Vec<ParsedServiceFile>,
+    Vec<UnresolvedReference>,
+) {
+    let known = messages
+        .iter()
+        .map(|msg| msg.get_full_name())
+        .collect::<BTreeSet<_>>();
+    let mut unresolved = BTreeSet::new();
+    let mut missing_references = |name: String, msg: &ParsedMessageFile| {
+        for field in msg.fields.iter() {
+            if field.field_type.package_name.is_some() && !known.contains(&field.get_full_name()) {
+                unresolved.insert(UnresolvedReference {
+                    referenced_by: name.clone(),
+                    missing: field.get_full_name(),
+                });
+            }
+        }
+    };
+    for msg in messages.iter() {
+        missing_references(msg.get_full_name(), msg);
+    }
+    for srv in services.iter() {
+        missing_references(srv.get_full_name(), &srv.request_type);
+        missing_references(srv.get_full_name(), &srv.response_type);
+    }
+
+    // Anything depending on a message with a missing reference can't be resolved either
+    let mut broken = unresolved
+        .iter()
+        .map(|reference| reference.referenced_by.clone())
+        .collect::<BTreeSet<_>>();
+    loop {
+        let newly_broken = messages
+            .iter()
+            .filter(|msg| !broken.contains(&msg.get_full_name()))
+            .filter(|msg| depends_on_any(msg, &broken))
+            .map(|msg| msg.get_full_name())
+            .collect::<Vec<_>>();
+        if newly_broken.is_empty() {
+            break;
+        }
+        broken.extend(newly_broken);
+    }
+
+    let messages = messages
+        .into_iter()
+        .filter(|msg| !broken.contains(&msg.get_full_name()))
+        .collect();
+    let services = services
+        .into_iter()
+        .filter(|srv| {
+            !broken.contains(&srv.get_full_name())
+                && !depends_on_any(&srv.request_type, &broken)
+                && !depends_on_any(&srv.response_type, &broken)
+        })
+        .collect();
+    (messages, services, unresolved.into_iter().collect())
+}
+
+fn depends_on_any(msg: &ParsedMessageFile, names: &BTreeSet<String>) -> bool {
+    msg.fields.iter().any(|field| {
+        field.field_type.package_name.is_some() && names.contains(&field.get_full_name())
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use crate::plan;
+
+    fn write(root: &std::path::Path, package: &str, file: &str, contents: &str) {
+        let package_dir = root.join(package);
+        let dir = package_dir.join(if file.ends_with(".msg") { "msg" } else { "srv" });
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            package_dir.join("package.xml"),
+            format!("<package format=\"2\"><name>{package}</name></package>"),
+        )
+        .unwrap();
+        std::fs::write(dir.join(file), contents).unwrap();
+    }
+
+    /// Confirms a plan lists everything that would be generated and writes nothing
+    #[test_log::test]
+    fn plan_reports_generated_types() {
+        let root = tempfile::tempdir().unwrap();
+        write(root.path(), "geo_msgs", "Point.msg", "float64 x\nfloat64 y");
+        write(root.path(), "geo_msgs", "Path.msg", "Point[] points");
+        write(
+            root.path(),
+            "geo_msgs",
+            "Plan.srv",
+            "Point goal\n---\nPath path",
+        );
+
+        let report = plan(vec![root.path().to_owned()]).unwrap();
+        assert!(report.is_ok(), "{report}");
+        assert_eq!(report.messages, vec!["geo_msgs/Path", "geo_msgs/Point"]);
+        assert_eq!(report.services, vec!["geo_msgs/Plan"]);
+        assert_eq!(report.dependent_paths.len(), 3);
+    }
+
+    /// Confirms missing references and parse errors are all reported instead of failing on the first
+    #[test_log::test]
+    fn plan_reports_unresolved_references_and_errors() {
+        let root = tempfile::tempdir().unwrap();
+        write(root.path(), "geo_msgs", "Point.msg", "float64 x\nfloat64 y");
+        write(
+            root.path(),
+            "geo_msgs",
+            "Pose.msg",
+            "Point position\nQuat orientation",
+        );
+        write(root.path(), "geo_msgs", "PoseArray.msg", "Pose[] poses");
+        write(root.path(), "geo_msgs", "Broken.msg", "float64");
+
+        let report = plan(vec![root.path().to_owned()]).unwrap();
+        assert!(!report.is_ok());
+        assert_eq!(report.messages, vec!["geo_msgs/Point"]);
+        assert_eq!(report.unresolved.len(), 1);
+        assert_eq!(report.unresolved[0].referenced_by, "geo_msgs/Pose");
+        assert_eq!(report.unresolved[0].missing, "geo_msgs/Quat");
+        assert_eq!(report.errors.len(), 1, "{report}");
+        assert!(report.errors[0].contains("Broken.msg"));
+    }
+}
@@ -22,25 +22,78 @@ pub enum RosVersion {
     ROS2,
 }
 
+/// The ROS installation an environment has been set up for, see [get_ros_version]
+#[derive(Clone, Debug, PartialEq)]
+pub struct RosEnvironment {
+    pub version: RosVersion,
+    /// Name of the distribution from `ROS_DISTRO`, e.g. `noetic` or `humble`, empty if not set
+    pub distro: String,
+}
+
+impl RosEnvironment {
+    pub fn is_ros1(&self) -> bool {
+        self.version == RosVersion::ROS1
+    }
+
+    pub fn is_ros2(&self) -> bool {
+        self.version == RosVersion::ROS2
+    }
+}
+
 const CATKIN_IGNORE: &str = "CATKIN_IGNORE";
 const PACKAGE_FILE_NAME: &str = "package.xml";
 const ROS_PACKAGE_PATH_ENV_VAR: &str = "ROS_PACKAGE_PATH";
+const AMENT_PREFIX_PATH_ENV_VAR: &str = "AMENT_PREFIX_PATH";
+const ROS_VERSION_ENV_VAR: &str = "ROS_VERSION";
+const ROS_DISTRO_ENV_VAR: &str = "ROS_DISTRO";
+
+/// Detects whether the environment has been set up for ROS1 or ROS2 by sourcing a setup file.
+///
+/// `ROS_VERSION` is used when set, otherwise a set `AMENT_PREFIX_PATH` indicates ROS2 and a set
+/// `ROS_PACKAGE_PATH` indicates ROS1. Returns None if none of them are set.
+pub fn get_ros_version() -> Option<RosEnvironment> {
+    ros_version_from(|name| std::env::var(name).ok())
+}
 
+fn ros_version_from(var: impl Fn(&str) -> Option<String>) -> Option<RosEnvironment> {
+    let version = match var(ROS_VERSION_ENV_VAR).as_deref().map(str::trim) {
+        Some("1") => RosVersion::ROS1,
+        Some("2") => RosVersion::ROS2,
+        _ if var(AMENT_PREFIX_PATH_ENV_VAR).is_some() => RosVersion::ROS2,
+        _ if var(ROS_PACKAGE_PATH_ENV_VAR).is_some() => RosVersion::ROS1,
+        _ => return None,
+    };
+    Some(RosEnvironment {
+        version,
+        distro: var(ROS_DISTRO_ENV_VAR).unwrap_or_default(),
+    })
+}
+
+/// Returns the paths to search for packages installed in the environment. These are the paths in
+/// `ROS_PACKAGE_PATH`, preceded for ROS2 by the `share` directories of each prefix in
+/// `AMENT_PREFIX_PATH`.
 pub fn get_search_paths() -> Vec<PathBuf> {
-    if let Ok(paths) = std::env::var(ROS_PACKAGE_PATH_ENV_VAR) {
-        #[cfg(unix)]
-        let separator = ":";
-        #[cfg(windows)]
-        let separator = ";";
-
-        paths
-            .split(separator)
-            .map(PathBuf::from)
-            .collect::<Vec<PathBuf>>()
-    } else {
-        log::warn!("No ROS_PACKAGE_PATH defined.");
-        vec![]
+    search_paths_from(|name| std::env::var(name).ok())
+}
+
+fn search_paths_from(var: impl Fn(&str) -> Option<String>) -> Vec<PathBuf> {
+    let is_ros2 = ros_version_from(&var).is_some_and(|env| env.is_ros2());
+    let mut paths = vec![];
+    if is_ros2 {
+        match var(AMENT_PREFIX_PATH_ENV_VAR) {
+            Some(prefixes) => {
+                paths.extend(std::env::split_paths(&prefixes).map(|prefix| prefix.join("share")))
+            }
+            None => log::warn!("No AMENT_PREFIX_PATH defined."),
+        }
     }
+    // Still searched for ROS2, where it can point at packages outside of any install prefix
+    match var(ROS_PACKAGE_PATH_ENV_VAR) {
+        Some(package_paths) => paths.extend(std::env::split_paths(&package_paths)),
+        None if !is_ros2 => log::warn!("No ROS_PACKAGE_PATH defined."),
+        None => {}
+    }
+    paths
 }
 
 /// Finds ROS packages within a list of search paths.
@@ -271,6 +324,81 @@ fn parse_ros_package_info(
 #[cfg(test)]
 mod test {
     use crate::utils;
+    use std::collections::HashMap;
+    use std::path::PathBuf;
+
+    /// Stands in for the process environment, which is shared by tests running in parallel
+    struct Env(HashMap<&'static str, String>);
+
+    impl Env {
+        fn new(vars: &[(&'static str, &str)]) -> Self {
+            Self(vars.iter().map(|(k, v)| (*k, v.to_string())).collect())
+        }
+
+        fn var(&self, name: &str) -> Option<String> {
+            self.0.get(name).cloned()
+        }
+    }
+
+    #[test]
+    fn detects_ros_version_from_environment() {
+        let detect = |vars| utils::ros_version_from(|name| Env::new(vars).var(name));
+        let ros1 = detect(&[
+            ("ROS_VERSION", "1"),
+            ("ROS_DISTRO", "noetic"),
+            ("ROS_PACKAGE_PATH", "/opt/ros/noetic/share"),
+        ])
+        .unwrap();
+        assert!(ros1.is_ros1() && !ros1.is_ros2());
+        assert_eq!(ros1.distro, "noetic");
+
+        let ros2 = detect(&[("ROS_VERSION", "2"), ("ROS_DISTRO", "humble")]).unwrap();
+        assert!(ros2.is_ros2());
+        assert_eq!(ros2.distro, "humble");
+
+        // Without ROS_VERSION the path variables decide
+        let ros2 = detect(&[("AMENT_PREFIX_PATH", "/opt/ros/humble")]).unwrap();
+        assert_eq!(ros2.version, utils::RosVersion::ROS2);
+        assert_eq!(ros2.distro, "");
+        let ros1 = detect(&[("ROS_PACKAGE_PATH", "/opt/ros/noetic/share")]).unwrap();
+        assert_eq!(ros1.version, utils::RosVersion::ROS1);
+
+        assert_eq!(detect(&[("ROS_DISTRO", "noetic")]), None);
+        assert_eq!(detect(&[]), None);
+    }
+
+    #[test]
+    fn search_paths_follow_ros_version() {
+        let search = |vars| utils::search_paths_from(|name| Env::new(vars).var(name));
+        assert_eq!(
+            search(&[("ROS_PACKAGE_PATH", "/opt/ros/noetic/share:/code/src")]),
+            vec![
+                PathBuf::from("/opt/ros/noetic/share"),
+                PathBuf::from("/code/src")
+            ]
+        );
+        assert_eq!(
+            search(&[
+                ("ROS_VERSION", "2"),
+                ("AMENT_PREFIX_PATH", "/code/install/my_msgs:/opt/ros/humble"),
+                ("ROS_PACKAGE_PATH", "/code/extra_msgs"),
+            ]),
+            vec![
+                PathBuf::from("/code/install/my_msgs/share"),
+                PathBuf::from("/opt/ros/humble/share"),
+                PathBuf::from("/code/extra_msgs")
+            ]
+        );
+        assert_eq!(
+            search(&[
+                ("ROS_VERSION", "2"),
+                ("ROS_PACKAGE_PATH", "/code/extra_msgs")
+            ]),
+            vec![PathBuf::from("/code/extra_msgs")]
+        );
+        assert!(search(&[("ROS_VERSION", "2")]).is_empty());
+        assert!(search(&[]).is_empty());
+    }
 
     #[test]
     fn verify_deduplicate_packages() {
@@ -27,7 +27,7 @@ impl Parse for RosLibRustMessagePaths {
 /// Paths are relative to where rustc is being invoked from your mileage may vary.
 ///
 /// In addition to provided paths, this will search paths found in the environment
-/// variable ROS_PACKAGE_PATH.
+/// variable ROS_PACKAGE_PATH, or the share directories in AMENT_PREFIX_PATH for ROS2.
 #[proc_macro]
 pub fn find_and_generate_ros_messages(input_stream: TokenStream) -> TokenStream {
     // Note: there is not currently a way for proc_macros to indicate that they need to be re-generated
@@ -61,3 +61,32 @@ pub fn find_and_generate_ros_messages_without_ros_package_path(
         }
     }
 }
+
+/// Implements a ROS1 nodelet, placed on an `impl Nodelets for Type` block in place of `#[async_trait]`.
+/// In addition to the trait impl, this implements `NodeletType` so the type can be registered with
+/// a `NodeletContainer` and created by name, which requires the type to implement `Default`.
+///
+/// The type is registered under its own name unless another is given, e.g. `#[nodelets("my_pkg/Camera")]`.
+#[proc_macro_attribute]
+pub fn nodelets(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let impl_block = parse_macro_input!(item as syn::ItemImpl);
+    let nodelet_type = impl_block.self_ty.clone();
+    let name = if attr.is_empty() {
+        let ty = &nodelet_type;
+        quote::quote!(#ty).to_string().replace(' ', "")
+    } else {
+        parse_macro_input!(attr as syn::LitStr).value()
+    };
+    quote::quote! {
+        #[::roslibrust::ros1::async_trait]
+        #impl_block
+
+        impl ::roslibrust::ros1::NodeletType for #nodelet_type {
+            const NODELET_TYPE: &'static str = #name;
+            fn create() -> ::std::boxed::Box<dyn ::roslibrust::ros1::Nodelets> {
+                ::std::boxed::Box::new(<#nodelet_type as ::std::default::Default>::default())
+            }
+        }
+    }
+    .into()
+}
@@ -18,13 +18,33 @@ const ROS_2_PATH: &str = concat!(
     "/../assets/ros2_common_interfaces"
 );
 const ROS_2_TEST_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../assets/ros2_test_msgs");
+const RCL_INTERFACES_PATH: &str =
+    concat!(env!("CARGO_MANIFEST_DIR"), "/../assets/ros2_rcl_interfaces");
+const RCL_INTERFACES_LIB: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/../roslibrust/src/rcl_interfaces/generated.rs"
+);
 lazy_static! {
     static ref ROS_2_PATHS: Vec<PathBuf> = vec![ROS_2_PATH.into(), ROS_2_TEST_PATH.into()];
 }
 
-/// This main function is used to generate the contents of ros1.rs, ros2.rs
+/// This main function is used to generate the contents of ros1.rs, ros2.rs, and the messages
+/// roslibrust ships behind its `rcl-interfaces` feature
+/// Pass `--dry-run` to only report what would be generated without writing any of them
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     env_logger::init();
+    if std::env::args().any(|arg| arg == "--dry-run") {
+        let ros1 = roslibrust_codegen::plan((*ROS_1_PATHS).clone())?;
+        println!("ros1.rs: {ros1}");
+        let ros2 = roslibrust_codegen::plan((*ROS_2_PATHS).clone())?;
+        println!("ros2.rs: {ros2}");
+        let rcl_interfaces = roslibrust_codegen::plan(vec![RCL_INTERFACES_PATH.into()])?;
+        println!("rcl_interfaces: {rcl_interfaces}");
+        if !ros1.is_ok() || !ros2.is_ok() || !rcl_interfaces.is_ok() {
+            return Err("Message generation would not succeed".into());
+        }
+        return Ok(());
+    }
     let (source, _paths) =
         roslibrust_codegen::find_and_generate_ros_messages_without_ros_package_path(
             (*ROS_1_PATHS).clone(),
@@ -39,6 +59,13 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         ])?;
     let source = format_rust_source(source.to_string().as_str()).to_string();
     std::fs::write(concat!(env!("CARGO_MANIFEST_DIR"), "/src/ros2.rs"), source)?;
+
+    let (source, _paths) =
+        roslibrust_codegen::find_and_generate_ros_messages_without_ros_package_path(vec![
+            RCL_INTERFACES_PATH.into(),
+        ])?;
+    let source = format_rust_source(source.to_string().as_str()).to_string();
+    std::fs::write(RCL_INTERFACES_LIB, source)?;
     Ok(())
 }
 
@@ -114,4 +141,25 @@ mod test {
             panic!("Changes detected see diff!");
         }
     }
+
+    /// Confirms that codegen has been run and changes committed
+    #[test]
+    fn rcl_interfaces_lib_is_up_to_date() {
+        let (source, _paths) =
+            roslibrust_codegen::find_and_generate_ros_messages_without_ros_package_path(vec![
+                RCL_INTERFACES_PATH.into(),
+            ])
+            .unwrap();
+        let source = format_rust_source(source.to_string().as_str()).to_string();
+        let lib_contents = std::fs::read_to_string(RCL_INTERFACES_LIB)
+            .expect("Failed to load current rcl_interfaces contents");
+
+        // Creating a diff so if there are changes output in CI is sane
+        let diff = diffy::create_patch(&source, &lib_contents);
+        println!("Diff is \n{}", diff);
+
+        if source.trim() != lib_contents.trim() {
+            panic!("Changes detected see diff!");
+        }
+    }
 }
@@ -1,4 +1,4 @@
-#[allow(unused_imports)]
+#[allow(unused_imports, deprecated)]
 pub mod actionlib_msgs {
     use super::diagnostic_msgs;
     use super::geometry_msgs;
@@ -19,6 +19,9 @@ pub mod actionlib_msgs {
         Debug,
         Clone,
         PartialEq,
+        Eq,
+        PartialOrd,
+        Ord,
     )]
     pub struct GoalID {
         pub r#stamp: ::roslibrust_codegen::integral_types::Time,
@@ -29,6 +32,12 @@ pub mod actionlib_msgs {
         const MD5SUM: &'static str = "29380925936d499346662d2ed1573d06";
         const DEFINITION : & 'static str = "# The stamp should store the time at which this goal was requested.\n# It is used by an action server when it tries to preempt all\n# goals that were requested before a certain time\nbuiltin_interfaces/Time stamp\n\n# The id provides a way to associate feedback and\n# result message with specific goal requests. The id\n# specified must be unique.\nstring id" ;
     }
+    impl GoalID {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            8usize + 4usize + self.r#id.len()
+        }
+    }
     #[allow(non_snake_case)]
     #[derive(
         :: serde :: Deserialize,
@@ -37,6 +46,9 @@ pub mod actionlib_msgs {
         Debug,
         Clone,
         PartialEq,
+        Eq,
+        PartialOrd,
+        Ord,
     )]
     pub struct GoalStatus {
         pub r#goal_id: self::GoalID,
@@ -49,16 +61,30 @@ pub mod actionlib_msgs {
         const DEFINITION : & 'static str = "GoalID goal_id\nuint8 status\nuint8 PENDING         = 0   # The goal has yet to be processed by the action server.\nuint8 ACTIVE          = 1   # The goal is currently being processed by the action server.\nuint8 PREEMPTED       = 2   # The goal received a cancel request after it started executing\n                            #   and has since completed its execution (Terminal State).\nuint8 SUCCEEDED       = 3   # The goal was achieved successfully by the action server\n                            #   (Terminal State).\nuint8 ABORTED         = 4   # The goal was aborted during execution by the action server due\n                            #    to some failure (Terminal State).\nuint8 REJECTED        = 5   # The goal was rejected by the action server without being processed,\n                            #    because the goal was unattainable or invalid (Terminal State).\nuint8 PREEMPTING      = 6   # The goal received a cancel request after it started executing\n                            #    and has not yet completed execution.\nuint8 RECALLING       = 7   # The goal received a cancel request before it started executing, but\n                            #    the action server has not yet confirmed that the goal is canceled.\nuint8 RECALLED        = 8   # The goal received a cancel request before it started executing\n                            #    and was successfully cancelled (Terminal State).\nuint8 LOST            = 9   # An action client can determine that a goal is LOST. This should not\n                            #    be sent over the wire by an action server.\n\n# Allow for the user to associate a string with GoalStatus for debugging.\nstring text" ;
     }
     impl GoalStatus {
+        #[doc = " = 0 (The goal has yet to be processed by the action server.)"]
         pub const r#PENDING: u8 = 0u8;
+        #[doc = " = 1 (The goal is currently being processed by the action server.)"]
         pub const r#ACTIVE: u8 = 1u8;
+        #[doc = " = 2 (The goal received a cancel request after it started executing and has since completed its execution (Terminal State).)"]
         pub const r#PREEMPTED: u8 = 2u8;
+        #[doc = " = 3 (The goal was achieved successfully by the action server (Terminal State).)"]
         pub const r#SUCCEEDED: u8 = 3u8;
+        #[doc = " = 4 (The goal was aborted during execution by the action server due to some failure (Terminal State).)"]
         pub const r#ABORTED: u8 = 4u8;
+        #[doc = " = 5 (The goal was rejected by the action server without being processed, because the goal was unattainable or invalid (Terminal State).)"]
         pub const r#REJECTED: u8 = 5u8;
+        #[doc = " = 6 (The goal received a cancel request after it started executing and has not yet completed execution.)"]
         pub const r#PREEMPTING: u8 = 6u8;
+        #[doc = " = 7 (The goal received a cancel request before it started executing, but the action server has not yet confirmed that the goal is canceled.)"]
         pub const r#RECALLING: u8 = 7u8;
+        #[doc = " = 8 (The goal received a cancel request before it started executing and was successfully cancelled (Terminal State).)"]
         pub const r#RECALLED: u8 = 8u8;
+        #[doc = " = 9 (An action client can determine that a goal is LOST. This should not be sent over the wire by an action server.)"]
         pub const r#LOST: u8 = 9u8;
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            self.r#goal_id.serialized_size_upper_bound() + 1usize + 4usize + self.r#text.len()
+        }
     }
     #[allow(non_snake_case)]
     #[derive(
@@ -68,6 +94,9 @@ pub mod actionlib_msgs {
         Debug,
         Clone,
         PartialEq,
+        Eq,
+        PartialOrd,
+        Ord,
     )]
     pub struct GoalStatusArray {
         pub r#header: std_msgs::Header,
@@ -78,8 +107,20 @@ pub mod actionlib_msgs {
         const MD5SUM: &'static str = "ad4c7a55992b9ff34d89596ca74a28e0";
         const DEFINITION : & 'static str = "# Stores the statuses for goals that are currently being tracked\n# by an action server\nstd_msgs/Header header\nGoalStatus[] status_list" ;
     }
+    impl GoalStatusArray {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            self.r#header.serialized_size_upper_bound()
+                + 4usize
+                + self
+                    .r#status_list
+                    .iter()
+                    .map(|element| element.serialized_size_upper_bound())
+                    .sum::<usize>()
+        }
+    }
 }
-#[allow(unused_imports)]
+#[allow(unused_imports, deprecated)]
 pub mod diagnostic_msgs {
     use super::actionlib_msgs;
     use super::geometry_msgs;
@@ -100,6 +141,9 @@ pub mod diagnostic_msgs {
         Debug,
         Clone,
         PartialEq,
+        Eq,
+        PartialOrd,
+        Ord,
     )]
     pub struct DiagnosticArray {
         pub r#header: std_msgs::Header,
@@ -110,6 +154,18 @@ pub mod diagnostic_msgs {
         const MD5SUM: &'static str = "7f04f8332be7e46b680724aa4e9a9d71";
         const DEFINITION : & 'static str = "# This message is used to send diagnostic information about the state of the robot.\nstd_msgs/Header header # for timestamp\nDiagnosticStatus[] status # an array of components being reported on" ;
     }
+    impl DiagnosticArray {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            self.r#header.serialized_size_upper_bound()
+                + 4usize
+                + self
+                    .r#status
+                    .iter()
+                    .map(|element| element.serialized_size_upper_bound())
+                    .sum::<usize>()
+        }
+    }
     #[allow(non_snake_case)]
     #[derive(
         :: serde :: Deserialize,
@@ -118,6 +174,9 @@ pub mod diagnostic_msgs {
         Debug,
         Clone,
         PartialEq,
+        Eq,
+        PartialOrd,
+        Ord,
     )]
     pub struct DiagnosticStatus {
         pub r#level: u8,
@@ -132,10 +191,30 @@ pub mod diagnostic_msgs {
         const DEFINITION : & 'static str = "# This message holds the status of an individual component of the robot.\n\n# Possible levels of operations.\nbyte OK=0\nbyte WARN=1\nbyte ERROR=2\nbyte STALE=3\n\n# Level of operation enumerated above.\nbyte level\n# A description of the test/component reporting.\nstring name\n# A description of the status.\nstring message\n# A hardware unique string.\nstring hardware_id\n# An array of values associated with the status.\nKeyValue[] values" ;
     }
     impl DiagnosticStatus {
+        #[doc = " = 0"]
         pub const r#OK: u8 = 0u8;
+        #[doc = " = 1"]
         pub const r#WARN: u8 = 1u8;
+        #[doc = " = 2"]
         pub const r#ERROR: u8 = 2u8;
+        #[doc = " = 3"]
         pub const r#STALE: u8 = 3u8;
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            1usize
+                + 4usize
+                + self.r#name.len()
+                + 4usize
+                + self.r#message.len()
+                + 4usize
+                + self.r#hardware_id.len()
+                + 4usize
+                + self
+                    .r#values
+                    .iter()
+                    .map(|element| element.serialized_size_upper_bound())
+                    .sum::<usize>()
+        }
     }
     #[allow(non_snake_case)]
     #[derive(
@@ -145,6 +224,9 @@ pub mod diagnostic_msgs {
         Debug,
         Clone,
         PartialEq,
+        Eq,
+        PartialOrd,
+        Ord,
     )]
     pub struct KeyValue {
         pub r#key: ::std::string::String,
@@ -155,6 +237,12 @@ pub mod diagnostic_msgs {
         const MD5SUM: &'static str = "cf57fdc6617a881a88c16e768132149c";
         const DEFINITION : & 'static str = "# What to label this value when viewing.\nstring key\n# A value to track over time.\nstring value" ;
     }
+    impl KeyValue {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            4usize + self.r#key.len() + 4usize + self.r#value.len()
+        }
+    }
     #[allow(non_snake_case)]
     #[derive(
         :: serde :: Deserialize,
@@ -163,6 +251,9 @@ pub mod diagnostic_msgs {
         Debug,
         Clone,
         PartialEq,
+        Eq,
+        PartialOrd,
+        Ord,
     )]
     pub struct AddDiagnosticsRequest {
         pub r#load_namespace: ::std::string::String,
@@ -172,6 +263,12 @@ pub mod diagnostic_msgs {
         const MD5SUM: &'static str = "c26cf6e164288fbc6050d74f838bcdf0";
         const DEFINITION : & 'static str = "# This service is used as part of the process for loading analyzers at runtime,\n# and should be used by a loader script or program, not as a standalone service.\n# Information about dynamic addition of analyzers can be found at\n# http://wiki.ros.org/diagnostics/Tutorials/Adding%20Analyzers%20at%20Runtime\n\n# The load_namespace parameter defines the namespace where parameters for the\n# initialization of analyzers in the diagnostic aggregator have been loaded. The\n# value should be a global name (i.e. /my/name/space), not a relative\n# (my/name/space) or private (~my/name/space) name. Analyzers will not be added\n# if a non-global name is used. The call will also fail if the namespace\n# contains parameters that follow a namespace structure that does not conform to\n# that expected by the analyzer definitions. See\n# http://wiki.ros.org/diagnostics/Tutorials/Configuring%20Diagnostic%20Aggregators\n# and http://wiki.ros.org/diagnostics/Tutorials/Using%20the%20GenericAnalyzer\n# for examples of the structure of yaml files which are expected to have been\n# loaded into the namespace.\nstring load_namespace" ;
     }
+    impl AddDiagnosticsRequest {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            4usize + self.r#load_namespace.len()
+        }
+    }
     #[allow(non_snake_case)]
     #[derive(
         :: serde :: Deserialize,
@@ -180,6 +277,9 @@ pub mod diagnostic_msgs {
         Debug,
         Clone,
         PartialEq,
+        Eq,
+        PartialOrd,
+        Ord,
     )]
     pub struct AddDiagnosticsResponse {
         pub r#success: bool,
@@ -190,6 +290,12 @@ pub mod diagnostic_msgs {
         const MD5SUM: &'static str = "937c9679a518e3a18d831e57125ea522";
         const DEFINITION : & 'static str = "# True if diagnostic aggregator was updated with new diagnostics, False\n# otherwise. A false return value means that either there is a bond in the\n# aggregator which already used the requested namespace, or the initialization\n# of analyzers failed.\nbool success\n\n# Message with additional information about the success or failure\nstring message" ;
     }
+    impl AddDiagnosticsResponse {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            1usize + 4usize + self.r#message.len()
+        }
+    }
     pub struct AddDiagnostics {}
     impl ::roslibrust_codegen::RosServiceType for AddDiagnostics {
         const ROS_SERVICE_NAME: &'static str = "diagnostic_msgs/AddDiagnostics";
@@ -205,6 +311,9 @@ pub mod diagnostic_msgs {
         Debug,
         Clone,
         PartialEq,
+        Eq,
+        PartialOrd,
+        Ord,
     )]
     pub struct SelfTestRequest {}
     impl ::roslibrust_codegen::RosMessageType for SelfTestRequest {
@@ -212,6 +321,12 @@ pub mod diagnostic_msgs {
         const MD5SUM: &'static str = "d41d8cd98f00b204e9800998ecf8427e";
         const DEFINITION: &'static str = "";
     }
+    impl SelfTestRequest {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            0usize
+        }
+    }
     #[allow(non_snake_case)]
     #[derive(
         :: serde :: Deserialize,
@@ -220,6 +335,9 @@ pub mod diagnostic_msgs {
         Debug,
         Clone,
         PartialEq,
+        Eq,
+        PartialOrd,
+        Ord,
     )]
     pub struct SelfTestResponse {
         pub r#id: ::std::string::String,
@@ -231,6 +349,20 @@ pub mod diagnostic_msgs {
         const MD5SUM: &'static str = "ac21b1bab7ab17546986536c22eb34e9";
         const DEFINITION: &'static str = "string id\nbyte passed\nDiagnosticStatus[] status";
     }
+    impl SelfTestResponse {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            4usize
+                + self.r#id.len()
+                + 1usize
+                + 4usize
+                + self
+                    .r#status
+                    .iter()
+                    .map(|element| element.serialized_size_upper_bound())
+                    .sum::<usize>()
+        }
+    }
     pub struct SelfTest {}
     impl ::roslibrust_codegen::RosServiceType for SelfTest {
         const ROS_SERVICE_NAME: &'static str = "diagnostic_msgs/SelfTest";
@@ -239,7 +371,7 @@ pub mod diagnostic_msgs {
         type Response = SelfTestResponse;
     }
 }
-#[allow(unused_imports)]
+#[allow(unused_imports, deprecated)]
 pub mod geometry_msgs {
     use super::actionlib_msgs;
     use super::diagnostic_msgs;
@@ -260,6 +392,7 @@ pub mod geometry_msgs {
         Debug,
         Clone,
         PartialEq,
+        PartialOrd,
     )]
     pub struct Accel {
         pub r#linear: self::Vector3,
@@ -270,6 +403,12 @@ pub mod geometry_msgs {
         const MD5SUM: &'static str = "9f195f881246fdfa2798d1d3eebca84a";
         const DEFINITION : & 'static str = "# This expresses acceleration in free space broken into its linear and angular parts.\nVector3  linear\nVector3  angular" ;
     }
+    impl Accel {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            48usize
+        }
+    }
     #[allow(non_snake_case)]
     #[derive(
         :: serde :: Deserialize,
@@ -278,6 +417,7 @@ pub mod geometry_msgs {
         Debug,
         Clone,
         PartialEq,
+        PartialOrd,
     )]
     pub struct AccelStamped {
         pub r#header: std_msgs::Header,
@@ -288,6 +428,12 @@ pub mod geometry_msgs {
         const MD5SUM: &'static str = "19a31cf6d39a90e769a5539f9a293621";
         const DEFINITION : & 'static str = "# An accel with reference coordinate frame and timestamp\nstd_msgs/Header header\nAccel accel" ;
     }
+    impl AccelStamped {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            self.r#header.serialized_size_upper_bound() + self.r#accel.serialized_size_upper_bound()
+        }
+    }
     #[allow(non_snake_case)]
     #[derive(
         :: serde :: Deserialize,
@@ -296,6 +442,7 @@ pub mod geometry_msgs {
         Debug,
         Clone,
         PartialEq,
+        PartialOrd,
     )]
     pub struct AccelWithCovariance {
         pub r#accel: self::Accel,
@@ -308,6 +455,12 @@ pub mod geometry_msgs {
         const MD5SUM: &'static str = "ad5a718d699c6be72a02b8d6a139f334";
         const DEFINITION : & 'static str = "# This expresses acceleration in free space with uncertainty.\n\nAccel accel\n\n# Row-major representation of the 6x6 covariance matrix\n# The orientation parameters use a fixed-axis representation.\n# In order, the parameters are:\n# (x, y, z, rotation about X axis, rotation about Y axis, rotation about Z axis)\nfloat64[36] covariance" ;
     }
+    impl AccelWithCovariance {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            336usize
+        }
+    }
     #[allow(non_snake_case)]
     #[derive(
         :: serde :: Deserialize,
@@ -316,6 +469,7 @@ pub mod geometry_msgs {
         Debug,
         Clone,
         PartialEq,
+        PartialOrd,
     )]
     pub struct AccelWithCovarianceStamped {
         pub r#header: std_msgs::Header,
@@ -326,6 +480,12 @@ pub mod geometry_msgs {
         const MD5SUM: &'static str = "36b6f1177d3c3f476d4c306279c6f18a";
         const DEFINITION : & 'static str = "# This represents an estimated accel with reference coordinate frame and timestamp.\nstd_msgs/Header header\nAccelWithCovariance accel" ;
     }
+    impl AccelWithCovarianceStamped {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            self.r#header.serialized_size_upper_bound() + self.r#accel.serialized_size_upper_bound()
+        }
+    }
     #[allow(non_snake_case)]
     #[derive(
         :: serde :: Deserialize,
@@ -334,6 +494,7 @@ pub mod geometry_msgs {
         Debug,
         Clone,
         PartialEq,
+        PartialOrd,
     )]
     pub struct Inertia {
         pub r#m: f64,
@@ -350,6 +511,12 @@ pub mod geometry_msgs {
         const MD5SUM: &'static str = "1d26e4bb6c83ff141c5cf0d883c2b0fe";
         const DEFINITION : & 'static str = "# Mass [kg]\nfloat64 m\n\n# Center of mass [m]\ngeometry_msgs/Vector3 com\n\n# Inertia Tensor [kg-m^2]\n#     | ixx ixy ixz |\n# I = | ixy iyy iyz |\n#     | ixz iyz izz |\nfloat64 ixx\nfloat64 ixy\nfloat64 ixz\nfloat64 iyy\nfloat64 iyz\nfloat64 izz" ;
     }
+    impl Inertia {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            80usize
+        }
+    }
     #[allow(non_snake_case)]
     #[derive(
         :: serde :: Deserialize,
@@ -358,6 +525,7 @@ pub mod geometry_msgs {
         Debug,
         Clone,
         PartialEq,
+        PartialOrd,
     )]
     pub struct InertiaStamped {
         pub r#header: std_msgs::Header,
@@ -368,6 +536,13 @@ pub mod geometry_msgs {
         const MD5SUM: &'static str = "d4fb75ac056292d6c4bbec5e51d25080";
         const DEFINITION : & 'static str = "# An Inertia with a time stamp and reference frame.\n\nstd_msgs/Header header\nInertia inertia" ;
     }
+    impl InertiaStamped {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            self.r#header.serialized_size_upper_bound()
+                + self.r#inertia.serialized_size_upper_bound()
+        }
+    }
     #[allow(non_snake_case)]
     #[derive(
         :: serde :: Deserialize,
@@ -376,6 +551,7 @@ pub mod geometry_msgs {
         Debug,
         Clone,
         PartialEq,
+        PartialOrd,
     )]
     pub struct Point {
         pub r#x: f64,
@@ -387,6 +563,12 @@ pub mod geometry_msgs {
         const MD5SUM: &'static str = "4a842b65f413084dc2b10fb484ea7f17";
         const DEFINITION : & 'static str = "# This contains the position of a point in free space\nfloat64 x\nfloat64 y\nfloat64 z" ;
     }
+    impl Point {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            24usize
+        }
+    }
     #[allow(non_snake_case)]
     #[derive(
         :: serde :: Deserialize,
@@ -395,6 +577,7 @@ pub mod geometry_msgs {
         Debug,
         Clone,
         PartialEq,
+        PartialOrd,
     )]
     pub struct Point32 {
         pub r#x: f32,
@@ -406,6 +589,12 @@ pub mod geometry_msgs {
         const MD5SUM: &'static str = "cc153912f1453b708d221682bc23d9ac";
         const DEFINITION : & 'static str = "# This contains the position of a point in free space(with 32 bits of precision).\n# It is recommended to use Point wherever possible instead of Point32.\n#\n# This recommendation is to promote interoperability.\n#\n# This message is designed to take up less space when sending\n# lots of points at once, as in the case of a PointCloud.\n\nfloat32 x\nfloat32 y\nfloat32 z" ;
     }
+    impl Point32 {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            12usize
+        }
+    }
     #[allow(non_snake_case)]
     #[derive(
         :: serde :: Deserialize,
@@ -414,6 +603,7 @@ pub mod geometry_msgs {
         Debug,
         Clone,
         PartialEq,
+        PartialOrd,
     )]
     pub struct PointStamped {
         pub r#header: std_msgs::Header,
@@ -424,6 +614,12 @@ pub mod geometry_msgs {
         const MD5SUM: &'static str = "938cb86faf4572821e49e2490701e6df";
         const DEFINITION : & 'static str = "# This represents a Point with reference coordinate frame and timestamp\n\nstd_msgs/Header header\nPoint point" ;
     }
+    impl PointStamped {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            self.r#header.serialized_size_upper_bound() + self.r#point.serialized_size_upper_bound()
+        }
+    }
     #[allow(non_snake_case)]
     #[derive(
         :: serde :: Deserialize,
@@ -432,6 +628,7 @@ pub mod geometry_msgs {
         Debug,
         Clone,
         PartialEq,
+        PartialOrd,
     )]
     pub struct Polygon {
         pub r#points: ::std::vec::Vec<self::Point32>,
@@ -441,6 +638,17 @@ pub mod geometry_msgs {
         const MD5SUM: &'static str = "cd60a26494a087f577976f0329fa120e";
         const DEFINITION : & 'static str = "# A specification of a polygon where the first and last points are assumed to be connected\n\nPoint32[] points" ;
     }
+    impl Polygon {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            4usize
+                + self
+                    .r#points
+                    .iter()
+                    .map(|element| element.serialized_size_upper_bound())
+                    .sum::<usize>()
+        }
+    }
     #[allow(non_snake_case)]
     #[derive(
         :: serde :: Deserialize,
@@ -449,6 +657,7 @@ pub mod geometry_msgs {
         Debug,
         Clone,
         PartialEq,
+        PartialOrd,
     )]
     pub struct PolygonStamped {
         pub r#header: std_msgs::Header,
@@ -459,6 +668,13 @@ pub mod geometry_msgs {
         const MD5SUM: &'static str = "56a3a2a80165092f696df3db62e18fbf";
         const DEFINITION : & 'static str = "# This represents a Polygon with reference coordinate frame and timestamp\n\nstd_msgs/Header header\nPolygon polygon" ;
     }
+    impl PolygonStamped {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            self.r#header.serialized_size_upper_bound()
+                + self.r#polygon.serialized_size_upper_bound()
+        }
+    }
     #[allow(non_snake_case)]
     #[derive(
         :: serde :: Deserialize,
@@ -467,6 +683,7 @@ pub mod geometry_msgs {
         Debug,
         Clone,
         PartialEq,
+        PartialOrd,
     )]
     pub struct Pose {
         pub r#position: self::Point,
@@ -477,6 +694,12 @@ pub mod geometry_msgs {
         const MD5SUM: &'static str = "e45d45a5a1ce597b249e23fb30fc871f";
         const DEFINITION : & 'static str = "# A representation of pose in free space, composed of position and orientation.\n\nPoint position\nQuaternion orientation" ;
     }
+    impl Pose {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            56usize
+        }
+    }
     #[allow(non_snake_case)]
     #[derive(
         :: serde :: Deserialize,
@@ -485,6 +708,7 @@ pub mod geometry_msgs {
         Debug,
         Clone,
         PartialEq,
+        PartialOrd,
     )]
     pub struct Pose2D {
         pub r#x: f64,
@@ -496,6 +720,12 @@ pub mod geometry_msgs {
         const MD5SUM: &'static str = "938fa65709584ad8e77d238529be13b8";
         const DEFINITION : & 'static str = "# Deprecated as of Foxy and will potentially be removed in any following release.\n# Please use the full 3D pose.\n\n# In general our recommendation is to use a full 3D representation of everything and for 2D specific applications make the appropriate projections into the plane for their calculations but optimally will preserve the 3D information during processing.\n\n# If we have parallel copies of 2D datatypes every UI and other pipeline will end up needing to have dual interfaces to plot everything. And you will end up with not being able to use 3D tools for 2D use cases even if they're completely valid, as you'd have to reimplement it with different inputs and outputs. It's not particularly hard to plot the 2D pose or compute the yaw error for the Pose message and there are already tools and libraries that can do this for you.# This expresses a position and orientation on a 2D manifold.\n\nfloat64 x\nfloat64 y\nfloat64 theta" ;
     }
+    impl Pose2D {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            24usize
+        }
+    }
     #[allow(non_snake_case)]
     #[derive(
         :: serde :: Deserialize,
@@ -504,6 +734,7 @@ pub mod geometry_msgs {
         Debug,
         Clone,
         PartialEq,
+        PartialOrd,
     )]
     pub struct PoseArray {
         pub r#header: std_msgs::Header,
@@ -514,6 +745,18 @@ pub mod geometry_msgs {
         const MD5SUM: &'static str = "ea7300c78ec47498d5f226be74a155e8";
         const DEFINITION : & 'static str = "# An array of poses with a header for global reference.\n\nstd_msgs/Header header\n\nPose[] poses" ;
     }
+    impl PoseArray {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            self.r#header.serialized_size_upper_bound()
+                + 4usize
+                + self
+                    .r#poses
+                    .iter()
+                    .map(|element| element.serialized_size_upper_bound())
+                    .sum::<usize>()
+        }
+    }
     #[allow(non_snake_case)]
     #[derive(
         :: serde :: Deserialize,
@@ -522,6 +765,7 @@ pub mod geometry_msgs {
         Debug,
         Clone,
         PartialEq,
+        PartialOrd,
     )]
     pub struct PoseStamped {
         pub r#header: std_msgs::Header,
@@ -532,6 +776,12 @@ pub mod geometry_msgs {
         const MD5SUM: &'static str = "c088ec4a70a5930b0ca46520d5745e2d";
         const DEFINITION : & 'static str = "# A Pose with reference coordinate frame and timestamp\n\nstd_msgs/Header header\nPose pose" ;
     }
+    impl PoseStamped {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            self.r#header.serialized_size_upper_bound() + self.r#pose.serialized_size_upper_bound()
+        }
+    }
     #[allow(non_snake_case)]
     #[derive(
         :: serde :: Deserialize,
@@ -540,6 +790,7 @@ pub mod geometry_msgs {
         Debug,
         Clone,
         PartialEq,
+        PartialOrd,
     )]
     pub struct PoseWithCovariance {
         pub r#pose: self::Pose,
@@ -552,6 +803,12 @@ pub mod geometry_msgs {
         const MD5SUM: &'static str = "c23e848cf1b7533a8d7c259073a97e6f";
         const DEFINITION : & 'static str = "# This represents a pose in free space with uncertainty.\n\nPose pose\n\n# Row-major representation of the 6x6 covariance matrix\n# The orientation parameters use a fixed-axis representation.\n# In order, the parameters are:\n# (x, y, z, rotation about X axis, rotation about Y axis, rotation about Z axis)\nfloat64[36] covariance" ;
     }
+    impl PoseWithCovariance {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            344usize
+        }
+    }
     #[allow(non_snake_case)]
     #[derive(
         :: serde :: Deserialize,
@@ -560,6 +817,7 @@ pub mod geometry_msgs {
         Debug,
         Clone,
         PartialEq,
+        PartialOrd,
     )]
     pub struct PoseWithCovarianceStamped {
         pub r#header: std_msgs::Header,
@@ -570,6 +828,12 @@ pub mod geometry_msgs {
         const MD5SUM: &'static str = "2178452bf195c1abe1e99b07b4e6c8f0";
         const DEFINITION : & 'static str = "# This expresses an estimated pose with a reference coordinate frame and timestamp\n\nstd_msgs/Header header\nPoseWithCovariance pose" ;
     }
+    impl PoseWithCovarianceStamped {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            self.r#header.serialized_size_upper_bound() + self.r#pose.serialized_size_upper_bound()
+        }
+    }
     #[allow(non_snake_case)]
     #[derive(
         :: serde :: Deserialize,
@@ -578,6 +842,7 @@ pub mod geometry_msgs {
         Debug,
         Clone,
         PartialEq,
+        PartialOrd,
     )]
     pub struct Quaternion {
         #[default(0f64)]
@@ -594,6 +859,12 @@ pub mod geometry_msgs {
         const MD5SUM: &'static str = "a779879fadf0160734f906b8c19c7004";
         const DEFINITION : & 'static str = "# This represents an orientation in free space in quaternion form.\n\nfloat64 x 0\nfloat64 y 0\nfloat64 z 0\nfloat64 w 1" ;
     }
+    impl Quaternion {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            32usize
+        }
+    }
     #[allow(non_snake_case)]
     #[derive(
         :: serde :: Deserialize,
@@ -602,6 +873,7 @@ pub mod geometry_msgs {
         Debug,
         Clone,
         PartialEq,
+        PartialOrd,
     )]
     pub struct QuaternionStamped {
         pub r#header: std_msgs::Header,
@@ -612,6 +884,13 @@ pub mod geometry_msgs {
         const MD5SUM: &'static str = "8f93ed7c8430d06bd82fefcc6f7a349e";
         const DEFINITION : & 'static str = "# This represents an orientation with reference coordinate frame and timestamp.\n\nstd_msgs/Header header\nQuaternion quaternion" ;
     }
+    impl QuaternionStamped {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            self.r#header.serialized_size_upper_bound()
+                + self.r#quaternion.serialized_size_upper_bound()
+        }
+    }
     #[allow(non_snake_case)]
     #[derive(
         :: serde :: Deserialize,
@@ -620,6 +899,7 @@ pub mod geometry_msgs {
         Debug,
         Clone,
         PartialEq,
+        PartialOrd,
     )]
     pub struct Transform {
         pub r#translation: self::Vector3,
@@ -630,6 +910,12 @@ pub mod geometry_msgs {
         const MD5SUM: &'static str = "ac9eff44abf714214112b05d54a3cf9b";
         const DEFINITION : & 'static str = "# This represents the transform between two coordinate frames in free space.\n\nVector3 translation\nQuaternion rotation" ;
     }
+    impl Transform {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            56usize
+        }
+    }
     #[allow(non_snake_case)]
     #[derive(
         :: serde :: Deserialize,
@@ -638,6 +924,7 @@ pub mod geometry_msgs {
         Debug,
         Clone,
         PartialEq,
+        PartialOrd,
     )]
     pub struct TransformStamped {
         pub r#header: std_msgs::Header,
@@ -649,6 +936,15 @@ pub mod geometry_msgs {
         const MD5SUM: &'static str = "09bf247c06cf7c69e8c55300b05a7a04";
         const DEFINITION : & 'static str = "# This expresses a transform from coordinate frame header.frame_id\n# to the coordinate frame child_frame_id at the time of header.stamp\n#\n# This message is mostly used by the\n# <a href=\"https://index.ros.org/p/tf2/\">tf2</a> package.\n# See its documentation for more information.\n#\n# The child_frame_id is necessary in addition to the frame_id\n# in the Header to communicate the full reference for the transform\n# in a self contained message.\n\n# The frame id in the header is used as the reference frame of this transform.\nstd_msgs/Header header\n\n# The frame id of the child frame to which this transform points.\nstring child_frame_id\n\n# Translation and rotation in 3-dimensions of child_frame_id from header.frame_id.\nTransform transform" ;
     }
+    impl TransformStamped {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            self.r#header.serialized_size_upper_bound()
+                + 4usize
+                + self.r#child_frame_id.len()
+                + self.r#transform.serialized_size_upper_bound()
+        }
+    }
     #[allow(non_snake_case)]
     #[derive(
         :: serde :: Deserialize,
@@ -657,6 +953,7 @@ pub mod geometry_msgs {
         Debug,
         Clone,
         PartialEq,
+        PartialOrd,
     )]
     pub struct Twist {
         pub r#linear: self::Vector3,
@@ -667,6 +964,12 @@ pub mod geometry_msgs {
         const MD5SUM: &'static str = "9f195f881246fdfa2798d1d3eebca84a";
         const DEFINITION : & 'static str = "# This expresses velocity in free space broken into its linear and angular parts.\n\nVector3  linear\nVector3  angular" ;
     }
+    impl Twist {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            48usize
+        }
+    }
     #[allow(non_snake_case)]
     #[derive(
         :: serde :: Deserialize,
@@ -675,6 +978,7 @@ pub mod geometry_msgs {
         Debug,
         Clone,
         PartialEq,
+        PartialOrd,
     )]
     pub struct TwistStamped {
         pub r#header: std_msgs::Header,
@@ -685,6 +989,12 @@ pub mod geometry_msgs {
         const MD5SUM: &'static str = "09f84400c1ca2e7e26a9da1232813bd0";
         const DEFINITION : & 'static str = "# A twist with reference coordinate frame and timestamp\n\nstd_msgs/Header header\nTwist twist" ;
     }
+    impl TwistStamped {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            self.r#header.serialized_size_upper_bound() + self.r#twist.serialized_size_upper_bound()
+        }
+    }
     #[allow(non_snake_case)]
     #[derive(
         :: serde :: Deserialize,
@@ -693,6 +1003,7 @@ pub mod geometry_msgs {
         Debug,
         Clone,
         PartialEq,
+        PartialOrd,
     )]
     pub struct TwistWithCovariance {
         pub r#twist: self::Twist,
@@ -705,6 +1016,12 @@ pub mod geometry_msgs {
         const MD5SUM: &'static str = "1fe8a28e6890a4cc3ae4c3ca5c7d82e6";
         const DEFINITION : & 'static str = "# This expresses velocity in free space with uncertainty.\n\nTwist twist\n\n# Row-major representation of the 6x6 covariance matrix\n# The orientation parameters use a fixed-axis representation.\n# In order, the parameters are:\n# (x, y, z, rotation about X axis, rotation about Y axis, rotation about Z axis)\nfloat64[36] covariance" ;
     }
+    impl TwistWithCovariance {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            336usize
+        }
+    }
     #[allow(non_snake_case)]
     #[derive(
         :: serde :: Deserialize,
@@ -713,6 +1030,7 @@ pub mod geometry_msgs {
         Debug,
         Clone,
         PartialEq,
+        PartialOrd,
     )]
     pub struct TwistWithCovarianceStamped {
         pub r#header: std_msgs::Header,
@@ -723,6 +1041,12 @@ pub mod geometry_msgs {
         const MD5SUM: &'static str = "7019807c85ce8602fb83180366470670";
         const DEFINITION : & 'static str = "# This represents an estimated twist with reference coordinate frame and timestamp.\n\nstd_msgs/Header header\nTwistWithCovariance twist" ;
     }
+    impl TwistWithCovarianceStamped {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            self.r#header.serialized_size_upper_bound() + self.r#twist.serialized_size_upper_bound()
+        }
+    }
     #[allow(non_snake_case)]
     #[derive(
         :: serde :: Deserialize,
@@ -731,6 +1055,7 @@ pub mod geometry_msgs {
         Debug,
         Clone,
         PartialEq,
+        PartialOrd,
     )]
     pub struct Vector3 {
         pub r#x: f64,
@@ -742,6 +1067,12 @@ pub mod geometry_msgs {
         const MD5SUM: &'static str = "4a842b65f413084dc2b10fb484ea7f17";
         const DEFINITION : & 'static str = "# This represents a vector in free space.\n\n# This is semantically different than a point.\n# A vector is always anchored at the origin.\n# When a transform is applied to a vector, only the rotational component is applied.\n\nfloat64 x\nfloat64 y\nfloat64 z" ;
     }
+    impl Vector3 {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            24usize
+        }
+    }
     #[allow(non_snake_case)]
     #[derive(
         :: serde :: Deserialize,
@@ -750,6 +1081,7 @@ pub mod geometry_msgs {
         Debug,
         Clone,
         PartialEq,
+        PartialOrd,
     )]
     pub struct Vector3Stamped {
         pub r#header: std_msgs::Header,
@@ -760,6 +1092,13 @@ pub mod geometry_msgs {
         const MD5SUM: &'static str = "5cd361f2989a2e76d5aaf432c3bf0fb9";
         const DEFINITION : & 'static str = "# This represents a Vector3 with reference coordinate frame and timestamp\n\n# Note that this follows vector semantics with it always anchored at the origin,\n# so the rotational elements of a transform are the only parts applied when transforming.\n\nstd_msgs/Header header\nVector3 vector" ;
     }
+    impl Vector3Stamped {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            self.r#header.serialized_size_upper_bound()
+                + self.r#vector.serialized_size_upper_bound()
+        }
+    }
     #[allow(non_snake_case)]
     #[derive(
         :: serde :: Deserialize,
@@ -768,6 +1107,7 @@ pub mod geometry_msgs {
         Debug,
         Clone,
         PartialEq,
+        PartialOrd,
     )]
     pub struct Wrench {
         pub r#force: self::Vector3,
@@ -778,6 +1118,12 @@ pub mod geometry_msgs {
         const MD5SUM: &'static str = "4f539cf138b23283b520fd271b567936";
         const DEFINITION : & 'static str = "# This represents force in free space, separated into its linear and angular parts.\n\nVector3  force\nVector3  torque" ;
     }
+    impl Wrench {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            48usize
+        }
+    }
     #[allow(non_snake_case)]
     #[derive(
         :: serde :: Deserialize,
@@ -786,6 +1132,7 @@ pub mod geometry_msgs {
         Debug,
         Clone,
         PartialEq,
+        PartialOrd,
     )]
     pub struct WrenchStamped {
         pub r#header: std_msgs::Header,
@@ -796,8 +1143,15 @@ pub mod geometry_msgs {
         const MD5SUM: &'static str = "5bc71556ab354cd6274d262a7de094a5";
         const DEFINITION : & 'static str = "# A wrench with reference coordinate frame and timestamp\n\nstd_msgs/Header header\nWrench wrench" ;
     }
+    impl WrenchStamped {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            self.r#header.serialized_size_upper_bound()
+                + self.r#wrench.serialized_size_upper_bound()
+        }
+    }
 }
-#[allow(unused_imports)]
+#[allow(unused_imports, deprecated)]
 pub mod nav_msgs {
     use super::actionlib_msgs;
     use super::diagnostic_msgs;
@@ -818,6 +1172,7 @@ pub mod nav_msgs {
         Debug,
         Clone,
         PartialEq,
+        PartialOrd,
     )]
     pub struct GridCells {
         pub r#header: std_msgs::Header,
@@ -830,6 +1185,20 @@ pub mod nav_msgs {
         const MD5SUM: &'static str = "bb9f07bfd2183241b5719f45a81f8cc5";
         const DEFINITION : & 'static str = "# An array of cells in a 2D grid\n\nstd_msgs/Header header\n\n# Width of each cell\nfloat32 cell_width\n\n# Height of each cell\nfloat32 cell_height\n\n# Each cell is represented by the Point at the center of the cell\ngeometry_msgs/Point[] cells" ;
     }
+    impl GridCells {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            self.r#header.serialized_size_upper_bound()
+                + 4usize
+                + 4usize
+                + 4usize
+                + self
+                    .r#cells
+                    .iter()
+                    .map(|element| element.serialized_size_upper_bound())
+                    .sum::<usize>()
+        }
+    }
     #[allow(non_snake_case)]
     #[derive(
         :: serde :: Deserialize,
@@ -838,6 +1207,7 @@ pub mod nav_msgs {
         Debug,
         Clone,
         PartialEq,
+        PartialOrd,
     )]
     pub struct MapMetaData {
         pub r#map_load_time: ::roslibrust_codegen::integral_types::Time,
@@ -851,6 +1221,12 @@ pub mod nav_msgs {
         const MD5SUM: &'static str = "d10232bae3de4ae536d98f679fce2cf2";
         const DEFINITION : & 'static str = "# This hold basic information about the characteristics of the OccupancyGrid\n\n# The time at which the map was loaded\nbuiltin_interfaces/Time map_load_time\n\n# The map resolution [m/cell]\nfloat32 resolution\n\n# Map width [cells]\nuint32 width\n\n# Map height [cells]\nuint32 height\n\n# The origin of the map [m, m, rad].  This is the real-world pose of the\n# bottom left corner of cell (0,0) in the map.\ngeometry_msgs/Pose origin" ;
     }
+    impl MapMetaData {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            8usize + 4usize + 4usize + 4usize + self.r#origin.serialized_size_upper_bound()
+        }
+    }
     #[allow(non_snake_case)]
     #[derive(
         :: serde :: Deserialize,
@@ -859,6 +1235,7 @@ pub mod nav_msgs {
         Debug,
         Clone,
         PartialEq,
+        PartialOrd,
     )]
     pub struct OccupancyGrid {
         pub r#header: std_msgs::Header,
@@ -870,6 +1247,15 @@ pub mod nav_msgs {
         const MD5SUM: &'static str = "2b0657f1993991bf3953916eb5ff5203";
         const DEFINITION : & 'static str = "# This represents a 2-D grid map\nstd_msgs/Header header\n\n# MetaData for the map\nMapMetaData info\n\n# The map data, in row-major order, starting with (0,0). \n# Cell (1, 0) will be listed second, representing the next cell in the x direction. \n# Cell (0, 1) will be at the index equal to info.width, followed by (1, 1).\n# The values inside are application dependent, but frequently, \n# 0 represents unoccupied, 1 represents definitely occupied, and\n# -1 represents unknown. \nint8[] data" ;
     }
+    impl OccupancyGrid {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            self.r#header.serialized_size_upper_bound()
+                + self.r#info.serialized_size_upper_bound()
+                + 4usize
+                + self.r#data.len()
+        }
+    }
     #[allow(non_snake_case)]
     #[derive(
         :: serde :: Deserialize,
@@ -878,6 +1264,7 @@ pub mod nav_msgs {
         Debug,
         Clone,
         PartialEq,
+        PartialOrd,
     )]
     pub struct Odometry {
         pub r#header: std_msgs::Header,
@@ -890,6 +1277,16 @@ pub mod nav_msgs {
         const MD5SUM: &'static str = "81a0900daae2c6c0acc71c9f8df88947";
         const DEFINITION : & 'static str = "# This represents an estimate of a position and velocity in free space.\n# The pose in this message should be specified in the coordinate frame given by header.frame_id\n# The twist in this message should be specified in the coordinate frame given by the child_frame_id\n\n# Includes the frame id of the pose parent.\nstd_msgs/Header header\n\n# Frame id the pose points to. The twist is in this coordinate frame.\nstring child_frame_id\n\n# Estimated pose that is typically relative to a fixed world frame.\ngeometry_msgs/PoseWithCovariance pose\n\n# Estimated linear and angular velocity relative to child_frame_id.\ngeometry_msgs/TwistWithCovariance twist" ;
     }
+    impl Odometry {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            self.r#header.serialized_size_upper_bound()
+                + 4usize
+                + self.r#child_frame_id.len()
+                + self.r#pose.serialized_size_upper_bound()
+                + self.r#twist.serialized_size_upper_bound()
+        }
+    }
     #[allow(non_snake_case)]
     #[derive(
         :: serde :: Deserialize,
@@ -898,6 +1295,7 @@ pub mod nav_msgs {
         Debug,
         Clone,
         PartialEq,
+        PartialOrd,
     )]
     pub struct Path {
         pub r#header: std_msgs::Header,
@@ -908,6 +1306,18 @@ pub mod nav_msgs {
         const MD5SUM: &'static str = "9f78b006a4c2cc2a146c12ed59d1bb7f";
         const DEFINITION : & 'static str = "# An array of poses that represents a Path for a robot to follow.\n\n# Indicates the frame_id of the path.\nstd_msgs/Header header\n\n# Array of poses to follow.\ngeometry_msgs/PoseStamped[] poses" ;
     }
+    impl Path {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            self.r#header.serialized_size_upper_bound()
+                + 4usize
+                + self
+                    .r#poses
+                    .iter()
+                    .map(|element| element.serialized_size_upper_bound())
+                    .sum::<usize>()
+        }
+    }
     #[allow(non_snake_case)]
     #[derive(
         :: serde :: Deserialize,
@@ -916,6 +1326,9 @@ pub mod nav_msgs {
         Debug,
         Clone,
         PartialEq,
+        Eq,
+        PartialOrd,
+        Ord,
     )]
     pub struct GetMapRequest {}
     impl ::roslibrust_codegen::RosMessageType for GetMapRequest {
@@ -923,6 +1336,12 @@ pub mod nav_msgs {
         const MD5SUM: &'static str = "d41d8cd98f00b204e9800998ecf8427e";
         const DEFINITION: &'static str = "# Get the map as a nav_msgs/OccupancyGrid";
     }
+    impl GetMapRequest {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            0usize
+        }
+    }
     #[allow(non_snake_case)]
     #[derive(
         :: serde :: Deserialize,
@@ -931,6 +1350,7 @@ pub mod nav_msgs {
         Debug,
         Clone,
         PartialEq,
+        PartialOrd,
     )]
     pub struct GetMapResponse {
         pub r#map: self::OccupancyGrid,
@@ -941,6 +1361,12 @@ pub mod nav_msgs {
         const DEFINITION: &'static str =
             "# The current map hosted by this map service.\nOccupancyGrid map";
     }
+    impl GetMapResponse {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            self.r#map.serialized_size_upper_bound()
+        }
+    }
     pub struct GetMap {}
     impl ::roslibrust_codegen::RosServiceType for GetMap {
         const ROS_SERVICE_NAME: &'static str = "nav_msgs/GetMap";
@@ -956,6 +1382,7 @@ pub mod nav_msgs {
         Debug,
         Clone,
         PartialEq,
+        PartialOrd,
     )]
     pub struct GetPlanRequest {
         pub r#start: geometry_msgs::PoseStamped,
@@ -967,6 +1394,14 @@ pub mod nav_msgs {
         const MD5SUM: &'static str = "e4855e4d3c7377c76ec90e403202286a";
         const DEFINITION : & 'static str = "# Get a plan from the current position to the goal Pose\n\n# The start pose for the plan\ngeometry_msgs/PoseStamped start\n\n# The final pose of the goal position\ngeometry_msgs/PoseStamped goal\n\n# If the goal is obstructed, how many meters the planner can\n# relax the constraint in x and y before failing.\nfloat32 tolerance" ;
     }
+    impl GetPlanRequest {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            self.r#start.serialized_size_upper_bound()
+                + self.r#goal.serialized_size_upper_bound()
+                + 4usize
+        }
+    }
     #[allow(non_snake_case)]
     #[derive(
         :: serde :: Deserialize,
@@ -975,6 +1410,7 @@ pub mod nav_msgs {
         Debug,
         Clone,
         PartialEq,
+        PartialOrd,
     )]
     pub struct GetPlanResponse {
         pub r#plan: self::Path,
@@ -985,6 +1421,12 @@ pub mod nav_msgs {
         const DEFINITION: &'static str =
             "# Array of poses from start to goal if one was successfully found.\nPath plan";
     }
+    impl GetPlanResponse {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            self.r#plan.serialized_size_upper_bound()
+        }
+    }
     pub struct GetPlan {}
     impl ::roslibrust_codegen::RosServiceType for GetPlan {
         const ROS_SERVICE_NAME: &'static str = "nav_msgs/GetPlan";
@@ -1000,6 +1442,9 @@ pub mod nav_msgs {
         Debug,
         Clone,
         PartialEq,
+        Eq,
+        PartialOrd,
+        Ord,
     )]
     pub struct LoadMapRequest {
         pub r#map_url: ::std::string::String,
@@ -1009,6 +1454,12 @@ pub mod nav_msgs {
         const MD5SUM: &'static str = "3813ba1ae85fbcd4dc88c90f1426b90b";
         const DEFINITION : & 'static str = "# URL of map resource\n# Can be an absolute path to a file: file:///path/to/maps/floor1.yaml\n# Or, relative to a ROS package: package://my_ros_package/maps/floor2.yaml\nstring map_url" ;
     }
+    impl LoadMapRequest {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            4usize + self.r#map_url.len()
+        }
+    }
     #[allow(non_snake_case)]
     #[derive(
         :: serde :: Deserialize,
@@ -1017,6 +1468,7 @@ pub mod nav_msgs {
         Debug,
         Clone,
         PartialEq,
+        PartialOrd,
     )]
     pub struct LoadMapResponse {
         pub r#map: self::OccupancyGrid,
@@ -1028,11 +1480,20 @@ pub mod nav_msgs {
         const DEFINITION : & 'static str = "# Result code defintions\nuint8 RESULT_SUCCESS=0\nuint8 RESULT_MAP_DOES_NOT_EXIST=1\nuint8 RESULT_INVALID_MAP_DATA=2\nuint8 RESULT_INVALID_MAP_METADATA=3\nuint8 RESULT_UNDEFINED_FAILURE=255\n\n# Returned map is only valid if result equals RESULT_SUCCESS\nnav_msgs/OccupancyGrid map\nuint8 result" ;
     }
     impl LoadMapResponse {
+        #[doc = " = 0"]
         pub const r#RESULT_SUCCESS: u8 = 0u8;
+        #[doc = " = 1"]
         pub const r#RESULT_MAP_DOES_NOT_EXIST: u8 = 1u8;
+        #[doc = " = 2"]
         pub const r#RESULT_INVALID_MAP_DATA: u8 = 2u8;
+        #[doc = " = 3"]
         pub const r#RESULT_INVALID_MAP_METADATA: u8 = 3u8;
+        #[doc = " = 255"]
         pub const r#RESULT_UNDEFINED_FAILURE: u8 = 255u8;
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            self.r#map.serialized_size_upper_bound() + 1usize
+        }
     }
     pub struct LoadMap {}
     impl ::roslibrust_codegen::RosServiceType for LoadMap {
@@ -1049,6 +1510,7 @@ pub mod nav_msgs {
         Debug,
         Clone,
         PartialEq,
+        PartialOrd,
     )]
     pub struct SetMapRequest {
         pub r#map: self::OccupancyGrid,
@@ -1059,6 +1521,13 @@ pub mod nav_msgs {
         const MD5SUM: &'static str = "98782a373ad73e1165352caf85923850";
         const DEFINITION : & 'static str = "# Set a new map together with an initial pose\n\n# Requested 2D map to be set.\nnav_msgs/OccupancyGrid map\n\n# Estimated initial pose when setting new map.\ngeometry_msgs/PoseWithCovarianceStamped initial_pose" ;
     }
+    impl SetMapRequest {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            self.r#map.serialized_size_upper_bound()
+                + self.r#initial_pose.serialized_size_upper_bound()
+        }
+    }
     #[allow(non_snake_case)]
     #[derive(
         :: serde :: Deserialize,
@@ -1067,6 +1536,9 @@ pub mod nav_msgs {
         Debug,
         Clone,
         PartialEq,
+        Eq,
+        PartialOrd,
+        Ord,
     )]
     pub struct SetMapResponse {
         pub r#success: bool,
@@ -1077,6 +1549,12 @@ pub mod nav_msgs {
         const DEFINITION: &'static str =
             "# True if the map was successfully set, false otherwise.\nbool success";
     }
+    impl SetMapResponse {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            1usize
+        }
+    }
     pub struct SetMap {}
     impl ::roslibrust_codegen::RosServiceType for SetMap {
         const ROS_SERVICE_NAME: &'static str = "nav_msgs/SetMap";
@@ -1085,7 +1563,7 @@ pub mod nav_msgs {
         type Response = SetMapResponse;
     }
 }
-#[allow(unused_imports)]
+#[allow(unused_imports, deprecated)]
 pub mod sensor_msgs {
     use super::actionlib_msgs;
     use super::diagnostic_msgs;
@@ -1106,6 +1584,7 @@ pub mod sensor_msgs {
         Debug,
         Clone,
         PartialEq,
+        PartialOrd,
     )]
     pub struct BatteryState {
         pub r#header: std_msgs::Header,
@@ -1131,27 +1610,71 @@ pub mod sensor_msgs {
         const DEFINITION : & 'static str = "# Constants are chosen to match the enums in the linux kernel\n# defined in include/linux/power_supply.h as of version 3.7\n# The one difference is for style reasons the constants are\n# all uppercase not mixed case.\n\n# Power supply status constants\nuint8 POWER_SUPPLY_STATUS_UNKNOWN = 0\nuint8 POWER_SUPPLY_STATUS_CHARGING = 1\nuint8 POWER_SUPPLY_STATUS_DISCHARGING = 2\nuint8 POWER_SUPPLY_STATUS_NOT_CHARGING = 3\nuint8 POWER_SUPPLY_STATUS_FULL = 4\n\n# Power supply health constants\nuint8 POWER_SUPPLY_HEALTH_UNKNOWN = 0\nuint8 POWER_SUPPLY_HEALTH_GOOD = 1\nuint8 POWER_SUPPLY_HEALTH_OVERHEAT = 2\nuint8 POWER_SUPPLY_HEALTH_DEAD = 3\nuint8 POWER_SUPPLY_HEALTH_OVERVOLTAGE = 4\nuint8 POWER_SUPPLY_HEALTH_UNSPEC_FAILURE = 5\nuint8 POWER_SUPPLY_HEALTH_COLD = 6\nuint8 POWER_SUPPLY_HEALTH_WATCHDOG_TIMER_EXPIRE = 7\nuint8 POWER_SUPPLY_HEALTH_SAFETY_TIMER_EXPIRE = 8\n\n# Power supply technology (chemistry) constants\nuint8 POWER_SUPPLY_TECHNOLOGY_UNKNOWN = 0\nuint8 POWER_SUPPLY_TECHNOLOGY_NIMH = 1\nuint8 POWER_SUPPLY_TECHNOLOGY_LION = 2\nuint8 POWER_SUPPLY_TECHNOLOGY_LIPO = 3\nuint8 POWER_SUPPLY_TECHNOLOGY_LIFE = 4\nuint8 POWER_SUPPLY_TECHNOLOGY_NICD = 5\nuint8 POWER_SUPPLY_TECHNOLOGY_LIMN = 6\n\nstd_msgs/Header  header\nfloat32 voltage          # Voltage in Volts (Mandatory)\nfloat32 temperature      # Temperature in Degrees Celsius (If unmeasured NaN)\nfloat32 current          # Negative when discharging (A)  (If unmeasured NaN)\nfloat32 charge           # Current charge in Ah  (If unmeasured NaN)\nfloat32 capacity         # Capacity in Ah (last full capacity)  (If unmeasured NaN)\nfloat32 design_capacity  # Capacity in Ah (design capacity)  (If unmeasured NaN)\nfloat32 percentage       # Charge percentage on 0 to 1 range  (If unmeasured NaN)\nuint8   power_supply_status     # The charging status as reported. Values defined above\nuint8   power_supply_health     # The battery health metric. Values defined above\nuint8   power_supply_technology # The battery chemistry. Values defined above\nbool    present          # True if the battery is present\n\nfloat32[] cell_voltage   # An array of individual cell voltages for each cell in the pack\n                         # If individual voltages unknown but number of cells known set each to NaN\nfloat32[] cell_temperature # An array of individual cell temperatures for each cell in the pack\n                           # If individual temperatures unknown but number of cells known set each to NaN\nstring location          # The location into which the battery is inserted. (slot number or plug)\nstring serial_number     # The best approximation of the battery serial number" ;
     }
     impl BatteryState {
+        #[doc = " = 0"]
         pub const r#POWER_SUPPLY_STATUS_UNKNOWN: u8 = 0u8;
+        #[doc = " = 1"]
         pub const r#POWER_SUPPLY_STATUS_CHARGING: u8 = 1u8;
+        #[doc = " = 2"]
         pub const r#POWER_SUPPLY_STATUS_DISCHARGING: u8 = 2u8;
+        #[doc = " = 3"]
         pub const r#POWER_SUPPLY_STATUS_NOT_CHARGING: u8 = 3u8;
+        #[doc = " = 4"]
         pub const r#POWER_SUPPLY_STATUS_FULL: u8 = 4u8;
+        #[doc = " = 0"]
         pub const r#POWER_SUPPLY_HEALTH_UNKNOWN: u8 = 0u8;
+        #[doc = " = 1"]
         pub const r#POWER_SUPPLY_HEALTH_GOOD: u8 = 1u8;
+        #[doc = " = 2"]
         pub const r#POWER_SUPPLY_HEALTH_OVERHEAT: u8 = 2u8;
+        #[doc = " = 3"]
         pub const r#POWER_SUPPLY_HEALTH_DEAD: u8 = 3u8;
+        #[doc = " = 4"]
         pub const r#POWER_SUPPLY_HEALTH_OVERVOLTAGE: u8 = 4u8;
+        #[doc = " = 5"]
         pub const r#POWER_SUPPLY_HEALTH_UNSPEC_FAILURE: u8 = 5u8;
+        #[doc = " = 6"]
         pub const r#POWER_SUPPLY_HEALTH_COLD: u8 = 6u8;
+        #[doc = " = 7"]
         pub const r#POWER_SUPPLY_HEALTH_WATCHDOG_TIMER_EXPIRE: u8 = 7u8;
+        #[doc = " = 8"]
         pub const r#POWER_SUPPLY_HEALTH_SAFETY_TIMER_EXPIRE: u8 = 8u8;
+        #[doc = " = 0"]
         pub const r#POWER_SUPPLY_TECHNOLOGY_UNKNOWN: u8 = 0u8;
+        #[doc = " = 1"]
         pub const r#POWER_SUPPLY_TECHNOLOGY_NIMH: u8 = 1u8;
+        #[doc = " = 2"]
         pub const r#POWER_SUPPLY_TECHNOLOGY_LION: u8 = 2u8;
+        #[doc = " = 3"]
         pub const r#POWER_SUPPLY_TECHNOLOGY_LIPO: u8 = 3u8;
+        #[doc = " = 4"]
         pub const r#POWER_SUPPLY_TECHNOLOGY_LIFE: u8 = 4u8;
+        #[doc = " = 5"]
         pub const r#POWER_SUPPLY_TECHNOLOGY_NICD: u8 = 5u8;
+        #[doc = " = 6"]
         pub const r#POWER_SUPPLY_TECHNOLOGY_LIMN: u8 = 6u8;
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            self.r#header.serialized_size_upper_bound()
+                + 4usize
+                + 4usize
+                + 4usize
+                + 4usize
+                + 4usize
+                + 4usize
+                + 4usize
+                + 1usize
+                + 1usize
+                + 1usize
+                + 1usize
+                + 4usize
+                + self.r#cell_voltage.len() * 4usize
+                + 4usize
+                + self.r#cell_temperature.len() * 4usize
+                + 4usize
+                + self.r#location.len()
+                + 4usize
+                + self.r#serial_number.len()
+        }
     }
     #[allow(non_snake_case)]
     #[derive(
@@ -1161,6 +1684,7 @@ pub mod sensor_msgs {
         Debug,
         Clone,
         PartialEq,
+        PartialOrd,
     )]
     pub struct CameraInfo {
         pub r#header: std_msgs::Header,
@@ -1180,6 +1704,24 @@ pub mod sensor_msgs {
         const MD5SUM: &'static str = "47b55ddbbf2ec398f94cddf328bbc2ac";
         const DEFINITION : & 'static str = "# This message defines meta information for a camera. It should be in a\n# camera namespace on topic \"camera_info\" and accompanied by up to five\n# image topics named:\n#\n#   image_raw - raw data from the camera driver, possibly Bayer encoded\n#   image            - monochrome, distorted\n#   image_color      - color, distorted\n#   image_rect       - monochrome, rectified\n#   image_rect_color - color, rectified\n#\n# The image_pipeline contains packages (image_proc, stereo_image_proc)\n# for producing the four processed image topics from image_raw and\n# camera_info. The meaning of the camera parameters are described in\n# detail at http://www.ros.org/wiki/image_pipeline/CameraInfo.\n#\n# The image_geometry package provides a user-friendly interface to\n# common operations using this meta information. If you want to, e.g.,\n# project a 3d point into image coordinates, we strongly recommend\n# using image_geometry.\n#\n# If the camera is uncalibrated, the matrices D, K, R, P should be left\n# zeroed out. In particular, clients may assume that K[0] == 0.0\n# indicates an uncalibrated camera.\n\n#######################################################################\n#                     Image acquisition info                          #\n#######################################################################\n\n# Time of image acquisition, camera coordinate frame ID\nstd_msgs/Header header # Header timestamp should be acquisition time of image\n                             # Header frame_id should be optical frame of camera\n                             # origin of frame should be optical center of camera\n                             # +x should point to the right in the image\n                             # +y should point down in the image\n                             # +z should point into the plane of the image\n\n\n#######################################################################\n#                      Calibration Parameters                         #\n#######################################################################\n# These are fixed during camera calibration. Their values will be the #\n# same in all messages until the camera is recalibrated. Note that    #\n# self-calibrating systems may \"recalibrate\" frequently.              #\n#                                                                     #\n# The internal parameters can be used to warp a raw (distorted) image #\n# to:                                                                 #\n#   1. An undistorted image (requires D and K)                        #\n#   2. A rectified image (requires D, K, R)                           #\n# The projection matrix P projects 3D points into the rectified image.#\n#######################################################################\n\n# The image dimensions with which the camera was calibrated.\n# Normally this will be the full camera resolution in pixels.\nuint32 height\nuint32 width\n\n# The distortion model used. Supported models are listed in\n# sensor_msgs/distortion_models.hpp. For most cameras, \"plumb_bob\" - a\n# simple model of radial and tangential distortion - is sufficent.\nstring distortion_model\n\n# The distortion parameters, size depending on the distortion model.\n# For \"plumb_bob\", the 5 parameters are: (k1, k2, t1, t2, k3).\nfloat64[] d\n\n# Intrinsic camera matrix for the raw (distorted) images.\n#     [fx  0 cx]\n# K = [ 0 fy cy]\n#     [ 0  0  1]\n# Projects 3D points in the camera coordinate frame to 2D pixel\n# coordinates using the focal lengths (fx, fy) and principal point\n# (cx, cy).\nfloat64[9]  k # 3x3 row-major matrix\n\n# Rectification matrix (stereo cameras only)\n# A rotation matrix aligning the camera coordinate system to the ideal\n# stereo image plane so that epipolar lines in both stereo images are\n# parallel.\nfloat64[9]  r # 3x3 row-major matrix\n\n# Projection/camera matrix\n#     [fx'  0  cx' Tx]\n# P = [ 0  fy' cy' Ty]\n#     [ 0   0   1   0]\n# By convention, this matrix specifies the intrinsic (camera) matrix\n#  of the processed (rectified) image. That is, the left 3x3 portion\n#  is the normal camera intrinsic matrix for the rectified image.\n# It projects 3D points in the camera coordinate frame to 2D pixel\n#  coordinates using the focal lengths (fx', fy') and principal point\n#  (cx', cy') - these may differ from the values in K.\n# For monocular cameras, Tx = Ty = 0. Normally, monocular cameras will\n#  also have R = the identity and P[1:3,1:3] = K.\n# For a stereo pair, the fourth column [Tx Ty 0]' is related to the\n#  position of the optical center of the second camera in the first\n#  camera's frame. We assume Tz = 0 so both cameras are in the same\n#  stereo image plane. The first camera always has Tx = Ty = 0. For\n#  the right (second) camera of a horizontal stereo pair, Ty = 0 and\n#  Tx = -fx' * B, where B is the baseline between the cameras.\n# Given a 3D point [X Y Z]', the projection (x, y) of the point onto\n#  the rectified image is given by:\n#  [u v w]' = P * [X Y Z 1]'\n#         x = u / w\n#         y = v / w\n#  This holds for both images of a stereo pair.\nfloat64[12] p # 3x4 row-major matrix\n\n\n#######################################################################\n#                      Operational Parameters                         #\n#######################################################################\n# These define the image region actually captured by the camera       #\n# driver. Although they affect the geometry of the output image, they #\n# may be changed freely without recalibrating the camera.             #\n#######################################################################\n\n# Binning refers here to any camera setting which combines rectangular\n#  neighborhoods of pixels into larger \"super-pixels.\" It reduces the\n#  resolution of the output image to\n#  (width / binning_x) x (height / binning_y).\n# The default values binning_x = binning_y = 0 is considered the same\n#  as binning_x = binning_y = 1 (no subsampling).\nuint32 binning_x\nuint32 binning_y\n\n# Region of interest (subwindow of full camera resolution), given in\n#  full resolution (unbinned) image coordinates. A particular ROI\n#  always denotes the same window of pixels on the camera sensor,\n#  regardless of binning settings.\n# The default setting of roi (all values 0) is considered the same as\n#  full resolution (roi.width = width, roi.height = height).\nRegionOfInterest roi" ;
     }
+    impl CameraInfo {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            self.r#header.serialized_size_upper_bound()
+                + 4usize
+                + 4usize
+                + 4usize
+                + self.r#distortion_model.len()
+                + 4usize
+                + self.r#d.len() * 8usize
+                + 72usize
+                + 72usize
+                + 96usize
+                + 4usize
+                + 4usize
+                + self.r#roi.serialized_size_upper_bound()
+        }
+    }
     #[allow(non_snake_case)]
     #[derive(
         :: serde :: Deserialize,
@@ -1188,6 +1730,7 @@ pub mod sensor_msgs {
         Debug,
         Clone,
         PartialEq,
+        PartialOrd,
     )]
     pub struct ChannelFloat32 {
         pub r#name: ::std::string::String,
@@ -1198,6 +1741,12 @@ pub mod sensor_msgs {
         const MD5SUM: &'static str = "3d40139cdd33dfedcb71ffeeeb42ae7f";
         const DEFINITION : & 'static str = "# This message is used by the PointCloud message to hold optional data\n# associated with each point in the cloud. The length of the values\n# array should be the same as the length of the points array in the\n# PointCloud, and each value should be associated with the corresponding\n# point.\n#\n# Channel names in existing practice include:\n#   \"u\", \"v\" - row and column (respectively) in the left stereo image.\n#              This is opposite to usual conventions but remains for\n#              historical reasons. The newer PointCloud2 message has no\n#              such problem.\n#   \"rgb\" - For point clouds produced by color stereo cameras. uint8\n#           (R,G,B) values packed into the least significant 24 bits,\n#           in order.\n#   \"intensity\" - laser or pixel intensity.\n#   \"distance\"\n\n# The channel name should give semantics of the channel (e.g.\n# \"intensity\" instead of \"value\").\nstring name\n\n# The values array should be 1-1 with the elements of the associated\n# PointCloud.\nfloat32[] values" ;
     }
+    impl ChannelFloat32 {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            4usize + self.r#name.len() + 4usize + self.r#values.len() * 4usize
+        }
+    }
     #[allow(non_snake_case)]
     #[derive(
         :: serde :: Deserialize,
@@ -1206,6 +1755,9 @@ pub mod sensor_msgs {
         Debug,
         Clone,
         PartialEq,
+        Eq,
+        PartialOrd,
+        Ord,
     )]
     pub struct CompressedImage {
         pub r#header: std_msgs::Header,
@@ -1217,6 +1769,16 @@ pub mod sensor_msgs {
         const MD5SUM: &'static str = "1df88053b24348f5f499666c7cb1d980";
         const DEFINITION : & 'static str = "# This message contains a compressed image.\n\nstd_msgs/Header header # Header timestamp should be acquisition time of image\n                             # Header frame_id should be optical frame of camera\n                             # origin of frame should be optical center of cameara\n                             # +x should point to the right in the image\n                             # +y should point down in the image\n                             # +z should point into to plane of the image\n\nstring format                # Specifies the format of the data\n                             #   Acceptable values:\n                             #     jpeg, png, tiff\n\nuint8[] data                 # Compressed image buffer" ;
     }
+    impl CompressedImage {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            self.r#header.serialized_size_upper_bound()
+                + 4usize
+                + self.r#format.len()
+                + 4usize
+                + self.r#data.len()
+        }
+    }
     #[allow(non_snake_case)]
     #[derive(
         :: serde :: Deserialize,
@@ -1225,6 +1787,7 @@ pub mod sensor_msgs {
         Debug,
         Clone,
         PartialEq,
+        PartialOrd,
     )]
     pub struct FluidPressure {
         pub r#header: std_msgs::Header,
@@ -1236,6 +1799,12 @@ pub mod sensor_msgs {
         const MD5SUM: &'static str = "4967e6ff4dcf72e6b8fca0600661e0b6";
         const DEFINITION : & 'static str = "# Single pressure reading.  This message is appropriate for measuring the\n# pressure inside of a fluid (air, water, etc).  This also includes\n# atmospheric or barometric pressure.\n#\n# This message is not appropriate for force/pressure contact sensors.\n\nstd_msgs/Header header # timestamp of the measurement\n                             # frame_id is the location of the pressure sensor\n\nfloat64 fluid_pressure       # Absolute pressure reading in Pascals.\n\nfloat64 variance             # 0 is interpreted as variance unknown" ;
     }
+    impl FluidPressure {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            self.r#header.serialized_size_upper_bound() + 8usize + 8usize
+        }
+    }
     #[allow(non_snake_case)]
     #[derive(
         :: serde :: Deserialize,
@@ -1244,6 +1813,7 @@ pub mod sensor_msgs {
         Debug,
         Clone,
         PartialEq,
+        PartialOrd,
     )]
     pub struct Illuminance {
         pub r#header: std_msgs::Header,
@@ -1255,6 +1825,12 @@ pub mod sensor_msgs {
         const MD5SUM: &'static str = "94ccac1a1be684df74466dfc561512aa";
         const DEFINITION : & 'static str = "# Single photometric illuminance measurement.  Light should be assumed to be\n# measured along the sensor's x-axis (the area of detection is the y-z plane).\n# The illuminance should have a 0 or positive value and be received with\n# the sensor's +X axis pointing toward the light source.\n#\n# Photometric illuminance is the measure of the human eye's sensitivity of the\n# intensity of light encountering or passing through a surface.\n#\n# All other Photometric and Radiometric measurements should not use this message.\n# This message cannot represent:\n#  - Luminous intensity (candela/light source output)\n#  - Luminance (nits/light output per area)\n#  - Irradiance (watt/area), etc.\n\nstd_msgs/Header header # timestamp is the time the illuminance was measured\n                             # frame_id is the location and direction of the reading\n\nfloat64 illuminance          # Measurement of the Photometric Illuminance in Lux.\n\nfloat64 variance             # 0 is interpreted as variance unknown" ;
     }
+    impl Illuminance {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            self.r#header.serialized_size_upper_bound() + 8usize + 8usize
+        }
+    }
     #[allow(non_snake_case)]
     #[derive(
         :: serde :: Deserialize,
@@ -1263,6 +1839,9 @@ pub mod sensor_msgs {
         Debug,
         Clone,
         PartialEq,
+        Eq,
+        PartialOrd,
+        Ord,
     )]
     pub struct Image {
         pub r#header: std_msgs::Header,
@@ -1278,6 +1857,20 @@ pub mod sensor_msgs {
         const MD5SUM: &'static str = "9c8b3d25a28b72f070da359dbecf985b";
         const DEFINITION : & 'static str = "# This message contains an uncompressed image\n# (0, 0) is at top-left corner of image\n\nstd_msgs/Header header # Header timestamp should be acquisition time of image\n                             # Header frame_id should be optical frame of camera\n                             # origin of frame should be optical center of cameara\n                             # +x should point to the right in the image\n                             # +y should point down in the image\n                             # +z should point into to plane of the image\n                             # If the frame_id here and the frame_id of the CameraInfo\n                             # message associated with the image conflict\n                             # the behavior is undefined\n\nuint32 height                # image height, that is, number of rows\nuint32 width                 # image width, that is, number of columns\n\n# The legal values for encoding are in file include/sensor_msgs/image_encodings.hpp\n# If you want to standardize a new string format, join\n# ros-users@lists.ros.org and send an email proposing a new encoding.\n\nstring encoding       # Encoding of pixels -- channel meaning, ordering, size\n                      # taken from the list of strings in include/sensor_msgs/image_encodings.hpp\n\nuint8 is_bigendian    # is this data bigendian?\nuint32 step           # Full row length in bytes\nuint8[] data          # actual matrix data, size is (step * rows)" ;
     }
+    impl Image {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            self.r#header.serialized_size_upper_bound()
+                + 4usize
+                + 4usize
+                + 4usize
+                + self.r#encoding.len()
+                + 1usize
+                + 4usize
+                + 4usize
+                + self.r#data.len()
+        }
+    }
     #[allow(non_snake_case)]
     #[derive(
         :: serde :: Deserialize,
@@ -1286,6 +1879,7 @@ pub mod sensor_msgs {
         Debug,
         Clone,
         PartialEq,
+        PartialOrd,
     )]
     pub struct Imu {
         pub r#header: std_msgs::Header,
@@ -1301,6 +1895,18 @@ pub mod sensor_msgs {
         const MD5SUM: &'static str = "058a92f712764b4ade1563e82041c569";
         const DEFINITION : & 'static str = "# This is a message to hold data from an IMU (Inertial Measurement Unit)\n#\n# Accelerations should be in m/s^2 (not in g's), and rotational velocity should be in rad/sec\n#\n# If the covariance of the measurement is known, it should be filled in (if all you know is the\n# variance of each measurement, e.g. from the datasheet, just put those along the diagonal)\n# A covariance matrix of all zeros will be interpreted as \"covariance unknown\", and to use the\n# data a covariance will have to be assumed or gotten from some other source\n#\n# If you have no estimate for one of the data elements (e.g. your IMU doesn't produce an\n# orientation estimate), please set element 0 of the associated covariance matrix to -1\n# If you are interpreting this message, please check for a value of -1 in the first element of each\n# covariance matrix, and disregard the associated estimate.\n\nstd_msgs/Header header\n\ngeometry_msgs/Quaternion orientation\nfloat64[9] orientation_covariance # Row major about x, y, z axes\n\ngeometry_msgs/Vector3 angular_velocity\nfloat64[9] angular_velocity_covariance # Row major about x, y, z axes\n\ngeometry_msgs/Vector3 linear_acceleration\nfloat64[9] linear_acceleration_covariance # Row major x, y z" ;
     }
+    impl Imu {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            self.r#header.serialized_size_upper_bound()
+                + self.r#orientation.serialized_size_upper_bound()
+                + 72usize
+                + self.r#angular_velocity.serialized_size_upper_bound()
+                + 72usize
+                + self.r#linear_acceleration.serialized_size_upper_bound()
+                + 72usize
+        }
+    }
     #[allow(non_snake_case)]
     #[derive(
         :: serde :: Deserialize,
@@ -1309,6 +1915,7 @@ pub mod sensor_msgs {
         Debug,
         Clone,
         PartialEq,
+        PartialOrd,
     )]
     pub struct JointState {
         pub r#header: std_msgs::Header,
@@ -1322,6 +1929,24 @@ pub mod sensor_msgs {
         const MD5SUM: &'static str = "3f61f1439a9898cdd864497d378ce55c";
         const DEFINITION : & 'static str = "# This is a message that holds data to describe the state of a set of torque controlled joints.\n#\n# The state of each joint (revolute or prismatic) is defined by:\n#  * the position of the joint (rad or m),\n#  * the velocity of the joint (rad/s or m/s) and\n#  * the effort that is applied in the joint (Nm or N).\n#\n# Each joint is uniquely identified by its name\n# The header specifies the time at which the joint states were recorded. All the joint states\n# in one message have to be recorded at the same time.\n#\n# This message consists of a multiple arrays, one for each part of the joint state.\n# The goal is to make each of the fields optional. When e.g. your joints have no\n# effort associated with them, you can leave the effort array empty.\n#\n# All arrays in this message should have the same size, or be empty.\n# This is the only way to uniquely associate the joint name with the correct\n# states.\n\nstd_msgs/Header header\n\nstring[] name\nfloat64[] position\nfloat64[] velocity\nfloat64[] effort" ;
     }
+    impl JointState {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            self.r#header.serialized_size_upper_bound()
+                + 4usize
+                + self
+                    .r#name
+                    .iter()
+                    .map(|element| 4usize + element.len())
+                    .sum::<usize>()
+                + 4usize
+                + self.r#position.len() * 8usize
+                + 4usize
+                + self.r#velocity.len() * 8usize
+                + 4usize
+                + self.r#effort.len() * 8usize
+        }
+    }
     #[allow(non_snake_case)]
     #[derive(
         :: serde :: Deserialize,
@@ -1330,6 +1955,7 @@ pub mod sensor_msgs {
         Debug,
         Clone,
         PartialEq,
+        PartialOrd,
     )]
     pub struct Joy {
         pub r#header: std_msgs::Header,
@@ -1341,6 +1967,16 @@ pub mod sensor_msgs {
         const MD5SUM: &'static str = "967f985c9ca9013a4669430613e3e016";
         const DEFINITION : & 'static str = "# Reports the state of a joystick's axes and buttons.\n\n# The timestamp is the time at which data is received from the joystick.\nstd_msgs/Header header\n\n# The axes measurements from a joystick.\nfloat32[] axes\n\n# The buttons measurements from a joystick.\nint32[] buttons" ;
     }
+    impl Joy {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            self.r#header.serialized_size_upper_bound()
+                + 4usize
+                + self.r#axes.len() * 4usize
+                + 4usize
+                + self.r#buttons.len() * 4usize
+        }
+    }
     #[allow(non_snake_case)]
     #[derive(
         :: serde :: Deserialize,
@@ -1349,6 +1985,7 @@ pub mod sensor_msgs {
         Debug,
         Clone,
         PartialEq,
+        PartialOrd,
     )]
     pub struct JoyFeedback {
         pub r#type: u8,
@@ -1361,9 +1998,16 @@ pub mod sensor_msgs {
         const DEFINITION : & 'static str = "# Declare of the type of feedback\nuint8 TYPE_LED    = 0\nuint8 TYPE_RUMBLE = 1\nuint8 TYPE_BUZZER = 2\n\nuint8 type\n\n# This will hold an id number for each type of each feedback.\n# Example, the first led would be id=0, the second would be id=1\nuint8 id\n\n# Intensity of the feedback, from 0.0 to 1.0, inclusive.  If device is\n# actually binary, driver should treat 0<=x<0.5 as off, 0.5<=x<=1 as on.\nfloat32 intensity" ;
     }
     impl JoyFeedback {
+        #[doc = " = 0"]
         pub const r#TYPE_LED: u8 = 0u8;
+        #[doc = " = 1"]
         pub const r#TYPE_RUMBLE: u8 = 1u8;
+        #[doc = " = 2"]
         pub const r#TYPE_BUZZER: u8 = 2u8;
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            6usize
+        }
     }
     #[allow(non_snake_case)]
     #[derive(
@@ -1373,6 +2017,7 @@ pub mod sensor_msgs {
         Debug,
         Clone,
         PartialEq,
+        PartialOrd,
     )]
     pub struct JoyFeedbackArray {
         pub r#array: ::std::vec::Vec<self::JoyFeedback>,
@@ -1383,6 +2028,17 @@ pub mod sensor_msgs {
         const DEFINITION: &'static str =
             "# This message publishes values for multiple feedback at once.\nJoyFeedback[] array";
     }
+    impl JoyFeedbackArray {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            4usize
+                + self
+                    .r#array
+                    .iter()
+                    .map(|element| element.serialized_size_upper_bound())
+                    .sum::<usize>()
+        }
+    }
     #[allow(non_snake_case)]
     #[derive(
         :: serde :: Deserialize,
@@ -1391,6 +2047,7 @@ pub mod sensor_msgs {
         Debug,
         Clone,
         PartialEq,
+        PartialOrd,
     )]
     pub struct LaserEcho {
         pub r#echoes: ::std::vec::Vec<f32>,
@@ -1400,6 +2057,12 @@ pub mod sensor_msgs {
         const MD5SUM: &'static str = "8bc5ae449b200fba4d552b4225586696";
         const DEFINITION : & 'static str = "# This message is a submessage of MultiEchoLaserScan and is not intended\n# to be used separately.\n\nfloat32[] echoes  # Multiple values of ranges or intensities.\n                  # Each array represents data from the same angle increment." ;
     }
+    impl LaserEcho {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            4usize + self.r#echoes.len() * 4usize
+        }
+    }
     #[allow(non_snake_case)]
     #[derive(
         :: serde :: Deserialize,
@@ -1408,6 +2071,7 @@ pub mod sensor_msgs {
         Debug,
         Clone,
         PartialEq,
+        PartialOrd,
     )]
     pub struct LaserScan {
         pub r#header: std_msgs::Header,
@@ -1426,6 +2090,23 @@ pub mod sensor_msgs {
         const MD5SUM: &'static str = "f86984b4383bf67523c75820e114e988";
         const DEFINITION : & 'static str = "# Single scan from a planar laser range-finder\n#\n# If you have another ranging device with different behavior (e.g. a sonar\n# array), please find or create a different message, since applications\n# will make fairly laser-specific assumptions about this data\n\nstd_msgs/Header header # timestamp in the header is the acquisition time of\n                             # the first ray in the scan.\n                             #\n                             # in frame frame_id, angles are measured around\n                             # the positive Z axis (counterclockwise, if Z is up)\n                             # with zero angle being forward along the x axis\n\nfloat32 angle_min            # start angle of the scan [rad]\nfloat32 angle_max            # end angle of the scan [rad]\nfloat32 angle_increment      # angular distance between measurements [rad]\n\nfloat32 time_increment       # time between measurements [seconds] - if your scanner\n                             # is moving, this will be used in interpolating position\n                             # of 3d points\nfloat32 scan_time            # time between scans [seconds]\n\nfloat32 range_min            # minimum range value [m]\nfloat32 range_max            # maximum range value [m]\n\nfloat32[] ranges             # range data [m]\n                             # (Note: values < range_min or > range_max should be discarded)\nfloat32[] intensities        # intensity data [device-specific units].  If your\n                             # device does not provide intensities, please leave\n                             # the array empty." ;
     }
+    impl LaserScan {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            self.r#header.serialized_size_upper_bound()
+                + 4usize
+                + 4usize
+                + 4usize
+                + 4usize
+                + 4usize
+                + 4usize
+                + 4usize
+                + 4usize
+                + self.r#ranges.len() * 4usize
+                + 4usize
+                + self.r#intensities.len() * 4usize
+        }
+    }
     #[allow(non_snake_case)]
     #[derive(
         :: serde :: Deserialize,
@@ -1434,6 +2115,7 @@ pub mod sensor_msgs {
         Debug,
         Clone,
         PartialEq,
+        PartialOrd,
     )]
     pub struct MagneticField {
         pub r#header: std_msgs::Header,
@@ -1445,6 +2127,14 @@ pub mod sensor_msgs {
         const MD5SUM: &'static str = "c8761d20eb9dc59addd882f1d4de2266";
         const DEFINITION : & 'static str = "# Measurement of the Magnetic Field vector at a specific location.\n#\n# If the covariance of the measurement is known, it should be filled in.\n# If all you know is the variance of each measurement, e.g. from the datasheet,\n# just put those along the diagonal.\n# A covariance matrix of all zeros will be interpreted as \"covariance unknown\",\n# and to use the data a covariance will have to be assumed or gotten from some\n# other source.\n\nstd_msgs/Header header               # timestamp is the time the\n                                           # field was measured\n                                           # frame_id is the location and orientation\n                                           # of the field measurement\n\ngeometry_msgs/Vector3 magnetic_field # x, y, and z components of the\n                                           # field vector in Tesla\n                                           # If your sensor does not output 3 axes,\n                                           # put NaNs in the components not reported.\n\nfloat64[9] magnetic_field_covariance       # Row major about x, y, z axes\n                                           # 0 is interpreted as variance unknown" ;
     }
+    impl MagneticField {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            self.r#header.serialized_size_upper_bound()
+                + self.r#magnetic_field.serialized_size_upper_bound()
+                + 72usize
+        }
+    }
     #[allow(non_snake_case)]
     #[derive(
         :: serde :: Deserialize,
@@ -1453,6 +2143,7 @@ pub mod sensor_msgs {
         Debug,
         Clone,
         PartialEq,
+        PartialOrd,
     )]
     pub struct MultiDOFJointState {
         pub r#header: std_msgs::Header,
@@ -1466,6 +2157,36 @@ pub mod sensor_msgs {
         const MD5SUM: &'static str = "9eb02d78422731545fd7e9b60069f261";
         const DEFINITION : & 'static str = "# Representation of state for joints with multiple degrees of freedom,\n# following the structure of JointState which can only represent a single degree of freedom.\n#\n# It is assumed that a joint in a system corresponds to a transform that gets applied\n# along the kinematic chain. For example, a planar joint (as in URDF) is 3DOF (x, y, yaw)\n# and those 3DOF can be expressed as a transformation matrix, and that transformation\n# matrix can be converted back to (x, y, yaw)\n#\n# Each joint is uniquely identified by its name\n# The header specifies the time at which the joint states were recorded. All the joint states\n# in one message have to be recorded at the same time.\n#\n# This message consists of a multiple arrays, one for each part of the joint state.\n# The goal is to make each of the fields optional. When e.g. your joints have no\n# wrench associated with them, you can leave the wrench array empty.\n#\n# All arrays in this message should have the same size, or be empty.\n# This is the only way to uniquely associate the joint name with the correct\n# states.\n\nstd_msgs/Header header\n\nstring[] joint_names\ngeometry_msgs/Transform[] transforms\ngeometry_msgs/Twist[] twist\ngeometry_msgs/Wrench[] wrench" ;
     }
+    impl MultiDOFJointState {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            self.r#header.serialized_size_upper_bound()
+                + 4usize
+                + self
+                    .r#joint_names
+                    .iter()
+                    .map(|element| 4usize + element.len())
+                    .sum::<usize>()
+                + 4usize
+                + self
+                    .r#transforms
+                    .iter()
+                    .map(|element| element.serialized_size_upper_bound())
+                    .sum::<usize>()
+                + 4usize
+                + self
+                    .r#twist
+                    .iter()
+                    .map(|element| element.serialized_size_upper_bound())
+                    .sum::<usize>()
+                + 4usize
+                + self
+                    .r#wrench
+                    .iter()
+                    .map(|element| element.serialized_size_upper_bound())
+                    .sum::<usize>()
+        }
+    }
     #[allow(non_snake_case)]
     #[derive(
         :: serde :: Deserialize,
@@ -1474,6 +2195,7 @@ pub mod sensor_msgs {
         Debug,
         Clone,
         PartialEq,
+        PartialOrd,
     )]
     pub struct MultiEchoLaserScan {
         pub r#header: std_msgs::Header,
@@ -1492,6 +2214,31 @@ pub mod sensor_msgs {
         const MD5SUM: &'static str = "fda674281c16cdee9a79d075ab27d12f";
         const DEFINITION : & 'static str = "# Single scan from a multi-echo planar laser range-finder\n#\n# If you have another ranging device with different behavior (e.g. a sonar\n# array), please find or create a different message, since applications\n# will make fairly laser-specific assumptions about this data\n\nstd_msgs/Header header # timestamp in the header is the acquisition time of\n                             # the first ray in the scan.\n                             #\n                             # in frame frame_id, angles are measured around\n                             # the positive Z axis (counterclockwise, if Z is up)\n                             # with zero angle being forward along the x axis\n\nfloat32 angle_min            # start angle of the scan [rad]\nfloat32 angle_max            # end angle of the scan [rad]\nfloat32 angle_increment      # angular distance between measurements [rad]\n\nfloat32 time_increment       # time between measurements [seconds] - if your scanner\n                             # is moving, this will be used in interpolating position\n                             # of 3d points\nfloat32 scan_time            # time between scans [seconds]\n\nfloat32 range_min            # minimum range value [m]\nfloat32 range_max            # maximum range value [m]\n\nLaserEcho[] ranges           # range data [m]\n                             # (Note: NaNs, values < range_min or > range_max should be discarded)\n                             # +Inf measurements are out of range\n                             # -Inf measurements are too close to determine exact distance.\nLaserEcho[] intensities      # intensity data [device-specific units].  If your\n                             # device does not provide intensities, please leave\n                             # the array empty." ;
     }
+    impl MultiEchoLaserScan {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            self.r#header.serialized_size_upper_bound()
+                + 4usize
+                + 4usize
+                + 4usize
+                + 4usize
+                + 4usize
+                + 4usize
+                + 4usize
+                + 4usize
+                + self
+                    .r#ranges
+                    .iter()
+                    .map(|element| element.serialized_size_upper_bound())
+                    .sum::<usize>()
+                + 4usize
+                + self
+                    .r#intensities
+                    .iter()
+                    .map(|element| element.serialized_size_upper_bound())
+                    .sum::<usize>()
+        }
+    }
     #[allow(non_snake_case)]
     #[derive(
         :: serde :: Deserialize,
@@ -1500,6 +2247,7 @@ pub mod sensor_msgs {
         Debug,
         Clone,
         PartialEq,
+        PartialOrd,
     )]
     pub struct NavSatFix {
         pub r#header: std_msgs::Header,
@@ -1516,10 +2264,24 @@ pub mod sensor_msgs {
         const DEFINITION : & 'static str = "# Navigation Satellite fix for any Global Navigation Satellite System\n#\n# Specified using the WGS 84 reference ellipsoid\n\n# header.stamp specifies the ROS time for this measurement (the\n#        corresponding satellite time may be reported using the\n#        sensor_msgs/TimeReference message).\n#\n# header.frame_id is the frame of reference reported by the satellite\n#        receiver, usually the location of the antenna.  This is a\n#        Euclidean frame relative to the vehicle, not a reference\n#        ellipsoid.\nstd_msgs/Header header\n\n# Satellite fix status information.\nNavSatStatus status\n\n# Latitude [degrees]. Positive is north of equator; negative is south.\nfloat64 latitude\n\n# Longitude [degrees]. Positive is east of prime meridian; negative is west.\nfloat64 longitude\n\n# Altitude [m]. Positive is above the WGS 84 ellipsoid\n# (quiet NaN if no altitude is available).\nfloat64 altitude\n\n# Position covariance [m^2] defined relative to a tangential plane\n# through the reported position. The components are East, North, and\n# Up (ENU), in row-major order.\n#\n# Beware: this coordinate system exhibits singularities at the poles.\nfloat64[9] position_covariance\n\n# If the covariance of the fix is known, fill it in completely. If the\n# GPS receiver provides the variance of each measurement, put them\n# along the diagonal. If only Dilution of Precision is available,\n# estimate an approximate covariance from that.\n\nuint8 COVARIANCE_TYPE_UNKNOWN = 0\nuint8 COVARIANCE_TYPE_APPROXIMATED = 1\nuint8 COVARIANCE_TYPE_DIAGONAL_KNOWN = 2\nuint8 COVARIANCE_TYPE_KNOWN = 3\n\nuint8 position_covariance_type" ;
     }
     impl NavSatFix {
+        #[doc = " = 0"]
         pub const r#COVARIANCE_TYPE_UNKNOWN: u8 = 0u8;
+        #[doc = " = 1"]
         pub const r#COVARIANCE_TYPE_APPROXIMATED: u8 = 1u8;
+        #[doc = " = 2"]
         pub const r#COVARIANCE_TYPE_DIAGONAL_KNOWN: u8 = 2u8;
+        #[doc = " = 3"]
         pub const r#COVARIANCE_TYPE_KNOWN: u8 = 3u8;
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            self.r#header.serialized_size_upper_bound()
+                + self.r#status.serialized_size_upper_bound()
+                + 8usize
+                + 8usize
+                + 8usize
+                + 72usize
+                + 1usize
+        }
     }
     #[allow(non_snake_case)]
     #[derive(
@@ -1529,6 +2291,9 @@ pub mod sensor_msgs {
         Debug,
         Clone,
         PartialEq,
+        Eq,
+        PartialOrd,
+        Ord,
     )]
     pub struct NavSatStatus {
         pub r#status: i8,
@@ -1540,14 +2305,26 @@ pub mod sensor_msgs {
         const DEFINITION : & 'static str = "# Navigation Satellite fix status for any Global Navigation Satellite System.\n#\n# Whether to output an augmented fix is determined by both the fix\n# type and the last time differential corrections were received.  A\n# fix is valid when status >= STATUS_FIX.\n\nint8 STATUS_NO_FIX =  -1        # unable to fix position\nint8 STATUS_FIX =      0        # unaugmented fix\nint8 STATUS_SBAS_FIX = 1        # with satellite-based augmentation\nint8 STATUS_GBAS_FIX = 2        # with ground-based augmentation\n\nint8 status\n\n# Bits defining which Global Navigation Satellite System signals were\n# used by the receiver.\n\nuint16 SERVICE_GPS =     1\nuint16 SERVICE_GLONASS = 2\nuint16 SERVICE_COMPASS = 4      # includes BeiDou.\nuint16 SERVICE_GALILEO = 8\n\nuint16 service" ;
     }
     impl NavSatStatus {
+        #[doc = " = -1 (unable to fix position)"]
         pub const r#STATUS_NO_FIX: i8 = -1i8;
+        #[doc = " = 0 (unaugmented fix)"]
         pub const r#STATUS_FIX: i8 = 0i8;
+        #[doc = " = 1 (with satellite-based augmentation)"]
         pub const r#STATUS_SBAS_FIX: i8 = 1i8;
+        #[doc = " = 2 (with ground-based augmentation)"]
         pub const r#STATUS_GBAS_FIX: i8 = 2i8;
+        #[doc = " = 1"]
         pub const r#SERVICE_GPS: u16 = 1u16;
+        #[doc = " = 2"]
         pub const r#SERVICE_GLONASS: u16 = 2u16;
+        #[doc = " = 4 (includes BeiDou.)"]
         pub const r#SERVICE_COMPASS: u16 = 4u16;
+        #[doc = " = 8"]
         pub const r#SERVICE_GALILEO: u16 = 8u16;
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            3usize
+        }
     }
     #[allow(non_snake_case)]
     #[derive(
@@ -1557,6 +2334,7 @@ pub mod sensor_msgs {
         Debug,
         Clone,
         PartialEq,
+        PartialOrd,
     )]
     pub struct PointCloud {
         pub r#header: std_msgs::Header,
@@ -1568,6 +2346,24 @@ pub mod sensor_msgs {
         const MD5SUM: &'static str = "95c9c548e015c235b38b961c79973db7";
         const DEFINITION : & 'static str = "## THIS MESSAGE IS DEPRECATED AS OF FOXY\n## Please use sensor_msgs/PointCloud2\n\n# This message holds a collection of 3d points, plus optional additional\n# information about each point.\n\n# Time of sensor data acquisition, coordinate frame ID.\nstd_msgs/Header header\n\n# Array of 3d points. Each Point32 should be interpreted as a 3d point\n# in the frame given in the header.\ngeometry_msgs/Point32[] points\n\n# Each channel should have the same number of elements as points array,\n# and the data in each channel should correspond 1:1 with each point.\n# Channel names in common practice are listed in ChannelFloat32.msg.\nChannelFloat32[] channels" ;
     }
+    impl PointCloud {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            self.r#header.serialized_size_upper_bound()
+                + 4usize
+                + self
+                    .r#points
+                    .iter()
+                    .map(|element| element.serialized_size_upper_bound())
+                    .sum::<usize>()
+                + 4usize
+                + self
+                    .r#channels
+                    .iter()
+                    .map(|element| element.serialized_size_upper_bound())
+                    .sum::<usize>()
+        }
+    }
     #[allow(non_snake_case)]
     #[derive(
         :: serde :: Deserialize,
@@ -1576,6 +2372,9 @@ pub mod sensor_msgs {
         Debug,
         Clone,
         PartialEq,
+        Eq,
+        PartialOrd,
+        Ord,
     )]
     pub struct PointCloud2 {
         pub r#header: std_msgs::Header,
@@ -1593,6 +2392,26 @@ pub mod sensor_msgs {
         const MD5SUM: &'static str = "c61ffb665fe19735825e4dd31b53913d";
         const DEFINITION : & 'static str = "# This message holds a collection of N-dimensional points, which may\n# contain additional information such as normals, intensity, etc. The\n# point data is stored as a binary blob, its layout described by the\n# contents of the \"fields\" array.\n#\n# The point cloud data may be organized 2d (image-like) or 1d (unordered).\n# Point clouds organized as 2d images may be produced by camera depth sensors\n# such as stereo or time-of-flight.\n\n# Time of sensor data acquisition, and the coordinate frame ID (for 3d points).\nstd_msgs/Header header\n\n# 2D structure of the point cloud. If the cloud is unordered, height is\n# 1 and width is the length of the point cloud.\nuint32 height\nuint32 width\n\n# Describes the channels and their layout in the binary data blob.\nPointField[] fields\n\nbool    is_bigendian # Is this data bigendian?\nuint32  point_step   # Length of a point in bytes\nuint32  row_step     # Length of a row in bytes\nuint8[] data         # Actual point data, size is (row_step*height)\n\nbool is_dense        # True if there are no invalid points" ;
     }
+    impl PointCloud2 {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            self.r#header.serialized_size_upper_bound()
+                + 4usize
+                + 4usize
+                + 4usize
+                + self
+                    .r#fields
+                    .iter()
+                    .map(|element| element.serialized_size_upper_bound())
+                    .sum::<usize>()
+                + 1usize
+                + 4usize
+                + 4usize
+                + 4usize
+                + self.r#data.len()
+                + 1usize
+        }
+    }
     #[allow(non_snake_case)]
     #[derive(
         :: serde :: Deserialize,
@@ -1601,6 +2420,9 @@ pub mod sensor_msgs {
         Debug,
         Clone,
         PartialEq,
+        Eq,
+        PartialOrd,
+        Ord,
     )]
     pub struct PointField {
         pub r#name: ::std::string::String,
@@ -1614,14 +2436,26 @@ pub mod sensor_msgs {
         const DEFINITION : & 'static str = "# This message holds the description of one point entry in the\n# PointCloud2 message format.\nuint8 INT8    = 1\nuint8 UINT8   = 2\nuint8 INT16   = 3\nuint8 UINT16  = 4\nuint8 INT32   = 5\nuint8 UINT32  = 6\nuint8 FLOAT32 = 7\nuint8 FLOAT64 = 8\n\n# Common PointField names are x, y, z, intensity, rgb, rgba\nstring name      # Name of field\nuint32 offset    # Offset from start of point struct\nuint8  datatype  # Datatype enumeration, see above\nuint32 count     # How many elements in the field" ;
     }
     impl PointField {
+        #[doc = " = 1"]
         pub const r#INT8: u8 = 1u8;
+        #[doc = " = 2"]
         pub const r#UINT8: u8 = 2u8;
+        #[doc = " = 3"]
         pub const r#INT16: u8 = 3u8;
+        #[doc = " = 4"]
         pub const r#UINT16: u8 = 4u8;
+        #[doc = " = 5"]
         pub const r#INT32: u8 = 5u8;
+        #[doc = " = 6"]
         pub const r#UINT32: u8 = 6u8;
+        #[doc = " = 7"]
         pub const r#FLOAT32: u8 = 7u8;
+        #[doc = " = 8"]
         pub const r#FLOAT64: u8 = 8u8;
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            4usize + self.r#name.len() + 4usize + 1usize + 4usize
+        }
     }
     #[allow(non_snake_case)]
     #[derive(
@@ -1631,6 +2465,7 @@ pub mod sensor_msgs {
         Debug,
         Clone,
         PartialEq,
+        PartialOrd,
     )]
     pub struct Range {
         pub r#header: std_msgs::Header,
@@ -1646,8 +2481,14 @@ pub mod sensor_msgs {
         const DEFINITION : & 'static str = "# Single range reading from an active ranger that emits energy and reports\n# one range reading that is valid along an arc at the distance measured.\n# This message is  not appropriate for laser scanners. See the LaserScan\n# message if you are working with a laser scanner.\n#\n# This message also can represent a fixed-distance (binary) ranger.  This\n# sensor will have min_range===max_range===distance of detection.\n# These sensors follow REP 117 and will output -Inf if the object is detected\n# and +Inf if the object is outside of the detection range.\n\nstd_msgs/Header header # timestamp in the header is the time the ranger\n                             # returned the distance reading\n\n# Radiation type enums\n# If you want a value added to this list, send an email to the ros-users list\nuint8 ULTRASOUND=0\nuint8 INFRARED=1\n\nuint8 radiation_type    # the type of radiation used by the sensor\n                        # (sound, IR, etc) [enum]\n\nfloat32 field_of_view   # the size of the arc that the distance reading is\n                        # valid for [rad]\n                        # the object causing the range reading may have\n                        # been anywhere within -field_of_view/2 and\n                        # field_of_view/2 at the measured range.\n                        # 0 angle corresponds to the x-axis of the sensor.\n\nfloat32 min_range       # minimum range value [m]\nfloat32 max_range       # maximum range value [m]\n                        # Fixed distance rangers require min_range==max_range\n\nfloat32 range           # range data [m]\n                        # (Note: values < range_min or > range_max should be discarded)\n                        # Fixed distance rangers only output -Inf or +Inf.\n                        # -Inf represents a detection within fixed distance.\n                        # (Detection too close to the sensor to quantify)\n                        # +Inf represents no detection within the fixed distance.\n                        # (Object out of range)" ;
     }
     impl Range {
+        #[doc = " = 0"]
         pub const r#ULTRASOUND: u8 = 0u8;
+        #[doc = " = 1"]
         pub const r#INFRARED: u8 = 1u8;
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            self.r#header.serialized_size_upper_bound() + 1usize + 4usize + 4usize + 4usize + 4usize
+        }
     }
     #[allow(non_snake_case)]
     #[derive(
@@ -1657,6 +2498,9 @@ pub mod sensor_msgs {
         Debug,
         Clone,
         PartialEq,
+        Eq,
+        PartialOrd,
+        Ord,
     )]
     pub struct RegionOfInterest {
         pub r#x_offset: u32,
@@ -1670,6 +2514,12 @@ pub mod sensor_msgs {
         const MD5SUM: &'static str = "bdb633039d588fcccb441a4d43ccfe09";
         const DEFINITION : & 'static str = "# This message is used to specify a region of interest within an image.\n#\n# When used to specify the ROI setting of the camera when the image was\n# taken, the height and width fields should either match the height and\n# width fields for the associated image; or height = width = 0\n# indicates that the full resolution image was captured.\n\nuint32 x_offset  # Leftmost pixel of the ROI\n                 # (0 if the ROI includes the left edge of the image)\nuint32 y_offset  # Topmost pixel of the ROI\n                 # (0 if the ROI includes the top edge of the image)\nuint32 height    # Height of ROI\nuint32 width     # Width of ROI\n\n# True if a distinct rectified ROI should be calculated from the \"raw\"\n# ROI in this message. Typically this should be False if the full image\n# is captured (ROI not used), and True if a subwindow is captured (ROI\n# used).\nbool do_rectify" ;
     }
+    impl RegionOfInterest {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            17usize
+        }
+    }
     #[allow(non_snake_case)]
     #[derive(
         :: serde :: Deserialize,
@@ -1678,6 +2528,7 @@ pub mod sensor_msgs {
         Debug,
         Clone,
         PartialEq,
+        PartialOrd,
     )]
     pub struct RelativeHumidity {
         pub r#header: std_msgs::Header,
@@ -1689,6 +2540,12 @@ pub mod sensor_msgs {
         const MD5SUM: &'static str = "71cfefa31dcc94f47083b1e89e6fa5c9";
         const DEFINITION : & 'static str = "# Single reading from a relative humidity sensor.\n# Defines the ratio of partial pressure of water vapor to the saturated vapor\n# pressure at a temperature.\n\nstd_msgs/Header header # timestamp of the measurement\n                             # frame_id is the location of the humidity sensor\n\nfloat64 relative_humidity    # Expression of the relative humidity\n                             # from 0.0 to 1.0.\n                             # 0.0 is no partial pressure of water vapor\n                             # 1.0 represents partial pressure of saturation\n\nfloat64 variance             # 0 is interpreted as variance unknown" ;
     }
+    impl RelativeHumidity {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            self.r#header.serialized_size_upper_bound() + 8usize + 8usize
+        }
+    }
     #[allow(non_snake_case)]
     #[derive(
         :: serde :: Deserialize,
@@ -1697,6 +2554,7 @@ pub mod sensor_msgs {
         Debug,
         Clone,
         PartialEq,
+        PartialOrd,
     )]
     pub struct Temperature {
         pub r#header: std_msgs::Header,
@@ -1708,6 +2566,12 @@ pub mod sensor_msgs {
         const MD5SUM: &'static str = "c6df0674fcfebff84a15927a80ebb14b";
         const DEFINITION : & 'static str = "# Single temperature reading.\n\nstd_msgs/Header header # timestamp is the time the temperature was measured\n                             # frame_id is the location of the temperature reading\n\nfloat64 temperature          # Measurement of the Temperature in Degrees Celsius.\n\nfloat64 variance             # 0 is interpreted as variance unknown." ;
     }
+    impl Temperature {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            self.r#header.serialized_size_upper_bound() + 8usize + 8usize
+        }
+    }
     #[allow(non_snake_case)]
     #[derive(
         :: serde :: Deserialize,
@@ -1716,6 +2580,9 @@ pub mod sensor_msgs {
         Debug,
         Clone,
         PartialEq,
+        Eq,
+        PartialOrd,
+        Ord,
     )]
     pub struct TimeReference {
         pub r#header: std_msgs::Header,
@@ -1727,6 +2594,12 @@ pub mod sensor_msgs {
         const MD5SUM: &'static str = "7cb7ae5aa838323e9028637e304e0ad7";
         const DEFINITION : & 'static str = "# Measurement from an external time source not actively synchronized with the system clock.\n\nstd_msgs/Header header      # stamp is system time for which measurement was valid\n                                  # frame_id is not used\n\nbuiltin_interfaces/Time time_ref  # corresponding time from this external source\nstring source                     # (optional) name of time source" ;
     }
+    impl TimeReference {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            self.r#header.serialized_size_upper_bound() + 8usize + 4usize + self.r#source.len()
+        }
+    }
     #[allow(non_snake_case)]
     #[derive(
         :: serde :: Deserialize,
@@ -1735,6 +2608,7 @@ pub mod sensor_msgs {
         Debug,
         Clone,
         PartialEq,
+        PartialOrd,
     )]
     pub struct SetCameraInfoRequest {
         pub r#camera_info: self::CameraInfo,
@@ -1744,6 +2618,12 @@ pub mod sensor_msgs {
         const MD5SUM: &'static str = "251c96e357751cc7c699c496178141d5";
         const DEFINITION : & 'static str = "# This service requests that a camera stores the given CameraInfo as that\n# camera's calibration information.\n#\n# The width and height in the camera_info field should match what the\n# camera is currently outputting on its camera_info topic, and the camera\n# will assume that the region of the imager that is being referred to is\n# the region that the camera is currently capturing.\n\nsensor_msgs/CameraInfo camera_info # The camera_info to store" ;
     }
+    impl SetCameraInfoRequest {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            self.r#camera_info.serialized_size_upper_bound()
+        }
+    }
     #[allow(non_snake_case)]
     #[derive(
         :: serde :: Deserialize,
@@ -1752,6 +2632,9 @@ pub mod sensor_msgs {
         Debug,
         Clone,
         PartialEq,
+        Eq,
+        PartialOrd,
+        Ord,
     )]
     pub struct SetCameraInfoResponse {
         pub r#success: bool,
@@ -1762,6 +2645,12 @@ pub mod sensor_msgs {
         const MD5SUM: &'static str = "2ec6f3eff0161f4257b808b12bc830c2";
         const DEFINITION : & 'static str = "bool success                             # True if the call succeeded\nstring status_message                    # Used to give details about success" ;
     }
+    impl SetCameraInfoResponse {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            1usize + 4usize + self.r#status_message.len()
+        }
+    }
     pub struct SetCameraInfo {}
     impl ::roslibrust_codegen::RosServiceType for SetCameraInfo {
         const ROS_SERVICE_NAME: &'static str = "sensor_msgs/SetCameraInfo";
@@ -1770,7 +2659,7 @@ pub mod sensor_msgs {
         type Response = SetCameraInfoResponse;
     }
 }
-#[allow(unused_imports)]
+#[allow(unused_imports, deprecated)]
 pub mod shape_msgs {
     use super::actionlib_msgs;
     use super::diagnostic_msgs;
@@ -1791,6 +2680,7 @@ pub mod shape_msgs {
         Debug,
         Clone,
         PartialEq,
+        PartialOrd,
     )]
     pub struct Mesh {
         pub r#triangles: ::std::vec::Vec<self::MeshTriangle>,
@@ -1801,6 +2691,23 @@ pub mod shape_msgs {
         const MD5SUM: &'static str = "1ffdae9486cd3316a121c578b47a85cc";
         const DEFINITION : & 'static str = "# Definition of a mesh.\n\n# List of triangles; the index values refer to positions in vertices[].\nMeshTriangle[] triangles\n\n# The actual vertices that make up the mesh.\ngeometry_msgs/Point[] vertices" ;
     }
+    impl Mesh {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            4usize
+                + self
+                    .r#triangles
+                    .iter()
+                    .map(|element| element.serialized_size_upper_bound())
+                    .sum::<usize>()
+                + 4usize
+                + self
+                    .r#vertices
+                    .iter()
+                    .map(|element| element.serialized_size_upper_bound())
+                    .sum::<usize>()
+        }
+    }
     #[allow(non_snake_case)]
     #[derive(
         :: serde :: Deserialize,
@@ -1809,6 +2716,9 @@ pub mod shape_msgs {
         Debug,
         Clone,
         PartialEq,
+        Eq,
+        PartialOrd,
+        Ord,
     )]
     pub struct MeshTriangle {
         pub r#vertex_indices: [u32; 3],
@@ -1819,6 +2729,12 @@ pub mod shape_msgs {
         const DEFINITION: &'static str =
             "# Definition of a triangle's vertices.\n\nuint32[3] vertex_indices";
     }
+    impl MeshTriangle {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            12usize
+        }
+    }
     #[allow(non_snake_case)]
     #[derive(
         :: serde :: Deserialize,
@@ -1827,6 +2743,7 @@ pub mod shape_msgs {
         Debug,
         Clone,
         PartialEq,
+        PartialOrd,
     )]
     pub struct Plane {
         pub r#coef: [f64; 4],
@@ -1836,6 +2753,12 @@ pub mod shape_msgs {
         const MD5SUM: &'static str = "2c1b92ed8f31492f8e73f6a4a44ca796";
         const DEFINITION : & 'static str = "# Representation of a plane, using the plane equation ax + by + cz + d = 0.\n#\n# a := coef[0]\n# b := coef[1]\n# c := coef[2]\n# d := coef[3]\nfloat64[4] coef" ;
     }
+    impl Plane {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            32usize
+        }
+    }
     #[allow(non_snake_case)]
     #[derive(
         :: serde :: Deserialize,
@@ -1844,35 +2767,57 @@ pub mod shape_msgs {
         Debug,
         Clone,
         PartialEq,
+        PartialOrd,
     )]
     pub struct SolidPrimitive {
         pub r#type: u8,
-        pub r#dimensions: [f64; 0],
+        pub r#dimensions: ::std::vec::Vec<f64>,
         pub r#polygon: geometry_msgs::Polygon,
     }
     impl ::roslibrust_codegen::RosMessageType for SolidPrimitive {
         const ROS_TYPE_NAME: &'static str = "shape_msgs/SolidPrimitive";
-        const MD5SUM: &'static str = "0cdf91a0a45ccd7bc1e0deb784cb2958";
+        const MD5SUM: &'static str = "8c7314fcbb621a6a7a6af3200d726861";
         const DEFINITION : & 'static str = "# Defines box, sphere, cylinder, cone and prism.\n# All shapes are defined to have their bounding boxes centered around 0,0,0.\n\nuint8 BOX=1\nuint8 SPHERE=2\nuint8 CYLINDER=3\nuint8 CONE=4\nuint8 PRISM=5\n\n# The type of the shape\nuint8 type\n\n# The dimensions of the shape\nfloat64[<=3] dimensions  # At no point will dimensions have a length > 3.\n\n# The meaning of the shape dimensions: each constant defines the index in the 'dimensions' array.\n\n# For type BOX, the X, Y, and Z dimensions are the length of the corresponding sides of the box.\nuint8 BOX_X=0\nuint8 BOX_Y=1\nuint8 BOX_Z=2\n\n# For the SPHERE type, only one component is used, and it gives the radius of the sphere.\nuint8 SPHERE_RADIUS=0\n\n# For the CYLINDER and CONE types, the center line is oriented along the Z axis.\n# Therefore the CYLINDER_HEIGHT (CONE_HEIGHT) component of dimensions gives the\n# height of the cylinder (cone).\n# The CYLINDER_RADIUS (CONE_RADIUS) component of dimensions gives the radius of\n# the base of the cylinder (cone).\n# Cone and cylinder primitives are defined to be circular. The tip of the cone\n# is pointing up, along +Z axis.\n\nuint8 CYLINDER_HEIGHT=0\nuint8 CYLINDER_RADIUS=1\n\nuint8 CONE_HEIGHT=0\nuint8 CONE_RADIUS=1\n\n# For the type PRISM, the center line is oriented along Z axis.\n# The PRISM_HEIGHT component of dimensions gives the\n# height of the prism.\n# The polygon defines the Z axis centered base of the prism.\n# The prism is constructed by extruding the base in +Z and -Z\n# directions by half of the PRISM_HEIGHT\n# Only x and y fields of the points are used in the polygon.\n# Points of the polygon are ordered counter-clockwise.\n\nuint8 PRISM_HEIGHT=0\ngeometry_msgs/Polygon polygon" ;
     }
     impl SolidPrimitive {
+        #[doc = " = 1"]
         pub const r#BOX: u8 = 1u8;
+        #[doc = " = 2"]
         pub const r#SPHERE: u8 = 2u8;
+        #[doc = " = 3"]
         pub const r#CYLINDER: u8 = 3u8;
+        #[doc = " = 4"]
         pub const r#CONE: u8 = 4u8;
+        #[doc = " = 5"]
         pub const r#PRISM: u8 = 5u8;
+        #[doc = " = 0"]
         pub const r#BOX_X: u8 = 0u8;
+        #[doc = " = 1"]
         pub const r#BOX_Y: u8 = 1u8;
+        #[doc = " = 2"]
         pub const r#BOX_Z: u8 = 2u8;
+        #[doc = " = 0"]
         pub const r#SPHERE_RADIUS: u8 = 0u8;
+        #[doc = " = 0"]
         pub const r#CYLINDER_HEIGHT: u8 = 0u8;
+        #[doc = " = 1"]
         pub const r#CYLINDER_RADIUS: u8 = 1u8;
+        #[doc = " = 0"]
         pub const r#CONE_HEIGHT: u8 = 0u8;
+        #[doc = " = 1"]
         pub const r#CONE_RADIUS: u8 = 1u8;
+        #[doc = " = 0"]
         pub const r#PRISM_HEIGHT: u8 = 0u8;
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            1usize
+                + 4usize
+                + self.r#dimensions.len().max(3usize) * 8usize
+                + self.r#polygon.serialized_size_upper_bound()
+        }
     }
 }
-#[allow(unused_imports)]
+#[allow(unused_imports, deprecated)]
 pub mod std_msgs {
     use super::actionlib_msgs;
     use super::diagnostic_msgs;
@@ -1893,6 +2838,9 @@ pub mod std_msgs {
         Debug,
         Clone,
         PartialEq,
+        Eq,
+        PartialOrd,
+        Ord,
     )]
     pub struct Bool {
         pub r#data: bool,
@@ -1902,6 +2850,12 @@ pub mod std_msgs {
         const MD5SUM: &'static str = "8b94c1b53db61fb6aed406028ad6332a";
         const DEFINITION : & 'static str = "# This was originally provided as an example message.\n# It is deprecated as of Foxy\n# It is recommended to create your own semantically meaningful message.\n# However if you would like to continue using this please use the equivalent in example_msgs.\n\nbool data" ;
     }
+    impl Bool {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            1usize
+        }
+    }
     #[allow(non_snake_case)]
     #[derive(
         :: serde :: Deserialize,
@@ -1910,6 +2864,9 @@ pub mod std_msgs {
         Debug,
         Clone,
         PartialEq,
+        Eq,
+        PartialOrd,
+        Ord,
     )]
     pub struct Byte {
         pub r#data: u8,
@@ -1919,6 +2876,12 @@ pub mod std_msgs {
         const MD5SUM: &'static str = "ad736a2e8818154c487bb80fe42ce43b";
         const DEFINITION : & 'static str = "# This was originally provided as an example message.\n# It is deprecated as of Foxy\n# It is recommended to create your own semantically meaningful message.\n# However if you would like to continue using this please use the equivalent in example_msgs.\n\nbyte data" ;
     }
+    impl Byte {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            1usize
+        }
+    }
     #[allow(non_snake_case)]
     #[derive(
         :: serde :: Deserialize,
@@ -1927,6 +2890,9 @@ pub mod std_msgs {
         Debug,
         Clone,
         PartialEq,
+        Eq,
+        PartialOrd,
+        Ord,
     )]
     pub struct ByteMultiArray {
         pub r#layout: self::MultiArrayLayout,
@@ -1937,6 +2903,12 @@ pub mod std_msgs {
         const MD5SUM: &'static str = "70ea476cbcfd65ac2f68f3cda1e891fe";
         const DEFINITION : & 'static str = "# This was originally provided as an example message.\n# It is deprecated as of Foxy\n# It is recommended to create your own semantically meaningful message.\n# However if you would like to continue using this please use the equivalent in example_msgs.\n\n# Please look at the MultiArrayLayout message definition for\n# documentation on all multiarrays.\n\nMultiArrayLayout  layout        # specification of data layout\nbyte[]            data          # array of data" ;
     }
+    impl ByteMultiArray {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            self.r#layout.serialized_size_upper_bound() + 4usize + self.r#data.len()
+        }
+    }
     #[allow(non_snake_case)]
     #[derive(
         :: serde :: Deserialize,
@@ -1945,6 +2917,9 @@ pub mod std_msgs {
         Debug,
         Clone,
         PartialEq,
+        Eq,
+        PartialOrd,
+        Ord,
     )]
     pub struct Char {
         pub r#data: u8,
@@ -1954,6 +2929,12 @@ pub mod std_msgs {
         const MD5SUM: &'static str = "1bf77f25acecdedba0e224b162199717";
         const DEFINITION : & 'static str = "# This was originally provided as an example message.\n# It is deprecated as of Foxy\n# It is recommended to create your own semantically meaningful message.\n# However if you would like to continue using this please use the equivalent in example_msgs.\n\nchar data" ;
     }
+    impl Char {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            1usize
+        }
+    }
     #[allow(non_snake_case)]
     #[derive(
         :: serde :: Deserialize,
@@ -1962,6 +2943,7 @@ pub mod std_msgs {
         Debug,
         Clone,
         PartialEq,
+        PartialOrd,
     )]
     pub struct ColorRGBA {
         pub r#r: f32,
@@ -1974,6 +2956,12 @@ pub mod std_msgs {
         const MD5SUM: &'static str = "a29a96539573343b1310c73607334b00";
         const DEFINITION: &'static str = "float32 r\nfloat32 g\nfloat32 b\nfloat32 a";
     }
+    impl ColorRGBA {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            16usize
+        }
+    }
     #[allow(non_snake_case)]
     #[derive(
         :: serde :: Deserialize,
@@ -1982,6 +2970,9 @@ pub mod std_msgs {
         Debug,
         Clone,
         PartialEq,
+        Eq,
+        PartialOrd,
+        Ord,
     )]
     pub struct Empty {}
     impl ::roslibrust_codegen::RosMessageType for Empty {
@@ -1989,6 +2980,12 @@ pub mod std_msgs {
         const MD5SUM: &'static str = "d41d8cd98f00b204e9800998ecf8427e";
         const DEFINITION: &'static str = "";
     }
+    impl Empty {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            0usize
+        }
+    }
     #[allow(non_snake_case)]
     #[derive(
         :: serde :: Deserialize,
@@ -1997,6 +2994,7 @@ pub mod std_msgs {
         Debug,
         Clone,
         PartialEq,
+        PartialOrd,
     )]
     pub struct Float32 {
         pub r#data: f32,
@@ -2006,6 +3004,12 @@ pub mod std_msgs {
         const MD5SUM: &'static str = "73fcbf46b49191e672908e50842a83d4";
         const DEFINITION : & 'static str = "# This was originally provided as an example message.\n# It is deprecated as of Foxy\n# It is recommended to create your own semantically meaningful message.\n# However if you would like to continue using this please use the equivalent in example_msgs.\n\nfloat32 data" ;
     }
+    impl Float32 {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            4usize
+        }
+    }
     #[allow(non_snake_case)]
     #[derive(
         :: serde :: Deserialize,
@@ -2014,6 +3018,7 @@ pub mod std_msgs {
         Debug,
         Clone,
         PartialEq,
+        PartialOrd,
     )]
     pub struct Float32MultiArray {
         pub r#layout: self::MultiArrayLayout,
@@ -2024,6 +3029,12 @@ pub mod std_msgs {
         const MD5SUM: &'static str = "6a40e0ffa6a17a503ac3f8616991b1f6";
         const DEFINITION : & 'static str = "# This was originally provided as an example message.\n# It is deprecated as of Foxy\n# It is recommended to create your own semantically meaningful message.\n# However if you would like to continue using this please use the equivalent in example_msgs.\n\n# Please look at the MultiArrayLayout message definition for\n# documentation on all multiarrays.\n\nMultiArrayLayout  layout        # specification of data layout\nfloat32[]         data          # array of data" ;
     }
+    impl Float32MultiArray {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            self.r#layout.serialized_size_upper_bound() + 4usize + self.r#data.len() * 4usize
+        }
+    }
     #[allow(non_snake_case)]
     #[derive(
         :: serde :: Deserialize,
@@ -2032,6 +3043,7 @@ pub mod std_msgs {
         Debug,
         Clone,
         PartialEq,
+        PartialOrd,
     )]
     pub struct Float64 {
         pub r#data: f64,
@@ -2041,6 +3053,12 @@ pub mod std_msgs {
         const MD5SUM: &'static str = "fdb28210bfa9d7c91146260178d9a584";
         const DEFINITION : & 'static str = "# This was originally provided as an example message.\n# It is deprecated as of Foxy\n# It is recommended to create your own semantically meaningful message.\n# However if you would like to continue using this please use the equivalent in example_msgs.\n\nfloat64 data" ;
     }
+    impl Float64 {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            8usize
+        }
+    }
     #[allow(non_snake_case)]
     #[derive(
         :: serde :: Deserialize,
@@ -2049,6 +3067,7 @@ pub mod std_msgs {
         Debug,
         Clone,
         PartialEq,
+        PartialOrd,
     )]
     pub struct Float64MultiArray {
         pub r#layout: self::MultiArrayLayout,
@@ -2059,6 +3078,12 @@ pub mod std_msgs {
         const MD5SUM: &'static str = "4b7d974086d4060e7db4613a7e6c3ba4";
         const DEFINITION : & 'static str = "# This was originally provided as an example message.\n# It is deprecated as of Foxy\n# It is recommended to create your own semantically meaningful message.\n# However if you would like to continue using this please use the equivalent in example_msgs.\n\n# Please look at the MultiArrayLayout message definition for\n# documentation on all multiarrays.\n\nMultiArrayLayout  layout        # specification of data layout\nfloat64[]         data          # array of data" ;
     }
+    impl Float64MultiArray {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            self.r#layout.serialized_size_upper_bound() + 4usize + self.r#data.len() * 8usize
+        }
+    }
     #[allow(non_snake_case)]
     #[derive(
         :: serde :: Deserialize,
@@ -2067,6 +3092,9 @@ pub mod std_msgs {
         Debug,
         Clone,
         PartialEq,
+        Eq,
+        PartialOrd,
+        Ord,
     )]
     pub struct Header {
         pub r#stamp: ::roslibrust_codegen::integral_types::Time,
@@ -2077,6 +3105,12 @@ pub mod std_msgs {
         const MD5SUM: &'static str = "5ed6b5dd1ef879ffb9c2ac51bab61a63";
         const DEFINITION : & 'static str = "# Standard metadata for higher-level stamped data types.\n# This is generally used to communicate timestamped data\n# in a particular coordinate frame.\n\n# Two-integer timestamp that is expressed as seconds and nanoseconds.\nbuiltin_interfaces/Time stamp\n\n# Transform frame with which this data is associated.\nstring frame_id" ;
     }
+    impl Header {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            8usize + 4usize + self.r#frame_id.len()
+        }
+    }
     #[allow(non_snake_case)]
     #[derive(
         :: serde :: Deserialize,
@@ -2085,6 +3119,9 @@ pub mod std_msgs {
         Debug,
         Clone,
         PartialEq,
+        Eq,
+        PartialOrd,
+        Ord,
     )]
     pub struct Int16 {
         pub r#data: i16,
@@ -2094,6 +3131,12 @@ pub mod std_msgs {
         const MD5SUM: &'static str = "8524586e34fbd7cb1c08c5f5f1ca0e57";
         const DEFINITION : & 'static str = "# This was originally provided as an example message.\n# It is deprecated as of Foxy\n# It is recommended to create your own semantically meaningful message.\n# However if you would like to continue using this please use the equivalent in example_msgs.\n\nint16 data" ;
     }
+    impl Int16 {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            2usize
+        }
+    }
     #[allow(non_snake_case)]
     #[derive(
         :: serde :: Deserialize,
@@ -2102,6 +3145,9 @@ pub mod std_msgs {
         Debug,
         Clone,
         PartialEq,
+        Eq,
+        PartialOrd,
+        Ord,
     )]
     pub struct Int16MultiArray {
         pub r#layout: self::MultiArrayLayout,
@@ -2112,6 +3158,12 @@ pub mod std_msgs {
         const MD5SUM: &'static str = "d9338d7f523fcb692fae9d0a0e9f067c";
         const DEFINITION : & 'static str = "# This was originally provided as an example message.\n# It is deprecated as of Foxy\n# It is recommended to create your own semantically meaningful message.\n# However if you would like to continue using this please use the equivalent in example_msgs.\n\n# Please look at the MultiArrayLayout message definition for\n# documentation on all multiarrays.\n\nMultiArrayLayout  layout        # specification of data layout\nint16[]           data          # array of data" ;
     }
+    impl Int16MultiArray {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            self.r#layout.serialized_size_upper_bound() + 4usize + self.r#data.len() * 2usize
+        }
+    }
     #[allow(non_snake_case)]
     #[derive(
         :: serde :: Deserialize,
@@ -2120,6 +3172,9 @@ pub mod std_msgs {
         Debug,
         Clone,
         PartialEq,
+        Eq,
+        PartialOrd,
+        Ord,
     )]
     pub struct Int32 {
         pub r#data: i32,
@@ -2129,6 +3184,12 @@ pub mod std_msgs {
         const MD5SUM: &'static str = "da5909fbe378aeaf85e547e830cc1bb7";
         const DEFINITION : & 'static str = "# This was originally provided as an example message.\n# It is deprecated as of Foxy\n# It is recommended to create your own semantically meaningful message.\n# However if you would like to continue using this please use the equivalent in example_msgs.\n\nint32 data" ;
     }
+    impl Int32 {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            4usize
+        }
+    }
     #[allow(non_snake_case)]
     #[derive(
         :: serde :: Deserialize,
@@ -2137,6 +3198,9 @@ pub mod std_msgs {
         Debug,
         Clone,
         PartialEq,
+        Eq,
+        PartialOrd,
+        Ord,
     )]
     pub struct Int32MultiArray {
         pub r#layout: self::MultiArrayLayout,
@@ -2147,6 +3211,12 @@ pub mod std_msgs {
         const MD5SUM: &'static str = "1d99f79f8b325b44fee908053e9c945b";
         const DEFINITION : & 'static str = "# This was originally provided as an example message.\n# It is deprecated as of Foxy\n# It is recommended to create your own semantically meaningful message.\n# However if you would like to continue using this please use the equivalent in example_msgs.\n\n# Please look at the MultiArrayLayout message definition for\n# documentation on all multiarrays.\n\nMultiArrayLayout  layout        # specification of data layout\nint32[]           data          # array of data" ;
     }
+    impl Int32MultiArray {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            self.r#layout.serialized_size_upper_bound() + 4usize + self.r#data.len() * 4usize
+        }
+    }
     #[allow(non_snake_case)]
     #[derive(
         :: serde :: Deserialize,
@@ -2155,6 +3225,9 @@ pub mod std_msgs {
         Debug,
         Clone,
         PartialEq,
+        Eq,
+        PartialOrd,
+        Ord,
     )]
     pub struct Int64 {
         pub r#data: i64,
@@ -2164,6 +3237,12 @@ pub mod std_msgs {
         const MD5SUM: &'static str = "34add168574510e6e17f5d23ecc077ef";
         const DEFINITION : & 'static str = "# This was originally provided as an example message.\n# It is deprecated as of Foxy\n# It is recommended to create your own semantically meaningful message.\n# However if you would like to continue using this please use the equivalent in example_msgs.\n\nint64 data" ;
     }
+    impl Int64 {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            8usize
+        }
+    }
     #[allow(non_snake_case)]
     #[derive(
         :: serde :: Deserialize,
@@ -2172,6 +3251,9 @@ pub mod std_msgs {
         Debug,
         Clone,
         PartialEq,
+        Eq,
+        PartialOrd,
+        Ord,
     )]
     pub struct Int64MultiArray {
         pub r#layout: self::MultiArrayLayout,
@@ -2182,6 +3264,12 @@ pub mod std_msgs {
         const MD5SUM: &'static str = "54865aa6c65be0448113a2afc6a49270";
         const DEFINITION : & 'static str = "# This was originally provided as an example message.\n# It is deprecated as of Foxy\n# It is recommended to create your own semantically meaningful message.\n# However if you would like to continue using this please use the equivalent in example_msgs.\n\n# Please look at the MultiArrayLayout message definition for\n# documentation on all multiarrays.\n\nMultiArrayLayout  layout        # specification of data layout\nint64[]           data          # array of data" ;
     }
+    impl Int64MultiArray {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            self.r#layout.serialized_size_upper_bound() + 4usize + self.r#data.len() * 8usize
+        }
+    }
     #[allow(non_snake_case)]
     #[derive(
         :: serde :: Deserialize,
@@ -2190,6 +3278,9 @@ pub mod std_msgs {
         Debug,
         Clone,
         PartialEq,
+        Eq,
+        PartialOrd,
+        Ord,
     )]
     pub struct Int8 {
         pub r#data: i8,
@@ -2199,6 +3290,12 @@ pub mod std_msgs {
         const MD5SUM: &'static str = "27ffa0c9c4b8fb8492252bcad9e5c57b";
         const DEFINITION : & 'static str = "# This was originally provided as an example message.\n# It is deprecated as of Foxy\n# It is recommended to create your own semantically meaningful message.\n# However if you would like to continue using this please use the equivalent in example_msgs.\n\nint8 data" ;
     }
+    impl Int8 {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            1usize
+        }
+    }
     #[allow(non_snake_case)]
     #[derive(
         :: serde :: Deserialize,
@@ -2207,6 +3304,9 @@ pub mod std_msgs {
         Debug,
         Clone,
         PartialEq,
+        Eq,
+        PartialOrd,
+        Ord,
     )]
     pub struct Int8MultiArray {
         pub r#layout: self::MultiArrayLayout,
@@ -2217,6 +3317,12 @@ pub mod std_msgs {
         const MD5SUM: &'static str = "d7c1af35a1b4781bbe79e03dd94b7c13";
         const DEFINITION : & 'static str = "# This was originally provided as an example message.\n# It is deprecated as of Foxy\n# It is recommended to create your own semantically meaningful message.\n# However if you would like to continue using this please use the equivalent in example_msgs.\n\n# Please look at the MultiArrayLayout message definition for\n# documentation on all multiarrays.\n\nMultiArrayLayout  layout        # specification of data layout\nint8[]            data          # array of data" ;
     }
+    impl Int8MultiArray {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            self.r#layout.serialized_size_upper_bound() + 4usize + self.r#data.len()
+        }
+    }
     #[allow(non_snake_case)]
     #[derive(
         :: serde :: Deserialize,
@@ -2225,6 +3331,9 @@ pub mod std_msgs {
         Debug,
         Clone,
         PartialEq,
+        Eq,
+        PartialOrd,
+        Ord,
     )]
     pub struct MultiArrayDimension {
         pub r#label: ::std::string::String,
@@ -2236,6 +3345,12 @@ pub mod std_msgs {
         const MD5SUM: &'static str = "4cd0c83a8683deae40ecdac60e53bfa8";
         const DEFINITION : & 'static str = "# This was originally provided as an example message.\n# It is deprecated as of Foxy\n# It is recommended to create your own semantically meaningful message.\n# However if you would like to continue using this please use the equivalent in example_msgs.\n\nstring label   # label of given dimension\nuint32 size    # size of given dimension (in type units)\nuint32 stride  # stride of given dimension" ;
     }
+    impl MultiArrayDimension {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            4usize + self.r#label.len() + 4usize + 4usize
+        }
+    }
     #[allow(non_snake_case)]
     #[derive(
         :: serde :: Deserialize,
@@ -2244,6 +3359,9 @@ pub mod std_msgs {
         Debug,
         Clone,
         PartialEq,
+        Eq,
+        PartialOrd,
+        Ord,
     )]
     pub struct MultiArrayLayout {
         pub r#dim: ::std::vec::Vec<self::MultiArrayDimension>,
@@ -2254,6 +3372,18 @@ pub mod std_msgs {
         const MD5SUM: &'static str = "0fed2a11c13e11c5571b4e2a995a91a3";
         const DEFINITION : & 'static str = "# This was originally provided as an example message.\n# It is deprecated as of Foxy\n# It is recommended to create your own semantically meaningful message.\n# However if you would like to continue using this please use the equivalent in example_msgs.\n\n# The multiarray declares a generic multi-dimensional array of a\n# particular data type.  Dimensions are ordered from outer most\n# to inner most.\n#\n# Accessors should ALWAYS be written in terms of dimension stride\n# and specified outer-most dimension first.\n#\n# multiarray(i,j,k) = data[data_offset + dim_stride[1]*i + dim_stride[2]*j + k]\n#\n# A standard, 3-channel 640x480 image with interleaved color channels\n# would be specified as:\n#\n# dim[0].label  = \"height\"\n# dim[0].size   = 480\n# dim[0].stride = 3*640*480 = 921600  (note dim[0] stride is just size of image)\n# dim[1].label  = \"width\"\n# dim[1].size   = 640\n# dim[1].stride = 3*640 = 1920\n# dim[2].label  = \"channel\"\n# dim[2].size   = 3\n# dim[2].stride = 3\n#\n# multiarray(i,j,k) refers to the ith row, jth column, and kth channel.\n\nMultiArrayDimension[] dim # Array of dimension properties\nuint32 data_offset        # padding bytes at front of data" ;
     }
+    impl MultiArrayLayout {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            4usize
+                + self
+                    .r#dim
+                    .iter()
+                    .map(|element| element.serialized_size_upper_bound())
+                    .sum::<usize>()
+                + 4usize
+        }
+    }
     #[allow(non_snake_case)]
     #[derive(
         :: serde :: Deserialize,
@@ -2262,6 +3392,9 @@ pub mod std_msgs {
         Debug,
         Clone,
         PartialEq,
+        Eq,
+        PartialOrd,
+        Ord,
     )]
     pub struct String {
         pub r#data: ::std::string::String,
@@ -2271,6 +3404,12 @@ pub mod std_msgs {
         const MD5SUM: &'static str = "992ce8a1687cec8c8bd883ec73ca41d1";
         const DEFINITION : & 'static str = "# This was originally provided as an example message.\n# It is deprecated as of Foxy\n# It is recommended to create your own semantically meaningful message.\n# However if you would like to continue using this please use the equivalent in example_msgs.\n\nstring data" ;
     }
+    impl String {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            4usize + self.r#data.len()
+        }
+    }
     #[allow(non_snake_case)]
     #[derive(
         :: serde :: Deserialize,
@@ -2279,6 +3418,9 @@ pub mod std_msgs {
         Debug,
         Clone,
         PartialEq,
+        Eq,
+        PartialOrd,
+        Ord,
     )]
     pub struct UInt16 {
         pub r#data: u16,
@@ -2288,6 +3430,12 @@ pub mod std_msgs {
         const MD5SUM: &'static str = "1df79edf208b629fe6b81923a544552d";
         const DEFINITION : & 'static str = "# This was originally provided as an example message.\n# It is deprecated as of Foxy\n# It is recommended to create your own semantically meaningful message.\n# However if you would like to continue using this please use the equivalent in example_msgs.\n\nuint16 data" ;
     }
+    impl UInt16 {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            2usize
+        }
+    }
     #[allow(non_snake_case)]
     #[derive(
         :: serde :: Deserialize,
@@ -2296,6 +3444,9 @@ pub mod std_msgs {
         Debug,
         Clone,
         PartialEq,
+        Eq,
+        PartialOrd,
+        Ord,
     )]
     pub struct UInt16MultiArray {
         pub r#layout: self::MultiArrayLayout,
@@ -2306,6 +3457,12 @@ pub mod std_msgs {
         const MD5SUM: &'static str = "52f264f1c973c4b73790d384c6cb4484";
         const DEFINITION : & 'static str = "# This was originally provided as an example message.\n# It is deprecated as of Foxy\n# It is recommended to create your own semantically meaningful message.\n# However if you would like to continue using this please use the equivalent in example_msgs.\n\n# Please look at the MultiArrayLayout message definition for\n# documentation on all multiarrays.\n\nMultiArrayLayout  layout        # specification of data layout\nuint16[]            data        # array of data" ;
     }
+    impl UInt16MultiArray {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            self.r#layout.serialized_size_upper_bound() + 4usize + self.r#data.len() * 2usize
+        }
+    }
     #[allow(non_snake_case)]
     #[derive(
         :: serde :: Deserialize,
@@ -2314,6 +3471,9 @@ pub mod std_msgs {
         Debug,
         Clone,
         PartialEq,
+        Eq,
+        PartialOrd,
+        Ord,
     )]
     pub struct UInt32 {
         pub r#data: u32,
@@ -2323,6 +3483,12 @@ pub mod std_msgs {
         const MD5SUM: &'static str = "304a39449588c7f8ce2df6e8001c5fce";
         const DEFINITION : & 'static str = "# This was originally provided as an example message.\n# It is deprecated as of Foxy\n# It is recommended to create your own semantically meaningful message.\n# However if you would like to continue using this please use the equivalent in example_msgs.\n\nuint32 data" ;
     }
+    impl UInt32 {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            4usize
+        }
+    }
     #[allow(non_snake_case)]
     #[derive(
         :: serde :: Deserialize,
@@ -2331,6 +3497,9 @@ pub mod std_msgs {
         Debug,
         Clone,
         PartialEq,
+        Eq,
+        PartialOrd,
+        Ord,
     )]
     pub struct UInt32MultiArray {
         pub r#layout: self::MultiArrayLayout,
@@ -2341,6 +3510,12 @@ pub mod std_msgs {
         const MD5SUM: &'static str = "4d6a180abc9be191b96a7eda6c8a233d";
         const DEFINITION : & 'static str = "# This was originally provided as an example message.\n# It is deprecated as of Foxy\n# It is recommended to create your own semantically meaningful message.\n# However if you would like to continue using this please use the equivalent in example_msgs.\n\n# Please look at the MultiArrayLayout message definition for\n# documentation on all multiarrays.\n\nMultiArrayLayout  layout        # specification of data layout\nuint32[]          data          # array of data" ;
     }
+    impl UInt32MultiArray {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            self.r#layout.serialized_size_upper_bound() + 4usize + self.r#data.len() * 4usize
+        }
+    }
     #[allow(non_snake_case)]
     #[derive(
         :: serde :: Deserialize,
@@ -2349,6 +3524,9 @@ pub mod std_msgs {
         Debug,
         Clone,
         PartialEq,
+        Eq,
+        PartialOrd,
+        Ord,
     )]
     pub struct UInt64 {
         pub r#data: u64,
@@ -2358,6 +3536,12 @@ pub mod std_msgs {
         const MD5SUM: &'static str = "1b2a79973e8bf53d7b53acb71299cb57";
         const DEFINITION : & 'static str = "# This was originally provided as an example message.\n# It is deprecated as of Foxy\n# It is recommended to create your own semantically meaningful message.\n# However if you would like to continue using this please use the equivalent in example_msgs.\n\nuint64 data" ;
     }
+    impl UInt64 {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            8usize
+        }
+    }
     #[allow(non_snake_case)]
     #[derive(
         :: serde :: Deserialize,
@@ -2366,6 +3550,9 @@ pub mod std_msgs {
         Debug,
         Clone,
         PartialEq,
+        Eq,
+        PartialOrd,
+        Ord,
     )]
     pub struct UInt64MultiArray {
         pub r#layout: self::MultiArrayLayout,
@@ -2376,6 +3563,12 @@ pub mod std_msgs {
         const MD5SUM: &'static str = "6088f127afb1d6c72927aa1247e945af";
         const DEFINITION : & 'static str = "# This was originally provided as an example message.\n# It is deprecated as of Foxy\n# It is recommended to create your own semantically meaningful message.\n# However if you would like to continue using this please use the equivalent in example_msgs.\n\n# Please look at the MultiArrayLayout message definition for\n# documentation on all multiarrays.\n\nMultiArrayLayout  layout        # specification of data layout\nuint64[]          data          # array of data" ;
     }
+    impl UInt64MultiArray {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            self.r#layout.serialized_size_upper_bound() + 4usize + self.r#data.len() * 8usize
+        }
+    }
     #[allow(non_snake_case)]
     #[derive(
         :: serde :: Deserialize,
@@ -2384,6 +3577,9 @@ pub mod std_msgs {
         Debug,
         Clone,
         PartialEq,
+        Eq,
+        PartialOrd,
+        Ord,
     )]
     pub struct UInt8 {
         pub r#data: u8,
@@ -2393,6 +3589,12 @@ pub mod std_msgs {
         const MD5SUM: &'static str = "7c8164229e7d2c17eb95e9231617fdee";
         const DEFINITION : & 'static str = "# This was originally provided as an example message.\n# It is deprecated as of Foxy\n# It is recommended to create your own semantically meaningful message.\n# However if you would like to continue using this please use the equivalent in example_msgs.\n\nuint8 data" ;
     }
+    impl UInt8 {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            1usize
+        }
+    }
     #[allow(non_snake_case)]
     #[derive(
         :: serde :: Deserialize,
@@ -2401,6 +3603,9 @@ pub mod std_msgs {
         Debug,
         Clone,
         PartialEq,
+        Eq,
+        PartialOrd,
+        Ord,
     )]
     pub struct UInt8MultiArray {
         pub r#layout: self::MultiArrayLayout,
@@ -2411,8 +3616,14 @@ pub mod std_msgs {
         const MD5SUM: &'static str = "82373f1612381bb6ee473b5cd6f5d89c";
         const DEFINITION : & 'static str = "# This was originally provided as an example message.\n# It is deprecated as of Foxy\n# It is recommended to create your own semantically meaningful message.\n# However if you would like to continue using this please use the equivalent in example_msgs.\n\n# Please look at the MultiArrayLayout message definition for\n# documentation on all multiarrays.\n\nMultiArrayLayout  layout        # specification of data layout\nuint8[]           data          # array of data" ;
     }
+    impl UInt8MultiArray {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            self.r#layout.serialized_size_upper_bound() + 4usize + self.r#data.len()
+        }
+    }
 }
-#[allow(unused_imports)]
+#[allow(unused_imports, deprecated)]
 pub mod std_srvs {
     use super::actionlib_msgs;
     use super::diagnostic_msgs;
@@ -2433,6 +3644,9 @@ pub mod std_srvs {
         Debug,
         Clone,
         PartialEq,
+        Eq,
+        PartialOrd,
+        Ord,
     )]
     pub struct EmptyRequest {}
     impl ::roslibrust_codegen::RosMessageType for EmptyRequest {
@@ -2440,6 +3654,12 @@ pub mod std_srvs {
         const MD5SUM: &'static str = "d41d8cd98f00b204e9800998ecf8427e";
         const DEFINITION: &'static str = "";
     }
+    impl EmptyRequest {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            0usize
+        }
+    }
     #[allow(non_snake_case)]
     #[derive(
         :: serde :: Deserialize,
@@ -2448,6 +3668,9 @@ pub mod std_srvs {
         Debug,
         Clone,
         PartialEq,
+        Eq,
+        PartialOrd,
+        Ord,
     )]
     pub struct EmptyResponse {}
     impl ::roslibrust_codegen::RosMessageType for EmptyResponse {
@@ -2455,6 +3678,12 @@ pub mod std_srvs {
         const MD5SUM: &'static str = "d41d8cd98f00b204e9800998ecf8427e";
         const DEFINITION: &'static str = "";
     }
+    impl EmptyResponse {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            0usize
+        }
+    }
     pub struct Empty {}
     impl ::roslibrust_codegen::RosServiceType for Empty {
         const ROS_SERVICE_NAME: &'static str = "std_srvs/Empty";
@@ -2470,6 +3699,9 @@ pub mod std_srvs {
         Debug,
         Clone,
         PartialEq,
+        Eq,
+        PartialOrd,
+        Ord,
     )]
     pub struct SetBoolRequest {
         pub r#data: bool,
@@ -2479,6 +3711,12 @@ pub mod std_srvs {
         const MD5SUM: &'static str = "8b94c1b53db61fb6aed406028ad6332a";
         const DEFINITION: &'static str = "bool data # e.g. for hardware enabling / disabling";
     }
+    impl SetBoolRequest {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            1usize
+        }
+    }
     #[allow(non_snake_case)]
     #[derive(
         :: serde :: Deserialize,
@@ -2487,6 +3725,9 @@ pub mod std_srvs {
         Debug,
         Clone,
         PartialEq,
+        Eq,
+        PartialOrd,
+        Ord,
     )]
     pub struct SetBoolResponse {
         pub r#success: bool,
@@ -2497,6 +3738,12 @@ pub mod std_srvs {
         const MD5SUM: &'static str = "937c9679a518e3a18d831e57125ea522";
         const DEFINITION : & 'static str = "bool success   # indicate successful run of triggered service\nstring message # informational, e.g. for error messages" ;
     }
+    impl SetBoolResponse {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            1usize + 4usize + self.r#message.len()
+        }
+    }
     pub struct SetBool {}
     impl ::roslibrust_codegen::RosServiceType for SetBool {
         const ROS_SERVICE_NAME: &'static str = "std_srvs/SetBool";
@@ -2512,6 +3759,9 @@ pub mod std_srvs {
         Debug,
         Clone,
         PartialEq,
+        Eq,
+        PartialOrd,
+        Ord,
     )]
     pub struct TriggerRequest {}
     impl ::roslibrust_codegen::RosMessageType for TriggerRequest {
@@ -2519,6 +3769,12 @@ pub mod std_srvs {
         const MD5SUM: &'static str = "d41d8cd98f00b204e9800998ecf8427e";
         const DEFINITION: &'static str = "";
     }
+    impl TriggerRequest {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            0usize
+        }
+    }
     #[allow(non_snake_case)]
     #[derive(
         :: serde :: Deserialize,
@@ -2527,6 +3783,9 @@ pub mod std_srvs {
         Debug,
         Clone,
         PartialEq,
+        Eq,
+        PartialOrd,
+        Ord,
     )]
     pub struct TriggerResponse {
         pub r#success: bool,
@@ -2537,6 +3796,12 @@ pub mod std_srvs {
         const MD5SUM: &'static str = "937c9679a518e3a18d831e57125ea522";
         const DEFINITION : & 'static str = "bool success   # indicate successful run of triggered service\nstring message # informational, e.g. for error messages" ;
     }
+    impl TriggerResponse {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            1usize + 4usize + self.r#message.len()
+        }
+    }
     pub struct Trigger {}
     impl ::roslibrust_codegen::RosServiceType for Trigger {
         const ROS_SERVICE_NAME: &'static str = "std_srvs/Trigger";
@@ -2545,7 +3810,7 @@ pub mod std_srvs {
         type Response = TriggerResponse;
     }
 }
-#[allow(unused_imports)]
+#[allow(unused_imports, deprecated)]
 pub mod stereo_msgs {
     use super::actionlib_msgs;
     use super::diagnostic_msgs;
@@ -2566,6 +3831,7 @@ pub mod stereo_msgs {
         Debug,
         Clone,
         PartialEq,
+        PartialOrd,
     )]
     pub struct DisparityImage {
         pub r#header: std_msgs::Header,
@@ -2582,8 +3848,21 @@ pub mod stereo_msgs {
         const MD5SUM: &'static str = "cb0de8feef04280238c7b77d74b2beca";
         const DEFINITION : & 'static str = "# Separate header for compatibility with current TimeSynchronizer.\n# Likely to be removed in a later release, use image.header instead.\nstd_msgs/Header header\n\n# Floating point disparity image. The disparities are pre-adjusted for any\n# x-offset between the principal points of the two cameras (in the case\n# that they are verged). That is: d = x_l - x_r - (cx_l - cx_r)\nsensor_msgs/Image image\n\n# Stereo geometry. For disparity d, the depth from the camera is Z = fT/d.\nfloat32 f # Focal length, pixels\nfloat32 t # Baseline, world units\n\n# Subwindow of (potentially) valid disparity values.\nsensor_msgs/RegionOfInterest valid_window\n\n# The range of disparities searched.\n# In the disparity image, any disparity less than min_disparity is invalid.\n# The disparity search range defines the horopter, or 3D volume that the\n# stereo algorithm can \"see\". Points with Z outside of:\n#     Z_min = fT / max_disparity\n#     Z_max = fT / min_disparity\n# could not be found.\nfloat32 min_disparity\nfloat32 max_disparity\n\n# Smallest allowed disparity increment. The smallest achievable depth range\n# resolution is delta_Z = (Z^2/fT)*delta_d.\nfloat32 delta_d" ;
     }
+    impl DisparityImage {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            self.r#header.serialized_size_upper_bound()
+                + self.r#image.serialized_size_upper_bound()
+                + 4usize
+                + 4usize
+                + self.r#valid_window.serialized_size_upper_bound()
+                + 4usize
+                + 4usize
+                + 4usize
+        }
+    }
 }
-#[allow(unused_imports)]
+#[allow(unused_imports, deprecated)]
 pub mod test_msgs {
     use super::actionlib_msgs;
     use super::diagnostic_msgs;
@@ -2604,6 +3883,7 @@ pub mod test_msgs {
         Debug,
         Clone,
         PartialEq,
+        PartialOrd,
     )]
     pub struct Defaults {
         #[default(42u8)]
@@ -2618,14 +3898,55 @@ pub mod test_msgs {
         pub r#f_samples: ::std::vec::Vec<f32>,
         #[default(_code = "[\"hello\", \"world\"].iter().map(|x| x.to_string()).collect()")]
         pub r#s_vec: ::std::vec::Vec<::std::string::String>,
+        #[default(_code = "[\"hello\", \"world\"].iter().map(|x| x.to_string()).collect()")]
+        pub r#s_vec_2: ::std::vec::Vec<::std::string::String>,
+        #[default("Jane Doe")]
+        pub r#single_quote: ::std::string::String,
+        #[default(_code = "[1, 2, 3]")]
+        pub r#fixed_samples: [i32; 3],
+        #[default(_code = "[\"first\", \"second\"].map(|x| x.to_string())")]
+        pub r#fixed_names: [::std::string::String; 2],
     }
     impl ::roslibrust_codegen::RosMessageType for Defaults {
         const ROS_TYPE_NAME: &'static str = "test_msgs/Defaults";
-        const MD5SUM: &'static str = "43c441dc2b521c313f54affd982b5314";
-        const DEFINITION : & 'static str = "# This message is specifically for testing generating of default values\n# Examples based on https://docs.ros.org/en/rolling/Concepts/About-ROS-Interfaces.html\nuint8 x 42\nint16 y -2000\nstring full_name \"John Doe\"\nint32[] samples [-200, -100, 0, 100, 200]\n\n# More complicated examples to stress the system, floats with mixed precision\nfloat32[] f_samples [-200, -1.0, 0]\nstring[] s_vec [\"hello\", \"world\"]\n# This may or may not be valid ROS, it probably is, but we don't handle yet\n# TODO handle this somehow\n# string[] s_vec_2 ['hello', 'world']\n\n# TODO ROS says this is valid, but we currently don't handle\n#string single_quote 'Jane Doe'" ;
+        const MD5SUM: &'static str = "274eae67df0117f6dec5113fa53f0ea7";
+        const DEFINITION : & 'static str = "# This message is specifically for testing generating of default values\n# Examples based on https://docs.ros.org/en/rolling/Concepts/About-ROS-Interfaces.html\nuint8 x 42\nint16 y -2000\nstring full_name \"John Doe\"\nint32[] samples [-200, -100, 0, 100, 200]\n\n# More complicated examples to stress the system, floats with mixed precision\nfloat32[] f_samples [-200, -1.0, 0]\nstring[] s_vec [\"hello\", \"world\"]\nstring[] s_vec_2 ['hello', 'world']\nstring single_quote 'Jane Doe'\n\n# Fixed size arrays\nint32[3] fixed_samples [1, 2, 3]\nstring[2] fixed_names ['first', \"second\"]" ;
+    }
+    impl Defaults {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            1usize
+                + 2usize
+                + 4usize
+                + self.r#full_name.len()
+                + 4usize
+                + self.r#samples.len() * 4usize
+                + 4usize
+                + self.r#f_samples.len() * 4usize
+                + 4usize
+                + self
+                    .r#s_vec
+                    .iter()
+                    .map(|element| 4usize + element.len())
+                    .sum::<usize>()
+                + 4usize
+                + self
+                    .r#s_vec_2
+                    .iter()
+                    .map(|element| 4usize + element.len())
+                    .sum::<usize>()
+                + 4usize
+                + self.r#single_quote.len()
+                + 12usize
+                + self
+                    .r#fixed_names
+                    .iter()
+                    .map(|element| 4usize + element.len())
+                    .sum::<usize>()
+        }
     }
 }
-#[allow(unused_imports)]
+#[allow(unused_imports, deprecated)]
 pub mod trajectory_msgs {
     use super::actionlib_msgs;
     use super::diagnostic_msgs;
@@ -2646,6 +3967,7 @@ pub mod trajectory_msgs {
         Debug,
         Clone,
         PartialEq,
+        PartialOrd,
     )]
     pub struct JointTrajectory {
         pub r#header: std_msgs::Header,
@@ -2657,6 +3979,24 @@ pub mod trajectory_msgs {
         const MD5SUM: &'static str = "d63e3b4556d9dbd9f48b5ab4a03f1fee";
         const DEFINITION : & 'static str = "# The header is used to specify the coordinate frame and the reference time for\n# the trajectory durations\nstd_msgs/Header header\n\n# The names of the active joints in each trajectory point. These names are\n# ordered and must correspond to the values in each trajectory point.\nstring[] joint_names\n\n# Array of trajectory points, which describe the positions, velocities,\n# accelerations and/or efforts of the joints at each time point.\nJointTrajectoryPoint[] points" ;
     }
+    impl JointTrajectory {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            self.r#header.serialized_size_upper_bound()
+                + 4usize
+                + self
+                    .r#joint_names
+                    .iter()
+                    .map(|element| 4usize + element.len())
+                    .sum::<usize>()
+                + 4usize
+                + self
+                    .r#points
+                    .iter()
+                    .map(|element| element.serialized_size_upper_bound())
+                    .sum::<usize>()
+        }
+    }
     #[allow(non_snake_case)]
     #[derive(
         :: serde :: Deserialize,
@@ -2665,6 +4005,7 @@ pub mod trajectory_msgs {
         Debug,
         Clone,
         PartialEq,
+        PartialOrd,
     )]
     pub struct JointTrajectoryPoint {
         pub r#positions: ::std::vec::Vec<f64>,
@@ -2678,6 +4019,20 @@ pub mod trajectory_msgs {
         const MD5SUM: &'static str = "2c812f86aa886c93954e333721749ac5";
         const DEFINITION : & 'static str = "# Each trajectory point specifies either positions[, velocities[, accelerations]]\n# or positions[, effort] for the trajectory to be executed.\n# All specified values are in the same order as the joint names in JointTrajectory.msg.\n\n# Single DOF joint positions for each joint relative to their \"0\" position.\n# The units depend on the specific joint type: radians for revolute or\n# continuous joints, and meters for prismatic joints.\nfloat64[] positions\n\n# The rate of change in position of each joint. Units are joint type dependent.\n# Radians/second for revolute or continuous joints, and meters/second for\n# prismatic joints.\nfloat64[] velocities\n\n# Rate of change in velocity of each joint. Units are joint type dependent.\n# Radians/second^2 for revolute or continuous joints, and meters/second^2 for\n# prismatic joints.\nfloat64[] accelerations\n\n# The torque or the force to be applied at each joint. For revolute/continuous\n# joints effort denotes a torque in newton-meters. For prismatic joints, effort\n# denotes a force in newtons.\nfloat64[] effort\n\n# Desired time from the trajectory start to arrive at this trajectory point.\nbuiltin_interfaces/Duration time_from_start" ;
     }
+    impl JointTrajectoryPoint {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            4usize
+                + self.r#positions.len() * 8usize
+                + 4usize
+                + self.r#velocities.len() * 8usize
+                + 4usize
+                + self.r#accelerations.len() * 8usize
+                + 4usize
+                + self.r#effort.len() * 8usize
+                + 8usize
+        }
+    }
     #[allow(non_snake_case)]
     #[derive(
         :: serde :: Deserialize,
@@ -2686,6 +4041,7 @@ pub mod trajectory_msgs {
         Debug,
         Clone,
         PartialEq,
+        PartialOrd,
     )]
     pub struct MultiDOFJointTrajectory {
         pub r#header: std_msgs::Header,
@@ -2697,6 +4053,24 @@ pub mod trajectory_msgs {
         const MD5SUM: &'static str = "d00d14d97bd70c5eb648278240cfb066";
         const DEFINITION : & 'static str = "# The header is used to specify the coordinate frame and the reference time for the trajectory durations\nstd_msgs/Header header\n\n# A representation of a multi-dof joint trajectory (each point is a transformation)\n# Each point along the trajectory will include an array of positions/velocities/accelerations\n# that has the same length as the array of joint names, and has the same order of joints as \n# the joint names array.\n\nstring[] joint_names\nMultiDOFJointTrajectoryPoint[] points" ;
     }
+    impl MultiDOFJointTrajectory {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            self.r#header.serialized_size_upper_bound()
+                + 4usize
+                + self
+                    .r#joint_names
+                    .iter()
+                    .map(|element| 4usize + element.len())
+                    .sum::<usize>()
+                + 4usize
+                + self
+                    .r#points
+                    .iter()
+                    .map(|element| element.serialized_size_upper_bound())
+                    .sum::<usize>()
+        }
+    }
     #[allow(non_snake_case)]
     #[derive(
         :: serde :: Deserialize,
@@ -2705,6 +4079,7 @@ pub mod trajectory_msgs {
         Debug,
         Clone,
         PartialEq,
+        PartialOrd,
     )]
     pub struct MultiDOFJointTrajectoryPoint {
         pub r#transforms: ::std::vec::Vec<geometry_msgs::Transform>,
@@ -2717,8 +4092,32 @@ pub mod trajectory_msgs {
         const MD5SUM: &'static str = "6731945e53cbc0fbc6e93c28f7416a71";
         const DEFINITION : & 'static str = "# Each multi-dof joint can specify a transform (up to 6 DOF).\ngeometry_msgs/Transform[] transforms\n\n# There can be a velocity specified for the origin of the joint.\ngeometry_msgs/Twist[] velocities\n\n# There can be an acceleration specified for the origin of the joint.\ngeometry_msgs/Twist[] accelerations\n\n# Desired time from the trajectory start to arrive at this trajectory point.\nbuiltin_interfaces/Duration time_from_start" ;
     }
+    impl MultiDOFJointTrajectoryPoint {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            4usize
+                + self
+                    .r#transforms
+                    .iter()
+                    .map(|element| element.serialized_size_upper_bound())
+                    .sum::<usize>()
+                + 4usize
+                + self
+                    .r#velocities
+                    .iter()
+                    .map(|element| element.serialized_size_upper_bound())
+                    .sum::<usize>()
+                + 4usize
+                + self
+                    .r#accelerations
+                    .iter()
+                    .map(|element| element.serialized_size_upper_bound())
+                    .sum::<usize>()
+                + 8usize
+        }
+    }
 }
-#[allow(unused_imports)]
+#[allow(unused_imports, deprecated)]
 pub mod visualization_msgs {
     use super::actionlib_msgs;
     use super::diagnostic_msgs;
@@ -2739,6 +4138,7 @@ pub mod visualization_msgs {
         Debug,
         Clone,
         PartialEq,
+        PartialOrd,
     )]
     pub struct ImageMarker {
         pub r#header: std_msgs::Header,
@@ -2761,13 +4161,47 @@ pub mod visualization_msgs {
         const DEFINITION : & 'static str = "int32 CIRCLE=0\nint32 LINE_STRIP=1\nint32 LINE_LIST=2\nint32 POLYGON=3\nint32 POINTS=4\n\nint32 ADD=0\nint32 REMOVE=1\n\nstd_msgs/Header header\n# Namespace which is used with the id to form a unique id.\nstring ns\n# Unique id within the namespace.\nint32 id\n# One of the above types, e.g. CIRCLE, LINE_STRIP, etc.\nint32 type\n# Either ADD or REMOVE.\nint32 action\n# Two-dimensional coordinate position, in pixel-coordinates.\ngeometry_msgs/Point position\n# The scale of the object, e.g. the diameter for a CIRCLE.\nfloat32 scale\n# The outline color of the marker.\nstd_msgs/ColorRGBA outline_color\n# Whether or not to fill in the shape with color.\nuint8 filled\n# Fill color; in the range: [0.0-1.0]\nstd_msgs/ColorRGBA fill_color\n# How long the object should last before being automatically deleted.\n# 0 indicates forever.\nbuiltin_interfaces/Duration lifetime\n\n# Coordinates in 2D in pixel coords. Used for LINE_STRIP, LINE_LIST, POINTS, etc.\ngeometry_msgs/Point[] points\n# The color for each line, point, etc. in the points field.\nstd_msgs/ColorRGBA[] outline_colors" ;
     }
     impl ImageMarker {
+        #[doc = " = 0"]
         pub const r#CIRCLE: i32 = 0i32;
+        #[doc = " = 1"]
         pub const r#LINE_STRIP: i32 = 1i32;
+        #[doc = " = 2"]
         pub const r#LINE_LIST: i32 = 2i32;
+        #[doc = " = 3"]
         pub const r#POLYGON: i32 = 3i32;
+        #[doc = " = 4"]
         pub const r#POINTS: i32 = 4i32;
+        #[doc = " = 0"]
         pub const r#ADD: i32 = 0i32;
+        #[doc = " = 1"]
         pub const r#REMOVE: i32 = 1i32;
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            self.r#header.serialized_size_upper_bound()
+                + 4usize
+                + self.r#ns.len()
+                + 4usize
+                + 4usize
+                + 4usize
+                + self.r#position.serialized_size_upper_bound()
+                + 4usize
+                + self.r#outline_color.serialized_size_upper_bound()
+                + 1usize
+                + self.r#fill_color.serialized_size_upper_bound()
+                + 8usize
+                + 4usize
+                + self
+                    .r#points
+                    .iter()
+                    .map(|element| element.serialized_size_upper_bound())
+                    .sum::<usize>()
+                + 4usize
+                + self
+                    .r#outline_colors
+                    .iter()
+                    .map(|element| element.serialized_size_upper_bound())
+                    .sum::<usize>()
+        }
     }
     #[allow(non_snake_case)]
     #[derive(
@@ -2777,6 +4211,7 @@ pub mod visualization_msgs {
         Debug,
         Clone,
         PartialEq,
+        PartialOrd,
     )]
     pub struct InteractiveMarker {
         pub r#header: std_msgs::Header,
@@ -2792,6 +4227,30 @@ pub mod visualization_msgs {
         const MD5SUM: &'static str = "d71737fa44c5bdefd6bdb4fa9b2b86e5";
         const DEFINITION : & 'static str = "# Time/frame info.\n# If header.time is set to 0, the marker will be retransformed into\n# its frame on each timestep. You will receive the pose feedback\n# in the same frame.\n# Otherwise, you might receive feedback in a different frame.\n# For rviz, this will be the current 'fixed frame' set by the user.\nstd_msgs/Header header\n\n# Initial pose. Also, defines the pivot point for rotations.\ngeometry_msgs/Pose pose\n\n# Identifying string. Must be globally unique in\n# the topic that this message is sent through.\nstring name\n\n# Short description (< 40 characters).\nstring description\n\n# Scale to be used for default controls (default=1).\nfloat32 scale\n\n# All menu and submenu entries associated with this marker.\nMenuEntry[] menu_entries\n\n# List of controls displayed for this marker.\nInteractiveMarkerControl[] controls" ;
     }
+    impl InteractiveMarker {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            self.r#header.serialized_size_upper_bound()
+                + self.r#pose.serialized_size_upper_bound()
+                + 4usize
+                + self.r#name.len()
+                + 4usize
+                + self.r#description.len()
+                + 4usize
+                + 4usize
+                + self
+                    .r#menu_entries
+                    .iter()
+                    .map(|element| element.serialized_size_upper_bound())
+                    .sum::<usize>()
+                + 4usize
+                + self
+                    .r#controls
+                    .iter()
+                    .map(|element| element.serialized_size_upper_bound())
+                    .sum::<usize>()
+        }
+    }
     #[allow(non_snake_case)]
     #[derive(
         :: serde :: Deserialize,
@@ -2800,6 +4259,7 @@ pub mod visualization_msgs {
         Debug,
         Clone,
         PartialEq,
+        PartialOrd,
     )]
     pub struct InteractiveMarkerControl {
         pub r#name: ::std::string::String,
@@ -2817,19 +4277,50 @@ pub mod visualization_msgs {
         const DEFINITION : & 'static str = "# Represents a control that is to be displayed together with an interactive marker\n\n# Identifying string for this control.\n# You need to assign a unique value to this to receive feedback from the GUI\n# on what actions the user performs on this control (e.g. a button click).\nstring name\n\n\n# Defines the local coordinate frame (relative to the pose of the parent\n# interactive marker) in which is being rotated and translated.\n# Default: Identity\ngeometry_msgs/Quaternion orientation\n\n\n# Orientation mode: controls how orientation changes.\n# INHERIT: Follow orientation of interactive marker\n# FIXED: Keep orientation fixed at initial state\n# VIEW_FACING: Align y-z plane with screen (x: forward, y:left, z:up).\nuint8 INHERIT = 0\nuint8 FIXED = 1\nuint8 VIEW_FACING = 2\n\nuint8 orientation_mode\n\n# Interaction mode for this control\n#\n# NONE: This control is only meant for visualization; no context menu.\n# MENU: Like NONE, but right-click menu is active.\n# BUTTON: Element can be left-clicked.\n# MOVE_AXIS: Translate along local x-axis.\n# MOVE_PLANE: Translate in local y-z plane.\n# ROTATE_AXIS: Rotate around local x-axis.\n# MOVE_ROTATE: Combines MOVE_PLANE and ROTATE_AXIS.\nuint8 NONE = 0\nuint8 MENU = 1\nuint8 BUTTON = 2\nuint8 MOVE_AXIS = 3\nuint8 MOVE_PLANE = 4\nuint8 ROTATE_AXIS = 5\nuint8 MOVE_ROTATE = 6\n# \"3D\" interaction modes work with the mouse+SHIFT+CTRL or with 3D cursors.\n# MOVE_3D: Translate freely in 3D space.\n# ROTATE_3D: Rotate freely in 3D space about the origin of parent frame.\n# MOVE_ROTATE_3D: Full 6-DOF freedom of translation and rotation about the cursor origin.\nuint8 MOVE_3D = 7\nuint8 ROTATE_3D = 8\nuint8 MOVE_ROTATE_3D = 9\n\nuint8 interaction_mode\n\n\n# If true, the contained markers will also be visible\n# when the gui is not in interactive mode.\nbool always_visible\n\n\n# Markers to be displayed as custom visual representation.\n# Leave this empty to use the default control handles.\n#\n# Note:\n# - The markers can be defined in an arbitrary coordinate frame,\n#   but will be transformed into the local frame of the interactive marker.\n# - If the header of a marker is empty, its pose will be interpreted as\n#   relative to the pose of the parent interactive marker.\nMarker[] markers\n\n\n# In VIEW_FACING mode, set this to true if you don't want the markers\n# to be aligned with the camera view point. The markers will show up\n# as in INHERIT mode.\nbool independent_marker_orientation\n\n\n# Short description (< 40 characters) of what this control does,\n# e.g. \"Move the robot\".\n# Default: A generic description based on the interaction mode\nstring description" ;
     }
     impl InteractiveMarkerControl {
+        #[doc = " = 0"]
         pub const r#INHERIT: u8 = 0u8;
+        #[doc = " = 1"]
         pub const r#FIXED: u8 = 1u8;
+        #[doc = " = 2"]
         pub const r#VIEW_FACING: u8 = 2u8;
+        #[doc = " = 0"]
         pub const r#NONE: u8 = 0u8;
+        #[doc = " = 1"]
         pub const r#MENU: u8 = 1u8;
+        #[doc = " = 2"]
         pub const r#BUTTON: u8 = 2u8;
+        #[doc = " = 3"]
         pub const r#MOVE_AXIS: u8 = 3u8;
+        #[doc = " = 4"]
         pub const r#MOVE_PLANE: u8 = 4u8;
+        #[doc = " = 5"]
         pub const r#ROTATE_AXIS: u8 = 5u8;
+        #[doc = " = 6"]
         pub const r#MOVE_ROTATE: u8 = 6u8;
+        #[doc = " = 7"]
         pub const r#MOVE_3D: u8 = 7u8;
+        #[doc = " = 8"]
         pub const r#ROTATE_3D: u8 = 8u8;
+        #[doc = " = 9"]
         pub const r#MOVE_ROTATE_3D: u8 = 9u8;
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            4usize
+                + self.r#name.len()
+                + self.r#orientation.serialized_size_upper_bound()
+                + 1usize
+                + 1usize
+                + 1usize
+                + 4usize
+                + self
+                    .r#markers
+                    .iter()
+                    .map(|element| element.serialized_size_upper_bound())
+                    .sum::<usize>()
+                + 1usize
+                + 4usize
+                + self.r#description.len()
+        }
     }
     #[allow(non_snake_case)]
     #[derive(
@@ -2839,6 +4330,7 @@ pub mod visualization_msgs {
         Debug,
         Clone,
         PartialEq,
+        PartialOrd,
     )]
     pub struct InteractiveMarkerFeedback {
         pub r#header: std_msgs::Header,
@@ -2857,12 +4349,33 @@ pub mod visualization_msgs {
         const DEFINITION : & 'static str = "# Time/frame info.\nstd_msgs/Header header\n\n# Identifying string. Must be unique in the topic namespace.\nstring client_id\n\n# Feedback message sent back from the GUI, e.g.\n# when the status of an interactive marker was modified by the user.\n\n# Specifies which interactive marker and control this message refers to\nstring marker_name\nstring control_name\n\n# Type of the event\n# KEEP_ALIVE: sent while dragging to keep up control of the marker\n# MENU_SELECT: a menu entry has been selected\n# BUTTON_CLICK: a button control has been clicked\n# POSE_UPDATE: the pose has been changed using one of the controls\nuint8 KEEP_ALIVE = 0\nuint8 POSE_UPDATE = 1\nuint8 MENU_SELECT = 2\nuint8 BUTTON_CLICK = 3\n\nuint8 MOUSE_DOWN = 4\nuint8 MOUSE_UP = 5\n\nuint8 event_type\n\n# Current pose of the marker\n# Note: Has to be valid for all feedback types.\ngeometry_msgs/Pose pose\n\n# Contains the ID of the selected menu entry\n# Only valid for MENU_SELECT events.\nuint32 menu_entry_id\n\n# If event_type is BUTTON_CLICK, MOUSE_DOWN, or MOUSE_UP, mouse_point\n# may contain the 3 dimensional position of the event on the\n# control.  If it does, mouse_point_valid will be true.  mouse_point\n# will be relative to the frame listed in the header.\ngeometry_msgs/Point mouse_point\nbool mouse_point_valid" ;
     }
     impl InteractiveMarkerFeedback {
+        #[doc = " = 0"]
         pub const r#KEEP_ALIVE: u8 = 0u8;
+        #[doc = " = 1"]
         pub const r#POSE_UPDATE: u8 = 1u8;
+        #[doc = " = 2"]
         pub const r#MENU_SELECT: u8 = 2u8;
+        #[doc = " = 3"]
         pub const r#BUTTON_CLICK: u8 = 3u8;
+        #[doc = " = 4"]
         pub const r#MOUSE_DOWN: u8 = 4u8;
+        #[doc = " = 5"]
         pub const r#MOUSE_UP: u8 = 5u8;
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            self.r#header.serialized_size_upper_bound()
+                + 4usize
+                + self.r#client_id.len()
+                + 4usize
+                + self.r#marker_name.len()
+                + 4usize
+                + self.r#control_name.len()
+                + 1usize
+                + self.r#pose.serialized_size_upper_bound()
+                + 4usize
+                + self.r#mouse_point.serialized_size_upper_bound()
+                + 1usize
+        }
     }
     #[allow(non_snake_case)]
     #[derive(
@@ -2872,6 +4385,7 @@ pub mod visualization_msgs {
         Debug,
         Clone,
         PartialEq,
+        PartialOrd,
     )]
     pub struct InteractiveMarkerInit {
         pub r#server_id: ::std::string::String,
@@ -2883,6 +4397,20 @@ pub mod visualization_msgs {
         const MD5SUM: &'static str = "5d275694a5cb7ea4627f917a9eb1b4cd";
         const DEFINITION : & 'static str = "# Identifying string. Must be unique in the topic namespace\n# that this server works on.\nstring server_id\n\n# Sequence number.\n# The client will use this to detect if it has missed a subsequent\n# update.  Every update message will have the same sequence number as\n# an init message.  Clients will likely want to unsubscribe from the\n# init topic after a successful initialization to avoid receiving\n# duplicate data.\nuint64 seq_num\n\n# All markers.\nInteractiveMarker[] markers" ;
     }
+    impl InteractiveMarkerInit {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            4usize
+                + self.r#server_id.len()
+                + 8usize
+                + 4usize
+                + self
+                    .r#markers
+                    .iter()
+                    .map(|element| element.serialized_size_upper_bound())
+                    .sum::<usize>()
+        }
+    }
     #[allow(non_snake_case)]
     #[derive(
         :: serde :: Deserialize,
@@ -2891,6 +4419,7 @@ pub mod visualization_msgs {
         Debug,
         Clone,
         PartialEq,
+        PartialOrd,
     )]
     pub struct InteractiveMarkerPose {
         pub r#header: std_msgs::Header,
@@ -2902,6 +4431,15 @@ pub mod visualization_msgs {
         const MD5SUM: &'static str = "b88540594a0f8e3fe46c720be41faa03";
         const DEFINITION : & 'static str = "# Time/frame info.\nstd_msgs/Header header\n\n# Initial pose. Also, defines the pivot point for rotations.\ngeometry_msgs/Pose pose\n\n# Identifying string. Must be globally unique in\n# the topic that this message is sent through.\nstring name" ;
     }
+    impl InteractiveMarkerPose {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            self.r#header.serialized_size_upper_bound()
+                + self.r#pose.serialized_size_upper_bound()
+                + 4usize
+                + self.r#name.len()
+        }
+    }
     #[allow(non_snake_case)]
     #[derive(
         :: serde :: Deserialize,
@@ -2910,6 +4448,7 @@ pub mod visualization_msgs {
         Debug,
         Clone,
         PartialEq,
+        PartialOrd,
     )]
     pub struct InteractiveMarkerUpdate {
         pub r#server_id: ::std::string::String,
@@ -2925,8 +4464,35 @@ pub mod visualization_msgs {
         const DEFINITION : & 'static str = "# Identifying string. Must be unique in the topic namespace\n# that this server works on.\nstring server_id\n\n# Sequence number.\n# The client will use this to detect if it has missed an update.\nuint64 seq_num\n\n# Type holds the purpose of this message.  It must be one of UPDATE or KEEP_ALIVE.\n# UPDATE: Incremental update to previous state.\n#         The sequence number must be 1 higher than for\n#         the previous update.\n# KEEP_ALIVE: Indicates the that the server is still living.\n#             The sequence number does not increase.\n#             No payload data should be filled out (markers, poses, or erases).\nuint8 KEEP_ALIVE = 0\nuint8 UPDATE = 1\n\nuint8 type\n\n# Note: No guarantees on the order of processing.\n#       Contents must be kept consistent by sender.\n\n# Markers to be added or updated\nInteractiveMarker[] markers\n\n# Poses of markers that should be moved\nInteractiveMarkerPose[] poses\n\n# Names of markers to be erased\nstring[] erases" ;
     }
     impl InteractiveMarkerUpdate {
+        #[doc = " = 0"]
         pub const r#KEEP_ALIVE: u8 = 0u8;
+        #[doc = " = 1"]
         pub const r#UPDATE: u8 = 1u8;
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            4usize
+                + self.r#server_id.len()
+                + 8usize
+                + 1usize
+                + 4usize
+                + self
+                    .r#markers
+                    .iter()
+                    .map(|element| element.serialized_size_upper_bound())
+                    .sum::<usize>()
+                + 4usize
+                + self
+                    .r#poses
+                    .iter()
+                    .map(|element| element.serialized_size_upper_bound())
+                    .sum::<usize>()
+                + 4usize
+                + self
+                    .r#erases
+                    .iter()
+                    .map(|element| 4usize + element.len())
+                    .sum::<usize>()
+        }
     }
     #[allow(non_snake_case)]
     #[derive(
@@ -2936,6 +4502,7 @@ pub mod visualization_msgs {
         Debug,
         Clone,
         PartialEq,
+        PartialOrd,
     )]
     pub struct Marker {
         pub r#header: std_msgs::Header,
@@ -2964,31 +4531,89 @@ pub mod visualization_msgs {
         const DEFINITION : & 'static str = "# See:\n#  - http://www.ros.org/wiki/rviz/DisplayTypes/Marker\n#  - http://www.ros.org/wiki/rviz/Tutorials/Markers%3A%20Basic%20Shapes\n#\n# for more information on using this message with rviz.\n\nint32 ARROW=0\nint32 CUBE=1\nint32 SPHERE=2\nint32 CYLINDER=3\nint32 LINE_STRIP=4\nint32 LINE_LIST=5\nint32 CUBE_LIST=6\nint32 SPHERE_LIST=7\nint32 POINTS=8\nint32 TEXT_VIEW_FACING=9\nint32 MESH_RESOURCE=10\nint32 TRIANGLE_LIST=11\n\nint32 ADD=0\nint32 MODIFY=0\nint32 DELETE=2\nint32 DELETEALL=3\n\n# Header for timestamp and frame id.\nstd_msgs/Header header\n# Namespace in which to place the object.\n# Used in conjunction with id to create a unique name for the object.\nstring ns\n# Object ID used in conjunction with the namespace for manipulating and deleting the object later.\nint32 id\n# Type of object.\nint32 type\n# Action to take; one of:\n#  - 0 add/modify an object\n#  - 1 (deprecated)\n#  - 2 deletes an object (with the given ns and id)\n#  - 3 deletes all objects (or those with the given ns if any)\nint32 action\n# Pose of the object with respect the frame_id specified in the header.\ngeometry_msgs/Pose pose\n# Scale of the object; 1,1,1 means default (usually 1 meter square).\ngeometry_msgs/Vector3 scale\n# Color of the object; in the range: [0.0-1.0]\nstd_msgs/ColorRGBA color\n# How long the object should last before being automatically deleted.\n# 0 indicates forever.\nbuiltin_interfaces/Duration lifetime\n# If this marker should be frame-locked, i.e. retransformed into its frame every timestep.\nbool frame_locked\n\n# Only used if the type specified has some use for them (eg. POINTS, LINE_STRIP, etc.)\ngeometry_msgs/Point[] points\n# Only used if the type specified has some use for them (eg. POINTS, LINE_STRIP, etc.)\n# The number of colors provided must either be 0 or equal to the number of points provided.\n# NOTE: alpha is not yet used\nstd_msgs/ColorRGBA[] colors\n\n# Texture resource is a special URI that can either reference a texture file in\n# a format acceptable to (resource retriever)[https://index.ros.org/p/resource_retriever/]\n# or an embedded texture via a string matching the format:\n#   \"embedded://texture_name\"\nstring texture_resource\n# An image to be loaded into the rendering engine as the texture for this marker.\n# This will be used iff texture_resource is set to embedded.\nsensor_msgs/CompressedImage texture\n# Location of each vertex within the texture; in the range: [0.0-1.0]\nUVCoordinate[] uv_coordinates\n\n# Only used for text markers\nstring text\n\n# Only used for MESH_RESOURCE markers.\n# Similar to texture_resource, mesh_resource uses resource retriever to load a mesh.\n# Optionally, a mesh file can be sent in-message via the mesh_file field. If doing so,\n# use the following format for mesh_resource:\n#   \"embedded://mesh_name\"\nstring mesh_resource\nMeshFile mesh_file\nbool mesh_use_embedded_materials" ;
     }
     impl Marker {
+        #[doc = " = 0"]
         pub const r#ARROW: i32 = 0i32;
+        #[doc = " = 1"]
         pub const r#CUBE: i32 = 1i32;
+        #[doc = " = 2"]
         pub const r#SPHERE: i32 = 2i32;
+        #[doc = " = 3"]
         pub const r#CYLINDER: i32 = 3i32;
+        #[doc = " = 4"]
         pub const r#LINE_STRIP: i32 = 4i32;
+        #[doc = " = 5"]
         pub const r#LINE_LIST: i32 = 5i32;
+        #[doc = " = 6"]
         pub const r#CUBE_LIST: i32 = 6i32;
+        #[doc = " = 7"]
         pub const r#SPHERE_LIST: i32 = 7i32;
+        #[doc = " = 8"]
         pub const r#POINTS: i32 = 8i32;
+        #[doc = " = 9"]
         pub const r#TEXT_VIEW_FACING: i32 = 9i32;
+        #[doc = " = 10"]
         pub const r#MESH_RESOURCE: i32 = 10i32;
+        #[doc = " = 11"]
         pub const r#TRIANGLE_LIST: i32 = 11i32;
+        #[doc = " = 0"]
         pub const r#ADD: i32 = 0i32;
+        #[doc = " = 0"]
         pub const r#MODIFY: i32 = 0i32;
+        #[doc = " = 2"]
         pub const r#DELETE: i32 = 2i32;
+        #[doc = " = 3"]
         pub const r#DELETEALL: i32 = 3i32;
-    }
-    #[allow(non_snake_case)]
-    #[derive(
-        :: serde :: Deserialize,
-        :: serde :: Serialize,
-        :: smart_default :: SmartDefault,
-        Debug,
-        Clone,
-        PartialEq,
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            self.r#header.serialized_size_upper_bound()
+                + 4usize
+                + self.r#ns.len()
+                + 4usize
+                + 4usize
+                + 4usize
+                + self.r#pose.serialized_size_upper_bound()
+                + self.r#scale.serialized_size_upper_bound()
+                + self.r#color.serialized_size_upper_bound()
+                + 8usize
+                + 1usize
+                + 4usize
+                + self
+                    .r#points
+                    .iter()
+                    .map(|element| element.serialized_size_upper_bound())
+                    .sum::<usize>()
+                + 4usize
+                + self
+                    .r#colors
+                    .iter()
+                    .map(|element| element.serialized_size_upper_bound())
+                    .sum::<usize>()
+                + 4usize
+                + self.r#texture_resource.len()
+                + self.r#texture.serialized_size_upper_bound()
+                + 4usize
+                + self
+                    .r#uv_coordinates
+                    .iter()
+                    .map(|element| element.serialized_size_upper_bound())
+                    .sum::<usize>()
+                + 4usize
+                + self.r#text.len()
+                + 4usize
+                + self.r#mesh_resource.len()
+                + self.r#mesh_file.serialized_size_upper_bound()
+                + 1usize
+        }
+    }
+    #[allow(non_snake_case)]
+    #[derive(
+        :: serde :: Deserialize,
+        :: serde :: Serialize,
+        :: smart_default :: SmartDefault,
+        Debug,
+        Clone,
+        PartialEq,
+        PartialOrd,
     )]
     pub struct MarkerArray {
         pub r#markers: ::std::vec::Vec<self::Marker>,
@@ -2998,6 +4623,17 @@ pub mod visualization_msgs {
         const MD5SUM: &'static str = "11e38f15427197858cf46456867167bd";
         const DEFINITION: &'static str = "Marker[] markers";
     }
+    impl MarkerArray {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            4usize
+                + self
+                    .r#markers
+                    .iter()
+                    .map(|element| element.serialized_size_upper_bound())
+                    .sum::<usize>()
+        }
+    }
     #[allow(non_snake_case)]
     #[derive(
         :: serde :: Deserialize,
@@ -3006,6 +4642,9 @@ pub mod visualization_msgs {
         Debug,
         Clone,
         PartialEq,
+        Eq,
+        PartialOrd,
+        Ord,
     )]
     pub struct MenuEntry {
         pub r#id: u32,
@@ -3020,9 +4659,16 @@ pub mod visualization_msgs {
         const DEFINITION : & 'static str = "# MenuEntry message.\n#\n# Each InteractiveMarker message has an array of MenuEntry messages.\n# A collection of MenuEntries together describe a\n# menu/submenu/subsubmenu/etc tree, though they are stored in a flat\n# array.  The tree structure is represented by giving each menu entry\n# an ID number and a \"parent_id\" field.  Top-level entries are the\n# ones with parent_id = 0.  Menu entries are ordered within their\n# level the same way they are ordered in the containing array.  Parent\n# entries must appear before their children.\n#\n# Example:\n# - id = 3\n#   parent_id = 0\n#   title = \"fun\"\n# - id = 2\n#   parent_id = 0\n#   title = \"robot\"\n# - id = 4\n#   parent_id = 2\n#   title = \"pr2\"\n# - id = 5\n#   parent_id = 2\n#   title = \"turtle\"\n#\n# Gives a menu tree like this:\n#  - fun\n#  - robot\n#    - pr2\n#    - turtle\n\n# ID is a number for each menu entry.  Must be unique within the\n# control, and should never be 0.\nuint32 id\n\n# ID of the parent of this menu entry, if it is a submenu.  If this\n# menu entry is a top-level entry, set parent_id to 0.\nuint32 parent_id\n\n# menu / entry title\nstring title\n\n# Arguments to command indicated by command_type (below)\nstring command\n\n# Command_type stores the type of response desired when this menu\n# entry is clicked.\n# FEEDBACK: send an InteractiveMarkerFeedback message with menu_entry_id set to this entry's id.\n# ROSRUN: execute \"rosrun\" with arguments given in the command field (above).\n# ROSLAUNCH: execute \"roslaunch\" with arguments given in the command field (above).\nuint8 FEEDBACK=0\nuint8 ROSRUN=1\nuint8 ROSLAUNCH=2\nuint8 command_type" ;
     }
     impl MenuEntry {
+        #[doc = " = 0"]
         pub const r#FEEDBACK: u8 = 0u8;
+        #[doc = " = 1"]
         pub const r#ROSRUN: u8 = 1u8;
+        #[doc = " = 2"]
         pub const r#ROSLAUNCH: u8 = 2u8;
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            4usize + 4usize + 4usize + self.r#title.len() + 4usize + self.r#command.len() + 1usize
+        }
     }
     #[allow(non_snake_case)]
     #[derive(
@@ -3032,6 +4678,9 @@ pub mod visualization_msgs {
         Debug,
         Clone,
         PartialEq,
+        Eq,
+        PartialOrd,
+        Ord,
     )]
     pub struct MeshFile {
         pub r#filename: ::std::string::String,
@@ -3042,6 +4691,12 @@ pub mod visualization_msgs {
         const MD5SUM: &'static str = "39f264648e441626a1045a7d9ef1ba17";
         const DEFINITION : & 'static str = "# Used to send raw mesh files.\n\n# The filename is used for both debug purposes and to provide a file extension\n# for whatever parser is used.\nstring filename\n\n# This stores the raw text of the mesh file.\nuint8[] data" ;
     }
+    impl MeshFile {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            4usize + self.r#filename.len() + 4usize + self.r#data.len()
+        }
+    }
     #[allow(non_snake_case)]
     #[derive(
         :: serde :: Deserialize,
@@ -3050,6 +4705,7 @@ pub mod visualization_msgs {
         Debug,
         Clone,
         PartialEq,
+        PartialOrd,
     )]
     pub struct UVCoordinate {
         pub r#u: f32,
@@ -3060,6 +4716,12 @@ pub mod visualization_msgs {
         const MD5SUM: &'static str = "4f5254e0e12914c461d4b17a0cd07f7f";
         const DEFINITION : & 'static str = "# Location of the pixel as a ratio of the width of a 2D texture.\n# Values should be in range: [0.0-1.0].\nfloat32 u\nfloat32 v" ;
     }
+    impl UVCoordinate {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            8usize
+        }
+    }
     #[allow(non_snake_case)]
     #[derive(
         :: serde :: Deserialize,
@@ -3068,6 +4730,9 @@ pub mod visualization_msgs {
         Debug,
         Clone,
         PartialEq,
+        Eq,
+        PartialOrd,
+        Ord,
     )]
     pub struct GetInteractiveMarkersRequest {}
     impl ::roslibrust_codegen::RosMessageType for GetInteractiveMarkersRequest {
@@ -3075,6 +4740,12 @@ pub mod visualization_msgs {
         const MD5SUM: &'static str = "d41d8cd98f00b204e9800998ecf8427e";
         const DEFINITION: &'static str = "";
     }
+    impl GetInteractiveMarkersRequest {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            0usize
+        }
+    }
     #[allow(non_snake_case)]
     #[derive(
         :: serde :: Deserialize,
@@ -3083,6 +4754,7 @@ pub mod visualization_msgs {
         Debug,
         Clone,
         PartialEq,
+        PartialOrd,
     )]
     pub struct GetInteractiveMarkersResponse {
         pub r#sequence_number: u64,
@@ -3093,6 +4765,18 @@ pub mod visualization_msgs {
         const MD5SUM: &'static str = "923b76ef2c497d4ff5f83a061d424d3b";
         const DEFINITION : & 'static str = "# Sequence number.\n# Set to the sequence number of the latest update message\n# at the time the server received the request.\n# Clients use this to detect if any updates were missed.\nuint64 sequence_number\n\n# All interactive markers provided by the server.\nInteractiveMarker[] markers" ;
     }
+    impl GetInteractiveMarkersResponse {
+        #[doc = r" An upper bound on the size in bytes of this message in ros1 encoding, excluding the length prefix"]
+        pub fn serialized_size_upper_bound(&self) -> usize {
+            8usize
+                + 4usize
+                + self
+                    .r#markers
+                    .iter()
+                    .map(|element| element.serialized_size_upper_bound())
+                    .sum::<usize>()
+        }
+    }
     pub struct GetInteractiveMarkers {}
     impl ::roslibrust_codegen::RosServiceType for GetInteractiveMarkers {
         const ROS_SERVICE_NAME: &'static str = "visualization_msgs/GetInteractiveMarkers";
@@ -0,0 +1,14 @@
+// Expands the macro over several packages with parallel codegen enabled, which has to keep
+// proc_macro2 on the macro's thread. A failure here is a panic at compile time.
+roslibrust_codegen_macro::find_and_generate_ros_messages_without_ros_package_path!(
+    "assets/ros1_common_interfaces/rosapi",
+    "assets/ros2_rcl_interfaces",
+    "assets/ros2_test_msgs",
+);
+
+#[test]
+fn macro_expands_many_packages() {
+    let _: rosapi::TopicsRequest = Default::default();
+    let _: rcl_interfaces::Log = Default::default();
+    let _: test_msgs::Defaults = Default::default();
+}
@@ -1,4 +1,4 @@
-use roslibrust_codegen::{RosMessageType, RosServiceType};
+use roslibrust_codegen::{MessageValidator, RosMessageType, RosServiceType};
 use roslibrust_test::ros1::*;
 
 /// Ensures that associate constants are generated on the test_msgs correctly
@@ -26,6 +26,44 @@ fn test_md5sum_generation() {
     );
 }
 
+/// Each service is generated as a zero sized marker type tying its request and response together
+#[test]
+fn test_service_type_generation() {
+    fn type_names<T: RosServiceType>() -> (&'static str, &'static str, &'static str) {
+        (
+            T::ROS_SERVICE_NAME,
+            T::Request::ROS_TYPE_NAME,
+            T::Response::ROS_TYPE_NAME,
+        )
+    }
+    assert_eq!(
+        type_names::<std_srvs::SetBool>(),
+        (
+            "std_srvs/SetBool",
+            "std_srvs/SetBoolRequest",
+            "std_srvs/SetBoolResponse"
+        )
+    );
+    assert_eq!(
+        <std_srvs::SetBool as RosServiceType>::MD5SUM,
+        "09fb03525b03e7ea1fd3992bafd87e16"
+    );
+    assert_eq!(std::mem::size_of::<std_srvs::SetBool>(), 0);
+}
+
+#[test]
+fn test_fixed_size_generation() {
+    assert_eq!(geometry_msgs::Point::FIXED_SIZE, Some(24));
+    // Fixed length arrays of nested messages contribute their full size
+    assert_eq!(geometry_msgs::PoseWithCovariance::FIXED_SIZE, Some(344));
+    assert_eq!(sensor_msgs::RegionOfInterest::FIXED_SIZE, Some(17));
+    assert_eq!(std_msgs::Empty::FIXED_SIZE, Some(0));
+    // Strings and variable length arrays make a message variable length
+    assert_eq!(std_msgs::Header::FIXED_SIZE, None);
+    assert_eq!(geometry_msgs::PointStamped::FIXED_SIZE, None);
+    assert_eq!(geometry_msgs::Polygon::FIXED_SIZE, None);
+}
+
 #[test]
 fn fixed_sized_arrays() {
     // Prove the default works, compiler failure here is the test
@@ -38,3 +76,120 @@ fn fixed_sized_arrays() {
     let x: geometry_msgs::TwistWithCovariance = Default::default();
     let _y: [f64; 36] = x.covariance;
 }
+
+#[test]
+fn validate_annotated_constraints() {
+    let mut msg = test_msgs::BatteryStatus {
+        percentage: 80,
+        voltage: 12.6,
+        chemistry: "LiPo".to_owned(),
+        cell_status: vec![0, 1, 0],
+    };
+    assert_eq!(msg.validate(), Ok(vec![]));
+
+    msg.percentage = 120;
+    msg.voltage = 0.0;
+    msg.cell_status[2] = 7;
+    let errors = msg.validate().unwrap_err();
+    let fields = errors
+        .iter()
+        .map(|error| error.field.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(fields, vec!["percentage", "voltage", "cell_status[2]"]);
+    assert_eq!(
+        errors[0].to_string(),
+        "percentage = 120 violates range: [0, 100]"
+    );
+
+    msg = Default::default();
+    msg.voltage = 3.7;
+    msg.chemistry = "Lead acid".to_owned();
+    assert_eq!(msg.validate().unwrap_err()[0].field, "chemistry");
+}
+
+/// Size of the message body in ros1 encoding, without the 4 byte length prefix
+fn serialized_size<T: serde::Serialize>(msg: &T) -> usize {
+    serde_rosmsg::to_vec(msg).unwrap().len() - 4
+}
+
+#[test]
+fn size_upper_bound_covers_serialized_size() {
+    let point = geometry_msgs::Point {
+        x: 1.0,
+        y: 2.0,
+        z: 3.0,
+    };
+    assert_eq!(point.serialized_size_upper_bound(), serialized_size(&point));
+
+    for length in [0, 1, 7, 100] {
+        let mut image = sensor_msgs::Image::default();
+        image.header.frame_id = "c".repeat(length);
+        image.encoding = "rgb8".to_owned();
+        image.data = vec![3; length * 3];
+        assert!(image.serialized_size_upper_bound() >= serialized_size(&image));
+
+        let polygon = geometry_msgs::PolygonStamped {
+            polygon: geometry_msgs::Polygon {
+                points: vec![Default::default(); length],
+            },
+            ..Default::default()
+        };
+        assert!(polygon.serialized_size_upper_bound() >= serialized_size(&polygon));
+
+        let diagnostics = diagnostic_msgs::DiagnosticArray {
+            status: vec![
+                diagnostic_msgs::DiagnosticStatus {
+                    name: "n".repeat(length),
+                    values: vec![
+                        diagnostic_msgs::KeyValue {
+                            key: "k".to_owned(),
+                            value: "v".repeat(length),
+                        };
+                        length
+                    ],
+                    ..Default::default()
+                };
+                2
+            ],
+            ..Default::default()
+        };
+        assert!(diagnostics.serialized_size_upper_bound() >= serialized_size(&diagnostics));
+    }
+
+    // Without bounded arrays the estimate is exact
+    let header = std_msgs::Header {
+        frame_id: "base_link".to_owned(),
+        ..Default::default()
+    };
+    assert_eq!(header.serialized_size_upper_bound(), 4 + 8 + 4 + 9);
+    assert_eq!(
+        header.serialized_size_upper_bound(),
+        serialized_size(&header)
+    );
+}
+
+#[test]
+fn messages_are_ordered_by_field() {
+    // Without floats messages are totally ordered, field by field in declaration order
+    let header = |secs, frame_id: &str| std_msgs::Header {
+        seq: 1,
+        stamp: roslibrust_codegen::integral_types::Time { secs, nsecs: 0 },
+        frame_id: frame_id.to_owned(),
+    };
+    let headers: std::collections::BTreeSet<_> = [
+        header(2, "a"),
+        header(1, "b"),
+        header(1, "a"),
+        header(2, "a"),
+    ]
+    .into();
+    assert_eq!(
+        headers.into_iter().collect::<Vec<_>>(),
+        vec![header(1, "a"), header(1, "b"), header(2, "a")]
+    );
+
+    // With floats they're only partially ordered
+    let point = |x| geometry_msgs::Point { x, y: 0.0, z: 0.0 };
+    assert!(point(1.0) < point(2.0));
+    assert_eq!(point(f64::NAN).partial_cmp(&point(1.0)), None);
+}
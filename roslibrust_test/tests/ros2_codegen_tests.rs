@@ -9,6 +9,10 @@ fn test_defaults() {
     assert_eq!(x.samples, vec![-200, -100, 0, 100, 200]);
     assert_eq!(x.s_vec, vec!["hello", "world"]);
     assert_eq!(x.f_samples, vec![-200.0, -1.0, 0.0]);
+    assert_eq!(x.s_vec_2, vec!["hello", "world"]);
+    assert_eq!(x.single_quote, "Jane Doe");
+    assert_eq!(x.fixed_samples, [1, 2, 3]);
+    assert_eq!(x.fixed_names, ["first", "second"]);
 }
 
 #[test]
@@ -23,3 +27,30 @@ fn fixed_sized_arrays() {
     let x: geometry_msgs::TwistWithCovariance = Default::default();
     let _y: [f64; 36] = x.covariance;
 }
+
+#[test]
+fn bounded_arrays_are_generated_as_vec() {
+    // float64[<=3], compiler failure here is the test
+    let x: shape_msgs::SolidPrimitive = Default::default();
+    let _y: Vec<f64> = x.dimensions;
+}
+
+#[test]
+fn size_upper_bound_covers_serialized_size() {
+    let serialized_size =
+        |msg: &shape_msgs::SolidPrimitive| serde_rosmsg::to_vec(msg).unwrap().len() - 4;
+    // Bounded arrays are counted at their bound, even when shorter
+    let mut primitive = shape_msgs::SolidPrimitive::default();
+    for length in 0..=5 {
+        primitive.dimensions = vec![1.0; length];
+        let estimate = primitive.serialized_size_upper_bound();
+        assert!(estimate >= serialized_size(&primitive));
+        assert_eq!(estimate, 1 + 4 + 8 * length.max(3) + 4);
+    }
+
+    let defaults = test_msgs::Defaults::default();
+    assert_eq!(
+        defaults.serialized_size_upper_bound(),
+        serde_rosmsg::to_vec(&defaults).unwrap().len() - 4
+    );
+}